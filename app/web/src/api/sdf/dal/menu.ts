@@ -11,6 +11,12 @@ export interface Item {
   links?: LinkNodeItem[];
 }
 
+export interface TemplateItem {
+  kind: "template";
+  name: string;
+  template_id: string;
+}
+
 // TODO: This entire thing is wrong now, but should look like item eventually. -- Adam
 export interface LinkNodeItem {
   kind: "link";
@@ -20,4 +26,4 @@ export interface LinkNodeItem {
   name: string;
 }
 
-export type MenuItem = Category | Item | LinkNodeItem;
+export type MenuItem = Category | Item | TemplateItem | LinkNodeItem;
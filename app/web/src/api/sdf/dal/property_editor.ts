@@ -99,6 +99,8 @@ export interface PropertyEditorValue {
   key?: string;
   value: unknown;
   isFromExternalSource: boolean;
+  isOverridden: boolean;
+  isLocked: boolean;
 }
 
 export interface PropertyEditorValues {
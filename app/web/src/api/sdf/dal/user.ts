@@ -7,3 +7,10 @@ export interface User {
   created_at: IsoDateString;
   updated_at: IsoDateString;
 }
+
+export type WorkspaceRole = "owner" | "approver" | "editor" | "viewer";
+
+export interface WorkspaceMember {
+  user: User;
+  role: WorkspaceRole;
+}
@@ -295,6 +295,9 @@ export type SelectElementEvent = {
 export type DeleteElementsEvent = {
   elements: DiagramElementData[];
 };
+export type DuplicateElementsEvent = {
+  elements: DiagramElementData[];
+};
 export type InsertElementEvent = {
   diagramElementType: DiagramElementTypes;
   position: Vector2d;
@@ -365,6 +365,13 @@ export const useAssetStore = () => {
         this.LOAD_ASSET_LIST();
         const realtimeStore = useRealtimeStore();
         realtimeStore.subscribe(this.$id, `changeset/${changeSetId}`, [
+          {
+            eventType: "StreamStatus",
+            callback: (streamStatus) => {
+              if (!streamStatus.gap) return;
+              this.LOAD_ASSET_LIST();
+            },
+          },
           {
             eventType: "ChangeSetWritten",
             callback: (writtenChangeSetId) => {
@@ -4,7 +4,7 @@ import jwtDecode from "jwt-decode";
 import { ApiRequest } from "@si/vue-lib/pinia";
 import { posthog } from "@/utils/posthog";
 
-import { User } from "@/api/sdf/dal/user";
+import { User, WorkspaceRole } from "@/api/sdf/dal/user";
 import { Workspace } from "@/api/sdf/dal/workspace";
 
 const AUTH_PORTAL_URL = import.meta.env.VITE_AUTH_PORTAL_URL;
@@ -35,6 +35,7 @@ export const useAuthStore = defineStore("auth", {
     // TODO: these maybe should live in another module related to the user/org/groups/etc
     user: null as User | null,
     workspace: null as Workspace | null,
+    role: null as WorkspaceRole | null,
   }),
   getters: {
     // previously we checked only for the token existing
@@ -42,15 +43,22 @@ export const useAuthStore = defineStore("auth", {
     // so we must wait for the backend to be re-initialized
     userIsLoggedIn: (state) => !!state.token,
     userIsLoggedInAndInitialized: (state) => !!state.token && state.user?.pk,
+    canManageRoles: (state) => state.role === "owner",
+    canApplyChangeSets: (state) =>
+      state.role === "owner" || state.role === "approver",
+    canEdit: (state) => !!state.role && state.role !== "viewer",
   },
   actions: {
     // fetches user + workspace info from SDF - called on page refresh
     async RESTORE_AUTH() {
-      return new ApiRequest<Omit<LoginResponse, "jwt">>({
+      return new ApiRequest<
+        Omit<LoginResponse, "jwt"> & { role: WorkspaceRole | null }
+      >({
         url: "/session/restore_authentication",
         onSuccess: (response) => {
           this.user = response.user;
           this.workspace = response.workspace;
+          this.role = response.role;
         },
         onFail(e) {
           /* eslint-disable-next-line no-console */
@@ -173,6 +173,13 @@ export function useChangeSetsStore() {
         const realtimeStore = useRealtimeStore();
         // TODO: if selected change set gets cancelled/applied, need to show error if by other user, and switch to head...
         realtimeStore.subscribe(this.$id, `workspace/${workspacePk}`, [
+          {
+            eventType: "StreamStatus",
+            callback: (streamStatus) => {
+              if (!streamStatus.gap) return;
+              this.FETCH_CHANGE_SETS();
+            },
+          },
           {
             eventType: "ChangeSetCreated",
             callback: this.FETCH_CHANGE_SETS,
@@ -234,6 +234,48 @@ export const useComponentAttributesStore = (componentId: ComponentId) => {
           });
         },
 
+        async RESET_PROPERTY_VALUE(attributeValueId: string) {
+          if (changeSetsStore.creatingChangeSet)
+            throw new Error("race, wait until the change set is created");
+          if (changeSetId === nilId()) changeSetsStore.creatingChangeSet = true;
+
+          const statusStore = useStatusStore();
+          statusStore.markUpdateStarted();
+
+          return new ApiRequest<{ success: true }>({
+            method: "post",
+            url: "component/reset_property_editor_value",
+            params: {
+              attributeValueId,
+              componentId,
+              ...visibilityParams,
+            },
+            onFail() {
+              statusStore.cancelUpdateStarted();
+            },
+          });
+        },
+
+        async SET_PROPERTY_VALUE_LOCKED(
+          attributeValueId: string,
+          locked: boolean,
+        ) {
+          if (changeSetsStore.creatingChangeSet)
+            throw new Error("race, wait until the change set is created");
+          if (changeSetId === nilId()) changeSetsStore.creatingChangeSet = true;
+
+          return new ApiRequest<{ success: true }>({
+            method: "post",
+            url: "component/set_property_editor_value_locked",
+            params: {
+              attributeValueId,
+              componentId,
+              locked,
+              ...visibilityParams,
+            },
+          });
+        },
+
         async SET_COMPONENT_TYPE(payload: SetTypeArgs) {
           if (changeSetsStore.creatingChangeSet)
             throw new Error("race, wait until the change set is created");
@@ -262,6 +304,13 @@ export const useComponentAttributesStore = (componentId: ComponentId) => {
 
         const realtimeStore = useRealtimeStore();
         realtimeStore.subscribe(this.$id, `changeset/${changeSetId}`, [
+          {
+            eventType: "StreamStatus",
+            callback: (streamStatus) => {
+              if (!streamStatus.gap) return;
+              this.reloadPropertyEditorData();
+            },
+          },
           {
             eventType: "ChangeSetWritten",
             callback: (writtenChangeSetId) => {
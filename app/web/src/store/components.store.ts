@@ -40,6 +40,7 @@ export type EdgeId = string;
 export type SocketId = string;
 type SchemaId = string;
 type SchemaVariantId = string;
+export type ComponentTemplateId = string;
 
 type RawComponent = {
   actions: ActionPrototype[];
@@ -62,9 +63,24 @@ type RawComponent = {
   schemaVariantId: string;
   schemaVariantName: string;
   sockets: DiagramSocketDef[];
+  // effective tags, including those inherited from frames
+  tags: Record<string, string>;
   updatedInfo: ActorAndTimestamp;
 };
 
+export type ComponentTagInheritance = "none" | "children" | "descendants";
+export type ComponentTag = {
+  key: string;
+  value: string;
+  inheritance: ComponentTagInheritance;
+};
+export type EffectiveComponentTag = {
+  key: string;
+  value: string;
+  sourceComponentId: ComponentId;
+  inherited: boolean;
+};
+
 export type FullComponent = RawComponent & {
   parentNodeId?: ComponentNodeId;
   parentId?: ComponentId;
@@ -107,7 +123,9 @@ export type ComponentTreeNode = {
 } & FullComponent;
 
 export type MenuSchema = {
-  id: SchemaId;
+  // templates are listed and dropped on the diagram like schemas, but instantiated instead
+  kind: "schema" | "template";
+  id: SchemaId | ComponentTemplateId;
   displayName: string;
   color: string;
 };
@@ -208,8 +226,17 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
 
         // used by the diagram to track which schema is selected for insertion
         selectedInsertSchemaId: null as SchemaId | null,
+        // ... or which component template
+        selectedInsertTemplateId: null as ComponentTemplateId | null,
 
         refreshingStatus: {} as Record<ComponentId, boolean>,
+
+        // components must have every one of these tags (key -> value) to match the filter
+        tagFilter: {} as Record<string, string>,
+        tagsByComponentId: {} as Record<
+          ComponentId,
+          { tags: ComponentTag[]; effectiveTags: EffectiveComponentTag[] }
+        >,
       }),
       getters: {
         // transforming the diagram-y data back into more generic looking data
@@ -254,6 +281,10 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
               icon: typeIcon,
               isGroup: rc.nodeType !== "component",
               failingQualifications: qualifications?.failed || 0,
+              matchesFilter: _.every(
+                this.tagFilter,
+                (value, key) => rc.tags?.[key] === value,
+              ),
             } as FullComponent;
           });
         },
@@ -286,6 +317,12 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
           return parentsLookup;
         },
 
+        allTagKeys(): string[] {
+          return _.sortBy(
+            _.uniq(_.flatMap(this.rawComponentsById, (c) => _.keys(c.tags))),
+          );
+        },
+
         componentsByNodeId(): Record<ComponentNodeId, FullComponent> {
           return _.keyBy(_.values(this.componentsById), (c) => c.nodeId);
         },
@@ -394,6 +431,14 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
                 // TODO: add color + logo on categories?
                 schemas: _.compact(
                   _.map(category.items, (item) => {
+                    if (item.kind === "template") {
+                      return {
+                        kind: "template" as const,
+                        displayName: item.name,
+                        id: item.template_id,
+                        color: "#777",
+                      };
+                    }
                     // ignoring "link" items - don't think these are relevant at the moment
                     if (item.kind !== "item") return;
 
@@ -402,6 +447,7 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
                       this.schemaVariantsById,
                     ).find((v) => v.schemaId === item.schema_id);
                     return {
+                      kind: "schema" as const,
                       displayName: item.name,
                       id: item.schema_id,
                       // links: item.links, // not sure this is needed?
@@ -477,6 +523,70 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
           });
         },
 
+        setTagFilter(key: string, value: string | null) {
+          if (value === null) delete this.tagFilter[key];
+          else this.tagFilter[key] = value;
+        },
+
+        async FETCH_COMPONENT_TAGS(componentId: ComponentId) {
+          return new ApiRequest<{
+            tags: ComponentTag[];
+            effectiveTags: EffectiveComponentTag[];
+          }>({
+            url: "component/list_tags",
+            params: {
+              componentId,
+              ...visibilityParams,
+            },
+            onSuccess: (response) => {
+              this.tagsByComponentId[componentId] = response;
+            },
+          });
+        },
+        async SET_COMPONENT_TAG(
+          componentId: ComponentId,
+          key: string,
+          value: string,
+          inheritance: ComponentTagInheritance = "none",
+        ) {
+          if (changeSetsStore.creatingChangeSet)
+            throw new Error("race, wait until the change set is created");
+          if (changeSetId === nilId()) changeSetsStore.creatingChangeSet = true;
+
+          return new ApiRequest({
+            method: "post",
+            url: "component/set_tag",
+            params: {
+              componentId,
+              key,
+              value,
+              inheritance,
+              ...visibilityParams,
+            },
+            onSuccess: () => {
+              this.FETCH_COMPONENT_TAGS(componentId);
+            },
+          });
+        },
+        async REMOVE_COMPONENT_TAG(componentId: ComponentId, key: string) {
+          if (changeSetsStore.creatingChangeSet)
+            throw new Error("race, wait until the change set is created");
+          if (changeSetId === nilId()) changeSetsStore.creatingChangeSet = true;
+
+          return new ApiRequest({
+            method: "post",
+            url: "component/remove_tag",
+            params: {
+              componentId,
+              key,
+              ...visibilityParams,
+            },
+            onSuccess: () => {
+              this.FETCH_COMPONENT_TAGS(componentId);
+            },
+          });
+        },
+
         async FETCH_COMPONENT_DEBUG_VIEW(componentId: string) {
           return new ApiRequest<ComponentDebugView>({
             url: "component/debug",
@@ -572,6 +682,98 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
             },
           });
         },
+        async DUPLICATE_COMPONENTS(
+          componentIds: ComponentId[],
+          offset: Vector2d = { x: 50, y: 50 },
+        ) {
+          if (changeSetsStore.creatingChangeSet)
+            throw new Error("race, wait until the change set is created");
+          if (changeSetId === nilId()) changeSetsStore.creatingChangeSet = true;
+
+          return new ApiRequest<{
+            components: {
+              originalComponentId: ComponentId;
+              componentId: ComponentId;
+              nodeId: ComponentNodeId;
+              parentNodeId?: ComponentNodeId;
+            }[];
+          }>({
+            method: "post",
+            url: "diagram/duplicate_components",
+            headers: { accept: "application/json" },
+            params: {
+              componentIds,
+              offsetX: offset.x.toString(),
+              offsetY: offset.y.toString(),
+              ...visibilityParams,
+            },
+            onSuccess: (response) => {
+              // TODO: store component details rather than waiting for re-fetch
+            },
+          });
+        },
+        async SAVE_COMPONENT_TEMPLATE(
+          name: string,
+          componentIds: ComponentId[],
+          parameters: {
+            name: string;
+            description?: string;
+            defaultValue?: unknown;
+          }[] = [],
+          description?: string,
+        ) {
+          if (changeSetsStore.creatingChangeSet)
+            throw new Error("race, wait until the change set is created");
+          if (changeSetId === nilId()) changeSetsStore.creatingChangeSet = true;
+
+          return new ApiRequest<{ templateId: ComponentTemplateId }>({
+            method: "post",
+            url: "diagram/save_component_template",
+            headers: { accept: "application/json" },
+            params: {
+              name,
+              description,
+              componentIds,
+              parameters,
+              ...visibilityParams,
+            },
+            onSuccess: () => {
+              // re-fetch the menu so the new template shows up
+              this.FETCH_NODE_ADD_MENU();
+            },
+          });
+        },
+        async INSTANTIATE_COMPONENT_TEMPLATE(
+          templateId: ComponentTemplateId,
+          position: Vector2d,
+          parameters: Record<string, unknown> = {},
+        ) {
+          if (changeSetsStore.creatingChangeSet)
+            throw new Error("race, wait until the change set is created");
+          if (changeSetId === nilId()) changeSetsStore.creatingChangeSet = true;
+
+          return new ApiRequest<{
+            components: {
+              key: string;
+              componentId: ComponentId;
+              nodeId: ComponentNodeId;
+            }[];
+          }>({
+            method: "post",
+            url: "diagram/instantiate_component_template",
+            headers: { accept: "application/json" },
+            params: {
+              templateId,
+              x: position.x.toString(),
+              y: position.y.toString(),
+              parameters,
+              ...visibilityParams,
+            },
+            onSuccess: (response) => {
+              // TODO: store component details rather than waiting for re-fetch
+            },
+          });
+        },
         async CREATE_COMPONENT_CONNECTION(
           from: { nodeId: ComponentNodeId; socketId: SocketId },
           to: { nodeId: ComponentNodeId; socketId: SocketId },
@@ -936,6 +1138,35 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
           this.hoveredEdgeId = id;
         },
 
+        async BULK_UPDATE_PROPERTY_VALUE(
+          selection:
+            | { kind: "components"; componentIds: ComponentId[] }
+            | { kind: "schemaVariant"; schemaVariantId: SchemaVariantId },
+          propPath: string,
+          value: unknown,
+        ) {
+          if (changeSetsStore.creatingChangeSet)
+            throw new Error("race, wait until the change set is created");
+          if (changeSetId === nilId()) changeSetsStore.creatingChangeSet = true;
+
+          const statusStore = useStatusStore();
+          statusStore.markUpdateStarted();
+
+          return new ApiRequest<{ attributeValueIds: string[] }>({
+            method: "post",
+            url: "component/bulk_update_property_value",
+            params: {
+              selection,
+              propPath,
+              value,
+              ...visibilityParams,
+            },
+            onFail() {
+              statusStore.cancelUpdateStarted();
+            },
+          });
+        },
+
         async REFRESH_RESOURCE_INFO(componentId: ComponentId) {
           this.refreshingStatus[componentId] = true;
           return new ApiRequest({
@@ -976,6 +1207,13 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
         const realtimeStore = useRealtimeStore();
 
         realtimeStore.subscribe(this.$id, `changeset/${changeSetId}`, [
+          {
+            eventType: "StreamStatus",
+            callback: (streamStatus) => {
+              if (!streamStatus.gap) return;
+              this.FETCH_DIAGRAM_DATA();
+            },
+          },
           {
             eventType: "ComponentCreated",
             callback: (_update) => {
@@ -119,6 +119,13 @@ export const useFixesStore = () => {
             changeSetStore.selectedChangeSetId ?? "head"
           }`,
           [
+            {
+              eventType: "StreamStatus",
+              callback: (streamStatus) => {
+                if (!streamStatus.gap) return;
+                this.LOAD_FIX_BATCHES();
+              },
+            },
             {
               eventType: "ChangeSetWritten",
               callback: (writtenChangeSetId) => {
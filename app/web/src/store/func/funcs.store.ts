@@ -623,6 +623,13 @@ export const useFuncStore = () => {
 
         const realtimeStore = useRealtimeStore();
         realtimeStore.subscribe(this.$id, `changeset/${selectedChangeSetId}`, [
+          {
+            eventType: "StreamStatus",
+            callback: (streamStatus) => {
+              if (!streamStatus.gap) return;
+              this.FETCH_FUNC_LIST();
+            },
+          },
           {
             eventType: "ChangeSetWritten",
             callback: (writtenChangeSetId) => {
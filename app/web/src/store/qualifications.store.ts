@@ -172,6 +172,13 @@ export const useQualificationsStore = () => {
 
         const realtimeStore = useRealtimeStore();
         realtimeStore.subscribe(this.$id, `changeset/${changeSetId}`, [
+          {
+            eventType: "StreamStatus",
+            callback: (streamStatus) => {
+              if (!streamStatus.gap) return;
+              this.FETCH_QUALIFICATIONS_SUMMARY();
+            },
+          },
           {
             eventType: "CheckedQualifications",
             callback: () => {
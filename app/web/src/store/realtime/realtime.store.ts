@@ -35,15 +35,28 @@ type TrackedSubscription = EventTypeAndCallback & {
 type RealtimeEventMetadata = {
   version: number;
   workspace_pk: string;
+  // per-workspace sequence, used to resume the stream after reconnecting
+  sequence?: number;
 };
 
 export const useRealtimeStore = defineStore("realtime", () => {
   const authStore = useAuthStore();
 
+  // where we are in the stream of events, so that after reconnecting the backend can replay
+  // whatever we missed in the meantime
+  let streamEpoch: string | undefined;
+  let lastSequence: number | undefined;
+
   // ReconnectingWebsocket is a small wrapper around the native Websocket that should
   // handle basic reconnection logic
   const socket = new ReconnectingWebSocket(
-    () => `${API_WS_URL}/workspace_updates?token=Bearer+${authStore.token}`,
+    () => {
+      let url = `${API_WS_URL}/workspace_updates?token=Bearer+${authStore.token}`;
+      if (streamEpoch && lastSequence !== undefined) {
+        url += `&resumeEpoch=${streamEpoch}&resumeAfter=${lastSequence}`;
+      }
+      return url;
+    },
     [],
     {
       // see options https://www.npmjs.com/package/reconnecting-websocket#available-options
@@ -180,6 +193,14 @@ export const useRealtimeStore = defineStore("realtime", () => {
 
   socket.addEventListener("message", (messageEvent) => {
     const messageEventData = JSON.parse(messageEvent.data);
+    if (messageEventData.payload.kind === "StreamStatus") {
+      // when `gap` is set the backend could not replay everything we missed, so each store
+      // subscribed to StreamStatus reloads what it shows instead of waiting on the next event
+      streamEpoch = messageEventData.payload.data.epoch;
+      lastSequence = messageEventData.payload.data.sequence;
+    } else if (messageEventData.sequence !== undefined) {
+      lastSequence = messageEventData.sequence;
+    }
     handleEvent(
       messageEventData.payload.kind,
       messageEventData.payload.data,
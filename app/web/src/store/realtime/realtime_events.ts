@@ -75,6 +75,14 @@ export type WsEventPayloadMap = {
   //   components?: ComponentUpdateStatus[];
   // };
 
+  // sent when connecting and whenever events were missed, in which case `gap` is set and
+  // whatever is displayed may be stale
+  StreamStatus: {
+    epoch: string;
+    sequence: number;
+    gap: boolean;
+  };
+
   StatusUpdate: {
     pk: StatusUpdatePk;
     status: AttributeValueStatus | "statusStarted" | "statusFinished";
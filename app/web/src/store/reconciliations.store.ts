@@ -76,6 +76,13 @@ export const useReconciliationsStore = () => {
 
         const realtimeStore = useRealtimeStore();
         realtimeStore.subscribe(this.$id, `workspace/${workspacePk}/head`, [
+          {
+            eventType: "StreamStatus",
+            callback: (streamStatus) => {
+              if (!streamStatus.gap) return;
+              this.LOAD_RECONCILIATIONS();
+            },
+          },
           {
             eventType: "ChangeSetWritten",
             callback: (writtenChangeSetId) => {
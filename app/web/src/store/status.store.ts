@@ -428,6 +428,13 @@ export const useStatusStore = (forceChangeSetId?: ChangeSetId) => {
         let cleanupTimeout: Timeout;
 
         realtimeStore.subscribe(this.$id, `changeset/${changeSetId}`, [
+          {
+            eventType: "StreamStatus",
+            callback: (streamStatus) => {
+              if (!streamStatus.gap) return;
+              this.FETCH_CURRENT_STATUS();
+            },
+          },
           {
             eventType: "StatusUpdate",
             callback: (update, _metadata) => {
@@ -3,6 +3,7 @@ import * as _ from "lodash-es";
 import { watch } from "vue";
 import { addStoreHooks, ApiRequest } from "@si/vue-lib/pinia";
 import { Workspace } from "@/api/sdf/dal/workspace";
+import { WorkspaceMember, WorkspaceRole } from "@/api/sdf/dal/user";
 import { useAuthStore } from "./auth.store";
 import { useRouterStore } from "./router.store";
 
@@ -19,6 +20,7 @@ export const useWorkspacesStore = addStoreHooks(
     state: () => ({
       workspacesByPk: {} as Record<WorkspacePk, Workspace>,
       workspaceExports: [] as WorkspaceExportSummary[],
+      members: [] as WorkspaceMember[],
     }),
     getters: {
       allWorkspaces: (state) => _.values(state.workspacesByPk),
@@ -55,6 +57,25 @@ export const useWorkspacesStore = addStoreHooks(
           },
         });
       },
+      async FETCH_MEMBERS() {
+        return new ApiRequest<{ members: WorkspaceMember[] }>({
+          url: "/session/list_members",
+          onSuccess: (response) => {
+            this.members = response.members;
+          },
+        });
+      },
+      async SET_MEMBER_ROLE(userPk: string, role: WorkspaceRole) {
+        return new ApiRequest({
+          method: "post",
+          url: "/session/set_member_role",
+          params: { userPk, role },
+          onSuccess: () => {
+            const member = _.find(this.members, (m) => m.user.pk === userPk);
+            if (member) member.role = role;
+          },
+        });
+      },
     },
 
     onActivated() {
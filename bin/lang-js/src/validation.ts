@@ -17,6 +17,8 @@ const debug = Debug("langJs:validation");
 
 export interface ValidationRequest extends RequestWithCode {
   value: unknown;
+  // Other values the validation reads (props or input sockets), by name
+  inputs?: Record<string, unknown>;
 }
 
 // TODO: validation funcs should return array of error messages since more than one thing can be
@@ -72,12 +74,12 @@ async function execute(
   code: string,
   request: ValidationRequest
 ): Promise<ValidationResult> {
-  const { executionId, value } = request;
+  const { executionId, value, inputs } = request;
   let result: Record<string, unknown>;
   try {
     const runner = vm.run(code);
     result = await new Promise((resolve) => {
-      runner(value, (resolution: Record<string, unknown>) =>
+      runner(value, inputs ?? {}, (resolution: Record<string, unknown>) =>
         resolve(resolution)
       );
     });
@@ -124,9 +126,9 @@ async function execute(
 }
 
 const wrapCode = (code: string, handler: string) => `
-module.exports = function(value, callback) {
+module.exports = function(value, inputs, callback) {
   ${code}
-  const returnValue = ${handler}(value);
+  const returnValue = ${handler}(value, inputs);
   if (returnValue instanceof Promise) {
     returnValue.then((data) => callback(data))
       .catch((err) => {
@@ -49,6 +49,14 @@ pub(crate) struct Args {
     #[arg(long, env)]
     pub(crate) socket_addr: Option<String>,
 
+    /// The storage backend for module bundles [possible values: s3, local]
+    #[arg(long, env)]
+    pub(crate) storage_backend: Option<String>,
+
+    /// The directory module bundles are stored in when using the local storage backend
+    #[arg(long, env)]
+    pub(crate) local_storage_path: Option<String>,
+
     /// The s3 bucket access key id
     #[arg(long, env)]
     pub(crate) s3_access_key_id: Option<String>,
@@ -109,6 +117,12 @@ impl TryFrom<Args> for Config {
                 config_map.set("socket_addr", socket_addr);
             }
 
+            if let Some(storage_backend) = args.storage_backend {
+                config_map.set("storage_backend", storage_backend);
+            }
+            if let Some(local_storage_path) = args.local_storage_path {
+                config_map.set("local_storage.path", local_storage_path);
+            }
             if let Some(s3_access_key_id) = args.s3_access_key_id {
                 config_map.set("s3.access_key_id", s3_access_key_id);
             }
@@ -81,6 +81,7 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
 
     let (_resource_job_client, resource_job_processor) = JobProcessor::connect(&config).await?;
     let (_, status_receiver_job_processor) = JobProcessor::connect(&config).await?;
+    let (_, webhook_dispatcher_job_processor) = JobProcessor::connect(&config).await?;
 
     let pg_pool = Server::create_pg_pool(config.pg_pool()).await?;
 
@@ -131,6 +132,7 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
                 module_index_url,
             )?;
             let second_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let third_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
 
             Server::start_resource_refresh_scheduler(
                 pg_pool.clone(),
@@ -143,12 +145,22 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
             .await;
 
             Server::start_status_updater(
+                pg_pool.clone(),
+                nats.clone(),
+                status_receiver_job_processor,
+                veritech.clone(),
+                encryption_key,
+                second_shutdown_broadcast_rx,
+            )
+            .await?;
+
+            Server::start_webhook_dispatcher(
                 pg_pool,
                 nats,
-                status_receiver_job_processor,
+                webhook_dispatcher_job_processor,
                 veritech,
                 encryption_key,
-                second_shutdown_broadcast_rx,
+                third_shutdown_broadcast_rx,
             )
             .await?;
 
@@ -169,6 +181,7 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
             )
             .await?;
             let second_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let third_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
 
             Server::start_resource_refresh_scheduler(
                 pg_pool.clone(),
@@ -181,12 +194,22 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
             .await;
 
             Server::start_status_updater(
+                pg_pool.clone(),
+                nats.clone(),
+                status_receiver_job_processor,
+                veritech.clone(),
+                encryption_key,
+                second_shutdown_broadcast_rx,
+            )
+            .await?;
+
+            Server::start_webhook_dispatcher(
                 pg_pool,
                 nats,
-                status_receiver_job_processor,
+                webhook_dispatcher_job_processor,
                 veritech,
                 encryption_key,
-                second_shutdown_broadcast_rx,
+                third_shutdown_broadcast_rx,
             )
             .await?;
 
@@ -1,5 +1,5 @@
 use clap::{builder::PossibleValuesParser, Parser, Subcommand};
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 use strum::{Display, EnumString, EnumVariantNames};
 
 const NAME: &str = "si";
@@ -110,6 +110,8 @@ pub(crate) enum Commands {
     Update(UpdateArgs),
     /// Checks the status of the specified installation mode
     Status(StatusArgs),
+    /// Drives a workspace through a running sdf, printing json for scripting
+    Workspace(WorkspaceArgs),
     // Reports an error to System Initiative.
     // Report(ReportArgs),
 }
@@ -180,6 +182,187 @@ pub(crate) struct InstallArgs {
     pub skip_check: bool,
 }
 
+#[derive(Debug, clap::Args)]
+pub(crate) struct WorkspaceArgs {
+    /// The url of the sdf api. Defaults to the one of the sdf host and port
+    #[arg(long, env = "SI_SDF_URL")]
+    pub sdf_url: Option<String>,
+    /// An api token of the workspace, as created in its settings
+    #[arg(long, env = "SI_API_TOKEN", hide_env_values = true)]
+    pub api_token: String,
+    #[command(subcommand)]
+    pub command: WorkspaceCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum WorkspaceCommands {
+    /// Lists the open change sets
+    ListChangeSets(ListChangeSetsArgs),
+    /// Creates a change set
+    CreateChangeSet(CreateChangeSetArgs),
+    /// Creates a component from the name of its schema
+    CreateComponent(CreateComponentArgs),
+    /// Sets the value of a prop of a component by its path
+    SetProp(SetPropArgs),
+    /// Connects the sockets of two components by their names
+    Connect(ConnectArgs),
+    /// Shows the results of the qualifications of a component
+    Qualifications(QualificationsArgs),
+    /// Applies a change set to head
+    Apply(ApplyArgs),
+    /// Shows the changes needed for the workspace to match a manifest
+    PlanManifest(PlanManifestArgs),
+    /// Makes the changes needed for the workspace to match a manifest in a change set
+    ApplyManifest(ApplyManifestArgs),
+    /// Shows what importing a Terraform state file or CloudFormation template would change
+    PlanImport(PlanImportArgs),
+    /// Imports the resources of a Terraform state file or CloudFormation template
+    Import(ImportArgs),
+    /// Attaches the resources of a Terraform state file to the components it was imported as,
+    /// once the change set of the import is applied
+    AttachResources(AttachResourcesArgs),
+    /// Shows the results of fixes as they run
+    TailFixes(TailFixesArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct ListChangeSetsArgs {}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct CreateChangeSetArgs {
+    /// The name of the change set
+    pub name: String,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct CreateComponentArgs {
+    /// The change set to create the component in
+    #[arg(long, env = "SI_CHANGE_SET")]
+    pub change_set: String,
+    /// The name of the schema of the component
+    pub schema: String,
+    /// The name of the component
+    #[arg(long)]
+    pub name: Option<String>,
+    /// Where the component is placed on the diagram
+    #[arg(long, default_value = "0")]
+    pub x: isize,
+    /// Where the component is placed on the diagram
+    #[arg(long, default_value = "0")]
+    pub y: isize,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct SetPropArgs {
+    /// The change set the component is in
+    #[arg(long, env = "SI_CHANGE_SET")]
+    pub change_set: String,
+    /// The id of the component
+    pub component: String,
+    /// The path of the prop, like `/root/domain/region`. Entries of maps are reached by their key
+    pub path: String,
+    /// The value, as json. Anything that isn't json is taken as a string
+    pub value: String,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct ConnectArgs {
+    /// The change set the components are in
+    #[arg(long, env = "SI_CHANGE_SET")]
+    pub change_set: String,
+    /// The id of the component to connect from
+    pub from_component: String,
+    /// The name of the output socket to connect from
+    pub from_socket: String,
+    /// The id of the component to connect to
+    pub to_component: String,
+    /// The name of the input socket to connect to
+    pub to_socket: String,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct QualificationsArgs {
+    /// The change set the component is in
+    #[arg(long, env = "SI_CHANGE_SET")]
+    pub change_set: String,
+    /// The id of the component
+    pub component: String,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct ApplyArgs {
+    /// The change set to apply
+    #[arg(long, env = "SI_CHANGE_SET")]
+    pub change_set: String,
+    /// Show the results of the fixes the change set starts, until they have all run
+    #[clap(long)]
+    pub tail: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct PlanManifestArgs {
+    /// The change set to compare the manifest with. Defaults to head
+    #[arg(long, env = "SI_CHANGE_SET")]
+    pub change_set: Option<String>,
+    /// The manifest, as a yaml or json file
+    pub file: PathBuf,
+    /// Delete the components the manifest does not describe
+    #[clap(long)]
+    pub prune: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct ApplyManifestArgs {
+    /// The change set to make the changes in. Defaults to a new one
+    #[arg(long, env = "SI_CHANGE_SET")]
+    pub change_set: Option<String>,
+    /// The manifest, as a yaml or json file
+    pub file: PathBuf,
+    /// Delete the components the manifest does not describe
+    #[clap(long)]
+    pub prune: bool,
+    /// Apply the change set once the changes are made
+    #[clap(long)]
+    pub apply: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct PlanImportArgs {
+    /// The change set to compare the import with. Defaults to head
+    #[arg(long, env = "SI_CHANGE_SET")]
+    pub change_set: Option<String>,
+    /// What the file is
+    #[arg(long, value_parser = ["terraform", "cloudFormation"])]
+    pub source: String,
+    /// The Terraform state file (`.tfstate`) or CloudFormation template
+    pub file: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct ImportArgs {
+    /// The change set to import into. Defaults to a new one
+    #[arg(long, env = "SI_CHANGE_SET")]
+    pub change_set: Option<String>,
+    /// What the file is
+    #[arg(long, value_parser = ["terraform", "cloudFormation"])]
+    pub source: String,
+    /// The Terraform state file (`.tfstate`) or CloudFormation template
+    pub file: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct AttachResourcesArgs {
+    /// The Terraform state file (`.tfstate`) the resources were imported from
+    pub file: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct TailFixesArgs {
+    /// Stop once a batch of fixes has run
+    #[clap(long)]
+    pub until_batch_done: bool,
+}
+
 impl Args {
     pub(crate) fn mode(&self) -> Mode {
         Mode::from_str(&self.mode).expect("mode is a validated input str")
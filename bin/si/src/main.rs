@@ -1,8 +1,9 @@
-use crate::args::{Commands, Engine};
+use crate::args::{Args, Commands, Engine, WorkspaceArgs, WorkspaceCommands};
 use color_eyre::Result;
 use si_cli::engine::docker_engine::DockerEngine;
 use si_cli::engine::podman_engine::PodmanEngine;
 use si_cli::state::AppState;
+use si_cli::workspace::Workspace;
 use std::sync::Arc;
 use telemetry_application::{prelude::*, TelemetryConfig};
 use tokio::sync::oneshot::Sender;
@@ -22,6 +23,13 @@ async fn main() -> Result<()> {
         .build()?;
     let _telemetry = telemetry_application::init(config)?;
     let args = args::parse();
+
+    // Workspace commands talk to a running sdf, so they need neither a container engine nor the
+    // launcher around them.
+    if let Commands::Workspace(workspace_args) = &args.command {
+        return workspace(&args, workspace_args).await;
+    }
+
     let mode = args.mode();
     let is_preview = args.is_preview;
     let mut engine = args.engine();
@@ -146,9 +154,11 @@ async fn main() -> Result<()> {
         }
         Commands::Status(args) => {
             state.status(args.show_logs, args.log_lines).await?;
-        } // Commands::Report(_args) => {
-          //     state.report().await?;
-          // }
+        }
+        Commands::Workspace(_) => unreachable!("workspace commands run without the launcher"),
+        // Commands::Report(_args) => {
+        //     state.report().await?;
+        // }
     }
 
     drop(state);
@@ -159,6 +169,92 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+async fn workspace(args: &Args, workspace_args: &WorkspaceArgs) -> Result<()> {
+    let sdf_url = match &workspace_args.sdf_url {
+        Some(sdf_url) => sdf_url.clone(),
+        None => format!("http://{}:{}/api/", args.sdf_host, args.sdf_port),
+    };
+    let workspace = Workspace::new(&sdf_url, &workspace_args.api_token)?;
+
+    match &workspace_args.command {
+        WorkspaceCommands::ListChangeSets(_args) => {
+            workspace.list_change_sets().await?;
+        }
+        WorkspaceCommands::CreateChangeSet(args) => {
+            workspace.create_change_set(&args.name).await?;
+        }
+        WorkspaceCommands::CreateComponent(args) => {
+            workspace
+                .create_component(
+                    &args.change_set,
+                    &args.schema,
+                    args.name.as_deref(),
+                    args.x,
+                    args.y,
+                )
+                .await?;
+        }
+        WorkspaceCommands::SetProp(args) => {
+            workspace
+                .set_prop(&args.change_set, &args.component, &args.path, &args.value)
+                .await?;
+        }
+        WorkspaceCommands::Connect(args) => {
+            workspace
+                .connect(
+                    &args.change_set,
+                    &args.from_component,
+                    &args.from_socket,
+                    &args.to_component,
+                    &args.to_socket,
+                )
+                .await?;
+        }
+        WorkspaceCommands::Qualifications(args) => {
+            workspace
+                .qualifications(&args.change_set, &args.component)
+                .await?;
+        }
+        WorkspaceCommands::Apply(args) => {
+            workspace
+                .apply_change_set(&args.change_set, args.tail)
+                .await?;
+        }
+        WorkspaceCommands::PlanManifest(args) => {
+            workspace
+                .plan_manifest(args.change_set.as_deref(), &args.file, args.prune)
+                .await?;
+        }
+        WorkspaceCommands::ApplyManifest(args) => {
+            workspace
+                .apply_manifest(
+                    args.change_set.as_deref(),
+                    &args.file,
+                    args.prune,
+                    args.apply,
+                )
+                .await?;
+        }
+        WorkspaceCommands::PlanImport(args) => {
+            workspace
+                .plan_import(args.change_set.as_deref(), &args.source, &args.file)
+                .await?;
+        }
+        WorkspaceCommands::Import(args) => {
+            workspace
+                .import(args.change_set.as_deref(), &args.source, &args.file)
+                .await?;
+        }
+        WorkspaceCommands::AttachResources(args) => {
+            workspace.attach_resources(&args.file).await?;
+        }
+        WorkspaceCommands::TailFixes(args) => {
+            workspace.tail_fixes(args.until_batch_done).await?;
+        }
+    }
+    Ok(())
+}
+
 async fn wait_for_posthog_flush(done_sender: Sender<()>, sender: si_posthog::PosthogSender) {
     sender.run().await;
     done_sender
@@ -960,6 +960,7 @@ mod tests {
             execution_id: "1337".to_string(),
             handler: "validate".to_string(),
             value: "a string is a sequence of bytes".into(),
+            inputs: Default::default(),
             code_base64: base64_encode(
                 r"function validate(value) {
                     console.log('i came here to chew bubblegum and validate prop values');
@@ -6,6 +6,9 @@ pub struct ValidationRequest {
     pub execution_id: String,
     pub handler: String,
     pub value: serde_json::Value,
+    /// Other values the validation reads, by name. Passed to the handler as its second argument.
+    #[serde(default)]
+    pub inputs: serde_json::Map<String, serde_json::Value>,
     pub code_base64: String,
 }
 
@@ -7,13 +7,20 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{DalContext, HistoryActor, StandardModelError, User, UserPk};
+use crate::{ApiToken, ApiTokenPk, DalContext, HistoryActor, StandardModelError, User, UserPk};
 
 /// The actor entitiy that initiates an activitiy--this could represent be a person, service, etc.
 #[remain::sorted]
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 pub enum ActorView {
+    /// Represents automation acting through an [`ApiToken`]
+    ApiToken {
+        /// The token's ID
+        pk: ApiTokenPk,
+        /// A display label
+        label: String,
+    },
     /// Represents a system-generated activity
     System {
         /// A display label
@@ -54,6 +61,20 @@ impl ActorView {
                     label: user.name().to_string(),
                 })
             }
+            HistoryActor::ApiToken(api_token_pk) => {
+                let api_token = ApiToken::get_by_pk(ctx, api_token_pk)
+                    .await?
+                    .ok_or_else(|| {
+                        StandardModelError::ModelMissing(
+                            "api_tokens".to_string(),
+                            api_token_pk.to_string(),
+                        )
+                    })?;
+                Ok(Self::ApiToken {
+                    pk: api_token.pk(),
+                    label: api_token.name().to_string(),
+                })
+            }
             HistoryActor::SystemInit => Ok(Self::System {
                 label: Self::system_label(),
             }),
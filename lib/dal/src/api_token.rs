@@ -0,0 +1,283 @@
+//! This module contains [`ApiToken`], a long-lived credential that lets automation (such as CI
+//! pipelines) act on a [`Workspace`](crate::Workspace) without a session.
+//!
+//! Only a hash of the token is stored: the token itself is returned once, by [`ApiToken::new`].
+//! Tokens act with the [`WorkspaceRole`] they were created with, but never with more than their
+//! creator is currently allowed to do, and they can expire and be revoked. Whatever a token does
+//! is recorded with [`HistoryActor::ApiToken`].
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use si_data_nats::NatsError;
+use si_data_pg::PgError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    pk, DalContext, HistoryActor, HistoryEvent, HistoryEventError, Tenancy, Timestamp,
+    TransactionsError, User, UserCapability, UserError, UserPk, WorkspacePk, WorkspaceRole,
+};
+
+const API_TOKEN_FIND_BY_TOKEN: &str = include_str!("queries/api_token/find_by_token.sql");
+const API_TOKEN_GET_BY_PK: &str = include_str!("queries/api_token/get_by_pk.sql");
+const API_TOKEN_LIST_FOR_WORKSPACE: &str = include_str!("queries/api_token/list_for_workspace.sql");
+
+/// Every [`ApiToken`] starts with this prefix, which tells them apart from session tokens.
+pub const API_TOKEN_PREFIX: &str = "si_";
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ApiTokenError {
+    #[error("api tokens can only be created by users")]
+    CreatorNotUser,
+    #[error("api token name cannot be empty")]
+    EmptyName,
+    #[error("api token expiration is in the past: {0}")]
+    ExpiresInPast(DateTime<Utc>),
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("nats txn error: {0}")]
+    Nats(#[from] NatsError),
+    #[error("no workspace in tenancy")]
+    NoWorkspaceInTenancy,
+    #[error("api token not found: {0}")]
+    NotFound(ApiTokenPk),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("user error: {0}")]
+    User(#[from] UserError),
+}
+
+pub type ApiTokenResult<T> = Result<T, ApiTokenError>;
+
+pk!(ApiTokenPk);
+
+/// A long-lived credential for a [`Workspace`](crate::Workspace). See the
+/// [`module`](crate::api_token) for more information.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ApiToken {
+    pk: ApiTokenPk,
+    workspace_pk: WorkspacePk,
+    created_by_user_pk: UserPk,
+    name: String,
+    role: WorkspaceRole,
+    expires_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+}
+
+impl ApiToken {
+    /// Creates a token for the [`Workspace`](crate::Workspace) of the
+    /// [`Tenancy`](crate::Tenancy) of the [`DalContext`], on behalf of the [`User`] acting on it.
+    /// Returns the token along with its secret, which can't be retrieved again.
+    #[instrument(skip_all)]
+    pub async fn new(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        role: WorkspaceRole,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> ApiTokenResult<(Self, String)> {
+        let name = name.as_ref().trim();
+        if name.is_empty() {
+            return Err(ApiTokenError::EmptyName);
+        }
+        if let Some(expires_at) = expires_at {
+            if expires_at <= Utc::now() {
+                return Err(ApiTokenError::ExpiresInPast(expires_at));
+            }
+        }
+        let created_by_user_pk = match ctx.history_actor() {
+            HistoryActor::User(user_pk) => *user_pk,
+            HistoryActor::ApiToken(_) | HistoryActor::SystemInit => {
+                return Err(ApiTokenError::CreatorNotUser)
+            }
+        };
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(ApiTokenError::NoWorkspaceInTenancy)?;
+
+        let secret = format!(
+            "{API_TOKEN_PREFIX}{}",
+            hex::encode(rand::thread_rng().gen::<[u8; 32]>())
+        );
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM api_token_create_v1($1, $2, $3, $4, $5, $6)",
+                &[
+                    &workspace_pk,
+                    &created_by_user_pk,
+                    &name,
+                    &hash_secret(&secret),
+                    &role.as_ref(),
+                    &expires_at,
+                ],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        let object: Self = serde_json::from_value(json)?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "api_token.create",
+            "API token created",
+            &serde_json::json![{ "pk": object.pk, "name": object.name, "role": object.role }],
+        )
+        .await?;
+
+        Ok((object, secret))
+    }
+
+    pub fn pk(&self) -> ApiTokenPk {
+        self.pk
+    }
+
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn created_by_user_pk(&self) -> UserPk {
+        self.created_by_user_pk
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn role(&self) -> WorkspaceRole {
+        self.role
+    }
+
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    pub fn revoked_at(&self) -> Option<DateTime<Utc>> {
+        self.revoked_at
+    }
+
+    /// Whether the token can still be used: it was neither revoked nor has it expired.
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+            && self
+                .expires_at
+                .map_or(true, |expires_at| expires_at > Utc::now())
+    }
+
+    /// Finds the token with the given secret, across every [`Workspace`](crate::Workspace),
+    /// whether or not it is still [`active`](Self::is_active).
+    pub async fn find_by_secret(
+        ctx: &DalContext,
+        secret: impl AsRef<str>,
+    ) -> ApiTokenResult<Option<Self>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(API_TOKEN_FIND_BY_TOKEN, &[&hash_secret(secret.as_ref())])
+            .await?;
+        object_option_from_row(row)
+    }
+
+    /// Gets the token in the [`Workspace`](crate::Workspace) of the
+    /// [`Tenancy`](crate::Tenancy) of the [`DalContext`].
+    pub async fn get_by_pk(ctx: &DalContext, pk: ApiTokenPk) -> ApiTokenResult<Option<Self>> {
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(ApiTokenError::NoWorkspaceInTenancy)?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(API_TOKEN_GET_BY_PK, &[&pk])
+            .await?;
+        Ok(object_option_from_row(row)?.filter(|token: &Self| token.workspace_pk == workspace_pk))
+    }
+
+    /// Lists the tokens of the [`Workspace`](crate::Workspace) of the
+    /// [`Tenancy`](crate::Tenancy) of the [`DalContext`], newest first.
+    pub async fn list(ctx: &DalContext) -> ApiTokenResult<Vec<Self>> {
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(ApiTokenError::NoWorkspaceInTenancy)?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(API_TOKEN_LIST_FOR_WORKSPACE, &[&workspace_pk])
+            .await?;
+
+        let mut tokens = Vec::with_capacity(rows.len());
+        for row in rows {
+            let json: serde_json::Value = row.try_get("object")?;
+            tokens.push(serde_json::from_value(json)?);
+        }
+        Ok(tokens)
+    }
+
+    /// Revokes the token for good. Revoking a revoked token does nothing.
+    #[instrument(skip_all)]
+    pub async fn revoke(&mut self, ctx: &DalContext) -> ApiTokenResult<()> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one("SELECT object FROM api_token_revoke_v1($1)", &[&self.pk])
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        *self = serde_json::from_value(json)?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "api_token.revoke",
+            "API token revoked",
+            &serde_json::json![{ "pk": self.pk, "name": self.name }],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns whether the token is allowed to do the given thing: it has to be
+    /// [`active`](Self::is_active), its role has to allow it, and so does the current role of
+    /// the [`User`] who created it.
+    pub async fn authorize(
+        &self,
+        ctx: &DalContext,
+        capability: UserCapability,
+    ) -> ApiTokenResult<bool> {
+        if !self.is_active() || !self.role.can(capability) {
+            return Ok(false);
+        }
+        let ctx = ctx.clone_with_new_tenancy(Tenancy::new(self.workspace_pk));
+        Ok(User::authorize(&ctx, &self.created_by_user_pk, capability).await?)
+    }
+}
+
+/// Hashes a token secret the way it is stored, so the secret itself never reaches the database.
+fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+fn object_option_from_row(row: Option<si_data_pg::PgRow>) -> ApiTokenResult<Option<ApiToken>> {
+    match row {
+        Some(row) => {
+            let json: serde_json::Value = row.try_get("object")?;
+            Ok(Some(serde_json::from_value(json)?))
+        }
+        None => Ok(None),
+    }
+}
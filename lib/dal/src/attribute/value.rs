@@ -64,11 +64,12 @@ use crate::{
     standard_model::{self, TypeHint},
     standard_model_accessor, standard_model_belongs_to, standard_model_has_many,
     AttributeContextError, AttributePrototypeArgumentError, Component, ComponentId, DalContext,
-    Func, FuncBinding, FuncError, HistoryEventError, IndexMap, InternalProvider,
-    InternalProviderId, Prop, PropError, PropId, PropKind, StandardModel, StandardModelError,
-    Tenancy, Timestamp, TransactionsError, Visibility, WsEventError,
+    Func, FuncBackendKind, FuncBinding, FuncError, FuncId, HistoryEventError, IndexMap,
+    InternalProvider, InternalProviderId, Prop, PropError, PropId, PropKind, StandardModel,
+    StandardModelError, Tenancy, Timestamp, TransactionsError, Visibility, WsEventError,
 };
 
+pub mod provenance;
 pub mod view;
 
 const CHILD_ATTRIBUTE_VALUES_FOR_CONTEXT: &str =
@@ -178,8 +179,12 @@ pub enum AttributeValueError {
     NotFoundForInternalProviderContext(AttributeContext),
     #[error("No AttributeValue found for AttributeReadContext: {0:?}")]
     NotFoundForReadContext(AttributeReadContext),
+    #[error("attribute value is not overridden: {0}")]
+    NotOverridden(AttributeValueId),
     #[error("using json pointer for attribute view yielded no value")]
     NoValueForJsonPointer,
+    #[error("overridden attribute prototype {1} not found for attribute value: {0}")]
+    OverriddenAttributePrototypeNotFound(AttributeValueId, AttributePrototypeId),
     #[error(
         "parent must be for an array, map, or object prop: attribute resolver id {0} is for a {1}"
     )]
@@ -242,6 +247,15 @@ pub struct AttributeValue {
     /// If this is a `sealed_proxy`, then it should **not** update its [`FuncBindingReturnValueId`] from the
     /// [`AttributeValue`] referenced to in `proxy_for_attribute_value_id`.
     sealed_proxy: bool,
+    /// The [`AttributePrototypeId`] that computed this [`AttributeValue`] before it was manually
+    /// overridden, if it has been.
+    overridden_attribute_prototype_id: Option<AttributePrototypeId>,
+    /// The [`FuncId`] of the function that computed this [`AttributeValue`] before it was
+    /// manually overridden, if it has been.
+    overridden_func_id: Option<FuncId>,
+    /// If this is `locked`, then it will **not** be recomputed from its [`AttributePrototype`]
+    /// when the values it depends on change.
+    locked: bool,
     pub index_map: Option<IndexMap>,
     pub key: Option<String>,
     #[serde(flatten)]
@@ -301,6 +315,13 @@ impl AttributeValue {
         AttributeValueResult
     );
     standard_model_accessor!(sealed_proxy, bool, AttributeValueResult);
+    standard_model_accessor!(
+        overridden_attribute_prototype_id,
+        Option<Pk(AttributePrototypeId)>,
+        AttributeValueResult
+    );
+    standard_model_accessor!(overridden_func_id, Option<Pk(FuncId)>, AttributeValueResult);
+    standard_model_accessor!(locked, bool, AttributeValueResult);
     standard_model_accessor!(func_binding_id, Pk(FuncBindingId), AttributeValueResult);
     standard_model_accessor!(
         func_binding_return_value_id,
@@ -754,6 +775,10 @@ impl AttributeValue {
         // TODO(nick,paulo,zack,jacob): ensure we do not _have_ to do this in the future.
         let ctx = &ctx.clone_without_deleted_visibility();
 
+        // Setting a value that a function computes is an override, so we need to know what was
+        // computing it beforehand in order to be able to reset it later.
+        let computed_by = Self::computed_by(ctx, attribute_value_id).await?;
+
         let row = ctx.txns()
             .await?
             .pg()
@@ -773,6 +798,18 @@ impl AttributeValue {
 
         let new_attribute_value_id: AttributeValueId = row.try_get("new_attribute_value_id")?;
 
+        if let Some((attribute_prototype_id, func_id)) = computed_by {
+            let mut attribute_value = Self::get_by_id(ctx, &new_attribute_value_id)
+                .await?
+                .ok_or(AttributeValueError::MissingForId(new_attribute_value_id))?;
+            attribute_value
+                .set_overridden_attribute_prototype_id(ctx, Some(attribute_prototype_id))
+                .await?;
+            attribute_value
+                .set_overridden_func_id(ctx, Some(func_id))
+                .await?;
+        }
+
         // TODO(fnichol): we might want to fire off a status even at this point, however we've
         // already updated the initial attribute value, so is there much value?
 
@@ -788,6 +825,101 @@ impl AttributeValue {
         Ok((value, new_attribute_value_id))
     }
 
+    /// Finds the [`AttributePrototypeId`] and [`FuncId`] computing the given
+    /// [`AttributeValue`](Self) if it is for a [`Prop`] and its value comes from a function,
+    /// rather than from being set as given.
+    async fn computed_by(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+    ) -> AttributeValueResult<Option<(AttributePrototypeId, FuncId)>> {
+        let attribute_value = Self::get_by_id(ctx, &attribute_value_id)
+            .await?
+            .ok_or(AttributeValueError::MissingForId(attribute_value_id))?;
+        if !attribute_value
+            .context
+            .is_least_specific_field_kind_prop()?
+        {
+            return Ok(None);
+        }
+
+        let attribute_prototype = match attribute_value.attribute_prototype(ctx).await? {
+            Some(attribute_prototype) => attribute_prototype,
+            None => return Ok(None),
+        };
+        let func = Func::get_by_id(ctx, &attribute_prototype.func_id())
+            .await?
+            .ok_or_else(|| {
+                AttributeValueError::MissingFunc(attribute_prototype.func_id().to_string())
+            })?;
+        if provenance::is_set_func(*func.backend_kind())
+            || *func.backend_kind() == FuncBackendKind::Unset
+        {
+            return Ok(None);
+        }
+
+        Ok(Some((*attribute_prototype.id(), *func.id())))
+    }
+
+    /// Returns true if this [`AttributeValue`](Self) was set manually in place of the value its
+    /// function computed.
+    pub fn is_overridden(&self) -> bool {
+        self.overridden_attribute_prototype_id.is_some()
+    }
+
+    /// Discards the manual override of this [`AttributeValue`](Self), restoring the function that
+    /// computed it before it was overridden and re-running it. This also unlocks the value.
+    pub async fn reset_to_computed(&mut self, ctx: &DalContext) -> AttributeValueResult<()> {
+        let (overridden_attribute_prototype_id, overridden_func_id) = match (
+            self.overridden_attribute_prototype_id,
+            self.overridden_func_id,
+        ) {
+            (Some(attribute_prototype_id), Some(func_id)) => (attribute_prototype_id, func_id),
+            _ => return Err(AttributeValueError::NotOverridden(self.id)),
+        };
+
+        let mut attribute_prototype = self.attribute_prototype(ctx).await?.ok_or_else(|| {
+            AttributeValueError::AttributePrototypeNotFound(self.id, *ctx.visibility())
+        })?;
+        if *attribute_prototype.id() == overridden_attribute_prototype_id {
+            // The override replaced the function of the prototype in place, keeping its arguments.
+            attribute_prototype
+                .set_func_id(ctx, overridden_func_id)
+                .await
+                .map_err(|e| AttributeValueError::AttributePrototype(e.to_string()))?;
+        } else {
+            // The override created a prototype just for this value, so we point the value back
+            // at the original prototype and get rid of it.
+            if AttributePrototype::get_by_id(ctx, &overridden_attribute_prototype_id)
+                .await?
+                .is_none()
+            {
+                return Err(AttributeValueError::OverriddenAttributePrototypeNotFound(
+                    self.id,
+                    overridden_attribute_prototype_id,
+                ));
+            }
+            self.set_attribute_prototype(ctx, &overridden_attribute_prototype_id)
+                .await?;
+            attribute_prototype.delete_by_id(ctx).await?;
+        }
+
+        self.set_overridden_attribute_prototype_id(ctx, None::<AttributePrototypeId>)
+            .await?;
+        self.set_overridden_func_id(ctx, None::<FuncId>).await?;
+        self.set_locked(ctx, false).await?;
+
+        self.update_from_prototype_function(ctx).await?;
+
+        ctx.enqueue_job(DependentValuesUpdate::new(
+            ctx.access_builder(),
+            *ctx.visibility(),
+            vec![self.id],
+        ))
+        .await?;
+
+        Ok(())
+    }
+
     /// Insert a new value under the parent [`AttributeValue`] in the given [`AttributeContext`]. This is mostly only
     /// useful for adding elements to a [`PropKind::Array`], or to a [`PropKind::Map`]. Updating existing values in an
     /// [`Array`](PropKind::Array), or [`Map`](PropKind::Map), and setting/updating all other [`PropKind`] should be
@@ -1017,6 +1149,8 @@ impl AttributeValue {
     /// does not have a parent `Prop` (this is typically the `InternalProvider` for
     /// the "root" `Prop` of a `SchemaVariant`), then it will also enqueue a
     /// `CodeGeneration` job for the `Component`.
+    ///
+    /// Locked `AttributeValues` are left untouched.
     #[instrument(
         name = "attribute_value.update_from_prototype_function",
         skip_all,
@@ -1030,6 +1164,12 @@ impl AttributeValue {
         &mut self,
         ctx: &DalContext,
     ) -> AttributeValueResult<()> {
+        // Locked values keep what they were set to, no matter what changes upstream.
+        if self.locked {
+            debug!("AttributeValue is locked");
+            return Ok(());
+        }
+
         // Check if this AttributeValue is for an implicit InternalProvider as they have special behavior that doesn't involve
         // AttributePrototype and AttributePrototypeArguments.
         if self
@@ -1137,6 +1277,25 @@ impl AttributeValue {
         self.set_func_binding_return_value_id(ctx, *func_binding_return_value.id())
             .await?;
 
+        // Input sockets aren't part of the prop tree, so the validations reading them need to be
+        // re-run here rather than when the root prop is updated.
+        if self
+            .context
+            .is_least_specific_field_kind_internal_provider()?
+            && self.context.component_id().is_some()
+        {
+            let ctx_deletion = &ctx.clone_with_delete_visibility();
+            let component = Component::get_by_id(ctx_deletion, &self.context.component_id())
+                .await?
+                .ok_or(AttributeValueError::ComponentNotFoundById(
+                    self.context.component_id(),
+                ))?;
+            component
+                .check_validations_for_input_socket(ctx, self.context.internal_provider_id())
+                .await
+                .map_err(|e| AttributeValueError::Component(e.to_string()))?;
+        }
+
         // If the value we just updated was for a Prop, we might have run a function that
         // generates a deep data structure. If the Prop is an Array/Map/Object, then the
         // value should be an empty Array/Map/Object, while the unprocessed value contains
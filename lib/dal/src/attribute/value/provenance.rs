@@ -0,0 +1,419 @@
+//! This module contains [`AttributeValueProvenance`], which explains where the value of an
+//! [`AttributeValue`] came from by walking back through the
+//! [`AttributePrototype`](crate::AttributePrototype) that set it, the
+//! [`AttributePrototypeArguments`](crate::AttributePrototypeArgument) that fed its
+//! [`Func`](crate::Func), and the [`InternalProviders`](crate::InternalProvider) and
+//! [`ExternalProviders`](crate::ExternalProvider) those arguments read from (including across
+//! [`Edges`](crate::Edge) to other [`Components`](crate::Component)). This object does not exist
+//! in the database.
+
+use async_recursion::async_recursion;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::{
+    AttributePrototypeArgument, AttributeReadContext, AttributeValue, AttributeValueError,
+    AttributeValueId, AttributeValueResult, AttributeView, Component, ComponentId, DalContext,
+    ExternalProvider, ExternalProviderId, Func, FuncArgument, FuncBackendKind, FuncBinding, FuncId,
+    InternalProvider, InternalProviderId, Prop, PropId, StandardModel,
+};
+
+/// Where an [`AttributeValue`] lives on its [`Component`](crate::Component).
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AttributeValueLocation {
+    /// An output socket.
+    #[serde(rename_all = "camelCase")]
+    ExternalProvider {
+        external_provider_id: ExternalProviderId,
+        name: String,
+    },
+    /// An input socket, or the implicit [`InternalProvider`] of a [`Prop`] when `prop_id` is set.
+    #[serde(rename_all = "camelCase")]
+    InternalProvider {
+        internal_provider_id: InternalProviderId,
+        name: String,
+        prop_id: Option<PropId>,
+    },
+    /// A [`Prop`], with its path written as "/root/domain/name".
+    #[serde(rename_all = "camelCase")]
+    Prop { prop_id: PropId, path: String },
+}
+
+/// The [`Func`](crate::Func) of the [`AttributePrototype`](crate::AttributePrototype) which set
+/// an [`AttributeValue`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeValueProvenanceFunc {
+    pub func_id: FuncId,
+    pub name: String,
+    pub display_name: Option<String>,
+    pub backend_kind: FuncBackendKind,
+}
+
+/// An argument of a computing [`Func`](crate::Func) and where its value came from.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeValueProvenanceArgument {
+    pub name: String,
+    /// The value the [`Func`](crate::Func) was executed with for this argument.
+    pub value: Option<Value>,
+    /// Where the value was read from. An argument fed by several [`Edges`](crate::Edge) has one
+    /// source per connected [`Component`](crate::Component).
+    pub sources: Vec<AttributeValueProvenance>,
+}
+
+/// How an [`AttributeValue`] got its value.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AttributeValueProvenanceSource {
+    /// Computed by a [`Func`](crate::Func) from its arguments.
+    Computed {
+        func: AttributeValueProvenanceFunc,
+        arguments: Vec<AttributeValueProvenanceArgument>,
+    },
+    /// Set on the [`SchemaVariant`](crate::SchemaVariant) rather than on the
+    /// [`Component`](crate::Component) itself.
+    Default { func: AttributeValueProvenanceFunc },
+    /// Set on the [`Component`](crate::Component) itself.
+    Manual { func: AttributeValueProvenanceFunc },
+    /// Part of a value produced by the [`Func`](crate::Func) of a parent [`Prop`], such as a
+    /// [`Func`](crate::Func) returning a whole object.
+    ParentFunc {
+        parent: Box<AttributeValueProvenance>,
+    },
+    /// Passed on as-is by the implicit [`InternalProvider`] of a [`Prop`].
+    Prop {
+        source: Box<AttributeValueProvenance>,
+    },
+    /// The value was explained earlier in the tree and is not walked again.
+    Repeated,
+    /// Explicitly unset.
+    Unset,
+}
+
+/// Explains where the value of an [`AttributeValue`] came from, as a tree whose leaves are values
+/// set manually, set by default or computed without arguments. See the
+/// [`module`](crate::attribute::value::provenance) for more information.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeValueProvenance {
+    pub attribute_value_id: AttributeValueId,
+    pub component_id: ComponentId,
+    pub component_name: Option<String>,
+    pub location: AttributeValueLocation,
+    pub value: Option<Value>,
+    pub source: AttributeValueProvenanceSource,
+}
+
+impl AttributeValue {
+    /// Walk back to the sources of the value of [`Self`]. See
+    /// [`AttributeValueProvenance`] for more information.
+    pub async fn provenance(
+        &self,
+        ctx: &DalContext,
+    ) -> AttributeValueResult<AttributeValueProvenance> {
+        AttributeValueProvenance::for_attribute_value(ctx, self, &mut HashSet::new()).await
+    }
+}
+
+impl AttributeValueProvenance {
+    #[async_recursion]
+    async fn for_attribute_value(
+        ctx: &DalContext,
+        attribute_value: &AttributeValue,
+        visited: &mut HashSet<AttributeValueId>,
+    ) -> AttributeValueResult<Self> {
+        let context = attribute_value.context;
+        let component_id = context.component_id();
+        let component_name = if component_id.is_some() {
+            // Not every component has a name set, which is not worth failing over.
+            Component::find_name(ctx, component_id).await.ok()
+        } else {
+            None
+        };
+
+        let mut provenance = Self {
+            attribute_value_id: *attribute_value.id(),
+            component_id,
+            component_name,
+            location: Self::location(ctx, attribute_value).await?,
+            value: Self::value(ctx, attribute_value).await?,
+            source: AttributeValueProvenanceSource::Repeated,
+        };
+
+        // Several arguments can read from the same value, and sockets can be connected in a
+        // loop, so each value is only walked once.
+        if visited.insert(*attribute_value.id()) {
+            provenance.source = Self::source(ctx, attribute_value, visited).await?;
+        }
+
+        Ok(provenance)
+    }
+
+    async fn location(
+        ctx: &DalContext,
+        attribute_value: &AttributeValue,
+    ) -> AttributeValueResult<AttributeValueLocation> {
+        let context = attribute_value.context;
+        if context.is_least_specific_field_kind_prop()? {
+            let prop = Prop::get_by_id(ctx, &context.prop_id())
+                .await?
+                .ok_or_else(|| AttributeValueError::PropNotFound(context.prop_id()))?;
+            Ok(AttributeValueLocation::Prop {
+                prop_id: *prop.id(),
+                path: format!("/{}", prop.path().with_replaced_sep("/")),
+            })
+        } else if context.is_least_specific_field_kind_internal_provider()? {
+            let internal_provider =
+                InternalProvider::get_by_id(ctx, &context.internal_provider_id())
+                    .await?
+                    .ok_or_else(|| {
+                        AttributeValueError::InternalProviderNotFound(
+                            context.internal_provider_id(),
+                        )
+                    })?;
+            Ok(AttributeValueLocation::InternalProvider {
+                internal_provider_id: *internal_provider.id(),
+                name: internal_provider.name().to_owned(),
+                prop_id: Some(*internal_provider.prop_id()).filter(|prop_id| prop_id.is_some()),
+            })
+        } else {
+            let external_provider =
+                ExternalProvider::get_by_id(ctx, &context.external_provider_id())
+                    .await?
+                    .ok_or_else(|| {
+                        AttributeValueError::ExternalProvider(format!(
+                            "external provider not found: {}",
+                            context.external_provider_id()
+                        ))
+                    })?;
+            Ok(AttributeValueLocation::ExternalProvider {
+                external_provider_id: *external_provider.id(),
+                name: external_provider.name().to_owned(),
+            })
+        }
+    }
+
+    async fn value(
+        ctx: &DalContext,
+        attribute_value: &AttributeValue,
+    ) -> AttributeValueResult<Option<Value>> {
+        let value = attribute_value.get_value(ctx).await?;
+
+        // Containers only store an empty array/object themselves, so build up the full value
+        // from their children.
+        if matches!(value, Some(Value::Array(_) | Value::Object(_)))
+            && attribute_value
+                .context
+                .is_least_specific_field_kind_prop()?
+        {
+            let view = AttributeView::new(
+                ctx,
+                AttributeReadContext {
+                    prop_id: None,
+                    ..AttributeReadContext::from(attribute_value.context)
+                },
+                Some(*attribute_value.id()),
+            )
+            .await?;
+            return Ok(Some(view.value().clone()));
+        }
+
+        Ok(value)
+    }
+
+    async fn source(
+        ctx: &DalContext,
+        attribute_value: &AttributeValue,
+        visited: &mut HashSet<AttributeValueId>,
+    ) -> AttributeValueResult<AttributeValueProvenanceSource> {
+        let context = attribute_value.context;
+
+        // Implicit internal providers don't use their prototype's arguments, they emit the value
+        // of their prop (see InternalProvider::implicit_emit).
+        if context.is_least_specific_field_kind_internal_provider()? {
+            let internal_provider =
+                InternalProvider::get_by_id(ctx, &context.internal_provider_id())
+                    .await?
+                    .ok_or_else(|| {
+                        AttributeValueError::InternalProviderNotFound(
+                            context.internal_provider_id(),
+                        )
+                    })?;
+            if internal_provider.is_internal_consumer() {
+                let read_context = AttributeReadContext {
+                    prop_id: Some(*internal_provider.prop_id()),
+                    internal_provider_id: Some(InternalProviderId::NONE),
+                    external_provider_id: Some(ExternalProviderId::NONE),
+                    component_id: Some(context.component_id()),
+                };
+                let prop_attribute_value = AttributeValue::find_for_context(ctx, read_context)
+                    .await?
+                    .ok_or(AttributeValueError::NotFoundForReadContext(read_context))?;
+                return Ok(AttributeValueProvenanceSource::Prop {
+                    source: Box::new(
+                        Self::for_attribute_value(ctx, &prop_attribute_value, visited).await?,
+                    ),
+                });
+            }
+        }
+
+        let attribute_prototype = attribute_value
+            .attribute_prototype(ctx)
+            .await?
+            .ok_or(AttributeValueError::MissingAttributePrototype)?;
+        let func = Func::get_by_id(ctx, &attribute_prototype.func_id())
+            .await?
+            .ok_or_else(|| {
+                AttributeValueError::MissingFunc(attribute_prototype.func_id().to_string())
+            })?;
+        let provenance_func = AttributeValueProvenanceFunc {
+            func_id: *func.id(),
+            name: func.name().to_owned(),
+            display_name: func.display_name().map(ToOwned::to_owned),
+            backend_kind: *func.backend_kind(),
+        };
+
+        if *func.backend_kind() == FuncBackendKind::Unset {
+            return Ok(AttributeValueProvenanceSource::Unset);
+        }
+
+        if is_set_func(*func.backend_kind()) {
+            // Values within a deep value returned by the func of a parent prop are stored with
+            // the intrinsic "set" funcs, as if they had been set manually.
+            if let Some(parent_attribute_value) =
+                attribute_value.parent_attribute_value(ctx).await?
+            {
+                let parent_prototype = parent_attribute_value
+                    .attribute_prototype(ctx)
+                    .await?
+                    .ok_or(AttributeValueError::MissingAttributePrototype)?;
+                let parent_func = Func::get_by_id(ctx, &parent_prototype.func_id())
+                    .await?
+                    .ok_or_else(|| {
+                        AttributeValueError::MissingFunc(parent_prototype.func_id().to_string())
+                    })?;
+                if !is_set_func(*parent_func.backend_kind())
+                    && *parent_func.backend_kind() != FuncBackendKind::Unset
+                {
+                    return Ok(AttributeValueProvenanceSource::ParentFunc {
+                        parent: Box::new(
+                            Self::for_attribute_value(ctx, &parent_attribute_value, visited)
+                                .await?,
+                        ),
+                    });
+                }
+            }
+
+            return Ok(if attribute_prototype.context.component_id().is_some() {
+                AttributeValueProvenanceSource::Manual {
+                    func: provenance_func,
+                }
+            } else {
+                AttributeValueProvenanceSource::Default {
+                    func: provenance_func,
+                }
+            });
+        }
+
+        // The arguments the func was actually executed with.
+        let func_args = FuncBinding::get_by_id(ctx, &attribute_value.func_binding_id())
+            .await?
+            .map(|func_binding| func_binding.args().clone())
+            .unwrap_or(Value::Null);
+
+        let mut arguments: Vec<AttributeValueProvenanceArgument> = Vec::new();
+        for argument in
+            AttributePrototypeArgument::list_for_attribute_prototype(ctx, *attribute_prototype.id())
+                .await?
+        {
+            let func_argument = FuncArgument::get_by_id(ctx, &argument.func_argument_id())
+                .await?
+                .ok_or_else(|| {
+                    AttributeValueError::AttributePrototype(format!(
+                        "func argument not found: {}",
+                        argument.func_argument_id()
+                    ))
+                })?;
+
+            // Mirrors the filtering done when the arguments are gathered for execution: arguments
+            // across edges only apply to their head component, and only while the tail component
+            // exists.
+            let (source_component_id, internal_provider_id, external_provider_id) =
+                if argument.external_provider_id().is_some() {
+                    if argument.head_component_id() != context.component_id()
+                        || Component::get_by_id(ctx, &argument.tail_component_id())
+                            .await?
+                            .is_none()
+                    {
+                        continue;
+                    }
+                    (
+                        argument.tail_component_id(),
+                        InternalProviderId::NONE,
+                        argument.external_provider_id(),
+                    )
+                } else if argument.head_component_id().is_some() {
+                    if argument.head_component_id() != context.component_id() {
+                        continue;
+                    }
+                    (
+                        argument.tail_component_id(),
+                        argument.internal_provider_id(),
+                        ExternalProviderId::NONE,
+                    )
+                } else {
+                    (
+                        context.component_id(),
+                        argument.internal_provider_id(),
+                        ExternalProviderId::NONE,
+                    )
+                };
+
+            let read_context = AttributeReadContext {
+                prop_id: Some(PropId::NONE),
+                internal_provider_id: Some(internal_provider_id),
+                external_provider_id: Some(external_provider_id),
+                component_id: Some(source_component_id),
+            };
+            let source = match AttributeValue::find_for_context(ctx, read_context).await? {
+                Some(source_attribute_value) => {
+                    Some(Self::for_attribute_value(ctx, &source_attribute_value, visited).await?)
+                }
+                None => None,
+            };
+
+            let name = func_argument.name().to_owned();
+            match arguments.iter_mut().find(|existing| existing.name == name) {
+                Some(existing) => existing.sources.extend(source),
+                None => arguments.push(AttributeValueProvenanceArgument {
+                    value: func_args.get(&name).cloned(),
+                    name,
+                    sources: source.into_iter().collect(),
+                }),
+            }
+        }
+
+        Ok(AttributeValueProvenanceSource::Computed {
+            func: provenance_func,
+            arguments,
+        })
+    }
+}
+
+/// The intrinsic funcs which set a value as given, such as "si:setString".
+pub(crate) fn is_set_func(backend_kind: FuncBackendKind) -> bool {
+    matches!(
+        backend_kind,
+        FuncBackendKind::Array
+            | FuncBackendKind::Boolean
+            | FuncBackendKind::Integer
+            | FuncBackendKind::Map
+            | FuncBackendKind::Object
+            | FuncBackendKind::String
+    )
+}
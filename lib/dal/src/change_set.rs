@@ -10,7 +10,7 @@ use crate::standard_model::{object_option_from_row_option, objects_from_rows};
 use crate::ws_event::{WsEvent, WsEventError, WsPayload};
 use crate::{
     pk, Action, ActionError, HistoryEvent, HistoryEventError, LabelListError, StandardModelError,
-    Tenancy, Timestamp, TransactionsError, UserError, UserPk, Visibility,
+    Tenancy, Timestamp, TransactionsError, User, UserCapability, UserError, UserPk, Visibility,
 };
 use crate::{ComponentError, DalContext, WsEventResult};
 
@@ -112,6 +112,8 @@ impl ChangeSet {
 
     #[instrument(skip(ctx))]
     pub async fn apply(&mut self, ctx: &mut DalContext) -> ChangeSetResult<()> {
+        User::ensure_authorized(ctx, UserCapability::ApplyChangeSet).await?;
+
         let actor = serde_json::to_value(ctx.history_actor())?;
         let row = ctx
             .txns()
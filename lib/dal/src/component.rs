@@ -29,8 +29,9 @@ use crate::{
     standard_model, standard_model_accessor, standard_model_belongs_to, standard_model_has_many,
     ActionPrototypeError, AttributeContext, AttributeContextBuilderError, AttributeContextError,
     AttributePrototype, AttributePrototypeArgument, AttributePrototypeArgumentError,
-    AttributePrototypeError, AttributePrototypeId, AttributeReadContext, ComponentType, DalContext,
-    EdgeError, ExternalProvider, ExternalProviderError, ExternalProviderId, FixError, FixId, Func,
+    AttributePrototypeError, AttributePrototypeId, AttributeReadContext, ComponentTag,
+    ComponentTagError, ComponentTemplateError, ComponentType, DalContext, EdgeError,
+    ExternalProvider, ExternalProviderError, ExternalProviderId, FixError, FixId, Func,
     FuncBackendKind, FuncError, HistoryActor, HistoryEventError, InternalProvider,
     InternalProviderId, Node, NodeError, PropError, PropId, RootPropChild, Schema, SchemaError,
     SchemaId, Socket, StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError,
@@ -40,8 +41,10 @@ use crate::{
 use crate::{AttributeValueId, QualificationError};
 use crate::{Edge, FixResolverError, NodeKind};
 
+pub mod bulk;
 pub mod code;
 pub mod diff;
+pub mod duplicate;
 pub mod qualification;
 pub mod resource;
 pub mod status;
@@ -79,6 +82,10 @@ pub enum ComponentError {
     /// words, the value contained in the [`AttributeValue`](crate::AttributeValue) was "none".
     #[error("component protection is none for component ({0}) and attribute value ({1}")]
     ComponentProtectionIsNone(ComponentId, AttributeValueId),
+    #[error("component tag error: {0}")]
+    ComponentTag(#[from] ComponentTagError),
+    #[error("component template error: {0}")]
+    ComponentTemplate(#[from] Box<ComponentTemplateError>),
     /// No [`ComponentType`](crate::ComponentType) was found for the appropriate
     /// [`AttributeValue`](crate::AttributeValue) and [`Component`](crate::Component). In other
     /// words, the value contained in the [`AttributeValue`](crate::AttributeValue) was "none".
@@ -88,6 +95,8 @@ pub enum ComponentError {
     ComponentView(#[from] ComponentViewError),
     #[error(transparent)]
     ContextTransaction(#[from] TransactionsError),
+    #[error("no original component found for duplicate with key: {0}")]
+    DuplicateNotFound(String),
     #[error("edge error: {0}")]
     Edge(#[from] EdgeError),
     /// Found an [`ExternalProviderError`](crate::ExternalProviderError).
@@ -1019,6 +1028,8 @@ impl Component {
             .await?;
         let mut attr_values: Vec<AttributeValue> = standard_model::objects_from_rows(rows)?;
 
+        ComponentTag::delete_for_component(ctx, self.id).await?;
+
         for attr_value in attr_values.iter_mut() {
             attr_value.update_from_prototype_function(ctx).await?;
         }
@@ -1096,6 +1107,8 @@ impl Component {
             .await?;
         let mut attr_values: Vec<AttributeValue> = standard_model::objects_from_rows(rows)?;
 
+        ComponentTag::restore_for_component(ctx, component_id).await?;
+
         for attr_value in &mut attr_values {
             attr_value.update_from_prototype_function(ctx).await?;
         }
@@ -0,0 +1,131 @@
+//! This module contains the ability to set the value of a [`Prop`](crate::Prop) for many
+//! [`Components`](crate::Component) at once.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{hash_map::Entry, HashMap};
+
+use crate::attribute::context::AttributeContextBuilder;
+use crate::attribute::value::AttributeValue;
+use crate::component::ComponentResult;
+use crate::job::definition::DependentValuesUpdate;
+use crate::prop::PropPath;
+use crate::schema::variant::SchemaVariantId;
+use crate::{
+    AttributeReadContext, AttributeValueId, Component, ComponentError, ComponentId, DalContext,
+    Prop, PropId, StandardModel,
+};
+
+/// The [`Components`](Component) that a bulk edit applies to.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ComponentSelection {
+    /// The given [`Components`](Component), which may be of different
+    /// [`SchemaVariants`](crate::SchemaVariant).
+    #[serde(rename_all = "camelCase")]
+    Components { component_ids: Vec<ComponentId> },
+    /// Every [`Component`] of the given [`SchemaVariant`](crate::SchemaVariant).
+    #[serde(rename_all = "camelCase")]
+    SchemaVariant { schema_variant_id: SchemaVariantId },
+}
+
+impl ComponentSelection {
+    /// Resolves the selection to the [`ComponentIds`](Component) it refers to.
+    pub async fn component_ids(&self, ctx: &DalContext) -> ComponentResult<Vec<ComponentId>> {
+        match self {
+            Self::Components { component_ids } => {
+                let mut selected = Vec::with_capacity(component_ids.len());
+                for component_id in component_ids {
+                    if Component::get_by_id(ctx, component_id).await?.is_none() {
+                        return Err(ComponentError::NotFound(*component_id));
+                    }
+                    if !selected.contains(component_id) {
+                        selected.push(*component_id);
+                    }
+                }
+                Ok(selected)
+            }
+            Self::SchemaVariant { schema_variant_id } => {
+                Ok(Component::list_for_schema_variant(ctx, *schema_variant_id)
+                    .await?
+                    .iter()
+                    .map(|component| *component.id())
+                    .collect())
+            }
+        }
+    }
+}
+
+impl Component {
+    /// Sets the value of the [`Prop`] found at the [`PropPath`] for every selected
+    /// [`Component`]. Rather than one [`DependentValuesUpdate`] per write, a single one is
+    /// enqueued for all of them once every value has been written.
+    ///
+    /// Returns the [`AttributeValueIds`](AttributeValue) that were updated.
+    pub async fn bulk_update_prop_value(
+        ctx: &DalContext,
+        selection: &ComponentSelection,
+        prop_path: &PropPath,
+        value: Option<Value>,
+    ) -> ComponentResult<Vec<AttributeValueId>> {
+        let component_ids = selection.component_ids(ctx).await?;
+
+        // Components of the same schema variant share props, so we only need to find the prop
+        // once per variant.
+        let mut prop_ids: HashMap<SchemaVariantId, PropId> = HashMap::new();
+        let mut attribute_value_ids = Vec::with_capacity(component_ids.len());
+        for component_id in component_ids {
+            let schema_variant_id = Self::schema_variant_id(ctx, component_id).await?;
+            let prop_id = match prop_ids.entry(schema_variant_id) {
+                Entry::Occupied(occupied) => *occupied.get(),
+                Entry::Vacant(vacant) => {
+                    let prop = Prop::find_prop_by_path(ctx, schema_variant_id, prop_path).await?;
+                    *vacant.insert(*prop.id())
+                }
+            };
+
+            let attribute_read_context = AttributeReadContext {
+                prop_id: Some(prop_id),
+                component_id: Some(component_id),
+                ..AttributeReadContext::default()
+            };
+            let attribute_value = AttributeValue::find_for_context(ctx, attribute_read_context)
+                .await?
+                .ok_or(ComponentError::AttributeValueNotFoundForContext(
+                    attribute_read_context,
+                ))?;
+            let parent_attribute_value = attribute_value
+                .parent_attribute_value(ctx)
+                .await?
+                .ok_or_else(|| {
+                    ComponentError::ParentAttributeValueNotFound(*attribute_value.id())
+                })?;
+            let attribute_context =
+                AttributeContextBuilder::from(attribute_read_context).to_context()?;
+
+            let (_, attribute_value_id) =
+                AttributeValue::update_for_context_without_propagating_dependent_values(
+                    ctx,
+                    *attribute_value.id(),
+                    Some(*parent_attribute_value.id()),
+                    attribute_context,
+                    value.clone(),
+                    None,
+                )
+                .await?;
+            attribute_value_ids.push(attribute_value_id);
+        }
+
+        if !attribute_value_ids.is_empty() {
+            ctx.enqueue_job(DependentValuesUpdate::new(
+                ctx.access_builder(),
+                *ctx.visibility(),
+                attribute_value_ids.clone(),
+            ))
+            .await?;
+        }
+
+        Ok(attribute_value_ids)
+    }
+}
@@ -0,0 +1,131 @@
+//! This module contains the ability to duplicate [`Components`](crate::Component), along with
+//! their manually set values and the connections between them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::component::ComponentResult;
+use crate::component_template::parse_coordinate;
+use crate::edge::EdgeKind;
+use crate::node::NodeId;
+use crate::socket::SocketEdgeKind;
+use crate::{
+    Component, ComponentError, ComponentId, ComponentTemplateDefinition, DalContext, Edge, Socket,
+    StandardModel,
+};
+
+/// The [`Component`] created by [`Component::duplicate`] for one of the original ones.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatedComponent {
+    pub original_component_id: ComponentId,
+    pub component_id: ComponentId,
+    pub node_id: NodeId,
+    /// The [`Node`](crate::Node) of the frame the duplicate was placed in, if any. Sockets
+    /// between the frame and the duplicate are not connected automatically.
+    pub parent_node_id: Option<NodeId>,
+}
+
+impl Component {
+    /// Creates a copy of every given [`Component`], positioned at the given offset from the
+    /// original. Values set on the originals are copied, configuration edges between them are
+    /// recreated between the copies, and each copy is placed in the same frame as its original.
+    pub async fn duplicate(
+        ctx: &DalContext,
+        component_ids: &[ComponentId],
+        offset_x: i64,
+        offset_y: i64,
+    ) -> ComponentResult<Vec<DuplicatedComponent>> {
+        let mut definition = ComponentTemplateDefinition::capture(ctx, component_ids, Vec::new())
+            .await
+            .map_err(Box::new)?;
+
+        // The definition keeps positions relative to the top-left corner of the selection, so we
+        // need to find that corner again to place the copies next to the originals.
+        let mut min_x = None;
+        let mut min_y = None;
+        for component_id in component_ids {
+            let component = Self::get_by_id(ctx, component_id)
+                .await?
+                .ok_or(ComponentError::NotFound(*component_id))?;
+            let node = component
+                .node(ctx)
+                .await?
+                .pop()
+                .ok_or(ComponentError::NodeNotFoundForComponent(*component_id))?;
+            let (x, y) = (parse_coordinate(node.x()), parse_coordinate(node.y()));
+            min_x = Some(min_x.map_or(x, |min_x: i64| min_x.min(x)));
+            min_y = Some(min_y.map_or(y, |min_y: i64| min_y.min(y)));
+        }
+
+        for component in definition.components.iter_mut() {
+            component.name = format!("{} - Copy", component.name);
+        }
+
+        let instances = definition
+            .instantiate(
+                ctx,
+                min_x.unwrap_or_default() + offset_x,
+                min_y.unwrap_or_default() + offset_y,
+                HashMap::new(),
+            )
+            .await
+            .map_err(Box::new)?;
+
+        let mut duplicates = Vec::with_capacity(instances.len());
+        for instance in instances {
+            // Components are captured with their id as the key.
+            let original_component_id = component_ids
+                .iter()
+                .find(|component_id| component_id.to_string() == instance.key)
+                .copied()
+                .ok_or(ComponentError::DuplicateNotFound(instance.key))?;
+
+            // Frames that are part of the selection were duplicated too, but the copy of a
+            // component whose frame was not selected belongs in that same frame.
+            let mut parent_node_id = instance.parent_node_id;
+            if parent_node_id.is_none() {
+                for edge in Edge::list_for_component(ctx, original_component_id).await? {
+                    if *edge.kind() != EdgeKind::Symbolic
+                        || ComponentId::from(*edge.tail_object_id()) != original_component_id
+                    {
+                        continue;
+                    }
+
+                    let child_socket = Socket::find_frame_socket_for_node(
+                        ctx,
+                        instance.node_id,
+                        SocketEdgeKind::ConfigurationOutput,
+                    )
+                    .await?;
+                    let parent_socket = Socket::find_frame_socket_for_node(
+                        ctx,
+                        edge.head_node_id(),
+                        SocketEdgeKind::ConfigurationInput,
+                    )
+                    .await?;
+                    Edge::new_for_connection(
+                        ctx,
+                        edge.head_node_id(),
+                        *parent_socket.id(),
+                        instance.node_id,
+                        *child_socket.id(),
+                        EdgeKind::Symbolic,
+                    )
+                    .await?;
+                    parent_node_id = Some(edge.head_node_id());
+                    break;
+                }
+            }
+
+            duplicates.push(DuplicatedComponent {
+                original_component_id,
+                component_id: instance.component_id,
+                node_id: instance.node_id,
+                parent_node_id,
+            });
+        }
+
+        Ok(duplicates)
+    }
+}
@@ -14,8 +14,9 @@ use crate::func::binding::FuncBinding;
 use crate::func::binding_return_value::FuncBindingReturnValue;
 use crate::ComponentError;
 use crate::{
-    AttributeReadContext, Component, DalContext, ExternalProviderId, Func, FuncBackendKind,
-    InternalProviderId, PropError, PropId, StandardModel, ValidationPrototype, ValidationResolver,
+    AttributeReadContext, AttributeView, Component, DalContext, ExternalProviderId, Func,
+    FuncBackendKind, FuncBindingId, FuncId, InternalProviderId, PropError, PropId, StandardModel,
+    ValidationPrototype, ValidationPrototypeInput, ValidationResolver,
 };
 
 impl Component {
@@ -25,42 +26,39 @@ impl Component {
         validation_prototype: &ValidationPrototype,
         value_cache: &mut HashMap<PropId, (Option<Value>, AttributeValue)>,
     ) -> ComponentResult<()> {
-        let base_attribute_read_context = AttributeReadContext {
-            prop_id: None,
-            external_provider_id: Some(ExternalProviderId::NONE),
-            internal_provider_id: Some(InternalProviderId::NONE),
-            component_id: Some(self.id),
-        };
-
         let prop_id = validation_prototype.context().prop_id();
+        let (maybe_value, attribute_value) = self
+            .validation_value_for_prop(ctx, prop_id, value_cache)
+            .await?;
 
-        let (maybe_value, attribute_value) = match value_cache.get(&prop_id) {
-            Some((value, attribute_value)) => (value.to_owned(), attribute_value.clone()),
-            None => {
-                let attribute_read_context = AttributeReadContext {
-                    prop_id: Some(prop_id),
-                    ..base_attribute_read_context
-                };
-                let attribute_value = AttributeValue::find_for_context(ctx, attribute_read_context)
-                    .await?
-                    .ok_or(ComponentError::AttributeValueNotFoundForContext(
-                        attribute_read_context,
-                    ))?;
-
-                let value = match FuncBindingReturnValue::get_by_id(
-                    ctx,
-                    &attribute_value.func_binding_return_value_id(),
-                )
-                .await?
-                {
-                    Some(func_binding_return_value) => func_binding_return_value.value().cloned(),
-                    None => None,
-                };
-
-                value_cache.insert(prop_id, (value.clone(), attribute_value.clone()));
-                (value, attribute_value)
-            }
-        };
+        // Cross-field validations also see the values of their inputs, and their result is
+        // reported on every participating prop rather than only on their own.
+        let mut inputs = serde_json::Map::new();
+        let mut participating_attribute_values = vec![attribute_value];
+        for input in validation_prototype.inputs() {
+            let value = match input {
+                ValidationPrototypeInput::InputSocket {
+                    internal_provider_id,
+                    ..
+                } => {
+                    self.validation_value_for_input_socket(ctx, *internal_provider_id)
+                        .await?
+                }
+                ValidationPrototypeInput::Prop { prop_id, .. } => {
+                    let (value, attribute_value) = self
+                        .validation_value_for_prop(ctx, *prop_id, value_cache)
+                        .await?;
+                    if !participating_attribute_values
+                        .iter()
+                        .any(|participant| participant.id() == attribute_value.id())
+                    {
+                        participating_attribute_values.push(attribute_value);
+                    }
+                    value
+                }
+            };
+            inputs.insert(input.name().to_owned(), value.unwrap_or(Value::Null));
+        }
 
         let func = Func::get_by_id(ctx, &validation_prototype.func_id())
             .await?
@@ -71,11 +69,13 @@ impl Component {
                 // Deserialize the args, update the "value", and serialize the mutated args.
                 let mut args = FuncBackendValidationArgs::deserialize(validation_prototype.args())?;
                 args.validation = args.validation.update_value(&maybe_value)?;
+                args.inputs = inputs;
 
                 serde_json::to_value(args)?
             }
             FuncBackendKind::JsValidation => serde_json::to_value(FuncBackendJsValidationArgs {
                 value: maybe_value.unwrap_or(serde_json::json!(null)),
+                inputs,
             })?,
             kind => {
                 return Err(ComponentError::InvalidFuncBackendKindForValidations(*kind));
@@ -86,6 +86,153 @@ impl Component {
         let (func_binding, _) =
             FuncBinding::create_and_execute(ctx, mutated_args, *func.id()).await?;
 
+        for attribute_value in participating_attribute_values {
+            Self::set_validation_resolver(
+                ctx,
+                validation_prototype,
+                *func.id(),
+                *func_binding.id(),
+                &attribute_value,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Check validations for [`Self`].
+    pub async fn check_validations(&self, ctx: &DalContext) -> ComponentResult<()> {
+        let schema_variant = self
+            .schema_variant(ctx)
+            .await?
+            .ok_or(ComponentError::NoSchemaVariant(self.id))?;
+
+        let validation_prototypes =
+            ValidationPrototype::list_for_schema_variant(ctx, *schema_variant.id()).await?;
+
+        // Cache data necessary for assembling func arguments. We do this since a prop can have
+        // multiple validation prototypes within schema variant.
+        let mut cache: HashMap<PropId, (Option<Value>, AttributeValue)> = HashMap::new();
+
+        for validation_prototype in validation_prototypes {
+            self.check_single_validation(ctx, &validation_prototype, &mut cache)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Check the validations for [`Self`] which read the input socket of the given explicit
+    /// [`InternalProvider`](crate::InternalProvider). Input sockets are not part of the prop
+    /// tree, so a change to one does not re-run [`Self::check_validations`] on its own.
+    pub async fn check_validations_for_input_socket(
+        &self,
+        ctx: &DalContext,
+        internal_provider_id: InternalProviderId,
+    ) -> ComponentResult<()> {
+        let mut cache: HashMap<PropId, (Option<Value>, AttributeValue)> = HashMap::new();
+
+        for validation_prototype in
+            ValidationPrototype::list_for_input_socket(ctx, internal_provider_id).await?
+        {
+            self.check_single_validation(ctx, &validation_prototype, &mut cache)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Find the [`AttributeValue`] for a [`Prop`](crate::Prop) on [`Self`] along with the value
+    /// a validation should see for it.
+    async fn validation_value_for_prop(
+        &self,
+        ctx: &DalContext,
+        prop_id: PropId,
+        value_cache: &mut HashMap<PropId, (Option<Value>, AttributeValue)>,
+    ) -> ComponentResult<(Option<Value>, AttributeValue)> {
+        if let Some((value, attribute_value)) = value_cache.get(&prop_id) {
+            return Ok((value.to_owned(), attribute_value.clone()));
+        }
+
+        let base_attribute_read_context = AttributeReadContext {
+            prop_id: None,
+            external_provider_id: Some(ExternalProviderId::NONE),
+            internal_provider_id: Some(InternalProviderId::NONE),
+            component_id: Some(self.id),
+        };
+        let attribute_read_context = AttributeReadContext {
+            prop_id: Some(prop_id),
+            ..base_attribute_read_context
+        };
+        let attribute_value = AttributeValue::find_for_context(ctx, attribute_read_context)
+            .await?
+            .ok_or(ComponentError::AttributeValueNotFoundForContext(
+                attribute_read_context,
+            ))?;
+
+        let value = match FuncBindingReturnValue::get_by_id(
+            ctx,
+            &attribute_value.func_binding_return_value_id(),
+        )
+        .await?
+        {
+            Some(func_binding_return_value) => func_binding_return_value.value().cloned(),
+            None => None,
+        };
+
+        // Containers only store an empty array/object themselves, so build up the full
+        // value from their children for validations which check the whole collection.
+        let value = match value {
+            Some(Value::Array(_) | Value::Object(_)) => Some(
+                AttributeView::new(
+                    ctx,
+                    base_attribute_read_context,
+                    Some(*attribute_value.id()),
+                )
+                .await?
+                .value()
+                .clone(),
+            ),
+            value => value,
+        };
+
+        value_cache.insert(prop_id, (value.clone(), attribute_value.clone()));
+        Ok((value, attribute_value))
+    }
+
+    /// Find the value of an input socket on [`Self`], given its explicit
+    /// [`InternalProvider`](crate::InternalProvider).
+    async fn validation_value_for_input_socket(
+        &self,
+        ctx: &DalContext,
+        internal_provider_id: InternalProviderId,
+    ) -> ComponentResult<Option<Value>> {
+        let attribute_read_context = AttributeReadContext {
+            prop_id: Some(PropId::NONE),
+            internal_provider_id: Some(internal_provider_id),
+            external_provider_id: Some(ExternalProviderId::NONE),
+            component_id: Some(self.id),
+        };
+        let attribute_value = AttributeValue::find_for_context(ctx, attribute_read_context)
+            .await?
+            .ok_or(ComponentError::AttributeValueNotFoundForContext(
+                attribute_read_context,
+            ))?;
+
+        Ok(
+            FuncBindingReturnValue::get_by_id(ctx, &attribute_value.func_binding_return_value_id())
+                .await?
+                .and_then(|func_binding_return_value| func_binding_return_value.value().cloned()),
+        )
+    }
+
+    async fn set_validation_resolver(
+        ctx: &DalContext,
+        validation_prototype: &ValidationPrototype,
+        func_id: FuncId,
+        func_binding_id: FuncBindingId,
+        attribute_value: &AttributeValue,
+    ) -> ComponentResult<()> {
         let attribute_value_id = *attribute_value.id();
 
         // Does a resolver already exist for this validation func and attribute value? If so, we
@@ -95,17 +242,30 @@ impl Component {
         // attribute_value_id, since the way func_bindings are cached means the validation func
         // won't be created for the same validation func + value, despite running this on a
         // completely different attribute value (or even prop).
-        match ValidationResolver::find_for_attribute_value_and_validation_func(
-            ctx,
-            attribute_value_id,
-            *func.id(),
-        )
-        .await?
-        .pop()
-        {
+        //
+        // Cross-field validations look for the resolver of their own prototype instead, since an
+        // attribute value can take part in several of them using the same validation func.
+        let existing_resolver = if validation_prototype.inputs().is_empty() {
+            ValidationResolver::find_for_attribute_value_and_validation_func(
+                ctx,
+                attribute_value_id,
+                func_id,
+            )
+            .await?
+        } else {
+            ValidationResolver::find_for_attribute_value_and_prototype(
+                ctx,
+                attribute_value_id,
+                *validation_prototype.id(),
+            )
+            .await?
+        }
+        .pop();
+
+        match existing_resolver {
             Some(mut existing_resolver) => {
                 existing_resolver
-                    .set_validation_func_binding_id(ctx, func_binding.id())
+                    .set_validation_func_binding_id(ctx, func_binding_id)
                     .await?;
                 existing_resolver
                     .set_attribute_value_func_binding_return_value_id(
@@ -119,7 +279,7 @@ impl Component {
                     ctx,
                     *validation_prototype.id(),
                     attribute_value_id,
-                    *func_binding.id(),
+                    func_binding_id,
                 )
                 .await?;
             }
@@ -127,26 +287,4 @@ impl Component {
 
         Ok(())
     }
-
-    /// Check validations for [`Self`].
-    pub async fn check_validations(&self, ctx: &DalContext) -> ComponentResult<()> {
-        let schema_variant = self
-            .schema_variant(ctx)
-            .await?
-            .ok_or(ComponentError::NoSchemaVariant(self.id))?;
-
-        let validation_prototypes =
-            ValidationPrototype::list_for_schema_variant(ctx, *schema_variant.id()).await?;
-
-        // Cache data necessary for assembling func arguments. We do this since a prop can have
-        // multiple validation prototypes within schema variant.
-        let mut cache: HashMap<PropId, (Option<Value>, AttributeValue)> = HashMap::new();
-
-        for validation_prototype in validation_prototypes {
-            self.check_single_validation(ctx, &validation_prototype, &mut cache)
-                .await?;
-        }
-
-        Ok(())
-    }
 }
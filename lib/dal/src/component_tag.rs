@@ -0,0 +1,341 @@
+//! This module contains [`ComponentTag`], an arbitrary key/value pair (such as "team",
+//! "environment" or "cost center") attached to a [`Component`](crate::Component) to organize and
+//! filter [`Components`](crate::Component) independently of the props of their
+//! [`Schemas`](crate::Schema).
+//!
+//! Tags are standard models, so they respect the [`Visibility`] of the [`DalContext`]: a tag
+//! set in a [`ChangeSet`](crate::ChangeSet) only shows up on head once the
+//! [`ChangeSet`](crate::ChangeSet) is applied.
+//!
+//! Tags set on a frame can be inherited by the [`Components`](crate::Component) inside of it,
+//! depending on the [`ComponentTagInheritance`] of the tag. A tag set on a
+//! [`Component`](crate::Component) itself always wins over an inherited tag with the same key,
+//! and a tag inherited from a closer frame wins over one inherited from a frame further up.
+//!
+//! A [`Component`](crate::Component) has at most one tag per key, and its tags are deleted and
+//! restored together with it. Packages don't carry tags on their own: they only travel inside
+//! the definition of a [`ComponentTemplate`](crate::ComponentTemplate).
+
+use serde::{Deserialize, Serialize};
+use si_data_nats::NatsError;
+use si_data_pg::PgError;
+use std::collections::{BTreeMap, HashSet};
+use strum::{AsRefStr, Display, EnumString};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::edge::EdgeKind;
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor, ComponentId, DalContext,
+    Edge, EdgeError, HistoryEventError, StandardModel, StandardModelError, Tenancy, Timestamp,
+    TransactionsError, Visibility,
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ComponentTagError {
+    #[error("edge error: {0}")]
+    Edge(#[from] EdgeError),
+    #[error("tag key cannot be empty")]
+    EmptyKey,
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("nats txn error: {0}")]
+    Nats(#[from] NatsError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ComponentTagResult<T> = Result<T, ComponentTagError>;
+
+pk!(ComponentTagPk);
+pk!(ComponentTagId);
+
+/// Which [`Components`](crate::Component) inside of a frame inherit a tag set on the frame.
+#[remain::sorted]
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Display,
+    EnumString,
+    Eq,
+    PartialEq,
+    Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ComponentTagInheritance {
+    /// Only the [`Components`](crate::Component) directly inside of the frame.
+    Children,
+    /// Every [`Component`](crate::Component) inside of the frame, including those inside of
+    /// nested frames.
+    Descendants,
+    /// The tag only applies to the [`Component`](crate::Component) it is set on.
+    #[default]
+    None,
+}
+
+/// A tag that applies to a [`Component`](crate::Component), either because it was set on the
+/// [`Component`](crate::Component) itself or because it was inherited from a frame.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveComponentTag {
+    pub value: String,
+    /// The [`Component`](crate::Component) the tag was set on.
+    pub source_component_id: ComponentId,
+    pub inherited: bool,
+}
+
+/// A key/value pair attached to a [`Component`](crate::Component). See the
+/// [`module`](crate::component_tag) for more information.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ComponentTag {
+    pk: ComponentTagPk,
+    id: ComponentTagId,
+    component_id: ComponentId,
+    key: String,
+    value: String,
+    inheritance: ComponentTagInheritance,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: ComponentTag,
+    pk: ComponentTagPk,
+    id: ComponentTagId,
+    table_name: "component_tags",
+    history_event_label_base: "component_tag",
+    history_event_message_name: "Component Tag"
+}
+
+impl ComponentTag {
+    #[instrument(skip_all)]
+    pub async fn new(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        key: impl AsRef<str>,
+        value: impl AsRef<str>,
+        inheritance: ComponentTagInheritance,
+    ) -> ComponentTagResult<Self> {
+        let key = key.as_ref().trim();
+        if key.is_empty() {
+            return Err(ComponentTagError::EmptyKey);
+        }
+        let value = value.as_ref();
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM component_tag_create_v1($1, $2, $3, $4, $5, $6)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &component_id,
+                    &key,
+                    &value,
+                    &inheritance.as_ref(),
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    standard_model_accessor!(component_id, Pk(ComponentId), ComponentTagResult);
+    standard_model_accessor!(key, String, ComponentTagResult);
+    standard_model_accessor!(value, String, ComponentTagResult);
+    standard_model_accessor!(
+        inheritance,
+        Enum(ComponentTagInheritance),
+        ComponentTagResult
+    );
+
+    /// Lists the tags set on the [`Component`](crate::Component) itself, sorted by key.
+    pub async fn list_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentTagResult<Vec<Self>> {
+        let mut tags = Self::find_by_attr(ctx, "component_id", &component_id.to_string()).await?;
+        tags.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(tags)
+    }
+
+    pub async fn find_for_component_and_key(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        key: impl AsRef<str>,
+    ) -> ComponentTagResult<Option<Self>> {
+        let key = key.as_ref().trim();
+        Ok(Self::list_for_component(ctx, component_id)
+            .await?
+            .into_iter()
+            .find(|tag| tag.key == key))
+    }
+
+    /// Sets the tag with the given key on the [`Component`](crate::Component), replacing the
+    /// value and inheritance of the existing tag, if there is one.
+    #[instrument(skip_all)]
+    pub async fn upsert(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        key: impl AsRef<str>,
+        value: impl AsRef<str>,
+        inheritance: ComponentTagInheritance,
+    ) -> ComponentTagResult<Self> {
+        match Self::find_for_component_and_key(ctx, component_id, key.as_ref()).await? {
+            Some(mut tag) => {
+                if tag.value != value.as_ref() {
+                    tag.set_value(ctx, value.as_ref()).await?;
+                }
+                if tag.inheritance != inheritance {
+                    tag.set_inheritance(ctx, inheritance).await?;
+                }
+                Ok(tag)
+            }
+            None => Self::new(ctx, component_id, key, value, inheritance).await,
+        }
+    }
+
+    /// Removes the tag with the given key from the [`Component`](crate::Component), returning
+    /// whether there was one.
+    #[instrument(skip_all)]
+    pub async fn remove(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        key: impl AsRef<str>,
+    ) -> ComponentTagResult<bool> {
+        match Self::find_for_component_and_key(ctx, component_id, key).await? {
+            Some(mut tag) => {
+                tag.delete_by_id(ctx).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Deletes every tag set on the [`Component`](crate::Component), so that they go away
+    /// together with it.
+    #[instrument(skip_all)]
+    pub async fn delete_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentTagResult<()> {
+        for mut tag in Self::list_for_component(ctx, component_id).await? {
+            tag.delete_by_id(ctx).await?;
+        }
+        Ok(())
+    }
+
+    /// Brings back the tags that were deleted from the [`Component`](crate::Component) in the
+    /// current [`Visibility`], unless a tag with the same key was set since.
+    #[instrument(skip_all)]
+    pub async fn restore_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentTagResult<()> {
+        let mut keys: HashSet<String> = Self::list_for_component(ctx, component_id)
+            .await?
+            .into_iter()
+            .map(|tag| tag.key)
+            .collect();
+
+        let ctx_with_deleted = &ctx.clone_with_delete_visibility();
+        for mut tag in Self::list_for_component(ctx_with_deleted, component_id).await? {
+            if tag.visibility.deleted_at.is_some()
+                && tag.visibility.change_set_pk == ctx.visibility().change_set_pk
+                && keys.insert(tag.key.clone())
+            {
+                tag.undelete(ctx).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns every tag that applies to the [`Component`](crate::Component), by key: the tags
+    /// set on the [`Component`](crate::Component) itself, and those inherited from the frames it
+    /// is in.
+    #[instrument(skip_all)]
+    pub async fn effective_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ComponentTagResult<BTreeMap<String, EffectiveComponentTag>> {
+        let mut effective = BTreeMap::new();
+        for tag in Self::list_for_component(ctx, component_id).await? {
+            effective.insert(
+                tag.key,
+                EffectiveComponentTag {
+                    value: tag.value,
+                    source_component_id: component_id,
+                    inherited: false,
+                },
+            );
+        }
+
+        // Frames can't be nested in themselves through the diagram, but we still guard against
+        // cycles rather than looping forever.
+        let mut seen = HashSet::from([component_id]);
+        let mut depth = 1;
+        let mut current = frame_for_component(ctx, component_id).await?;
+        while let Some(frame_id) = current {
+            if !seen.insert(frame_id) {
+                break;
+            }
+
+            for tag in Self::list_for_component(ctx, frame_id).await? {
+                let inherits = match tag.inheritance {
+                    ComponentTagInheritance::Children => depth == 1,
+                    ComponentTagInheritance::Descendants => true,
+                    ComponentTagInheritance::None => false,
+                };
+                if inherits && !effective.contains_key(&tag.key) {
+                    effective.insert(
+                        tag.key,
+                        EffectiveComponentTag {
+                            value: tag.value,
+                            source_component_id: frame_id,
+                            inherited: true,
+                        },
+                    );
+                }
+            }
+
+            depth += 1;
+            current = frame_for_component(ctx, frame_id).await?;
+        }
+
+        Ok(effective)
+    }
+}
+
+/// Finds the frame the [`Component`](crate::Component) is directly inside of, if any. Frames are
+/// connected from the frame socket of the child to the frame socket of the parent.
+async fn frame_for_component(
+    ctx: &DalContext,
+    component_id: ComponentId,
+) -> ComponentTagResult<Option<ComponentId>> {
+    for edge in Edge::list_for_component(ctx, component_id).await? {
+        if *edge.kind() == EdgeKind::Symbolic
+            && ComponentId::from(*edge.tail_object_id()) == component_id
+        {
+            return Ok(Some(ComponentId::from(*edge.head_object_id())));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,1257 @@
+//! This module contains [`ComponentTemplate`], a saved group of connected
+//! [`Components`](crate::Component) which can be instantiated into a change set as many times as
+//! needed, instead of re-creating the same [`Components`](crate::Component) and
+//! [`Connections`](crate::Connection) by hand.
+//!
+//! A template records, for each captured [`Component`](crate::Component), its
+//! [`Schema`](crate::Schema), position (relative to the rest of the template), parent frame,
+//! [`tags`](crate::ComponentTag) and the values that were set on the
+//! [`Component`](crate::Component) itself (as opposed to values set by default or computed by a
+//! [`Func`](crate::Func)). It also records the configuration
+//! [`Edges`](crate::Edge) between the captured [`Components`](crate::Component).
+//!
+//! Any string value (or [`Component`](crate::Component) name) can contain `{{parameter}}`
+//! placeholders, which are substituted with the values given when instantiating the template. A
+//! value which is _only_ a placeholder is replaced by the parameter value as-is, so that
+//! non-string values can be parameterized too.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_data_nats::NatsError;
+use si_data_pg::PgError;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::action_prototype::ActionPrototypeContextField;
+use crate::attribute::value::provenance::is_set_func;
+use crate::component::view::ComponentViewError;
+use crate::edge::EdgeKind;
+use crate::job::definition::DependentValuesUpdate;
+use crate::node::NodeId;
+use crate::prop::PropPath;
+use crate::socket::{SocketEdgeKind, SocketError};
+use crate::standard_model::TypeHint;
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor, Action, ActionError,
+    ActionKind, ActionPrototype, ActionPrototypeContext, ActionPrototypeError,
+    AttributeContextBuilder, AttributeContextBuilderError, AttributeReadContext, AttributeValue,
+    AttributeValueError, AttributeValueId, Component, ComponentError, ComponentId, ComponentTag,
+    ComponentTagError, ComponentTagInheritance, ComponentView, Connection, DalContext,
+    DiagramError, Edge, EdgeError, ExternalProvider, ExternalProviderError, Func, FuncError,
+    HistoryEvent, HistoryEventError, NodeError, Prop, PropError, PropKind, Schema, SchemaError,
+    SchemaVariantId, Socket, StandardModel, StandardModelError, Tenancy, Timestamp,
+    TransactionsError, Visibility,
+};
+
+/// The "si" props which are not captured, since they are either set on creation or are managed
+/// by the system.
+const IGNORED_SI_PROPS: &[&str] = &["name", "protected"];
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ComponentTemplateError {
+    #[error("action error: {0}")]
+    Action(#[from] ActionError),
+    #[error("action prototype error: {0}")]
+    ActionPrototype(#[from] ActionPrototypeError),
+    #[error("attribute context builder error: {0}")]
+    AttributeContextBuilder(#[from] AttributeContextBuilderError),
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] AttributeValueError),
+    #[error("attribute value not found for context: {0:?}")]
+    AttributeValueNotFoundForContext(AttributeReadContext),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("component not found: {0}")]
+    ComponentNotFound(ComponentId),
+    #[error("component tag error: {0}")]
+    ComponentTag(#[from] ComponentTagError),
+    #[error("component view error: {0}")]
+    ComponentView(#[from] ComponentViewError),
+    #[error("diagram error: {0}")]
+    Diagram(#[from] DiagramError),
+    #[error("template component key used more than once: {0}")]
+    DuplicateComponentKey(String),
+    #[error("template parameter declared more than once: {0}")]
+    DuplicateParameter(String),
+    #[error("edge error: {0}")]
+    Edge(#[from] EdgeError),
+    #[error("cannot create a template without components")]
+    Empty,
+    #[error("external provider error: {0}")]
+    ExternalProvider(#[from] ExternalProviderError),
+    #[error("func error: {0}")]
+    Func(#[from] FuncError),
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("value for prop {0} does not match the prop kind {1}")]
+    InvalidValue(String, PropKind),
+    #[error("no value given for template parameter without a default: {0}")]
+    MissingParameter(String),
+    #[error("nats txn error: {0}")]
+    Nats(#[from] NatsError),
+    #[error("node error: {0}")]
+    Node(#[from] NodeError),
+    #[error("node not found for component: {0}")]
+    NodeNotFoundForComponent(ComponentId),
+    #[error("component template not found: {0}")]
+    NotFound(ComponentTemplateId),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("prop error: {0}")]
+    Prop(#[from] PropError),
+    #[error("prop not found at path {0}")]
+    PropNotFound(String),
+    #[error("schema error: {0}")]
+    Schema(#[from] SchemaError),
+    #[error("schema not found for component: {0}")]
+    SchemaNotFoundForComponent(ComponentId),
+    #[error("schema variant {1} not found for schema {0}")]
+    SchemaVariantNotFound(String, String),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("socket error: {0}")]
+    Socket(#[from] SocketError),
+    #[error("socket {1} not found for template component {0}")]
+    SocketNotFound(String, String),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("template component not found for key: {0}")]
+    UnknownComponentKey(String),
+    #[error("value given for unknown template parameter: {0}")]
+    UnknownParameter(String),
+}
+
+pub type ComponentTemplateResult<T> = Result<T, ComponentTemplateError>;
+
+pk!(ComponentTemplatePk);
+pk!(ComponentTemplateId);
+
+/// A parameter of a [`ComponentTemplate`], referred to as `{{name}}` in its values and
+/// [`Component`] names.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentTemplateParameter {
+    pub name: String,
+    pub description: Option<String>,
+    /// Used when no value is given for the parameter. When capturing a template, values equal to
+    /// the default are replaced by the placeholder for the parameter.
+    pub default_value: Option<Value>,
+}
+
+/// A value set on a [`ComponentTemplateComponent`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentTemplateValue {
+    /// The names of the props from "root" to the [`Prop`] the value is for.
+    pub path: Vec<String>,
+    pub value: Value,
+}
+
+/// A [`ComponentTag`] set on a [`ComponentTemplateComponent`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentTemplateTag {
+    pub key: String,
+    pub value: String,
+    #[serde(default)]
+    pub inheritance: ComponentTagInheritance,
+}
+
+/// A [`Component`] to create when instantiating a [`ComponentTemplate`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentTemplateComponent {
+    /// Identifies the component within the template, for [`edges`](ComponentTemplateEdge) and
+    /// parents.
+    pub key: String,
+    pub name: String,
+    pub schema_name: String,
+    /// The [`SchemaVariant`](crate::SchemaVariant) to use, or the default one for the
+    /// [`Schema`] if not set.
+    pub schema_variant_name: Option<String>,
+    /// The position, relative to the top-left corner of the template.
+    pub x: i64,
+    pub y: i64,
+    pub width: Option<String>,
+    pub height: Option<String>,
+    /// The key of the frame this component is placed in.
+    pub parent: Option<String>,
+    pub values: Vec<ComponentTemplateValue>,
+    /// The tags set on the component itself. Tags inherited from frames are not captured.
+    #[serde(default)]
+    pub tags: Vec<ComponentTemplateTag>,
+}
+
+/// A configuration [`Connection`] to create between two [`ComponentTemplateComponents`](ComponentTemplateComponent),
+/// with the [`Sockets`](Socket) referred to by name.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentTemplateEdge {
+    pub from_component: String,
+    pub from_socket: String,
+    pub to_component: String,
+    pub to_socket: String,
+}
+
+/// Everything needed to instantiate a [`ComponentTemplate`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentTemplateDefinition {
+    #[serde(default)]
+    pub parameters: Vec<ComponentTemplateParameter>,
+    #[serde(default)]
+    pub components: Vec<ComponentTemplateComponent>,
+    #[serde(default)]
+    pub edges: Vec<ComponentTemplateEdge>,
+}
+
+/// A [`Component`] created by [`ComponentTemplate::instantiate`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentTemplateInstanceComponent {
+    pub key: String,
+    pub component_id: ComponentId,
+    pub node_id: NodeId,
+    /// The [`Node`] of the frame the component was placed in, if any. Sockets between the frame
+    /// and the component are not connected automatically.
+    pub parent_node_id: Option<NodeId>,
+}
+
+/// A saved group of connected [`Components`](Component). See the
+/// [`module`](crate::component_template) for more information.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ComponentTemplate {
+    pk: ComponentTemplatePk,
+    id: ComponentTemplateId,
+    name: String,
+    description: Option<String>,
+    definition: ComponentTemplateDefinition,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: ComponentTemplate,
+    pk: ComponentTemplatePk,
+    id: ComponentTemplateId,
+    table_name: "component_templates",
+    history_event_label_base: "component_template",
+    history_event_message_name: "Component Template"
+}
+
+impl ComponentTemplate {
+    #[instrument(skip_all)]
+    pub async fn new(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        description: Option<impl AsRef<str>>,
+        definition: ComponentTemplateDefinition,
+    ) -> ComponentTemplateResult<Self> {
+        definition.validate()?;
+
+        let name = name.as_ref();
+        let description = description.as_ref().map(|description| description.as_ref());
+        let definition = serde_json::to_value(&definition)?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM component_template_create_v1($1, $2, $3, $4, $5)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &name,
+                    &description,
+                    &definition,
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    standard_model_accessor!(name, String, ComponentTemplateResult);
+    standard_model_accessor!(description, Option<String>, ComponentTemplateResult);
+
+    pub fn definition(&self) -> &ComponentTemplateDefinition {
+        &self.definition
+    }
+
+    #[instrument(skip_all)]
+    pub async fn set_definition(
+        &mut self,
+        ctx: &DalContext,
+        definition: ComponentTemplateDefinition,
+    ) -> ComponentTemplateResult<()> {
+        definition.validate()?;
+
+        let value = serde_json::to_value(&definition)?;
+        let updated_at = standard_model::update(
+            ctx,
+            Self::table_name(),
+            "definition",
+            self.id(),
+            &value,
+            TypeHint::JsonB,
+        )
+        .await?;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            &Self::history_event_label(vec!["updated"]),
+            &Self::history_event_message("updated"),
+            &serde_json::json![{
+                "pk": self.pk,
+                "field": "definition",
+                "value": &value,
+            }],
+        )
+        .await?;
+        self.timestamp.updated_at = updated_at;
+        self.definition = definition;
+
+        Ok(())
+    }
+
+    pub async fn find_by_name(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+    ) -> ComponentTemplateResult<Option<Self>> {
+        let name = name.as_ref();
+        Ok(Self::find_by_attr(ctx, "name", &name).await?.pop())
+    }
+
+    /// Creates a [`ComponentTemplate`] from the given [`Components`](Component), the
+    /// configuration [`Edges`](Edge) between them and the values set on them.
+    #[instrument(skip_all)]
+    pub async fn capture(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        description: Option<impl AsRef<str>>,
+        component_ids: &[ComponentId],
+        parameters: Vec<ComponentTemplateParameter>,
+    ) -> ComponentTemplateResult<Self> {
+        let definition =
+            ComponentTemplateDefinition::capture(ctx, component_ids, parameters).await?;
+        Self::new(ctx, name, description, definition).await
+    }
+
+    /// Creates the [`Components`](Component) of the template, with the top-left corner of the
+    /// template at the given position.
+    #[instrument(skip_all)]
+    pub async fn instantiate(
+        &self,
+        ctx: &DalContext,
+        x: i64,
+        y: i64,
+        parameters: HashMap<String, Value>,
+    ) -> ComponentTemplateResult<Vec<ComponentTemplateInstanceComponent>> {
+        self.definition.instantiate(ctx, x, y, parameters).await
+    }
+}
+
+impl ComponentTemplateDefinition {
+    /// Checks that component keys and parameter names are unique, and that every edge and parent
+    /// refers to a component of the template.
+    pub fn validate(&self) -> ComponentTemplateResult<()> {
+        let mut parameter_names = HashSet::new();
+        for parameter in &self.parameters {
+            if !parameter_names.insert(parameter.name.as_str()) {
+                return Err(ComponentTemplateError::DuplicateParameter(
+                    parameter.name.clone(),
+                ));
+            }
+        }
+
+        let mut keys = HashSet::new();
+        for component in &self.components {
+            if !keys.insert(component.key.as_str()) {
+                return Err(ComponentTemplateError::DuplicateComponentKey(
+                    component.key.clone(),
+                ));
+            }
+        }
+        for key in self
+            .components
+            .iter()
+            .filter_map(|component| component.parent.as_deref())
+            .chain(
+                self.edges
+                    .iter()
+                    .flat_map(|edge| [edge.from_component.as_str(), edge.to_component.as_str()]),
+            )
+        {
+            if !keys.contains(key) {
+                return Err(ComponentTemplateError::UnknownComponentKey(key.to_owned()));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn capture(
+        ctx: &DalContext,
+        component_ids: &[ComponentId],
+        parameters: Vec<ComponentTemplateParameter>,
+    ) -> ComponentTemplateResult<Self> {
+        if component_ids.is_empty() {
+            return Err(ComponentTemplateError::Empty);
+        }
+
+        let mut selected = Vec::new();
+        for component_id in component_ids {
+            if !selected.contains(component_id) {
+                selected.push(*component_id);
+            }
+        }
+
+        let mut components = Vec::with_capacity(selected.len());
+        let mut positions = Vec::with_capacity(selected.len());
+        for component_id in &selected {
+            let component = Component::get_by_id(ctx, component_id)
+                .await?
+                .ok_or(ComponentTemplateError::ComponentNotFound(*component_id))?;
+            let schema = component.schema(ctx).await?.ok_or(
+                ComponentTemplateError::SchemaNotFoundForComponent(*component_id),
+            )?;
+            let schema_variant = component.schema_variant(ctx).await?.ok_or(
+                ComponentTemplateError::SchemaNotFoundForComponent(*component_id),
+            )?;
+            let node = component.node(ctx).await?.pop().ok_or(
+                ComponentTemplateError::NodeNotFoundForComponent(*component_id),
+            )?;
+            positions.push((parse_coordinate(node.x()), parse_coordinate(node.y())));
+
+            let mut values = Vec::new();
+            let properties = ComponentView::new(ctx, *component_id).await?.properties;
+            for section in ["si", "domain"] {
+                let prop = Prop::find_prop_by_path(
+                    ctx,
+                    *schema_variant.id(),
+                    &PropPath::new(["root", section]),
+                )
+                .await?;
+                capture_values(ctx, *component_id, &prop, &properties, &mut values).await?;
+            }
+
+            components.push(ComponentTemplateComponent {
+                key: component_id.to_string(),
+                name: component.name(ctx).await?,
+                schema_name: schema.name().to_owned(),
+                schema_variant_name: Some(schema_variant.name().to_owned()),
+                x: 0,
+                y: 0,
+                width: node.width().map(ToOwned::to_owned),
+                height: node.height().map(ToOwned::to_owned),
+                parent: None,
+                values,
+                tags: ComponentTag::list_for_component(ctx, *component_id)
+                    .await?
+                    .into_iter()
+                    .map(|tag| ComponentTemplateTag {
+                        key: tag.key().to_owned(),
+                        value: tag.value().to_owned(),
+                        inheritance: *tag.inheritance(),
+                    })
+                    .collect(),
+            });
+        }
+
+        // Positions are kept relative to the top-left corner of the selection.
+        let min_x = positions.iter().map(|(x, _)| *x).min().unwrap_or_default();
+        let min_y = positions.iter().map(|(_, y)| *y).min().unwrap_or_default();
+        for (component, (x, y)) in components.iter_mut().zip(positions) {
+            component.x = x - min_x;
+            component.y = y - min_y;
+        }
+
+        let mut parents = HashMap::new();
+        let mut edges = Vec::new();
+        let mut seen_edge_ids = HashSet::new();
+        for component_id in &selected {
+            for edge in Edge::list_for_component(ctx, *component_id).await? {
+                if !seen_edge_ids.insert(*edge.id()) {
+                    continue;
+                }
+                let tail_component_id = ComponentId::from(*edge.tail_object_id());
+                let head_component_id = ComponentId::from(*edge.head_object_id());
+                if !selected.contains(&tail_component_id) || !selected.contains(&head_component_id)
+                {
+                    continue;
+                }
+
+                match edge.kind() {
+                    // Frames are connected from the frame socket of the child to the frame socket
+                    // of the parent.
+                    EdgeKind::Symbolic => {
+                        parents.insert(tail_component_id, head_component_id);
+                    }
+                    EdgeKind::Configuration => edges.push(edge),
+                }
+            }
+        }
+
+        let mut template_edges = Vec::new();
+        for edge in edges {
+            let tail_component_id = ComponentId::from(*edge.tail_object_id());
+            let head_component_id = ComponentId::from(*edge.head_object_id());
+
+            // Sockets between a frame and its children are connected again when the children
+            // are placed in the frame, so they are not part of the template.
+            if edge.head_socket_id() == edge.tail_socket_id()
+                || parents.get(&tail_component_id) == Some(&head_component_id)
+                || parents.get(&head_component_id) == Some(&tail_component_id)
+            {
+                continue;
+            }
+
+            let from_socket = Socket::get_by_id(ctx, edge.tail_socket_id())
+                .await?
+                .ok_or(EdgeError::SocketNotFound(*edge.tail_socket_id()))?;
+            let to_socket = Socket::get_by_id(ctx, edge.head_socket_id())
+                .await?
+                .ok_or(EdgeError::SocketNotFound(*edge.head_socket_id()))?;
+            template_edges.push(ComponentTemplateEdge {
+                from_component: tail_component_id.to_string(),
+                from_socket: from_socket.name().to_owned(),
+                to_component: head_component_id.to_string(),
+                to_socket: to_socket.name().to_owned(),
+            });
+        }
+
+        for (component, component_id) in components.iter_mut().zip(&selected) {
+            component.parent = parents.get(component_id).map(ToString::to_string);
+
+            for parameter in &parameters {
+                let (default_value, placeholder) = match &parameter.default_value {
+                    Some(default_value) => (default_value, format!("{{{{{}}}}}", parameter.name)),
+                    None => continue,
+                };
+                if default_value == &Value::String(component.name.clone()) {
+                    component.name = placeholder.clone();
+                }
+                for value in component.values.iter_mut() {
+                    if &value.value == default_value {
+                        value.value = Value::String(placeholder.clone());
+                    }
+                }
+                for tag in component.tags.iter_mut() {
+                    if default_value == &Value::String(tag.value.clone()) {
+                        tag.value = placeholder.clone();
+                    }
+                }
+            }
+        }
+
+        let definition = Self {
+            parameters,
+            components,
+            edges: template_edges,
+        };
+        definition.validate()?;
+
+        Ok(definition)
+    }
+
+    pub(crate) async fn instantiate(
+        &self,
+        ctx: &DalContext,
+        x: i64,
+        y: i64,
+        parameters: HashMap<String, Value>,
+    ) -> ComponentTemplateResult<Vec<ComponentTemplateInstanceComponent>> {
+        self.validate()?;
+        let parameters = self.resolve_parameters(parameters)?;
+
+        let mut instances: Vec<ComponentTemplateInstanceComponent> = Vec::new();
+        let mut attribute_value_ids = Vec::new();
+        for template_component in &self.components {
+            let schema = Schema::find_by_name(ctx, &template_component.schema_name).await?;
+            let schema_variant_id = match &template_component.schema_variant_name {
+                Some(schema_variant_name) => *schema
+                    .variants(ctx)
+                    .await?
+                    .iter()
+                    .find(|schema_variant| schema_variant.name() == schema_variant_name)
+                    .ok_or_else(|| {
+                        ComponentTemplateError::SchemaVariantNotFound(
+                            schema.name().to_owned(),
+                            schema_variant_name.clone(),
+                        )
+                    })?
+                    .id(),
+                None => *schema
+                    .default_schema_variant_id()
+                    .ok_or_else(|| SchemaError::NoDefaultVariant(*schema.id()))?,
+            };
+
+            let name = substitute(&Value::String(template_component.name.clone()), &parameters);
+            let name = match name {
+                Value::String(name) => name,
+                other => other.to_string(),
+            };
+            let (component, mut node) = Component::new(ctx, &name, schema_variant_id).await?;
+            node.set_geometry(
+                ctx,
+                (x + template_component.x).to_string(),
+                (y + template_component.y).to_string(),
+                template_component.width.as_deref(),
+                template_component.height.as_deref(),
+            )
+            .await?;
+
+            add_create_actions(ctx, schema_variant_id, *component.id()).await?;
+
+            for template_value in &template_component.values {
+                let value = substitute(&template_value.value, &parameters);
+                attribute_value_ids.extend(
+                    set_value_for_path(
+                        ctx,
+                        *component.id(),
+                        schema_variant_id,
+                        &template_value.path,
+                        value,
+                    )
+                    .await?,
+                );
+            }
+
+            for template_tag in &template_component.tags {
+                let value =
+                    match substitute(&Value::String(template_tag.value.clone()), &parameters) {
+                        Value::String(value) => value,
+                        other => other.to_string(),
+                    };
+                ComponentTag::new(
+                    ctx,
+                    *component.id(),
+                    &template_tag.key,
+                    value,
+                    template_tag.inheritance,
+                )
+                .await?;
+            }
+
+            instances.push(ComponentTemplateInstanceComponent {
+                key: template_component.key.clone(),
+                component_id: *component.id(),
+                node_id: *node.id(),
+                parent_node_id: None,
+            });
+        }
+
+        let node_ids: HashMap<String, NodeId> = instances
+            .iter()
+            .map(|instance| (instance.key.clone(), instance.node_id))
+            .collect();
+        for (template_component, instance) in self.components.iter().zip(instances.iter_mut()) {
+            let parent_key = match &template_component.parent {
+                Some(parent_key) => parent_key,
+                None => continue,
+            };
+            let parent_node_id = *node_ids
+                .get(parent_key)
+                .ok_or_else(|| ComponentTemplateError::UnknownComponentKey(parent_key.clone()))?;
+            let child_socket = Socket::find_frame_socket_for_node(
+                ctx,
+                instance.node_id,
+                SocketEdgeKind::ConfigurationOutput,
+            )
+            .await?;
+            let parent_socket = Socket::find_frame_socket_for_node(
+                ctx,
+                parent_node_id,
+                SocketEdgeKind::ConfigurationInput,
+            )
+            .await?;
+            Connection::new(
+                ctx,
+                instance.node_id,
+                *child_socket.id(),
+                parent_node_id,
+                *parent_socket.id(),
+                EdgeKind::Symbolic,
+            )
+            .await?;
+            instance.parent_node_id = Some(parent_node_id);
+        }
+
+        for edge in &self.edges {
+            let (from_node_id, to_node_id) = match (
+                node_ids.get(&edge.from_component),
+                node_ids.get(&edge.to_component),
+            ) {
+                (Some(from_node_id), Some(to_node_id)) => (*from_node_id, *to_node_id),
+                (None, _) => {
+                    return Err(ComponentTemplateError::UnknownComponentKey(
+                        edge.from_component.clone(),
+                    ))
+                }
+                (_, None) => {
+                    return Err(ComponentTemplateError::UnknownComponentKey(
+                        edge.to_component.clone(),
+                    ))
+                }
+            };
+            let from_socket = Socket::find_by_name_for_edge_kind_and_node(
+                ctx,
+                &edge.from_socket,
+                SocketEdgeKind::ConfigurationOutput,
+                from_node_id,
+            )
+            .await?
+            .ok_or_else(|| {
+                ComponentTemplateError::SocketNotFound(
+                    edge.from_component.clone(),
+                    edge.from_socket.clone(),
+                )
+            })?;
+            let to_socket = Socket::find_by_name_for_edge_kind_and_node(
+                ctx,
+                &edge.to_socket,
+                SocketEdgeKind::ConfigurationInput,
+                to_node_id,
+            )
+            .await?
+            .ok_or_else(|| {
+                ComponentTemplateError::SocketNotFound(
+                    edge.to_component.clone(),
+                    edge.to_socket.clone(),
+                )
+            })?;
+            Connection::new(
+                ctx,
+                from_node_id,
+                *from_socket.id(),
+                to_node_id,
+                *to_socket.id(),
+                EdgeKind::Configuration,
+            )
+            .await?;
+
+            // Like when connecting sockets on the diagram, the output socket is re-emitted so
+            // that the connected component picks up its value.
+            let from_component_id = instances
+                .iter()
+                .find(|instance| instance.node_id == from_node_id)
+                .map(|instance| instance.component_id)
+                .ok_or_else(|| {
+                    ComponentTemplateError::UnknownComponentKey(edge.from_component.clone())
+                })?;
+            let external_provider = ExternalProvider::find_for_socket(ctx, *from_socket.id())
+                .await?
+                .ok_or(EdgeError::ExternalProviderNotFoundForSocket(
+                    *from_socket.id(),
+                ))?;
+            let attribute_read_context = AttributeReadContext {
+                external_provider_id: Some(*external_provider.id()),
+                component_id: Some(from_component_id),
+                ..Default::default()
+            };
+            let attribute_value = AttributeValue::find_for_context(ctx, attribute_read_context)
+                .await?
+                .ok_or(ComponentTemplateError::AttributeValueNotFoundForContext(
+                    attribute_read_context,
+                ))?;
+            attribute_value_ids.push(*attribute_value.id());
+        }
+
+        if !attribute_value_ids.is_empty() {
+            ctx.enqueue_job(DependentValuesUpdate::new(
+                ctx.access_builder(),
+                *ctx.visibility(),
+                attribute_value_ids,
+            ))
+            .await?;
+        }
+
+        Ok(instances)
+    }
+
+    /// Combines the given parameter values with the defaults of the parameters which were not
+    /// given.
+    fn resolve_parameters(
+        &self,
+        mut given: HashMap<String, Value>,
+    ) -> ComponentTemplateResult<BTreeMap<String, Value>> {
+        let mut resolved = BTreeMap::new();
+        for parameter in &self.parameters {
+            let value = match given.remove(&parameter.name) {
+                Some(value) => value,
+                None => parameter.default_value.clone().ok_or_else(|| {
+                    ComponentTemplateError::MissingParameter(parameter.name.clone())
+                })?,
+            };
+            resolved.insert(parameter.name.clone(), value);
+        }
+
+        // Whatever is left was not declared by the template.
+        if let Some(name) = given.into_keys().min() {
+            return Err(ComponentTemplateError::UnknownParameter(name));
+        }
+
+        Ok(resolved)
+    }
+}
+
+/// Adds the "create" [`Actions`](Action) of the [`SchemaVariant`](crate::SchemaVariant) for a
+/// newly created [`Component`], as the diagram does. Nothing is added on head, since there is no
+/// change set to apply.
+pub(crate) async fn add_create_actions(
+    ctx: &DalContext,
+    schema_variant_id: SchemaVariantId,
+    component_id: ComponentId,
+) -> ComponentTemplateResult<()> {
+    if ctx.visibility().is_head() {
+        return Ok(());
+    }
+
+    for prototype in ActionPrototype::find_for_context_and_kind(
+        ctx,
+        ActionKind::Create,
+        ActionPrototypeContext::new_for_context_field(ActionPrototypeContextField::SchemaVariant(
+            schema_variant_id,
+        )),
+    )
+    .await?
+    {
+        Action::new(ctx, *prototype.id(), component_id).await?;
+    }
+
+    Ok(())
+}
+
+/// Records the values set on the [`Component`] itself for the children of the given object
+/// [`Prop`]. Objects are walked into, while arrays and maps are recorded as a whole.
+#[async_recursion::async_recursion]
+async fn capture_values(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    object_prop: &Prop,
+    properties: &Value,
+    values: &mut Vec<ComponentTemplateValue>,
+) -> ComponentTemplateResult<()> {
+    for prop in object_prop.child_props(ctx).await? {
+        let path = prop.path().as_owned_parts();
+        if path.len() == 3 && path[1] == "si" && IGNORED_SI_PROPS.contains(&path[2].as_str()) {
+            continue;
+        }
+
+        if *prop.kind() == PropKind::Object {
+            capture_values(ctx, component_id, &prop, properties, values).await?;
+            continue;
+        }
+
+        let attribute_read_context = AttributeReadContext {
+            prop_id: Some(*prop.id()),
+            component_id: Some(component_id),
+            ..AttributeReadContext::default()
+        };
+        let attribute_value =
+            match AttributeValue::find_for_context(ctx, attribute_read_context).await? {
+                Some(attribute_value) => attribute_value,
+                None => continue,
+            };
+        if attribute_value.context.component_id() != component_id {
+            continue;
+        }
+        let func_id = match attribute_value.attribute_prototype(ctx).await? {
+            Some(attribute_prototype) => attribute_prototype.func_id(),
+            None => continue,
+        };
+        let func = Func::get_by_id(ctx, &func_id)
+            .await?
+            .ok_or(FuncError::NotFound(func_id))?;
+        if !is_set_func(*func.backend_kind()) {
+            continue;
+        }
+
+        if let Some(value) = properties.pointer(&json_pointer(&path)) {
+            values.push(ComponentTemplateValue {
+                path,
+                value: value.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Sets the value of the [`Prop`] at the path (starting with "root") on the [`Component`],
+/// returning the [`AttributeValueIds`](AttributeValue) whose dependent values need updating.
+pub(crate) async fn set_value_for_path(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    schema_variant_id: SchemaVariantId,
+    path: &[String],
+    value: Value,
+) -> ComponentTemplateResult<Vec<AttributeValueId>> {
+    let prop = Prop::find_prop_by_path_opt(ctx, schema_variant_id, &PropPath::new(path))
+        .await?
+        .ok_or_else(|| ComponentTemplateError::PropNotFound(path.join("/")))?;
+    let attribute_read_context = AttributeReadContext {
+        prop_id: Some(*prop.id()),
+        component_id: Some(component_id),
+        ..AttributeReadContext::default()
+    };
+    let attribute_value = AttributeValue::find_for_context(ctx, attribute_read_context)
+        .await?
+        .ok_or(ComponentTemplateError::AttributeValueNotFoundForContext(
+            attribute_read_context,
+        ))?;
+    let parent_attribute_value = attribute_value
+        .parent_attribute_value(ctx)
+        .await?
+        .ok_or_else(|| ComponentError::ParentAttributeValueNotFound(*attribute_value.id()))?;
+
+    let mut attribute_value_ids = Vec::new();
+    set_value(
+        ctx,
+        component_id,
+        &prop,
+        *attribute_value.id(),
+        *parent_attribute_value.id(),
+        None,
+        value,
+        &mut attribute_value_ids,
+    )
+    .await?;
+    Ok(attribute_value_ids)
+}
+
+/// Sets the value of an [`AttributeValue`], setting the children of objects and inserting the
+/// elements of arrays and maps one by one, since they are separate
+/// [`AttributeValues`](AttributeValue).
+#[allow(clippy::too_many_arguments)]
+#[async_recursion::async_recursion]
+async fn set_value(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    prop: &Prop,
+    attribute_value_id: AttributeValueId,
+    parent_attribute_value_id: AttributeValueId,
+    key: Option<String>,
+    value: Value,
+    attribute_value_ids: &mut Vec<AttributeValueId>,
+) -> ComponentTemplateResult<()> {
+    let attribute_context = AttributeContextBuilder::new()
+        .set_prop_id(*prop.id())
+        .set_component_id(component_id)
+        .to_context()?;
+    let invalid_value =
+        || ComponentTemplateError::InvalidValue(prop.path().with_replaced_sep("/"), *prop.kind());
+
+    let container = match (prop.kind(), &value) {
+        (PropKind::Object, Value::Object(_)) | (PropKind::Map, Value::Object(_)) => {
+            Some(serde_json::json!({}))
+        }
+        (PropKind::Array, Value::Array(_)) => Some(serde_json::json!([])),
+        (PropKind::Object | PropKind::Map | PropKind::Array, _) => return Err(invalid_value()),
+        _ => None,
+    };
+    let (_, attribute_value_id) =
+        AttributeValue::update_for_context_without_propagating_dependent_values(
+            ctx,
+            attribute_value_id,
+            Some(parent_attribute_value_id),
+            attribute_context,
+            Some(container.unwrap_or_else(|| value.clone())),
+            key,
+        )
+        .await?;
+    attribute_value_ids.push(attribute_value_id);
+
+    match (prop.kind(), value) {
+        (PropKind::Object, Value::Object(object)) => {
+            let child_props = prop.child_props(ctx).await?;
+            for (name, child_value) in object {
+                let child_prop = child_props
+                    .iter()
+                    .find(|child_prop| child_prop.name() == name)
+                    .ok_or_else(|| {
+                        ComponentTemplateError::PropNotFound(format!(
+                            "{}/{name}",
+                            prop.path().with_replaced_sep("/")
+                        ))
+                    })?;
+                let child_read_context = AttributeReadContext {
+                    prop_id: Some(*child_prop.id()),
+                    component_id: Some(component_id),
+                    ..AttributeReadContext::default()
+                };
+                let child_attribute_value = AttributeValue::find_with_parent_and_key_for_context(
+                    ctx,
+                    Some(attribute_value_id),
+                    None,
+                    child_read_context,
+                )
+                .await?
+                .ok_or(
+                    ComponentTemplateError::AttributeValueNotFoundForContext(child_read_context),
+                )?;
+                set_value(
+                    ctx,
+                    component_id,
+                    child_prop,
+                    *child_attribute_value.id(),
+                    attribute_value_id,
+                    None,
+                    child_value,
+                    attribute_value_ids,
+                )
+                .await?;
+            }
+        }
+        (PropKind::Array | PropKind::Map, value) => {
+            let element_prop = prop
+                .child_props(ctx)
+                .await?
+                .pop()
+                .ok_or(PropError::ArrayMissingElementChild(*prop.id()))?;
+            let element_context = AttributeContextBuilder::new()
+                .set_prop_id(*element_prop.id())
+                .set_component_id(component_id)
+                .to_context()?;
+            let elements: Vec<(Option<String>, Value)> = match value {
+                Value::Array(array) => array.into_iter().map(|value| (None, value)).collect(),
+                Value::Object(map) => map
+                    .into_iter()
+                    .map(|(key, value)| (Some(key), value))
+                    .collect(),
+                _ => return Err(invalid_value()),
+            };
+            for (key, element_value) in elements {
+                let element_attribute_value_id = AttributeValue::insert_for_context(
+                    ctx,
+                    element_context,
+                    attribute_value_id,
+                    None,
+                    key.clone(),
+                )
+                .await?;
+                set_value(
+                    ctx,
+                    component_id,
+                    &element_prop,
+                    element_attribute_value_id,
+                    attribute_value_id,
+                    key,
+                    element_value,
+                    attribute_value_ids,
+                )
+                .await?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Replaces the `{{parameter}}` placeholders in the strings of the value.
+fn substitute(value: &Value, parameters: &BTreeMap<String, Value>) -> Value {
+    match value {
+        Value::String(string) => {
+            for (name, parameter_value) in parameters {
+                if string == &format!("{{{{{name}}}}}") {
+                    return parameter_value.clone();
+                }
+            }
+
+            let mut string = string.clone();
+            for (name, parameter_value) in parameters {
+                let text = match parameter_value {
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                string = string.replace(&format!("{{{{{name}}}}}"), &text);
+            }
+            Value::String(string)
+        }
+        Value::Array(array) => Value::Array(
+            array
+                .iter()
+                .map(|value| substitute(value, parameters))
+                .collect(),
+        ),
+        Value::Object(object) => Value::Object(
+            object
+                .iter()
+                .map(|(key, value)| (key.clone(), substitute(value, parameters)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Converts a prop path starting with "root" into a JSON pointer into the properties of a
+/// [`ComponentView`].
+pub(crate) fn json_pointer(path: &[String]) -> String {
+    path.iter()
+        .skip(1)
+        .map(|part| format!("/{}", part.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Positions are stored as strings and can be fractional.
+pub(crate) fn parse_coordinate(coordinate: &str) -> i64 {
+    coordinate
+        .parse::<f64>()
+        .map_or(0, |coordinate| coordinate.round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn parameters() -> BTreeMap<String, Value> {
+        BTreeMap::from([
+            ("count".to_owned(), json!(3)),
+            ("region".to_owned(), json!("us-east-2")),
+        ])
+    }
+
+    #[test]
+    fn substitute_whole_value_keeps_type() {
+        assert_eq!(json!(3), substitute(&json!("{{count}}"), &parameters()));
+        assert_eq!(
+            json!("us-east-2"),
+            substitute(&json!("{{region}}"), &parameters())
+        );
+    }
+
+    #[test]
+    fn substitute_within_text() {
+        assert_eq!(
+            json!("vpc-us-east-2-3"),
+            substitute(&json!("vpc-{{region}}-{{count}}"), &parameters())
+        );
+        assert_eq!(
+            json!("{{unknown}}"),
+            substitute(&json!("{{unknown}}"), &parameters())
+        );
+    }
+
+    #[test]
+    fn substitute_nested() {
+        assert_eq!(
+            json!({"tags": ["{{region}}", "a-{{region}}"], "count": 1}),
+            substitute(
+                &json!({"tags": ["{{region}}", "a-{{region}}"], "count": 1}),
+                &BTreeMap::new()
+            )
+        );
+        assert_eq!(
+            json!({"tags": ["us-east-2", "a-us-east-2"], "count": 3}),
+            substitute(
+                &json!({"tags": ["{{region}}", "a-{{region}}"], "count": "{{count}}"}),
+                &parameters()
+            )
+        );
+    }
+
+    #[test]
+    fn resolve_parameters() {
+        let definition = ComponentTemplateDefinition {
+            parameters: vec![
+                ComponentTemplateParameter {
+                    name: "region".to_owned(),
+                    description: None,
+                    default_value: Some(json!("us-east-2")),
+                },
+                ComponentTemplateParameter {
+                    name: "name".to_owned(),
+                    description: None,
+                    default_value: None,
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            BTreeMap::from([
+                ("name".to_owned(), json!("vpc")),
+                ("region".to_owned(), json!("us-east-2")),
+            ]),
+            definition
+                .resolve_parameters(HashMap::from([("name".to_owned(), json!("vpc"))]))
+                .expect("could not resolve parameters")
+        );
+        assert!(matches!(
+            definition.resolve_parameters(HashMap::new()),
+            Err(ComponentTemplateError::MissingParameter(name)) if name == "name"
+        ));
+        assert!(matches!(
+            definition.resolve_parameters(HashMap::from([
+                ("name".to_owned(), json!("vpc")),
+                ("zone".to_owned(), json!("a")),
+            ])),
+            Err(ComponentTemplateError::UnknownParameter(name)) if name == "zone"
+        ));
+    }
+
+    #[test]
+    fn validate() {
+        let component = |key: &str, parent: Option<&str>| ComponentTemplateComponent {
+            key: key.to_owned(),
+            name: key.to_owned(),
+            schema_name: "starfield".to_owned(),
+            schema_variant_name: None,
+            x: 0,
+            y: 0,
+            width: None,
+            height: None,
+            parent: parent.map(ToOwned::to_owned),
+            values: Vec::new(),
+            tags: Vec::new(),
+        };
+
+        let mut definition = ComponentTemplateDefinition {
+            components: vec![component("frame", None), component("child", Some("frame"))],
+            edges: vec![ComponentTemplateEdge {
+                from_component: "frame".to_owned(),
+                from_socket: "bethesda".to_owned(),
+                to_component: "child".to_owned(),
+                to_socket: "bethesda".to_owned(),
+            }],
+            ..Default::default()
+        };
+        assert!(definition.validate().is_ok());
+
+        definition.components.push(component("child", None));
+        assert!(matches!(
+            definition.validate(),
+            Err(ComponentTemplateError::DuplicateComponentKey(key)) if key == "child"
+        ));
+
+        definition.components.pop();
+        definition.components[1].parent = Some("nope".to_owned());
+        assert!(matches!(
+            definition.validate(),
+            Err(ComponentTemplateError::UnknownComponentKey(key)) if key == "nope"
+        ));
+    }
+
+    #[test]
+    fn json_pointer_from_path() {
+        assert_eq!(
+            "/domain/tags/a~1b",
+            json_pointer(&[
+                "root".to_owned(),
+                "domain".to_owned(),
+                "tags".to_owned(),
+                "a/b".to_owned()
+            ])
+        );
+    }
+}
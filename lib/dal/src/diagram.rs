@@ -17,9 +17,9 @@ use crate::schema::variant::SchemaVariantError;
 use crate::socket::SocketError;
 use crate::{
     ActionPrototypeError, AttributeContextBuilderError, AttributePrototypeArgumentError,
-    AttributeValueError, ChangeSetPk, ComponentError, ComponentId, DalContext, Edge, EdgeError,
-    Node, NodeError, NodeId, NodeKind, PropError, SchemaError, SocketId, StandardModel,
-    StandardModelError,
+    AttributeValueError, ChangeSetPk, ComponentError, ComponentId, ComponentTagError, DalContext,
+    Edge, EdgeError, Node, NodeError, NodeId, NodeKind, PropError, SchemaError, SocketId,
+    StandardModel, StandardModelError,
 };
 
 pub mod connection;
@@ -48,6 +48,8 @@ pub enum DiagramError {
     ComponentNotFound,
     #[error("component status not found for component: {0}")]
     ComponentStatusNotFound(ComponentId),
+    #[error("component tag error: {0}")]
+    ComponentTag(#[from] ComponentTagError),
     #[error("deletion timestamp not found")]
     DeletionTimeStamp,
     #[error("edge error: {0}")]
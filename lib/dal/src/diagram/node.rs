@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use strum::{AsRefStr, Display, EnumIter, EnumString};
 
 use crate::change_status::ChangeStatus;
@@ -8,8 +9,8 @@ use crate::schema::SchemaUiMenu;
 use crate::socket::{SocketArity, SocketEdgeKind};
 use crate::{
     history_event, ActionKind, ActionPrototype, ActionPrototypeContext, ActionPrototypeView,
-    ActorView, Component, ComponentId, ComponentStatus, ComponentType, DalContext, DiagramError,
-    HistoryActorTimestamp, Node, NodeId, ResourceView, SchemaVariant, StandardModel,
+    ActorView, Component, ComponentId, ComponentStatus, ComponentTag, ComponentType, DalContext,
+    DiagramError, HistoryActorTimestamp, Node, NodeId, ResourceView, SchemaVariant, StandardModel,
 };
 
 #[remain::sorted]
@@ -160,6 +161,9 @@ pub struct DiagramComponentView {
     node_type: ComponentType,
     change_status: ChangeStatus,
     resource: ResourceView,
+    /// The values of the tags that apply to the [`Component`], including those inherited from
+    /// frames, by key.
+    tags: BTreeMap<String, String>,
 
     created_info: HistoryEventMetadata,
     updated_info: HistoryEventMetadata,
@@ -240,6 +244,12 @@ impl DiagramComponentView {
         // TODO(theo): probably dont want to fetch this here and load totally separately, but we inherited from existing endpoints
         let resource = ResourceView::new(component.resource(ctx).await?);
 
+        let tags = ComponentTag::effective_for_component(ctx, *component.id())
+            .await?
+            .into_iter()
+            .map(|(key, tag)| (key, tag.value))
+            .collect();
+
         let action_prototypes = ActionPrototype::find_for_context(
             ctx,
             ActionPrototypeContext {
@@ -278,6 +288,7 @@ impl DiagramComponentView {
             node_type: component.get_type(ctx).await?,
             change_status,
             resource,
+            tags,
             actions: action_views,
             created_info,
             updated_info,
@@ -304,6 +315,10 @@ impl DiagramComponentView {
     pub fn resource(&self) -> &ResourceView {
         &self.resource
     }
+
+    pub fn tags(&self) -> &BTreeMap<String, String> {
+        &self.tags
+    }
 }
 
 // TODO(theo,victor): this should probably move and be used more generally in a few places?
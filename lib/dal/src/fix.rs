@@ -17,7 +17,8 @@ use crate::{
     ActionPrototype, ActionPrototypeError, ActionPrototypeId, Component, ComponentError,
     ComponentId, DalContext, FixBatch, FixResolverError, Func, FuncError, HistoryEventError,
     ResourceView, SchemaError, StandardModel, StandardModelError, Tenancy, Timestamp,
-    TransactionsError, Visibility, WsEvent, WsEventError, WsEventResult, WsPayload,
+    TransactionsError, User, UserCapability, UserError, Visibility, WsEvent, WsEventError,
+    WsEventResult, WsPayload,
 };
 use veritech_client::ResourceStatus;
 
@@ -110,6 +111,8 @@ pub enum FixError {
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
     #[error(transparent)]
+    User(#[from] UserError),
+    #[error(transparent)]
     WsEvent(#[from] WsEventError),
 }
 
@@ -240,6 +243,8 @@ impl Fix {
         ctx: &DalContext,
         action_prototype: &ActionPrototype,
     ) -> FixResult<Option<ActionRunResult>> {
+        User::ensure_authorized(ctx, UserCapability::RunFix).await?;
+
         // Stamp started and run the workflow.
         self.stamp_started(ctx).await?;
 
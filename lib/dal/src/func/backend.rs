@@ -14,6 +14,7 @@ use crate::{label_list::ToLabelList, DalContext, Func, FuncId, PropKind, Standar
 pub mod array;
 pub mod boolean;
 pub mod diff;
+pub mod expression;
 pub mod identity;
 pub mod integer;
 pub mod js_action;
@@ -35,6 +36,8 @@ pub enum FuncBackendError {
     DispatchMissingBase64(FuncId),
     #[error("dispatch func missing handler {0}")]
     DispatchMissingHandler(FuncId),
+    #[error("expression error: {0}")]
+    Expression(#[from] expression::ExpressionError),
     #[error("function result action run error: {0:?}")]
     FunctionResultActionRun(FunctionResult<ActionRunResultSuccess>),
     #[error("invalid data - expected a valid array entry value, got: {0}")]
@@ -76,6 +79,8 @@ pub enum FuncBackendKind {
     Boolean,
     /// Comparison between two JSON values
     Diff,
+    /// An expression evaluated natively in dal, see [`expression`].
+    Expression,
     /// Mathematical identity of the [`Func`](crate::Func)'s arguments.
     Identity,
     Integer,
@@ -0,0 +1,258 @@
+//! A small expression language for attribute functions that are too simple to be worth a trip
+//! through cyclone, such as joining two strings or picking a key out of a map. Expressions are
+//! evaluated in-process, can only see the [`Func's`](crate::Func) arguments and always produce
+//! the same result for the same arguments.
+//!
+//! The expression is stored as the code of a [`Func`](crate::Func) with the
+//! [`Expression`](crate::FuncBackendKind::Expression) backend, and each of its arguments is
+//! available as a variable of the same name:
+//!
+//! ```text
+//! `${name}-${environment}`                  template strings
+//! tags["Name"] ?? name                      lookups ("null" when missing) and defaults
+//! port + 1                                  arithmetic (+ - * / %), "+" also joins strings
+//! protocol == "tcp" && port > 0             comparisons and boolean logic
+//! protocol == "tcp" ? port : null           conditionals
+//! { name: lower(name), ports: [80, 443] }   arrays and objects
+//! ```
+//!
+//! The available functions are `contains`, `endsWith`, `fromJson`, `join`, `keys`, `length`,
+//! `lower`, `max`, `min`, `number`, `replace`, `split`, `startsWith`, `string`, `toJson`,
+//! `trim`, `upper` and `values`. String functions return "null" when given "null".
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::func::backend::{FuncBackend, FuncBackendResult};
+
+mod interpreter;
+mod parser;
+
+/// The longest expression, in characters, that will be parsed.
+const MAX_SOURCE_LENGTH: usize = 16 * 1024;
+/// How deeply expressions may be nested.
+const MAX_DEPTH: usize = 64;
+/// The longest string, in bytes, an expression may produce.
+const MAX_STRING_LENGTH: usize = 1024 * 1024;
+
+#[remain::sorted]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ExpressionError {
+    #[error("{function} expects {expected} argument(s), but got {found}")]
+    ArgumentCount {
+        function: String,
+        expected: &'static str,
+        found: usize,
+    },
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("integer overflow")]
+    IntegerOverflow,
+    #[error("syntax error at character {position}: {message}")]
+    Syntax { position: usize, message: String },
+    #[error("expression is nested more than {MAX_DEPTH} levels deep")]
+    TooDeep,
+    #[error("expression is longer than {MAX_SOURCE_LENGTH} characters")]
+    TooLong,
+    #[error("type error: {0}")]
+    Type(String),
+    #[error("unknown function: {0}")]
+    UnknownFunction(String),
+    #[error("unknown variable: {0}")]
+    UnknownVariable(String),
+    #[error("expression produced a string longer than {MAX_STRING_LENGTH} bytes")]
+    ValueTooLarge,
+}
+
+pub type ExpressionResult<T> = Result<T, ExpressionError>;
+
+/// Parse and evaluate an expression with the given variables.
+pub fn evaluate(
+    source: &str,
+    variables: &serde_json::Map<String, Value>,
+) -> ExpressionResult<Value> {
+    let expression = parser::parse(source)?;
+    interpreter::evaluate(&expression, variables)
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendExpressionArgs {
+    /// The source of the expression, which is the code of the [`Func`](crate::Func).
+    pub expression: String,
+    /// The arguments of the [`Func`](crate::Func), by name.
+    pub arguments: Value,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FuncBackendExpression {
+    args: FuncBackendExpressionArgs,
+}
+
+#[async_trait]
+impl FuncBackend for FuncBackendExpression {
+    type Args = FuncBackendExpressionArgs;
+
+    fn new(args: Self::Args) -> Box<Self> {
+        Box::new(Self { args })
+    }
+
+    async fn inline(
+        self: Box<Self>,
+    ) -> FuncBackendResult<(Option<serde_json::Value>, Option<serde_json::Value>)> {
+        let variables = match self.args.arguments {
+            Value::Object(arguments) => arguments,
+            Value::Null => serde_json::Map::new(),
+            arguments => {
+                return Err(ExpressionError::Type(format!(
+                    "expression arguments must be an object, found {arguments}"
+                ))
+                .into())
+            }
+        };
+
+        let value = evaluate(&self.args.expression, &variables)?;
+        Ok((Some(value.clone()), Some(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn eval(source: &str) -> ExpressionResult<Value> {
+        let variables = json!({
+            "name": "Web Server",
+            "environment": "prod",
+            "port": 8080,
+            "ratio": 0.5,
+            "protocol": "tcp",
+            "tags": { "Name": "web", "Team": "infra" },
+            "cidrs": ["10.0.0.0/16", "10.1.0.0/16"],
+            "missing": null,
+        });
+        evaluate(
+            source,
+            variables.as_object().expect("variables are an object"),
+        )
+    }
+
+    #[test]
+    fn templates_and_strings() {
+        assert_eq!(
+            Ok(json!("Web Server-prod")),
+            eval("`${name}-${environment}`")
+        );
+        assert_eq!(
+            Ok(json!("web-server.prod:8080")),
+            eval(r#"`${replace(lower(name), " ", "-")}.${environment}:${port}`"#)
+        );
+        assert_eq!(Ok(json!("prod8080")), eval("environment + port"));
+        assert_eq!(Ok(json!("a, b")), eval(r#"join(["a", 'b'], ", ")"#));
+        assert_eq!(Ok(json!(["a", "b"])), eval(r#"split("a/b", "/")"#));
+        assert_eq!(Ok(json!("x: ")), eval("`x: ${missing}`"));
+        assert_eq!(Ok(json!(null)), eval("upper(missing)"));
+        assert_eq!(Ok(json!("\"q\"\n`")), eval(r#"'"q"\n\`'"#));
+    }
+
+    #[test]
+    fn lookups_and_defaults() {
+        assert_eq!(Ok(json!("web")), eval(r#"tags["Name"]"#));
+        assert_eq!(Ok(json!("infra")), eval("tags.Team"));
+        assert_eq!(Ok(json!(null)), eval("tags.Owner"));
+        assert_eq!(Ok(json!(null)), eval("missing.deeply.nested"));
+        assert_eq!(Ok(json!("nobody")), eval(r#"tags.Owner ?? "nobody""#));
+        assert_eq!(Ok(json!("10.1.0.0/16")), eval("cidrs[1]"));
+        assert_eq!(Ok(json!(null)), eval("cidrs[5]"));
+        assert_eq!(Ok(json!(["Name", "Team"])), eval("keys(tags)"));
+        assert_eq!(Ok(json!(2)), eval("length(cidrs)"));
+        assert_eq!(Ok(json!(true)), eval(r#"contains(cidrs, "10.0.0.0/16")"#));
+        assert_eq!(
+            Err(ExpressionError::UnknownVariable("nope".to_string())),
+            eval("nope")
+        );
+    }
+
+    #[test]
+    fn arithmetic_and_logic() {
+        assert_eq!(Ok(json!(8081)), eval("port + 1"));
+        assert_eq!(Ok(json!(7)), eval("1 + 2 * 3"));
+        assert_eq!(Ok(json!(9)), eval("(1 + 2) * 3"));
+        assert_eq!(Ok(json!(4)), eval("8 / 2"));
+        assert_eq!(Ok(json!(2.5)), eval("5 / 2"));
+        assert_eq!(Ok(json!(1.0)), eval("ratio * 2"));
+        assert_eq!(Ok(json!(-3)), eval("-(1 + 2)"));
+        assert_eq!(Ok(json!(true)), eval("1 == 1.0"));
+        assert_eq!(
+            Ok(json!(true)),
+            eval(r#"protocol == "tcp" && port >= 1024"#)
+        );
+        assert_eq!(Ok(json!(false)), eval("!(port > 1) || missing"));
+        assert_eq!(Ok(json!(8080)), eval(r#"protocol == "tcp" ? port : null"#));
+        assert_eq!(Ok(json!(null)), eval(r#"protocol == "udp" ? port : null"#));
+        assert_eq!(Ok(json!(443)), eval("max(80, 443, 22)"));
+        assert_eq!(Ok(json!(12)), eval(r#"number("12")"#));
+        assert_eq!(Err(ExpressionError::DivisionByZero), eval("port % 0"));
+        assert_eq!(
+            Err(ExpressionError::IntegerOverflow),
+            eval("9223372036854775807 + 1")
+        );
+    }
+
+    #[test]
+    fn collections() {
+        assert_eq!(
+            Ok(json!({ "name": "web", "ports": [80, 8080], "Team": "infra" })),
+            eval(r#"{ name: tags.Name, "ports": [80, port], Team: tags.Team, }"#)
+        );
+        assert_eq!(Ok(json!([1, 2, 3])), eval("[1] + [2, 3]"));
+        assert_eq!(
+            Ok(json!({ "a": 1, "b": 3 })),
+            eval("{ a: 1, b: 2 } + { b: 3 }")
+        );
+        assert_eq!(Ok(json!(r#"{"a":[1]}"#)), eval("toJson({ a: [1] })"));
+        assert_eq!(Ok(json!({ "a": [1] })), eval(r#"fromJson("{\"a\": [1]}")"#));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            eval("1 +"),
+            Err(ExpressionError::Syntax { position: 4, .. })
+        ));
+        assert!(matches!(
+            eval("`${name"),
+            Err(ExpressionError::Syntax { .. })
+        ));
+        assert!(matches!(
+            eval("name name"),
+            Err(ExpressionError::Syntax { .. })
+        ));
+        assert_eq!(
+            Err(ExpressionError::UnknownFunction("eval".to_string())),
+            eval("eval(name)")
+        );
+        assert!(matches!(
+            eval("lower(name, name)"),
+            Err(ExpressionError::ArgumentCount { .. })
+        ));
+        assert!(matches!(eval("port - name"), Err(ExpressionError::Type(_))));
+        assert_eq!(
+            Err(ExpressionError::TooDeep),
+            eval(&format!("{}1{}", "(".repeat(500), ")".repeat(500)))
+        );
+        assert_eq!(
+            Err(ExpressionError::TooDeep),
+            eval(&vec!["1"; 500].join(" + "))
+        );
+        // Every "replace" with an empty pattern grows the string elevenfold.
+        let mut amplified = r#""abcdefghij""#.to_string();
+        for _ in 0..6 {
+            amplified = format!(r#"replace({amplified}, "", "abcdefghij")"#);
+        }
+        assert_eq!(Err(ExpressionError::ValueTooLarge), eval(&amplified));
+    }
+}
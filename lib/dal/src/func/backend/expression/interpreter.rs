@@ -0,0 +1,565 @@
+//! Evaluates a parsed [`Expression`] against a set of variables. Evaluation never touches
+//! anything outside of the variables it is given, so the same variables always produce the same
+//! value.
+
+use serde_json::{Map, Number, Value};
+
+use super::parser::{BinaryOp, Expression, ExpressionKind, TemplatePart, UnaryOp};
+use super::{ExpressionError, ExpressionResult, MAX_STRING_LENGTH};
+
+pub(super) fn evaluate(
+    expression: &Expression,
+    variables: &Map<String, Value>,
+) -> ExpressionResult<Value> {
+    Interpreter { variables }.evaluate(expression)
+}
+
+struct Interpreter<'a> {
+    variables: &'a Map<String, Value>,
+}
+
+impl Interpreter<'_> {
+    fn evaluate(&self, expression: &Expression) -> ExpressionResult<Value> {
+        match &expression.kind {
+            ExpressionKind::Literal(value) => Ok(value.clone()),
+            ExpressionKind::Variable(name) => self
+                .variables
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ExpressionError::UnknownVariable(name.clone())),
+            ExpressionKind::Template(parts) => {
+                let mut text = String::new();
+                for part in parts {
+                    match part {
+                        TemplatePart::Text(part) => text.push_str(part),
+                        TemplatePart::Expression(part) => {
+                            text.push_str(&to_text(&self.evaluate(part)?)?)
+                        }
+                    }
+                    check_length(&text)?;
+                }
+                Ok(Value::String(text))
+            }
+            ExpressionKind::Array(items) => Ok(Value::Array(
+                items
+                    .iter()
+                    .map(|item| self.evaluate(item))
+                    .collect::<ExpressionResult<_>>()?,
+            )),
+            ExpressionKind::Object(entries) => {
+                let mut object = Map::new();
+                for (key, value) in entries {
+                    object.insert(key.clone(), self.evaluate(value)?);
+                }
+                Ok(Value::Object(object))
+            }
+            ExpressionKind::Lookup(base, key) => {
+                let base = self.evaluate(base)?;
+                let key = self.evaluate(key)?;
+                lookup(base, &key)
+            }
+            ExpressionKind::Call(name, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.evaluate(argument))
+                    .collect::<ExpressionResult<Vec<_>>>()?;
+                call(name, arguments)
+            }
+            ExpressionKind::Unary(op, operand) => {
+                let operand = self.evaluate(operand)?;
+                match op {
+                    UnaryOp::Not => Ok(Value::Bool(!is_truthy(&operand))),
+                    UnaryOp::Negate => match as_number(&operand, "-")? {
+                        Numeric::Integer(i) => i
+                            .checked_neg()
+                            .map(Value::from)
+                            .ok_or(ExpressionError::IntegerOverflow),
+                        Numeric::Float(f) => float(-f),
+                    },
+                }
+            }
+            ExpressionKind::Binary(op, left, right) => self.binary(*op, left, right),
+            ExpressionKind::Conditional(condition, then, otherwise) => {
+                if is_truthy(&self.evaluate(condition)?) {
+                    self.evaluate(then)
+                } else {
+                    self.evaluate(otherwise)
+                }
+            }
+        }
+    }
+
+    fn binary(
+        &self,
+        op: BinaryOp,
+        left: &Expression,
+        right: &Expression,
+    ) -> ExpressionResult<Value> {
+        let left = self.evaluate(left)?;
+
+        // The short-circuiting operators only evaluate the right side when they need it.
+        match op {
+            BinaryOp::And => {
+                return Ok(Value::Bool(
+                    is_truthy(&left) && is_truthy(&self.evaluate(right)?),
+                ))
+            }
+            BinaryOp::Or => {
+                return Ok(Value::Bool(
+                    is_truthy(&left) || is_truthy(&self.evaluate(right)?),
+                ))
+            }
+            BinaryOp::Coalesce => {
+                return if left.is_null() {
+                    self.evaluate(right)
+                } else {
+                    Ok(left)
+                }
+            }
+            _ => {}
+        }
+
+        let right = self.evaluate(right)?;
+        match op {
+            BinaryOp::Add => add(left, right),
+            BinaryOp::Subtract | BinaryOp::Multiply | BinaryOp::Divide | BinaryOp::Remainder => {
+                arithmetic(op, &left, &right)
+            }
+            BinaryOp::Equal => Ok(Value::Bool(equals(&left, &right))),
+            BinaryOp::NotEqual => Ok(Value::Bool(!equals(&left, &right))),
+            BinaryOp::Less => compare(&left, &right, "<").map(|o| Value::Bool(o.is_lt())),
+            BinaryOp::LessOrEqual => compare(&left, &right, "<=").map(|o| Value::Bool(o.is_le())),
+            BinaryOp::Greater => compare(&left, &right, ">").map(|o| Value::Bool(o.is_gt())),
+            BinaryOp::GreaterOrEqual => {
+                compare(&left, &right, ">=").map(|o| Value::Bool(o.is_ge()))
+            }
+            BinaryOp::And | BinaryOp::Or | BinaryOp::Coalesce => unreachable!("handled above"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Numeric {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Numeric {
+    fn as_f64(self) -> f64 {
+        match self {
+            Self::Integer(i) => i as f64,
+            Self::Float(f) => f,
+        }
+    }
+}
+
+fn as_number(value: &Value, operation: &str) -> ExpressionResult<Numeric> {
+    match value {
+        Value::Number(number) => Ok(match number.as_i64() {
+            Some(i) => Numeric::Integer(i),
+            None => Numeric::Float(number.as_f64().unwrap_or(f64::NAN)),
+        }),
+        value => Err(ExpressionError::Type(format!(
+            "{operation} expects a number, found {}",
+            type_name(value)
+        ))),
+    }
+}
+
+fn float(f: f64) -> ExpressionResult<Value> {
+    Number::from_f64(f)
+        .map(Value::Number)
+        .ok_or_else(|| ExpressionError::Type("result is not a finite number".to_string()))
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(number) => number.as_f64().is_some_and(|f| f != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(_) | Value::Object(_) => true,
+    }
+}
+
+fn check_length(text: &str) -> ExpressionResult<()> {
+    if text.len() > MAX_STRING_LENGTH {
+        return Err(ExpressionError::ValueTooLarge);
+    }
+    Ok(())
+}
+
+fn string(text: String) -> ExpressionResult<Value> {
+    check_length(&text)?;
+    Ok(Value::String(text))
+}
+
+/// Convert a value to the text it is shown as in templates and string concatenation.
+fn to_text(value: &Value) -> ExpressionResult<String> {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    };
+    check_length(&text)?;
+    Ok(text)
+}
+
+fn lookup(base: Value, key: &Value) -> ExpressionResult<Value> {
+    match (base, key) {
+        (Value::Null, _) => Ok(Value::Null),
+        (Value::Object(mut object), Value::String(key)) => {
+            Ok(object.remove(key).unwrap_or(Value::Null))
+        }
+        (Value::Array(mut array), Value::Number(index)) => match index.as_u64() {
+            Some(index) => Ok(usize::try_from(index)
+                .ok()
+                .filter(|index| *index < array.len())
+                .map(|index| array.swap_remove(index))
+                .unwrap_or(Value::Null)),
+            None => Err(ExpressionError::Type(format!(
+                "array index must be a non-negative integer, found {index}"
+            ))),
+        },
+        (base, key) => Err(ExpressionError::Type(format!(
+            "cannot look up a {} in a {}",
+            type_name(key),
+            type_name(&base)
+        ))),
+    }
+}
+
+fn add(left: Value, right: Value) -> ExpressionResult<Value> {
+    match (left, right) {
+        (left @ Value::String(_), right) | (left, right @ Value::String(_)) => {
+            string(to_text(&left)? + &to_text(&right)?)
+        }
+        (Value::Array(mut left), Value::Array(right)) => {
+            left.extend(right);
+            Ok(Value::Array(left))
+        }
+        (Value::Object(mut left), Value::Object(right)) => {
+            left.extend(right);
+            Ok(Value::Object(left))
+        }
+        (left, right) => arithmetic(BinaryOp::Add, &left, &right),
+    }
+}
+
+fn arithmetic(op: BinaryOp, left: &Value, right: &Value) -> ExpressionResult<Value> {
+    let symbol = match op {
+        BinaryOp::Add => "+",
+        BinaryOp::Subtract => "-",
+        BinaryOp::Multiply => "*",
+        BinaryOp::Divide => "/",
+        _ => "%",
+    };
+    let left = as_number(left, symbol)?;
+    let right = as_number(right, symbol)?;
+
+    if matches!(op, BinaryOp::Divide | BinaryOp::Remainder) && right.as_f64() == 0.0 {
+        return Err(ExpressionError::DivisionByZero);
+    }
+
+    match (left, right) {
+        (Numeric::Integer(l), Numeric::Integer(r)) => {
+            let result = match op {
+                BinaryOp::Add => l.checked_add(r),
+                BinaryOp::Subtract => l.checked_sub(r),
+                BinaryOp::Multiply => l.checked_mul(r),
+                BinaryOp::Divide => match l.checked_rem(r) {
+                    Some(0) => l.checked_div(r),
+                    Some(_) => return float(l as f64 / r as f64),
+                    None => None,
+                },
+                _ => l.checked_rem(r),
+            };
+            result
+                .map(Value::from)
+                .ok_or(ExpressionError::IntegerOverflow)
+        }
+        (left, right) => {
+            let (l, r) = (left.as_f64(), right.as_f64());
+            float(match op {
+                BinaryOp::Add => l + r,
+                BinaryOp::Subtract => l - r,
+                BinaryOp::Multiply => l * r,
+                BinaryOp::Divide => l / r,
+                _ => l % r,
+            })
+        }
+    }
+}
+
+fn equals(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => match (l.as_i64(), r.as_i64()) {
+            (Some(l), Some(r)) => l == r,
+            _ => l.as_f64() == r.as_f64(),
+        },
+        (left, right) => left == right,
+    }
+}
+
+fn compare(left: &Value, right: &Value, operation: &str) -> ExpressionResult<std::cmp::Ordering> {
+    match (left, right) {
+        (Value::String(l), Value::String(r)) => Ok(l.cmp(r)),
+        (Value::Number(_), Value::Number(_)) => {
+            match (as_number(left, operation)?, as_number(right, operation)?) {
+                (Numeric::Integer(l), Numeric::Integer(r)) => Ok(l.cmp(&r)),
+                (l, r) => l.as_f64().partial_cmp(&r.as_f64()).ok_or_else(|| {
+                    ExpressionError::Type(format!("{operation} cannot compare these numbers"))
+                }),
+            }
+        }
+        (left, right) => Err(ExpressionError::Type(format!(
+            "{operation} cannot compare a {} with a {}",
+            type_name(left),
+            type_name(right)
+        ))),
+    }
+}
+
+fn argument_count(
+    function: &str,
+    arguments: &[Value],
+    min: usize,
+    max: usize,
+    expected: &'static str,
+) -> ExpressionResult<()> {
+    if arguments.len() < min || arguments.len() > max {
+        return Err(ExpressionError::ArgumentCount {
+            function: function.to_string(),
+            expected,
+            found: arguments.len(),
+        });
+    }
+    Ok(())
+}
+
+fn as_str<'a>(function: &str, value: &'a Value) -> ExpressionResult<&'a str> {
+    value.as_str().ok_or_else(|| {
+        ExpressionError::Type(format!(
+            "{function} expects a string, found {}",
+            type_name(value)
+        ))
+    })
+}
+
+fn call(function: &str, mut arguments: Vec<Value>) -> ExpressionResult<Value> {
+    match function {
+        "contains" => {
+            argument_count(function, &arguments, 2, 2, "2")?;
+            let needle = arguments.pop().unwrap_or_default();
+            let haystack = arguments.pop().unwrap_or_default();
+            Ok(Value::Bool(match haystack {
+                Value::Null => false,
+                Value::String(s) => s.contains(as_str(function, &needle)?),
+                Value::Array(items) => items.iter().any(|item| equals(item, &needle)),
+                Value::Object(object) => object.contains_key(as_str(function, &needle)?),
+                value => {
+                    return Err(ExpressionError::Type(format!(
+                        "contains expects a string, array or object, found {}",
+                        type_name(&value)
+                    )))
+                }
+            }))
+        }
+        "endsWith" | "startsWith" => {
+            argument_count(function, &arguments, 2, 2, "2")?;
+            if arguments[0].is_null() {
+                return Ok(Value::Null);
+            }
+            let text = as_str(function, &arguments[0])?;
+            let affix = as_str(function, &arguments[1])?;
+            Ok(Value::Bool(if function == "endsWith" {
+                text.ends_with(affix)
+            } else {
+                text.starts_with(affix)
+            }))
+        }
+        "fromJson" => {
+            argument_count(function, &arguments, 1, 1, "1")?;
+            if arguments[0].is_null() {
+                return Ok(Value::Null);
+            }
+            serde_json::from_str(as_str(function, &arguments[0])?)
+                .map_err(|err| ExpressionError::Type(format!("fromJson: {err}")))
+        }
+        "join" => {
+            argument_count(function, &arguments, 1, 2, "1 or 2")?;
+            let separator = match arguments.get(1) {
+                Some(separator) => as_str(function, separator)?,
+                None => "",
+            };
+            match &arguments[0] {
+                Value::Null => Ok(Value::Null),
+                Value::Array(items) => {
+                    let mut text = String::new();
+                    for (index, item) in items.iter().enumerate() {
+                        if index > 0 {
+                            text.push_str(separator);
+                        }
+                        text.push_str(&to_text(item)?);
+                        check_length(&text)?;
+                    }
+                    Ok(Value::String(text))
+                }
+                value => Err(ExpressionError::Type(format!(
+                    "join expects an array, found {}",
+                    type_name(value)
+                ))),
+            }
+        }
+        "keys" | "values" => {
+            argument_count(function, &arguments, 1, 1, "1")?;
+            match arguments.pop().unwrap_or_default() {
+                Value::Null => Ok(Value::Null),
+                Value::Object(object) if function == "keys" => Ok(Value::Array(
+                    object
+                        .keys()
+                        .map(|key| Value::String(key.clone()))
+                        .collect(),
+                )),
+                Value::Object(object) => Ok(Value::Array(object.values().cloned().collect())),
+                value => Err(ExpressionError::Type(format!(
+                    "{function} expects an object, found {}",
+                    type_name(&value)
+                ))),
+            }
+        }
+        "length" => {
+            argument_count(function, &arguments, 1, 1, "1")?;
+            match &arguments[0] {
+                Value::Null => Ok(Value::Null),
+                Value::String(s) => Ok(s.chars().count().into()),
+                Value::Array(items) => Ok(items.len().into()),
+                Value::Object(object) => Ok(object.len().into()),
+                value => Err(ExpressionError::Type(format!(
+                    "length expects a string, array or object, found {}",
+                    type_name(value)
+                ))),
+            }
+        }
+        "lower" | "upper" | "trim" => {
+            argument_count(function, &arguments, 1, 1, "1")?;
+            if arguments[0].is_null() {
+                return Ok(Value::Null);
+            }
+            let text = as_str(function, &arguments[0])?;
+            string(match function {
+                "lower" => text.to_lowercase(),
+                "upper" => text.to_uppercase(),
+                _ => text.trim().to_string(),
+            })
+        }
+        "max" | "min" => {
+            argument_count(function, &arguments, 1, usize::MAX, "at least 1")?;
+            let candidates = match arguments.as_slice() {
+                [Value::Array(items)] => items.clone(),
+                _ => arguments,
+            };
+            let mut best: Option<Value> = None;
+            for candidate in candidates {
+                as_number(&candidate, function)?;
+                best = Some(match best {
+                    None => candidate,
+                    Some(best) => {
+                        let ordering = compare(&candidate, &best, function)?;
+                        let better = if function == "max" {
+                            ordering.is_gt()
+                        } else {
+                            ordering.is_lt()
+                        };
+                        if better {
+                            candidate
+                        } else {
+                            best
+                        }
+                    }
+                });
+            }
+            Ok(best.unwrap_or(Value::Null))
+        }
+        "number" => {
+            argument_count(function, &arguments, 1, 1, "1")?;
+            match arguments.pop().unwrap_or_default() {
+                value @ (Value::Null | Value::Number(_)) => Ok(value),
+                Value::Bool(b) => Ok(Value::from(i64::from(b))),
+                Value::String(s) => {
+                    let s = s.trim();
+                    if let Ok(i) = s.parse::<i64>() {
+                        Ok(i.into())
+                    } else {
+                        s.parse::<f64>()
+                            .ok()
+                            .and_then(Number::from_f64)
+                            .map(Value::Number)
+                            .ok_or_else(|| ExpressionError::Type(format!("{s:?} is not a number")))
+                    }
+                }
+                value => Err(ExpressionError::Type(format!(
+                    "number expects a string, found {}",
+                    type_name(&value)
+                ))),
+            }
+        }
+        "replace" => {
+            argument_count(function, &arguments, 3, 3, "3")?;
+            if arguments[0].is_null() {
+                return Ok(Value::Null);
+            }
+            let text = as_str(function, &arguments[0])?;
+            let pattern = as_str(function, &arguments[1])?;
+            let replacement = as_str(function, &arguments[2])?;
+
+            // Bound the size of the result before building it.
+            let occurrences = if pattern.is_empty() {
+                text.chars().count() + 1
+            } else {
+                text.matches(pattern).count()
+            };
+            let length = text
+                .len()
+                .saturating_add(occurrences.saturating_mul(replacement.len()));
+            if length > MAX_STRING_LENGTH {
+                return Err(ExpressionError::ValueTooLarge);
+            }
+            string(text.replace(pattern, replacement))
+        }
+        "split" => {
+            argument_count(function, &arguments, 2, 2, "2")?;
+            if arguments[0].is_null() {
+                return Ok(Value::Null);
+            }
+            let text = as_str(function, &arguments[0])?;
+            let separator = as_str(function, &arguments[1])?;
+            Ok(Value::Array(
+                text.split(separator).map(|part| part.into()).collect(),
+            ))
+        }
+        "string" => {
+            argument_count(function, &arguments, 1, 1, "1")?;
+            match &arguments[0] {
+                Value::Null => Ok(Value::Null),
+                value => string(to_text(value)?),
+            }
+        }
+        "toJson" => {
+            argument_count(function, &arguments, 1, 1, "1")?;
+            string(arguments[0].to_string())
+        }
+        _ => Err(ExpressionError::UnknownFunction(function.to_string())),
+    }
+}
@@ -0,0 +1,473 @@
+//! A recursive descent parser for expressions. Precedence, from loosest to tightest:
+//!
+//! ```text
+//! a ? b : c
+//! a ?? b
+//! a || b
+//! a && b
+//! a == b, a != b
+//! a < b, a <= b, a > b, a >= b
+//! a + b, a - b
+//! a * b, a / b, a % b
+//! !a, -a
+//! a.b, a[b], f(a, b)
+//! ```
+
+use serde_json::Value;
+
+use super::{ExpressionError, ExpressionResult, MAX_DEPTH, MAX_SOURCE_LENGTH};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum UnaryOp {
+    Negate,
+    Not,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum BinaryOp {
+    Add,
+    And,
+    Coalesce,
+    Divide,
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Multiply,
+    NotEqual,
+    Or,
+    Remainder,
+    Subtract,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum TemplatePart {
+    Expression(Expression),
+    Text(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum ExpressionKind {
+    Array(Vec<Expression>),
+    Binary(BinaryOp, Box<Expression>, Box<Expression>),
+    Call(String, Vec<Expression>),
+    Conditional(Box<Expression>, Box<Expression>, Box<Expression>),
+    Literal(Value),
+    Lookup(Box<Expression>, Box<Expression>),
+    Object(Vec<(String, Expression)>),
+    Template(Vec<TemplatePart>),
+    Unary(UnaryOp, Box<Expression>),
+    Variable(String),
+}
+
+/// A node of the parsed expression, along with how deeply nested it is. Since evaluation recurses
+/// through the tree, the depth is bounded when the node is built.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Expression {
+    pub(super) kind: ExpressionKind,
+    depth: usize,
+}
+
+impl Expression {
+    fn new(kind: ExpressionKind) -> ExpressionResult<Self> {
+        let child_depth = match &kind {
+            ExpressionKind::Array(items) | ExpressionKind::Call(_, items) => {
+                items.iter().map(|item| item.depth).max()
+            }
+            ExpressionKind::Binary(_, left, right) | ExpressionKind::Lookup(left, right) => {
+                Some(left.depth.max(right.depth))
+            }
+            ExpressionKind::Conditional(condition, then, otherwise) => {
+                Some(condition.depth.max(then.depth).max(otherwise.depth))
+            }
+            ExpressionKind::Object(entries) => entries.iter().map(|(_, value)| value.depth).max(),
+            ExpressionKind::Template(parts) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    TemplatePart::Expression(expression) => Some(expression.depth),
+                    TemplatePart::Text(_) => None,
+                })
+                .max(),
+            ExpressionKind::Unary(_, operand) => Some(operand.depth),
+            ExpressionKind::Literal(_) | ExpressionKind::Variable(_) => None,
+        };
+
+        let depth = child_depth.unwrap_or(0) + 1;
+        if depth > MAX_DEPTH {
+            return Err(ExpressionError::TooDeep);
+        }
+        Ok(Self { kind, depth })
+    }
+}
+
+pub(super) fn parse(source: &str) -> ExpressionResult<Expression> {
+    let chars: Vec<char> = source.chars().collect();
+    if chars.len() > MAX_SOURCE_LENGTH {
+        return Err(ExpressionError::TooLong);
+    }
+
+    let mut parser = Parser {
+        chars,
+        position: 0,
+        nesting: 0,
+    };
+    let expression = parser.expression()?;
+    parser.skip_whitespace();
+    if parser.peek().is_some() {
+        return Err(parser.error("expected the end of the expression"));
+    }
+    Ok(expression)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    /// How many nested calls to [`Self::expression`] (and [`Self::unary`]) are in progress, which
+    /// bounds the recursion of the parser itself.
+    nesting: usize,
+}
+
+impl Parser {
+    fn expression(&mut self) -> ExpressionResult<Expression> {
+        self.nesting += 1;
+        if self.nesting > MAX_DEPTH {
+            return Err(ExpressionError::TooDeep);
+        }
+        let result = self.conditional();
+        self.nesting -= 1;
+        result
+    }
+
+    fn conditional(&mut self) -> ExpressionResult<Expression> {
+        let condition = self.binary(0)?;
+        if !self.eat("?") {
+            return Ok(condition);
+        }
+
+        let then = self.expression()?;
+        self.expect(":")?;
+        let otherwise = self.expression()?;
+        Expression::new(ExpressionKind::Conditional(
+            Box::new(condition),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    /// Parse binary operators which bind at least as tightly as the given precedence, by
+    /// precedence climbing. This keeps the recursion per level of parentheses shallow, compared
+    /// to a function per precedence level.
+    fn binary(&mut self, min_precedence: u8) -> ExpressionResult<Expression> {
+        let mut left = self.unary()?;
+        while let Some((token, op, precedence)) = self.binary_operator() {
+            if precedence < min_precedence {
+                break;
+            }
+            self.position += token.len();
+            let right = self.binary(precedence + 1)?;
+            left = Expression::new(ExpressionKind::Binary(op, Box::new(left), Box::new(right)))?;
+        }
+        Ok(left)
+    }
+
+    /// Find the binary operator which comes next, without consuming it.
+    fn binary_operator(&mut self) -> Option<(&'static str, BinaryOp, u8)> {
+        // The two character operators must be tried first.
+        const OPERATORS: &[(&str, BinaryOp, u8)] = &[
+            ("??", BinaryOp::Coalesce, 0),
+            ("||", BinaryOp::Or, 1),
+            ("&&", BinaryOp::And, 2),
+            ("==", BinaryOp::Equal, 3),
+            ("!=", BinaryOp::NotEqual, 3),
+            ("<=", BinaryOp::LessOrEqual, 4),
+            (">=", BinaryOp::GreaterOrEqual, 4),
+            ("<", BinaryOp::Less, 4),
+            (">", BinaryOp::Greater, 4),
+            ("+", BinaryOp::Add, 5),
+            ("-", BinaryOp::Subtract, 5),
+            ("*", BinaryOp::Multiply, 6),
+            ("/", BinaryOp::Divide, 6),
+            ("%", BinaryOp::Remainder, 6),
+        ];
+
+        self.skip_whitespace();
+        OPERATORS.iter().copied().find(|(token, _, _)| {
+            token
+                .chars()
+                .enumerate()
+                .all(|(offset, c)| self.peek_at(offset) == Some(c))
+        })
+    }
+
+    fn unary(&mut self) -> ExpressionResult<Expression> {
+        let op = if self.eat("!") {
+            UnaryOp::Not
+        } else if self.eat("-") {
+            UnaryOp::Negate
+        } else {
+            return self.postfix();
+        };
+
+        self.nesting += 1;
+        if self.nesting > MAX_DEPTH {
+            return Err(ExpressionError::TooDeep);
+        }
+        let operand = self.unary();
+        self.nesting -= 1;
+        Expression::new(ExpressionKind::Unary(op, Box::new(operand?)))
+    }
+
+    fn postfix(&mut self) -> ExpressionResult<Expression> {
+        let mut expression = self.primary()?;
+        loop {
+            let key = if self.eat(".") {
+                let key = self
+                    .identifier()
+                    .ok_or_else(|| self.error("expected a key after \".\""))?;
+                Expression::new(ExpressionKind::Literal(Value::String(key)))?
+            } else if self.eat("[") {
+                let key = self.expression()?;
+                self.expect("]")?;
+                key
+            } else {
+                return Ok(expression);
+            };
+            expression =
+                Expression::new(ExpressionKind::Lookup(Box::new(expression), Box::new(key)))?;
+        }
+    }
+
+    fn primary(&mut self) -> ExpressionResult<Expression> {
+        self.skip_whitespace();
+        let kind = match self.peek() {
+            None => return Err(self.error("unexpected end of expression")),
+            Some('(') => {
+                self.position += 1;
+                let expression = self.expression()?;
+                self.expect(")")?;
+                return Ok(expression);
+            }
+            Some('[') => {
+                self.position += 1;
+                ExpressionKind::Array(self.list("]")?)
+            }
+            Some('{') => {
+                self.position += 1;
+                ExpressionKind::Object(self.object()?)
+            }
+            Some(quote @ ('"' | '\'')) => {
+                self.position += 1;
+                ExpressionKind::Literal(Value::String(self.string(quote)?))
+            }
+            Some('`') => {
+                self.position += 1;
+                ExpressionKind::Template(self.template()?)
+            }
+            Some(c) if c.is_ascii_digit() => ExpressionKind::Literal(self.number()?),
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name = self.identifier().unwrap_or_default();
+                match name.as_str() {
+                    "true" => ExpressionKind::Literal(Value::Bool(true)),
+                    "false" => ExpressionKind::Literal(Value::Bool(false)),
+                    "null" => ExpressionKind::Literal(Value::Null),
+                    _ if self.eat("(") => ExpressionKind::Call(name, self.list(")")?),
+                    _ => ExpressionKind::Variable(name),
+                }
+            }
+            Some(c) => return Err(self.error(format!("unexpected character {c:?}"))),
+        };
+        Expression::new(kind)
+    }
+
+    /// Parse comma separated expressions up to the closing token, allowing a trailing comma.
+    fn list(&mut self, close: &str) -> ExpressionResult<Vec<Expression>> {
+        let mut items = Vec::new();
+        while !self.eat(close) {
+            items.push(self.expression()?);
+            if !self.eat(",") {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn object(&mut self) -> ExpressionResult<Vec<(String, Expression)>> {
+        let mut entries = Vec::new();
+        while !self.eat("}") {
+            self.skip_whitespace();
+            let key = match self.peek() {
+                Some(quote @ ('"' | '\'')) => {
+                    self.position += 1;
+                    self.string(quote)?
+                }
+                _ => self
+                    .identifier()
+                    .ok_or_else(|| self.error("expected an object key"))?,
+            };
+            self.expect(":")?;
+            entries.push((key, self.expression()?));
+            if !self.eat(",") {
+                self.expect("}")?;
+                break;
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Parse the rest of a quoted string, after the opening quote.
+    fn string(&mut self, quote: char) -> ExpressionResult<String> {
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => return Ok(string),
+                Some('\\') => string.push(self.escape()?),
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    /// Parse the rest of a template string, after the opening backtick.
+    fn template(&mut self) -> ExpressionResult<Vec<TemplatePart>> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error("unterminated template string")),
+                Some('`') => break,
+                Some('\\') => text.push(self.escape()?),
+                Some('$') if self.peek() == Some('{') => {
+                    self.position += 1;
+                    if !text.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(TemplatePart::Expression(self.expression()?));
+                    self.expect("}")?;
+                }
+                Some(c) => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+        Ok(parts)
+    }
+
+    fn escape(&mut self) -> ExpressionResult<char> {
+        Ok(match self.next() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some(c @ ('\\' | '"' | '\'' | '`' | '$')) => c,
+            Some(c) => return Err(self.error(format!("unknown escape sequence \\{c}"))),
+            None => return Err(self.error("unterminated string")),
+        })
+    }
+
+    fn number(&mut self) -> ExpressionResult<Value> {
+        let start = self.position;
+        let mut is_float = false;
+        self.digits();
+        if self.peek() == Some('.') && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) {
+            is_float = true;
+            self.position += 1;
+            self.digits();
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            is_float = true;
+            self.position += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.position += 1;
+            }
+            if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return Err(self.error("expected digits in the exponent"));
+            }
+            self.digits();
+        }
+
+        let text: String = self.chars[start..self.position].iter().collect();
+        let number = if is_float {
+            text.parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+        } else {
+            text.parse::<i64>().ok().map(serde_json::Number::from)
+        };
+        number
+            .map(Value::Number)
+            .ok_or_else(|| self.error(format!("{text} is not a valid number")))
+    }
+
+    fn digits(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c.is_alphabetic() || c == '_' => {}
+            _ => return None,
+        }
+
+        let mut identifier = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_alphanumeric() || *c == '_') {
+            identifier.push(c);
+            self.position += 1;
+        }
+        Some(identifier)
+    }
+
+    /// Consume the token (after any whitespace) if it comes next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let matches = token
+            .chars()
+            .enumerate()
+            .all(|(offset, c)| self.peek_at(offset) == Some(c));
+        if matches {
+            self.position += token.chars().count();
+        }
+        matches
+    }
+
+    fn expect(&mut self, token: &str) -> ExpressionResult<()> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected \"{token}\"")))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.position += 1;
+        }
+        c
+    }
+
+    fn error(&self, message: impl Into<String>) -> ExpressionError {
+        ExpressionError::Syntax {
+            position: self.position + 1,
+            message: message.into(),
+        }
+    }
+}
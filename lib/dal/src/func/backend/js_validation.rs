@@ -17,11 +17,17 @@ pub struct FuncBackendJsValidation {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct FuncBackendJsValidationArgs {
     pub value: Value,
+    /// The values of the [`ValidationPrototype's`](crate::ValidationPrototype) inputs, by name.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub inputs: serde_json::Map<String, Value>,
 }
 
 impl FuncBackendJsValidationArgs {
     pub fn new(value: Value) -> Self {
-        Self { value }
+        Self {
+            value,
+            inputs: serde_json::Map::new(),
+        }
     }
 }
 
@@ -41,6 +47,7 @@ impl FuncDispatch for FuncBackendJsValidation {
             handler: handler.into(),
             code_base64: code_base64.to_owned(),
             value: args.value,
+            inputs: args.inputs,
         };
 
         Box::new(Self { context, request })
@@ -1,11 +1,13 @@
 use async_trait::async_trait;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::net::IpAddr;
 use std::str::FromStr;
+use url::Url;
 
 use crate::func::backend::{FuncBackend, FuncBackendResult};
-use crate::validation::{Validation, ValidationError, ValidationErrorKind};
+use crate::validation::{json_schema, Validation, ValidationError, ValidationErrorKind};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct FuncBackendValidation {
@@ -15,11 +17,17 @@ pub struct FuncBackendValidation {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct FuncBackendValidationArgs {
     pub validation: Validation,
+    /// The values of the [`ValidationPrototype's`](crate::ValidationPrototype) inputs, by name.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub inputs: serde_json::Map<String, Value>,
 }
 
 impl FuncBackendValidationArgs {
     pub fn new(validation: Validation) -> Self {
-        Self { validation }
+        Self {
+            validation,
+            inputs: serde_json::Map::new(),
+        }
     }
 }
 
@@ -42,7 +50,30 @@ impl FuncBackend for FuncBackendValidation {
             level: None,
         };
 
-        let maybe_validation_error = match self.args.validation {
+        // NOTE: the newer validations below treat a missing value as valid, so that they can be
+        // combined with a "not empty" validation when the value is also required.
+        let FuncBackendValidationArgs { validation, inputs } = self.args;
+        let maybe_validation_error = match validation {
+            Validation::ArrayItemsAreUnique { value } => value.and_then(|value| {
+                let duplicate = value
+                    .iter()
+                    .enumerate()
+                    .find(|(index, item)| value[..*index].contains(*item));
+                duplicate.map(|(index, item)| ValidationError {
+                    message: format!("item {index} ({item}) is a duplicate"),
+                    kind: ValidationErrorKind::ArrayItemsNotUnique,
+                    link: None,
+                    level: None,
+                })
+            }),
+            Validation::ArrayLengthIsBetween { value, lower_bound, upper_bound } => value.and_then(|value| {
+                out_of_bounds(value.len() as i64, lower_bound, upper_bound).map(|bounds| ValidationError {
+                    message: format!("array length ({}) is not {bounds}", value.len()),
+                    kind: ValidationErrorKind::ArrayLengthNotInRange,
+                    link: None,
+                    level: None,
+                })
+            }),
             Validation::IntegerIsBetweenTwoIntegers { value, lower_bound, upper_bound } => match value {
                 Some(value) => match value > lower_bound && value < upper_bound {
                     true => None,
@@ -55,6 +86,37 @@ impl FuncBackend for FuncBackendValidation {
                 },
                 None => Some(value_must_be_present_error),
             },
+            Validation::IntegerIsInRange { value, lower_bound, upper_bound } => value.and_then(|value| {
+                out_of_bounds(value, lower_bound, upper_bound).map(|bounds| ValidationError {
+                    message: format!("value ({value}) is not {bounds}"),
+                    kind: ValidationErrorKind::IntegerNotInRange,
+                    link: None,
+                    level: None,
+                })
+            }),
+            Validation::MapKeysMatchRegex { value, pattern } => match Regex::new(&pattern) {
+                Ok(re) => value.and_then(|value| {
+                    value.keys().find(|key| !re.is_match(key)).map(|key| ValidationError {
+                        message: format!("key ({key}) does not match pattern ({pattern})"),
+                        kind: ValidationErrorKind::MapKeyDoesNotMatchRegex,
+                        link: None,
+                        level: None,
+                    })
+                }),
+                Err(e) => Some(invalid_regex_error(&pattern, e)),
+            },
+            Validation::ObjectMatchesJsonSchema { value, schema } => value.and_then(|value| {
+                let errors = json_schema::validate(&schema, &value);
+                match errors.is_empty() {
+                    true => None,
+                    false => Some(ValidationError {
+                        message: format!("value does not match schema: {}", errors.join("; ")),
+                        kind: ValidationErrorKind::JsonSchemaMismatch,
+                        link: None,
+                        level: None,
+                    }),
+                }
+            }),
             Validation::IntegerIsNotEmpty { value} => match value {
                 Some(_value) => None,
                 None => Some(value_must_be_present_error),
@@ -78,6 +140,120 @@ impl FuncBackend for FuncBackendValidation {
                 },
                 None => Some(value_must_be_present_error),
             },
+            Validation::StringIsValidArn { value, service } => value.and_then(|value| {
+                let re = Regex::new(r"^arn:aws(-[a-z]+)*:([a-z0-9-]+):[a-z0-9-]*:(\d{12})?:.+$").unwrap();
+                let captured_service = re.captures(&value).and_then(|captures| captures.get(2)).map(|m| m.as_str().to_owned());
+                match (captured_service, service) {
+                    (Some(found), Some(expected)) if found != expected => Some(ValidationError {
+                        message: format!("value ({value}) is not an ARN for the {expected} service"),
+                        kind: ValidationErrorKind::InvalidArn,
+                        link: None,
+                        level: None,
+                    }),
+                    (Some(_), _) => None,
+                    (None, _) => Some(ValidationError {
+                        message: format!("value ({value}) is not a valid ARN"),
+                        kind: ValidationErrorKind::InvalidArn,
+                        link: None,
+                        level: None,
+                    }),
+                }
+            }),
+            Validation::StringIsValidCidr { value } => value.and_then(|value| {
+                match is_valid_cidr(&value) {
+                    true => None,
+                    false => Some(ValidationError {
+                        message: format!("value ({value}) is not a valid CIDR block"),
+                        kind: ValidationErrorKind::InvalidCidr,
+                        link: None,
+                        level: None,
+                    }),
+                }
+            }),
+            Validation::StringIsValidHostname { value } => value.and_then(|value| {
+                match is_valid_hostname(&value) {
+                    true => None,
+                    false => Some(ValidationError {
+                        message: format!("value ({value}) is not a valid hostname"),
+                        kind: ValidationErrorKind::InvalidHostname,
+                        link: None,
+                        level: None,
+                    }),
+                }
+            }),
+            Validation::StringIsValidUrl { value } => value.and_then(|value| {
+                match Url::parse(&value) {
+                    Ok(url) if url.has_host() => None,
+                    Ok(_) => Some(ValidationError {
+                        message: format!("value ({value}) is not a valid url: missing host"),
+                        kind: ValidationErrorKind::InvalidUrl,
+                        link: None,
+                        level: None,
+                    }),
+                    Err(e) => Some(ValidationError {
+                        message: format!("value ({value}) is not a valid url: {e}"),
+                        kind: ValidationErrorKind::InvalidUrl,
+                        link: None,
+                        level: None,
+                    }),
+                }
+            }),
+            Validation::StringLengthIsBetween { value, lower_bound, upper_bound } => value.and_then(|value| {
+                let length = value.chars().count();
+                out_of_bounds(length as i64, lower_bound, upper_bound).map(|bounds| ValidationError {
+                    message: format!("value length ({length}) is not {bounds}"),
+                    kind: ValidationErrorKind::StringLengthNotInRange,
+                    link: None,
+                    level: None,
+                })
+            }),
+            Validation::StringMatchesRegex { value, pattern } => match Regex::new(&pattern) {
+                Ok(re) => value.and_then(|value| match re.is_match(&value) {
+                    true => None,
+                    false => Some(ValidationError {
+                        message: format!("value ({value}) does not match pattern ({pattern})"),
+                        kind: ValidationErrorKind::StringDoesNotMatchRegex,
+                        link: None,
+                        level: None,
+                    }),
+                }),
+                Err(e) => Some(invalid_regex_error(&pattern, e)),
+            },
+            Validation::StringCidrIsWithinInput { value, input } => value.and_then(|value| {
+                let blocks: Vec<&str> = match inputs.get(&input) {
+                    Some(Value::String(block)) => vec![block.as_str()],
+                    Some(Value::Array(blocks)) => blocks.iter().filter_map(Value::as_str).collect(),
+                    _ => vec![],
+                };
+                // Nothing to check against until the input has a value, and malformed blocks are
+                // left to "StringIsValidCidr".
+                let inner = parse_cidr(&value)?;
+                if blocks.is_empty() || blocks.iter().filter_map(|block| parse_cidr(block)).any(|outer| cidr_contains(outer, inner)) {
+                    return None;
+                }
+                Some(ValidationError {
+                    message: format!("value ({value}) is not within {input} ({})", blocks.join(", ")),
+                    kind: ValidationErrorKind::CidrNotWithinInput,
+                    link: None,
+                    level: None,
+                })
+            }),
+            Validation::ValueIsRequiredWhenInputEquals { value, input, expected } => {
+                let is_missing = match value {
+                    None | Some(Value::Null) => true,
+                    Some(Value::String(value)) => value.is_empty(),
+                    Some(_) => false,
+                };
+                match is_missing && inputs.get(&input) == Some(&expected) {
+                    true => Some(ValidationError {
+                        message: format!("This field is required when {input} is {expected}"),
+                        kind: ValidationErrorKind::ValueRequiredByInput,
+                        link: None,
+                        level: None,
+                    }),
+                    false => None,
+                }
+            },
             Validation::StringIsHexColor { value } => match value {
                 Some(value) => {
                     let re = Regex::new(r"^#[\dA-Fa-f]{6,8}$").unwrap();
@@ -150,3 +326,323 @@ impl FuncBackend for FuncBackendValidation {
         Ok((Some(value.clone()), Some(value)))
     }
 }
+
+/// Returns a description of the bounds if "value" falls outside of them (inclusive).
+fn out_of_bounds(value: i64, lower_bound: Option<i64>, upper_bound: Option<i64>) -> Option<String> {
+    let too_low = lower_bound.map(|lower| value < lower).unwrap_or(false);
+    let too_high = upper_bound.map(|upper| value > upper).unwrap_or(false);
+    if !too_low && !too_high {
+        return None;
+    }
+
+    Some(match (lower_bound, upper_bound) {
+        (Some(lower), Some(upper)) => format!("between {lower} and {upper}"),
+        (Some(lower), None) => format!("at least {lower}"),
+        (None, Some(upper)) => format!("at most {upper}"),
+        (None, None) => unreachable!("value can only be out of bounds if a bound is set"),
+    })
+}
+
+fn invalid_regex_error(pattern: &str, e: regex::Error) -> ValidationError {
+    ValidationError {
+        message: format!("validation pattern ({pattern}) is not a valid regular expression: {e}"),
+        kind: ValidationErrorKind::InvalidRegex,
+        link: None,
+        level: None,
+    }
+}
+
+fn is_valid_cidr(value: &str) -> bool {
+    parse_cidr(value).is_some()
+}
+
+fn parse_cidr(value: &str) -> Option<(IpAddr, u8)> {
+    let (address, prefix) = value.split_once('/')?;
+    let address = IpAddr::from_str(address).ok()?;
+    let max_prefix = match address {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    };
+
+    u8::from_str(prefix)
+        .ok()
+        .filter(|prefix| *prefix <= max_prefix)
+        .map(|prefix| (address, prefix))
+}
+
+/// Returns whether the "inner" CIDR block lies entirely within the "outer" one.
+fn cidr_contains(outer: (IpAddr, u8), inner: (IpAddr, u8)) -> bool {
+    let ((outer_address, outer_prefix), (inner_address, inner_prefix)) = (outer, inner);
+    if inner_prefix < outer_prefix {
+        return false;
+    }
+
+    match (outer_address, inner_address) {
+        (IpAddr::V4(outer_address), IpAddr::V4(inner_address)) => {
+            let mask = u32::MAX
+                .checked_shl(32 - u32::from(outer_prefix))
+                .unwrap_or(0);
+            u32::from(outer_address) & mask == u32::from(inner_address) & mask
+        }
+        (IpAddr::V6(outer_address), IpAddr::V6(inner_address)) => {
+            let mask = u128::MAX
+                .checked_shl(128 - u32::from(outer_prefix))
+                .unwrap_or(0);
+            u128::from(outer_address) & mask == u128::from(inner_address) & mask
+        }
+        _ => false,
+    }
+}
+
+fn is_valid_hostname(value: &str) -> bool {
+    let hostname = value.strip_suffix('.').unwrap_or(value);
+    if hostname.is_empty() || hostname.len() > 253 {
+        return false;
+    }
+
+    hostname.split('.').all(|label| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn errors(validation: Validation) -> Vec<ValidationErrorKind> {
+        errors_with_inputs(validation, serde_json::Map::new()).await
+    }
+
+    async fn errors_with_inputs(
+        validation: Validation,
+        inputs: serde_json::Map<String, Value>,
+    ) -> Vec<ValidationErrorKind> {
+        let (value, _) =
+            FuncBackendValidation::new(FuncBackendValidationArgs { validation, inputs })
+                .inline()
+                .await
+                .expect("validation should run");
+        let errors: Vec<ValidationError> =
+            serde_json::from_value(value.expect("validation returns a value"))
+                .expect("validation returns errors");
+        errors.into_iter().map(|error| error.kind).collect()
+    }
+
+    #[tokio::test]
+    async fn string_validations() {
+        assert!(errors(Validation::StringMatchesRegex {
+            value: Some("web-01".to_string()),
+            pattern: "^[a-z]+-\\d+$".to_string(),
+        })
+        .await
+        .is_empty());
+        assert_eq!(
+            vec![ValidationErrorKind::InvalidRegex],
+            errors(Validation::StringMatchesRegex {
+                value: None,
+                pattern: "(".to_string(),
+            })
+            .await
+        );
+        assert_eq!(
+            vec![ValidationErrorKind::StringLengthNotInRange],
+            errors(Validation::StringLengthIsBetween {
+                value: Some("toolong".to_string()),
+                lower_bound: None,
+                upper_bound: Some(3),
+            })
+            .await
+        );
+
+        for (cidr, valid) in [
+            ("10.0.0.0/16", true),
+            ("fd00::/8", true),
+            ("10.0.0.0/33", false),
+            ("10.0.0.0", false),
+        ] {
+            let expected = if valid {
+                vec![]
+            } else {
+                vec![ValidationErrorKind::InvalidCidr]
+            };
+            assert_eq!(
+                expected,
+                errors(Validation::StringIsValidCidr {
+                    value: Some(cidr.to_string())
+                })
+                .await,
+                "{cidr}"
+            );
+        }
+
+        for (hostname, valid) in [
+            ("example.com", true),
+            ("localhost.", true),
+            ("-bad.example.com", false),
+            ("bad_host", false),
+        ] {
+            let expected = if valid {
+                vec![]
+            } else {
+                vec![ValidationErrorKind::InvalidHostname]
+            };
+            assert_eq!(
+                expected,
+                errors(Validation::StringIsValidHostname {
+                    value: Some(hostname.to_string())
+                })
+                .await,
+                "{hostname}"
+            );
+        }
+
+        assert!(errors(Validation::StringIsValidUrl {
+            value: Some("https://systeminit.com/docs".to_string())
+        })
+        .await
+        .is_empty());
+        assert_eq!(
+            vec![ValidationErrorKind::InvalidUrl],
+            errors(Validation::StringIsValidUrl {
+                value: Some("systeminit.com".to_string())
+            })
+            .await
+        );
+
+        assert!(errors(Validation::StringIsValidArn {
+            value: Some("arn:aws:iam::123456789012:role/poop".to_string()),
+            service: Some("iam".to_string()),
+        })
+        .await
+        .is_empty());
+        assert_eq!(
+            vec![ValidationErrorKind::InvalidArn],
+            errors(Validation::StringIsValidArn {
+                value: Some("arn:aws:s3:::bucket".to_string()),
+                service: Some("iam".to_string()),
+            })
+            .await
+        );
+        assert_eq!(
+            vec![ValidationErrorKind::InvalidArn],
+            errors(Validation::StringIsValidArn {
+                value: Some("not-an-arn".to_string()),
+                service: None,
+            })
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn collection_validations() {
+        assert_eq!(
+            vec![ValidationErrorKind::ArrayItemsNotUnique],
+            errors(Validation::ArrayItemsAreUnique {
+                value: Some(vec![serde_json::json!("a"), serde_json::json!("a")]),
+            })
+            .await
+        );
+        assert_eq!(
+            vec![ValidationErrorKind::ArrayLengthNotInRange],
+            errors(Validation::ArrayLengthIsBetween {
+                value: Some(vec![]),
+                lower_bound: Some(1),
+                upper_bound: None,
+            })
+            .await
+        );
+        assert_eq!(
+            vec![ValidationErrorKind::MapKeyDoesNotMatchRegex],
+            errors(Validation::MapKeysMatchRegex {
+                value: serde_json::json!({ "ok": 1, "Not OK": 2 })
+                    .as_object()
+                    .cloned(),
+                pattern: "^[a-z]+$".to_string(),
+            })
+            .await
+        );
+        assert_eq!(
+            vec![ValidationErrorKind::JsonSchemaMismatch],
+            errors(Validation::ObjectMatchesJsonSchema {
+                value: Some(serde_json::json!({ "port": "eighty" })),
+                schema: serde_json::json!({
+                    "type": "object",
+                    "properties": { "port": { "type": "integer" } },
+                }),
+            })
+            .await
+        );
+        assert!(errors(Validation::IntegerIsInRange {
+            value: Some(443),
+            lower_bound: Some(1),
+            upper_bound: Some(65535),
+        })
+        .await
+        .is_empty());
+    }
+
+    #[tokio::test]
+    async fn cross_field_validations() {
+        let inputs = |value: Value| value.as_object().cloned().expect("inputs are an object");
+
+        let port_required = |value: Option<Value>| Validation::ValueIsRequiredWhenInputEquals {
+            value,
+            input: "protocol".to_string(),
+            expected: serde_json::json!("tcp"),
+        };
+        assert_eq!(
+            vec![ValidationErrorKind::ValueRequiredByInput],
+            errors_with_inputs(
+                port_required(None),
+                inputs(serde_json::json!({ "protocol": "tcp" }))
+            )
+            .await
+        );
+        assert!(errors_with_inputs(
+            port_required(Some(serde_json::json!(22))),
+            inputs(serde_json::json!({ "protocol": "tcp" }))
+        )
+        .await
+        .is_empty());
+        assert!(errors_with_inputs(
+            port_required(None),
+            inputs(serde_json::json!({ "protocol": "icmp" }))
+        )
+        .await
+        .is_empty());
+
+        let subnet = |cidr: &str| Validation::StringCidrIsWithinInput {
+            value: Some(cidr.to_string()),
+            input: "vpcCidr".to_string(),
+        };
+        for (cidr, vpc, valid) in [
+            ("10.0.1.0/24", serde_json::json!("10.0.0.0/16"), true),
+            ("10.1.0.0/24", serde_json::json!("10.0.0.0/16"), false),
+            ("10.0.0.0/8", serde_json::json!("10.0.0.0/16"), false),
+            (
+                "10.1.0.0/24",
+                serde_json::json!(["10.0.0.0/16", "10.1.0.0/16"]),
+                true,
+            ),
+            ("fd00:1::/64", serde_json::json!("fd00::/16"), true),
+            ("10.0.1.0/24", serde_json::json!("fd00::/16"), false),
+            ("10.0.1.0/24", Value::Null, true),
+        ] {
+            let expected = if valid {
+                vec![]
+            } else {
+                vec![ValidationErrorKind::CidrNotWithinInput]
+            };
+            assert_eq!(
+                expected,
+                errors_with_inputs(subnet(cidr), inputs(serde_json::json!({ "vpcCidr": vpc })))
+                    .await,
+                "{cidr}"
+            );
+        }
+    }
+}
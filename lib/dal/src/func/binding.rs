@@ -14,6 +14,7 @@ use crate::{
         array::FuncBackendArray,
         boolean::FuncBackendBoolean,
         diff::FuncBackendDiff,
+        expression::{FuncBackendExpression, FuncBackendExpressionArgs},
         identity::FuncBackendIdentity,
         integer::FuncBackendInteger,
         js_action::FuncBackendJsAction,
@@ -268,6 +269,15 @@ impl FuncBinding {
             FuncBackendKind::Boolean => FuncBackendBoolean::create_and_execute(&self.args).await,
             FuncBackendKind::Identity => FuncBackendIdentity::create_and_execute(&self.args).await,
             FuncBackendKind::Diff => FuncBackendDiff::create_and_execute(&self.args).await,
+            FuncBackendKind::Expression => {
+                let args = FuncBackendExpressionArgs {
+                    expression: func
+                        .code_plaintext()?
+                        .ok_or(FuncBackendError::DispatchMissingBase64(*func.id()))?,
+                    arguments: self.args.clone(),
+                };
+                FuncBackendExpression::create_and_execute(&serde_json::to_value(args)?).await
+            }
             FuncBackendKind::Integer => FuncBackendInteger::create_and_execute(&self.args).await,
             FuncBackendKind::Map => FuncBackendMap::create_and_execute(&self.args).await,
             FuncBackendKind::Object => FuncBackendObject::create_and_execute(&self.args).await,
@@ -347,6 +357,7 @@ impl FuncBinding {
             | FuncBackendKind::Boolean
             | FuncBackendKind::Identity
             | FuncBackendKind::Diff
+            | FuncBackendKind::Expression
             | FuncBackendKind::Integer
             | FuncBackendKind::Map
             | FuncBackendKind::Object
@@ -1,4 +1,5 @@
 use crate::{Tenancy, TransactionsError};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use strum::Display as StrumDisplay;
 use thiserror::Error;
@@ -7,7 +8,7 @@ use si_data_nats::NatsError;
 use si_data_pg::PgError;
 use telemetry::prelude::*;
 
-use crate::{pk, DalContext, Timestamp, UserPk};
+use crate::{pk, ApiTokenPk, DalContext, Timestamp, UserPk};
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -24,9 +25,15 @@ pub enum HistoryEventError {
 
 pub type HistoryEventResult<T> = Result<T, HistoryEventError>;
 
+const HISTORY_EVENT_LIST: &str = include_str!("queries/history_event/list.sql");
+
+/// The most [`HistoryEvents`](HistoryEvent) [`HistoryEvent::list`] returns at once.
+pub const HISTORY_EVENT_PAGE_SIZE_MAX: usize = 1000;
+
 #[remain::sorted]
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq, StrumDisplay, Clone, Copy)]
 pub enum HistoryActor {
+    ApiToken(ApiTokenPk),
     SystemInit,
     User(UserPk),
 }
@@ -35,6 +42,7 @@ impl HistoryActor {
     pub fn distinct_id(&self) -> String {
         match self {
             HistoryActor::User(pk) => pk.to_string(),
+            HistoryActor::ApiToken(pk) => pk.to_string(),
             HistoryActor::SystemInit => "unknown-backend".to_string(),
         }
     }
@@ -64,6 +72,46 @@ pub struct HistoryEvent {
     pub timestamp: Timestamp,
 }
 
+/// Narrows down the [`HistoryEvents`](HistoryEvent) returned by [`HistoryEvent::list`]. Every
+/// filter that is set has to match.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEventFilter {
+    pub actor: Option<HistoryActor>,
+    /// Matches labels starting with the prefix. A trailing `*` is ignored, so `change_set.*` and
+    /// `change_set.` match the same events.
+    pub label_prefix: Option<String>,
+    /// Matches events that happened at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Matches events that happened before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Matches events whose data mentions this id (or pk) anywhere.
+    pub object_id: Option<String>,
+}
+
+impl HistoryEventFilter {
+    fn label_pattern(&self) -> Option<String> {
+        self.label_prefix.as_ref().map(|prefix| {
+            let prefix = prefix.strip_suffix('*').unwrap_or(prefix);
+            let escaped = prefix
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("{escaped}%")
+        })
+    }
+}
+
+/// A page of [`HistoryEvents`](HistoryEvent), newest first.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEventPage {
+    pub events: Vec<HistoryEvent>,
+    /// Pass this to [`HistoryEvent::list`] to get the next page. There are no more events when
+    /// it is [`None`].
+    pub next_cursor: Option<HistoryEventPk>,
+}
+
 impl HistoryEvent {
     #[instrument(skip(ctx, label, message))]
     pub async fn new(
@@ -89,4 +137,72 @@ impl HistoryEvent {
         let object: HistoryEvent = serde_json::from_value(json)?;
         Ok(object)
     }
+
+    /// Lists the [`HistoryEvents`](HistoryEvent) of the [`Workspace`](crate::Workspace) of the
+    /// [`Tenancy`] of the [`DalContext`] that match the filter, newest first. Events that aren't
+    /// tied to a [`Workspace`](crate::Workspace), such as [`User`](crate::User) creation, are
+    /// never listed.
+    ///
+    /// Pages hold at most `page_size` events (capped at [`HISTORY_EVENT_PAGE_SIZE_MAX`]), and
+    /// start after the event of the cursor, if there is one.
+    #[instrument(skip(ctx))]
+    pub async fn list(
+        ctx: &DalContext,
+        filter: &HistoryEventFilter,
+        cursor: Option<HistoryEventPk>,
+        page_size: usize,
+    ) -> HistoryEventResult<HistoryEventPage> {
+        let workspace_pk = match ctx.tenancy().workspace_pk() {
+            Some(workspace_pk) => workspace_pk,
+            None => {
+                return Ok(HistoryEventPage {
+                    events: Vec::new(),
+                    next_cursor: None,
+                })
+            }
+        };
+        let page_size = page_size.clamp(1, HISTORY_EVENT_PAGE_SIZE_MAX);
+        let actor = filter
+            .actor
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()?;
+
+        // We fetch one more event than asked for to know whether there is another page.
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                HISTORY_EVENT_LIST,
+                &[
+                    &workspace_pk,
+                    &actor,
+                    &filter.label_pattern(),
+                    &filter.since,
+                    &filter.until,
+                    &filter.object_id,
+                    &cursor,
+                    &(page_size as i64 + 1),
+                ],
+            )
+            .await?;
+
+        let mut events = Vec::with_capacity(rows.len());
+        for row in rows {
+            let json: serde_json::Value = row.try_get("object")?;
+            events.push(serde_json::from_value::<HistoryEvent>(json)?);
+        }
+        let next_cursor = if events.len() > page_size {
+            events.truncate(page_size);
+            events.last().map(|event| event.pk)
+        } else {
+            None
+        };
+
+        Ok(HistoryEventPage {
+            events,
+            next_cursor,
+        })
+    }
 }
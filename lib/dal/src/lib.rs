@@ -20,12 +20,15 @@ use crate::builtins::SelectedTestBuiltinSchemas;
 pub mod action;
 pub mod action_prototype;
 pub mod actor_view;
+pub mod api_token;
 pub mod attribute;
 pub mod builtins;
 pub mod change_set;
 pub mod change_status;
 pub mod code_view;
 pub mod component;
+pub mod component_tag;
+pub mod component_template;
 pub mod context;
 pub mod cyclone_key_pair;
 pub mod diagram;
@@ -40,6 +43,7 @@ pub mod job_failure;
 pub mod jwt_key;
 pub mod key_pair;
 pub mod label_list;
+pub mod manifest;
 pub mod node;
 pub mod node_menu;
 pub mod pkg;
@@ -50,7 +54,9 @@ pub mod prototype_context;
 pub mod prototype_list_for_func;
 pub mod provider;
 pub mod qualification;
+pub mod query;
 pub mod reconciliation_prototype;
+pub mod resource_import;
 pub mod schema;
 pub mod secret;
 pub mod socket;
@@ -64,6 +70,7 @@ pub mod timestamp;
 pub mod user;
 pub mod validation;
 pub mod visibility;
+pub mod webhook;
 pub mod workspace;
 pub mod ws_event;
 
@@ -73,6 +80,11 @@ pub use action_prototype::{
     ActionPrototypeView,
 };
 pub use actor_view::ActorView;
+pub use api_token::{ApiToken, ApiTokenError, ApiTokenPk, ApiTokenResult, API_TOKEN_PREFIX};
+pub use attribute::value::provenance::{
+    AttributeValueLocation, AttributeValueProvenance, AttributeValueProvenanceArgument,
+    AttributeValueProvenanceFunc, AttributeValueProvenanceSource,
+};
 pub use attribute::value::view::AttributeView;
 pub use attribute::{
     context::{
@@ -95,8 +107,16 @@ pub use builtins::{BuiltinsError, BuiltinsResult};
 pub use change_set::{ChangeSet, ChangeSetError, ChangeSetPk, ChangeSetStatus};
 pub use code_view::{CodeLanguage, CodeView};
 pub use component::{
-    resource::ResourceView, status::ComponentStatus, status::HistoryActorTimestamp, Component,
-    ComponentError, ComponentId, ComponentView, ComponentViewProperties,
+    bulk::ComponentSelection, duplicate::DuplicatedComponent, resource::ResourceView,
+    status::ComponentStatus, status::HistoryActorTimestamp, Component, ComponentError, ComponentId,
+    ComponentView, ComponentViewProperties,
+};
+pub use component_tag::{
+    ComponentTag, ComponentTagError, ComponentTagId, ComponentTagInheritance, EffectiveComponentTag,
+};
+pub use component_template::{
+    ComponentTemplate, ComponentTemplateDefinition, ComponentTemplateError, ComponentTemplateId,
+    ComponentTemplateParameter,
 };
 pub use context::{
     AccessBuilder, Connections, DalContext, DalContextBuilder, RequestContext, ServicesContext,
@@ -117,7 +137,10 @@ pub use func::{
     binding::{FuncBinding, FuncBindingError, FuncBindingId},
     Func, FuncError, FuncId, FuncResult,
 };
-pub use history_event::{HistoryActor, HistoryEvent, HistoryEventError};
+pub use history_event::{
+    HistoryActor, HistoryEvent, HistoryEventError, HistoryEventFilter, HistoryEventPage,
+    HistoryEventPk,
+};
 pub use index_map::IndexMap;
 pub use job::definition::DependentValuesUpdate;
 pub use job::processor::{JobQueueProcessor, NatsProcessor};
@@ -125,6 +148,7 @@ pub use job_failure::{JobFailure, JobFailureError, JobFailureResult};
 pub use jwt_key::JwtPublicSigningKey;
 pub use key_pair::{KeyPair, KeyPairError, KeyPairResult, PublicKey};
 pub use label_list::{LabelEntry, LabelList, LabelListError};
+pub use manifest::{Manifest, ManifestChange, ManifestError, ManifestPlan};
 pub use node::NodeId;
 pub use node::{Node, NodeError, NodeKind};
 pub use node_menu::NodeMenuError;
@@ -136,10 +160,14 @@ pub use prototype_list_for_func::{
 pub use provider::external::{ExternalProvider, ExternalProviderError, ExternalProviderId};
 pub use provider::internal::{InternalProvider, InternalProviderError, InternalProviderId};
 pub use qualification::{QualificationError, QualificationView};
+pub use query::{ComponentFilter, ComponentQueryMatch, PropPredicate, QueryError, QueryResult};
 pub use reconciliation_prototype::{
     ReconciliationPrototype, ReconciliationPrototypeContext, ReconciliationPrototypeError,
     ReconciliationPrototypeId,
 };
+pub use resource_import::{
+    ImportMapping, ImportSource, ResourceImport, ResourceImportError, ResourceImportPlan,
+};
 pub use schema::variant::leaves::LeafInput;
 pub use schema::variant::leaves::LeafInputLocation;
 pub use schema::variant::leaves::LeafKind;
@@ -159,15 +187,21 @@ pub use status::{
 };
 pub use tenancy::{Tenancy, TenancyError};
 pub use timestamp::{Timestamp, TimestampError};
-pub use user::{User, UserClaim, UserError, UserPk, UserResult};
+pub use user::{
+    User, UserCapability, UserClaim, UserError, UserPk, UserResult, WorkspaceMember, WorkspaceRole,
+};
 pub use validation::prototype::{
     context::ValidationPrototypeContext, ValidationPrototype, ValidationPrototypeError,
-    ValidationPrototypeId,
+    ValidationPrototypeId, ValidationPrototypeInput,
 };
 pub use validation::resolver::{
     ValidationResolver, ValidationResolverError, ValidationResolverId, ValidationStatus,
 };
 pub use visibility::{Visibility, VisibilityError};
+pub use webhook::{
+    Webhook, WebhookAttempt, WebhookClient, WebhookDelivery, WebhookDeliveryPk,
+    WebhookDeliveryStatus, WebhookError, WebhookEventKind, WebhookPk, WebhookResult,
+};
 pub use workspace::{Workspace, WorkspaceError, WorkspacePk, WorkspaceResult, WorkspaceSignup};
 pub use ws_event::{WsEvent, WsEventError, WsEventResult, WsPayload};
 
@@ -0,0 +1,775 @@
+//! This module contains [`Manifest`], a declarative description of [`Components`](crate::Component),
+//! their values and the [`Connections`](crate::Connection) between them, meant to be kept in a
+//! YAML or JSON file alongside whatever else describes the infrastructure.
+//!
+//! [`Manifest::plan`] compares the manifest with the [`Components`](crate::Component) visible in
+//! the current [`Visibility`](crate::Visibility), returning the [`changes`](ManifestChange) needed
+//! for them to match it, and [`ManifestPlan::apply`] makes those changes. Plans are usually
+//! applied in a change set, so that they can be reviewed like any other change.
+//!
+//! [`Components`](crate::Component) are matched by name. Only the values written in the manifest
+//! are managed: everything else keeps whatever it was set to, or computed as. Objects are
+//! compared prop by prop, while arrays and maps are compared as a whole. Since the elements of
+//! arrays and maps can't be removed, a changed array or map can only be set where it is still
+//! empty.
+//!
+//! Frames are not described by manifests: the parent of a [`Component`](crate::Component) and
+//! the connections between a frame and its children are left alone, and frames are never pruned.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use thiserror::Error;
+
+use crate::component::view::ComponentViewError;
+use crate::component_template::{add_create_actions, json_pointer, set_value_for_path};
+use crate::edge::{EdgeId, EdgeKind};
+use crate::job::definition::DependentValuesUpdate;
+use crate::node::NodeId;
+use crate::prop::PropPath;
+use crate::socket::{SocketEdgeKind, SocketError};
+use crate::{
+    AttributeReadContext, AttributeValue, AttributeValueError, Component, ComponentError,
+    ComponentId, ComponentTemplateError, ComponentType, ComponentView, DalContext, Edge, EdgeError,
+    ExternalProvider, ExternalProviderError, NodeError, Prop, PropError, PropKind, Schema,
+    SchemaError, SchemaVariantId, Socket, StandardModel, StandardModelError, TransactionsError,
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ManifestError {
+    #[error("more than one component is named {0}")]
+    AmbiguousComponentName(String),
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] AttributeValueError),
+    #[error("attribute value not found for context: {0:?}")]
+    AttributeValueNotFoundForContext(AttributeReadContext),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("component not found: {0}")]
+    ComponentNotFound(ComponentId),
+    #[error("component {0} is a {1}, but the manifest describes a {2}")]
+    ComponentSchemaMismatch(String, String, String),
+    #[error("component template error: {0}")]
+    ComponentTemplate(#[from] ComponentTemplateError),
+    #[error("component view error: {0}")]
+    ComponentView(#[from] ComponentViewError),
+    #[error("{1} of component {0} already has elements, which can't be replaced")]
+    ContainerNotEmpty(String, String),
+    #[error("component {0} is described more than once")]
+    DuplicateComponentName(String),
+    #[error("edge error: {0}")]
+    Edge(#[from] EdgeError),
+    #[error("external provider error: {0}")]
+    ExternalProvider(#[from] ExternalProviderError),
+    #[error("value for prop {0} does not match the prop kind {1}")]
+    InvalidValue(String, PropKind),
+    #[error("node error: {0}")]
+    Node(#[from] NodeError),
+    #[error("node not found for component: {0}")]
+    NodeNotFoundForComponent(ComponentId),
+    #[error("prop error: {0}")]
+    Prop(#[from] PropError),
+    #[error("prop not found: {0}")]
+    PropNotFound(String),
+    #[error("schema error: {0}")]
+    Schema(#[from] SchemaError),
+    #[error("schema not found for component: {0}")]
+    SchemaNotFoundForComponent(ComponentId),
+    #[error("schema variant {1} not found for schema {0}")]
+    SchemaVariantNotFound(String, String),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("serde yaml error: {0}")]
+    SerdeYaml(#[from] serde_yaml::Error),
+    #[error("socket error: {0}")]
+    Socket(#[from] SocketError),
+    #[error("socket {1} not found for component {0}")]
+    SocketNotFound(String, String),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("component {0} is not described by the manifest")]
+    UnknownComponent(String),
+}
+
+pub type ManifestResult<T> = Result<T, ManifestError>;
+
+/// A [`Component`] described by a [`Manifest`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestComponent {
+    /// Identifies the component, both within the manifest and in the workspace.
+    pub name: String,
+    pub schema_name: String,
+    /// The [`SchemaVariant`](crate::SchemaVariant) to use, or the default one for the
+    /// [`Schema`] if not set.
+    pub schema_variant_name: Option<String>,
+    /// Where the component is placed when it is created. Existing components are not moved.
+    #[serde(default)]
+    pub x: i64,
+    #[serde(default)]
+    pub y: i64,
+    /// The values of the component, laid out like the props under "root", for example
+    /// `{"domain": {"region": "us-east-2"}}`.
+    #[serde(default)]
+    pub values: serde_json::Map<String, Value>,
+}
+
+/// A configuration [`Connection`](crate::Connection) between two
+/// [`ManifestComponents`](ManifestComponent), with the [`Sockets`](Socket) referred to by name.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestConnection {
+    pub from_component: String,
+    pub from_socket: String,
+    pub to_component: String,
+    pub to_socket: String,
+}
+
+/// The [`Components`](Component) of a workspace and the
+/// [`Connections`](crate::Connection) between them, as they should be. See the
+/// [`module`](crate::manifest) for more information.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    #[serde(default)]
+    pub components: Vec<ManifestComponent>,
+    #[serde(default)]
+    pub connections: Vec<ManifestConnection>,
+}
+
+/// A single change of a [`ManifestPlan`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ManifestChange {
+    #[serde(rename_all = "camelCase")]
+    CreateComponent {
+        name: String,
+        schema_name: String,
+        schema_variant_id: SchemaVariantId,
+        x: i64,
+        y: i64,
+    },
+    /// Sets the value of the [`Prop`] at `path` (starting with "root"). `from` is the current
+    /// value, if the component exists and the prop has one.
+    #[serde(rename_all = "camelCase")]
+    SetValue {
+        component: String,
+        path: Vec<String>,
+        from: Option<Value>,
+        to: Value,
+    },
+    CreateConnection(ManifestConnection),
+    #[serde(rename_all = "camelCase")]
+    DeleteConnection {
+        edge_id: EdgeId,
+        #[serde(flatten)]
+        connection: ManifestConnection,
+    },
+    /// Only planned when pruning, for components the manifest does not describe, other than
+    /// frames.
+    #[serde(rename_all = "camelCase")]
+    DeleteComponent {
+        name: String,
+        component_id: ComponentId,
+    },
+}
+
+/// What [`Manifest::plan`] found needs to change, in the order [`Self::apply`] makes the
+/// changes.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestPlan {
+    pub changes: Vec<ManifestChange>,
+    /// The existing [`Components`](Component) described by the manifest, by name.
+    pub components: BTreeMap<String, ComponentId>,
+}
+
+impl Manifest {
+    /// Parses a manifest written in JSON or YAML.
+    pub fn parse(text: &str) -> ManifestResult<Self> {
+        if text.trim_start().starts_with('{') {
+            Ok(serde_json::from_str(text)?)
+        } else {
+            Ok(serde_yaml::from_str(text)?)
+        }
+    }
+
+    /// Checks that component names are unique and that connections are between described
+    /// components.
+    pub fn validate(&self) -> ManifestResult<()> {
+        let mut names = HashSet::new();
+        for component in &self.components {
+            if !names.insert(component.name.as_str()) {
+                return Err(ManifestError::DuplicateComponentName(
+                    component.name.clone(),
+                ));
+            }
+        }
+
+        for connection in &self.connections {
+            for name in [&connection.from_component, &connection.to_component] {
+                if !names.contains(name.as_str()) {
+                    return Err(ManifestError::UnknownComponent(name.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compares the manifest with the [`Components`](Component) of the current
+    /// [`Visibility`](crate::Visibility). With `prune`, the components the manifest does not
+    /// describe are deleted, except for frames.
+    pub async fn plan(&self, ctx: &DalContext, prune: bool) -> ManifestResult<ManifestPlan> {
+        self.validate()?;
+
+        let mut existing: BTreeMap<String, Vec<Component>> = BTreeMap::new();
+        for component in Component::list(ctx).await? {
+            existing
+                .entry(component.name(ctx).await?)
+                .or_default()
+                .push(component);
+        }
+
+        let mut plan = ManifestPlan::default();
+        let mut created = Vec::new();
+        let mut set_values = Vec::new();
+        for manifest_component in &self.components {
+            let name = &manifest_component.name;
+            let (component_id, schema_variant_id) =
+                match existing.get(name).map(Vec::as_slice).unwrap_or_default() {
+                    [] => {
+                        let schema_variant_id = manifest_component.schema_variant_id(ctx).await?;
+                        created.push(ManifestChange::CreateComponent {
+                            name: name.clone(),
+                            schema_name: manifest_component.schema_name.clone(),
+                            schema_variant_id,
+                            x: manifest_component.x,
+                            y: manifest_component.y,
+                        });
+                        (None, schema_variant_id)
+                    }
+                    [component] => {
+                        let schema_variant_id =
+                            manifest_component.check_schema(ctx, component).await?;
+                        plan.components.insert(name.clone(), *component.id());
+                        (Some(*component.id()), schema_variant_id)
+                    }
+                    _ => return Err(ManifestError::AmbiguousComponentName(name.clone())),
+                };
+
+            let root_prop =
+                Prop::find_prop_by_path(ctx, schema_variant_id, &PropPath::new(["root"])).await?;
+            let mut values = Vec::new();
+            collect_values(
+                ctx,
+                &root_prop,
+                vec!["root".to_owned()],
+                Value::Object(manifest_component.values.clone()),
+                &mut values,
+            )
+            .await?;
+
+            let properties = match component_id {
+                Some(component_id) => ComponentView::new(ctx, component_id).await?.properties,
+                None => Value::Null,
+            };
+            for (path, to) in values {
+                let from = properties.pointer(&json_pointer(&path)).cloned();
+                if from.as_ref() == Some(&to) {
+                    continue;
+                }
+                let has_elements = match &from {
+                    Some(Value::Array(array)) => !array.is_empty(),
+                    Some(Value::Object(map)) => !map.is_empty(),
+                    _ => false,
+                };
+                if has_elements {
+                    return Err(ManifestError::ContainerNotEmpty(
+                        name.clone(),
+                        path.join("/"),
+                    ));
+                }
+                set_values.push(ManifestChange::SetValue {
+                    component: name.clone(),
+                    path,
+                    from,
+                    to,
+                });
+            }
+        }
+
+        let current = current_connections(ctx, &plan.components).await?;
+        for (connection, edge_id) in &current {
+            if !self.connections.contains(connection) {
+                plan.changes.push(ManifestChange::DeleteConnection {
+                    edge_id: *edge_id,
+                    connection: connection.clone(),
+                });
+            }
+        }
+        plan.changes.extend(created);
+        plan.changes.extend(set_values);
+        for connection in &self.connections {
+            if !current.contains_key(connection) {
+                plan.changes
+                    .push(ManifestChange::CreateConnection(connection.clone()));
+            }
+        }
+
+        if prune {
+            let names: HashSet<&str> = self
+                .components
+                .iter()
+                .map(|component| component.name.as_str())
+                .collect();
+            for (name, components) in &existing {
+                if names.contains(name.as_str()) {
+                    continue;
+                }
+                for component in components {
+                    if component.get_type(ctx).await? != ComponentType::Component {
+                        continue;
+                    }
+                    plan.changes.push(ManifestChange::DeleteComponent {
+                        name: name.clone(),
+                        component_id: *component.id(),
+                    });
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+}
+
+impl ManifestComponent {
+    async fn schema_variant_id(&self, ctx: &DalContext) -> ManifestResult<SchemaVariantId> {
+        let schema = Schema::find_by_name(ctx, &self.schema_name).await?;
+        Ok(match &self.schema_variant_name {
+            Some(schema_variant_name) => *schema
+                .variants(ctx)
+                .await?
+                .iter()
+                .find(|schema_variant| schema_variant.name() == schema_variant_name)
+                .ok_or_else(|| {
+                    ManifestError::SchemaVariantNotFound(
+                        schema.name().to_owned(),
+                        schema_variant_name.clone(),
+                    )
+                })?
+                .id(),
+            None => *schema
+                .default_schema_variant_id()
+                .ok_or_else(|| SchemaError::NoDefaultVariant(*schema.id()))?,
+        })
+    }
+
+    /// Checks that an existing [`Component`] is of the [`Schema`] (and variant, if given) the
+    /// manifest describes, returning its [`SchemaVariantId`].
+    async fn check_schema(
+        &self,
+        ctx: &DalContext,
+        component: &Component,
+    ) -> ManifestResult<SchemaVariantId> {
+        let schema = component
+            .schema(ctx)
+            .await?
+            .ok_or(ManifestError::SchemaNotFoundForComponent(*component.id()))?;
+        let schema_variant = component
+            .schema_variant(ctx)
+            .await?
+            .ok_or(ManifestError::SchemaNotFoundForComponent(*component.id()))?;
+
+        let matches = schema.name() == self.schema_name
+            && self
+                .schema_variant_name
+                .as_ref()
+                .map_or(true, |name| name == schema_variant.name());
+        if !matches {
+            return Err(ManifestError::ComponentSchemaMismatch(
+                self.name.clone(),
+                schema.name().to_owned(),
+                self.schema_name.clone(),
+            ));
+        }
+
+        Ok(*schema_variant.id())
+    }
+}
+
+impl ManifestPlan {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Makes the changes of the plan, returning the [`Components`](Component) described by the
+    /// manifest by name, including the ones that were created.
+    pub async fn apply(&self, ctx: &DalContext) -> ManifestResult<BTreeMap<String, ComponentId>> {
+        let mut components = self.components.clone();
+        let mut schema_variant_ids: HashMap<ComponentId, SchemaVariantId> = HashMap::new();
+        let mut node_ids: HashMap<ComponentId, NodeId> = HashMap::new();
+        let mut attribute_value_ids = Vec::new();
+
+        for change in &self.changes {
+            match change {
+                ManifestChange::DeleteConnection { edge_id, .. } => {
+                    if let Some(mut edge) = Edge::get_by_id(ctx, edge_id).await? {
+                        edge.delete_and_propagate(ctx).await?;
+                    }
+                }
+                ManifestChange::CreateComponent {
+                    name,
+                    schema_variant_id,
+                    x,
+                    y,
+                    ..
+                } => {
+                    let (component, mut node) =
+                        Component::new(ctx, name, *schema_variant_id).await?;
+                    node.set_geometry(
+                        ctx,
+                        x.to_string(),
+                        y.to_string(),
+                        None::<&str>,
+                        None::<&str>,
+                    )
+                    .await?;
+                    add_create_actions(ctx, *schema_variant_id, *component.id()).await?;
+
+                    components.insert(name.clone(), *component.id());
+                    schema_variant_ids.insert(*component.id(), *schema_variant_id);
+                    node_ids.insert(*component.id(), *node.id());
+                }
+                ManifestChange::SetValue {
+                    component,
+                    path,
+                    to,
+                    ..
+                } => {
+                    let component_id = *components
+                        .get(component)
+                        .ok_or_else(|| ManifestError::UnknownComponent(component.clone()))?;
+                    let schema_variant_id = match schema_variant_ids.get(&component_id) {
+                        Some(schema_variant_id) => *schema_variant_id,
+                        None => {
+                            let schema_variant_id = *Component::get_by_id(ctx, &component_id)
+                                .await?
+                                .ok_or(ManifestError::ComponentNotFound(component_id))?
+                                .schema_variant(ctx)
+                                .await?
+                                .ok_or(ManifestError::SchemaNotFoundForComponent(component_id))?
+                                .id();
+                            schema_variant_ids.insert(component_id, schema_variant_id);
+                            schema_variant_id
+                        }
+                    };
+                    attribute_value_ids.extend(
+                        set_value_for_path(ctx, component_id, schema_variant_id, path, to.clone())
+                            .await?,
+                    );
+                }
+                ManifestChange::CreateConnection(connection) => {
+                    let from_component_id =
+                        *components.get(&connection.from_component).ok_or_else(|| {
+                            ManifestError::UnknownComponent(connection.from_component.clone())
+                        })?;
+                    let to_component_id =
+                        *components.get(&connection.to_component).ok_or_else(|| {
+                            ManifestError::UnknownComponent(connection.to_component.clone())
+                        })?;
+                    let from_node_id = node_id(ctx, &mut node_ids, from_component_id).await?;
+                    let to_node_id = node_id(ctx, &mut node_ids, to_component_id).await?;
+
+                    let from_socket = Socket::find_by_name_for_edge_kind_and_node(
+                        ctx,
+                        &connection.from_socket,
+                        SocketEdgeKind::ConfigurationOutput,
+                        from_node_id,
+                    )
+                    .await?
+                    .ok_or_else(|| {
+                        ManifestError::SocketNotFound(
+                            connection.from_component.clone(),
+                            connection.from_socket.clone(),
+                        )
+                    })?;
+                    let to_socket = Socket::find_by_name_for_edge_kind_and_node(
+                        ctx,
+                        &connection.to_socket,
+                        SocketEdgeKind::ConfigurationInput,
+                        to_node_id,
+                    )
+                    .await?
+                    .ok_or_else(|| {
+                        ManifestError::SocketNotFound(
+                            connection.to_component.clone(),
+                            connection.to_socket.clone(),
+                        )
+                    })?;
+                    Edge::new_for_connection(
+                        ctx,
+                        to_node_id,
+                        *to_socket.id(),
+                        from_node_id,
+                        *from_socket.id(),
+                        EdgeKind::Configuration,
+                    )
+                    .await?;
+
+                    // Like when connecting sockets on the diagram, the output socket is
+                    // re-emitted so that the connected component picks up its value.
+                    let external_provider =
+                        ExternalProvider::find_for_socket(ctx, *from_socket.id())
+                            .await?
+                            .ok_or(EdgeError::ExternalProviderNotFoundForSocket(
+                                *from_socket.id(),
+                            ))?;
+                    let attribute_read_context = AttributeReadContext {
+                        external_provider_id: Some(*external_provider.id()),
+                        component_id: Some(from_component_id),
+                        ..Default::default()
+                    };
+                    let attribute_value =
+                        AttributeValue::find_for_context(ctx, attribute_read_context)
+                            .await?
+                            .ok_or(ManifestError::AttributeValueNotFoundForContext(
+                                attribute_read_context,
+                            ))?;
+                    attribute_value_ids.push(*attribute_value.id());
+                }
+                ManifestChange::DeleteComponent { component_id, .. } => {
+                    if let Some(mut component) = Component::get_by_id(ctx, component_id).await? {
+                        component.delete_and_propagate(ctx).await?;
+                    }
+                }
+            }
+        }
+
+        if !attribute_value_ids.is_empty() {
+            ctx.enqueue_job(DependentValuesUpdate::new(
+                ctx.access_builder(),
+                *ctx.visibility(),
+                attribute_value_ids,
+            ))
+            .await?;
+        }
+
+        Ok(components)
+    }
+}
+
+/// Splits the values of a [`ManifestComponent`] into the values of individual props, walking
+/// into objects. Arrays and maps are kept whole.
+#[async_recursion::async_recursion]
+async fn collect_values(
+    ctx: &DalContext,
+    prop: &Prop,
+    path: Vec<String>,
+    value: Value,
+    values: &mut Vec<(Vec<String>, Value)>,
+) -> ManifestResult<()> {
+    match (prop.kind(), value) {
+        (PropKind::Object, Value::Object(object)) => {
+            let child_props = prop.child_props(ctx).await?;
+            for (name, child_value) in object {
+                let child_prop = child_props
+                    .iter()
+                    .find(|child_prop| child_prop.name() == name)
+                    .ok_or_else(|| {
+                        ManifestError::PropNotFound(format!("{}/{name}", path.join("/")))
+                    })?;
+                let mut child_path = path.clone();
+                child_path.push(name);
+                collect_values(ctx, child_prop, child_path, child_value, values).await?;
+            }
+        }
+        (PropKind::Object, _) => {
+            return Err(ManifestError::InvalidValue(
+                path.join("/"),
+                PropKind::Object,
+            ))
+        }
+        (_, value) => values.push((path, value)),
+    }
+
+    Ok(())
+}
+
+/// The configuration [`Edges`](Edge) between the given [`Components`](Component), as
+/// [`ManifestConnections`](ManifestConnection). Edges between a frame and its children are left
+/// out.
+async fn current_connections(
+    ctx: &DalContext,
+    components: &BTreeMap<String, ComponentId>,
+) -> ManifestResult<BTreeMap<ManifestConnection, EdgeId>> {
+    let names: HashMap<ComponentId, &String> = components
+        .iter()
+        .map(|(name, component_id)| (*component_id, name))
+        .collect();
+
+    let mut parents = HashSet::new();
+    let mut edges = Vec::new();
+    let mut seen_edge_ids = HashSet::new();
+    for component_id in components.values() {
+        for edge in Edge::list_for_component(ctx, *component_id).await? {
+            if !seen_edge_ids.insert(*edge.id()) {
+                continue;
+            }
+            let tail_component_id = ComponentId::from(*edge.tail_object_id());
+            let head_component_id = ComponentId::from(*edge.head_object_id());
+            match edge.kind() {
+                EdgeKind::Symbolic => {
+                    parents.insert((tail_component_id, head_component_id));
+                }
+                EdgeKind::Configuration => edges.push(edge),
+            }
+        }
+    }
+
+    let mut connections = BTreeMap::new();
+    for edge in edges {
+        let tail_component_id = ComponentId::from(*edge.tail_object_id());
+        let head_component_id = ComponentId::from(*edge.head_object_id());
+        let (from_component, to_component) =
+            match (names.get(&tail_component_id), names.get(&head_component_id)) {
+                (Some(from_component), Some(to_component)) => (from_component, to_component),
+                _ => continue,
+            };
+        if edge.head_socket_id() == edge.tail_socket_id()
+            || parents.contains(&(tail_component_id, head_component_id))
+            || parents.contains(&(head_component_id, tail_component_id))
+        {
+            continue;
+        }
+
+        let from_socket = Socket::get_by_id(ctx, edge.tail_socket_id())
+            .await?
+            .ok_or(EdgeError::SocketNotFound(*edge.tail_socket_id()))?;
+        let to_socket = Socket::get_by_id(ctx, edge.head_socket_id())
+            .await?
+            .ok_or(EdgeError::SocketNotFound(*edge.head_socket_id()))?;
+        connections.insert(
+            ManifestConnection {
+                from_component: (*from_component).clone(),
+                from_socket: from_socket.name().to_owned(),
+                to_component: (*to_component).clone(),
+                to_socket: to_socket.name().to_owned(),
+            },
+            *edge.id(),
+        );
+    }
+
+    Ok(connections)
+}
+
+async fn node_id(
+    ctx: &DalContext,
+    node_ids: &mut HashMap<ComponentId, NodeId>,
+    component_id: ComponentId,
+) -> ManifestResult<NodeId> {
+    if let Some(node_id) = node_ids.get(&component_id) {
+        return Ok(*node_id);
+    }
+
+    let node_id = *Component::get_by_id(ctx, &component_id)
+        .await?
+        .ok_or(ManifestError::ComponentNotFound(component_id))?
+        .node(ctx)
+        .await?
+        .pop()
+        .ok_or(ManifestError::NodeNotFoundForComponent(component_id))?
+        .id();
+    node_ids.insert(component_id, node_id);
+    Ok(node_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const YAML: &str = r#"
+components:
+  - name: server
+    schemaName: starfield
+    x: 100
+    values:
+      domain:
+        freestar: t2.micro
+  - name: client
+    schemaName: fallout
+connections:
+  - fromComponent: client
+    fromSocket: bethesda
+    toComponent: server
+    toSocket: bethesda
+"#;
+
+    #[test]
+    fn parse_yaml() {
+        let manifest = Manifest::parse(YAML).expect("could not parse yaml manifest");
+        assert_eq!(2, manifest.components.len());
+        assert_eq!(100, manifest.components[0].x);
+        assert_eq!(0, manifest.components[0].y);
+        assert_eq!(
+            Some(&json!({ "freestar": "t2.micro" })),
+            manifest.components[0].values.get("domain"),
+        );
+        assert!(manifest.components[1].values.is_empty());
+        assert_eq!("bethesda", manifest.connections[0].from_socket);
+    }
+
+    #[test]
+    fn parse_json_matches_yaml() {
+        let json = serde_json::to_string(&Manifest::parse(YAML).expect("could not parse yaml"))
+            .expect("could not serialize manifest");
+        assert_eq!(
+            Manifest::parse(YAML).expect("could not parse yaml"),
+            Manifest::parse(&json).expect("could not parse json"),
+        );
+    }
+
+    #[test]
+    fn validate() {
+        let mut manifest = Manifest::parse(YAML).expect("could not parse yaml manifest");
+        assert!(manifest.validate().is_ok());
+
+        manifest.connections[0].to_component = "nowhere".to_owned();
+        assert!(matches!(
+            manifest.validate(),
+            Err(ManifestError::UnknownComponent(name)) if name == "nowhere"
+        ));
+
+        manifest.connections.clear();
+        manifest.components[1].name = "server".to_owned();
+        assert!(matches!(
+            manifest.validate(),
+            Err(ManifestError::DuplicateComponentName(name)) if name == "server"
+        ));
+    }
+
+    #[test]
+    fn serialize_change() {
+        let change = ManifestChange::SetValue {
+            component: "server".to_owned(),
+            path: vec!["root".to_owned(), "domain".to_owned()],
+            from: None,
+            to: json!("t2.micro"),
+        };
+        assert_eq!(
+            json!({
+                "kind": "setValue",
+                "component": "server",
+                "path": ["root", "domain"],
+                "from": null,
+                "to": "t2.micro",
+            }),
+            serde_json::to_value(change).expect("could not serialize change"),
+        );
+    }
+}
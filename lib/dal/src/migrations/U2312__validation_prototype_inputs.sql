@@ -0,0 +1 @@
+ALTER TABLE validation_prototypes ADD COLUMN inputs jsonb NOT NULL DEFAULT '[]'::jsonb;
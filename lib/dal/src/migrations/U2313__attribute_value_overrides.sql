@@ -0,0 +1,3 @@
+ALTER TABLE attribute_values ADD COLUMN overridden_attribute_prototype_id ident;
+ALTER TABLE attribute_values ADD COLUMN overridden_func_id ident;
+ALTER TABLE attribute_values ADD COLUMN locked bool NOT NULL DEFAULT false;
@@ -0,0 +1,44 @@
+-- Everyone who already belongs to a workspace keeps the access they had before roles existed.
+ALTER TABLE user_belongs_to_workspaces ADD COLUMN role text NOT NULL DEFAULT 'owner';
+ALTER TABLE user_belongs_to_workspaces ALTER COLUMN role DROP DEFAULT;
+
+CREATE OR REPLACE FUNCTION user_associate_workspace_v1(
+    this_user_pk ident,
+    this_workspace_pk ident
+    ) RETURNS void AS
+$$
+DECLARE
+    this_role text;
+BEGIN
+    -- The first member of a workspace owns it, everyone joining after them can edit it.
+    IF EXISTS (SELECT 1
+               FROM user_belongs_to_workspaces
+               WHERE workspace_pk = this_workspace_pk
+                 AND visibility_deleted_at IS NULL) THEN
+        this_role := 'editor';
+    ELSE
+        this_role := 'owner';
+    END IF;
+
+    INSERT INTO user_belongs_to_workspaces (user_pk, workspace_pk, role)
+        VALUES (this_user_pk, this_workspace_pk, this_role)
+        ON CONFLICT DO NOTHING;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION user_set_workspace_role_v1(
+    this_user_pk ident,
+    this_workspace_pk ident,
+    this_role text
+    ) RETURNS boolean AS
+$$
+BEGIN
+    UPDATE user_belongs_to_workspaces
+    SET role = this_role, updated_at = CLOCK_TIMESTAMP()
+    WHERE user_pk = this_user_pk
+      AND workspace_pk = this_workspace_pk
+      AND visibility_deleted_at IS NULL;
+
+    RETURN FOUND;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,57 @@
+CREATE TABLE api_tokens
+(
+    pk                          ident primary key default ident_create_v1(),
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    workspace_pk                ident                    NOT NULL,
+    created_by_user_pk          ident                    NOT NULL,
+    name                        text                     NOT NULL,
+    -- Only the hash of the token is stored, the token itself is shown once when it is created.
+    token_hash                  text                     NOT NULL,
+    role                        text                     NOT NULL,
+    expires_at                  timestamp with time zone,
+    revoked_at                  timestamp with time zone
+);
+CREATE UNIQUE INDEX ON api_tokens (token_hash);
+CREATE INDEX ON api_tokens (workspace_pk);
+
+CREATE OR REPLACE FUNCTION api_token_create_v1(
+    this_workspace_pk ident,
+    this_created_by_user_pk ident,
+    this_name text,
+    this_token text,
+    this_role text,
+    this_expires_at timestamp with time zone,
+    OUT object json) AS
+$$
+DECLARE
+    this_new_row           api_tokens%ROWTYPE;
+BEGIN
+    INSERT INTO api_tokens (workspace_pk, created_by_user_pk, name, token_hash, role, expires_at)
+    VALUES (this_workspace_pk,
+            this_created_by_user_pk,
+            this_name,
+            ENCODE(DIGEST(this_token, 'sha256'), 'hex'),
+            this_role,
+            this_expires_at)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION api_token_revoke_v1(
+    this_pk ident,
+    OUT object json) AS
+$$
+DECLARE
+    this_row           api_tokens%ROWTYPE;
+BEGIN
+    UPDATE api_tokens
+    SET revoked_at = COALESCE(revoked_at, CLOCK_TIMESTAMP()), updated_at = CLOCK_TIMESTAMP()
+    WHERE pk = this_pk
+    RETURNING * INTO this_row;
+
+    object := row_to_json(this_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
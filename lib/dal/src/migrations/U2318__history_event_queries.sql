@@ -0,0 +1,8 @@
+-- Audit log queries page through the events of a workspace, newest first, optionally narrowed
+-- down to labels starting with a prefix.
+CREATE INDEX IF NOT EXISTS history_events_workspace_created_at_idx
+    ON history_events (tenancy_workspace_pk, created_at DESC, pk DESC);
+CREATE INDEX IF NOT EXISTS history_events_label_idx
+    ON history_events (label text_pattern_ops);
+CREATE INDEX IF NOT EXISTS history_events_actor_idx
+    ON history_events USING GIN (actor);
@@ -0,0 +1,143 @@
+CREATE TABLE webhooks
+(
+    pk                          ident primary key default ident_create_v1(),
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    workspace_pk                ident                    NOT NULL,
+    url                         text                     NOT NULL,
+    -- A json array of the event kinds the webhook is subscribed to.
+    event_kinds                 jsonb                    NOT NULL DEFAULT '[]'::jsonb,
+    -- Used to sign every delivery, so receivers can tell they come from us.
+    secret                      text                     NOT NULL,
+    enabled                     bool                     NOT NULL DEFAULT TRUE
+);
+CREATE INDEX ON webhooks (workspace_pk);
+
+CREATE TABLE webhook_deliveries
+(
+    pk                          ident primary key default ident_create_v1(),
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    webhook_pk                  ident                    NOT NULL REFERENCES webhooks (pk) ON DELETE CASCADE,
+    event_kind                  text                     NOT NULL,
+    payload                     jsonb                    NOT NULL,
+    status                      text                     NOT NULL DEFAULT 'pending',
+    attempts                    integer                  NOT NULL DEFAULT 0,
+    last_status_code            integer,
+    last_error                  text,
+    next_attempt_at             timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    delivered_at                timestamp with time zone
+);
+CREATE INDEX ON webhook_deliveries (webhook_pk, created_at DESC);
+CREATE INDEX ON webhook_deliveries (next_attempt_at) WHERE status = 'pending';
+
+CREATE OR REPLACE FUNCTION webhook_create_v1(
+    this_workspace_pk ident,
+    this_url text,
+    this_event_kinds jsonb,
+    this_secret text,
+    OUT object json) AS
+$$
+DECLARE
+    this_new_row           webhooks%ROWTYPE;
+BEGIN
+    INSERT INTO webhooks (workspace_pk, url, event_kinds, secret)
+    VALUES (this_workspace_pk, this_url, this_event_kinds, this_secret)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION webhook_update_v1(
+    this_pk ident,
+    this_url text,
+    this_event_kinds jsonb,
+    this_enabled bool,
+    OUT object json) AS
+$$
+DECLARE
+    this_row           webhooks%ROWTYPE;
+BEGIN
+    UPDATE webhooks
+    SET url = this_url, event_kinds = this_event_kinds, enabled = this_enabled, updated_at = CLOCK_TIMESTAMP()
+    WHERE pk = this_pk
+    RETURNING * INTO this_row;
+
+    object := row_to_json(this_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION webhook_delivery_create_v1(
+    this_webhook_pk ident,
+    this_event_kind text,
+    this_payload jsonb,
+    OUT object json) AS
+$$
+DECLARE
+    this_new_row           webhook_deliveries%ROWTYPE;
+BEGIN
+    INSERT INTO webhook_deliveries (webhook_pk, event_kind, payload)
+    VALUES (this_webhook_pk, this_event_kind, this_payload)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Claims pending deliveries that are due by pushing their next attempt past the lease, so that
+-- concurrent dispatchers never pick up the same delivery twice.
+CREATE OR REPLACE FUNCTION webhook_deliveries_claim_due_v1(
+    this_limit bigint,
+    this_lease_seconds integer)
+    RETURNS TABLE
+            (
+                object json
+            )
+AS
+$$
+BEGIN
+    RETURN QUERY
+        UPDATE webhook_deliveries
+        SET next_attempt_at = CLOCK_TIMESTAMP() + make_interval(secs => this_lease_seconds),
+            updated_at      = CLOCK_TIMESTAMP()
+        WHERE pk IN (SELECT pk
+                     FROM webhook_deliveries
+                     WHERE status = 'pending'
+                       AND next_attempt_at <= CLOCK_TIMESTAMP()
+                     ORDER BY next_attempt_at
+                     LIMIT this_limit FOR UPDATE SKIP LOCKED)
+        RETURNING row_to_json(webhook_deliveries.*) AS object;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Records one attempt at a delivery. A null retry delay means the delivery won't be attempted
+-- again: it is marked as failed, unless the attempt succeeded.
+CREATE OR REPLACE FUNCTION webhook_delivery_record_attempt_v1(
+    this_pk ident,
+    this_succeeded bool,
+    this_status_code integer,
+    this_error text,
+    this_retry_in_seconds integer,
+    OUT object json) AS
+$$
+DECLARE
+    this_row           webhook_deliveries%ROWTYPE;
+BEGIN
+    UPDATE webhook_deliveries
+    SET attempts         = attempts + 1,
+        last_status_code = this_status_code,
+        last_error       = this_error,
+        status           = CASE
+                               WHEN this_succeeded THEN 'succeeded'
+                               WHEN this_retry_in_seconds IS NULL THEN 'failed'
+                               ELSE 'pending' END,
+        delivered_at     = CASE WHEN this_succeeded THEN CLOCK_TIMESTAMP() END,
+        next_attempt_at  = CLOCK_TIMESTAMP() + make_interval(secs => COALESCE(this_retry_in_seconds, 0)),
+        updated_at       = CLOCK_TIMESTAMP()
+    WHERE pk = this_pk
+    RETURNING * INTO this_row;
+
+    object := row_to_json(this_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
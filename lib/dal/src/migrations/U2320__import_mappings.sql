@@ -0,0 +1,48 @@
+CREATE TABLE import_mappings
+(
+    pk                          ident                    PRIMARY KEY DEFAULT ident_create_v1(),
+    id                          ident                    NOT NULL DEFAULT ident_create_v1(),
+    tenancy_workspace_pk        ident,
+    visibility_change_set_pk    ident                    NOT NULL DEFAULT ident_nil_v1(),
+    visibility_deleted_at       timestamp with time zone,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    schema_variant_id           ident                    NOT NULL,
+    source                      text                     NOT NULL,
+    resource_type               text                     NOT NULL,
+    attributes                  jsonb                    NOT NULL DEFAULT '[]'::jsonb
+);
+SELECT standard_model_table_constraints_v1('import_mappings');
+CREATE INDEX ON import_mappings (resource_type);
+CREATE INDEX ON import_mappings (schema_variant_id);
+
+INSERT INTO standard_models (table_name, table_type, history_event_label_base, history_event_message_name)
+VALUES ('import_mappings', 'model', 'import_mapping', 'Import Mapping');
+
+CREATE OR REPLACE FUNCTION import_mapping_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_schema_variant_id ident,
+    this_source text,
+    this_resource_type text,
+    this_attributes jsonb,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           import_mappings%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO import_mappings (tenancy_workspace_pk, visibility_change_set_pk,
+                                 schema_variant_id, source, resource_type, attributes)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_schema_variant_id, this_source, this_resource_type, this_attributes)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,52 @@
+-- Only the most recently updated mapping for a source and resource type was ever found, so the
+-- others can go.
+DELETE
+FROM import_mappings
+WHERE pk IN (SELECT pk
+             FROM (SELECT pk,
+                          ROW_NUMBER() OVER (
+                              PARTITION BY tenancy_workspace_pk, visibility_change_set_pk, source, resource_type
+                              ORDER BY updated_at DESC
+                              ) AS position
+                   FROM import_mappings
+                   WHERE visibility_deleted_at IS NULL) AS ranked
+             WHERE position > 1);
+
+CREATE UNIQUE INDEX import_mappings_resource_type
+    ON import_mappings (tenancy_workspace_pk, visibility_change_set_pk, source, resource_type)
+    WHERE visibility_deleted_at IS NULL;
+
+-- Creating a mapping that already exists updates it instead, so concurrent upserts can't
+-- duplicate it.
+CREATE OR REPLACE FUNCTION import_mapping_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_schema_variant_id ident,
+    this_source text,
+    this_resource_type text,
+    this_attributes jsonb,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           import_mappings%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO import_mappings (tenancy_workspace_pk, visibility_change_set_pk,
+                                 schema_variant_id, source, resource_type, attributes)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_schema_variant_id, this_source, this_resource_type, this_attributes)
+    ON CONFLICT (tenancy_workspace_pk, visibility_change_set_pk, source, resource_type)
+        WHERE visibility_deleted_at IS NULL
+        DO UPDATE SET schema_variant_id = EXCLUDED.schema_variant_id,
+                      attributes        = EXCLUDED.attributes,
+                      updated_at        = CLOCK_TIMESTAMP()
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,27 @@
+-- The token is hashed before it reaches the database, so it never shows up as a query parameter.
+DROP FUNCTION IF EXISTS api_token_create_v1(ident, ident, text, text, text, timestamp with time zone);
+
+CREATE OR REPLACE FUNCTION api_token_create_v1(
+    this_workspace_pk ident,
+    this_created_by_user_pk ident,
+    this_name text,
+    this_token_hash text,
+    this_role text,
+    this_expires_at timestamp with time zone,
+    OUT object json) AS
+$$
+DECLARE
+    this_new_row           api_tokens%ROWTYPE;
+BEGIN
+    INSERT INTO api_tokens (workspace_pk, created_by_user_pk, name, token_hash, role, expires_at)
+    VALUES (this_workspace_pk,
+            this_created_by_user_pk,
+            this_name,
+            this_token_hash,
+            this_role,
+            this_expires_at)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
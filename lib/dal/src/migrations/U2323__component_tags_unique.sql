@@ -0,0 +1,63 @@
+-- Only the most recently updated tag for a component and key was ever found, so the others can
+-- go.
+DELETE
+FROM component_tags
+WHERE pk IN (SELECT pk
+             FROM (SELECT pk,
+                          ROW_NUMBER() OVER (
+                              PARTITION BY tenancy_workspace_pk, visibility_change_set_pk, component_id, key
+                              ORDER BY updated_at DESC
+                              ) AS position
+                   FROM component_tags
+                   WHERE visibility_deleted_at IS NULL) AS ranked
+             WHERE position > 1);
+
+CREATE UNIQUE INDEX component_tags_key
+    ON component_tags (tenancy_workspace_pk, visibility_change_set_pk, component_id, key)
+    WHERE visibility_deleted_at IS NULL;
+
+-- Creating a tag that already exists updates it instead, so concurrent upserts can't duplicate
+-- it.
+CREATE OR REPLACE FUNCTION component_tag_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_component_id ident,
+    this_key text,
+    this_value text,
+    this_inheritance text,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           component_tags%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO component_tags (tenancy_workspace_pk, visibility_change_set_pk,
+                                component_id, key, value, inheritance)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_component_id, this_key, this_value, this_inheritance)
+    ON CONFLICT (tenancy_workspace_pk, visibility_change_set_pk, component_id, key)
+        WHERE visibility_deleted_at IS NULL
+        DO UPDATE SET value       = EXCLUDED.value,
+                      inheritance = EXCLUDED.inheritance,
+                      updated_at  = CLOCK_TIMESTAMP()
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Tags of components that were already deleted would otherwise stay behind.
+UPDATE component_tags
+SET visibility_deleted_at = components.visibility_deleted_at,
+    updated_at            = CLOCK_TIMESTAMP()
+FROM components
+WHERE components.id = component_tags.component_id
+  AND components.tenancy_workspace_pk = component_tags.tenancy_workspace_pk
+  AND components.visibility_change_set_pk = component_tags.visibility_change_set_pk
+  AND components.visibility_deleted_at IS NOT NULL
+  AND component_tags.visibility_deleted_at IS NULL;
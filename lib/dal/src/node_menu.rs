@@ -1,6 +1,7 @@
 //! This module is responsible for creating NodeMenus. At the moment, it only really makes
 //! the node add menu. It creates a tree for the menu, and can create it from the
-//! [`Schema`](crate::Schema)'s menu items based on the diagram context for the menu.
+//! [`Schema`](crate::Schema)'s menu items based on the diagram context for the menu, along with
+//! the available [`ComponentTemplates`](crate::ComponentTemplate).
 
 use serde::{Deserialize, Serialize};
 use si_data_pg::PgError;
@@ -10,7 +11,10 @@ use thiserror::Error;
 
 use crate::schema::SchemaUiMenu;
 use crate::DalContext;
-use crate::{SchemaError, SchemaId, StandardModel, StandardModelError};
+use crate::{
+    ComponentTemplate, ComponentTemplateId, SchemaError, SchemaId, StandardModel,
+    StandardModelError,
+};
 
 #[allow(clippy::large_enum_variant)]
 #[remain::sorted]
@@ -72,12 +76,27 @@ impl Item {
     }
 }
 
+/// A [`ComponentTemplate`] which can be instantiated from the menu.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TemplateItem {
+    pub name: String,
+    pub template_id: ComponentTemplateId,
+}
+
+impl TemplateItem {
+    pub fn new(name: impl Into<String>, template_id: ComponentTemplateId) -> Self {
+        let name = name.into();
+        TemplateItem { name, template_id }
+    }
+}
+
 #[remain::sorted]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 pub enum MenuItem {
     Category(Category),
     Item(Item),
+    Template(TemplateItem),
 }
 
 impl MenuItem {
@@ -93,6 +112,7 @@ impl MenuItem {
         match self {
             MenuItem::Category(c) => &c.name,
             MenuItem::Item(i) => &i.name,
+            MenuItem::Template(t) => &t.name,
         }
     }
 
@@ -206,11 +226,15 @@ impl MenuItems {
 #[derive(Deserialize, Serialize, Debug)]
 pub struct GenerateMenuItem {
     pub raw_items: Vec<(Vec<String>, Item)>,
+    pub raw_templates: Vec<TemplateItem>,
     menu_items: MenuItems,
 }
 
+/// The category [`ComponentTemplates`](ComponentTemplate) are listed under.
+pub const TEMPLATES_CATEGORY: &str = "Templates";
+
 impl GenerateMenuItem {
-    /// Generates raw items and initializes menu items as an empty vec.
+    /// Generates raw items (and templates) and initializes menu items as an empty vec.
     pub async fn new(ctx: &DalContext, include_ui_hidden: bool) -> NodeMenuResult<Self> {
         let mut item_list = Vec::new();
 
@@ -233,8 +257,16 @@ impl GenerateMenuItem {
             }
         }
 
+        let mut templates = ComponentTemplate::list(ctx).await?;
+        templates.sort_by(|a, b| a.name().cmp(b.name()));
+        let template_list = templates
+            .into_iter()
+            .map(|template| TemplateItem::new(template.name(), *template.id()))
+            .collect();
+
         Ok(Self {
             raw_items: item_list,
+            raw_templates: template_list,
             menu_items: MenuItems::new(),
         })
     }
@@ -246,6 +278,11 @@ impl GenerateMenuItem {
             self.menu_items
                 .insert_menu_item(&path, MenuItem::Item(item))?;
         }
+        let templates_path = vec![TEMPLATES_CATEGORY.to_string()];
+        for template in self.raw_templates {
+            self.menu_items
+                .insert_menu_item(&templates_path, MenuItem::Template(template))?;
+        }
         self.menu_items.to_json_value()
     }
 }
@@ -5,7 +5,7 @@ mod export;
 mod import;
 
 pub use export::{get_component_type, PkgExporter};
-pub use import::{import_pkg, import_pkg_from_pkg, ImportOptions};
+pub use import::{import_pkg, import_pkg_from_pkg, restore_workspace_backup, ImportOptions};
 
 use si_pkg::{FuncSpecBackendKind, FuncSpecBackendResponseType, SiPkgError, SpecError};
 
@@ -20,11 +20,12 @@ use crate::{
     socket::SocketError,
     ActionPrototypeError, AttributeContextBuilderError, AttributePrototypeArgumentError,
     AttributePrototypeArgumentId, AttributePrototypeError, AttributePrototypeId,
-    AttributeReadContext, AttributeValueError, ChangeSetError, ChangeSetPk, ExternalProviderError,
-    ExternalProviderId, FuncBackendKind, FuncBackendResponseType, FuncError, FuncId,
-    InternalProviderError, InternalProviderId, PropError, PropId, PropKind, SchemaError, SchemaId,
-    SchemaVariantError, SchemaVariantId, StandardModelError, ValidationPrototypeError,
-    WorkspaceError, WorkspacePk,
+    AttributeReadContext, AttributeValueError, ChangeSetError, ChangeSetPk, ComponentTemplateError,
+    ComponentTemplateId, ExternalProviderError, ExternalProviderId, FuncBackendKind,
+    FuncBackendResponseType, FuncError, FuncId, InternalProviderError, InternalProviderId,
+    PropError, PropId, PropKind, SchemaError, SchemaId, SchemaVariantError, SchemaVariantId,
+    StandardModelError, ValidationPrototypeError, ValidationPrototypeId, WorkspaceError,
+    WorkspacePk,
 };
 
 #[remain::sorted]
@@ -60,8 +61,14 @@ pub enum PkgError {
     ChangeSet(#[from] ChangeSetError),
     #[error("change set {0} not found")]
     ChangeSetNotFound(ChangeSetPk),
+    #[error(transparent)]
+    ComponentTemplate(#[from] ComponentTemplateError),
+    #[error("component template {0} not found")]
+    ComponentTemplateNotFound(ComponentTemplateId),
     #[error("map item prop {0} has both custom key prototypes and custom prop only prototype")]
     ConflictingMapKeyPrototypes(PropId),
+    #[error("validation prototype {0} has inputs, which packages cannot express")]
+    CrossFieldValidationNotExportable(ValidationPrototypeId),
     #[error("expected data on an SiPkg node, but none found: {0}")]
     DataNotFound(String),
     #[error("Cannot find Socket for explicit InternalProvider {0}")]
@@ -124,6 +131,8 @@ pub enum PkgError {
     MissingSchemaVariantDefinition(SchemaVariantId),
     #[error("Unique id missing for node in workspace backup: {0}")]
     MissingUniqueIdForNode(String),
+    #[error("Package {0} is not a workspace backup")]
+    NotAWorkspaceBackup(String),
     #[error("Package with that hash already installed: {0}")]
     PackageAlreadyInstalled(String),
     #[error(transparent)]
@@ -166,6 +175,8 @@ pub enum PkgError {
     Workspace(#[from] WorkspaceError),
     #[error("Cannot find default change set \"{0}\" in workspace backup")]
     WorkspaceBackupNoDefaultChangeSet(String),
+    #[error("Workspace backup is for workspace {1}, cannot restore it into workspace {0}")]
+    WorkspaceBackupWorkspaceMismatch(WorkspacePk, WorkspacePk),
     #[error("Workspace backup missing workspace name")]
     WorkspaceNameNotInBackup,
     #[error("Workspace not found: {0}")]
@@ -192,6 +203,7 @@ impl From<FuncBackendKind> for FuncSpecBackendKind {
             FuncBackendKind::Array => Self::Array,
             FuncBackendKind::Boolean => Self::Boolean,
             FuncBackendKind::Diff => Self::Diff,
+            FuncBackendKind::Expression => Self::Expression,
             FuncBackendKind::Identity => Self::Identity,
             FuncBackendKind::Integer => Self::Integer,
             FuncBackendKind::JsAction => Self::JsAction,
@@ -214,6 +226,7 @@ impl From<FuncSpecBackendKind> for FuncBackendKind {
             FuncSpecBackendKind::Array => Self::Array,
             FuncSpecBackendKind::Boolean => Self::Boolean,
             FuncSpecBackendKind::Diff => Self::Diff,
+            FuncSpecBackendKind::Expression => Self::Expression,
             FuncSpecBackendKind::Identity => Self::Identity,
             FuncSpecBackendKind::Integer => Self::Integer,
             FuncSpecBackendKind::JsAction => Self::JsAction,
@@ -3,12 +3,12 @@ use strum::IntoEnumIterator;
 use telemetry::prelude::*;
 
 use si_pkg::{
-    ActionFuncSpec, AttrFuncInputSpec, AttrFuncInputSpecKind, ChangeSetSpec, FuncArgumentSpec,
-    FuncSpec, FuncSpecData, LeafFunctionSpec, MapKeyFuncSpec, PkgSpec, PropSpec, PropSpecBuilder,
-    PropSpecKind, SchemaSpec, SchemaSpecData, SchemaVariantSpec, SchemaVariantSpecBuilder,
-    SchemaVariantSpecComponentType, SchemaVariantSpecData, SchemaVariantSpecPropRoot, SiPkg,
-    SiPkgKind, SiPropFuncSpec, SiPropFuncSpecKind, SocketSpec, SocketSpecData, SocketSpecKind,
-    SpecError, ValidationSpec, ValidationSpecKind,
+    ActionFuncSpec, AttrFuncInputSpec, AttrFuncInputSpecKind, ChangeSetSpec, ComponentTemplateSpec,
+    FuncArgumentSpec, FuncSpec, FuncSpecData, LeafFunctionSpec, MapKeyFuncSpec, PkgSpec, PropSpec,
+    PropSpecBuilder, PropSpecKind, SchemaSpec, SchemaSpecData, SchemaVariantSpec,
+    SchemaVariantSpecBuilder, SchemaVariantSpecComponentType, SchemaVariantSpecData,
+    SchemaVariantSpecPropRoot, SiPkg, SiPkgKind, SiPropFuncSpec, SiPropFuncSpecKind, SocketSpec,
+    SocketSpecData, SocketSpecKind, SpecError, ValidationSpec, ValidationSpecKind,
 };
 
 use crate::func::intrinsics::IntrinsicFunc;
@@ -19,10 +19,11 @@ use crate::{
     socket::SocketKind,
     validation::Validation,
     ActionPrototype, ActionPrototypeContext, AttributeContextBuilder, AttributePrototype,
-    AttributePrototypeArgument, AttributeReadContext, AttributeValue, ChangeSet, ComponentType,
-    DalContext, ExternalProvider, ExternalProviderId, Func, FuncId, InternalProvider,
-    InternalProviderId, LeafInputLocation, LeafKind, Prop, PropId, PropKind, Schema, SchemaId,
-    SchemaVariant, SchemaVariantError, SchemaVariantId, Socket, StandardModel, ValidationPrototype,
+    AttributePrototypeArgument, AttributeReadContext, AttributeValue, ChangeSet, ComponentTemplate,
+    ComponentTemplateId, ComponentType, DalContext, ExternalProvider, ExternalProviderId, Func,
+    FuncId, InternalProvider, InternalProviderId, LeafInputLocation, LeafKind, Prop, PropId,
+    PropKind, Schema, SchemaId, SchemaVariant, SchemaVariantError, SchemaVariantId, Socket,
+    StandardModel, ValidationPrototype,
 };
 use crate::{ChangeSetPk, Workspace};
 
@@ -92,6 +93,7 @@ pub struct PkgExporter {
     kind: SiPkgKind,
     created_by: String,
     schema_ids: Option<Vec<SchemaId>>,
+    component_template_ids: Option<Vec<ComponentTemplateId>>,
     func_map: FuncSpecMap,
     is_workspace_export: bool,
 }
@@ -131,6 +133,7 @@ impl PkgExporter {
             kind: SiPkgKind::Module,
             created_by: created_by.into(),
             schema_ids: Some(schema_ids),
+            component_template_ids: Some(Vec::new()),
             func_map: FuncSpecMap::new(),
             is_workspace_export: false,
         }
@@ -149,11 +152,24 @@ impl PkgExporter {
             kind: SiPkgKind::WorkspaceBackup,
             created_by: created_by.into(),
             schema_ids: None,
+            component_template_ids: None,
             func_map: FuncSpecMap::new(),
             is_workspace_export: true,
         }
     }
 
+    /// Includes the given [`ComponentTemplates`](ComponentTemplate) in a module. Workspace
+    /// backups always include every template on head.
+    pub fn include_component_templates(
+        &mut self,
+        component_template_ids: impl IntoIterator<Item = ComponentTemplateId>,
+    ) -> &mut Self {
+        if let Some(ids) = &mut self.component_template_ids {
+            ids.extend(component_template_ids);
+        }
+        self
+    }
+
     pub async fn export_as_bytes(&mut self, ctx: &DalContext) -> PkgResult<Vec<u8>> {
         match self.kind {
             SiPkgKind::Module => info!("Building module package"),
@@ -812,6 +828,27 @@ impl PkgExporter {
                 .set_prop_id(entry.prop_id)
                 .to_context()?;
 
+            // TODO: handle default values for complex types. We also cannot set default values for
+            // children of arrays and maps, at any depth (currently), since that requires tracking the
+            // key or index
+            let default_value_attribute_value = if matches!(
+                entry.builder.get_kind(),
+                Some(PropSpecKind::String)
+                    | Some(PropSpecKind::Number)
+                    | Some(PropSpecKind::Boolean)
+            ) && !entry.inside_map_or_array
+            {
+                AttributeValue::find_for_context(ctx, context.into()).await?
+            } else {
+                None
+            };
+
+            // An overridden value keeps the arguments of its prototype, so we export the function
+            // that computed it and let the default value stand in for the override
+            let overridden_func_id = default_value_attribute_value
+                .as_ref()
+                .and_then(|av| av.overridden_func_id().copied());
+
             if let Some(prototype) =
                 AttributePrototype::find_for_context_and_key(ctx, context, &None)
                     .await?
@@ -822,7 +859,12 @@ impl PkgExporter {
                 }
 
                 if let Some((func_unique_id, mut inputs)) = self
-                    .export_input_func_and_arguments(ctx, change_set_pk, &prototype)
+                    .export_func_and_arguments(
+                        ctx,
+                        change_set_pk,
+                        &prototype,
+                        overridden_func_id.unwrap_or(prototype.func_id()),
+                    )
                     .await?
                 {
                     entry.builder.has_data(true);
@@ -834,22 +876,15 @@ impl PkgExporter {
                 }
             }
 
-            // TODO: handle default values for complex types. We also cannot set default values for
-            // children of arrays and maps, at any depth (currently), since that requires tracking the
-            // key or index
-            if matches!(
-                entry.builder.get_kind(),
-                Some(PropSpecKind::String)
-                    | Some(PropSpecKind::Number)
-                    | Some(PropSpecKind::Boolean)
-            ) && !entry.inside_map_or_array
-            {
-                if let Some(av) = AttributeValue::find_for_context(ctx, context.into()).await? {
-                    if let Some(default_value) = av.get_value(ctx).await? {
-                        entry.builder.has_data(true);
-                        entry.builder.default_value(default_value);
-                    }
+            if let Some(av) = default_value_attribute_value {
+                if let Some(default_value) = av.get_value(ctx).await? {
+                    entry.builder.has_data(true);
+                    entry.builder.default_value(default_value);
                 }
+                entry
+                    .builder
+                    .overridden(av.is_overridden())
+                    .locked(av.locked());
             }
 
             for validation in self
@@ -887,8 +922,21 @@ impl PkgExporter {
         change_set_pk: Option<ChangeSetPk>,
         proto: &AttributePrototype,
     ) -> PkgResult<Option<(String, Vec<AttrFuncInputSpec>)>> {
-        let proto_func = Func::get_by_id(ctx, &proto.func_id()).await?.ok_or(
-            PkgError::MissingAttributePrototypeFunc(*proto.id(), proto.func_id()),
+        self.export_func_and_arguments(ctx, change_set_pk, proto, proto.func_id())
+            .await
+    }
+
+    /// Exports the arguments of the prototype for the given func, which is not the func of the
+    /// prototype when its value has been overridden.
+    async fn export_func_and_arguments(
+        &self,
+        ctx: &DalContext,
+        change_set_pk: Option<ChangeSetPk>,
+        proto: &AttributePrototype,
+        func_id: FuncId,
+    ) -> PkgResult<Option<(String, Vec<AttrFuncInputSpec>)>> {
+        let proto_func = Func::get_by_id(ctx, &func_id).await?.ok_or(
+            PkgError::MissingAttributePrototypeFunc(*proto.id(), func_id),
         )?;
 
         let apas: Vec<AttributePrototypeArgument> =
@@ -996,6 +1044,10 @@ impl PkgExporter {
                 continue;
             }
 
+            if !prototype.inputs().is_empty() {
+                return Err(PkgError::CrossFieldValidationNotExportable(*prototype.id()));
+            }
+
             let mut spec_builder = ValidationSpec::builder();
 
             if self.is_workspace_export {
@@ -1049,6 +1101,79 @@ impl PkgExporter {
                     Validation::StringIsHexColor { .. } => {
                         spec_builder.kind(ValidationSpecKind::StringIsHexColor);
                     }
+                    Validation::ArrayItemsAreUnique { .. } => {
+                        spec_builder.kind(ValidationSpecKind::ArrayItemsAreUnique);
+                    }
+                    Validation::ArrayLengthIsBetween {
+                        lower_bound,
+                        upper_bound,
+                        ..
+                    } => {
+                        spec_builder.kind(ValidationSpecKind::ArrayLengthIsBetween);
+                        if let Some(lower_bound) = lower_bound {
+                            spec_builder.lower_bound(lower_bound);
+                        }
+                        if let Some(upper_bound) = upper_bound {
+                            spec_builder.upper_bound(upper_bound);
+                        }
+                    }
+                    Validation::IntegerIsInRange {
+                        lower_bound,
+                        upper_bound,
+                        ..
+                    } => {
+                        spec_builder.kind(ValidationSpecKind::IntegerIsInRange);
+                        if let Some(lower_bound) = lower_bound {
+                            spec_builder.lower_bound(lower_bound);
+                        }
+                        if let Some(upper_bound) = upper_bound {
+                            spec_builder.upper_bound(upper_bound);
+                        }
+                    }
+                    Validation::MapKeysMatchRegex { pattern, .. } => {
+                        spec_builder.kind(ValidationSpecKind::MapKeysMatchRegex);
+                        spec_builder.expected_string(pattern);
+                    }
+                    Validation::ObjectMatchesJsonSchema { schema, .. } => {
+                        spec_builder.kind(ValidationSpecKind::ObjectMatchesJsonSchema);
+                        spec_builder.json_schema(schema);
+                    }
+                    Validation::StringIsValidArn { service, .. } => {
+                        spec_builder.kind(ValidationSpecKind::StringIsValidArn);
+                        if let Some(service) = service {
+                            spec_builder.expected_string(service);
+                        }
+                    }
+                    Validation::StringIsValidCidr { .. } => {
+                        spec_builder.kind(ValidationSpecKind::StringIsValidCidr);
+                    }
+                    Validation::StringIsValidHostname { .. } => {
+                        spec_builder.kind(ValidationSpecKind::StringIsValidHostname);
+                    }
+                    Validation::StringIsValidUrl { .. } => {
+                        spec_builder.kind(ValidationSpecKind::StringIsValidUrl);
+                    }
+                    Validation::StringLengthIsBetween {
+                        lower_bound,
+                        upper_bound,
+                        ..
+                    } => {
+                        spec_builder.kind(ValidationSpecKind::StringLengthIsBetween);
+                        if let Some(lower_bound) = lower_bound {
+                            spec_builder.lower_bound(lower_bound);
+                        }
+                        if let Some(upper_bound) = upper_bound {
+                            spec_builder.upper_bound(upper_bound);
+                        }
+                    }
+                    Validation::StringMatchesRegex { pattern, .. } => {
+                        spec_builder.kind(ValidationSpecKind::StringMatchesRegex);
+                        spec_builder.expected_string(pattern);
+                    }
+                    Validation::StringCidrIsWithinInput { .. }
+                    | Validation::ValueIsRequiredWhenInputEquals { .. } => {
+                        return Err(PkgError::CrossFieldValidationNotExportable(*prototype.id()));
+                    }
                 },
                 None => {
                     let func_spec = self
@@ -1232,6 +1357,15 @@ impl PkgExporter {
     }
 
     pub async fn export(&mut self, ctx: &DalContext) -> PkgResult<SiPkg> {
+        let spec = self.export_as_spec(ctx).await?;
+        let pkg = SiPkg::load_from_spec(spec)?;
+
+        Ok(pkg)
+    }
+
+    /// Builds the [`PkgSpec`] for the package without loading it into a [`SiPkg`], so that it
+    /// can be inspected (for example, linted) before it is written out.
+    pub async fn export_as_spec(&mut self, ctx: &DalContext) -> PkgResult<PkgSpec> {
         let mut pkg_spec_builder = PkgSpec::builder();
         pkg_spec_builder
             .name(&self.name)
@@ -1285,10 +1419,50 @@ impl PkgExporter {
             }
         }
 
-        let spec = pkg_spec_builder.build()?;
-        let pkg = SiPkg::load_from_spec(spec)?;
+        for component_template in self.export_component_templates(ctx).await? {
+            pkg_spec_builder.component_template(component_template);
+        }
 
-        Ok(pkg)
+        Ok(pkg_spec_builder.build()?)
+    }
+
+    async fn export_component_templates(
+        &self,
+        ctx: &DalContext,
+    ) -> PkgResult<Vec<ComponentTemplateSpec>> {
+        let component_templates = match &self.component_template_ids {
+            Some(component_template_ids) => {
+                let mut component_templates = Vec::with_capacity(component_template_ids.len());
+                for component_template_id in component_template_ids {
+                    component_templates.push(
+                        ComponentTemplate::get_by_id(ctx, component_template_id)
+                            .await?
+                            .ok_or(PkgError::ComponentTemplateNotFound(*component_template_id))?,
+                    );
+                }
+                component_templates
+            }
+            None => {
+                let head_ctx = ctx.clone_with_new_visibility(ctx.visibility().to_head());
+                ComponentTemplate::list(&head_ctx).await?
+            }
+        };
+
+        let mut specs = Vec::with_capacity(component_templates.len());
+        for component_template in component_templates {
+            let mut builder = ComponentTemplateSpec::builder();
+            if let Some(description) = component_template.description() {
+                builder.description(description);
+            }
+            specs.push(
+                builder
+                    .name(component_template.name())
+                    .definition(serde_json::to_value(component_template.definition())?)
+                    .build()?,
+            );
+        }
+
+        Ok(specs)
     }
 }
 
@@ -3,10 +3,10 @@ use telemetry::prelude::*;
 use tokio::sync::Mutex;
 
 use si_pkg::{
-    SchemaVariantSpecPropRoot, SiPkg, SiPkgActionFunc, SiPkgAttrFuncInputView, SiPkgError,
-    SiPkgFunc, SiPkgFuncArgument, SiPkgFuncData, SiPkgKind, SiPkgLeafFunction, SiPkgMetadata,
-    SiPkgProp, SiPkgPropData, SiPkgSchema, SiPkgSchemaData, SiPkgSchemaVariant, SiPkgSocket,
-    SiPkgSocketData, SocketSpecKind, ValidationSpec,
+    SchemaVariantSpecPropRoot, SiPkg, SiPkgActionFunc, SiPkgAttrFuncInputView,
+    SiPkgComponentTemplate, SiPkgError, SiPkgFunc, SiPkgFuncArgument, SiPkgFuncData, SiPkgKind,
+    SiPkgLeafFunction, SiPkgMetadata, SiPkgProp, SiPkgPropData, SiPkgSchema, SiPkgSchemaData,
+    SiPkgSchemaVariant, SiPkgSocket, SiPkgSocketData, SocketSpecKind, ValidationSpec,
 };
 
 use crate::{
@@ -30,11 +30,11 @@ use crate::{
     validation::{Validation, ValidationKind},
     ActionKind, ActionPrototype, ActionPrototypeContext, AttributeContextBuilder,
     AttributePrototype, AttributePrototypeArgument, AttributePrototypeId, AttributeReadContext,
-    AttributeValue, AttributeValueError, ChangeSet, ChangeSetPk, DalContext, ExternalProvider,
-    ExternalProviderId, Func, FuncArgument, FuncError, FuncId, InternalProvider, LeafKind, Prop,
-    PropId, PropKind, Schema, SchemaId, SchemaVariant, SchemaVariantError, SchemaVariantId, Socket,
-    StandardModel, Tenancy, ValidationPrototype, ValidationPrototypeContext, Workspace,
-    WorkspacePk,
+    AttributeValue, AttributeValueError, ChangeSet, ChangeSetPk, ComponentTemplate,
+    ComponentTemplateDefinition, DalContext, ExternalProvider, ExternalProviderId, Func,
+    FuncArgument, FuncError, FuncId, InternalProvider, LeafKind, Prop, PropId, PropKind, Schema,
+    SchemaId, SchemaVariant, SchemaVariantError, SchemaVariantId, Socket, StandardModel, Tenancy,
+    ValidationPrototype, ValidationPrototypeContext, Workspace, WorkspacePk,
 };
 
 use super::{PkgError, PkgResult};
@@ -239,10 +239,9 @@ pub async fn import_pkg_from_pkg(
         )
     };
 
-    let mut change_set_things = ChangeSetThingMap::new();
-
     match metadata.kind() {
         SiPkgKind::Module => {
+            let mut change_set_things = ChangeSetThingMap::new();
             let installed_schema_variant_ids = import_change_set(
                 ctx,
                 None,
@@ -254,73 +253,156 @@ pub async fn import_pkg_from_pkg(
                 &options,
             )
             .await?;
+            import_component_templates(ctx, &pkg.component_templates()?).await?;
 
             Ok((installed_pkg_id, installed_schema_variant_ids))
         }
         SiPkgKind::WorkspaceBackup => {
-            let mut ctx = ctx.clone_with_new_visibility(ctx.visibility().to_head());
-
-            let workspace_pk = WorkspacePk::from_str(
-                metadata
-                    .workspace_pk()
-                    .ok_or(PkgError::WorkspacePkNotInBackup)?,
-            )?;
-            let workspace_name = metadata
-                .workspace_name()
-                .ok_or(PkgError::WorkspaceNameNotInBackup)?;
-            let default_change_set_name = metadata.default_change_set().unwrap_or("head");
-
-            Workspace::clear_or_create_workspace(&mut ctx, workspace_pk, workspace_name).await?;
-
-            ctx.update_tenancy(Tenancy::new(workspace_pk));
-
-            let change_sets = pkg.change_sets()?;
-            let default_change_set = change_sets
-                .iter()
-                .find(|cs| cs.name() == default_change_set_name)
-                .ok_or(PkgError::WorkspaceBackupNoDefaultChangeSet(
-                    default_change_set_name.into(),
-                ))?;
+            import_workspace_backup(ctx, pkg, &metadata, installed_pkg_id, &options).await?;
 
-            import_change_set(
-                &ctx,
-                Some(ChangeSetPk::NONE),
-                &metadata,
-                &default_change_set.funcs()?,
-                &default_change_set.schemas()?,
-                installed_pkg_id,
-                &mut change_set_things,
-                &options,
-            )
-            .await?;
+            Ok((None, vec![]))
+        }
+    }
+}
 
-            for change_set in change_sets {
-                if change_set.name() == default_change_set_name {
-                    continue;
-                }
+/// Rebuilds the workspace a backup was taken from, including each of its change sets, as it was
+/// at the point the backup was taken. Any existing contents of the workspace are cleared first.
+///
+/// The backup must have been taken from the workspace of the provided context, so that one
+/// workspace can never be overwritten with the backup of another.
+pub async fn restore_workspace_backup(ctx: &DalContext, pkg: &SiPkg) -> PkgResult<WorkspacePk> {
+    let metadata = pkg.metadata()?;
+    if metadata.kind() != SiPkgKind::WorkspaceBackup {
+        return Err(PkgError::NotAWorkspaceBackup(metadata.name().to_owned()));
+    }
+
+    let backup_workspace_pk = WorkspacePk::from_str(
+        metadata
+            .workspace_pk()
+            .ok_or(PkgError::WorkspacePkNotInBackup)?,
+    )?;
+    let workspace_pk = ctx.tenancy().workspace_pk().unwrap_or(WorkspacePk::NONE);
+    if backup_workspace_pk != workspace_pk {
+        return Err(PkgError::WorkspaceBackupWorkspaceMismatch(
+            workspace_pk,
+            backup_workspace_pk,
+        ));
+    }
+
+    // Restoring the same backup point more than once is expected, so the restore is not recorded
+    // as an installed package
+    let options = ImportOptions {
+        no_record: true,
+        ..Default::default()
+    };
+    import_workspace_backup(ctx, pkg, &metadata, None, &options).await?;
+
+    Ok(workspace_pk)
+}
+
+async fn import_workspace_backup(
+    ctx: &DalContext,
+    pkg: &SiPkg,
+    metadata: &SiPkgMetadata,
+    installed_pkg_id: Option<InstalledPkgId>,
+    options: &ImportOptions,
+) -> PkgResult<()> {
+    let mut change_set_things = ChangeSetThingMap::new();
+    let mut ctx = ctx.clone_with_new_visibility(ctx.visibility().to_head());
+
+    let workspace_pk = WorkspacePk::from_str(
+        metadata
+            .workspace_pk()
+            .ok_or(PkgError::WorkspacePkNotInBackup)?,
+    )?;
+    let workspace_name = metadata
+        .workspace_name()
+        .ok_or(PkgError::WorkspaceNameNotInBackup)?;
+    let default_change_set_name = metadata.default_change_set().unwrap_or("head");
+
+    Workspace::clear_or_create_workspace(&mut ctx, workspace_pk, workspace_name).await?;
+
+    ctx.update_tenancy(Tenancy::new(workspace_pk));
+
+    let change_sets = pkg.change_sets()?;
+    let default_change_set = change_sets
+        .iter()
+        .find(|cs| cs.name() == default_change_set_name)
+        .ok_or(PkgError::WorkspaceBackupNoDefaultChangeSet(
+            default_change_set_name.into(),
+        ))?;
+
+    import_change_set(
+        &ctx,
+        Some(ChangeSetPk::NONE),
+        metadata,
+        &default_change_set.funcs()?,
+        &default_change_set.schemas()?,
+        installed_pkg_id,
+        &mut change_set_things,
+        options,
+    )
+    .await?;
+
+    import_component_templates(&ctx, &pkg.component_templates()?).await?;
+
+    for change_set in change_sets {
+        if change_set.name() == default_change_set_name {
+            continue;
+        }
+
+        // Revert to head to create new change set
+        let ctx = ctx.clone_with_new_visibility(ctx.visibility().to_head());
+        let new_cs = ChangeSet::new(&ctx, change_set.name(), None).await?;
+        // Switch to new change set visibility
+        let ctx = ctx.clone_with_new_visibility(ctx.visibility().to_change_set(new_cs.pk));
 
-                // Revert to head to create new change set
-                let ctx = ctx.clone_with_new_visibility(ctx.visibility().to_head());
-                let new_cs = ChangeSet::new(&ctx, change_set.name(), None).await?;
-                // Switch to new change set visibility
-                let ctx = ctx.clone_with_new_visibility(ctx.visibility().to_change_set(new_cs.pk));
-
-                import_change_set(
-                    &ctx,
-                    Some(new_cs.pk),
-                    &metadata,
-                    &change_set.funcs()?,
-                    &change_set.schemas()?,
-                    installed_pkg_id,
-                    &mut change_set_things,
-                    &options,
+        import_change_set(
+            &ctx,
+            Some(new_cs.pk),
+            metadata,
+            &change_set.funcs()?,
+            &change_set.schemas()?,
+            installed_pkg_id,
+            &mut change_set_things,
+            options,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Creates the [`ComponentTemplates`](ComponentTemplate) of a package, replacing the definition
+/// of any existing template with the same name.
+async fn import_component_templates(
+    ctx: &DalContext,
+    component_templates: &[SiPkgComponentTemplate<'_>],
+) -> PkgResult<()> {
+    for template_spec in component_templates {
+        let definition: ComponentTemplateDefinition =
+            serde_json::from_value(template_spec.definition().to_owned())?;
+
+        match ComponentTemplate::find_by_name(ctx, template_spec.name()).await? {
+            Some(mut component_template) => {
+                component_template
+                    .set_description(ctx, template_spec.description())
+                    .await?;
+                component_template.set_definition(ctx, definition).await?;
+            }
+            None => {
+                ComponentTemplate::new(
+                    ctx,
+                    template_spec.name(),
+                    template_spec.description(),
+                    definition,
                 )
                 .await?;
             }
-
-            Ok((None, vec![]))
         }
     }
+
+    Ok(())
 }
 
 pub async fn import_pkg(ctx: &DalContext, pkg_file_path: impl AsRef<Path>) -> PkgResult<SiPkg> {
@@ -907,7 +989,9 @@ struct PropVisitContext<'a> {
     pub schema_variant_id: SchemaVariantId,
     pub attr_funcs: Mutex<Vec<AttrFuncInfo>>,
     pub default_values: Mutex<Vec<DefaultValueInfo>>,
+    pub locked_props: Mutex<Vec<PropId>>,
     pub map_key_funcs: Mutex<Vec<(String, AttrFuncInfo)>>,
+    pub overridden_values: Mutex<Vec<DefaultValueInfo>>,
     pub validations: Mutex<Vec<(PropId, ValidationSpec)>>,
     pub change_set_pk: Option<ChangeSetPk>,
 }
@@ -1217,7 +1301,9 @@ async fn import_action_func(
 struct CreatePropsSideEffects {
     attr_funcs: Vec<AttrFuncInfo>,
     default_values: Vec<DefaultValueInfo>,
+    locked_props: Vec<PropId>,
     map_key_funcs: Vec<(String, AttrFuncInfo)>,
+    overridden_values: Vec<DefaultValueInfo>,
     validations: Vec<(PropId, ValidationSpec)>,
 }
 
@@ -1236,7 +1322,9 @@ impl Extend<CreatePropsSideEffects> for CreatePropsSideEffects {
         for element in iter {
             self.attr_funcs.extend(element.attr_funcs);
             self.default_values.extend(element.default_values);
+            self.locked_props.extend(element.locked_props);
             self.map_key_funcs.extend(element.map_key_funcs);
+            self.overridden_values.extend(element.overridden_values);
             self.validations.extend(element.validations);
         }
     }
@@ -1255,7 +1343,9 @@ async fn create_props(
         schema_variant_id,
         attr_funcs: Mutex::new(vec![]),
         default_values: Mutex::new(vec![]),
+        locked_props: Mutex::new(vec![]),
         map_key_funcs: Mutex::new(vec![]),
+        overridden_values: Mutex::new(vec![]),
         validations: Mutex::new(vec![]),
         change_set_pk,
     };
@@ -1269,7 +1359,9 @@ async fn create_props(
     Ok(CreatePropsSideEffects {
         attr_funcs: context.attr_funcs.into_inner(),
         default_values: context.default_values.into_inner(),
+        locked_props: context.locked_props.into_inner(),
         map_key_funcs: context.map_key_funcs.into_inner(),
+        overridden_values: context.overridden_values.into_inner(),
         validations: context.validations.into_inner(),
     })
 }
@@ -1612,6 +1704,22 @@ async fn import_schema_variant(
             .await?;
         }
 
+        // Overridden values replace what the attribute functions compute, so they have to be set
+        // once the functions are in place (which is also what marks them as overridden)
+        for overridden_value_info in side_effects.overridden_values {
+            set_default_value(ctx, overridden_value_info).await?;
+        }
+
+        for prop_id in side_effects.locked_props {
+            let attribute_read_context = AttributeReadContext::default_with_prop(prop_id);
+            let mut attribute_value = AttributeValue::find_for_context(ctx, attribute_read_context)
+                .await?
+                .ok_or(AttributeValueError::NotFoundForReadContext(
+                    attribute_read_context,
+                ))?;
+            attribute_value.set_locked(ctx, true).await?;
+        }
+
         for (prop_id, validation_spec) in side_effects.validations {
             import_prop_validation(
                 ctx,
@@ -2014,10 +2122,13 @@ async fn create_validation(
     schema_variant_id: SchemaVariantId,
 ) -> PkgResult<ValidationPrototype> {
     let (validation_func_id, validation_args) = match validation_kind {
-        ValidationKind::Builtin(validation) => (
-            builtin_func_id,
-            serde_json::to_value(FuncBackendValidationArgs::new(validation))?,
-        ),
+        ValidationKind::Builtin(validation) => {
+            validation.ensure_supported()?;
+            (
+                builtin_func_id,
+                serde_json::to_value(FuncBackendValidationArgs::new(validation))?,
+            )
+        }
 
         ValidationKind::Custom(func_id) => (func_id, serde_json::json!(null)),
     };
@@ -2122,6 +2233,69 @@ async fn import_prop_validation(
         ValidationSpec::StringIsValidIpAddr { .. } => {
             ValidationKind::Builtin(Validation::StringIsValidIpAddr { value: None })
         }
+        ValidationSpec::ArrayItemsAreUnique { .. } => {
+            ValidationKind::Builtin(Validation::ArrayItemsAreUnique { value: None })
+        }
+        ValidationSpec::ArrayLengthIsBetween {
+            lower_bound,
+            upper_bound,
+            ..
+        } => ValidationKind::Builtin(Validation::ArrayLengthIsBetween {
+            value: None,
+            lower_bound: *lower_bound,
+            upper_bound: *upper_bound,
+        }),
+        ValidationSpec::IntegerIsInRange {
+            lower_bound,
+            upper_bound,
+            ..
+        } => ValidationKind::Builtin(Validation::IntegerIsInRange {
+            value: None,
+            lower_bound: *lower_bound,
+            upper_bound: *upper_bound,
+        }),
+        ValidationSpec::MapKeysMatchRegex { pattern, .. } => {
+            ValidationKind::Builtin(Validation::MapKeysMatchRegex {
+                value: None,
+                pattern: pattern.to_owned(),
+            })
+        }
+        ValidationSpec::ObjectMatchesJsonSchema { schema, .. } => {
+            ValidationKind::Builtin(Validation::ObjectMatchesJsonSchema {
+                value: None,
+                schema: schema.to_owned(),
+            })
+        }
+        ValidationSpec::StringIsValidArn { service, .. } => {
+            ValidationKind::Builtin(Validation::StringIsValidArn {
+                value: None,
+                service: service.to_owned(),
+            })
+        }
+        ValidationSpec::StringIsValidCidr { .. } => {
+            ValidationKind::Builtin(Validation::StringIsValidCidr { value: None })
+        }
+        ValidationSpec::StringIsValidHostname { .. } => {
+            ValidationKind::Builtin(Validation::StringIsValidHostname { value: None })
+        }
+        ValidationSpec::StringIsValidUrl { .. } => {
+            ValidationKind::Builtin(Validation::StringIsValidUrl { value: None })
+        }
+        ValidationSpec::StringLengthIsBetween {
+            lower_bound,
+            upper_bound,
+            ..
+        } => ValidationKind::Builtin(Validation::StringLengthIsBetween {
+            value: None,
+            lower_bound: *lower_bound,
+            upper_bound: *upper_bound,
+        }),
+        ValidationSpec::StringMatchesRegex { pattern, .. } => {
+            ValidationKind::Builtin(Validation::StringMatchesRegex {
+                value: None,
+                pattern: pattern.to_owned(),
+            })
+        }
         ValidationSpec::CustomValidation { func_unique_id, .. } => {
             ValidationKind::Custom(match thing_map.get(None, func_unique_id.as_str()) {
                 Some(Thing::Func(func)) => *func.id(),
@@ -2324,7 +2498,15 @@ async fn create_prop(
             // Default values for complex types are not yet supported in packages
             _ => None,
         } {
-            ctx.default_values.lock().await.push(default_value_info);
+            if data.overridden {
+                ctx.overridden_values.lock().await.push(default_value_info);
+            } else {
+                ctx.default_values.lock().await.push(default_value_info);
+            }
+        }
+
+        if data.locked {
+            ctx.locked_props.lock().await.push(prop_id);
         }
     }
 
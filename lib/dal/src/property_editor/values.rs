@@ -77,6 +77,8 @@ impl PropertyEditorValues {
                         .and_then(|f| f.value().cloned())
                         .unwrap_or(Value::Null),
                     is_from_external_source,
+                    is_overridden: work.attribute_value.is_overridden(),
+                    is_locked: work.attribute_value.locked(),
                 },
             );
             if let Some(parent_id) = work.parent_attribute_value_id {
@@ -109,6 +111,10 @@ pub struct PropertyEditorValue {
     pub key: Option<String>,
     value: Value,
     is_from_external_source: bool,
+    /// Whether the value was set manually in place of the one its function computed.
+    is_overridden: bool,
+    /// Whether the value is kept as is when the values it depends on change.
+    is_locked: bool,
 }
 
 impl PropertyEditorValue {
@@ -124,6 +130,14 @@ impl PropertyEditorValue {
         self.prop_id.into()
     }
 
+    pub fn is_overridden(&self) -> bool {
+        self.is_overridden
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.is_locked
+    }
+
     /// Returns the [`Prop`](crate::Prop) corresponding to the "prop_id" field.
     pub async fn prop(&self, ctx: &DalContext) -> PropertyEditorResult<Prop> {
         let prop = Prop::get_by_id(ctx, &self.prop_id.into())
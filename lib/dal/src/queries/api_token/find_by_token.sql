@@ -0,0 +1,3 @@
+SELECT row_to_json(api_tokens.*) AS object
+FROM api_tokens
+WHERE api_tokens.token_hash = $1
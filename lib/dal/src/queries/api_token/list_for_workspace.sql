@@ -0,0 +1,4 @@
+SELECT row_to_json(api_tokens.*) AS object
+FROM api_tokens
+WHERE api_tokens.workspace_pk = $1
+ORDER BY api_tokens.created_at DESC
@@ -0,0 +1,14 @@
+SELECT row_to_json(history_events.*) AS object
+FROM history_events
+WHERE history_events.tenancy_workspace_pk = $1
+  AND ($2::jsonb IS NULL OR history_events.actor = $2::jsonb)
+  AND ($3::text IS NULL OR history_events.label LIKE $3::text)
+  AND ($4::timestamp with time zone IS NULL OR history_events.created_at >= $4)
+  AND ($5::timestamp with time zone IS NULL OR history_events.created_at < $5)
+  AND ($6::text IS NULL
+    OR jsonb_path_exists(history_events.data, '$.** ? (@ == $id)', jsonb_build_object('id', $6::text)))
+  AND ($7::ident IS NULL
+    OR (history_events.created_at, history_events.pk) <
+       (SELECT cursor.created_at, cursor.pk FROM history_events AS cursor WHERE cursor.pk = $7))
+ORDER BY history_events.created_at DESC, history_events.pk DESC
+LIMIT $8
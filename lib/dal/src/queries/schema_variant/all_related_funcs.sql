@@ -23,6 +23,33 @@
 
 UNION ALL
 
+-- funcs computing values before they were overridden, which can be reset to them
+(SELECT DISTINCT ON (id) funcs.id as id, row_to_json(funcs.*) AS object
+ FROM props_v1($1, $2) as props
+          JOIN attribute_values_v1($1, $2) av
+               ON av.attribute_context_prop_id = props.id
+          JOIN funcs_v1($1, $2) funcs
+               ON funcs.id = av.overridden_func_id
+ WHERE props.id in
+       (WITH RECURSIVE recursive_props
+                           AS
+                           (SELECT root_prop_id AS prop_id
+                            FROM schema_variants_v1($1, $2) AS schema_variants
+                            WHERE schema_variants.id = $3
+                            UNION ALL
+                            SELECT pbp.object_id as prop_id
+                            FROM prop_belongs_to_prop_v1($1, $2) AS pbp
+                                     JOIN recursive_props
+                                          ON pbp.belongs_to_id = recursive_props.prop_id)
+        SELECT prop_id
+        FROM recursive_props)
+   AND funcs.code_sha256 != '0'
+   AND av.attribute_context_internal_provider_id = ident_nil_v1()
+   AND av.attribute_context_external_provider_id = ident_nil_v1()
+   AND av.attribute_context_component_id = ident_nil_v1())
+
+UNION ALL
+
 (SELECT DISTINCT ON (id) funcs.id as id, row_to_json(funcs.*) AS object
  FROM internal_providers_v1($1, $2) ip
           JOIN attribute_prototypes_v1($1, $2) ap
@@ -0,0 +1,6 @@
+SELECT row_to_json(validation_prototypes.*) AS object
+FROM validation_prototypes_v1($1, $2) as validation_prototypes
+WHERE validation_prototypes.inputs @> jsonb_build_array(
+    jsonb_build_object('kind', 'inputSocket', 'internalProviderId', $3::ident::text)
+)
+ORDER BY validation_prototypes.id;
@@ -2,4 +2,11 @@ select row_to_json(validation_resolvers.*) as object
 from validation_resolvers_v1($1, $2) as validation_resolvers
 where attribute_value_id = $3
   and validation_func_id = $4
+  -- Resolvers for cross-field validations are found by their prototype instead, since several of
+  -- them can share a validation func and an attribute value.
+  and validation_prototype_id not in (
+    select id
+    from validation_prototypes_v1($1, $2) as validation_prototypes
+    where validation_prototypes.inputs != '[]'::jsonb
+  )
 order by validation_resolvers.id desc
\ No newline at end of file
@@ -0,0 +1,3 @@
+SELECT row_to_json(webhooks.*) AS object
+FROM webhooks
+WHERE webhooks.pk = $1
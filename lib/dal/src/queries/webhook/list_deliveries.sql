@@ -0,0 +1,5 @@
+SELECT row_to_json(webhook_deliveries.*) AS object
+FROM webhook_deliveries
+WHERE webhook_deliveries.webhook_pk = $1
+ORDER BY webhook_deliveries.created_at DESC
+LIMIT $2
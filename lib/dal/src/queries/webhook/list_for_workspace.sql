@@ -0,0 +1,4 @@
+SELECT row_to_json(webhooks.*) AS object
+FROM webhooks
+WHERE webhooks.workspace_pk = $1
+ORDER BY webhooks.created_at DESC
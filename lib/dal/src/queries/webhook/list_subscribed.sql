@@ -0,0 +1,5 @@
+SELECT row_to_json(webhooks.*) AS object
+FROM webhooks
+WHERE webhooks.workspace_pk = $1
+  AND webhooks.enabled
+  AND webhooks.event_kinds ? $2
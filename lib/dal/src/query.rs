@@ -0,0 +1,691 @@
+//! A small query language for finding [`Components`](crate::Component) without exporting the
+//! workspace. Queries are evaluated against the [`AttributeValues`](crate::AttributeValue) of
+//! each [`Component`] as seen from the [`Visibility`](crate::Visibility) of the
+//! [`DalContext`], so the same query can give different answers on head and in a
+//! [`ChangeSet`](crate::ChangeSet).
+//!
+//! A query can either be built directly as a [`ComponentFilter`] or parsed from text:
+//!
+//! ```text
+//! schema = "EC2 Instance"                   the name of the schema
+//! variant = "v0"                            the name of the schema variant
+//! domain/InstanceType startsWith "t2"       prop paths are relative to "/root"
+//! si/name != "web"                          =, !=, <, <=, >, >=
+//! domain/tags contains "Name"               contains, startsWith, endsWith
+//! domain/KeyName exists                     set and not "null"
+//! qualification = failure                   success, warning, failure
+//! resource = error                          ok, warning, error
+//! resource exists                           a resource has been created
+//! change = added                            added, modified, unmodified
+//! connectedTo "01H9B0D6AVBKZGEEKGWXM3J8FF"  connected to the component with the given id
+//! tag "team" = "platform"                   tags, including those inherited from frames
+//! ```
+//!
+//! Filters can be combined with `and`, `or`, `not` and parentheses, in that order of precedence
+//! from tightest to loosest, e.g. `schema = "EC2 Instance" and not qualification = success`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use telemetry::prelude::*;
+use thiserror::Error;
+use veritech_client::ResourceStatus;
+
+use crate::change_status::{ChangeStatus, ChangeStatusError, ComponentChangeStatus};
+use crate::component::view::ComponentViewError;
+use crate::func::backend::js_action::ActionRunResult;
+use crate::qualification::QualificationSubCheckStatus;
+use crate::{
+    Component, ComponentError, ComponentId, ComponentTag, ComponentTagError, ComponentView,
+    DalContext, Edge, EdgeError, StandardModel, StandardModelError,
+};
+
+mod parser;
+
+/// The longest query, in characters, that will be parsed.
+const MAX_SOURCE_LENGTH: usize = 16 * 1024;
+/// How deeply filters may be nested.
+const MAX_DEPTH: usize = 64;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum QueryError {
+    #[error("change status error: {0}")]
+    ChangeStatus(#[from] ChangeStatusError),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("component tag error: {0}")]
+    ComponentTag(#[from] ComponentTagError),
+    #[error("component view error: {0}")]
+    ComponentView(#[from] ComponentViewError),
+    #[error("edge error: {0}")]
+    Edge(#[from] EdgeError),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("syntax error at character {position}: {message}")]
+    Syntax { position: usize, message: String },
+    #[error("query is nested more than {MAX_DEPTH} levels deep")]
+    TooDeep,
+    #[error("query is longer than {MAX_SOURCE_LENGTH} characters")]
+    TooLong,
+}
+
+pub type QueryResult<T> = Result<T, QueryError>;
+
+/// A predicate over a single [`Component`].
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ComponentFilter {
+    /// Matches when every one of the filters matches.
+    All { filters: Vec<ComponentFilter> },
+    /// Matches when at least one of the filters matches.
+    Any { filters: Vec<ComponentFilter> },
+    /// Matches on how the [`Component`] was changed in the current
+    /// [`ChangeSet`](crate::ChangeSet). Everything is unmodified on head.
+    ChangeStatus { status: ChangeStatus },
+    /// Matches when an [`Edge`] connects the [`Component`] to the given one, in either direction.
+    #[serde(rename_all = "camelCase")]
+    ConnectedTo { component_id: ComponentId },
+    /// Matches when the filter does not.
+    Not { filter: Box<ComponentFilter> },
+    /// Matches on the value found at the path, relative to "/root", such as "domain/region".
+    Prop {
+        path: String,
+        predicate: PropPredicate,
+    },
+    /// Matches on the overall status of the [`Component's`](Component) qualifications: a
+    /// failure if any of them failed, otherwise a warning if any of them warned, otherwise a
+    /// success. This is the same rule as the [`QualificationSummary`](crate::qualification::QualificationSummary).
+    QualificationStatus { status: QualificationSubCheckStatus },
+    /// Matches when the [`Component`] has a resource.
+    ResourceExists,
+    /// Matches on the status of the [`Component's`](Component) resource. A [`Component`] without
+    /// a resource has an "ok" status.
+    ResourceStatus { status: ResourceStatus },
+    /// Matches on the name of the [`Component's`](Component) [`Schema`](crate::Schema).
+    Schema { name: String },
+    /// Matches on the name of the [`Component's`](Component)
+    /// [`SchemaVariant`](crate::SchemaVariant).
+    SchemaVariant { name: String },
+    /// Matches on the value of the [`Component's`](Component) tag with the given key, whether
+    /// it was set on the [`Component`] or inherited from a frame.
+    Tag {
+        key: String,
+        predicate: PropPredicate,
+    },
+}
+
+/// A predicate over the value found at a prop path. A missing value is treated as "null".
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "op", content = "value", rename_all = "camelCase")]
+pub enum PropPredicate {
+    /// Substrings of strings, elements of arrays and keys of objects.
+    Contains(Value),
+    EndsWith(String),
+    Equals(Value),
+    Exists,
+    GreaterThan(Value),
+    GreaterThanOrEqual(Value),
+    LessThan(Value),
+    LessThanOrEqual(Value),
+    NotEquals(Value),
+    StartsWith(String),
+}
+
+/// A [`Component`] that matched a [`ComponentFilter`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentQueryMatch {
+    pub component_id: ComponentId,
+    /// The values of the prop paths used by the filter and of any additionally selected ones.
+    pub values: BTreeMap<String, Value>,
+}
+
+impl ComponentFilter {
+    /// Parse a [`ComponentFilter`] from the query language described in the
+    /// [module documentation](self).
+    pub fn parse(source: &str) -> QueryResult<Self> {
+        parser::parse(source)
+    }
+
+    /// Find every [`Component`] the filter matches. Alongside the values of the prop paths used
+    /// by the filter, the values of the `select` prop paths are returned for each match.
+    #[instrument(skip_all)]
+    pub async fn execute(
+        &self,
+        ctx: &DalContext,
+        select: &[String],
+    ) -> QueryResult<Vec<ComponentQueryMatch>> {
+        let mut facets = Facets::default();
+        self.collect_facets(&mut facets);
+
+        let mut paths = Vec::new();
+        self.collect_prop_paths(&mut paths);
+        for path in select {
+            if !paths.contains(path) {
+                paths.push(path.clone());
+            }
+        }
+        facets.properties |= !paths.is_empty();
+
+        let change_statuses = if facets.change_status && !ctx.visibility().is_head() {
+            let mut change_statuses = HashMap::new();
+            // Components which were added in the change set count as added, even if they were
+            // modified afterwards.
+            for group in ComponentChangeStatus::list_modified(ctx)
+                .await?
+                .into_iter()
+                .chain(ComponentChangeStatus::list_added(ctx).await?)
+            {
+                change_statuses.insert(group.component_id, group.component_status);
+            }
+            change_statuses
+        } else {
+            HashMap::new()
+        };
+
+        let mut matches = Vec::new();
+        for component in Component::list(ctx).await? {
+            let facts = ComponentFacts::load(ctx, &component, &facets, &change_statuses).await?;
+            if !self.matches(&facts) {
+                continue;
+            }
+
+            let values = paths
+                .iter()
+                .map(|path| {
+                    (
+                        path.clone(),
+                        facts.prop(path).cloned().unwrap_or(Value::Null),
+                    )
+                })
+                .collect();
+            matches.push(ComponentQueryMatch {
+                component_id: *component.id(),
+                values,
+            });
+        }
+        Ok(matches)
+    }
+
+    fn matches(&self, facts: &ComponentFacts) -> bool {
+        match self {
+            Self::All { filters } => filters.iter().all(|filter| filter.matches(facts)),
+            Self::Any { filters } => filters.iter().any(|filter| filter.matches(facts)),
+            Self::ChangeStatus { status } => facts.change_status == Some(*status),
+            Self::ConnectedTo { component_id } => {
+                facts.connected_component_ids.contains(component_id)
+            }
+            Self::Not { filter } => !filter.matches(facts),
+            Self::Prop { path, predicate } => predicate.matches(facts.prop(path)),
+            Self::QualificationStatus { status } => facts.qualification_status == Some(*status),
+            Self::ResourceExists => facts
+                .resource
+                .as_ref()
+                .is_some_and(|resource| resource.payload.is_some()),
+            Self::ResourceStatus { status } => facts
+                .resource
+                .as_ref()
+                .is_some_and(|resource| resource.status == *status),
+            Self::Schema { name } => facts.schema_name.as_ref() == Some(name),
+            Self::SchemaVariant { name } => facts.schema_variant_name.as_ref() == Some(name),
+            Self::Tag { key, predicate } => predicate.matches(facts.tags.get(key)),
+        }
+    }
+
+    fn collect_facets(&self, facets: &mut Facets) {
+        match self {
+            Self::All { filters } | Self::Any { filters } => {
+                for filter in filters {
+                    filter.collect_facets(facets);
+                }
+            }
+            Self::ChangeStatus { .. } => facets.change_status = true,
+            Self::ConnectedTo { .. } => facets.edges = true,
+            Self::Not { filter } => filter.collect_facets(facets),
+            Self::Prop { .. } => facets.properties = true,
+            Self::QualificationStatus { .. } => facets.qualifications = true,
+            Self::ResourceExists | Self::ResourceStatus { .. } => facets.resource = true,
+            Self::Schema { .. } => facets.schema = true,
+            Self::SchemaVariant { .. } => facets.schema_variant = true,
+            Self::Tag { .. } => facets.tags = true,
+        }
+    }
+
+    fn collect_prop_paths(&self, paths: &mut Vec<String>) {
+        match self {
+            Self::All { filters } | Self::Any { filters } => {
+                for filter in filters {
+                    filter.collect_prop_paths(paths);
+                }
+            }
+            Self::Not { filter } => filter.collect_prop_paths(paths),
+            Self::Prop { path, .. } => {
+                if !paths.contains(path) {
+                    paths.push(path.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl PropPredicate {
+    /// Whether the value, if there is one, satisfies the predicate.
+    pub fn matches(&self, value: Option<&Value>) -> bool {
+        let value = value.unwrap_or(&Value::Null);
+        match self {
+            Self::Contains(expected) => match (value, expected) {
+                (Value::String(value), Value::String(expected)) => value.contains(expected),
+                (Value::Array(elements), expected) => elements
+                    .iter()
+                    .any(|element| values_equal(element, expected)),
+                (Value::Object(object), Value::String(key)) => object.contains_key(key),
+                _ => false,
+            },
+            Self::EndsWith(suffix) => value.as_str().is_some_and(|value| value.ends_with(suffix)),
+            Self::Equals(expected) => values_equal(value, expected),
+            Self::Exists => !value.is_null(),
+            Self::GreaterThan(expected) => compare(value, expected) == Some(Ordering::Greater),
+            Self::GreaterThanOrEqual(expected) => matches!(
+                compare(value, expected),
+                Some(Ordering::Greater | Ordering::Equal)
+            ),
+            Self::LessThan(expected) => compare(value, expected) == Some(Ordering::Less),
+            Self::LessThanOrEqual(expected) => matches!(
+                compare(value, expected),
+                Some(Ordering::Less | Ordering::Equal)
+            ),
+            Self::NotEquals(expected) => !values_equal(value, expected),
+            Self::StartsWith(prefix) => value
+                .as_str()
+                .is_some_and(|value| value.starts_with(prefix)),
+        }
+    }
+}
+
+/// Numbers are compared by value, so that "1" and "1.0" are equal.
+fn values_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => left.as_f64() == right.as_f64(),
+        _ => left == right,
+    }
+}
+
+/// Only numbers and strings are ordered, and only against values of the same type.
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => left.as_f64()?.partial_cmp(&right.as_f64()?),
+        (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+        _ => None,
+    }
+}
+
+/// Turn a prop path, with or without a leading "/root", into a JSON pointer into the
+/// properties of a [`ComponentView`].
+fn json_pointer(path: &str) -> String {
+    let path = path.trim_start_matches('/');
+    let path = path.strip_prefix("root").map_or(path, |rest| {
+        if rest.is_empty() || rest.starts_with('/') {
+            rest.trim_start_matches('/')
+        } else {
+            path
+        }
+    });
+
+    path.split('/')
+        .filter(|part| !part.is_empty())
+        .map(|part| format!("/{}", part.replace('~', "~0")))
+        .collect()
+}
+
+/// Which parts of a [`Component`] a filter needs, so that only those are loaded.
+#[derive(Default, Debug)]
+struct Facets {
+    change_status: bool,
+    edges: bool,
+    properties: bool,
+    qualifications: bool,
+    resource: bool,
+    schema: bool,
+    schema_variant: bool,
+    tags: bool,
+}
+
+/// Everything about a [`Component`] that a [`ComponentFilter`] can match on.
+#[derive(Default, Debug)]
+struct ComponentFacts {
+    change_status: Option<ChangeStatus>,
+    connected_component_ids: HashSet<ComponentId>,
+    properties: Value,
+    qualification_status: Option<QualificationSubCheckStatus>,
+    resource: Option<ActionRunResult>,
+    schema_name: Option<String>,
+    schema_variant_name: Option<String>,
+    tags: HashMap<String, Value>,
+}
+
+impl ComponentFacts {
+    async fn load(
+        ctx: &DalContext,
+        component: &Component,
+        facets: &Facets,
+        change_statuses: &HashMap<ComponentId, ChangeStatus>,
+    ) -> QueryResult<Self> {
+        let component_id = *component.id();
+        let mut facts = Self::default();
+
+        if facets.change_status {
+            facts.change_status = Some(
+                change_statuses
+                    .get(&component_id)
+                    .copied()
+                    .unwrap_or(ChangeStatus::Unmodified),
+            );
+        }
+
+        if facets.edges {
+            for edge in Edge::list_for_component(ctx, component_id).await? {
+                for object_id in [*edge.head_object_id(), *edge.tail_object_id()] {
+                    let connected_component_id = ComponentId::from(object_id);
+                    if connected_component_id != component_id {
+                        facts.connected_component_ids.insert(connected_component_id);
+                    }
+                }
+            }
+        }
+
+        if facets.properties {
+            facts.properties = ComponentView::new(ctx, component_id).await?.properties;
+        }
+
+        if facets.qualifications {
+            let mut status = QualificationSubCheckStatus::Success;
+            for qualification in Component::list_qualifications(ctx, component_id).await? {
+                match qualification.result.map(|result| result.status) {
+                    Some(QualificationSubCheckStatus::Failure) => {
+                        status = QualificationSubCheckStatus::Failure;
+                        break;
+                    }
+                    Some(QualificationSubCheckStatus::Warning) => {
+                        status = QualificationSubCheckStatus::Warning;
+                    }
+                    _ => {}
+                }
+            }
+            facts.qualification_status = Some(status);
+        }
+
+        if facets.resource {
+            facts.resource = Some(Component::resource_by_id(ctx, component_id).await?);
+        }
+
+        if facets.schema {
+            facts.schema_name = component
+                .schema(ctx)
+                .await?
+                .map(|schema| schema.name().to_owned());
+        }
+
+        if facets.schema_variant {
+            facts.schema_variant_name = component
+                .schema_variant(ctx)
+                .await?
+                .map(|schema_variant| schema_variant.name().to_owned());
+        }
+
+        if facets.tags {
+            facts.tags = ComponentTag::effective_for_component(ctx, component_id)
+                .await?
+                .into_iter()
+                .map(|(key, tag)| (key, Value::String(tag.value)))
+                .collect();
+        }
+
+        Ok(facts)
+    }
+
+    fn prop(&self, path: &str) -> Option<&Value> {
+        self.properties.pointer(&json_pointer(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn prop(path: &str, predicate: PropPredicate) -> ComponentFilter {
+        ComponentFilter::Prop {
+            path: path.to_owned(),
+            predicate,
+        }
+    }
+
+    fn syntax_error(source: &str) -> (usize, String) {
+        match ComponentFilter::parse(source) {
+            Err(QueryError::Syntax { position, message }) => (position, message),
+            other => panic!("expected a syntax error for {source:?}, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_filters() {
+        assert_eq!(
+            ComponentFilter::Schema {
+                name: "EC2 Instance".to_owned()
+            },
+            ComponentFilter::parse(r#"schema = "EC2 Instance""#).expect("could not parse")
+        );
+        assert_eq!(
+            ComponentFilter::SchemaVariant {
+                name: "v0".to_owned()
+            },
+            ComponentFilter::parse("variant = 'v0'").expect("could not parse")
+        );
+        assert_eq!(
+            prop(
+                "domain/InstanceType",
+                PropPredicate::StartsWith("t2".to_owned())
+            ),
+            ComponentFilter::parse(r#"domain/InstanceType startsWith "t2""#)
+                .expect("could not parse")
+        );
+        assert_eq!(
+            prop("/root/si/name", PropPredicate::NotEquals(json!("web"))),
+            ComponentFilter::parse(r#"/root/si/name != "web""#).expect("could not parse")
+        );
+        assert_eq!(
+            prop(
+                "domain/port",
+                PropPredicate::GreaterThanOrEqual(json!(-1.5))
+            ),
+            ComponentFilter::parse("domain/port >= -1.5").expect("could not parse")
+        );
+        assert_eq!(
+            prop("domain/enabled", PropPredicate::Equals(json!(true))),
+            ComponentFilter::parse("domain/enabled = true").expect("could not parse")
+        );
+        assert_eq!(
+            prop("domain/KeyName", PropPredicate::Exists),
+            ComponentFilter::parse("domain/KeyName exists").expect("could not parse")
+        );
+        assert_eq!(
+            ComponentFilter::QualificationStatus {
+                status: QualificationSubCheckStatus::Failure
+            },
+            ComponentFilter::parse("qualification = failure").expect("could not parse")
+        );
+        assert_eq!(
+            ComponentFilter::ResourceStatus {
+                status: ResourceStatus::Error
+            },
+            ComponentFilter::parse("resource = error").expect("could not parse")
+        );
+        assert_eq!(
+            ComponentFilter::ResourceExists,
+            ComponentFilter::parse("resource exists").expect("could not parse")
+        );
+        assert_eq!(
+            prop("resource/payload", PropPredicate::Exists),
+            ComponentFilter::parse("resource/payload exists").expect("could not parse")
+        );
+        assert_eq!(
+            ComponentFilter::ChangeStatus {
+                status: ChangeStatus::Added
+            },
+            ComponentFilter::parse("change = added").expect("could not parse")
+        );
+        assert_eq!(
+            ComponentFilter::ConnectedTo {
+                component_id: ComponentId::from_str("01H9B0D6AVBKZGEEKGWXM3J8FF")
+                    .expect("could not parse component id")
+            },
+            ComponentFilter::parse(r#"connectedTo "01H9B0D6AVBKZGEEKGWXM3J8FF""#)
+                .expect("could not parse")
+        );
+        assert_eq!(
+            ComponentFilter::Tag {
+                key: "cost center".to_owned(),
+                predicate: PropPredicate::Equals(json!("R&D")),
+            },
+            ComponentFilter::parse(r#"tag "cost center" = "R&D""#).expect("could not parse")
+        );
+        assert_eq!(
+            ComponentFilter::Tag {
+                key: "team".to_owned(),
+                predicate: PropPredicate::Exists,
+            },
+            ComponentFilter::parse(r#"tag "team" exists"#).expect("could not parse")
+        );
+    }
+
+    #[test]
+    fn parse_precedence() {
+        let schema = ComponentFilter::Schema {
+            name: "Region".to_owned(),
+        };
+        let failed = ComponentFilter::QualificationStatus {
+            status: QualificationSubCheckStatus::Failure,
+        };
+        let warned = ComponentFilter::QualificationStatus {
+            status: QualificationSubCheckStatus::Warning,
+        };
+
+        assert_eq!(
+            ComponentFilter::Any {
+                filters: vec![
+                    ComponentFilter::All {
+                        filters: vec![schema.clone(), failed.clone()]
+                    },
+                    warned.clone(),
+                ]
+            },
+            ComponentFilter::parse(
+                r#"schema = "Region" and qualification = failure or qualification = warning"#
+            )
+            .expect("could not parse")
+        );
+        assert_eq!(
+            ComponentFilter::All {
+                filters: vec![
+                    schema.clone(),
+                    ComponentFilter::Any {
+                        filters: vec![failed.clone(), warned.clone()]
+                    },
+                ]
+            },
+            ComponentFilter::parse(
+                r#"schema = "Region" and (qualification = failure or qualification = warning)"#
+            )
+            .expect("could not parse")
+        );
+        assert_eq!(
+            ComponentFilter::All {
+                filters: vec![
+                    ComponentFilter::Not {
+                        filter: Box::new(schema)
+                    },
+                    failed,
+                ]
+            },
+            ComponentFilter::parse(r#"not schema = "Region" and qualification = failure"#)
+                .expect("could not parse")
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!((1, "expected a filter".to_owned()), syntax_error(""));
+        assert_eq!(
+            (10, "expected a quoted string".to_owned()),
+            syntax_error("schema = Region")
+        );
+        assert_eq!(
+            (17, "expected one of failure, success, warning".to_owned()),
+            syntax_error("qualification = failed")
+        );
+        assert_eq!(
+            (13, "expected a comparison".to_owned()),
+            syntax_error("domain/name ~ 1")
+        );
+        assert_eq!(
+            (19, "expected \")\"".to_owned()),
+            syntax_error(r#"(schema = "Region""#)
+        );
+        assert_eq!(
+            (
+                19,
+                "expected \"and\", \"or\" or the end of the query".to_owned()
+            ),
+            syntax_error(r#"schema = "Region" variant = "v0""#)
+        );
+        assert!(matches!(
+            ComponentFilter::parse(&"(".repeat(MAX_DEPTH + 1)),
+            Err(QueryError::TooDeep)
+        ));
+    }
+
+    #[test]
+    fn predicates() {
+        let value = json!({
+            "si": { "name": "web" },
+            "domain": {
+                "InstanceType": "t2.micro",
+                "port": 8080,
+                "tags": { "Name": "web" },
+                "ports": [80, 443],
+                "KeyName": null,
+            },
+        });
+        let facts = ComponentFacts {
+            properties: value,
+            ..ComponentFacts::default()
+        };
+        let matches = |source: &str| {
+            ComponentFilter::parse(source)
+                .expect("could not parse")
+                .matches(&facts)
+        };
+
+        assert!(matches(r#"domain/InstanceType startsWith "t2""#));
+        assert!(matches(r#"domain/InstanceType endsWith "micro""#));
+        assert!(matches(r#"/root/domain/InstanceType contains ".""#));
+        assert!(!matches(r#"domain/InstanceType startsWith "m5""#));
+        assert!(matches("domain/port = 8080.0"));
+        assert!(matches("domain/port > 443 and domain/port <= 8080"));
+        assert!(!matches(r#"domain/port < "9000""#));
+        assert!(matches(r#"domain/tags contains "Name""#));
+        assert!(matches("domain/ports contains 443"));
+        assert!(matches("domain/tags/Name exists"));
+        assert!(!matches("domain/KeyName exists"));
+        assert!(!matches("domain/missing exists"));
+        assert!(matches("domain/missing = null"));
+        assert!(matches(r#"not si/name = "db" or si/name = "db""#));
+    }
+}
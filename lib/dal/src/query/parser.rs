@@ -0,0 +1,371 @@
+//! A recursive descent parser for queries. Precedence, from loosest to tightest:
+//!
+//! ```text
+//! a or b
+//! a and b
+//! not a
+//! (a), comparisons
+//! ```
+
+use serde_json::Value;
+use std::str::FromStr;
+use veritech_client::ResourceStatus;
+
+use super::{
+    ComponentFilter, PropPredicate, QueryError, QueryResult, MAX_DEPTH, MAX_SOURCE_LENGTH,
+};
+use crate::change_status::ChangeStatus;
+use crate::qualification::QualificationSubCheckStatus;
+use crate::ComponentId;
+
+pub(super) fn parse(source: &str) -> QueryResult<ComponentFilter> {
+    let chars: Vec<char> = source.chars().collect();
+    if chars.len() > MAX_SOURCE_LENGTH {
+        return Err(QueryError::TooLong);
+    }
+
+    let mut parser = Parser {
+        chars,
+        position: 0,
+        nesting: 0,
+    };
+    let filter = parser.or()?;
+    parser.skip_whitespace();
+    if parser.peek().is_some() {
+        return Err(parser.error("expected \"and\", \"or\" or the end of the query"));
+    }
+    Ok(filter)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    /// How many nested parentheses and "not"s are being parsed, which bounds the recursion of
+    /// the parser itself.
+    nesting: usize,
+}
+
+impl Parser {
+    fn or(&mut self) -> QueryResult<ComponentFilter> {
+        let mut filters = vec![self.and()?];
+        while self.keyword("or") {
+            filters.push(self.and()?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            ComponentFilter::Any { filters }
+        })
+    }
+
+    fn and(&mut self) -> QueryResult<ComponentFilter> {
+        let mut filters = vec![self.not()?];
+        while self.keyword("and") {
+            filters.push(self.not()?);
+        }
+        Ok(if filters.len() == 1 {
+            filters.remove(0)
+        } else {
+            ComponentFilter::All { filters }
+        })
+    }
+
+    fn not(&mut self) -> QueryResult<ComponentFilter> {
+        if self.keyword("not") {
+            let filter = self.nested(Self::not)?;
+            return Ok(ComponentFilter::Not {
+                filter: Box::new(filter),
+            });
+        }
+
+        if self.eat("(") {
+            let filter = self.nested(Self::or)?;
+            self.expect(")")?;
+            return Ok(filter);
+        }
+
+        self.filter()
+    }
+
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> QueryResult<ComponentFilter>,
+    ) -> QueryResult<ComponentFilter> {
+        self.nesting += 1;
+        if self.nesting > MAX_DEPTH {
+            return Err(QueryError::TooDeep);
+        }
+        let result = parse(self);
+        self.nesting -= 1;
+        result
+    }
+
+    fn filter(&mut self) -> QueryResult<ComponentFilter> {
+        let start = self.position;
+        let word = self.word().ok_or_else(|| self.error("expected a filter"))?;
+
+        Ok(match word.as_str() {
+            "change" => {
+                self.expect("=")?;
+                ComponentFilter::ChangeStatus {
+                    status: self.choice(&[
+                        ("added", ChangeStatus::Added),
+                        ("modified", ChangeStatus::Modified),
+                        ("unmodified", ChangeStatus::Unmodified),
+                    ])?,
+                }
+            }
+            "connectedTo" => {
+                let component_id = self.string()?;
+                ComponentFilter::ConnectedTo {
+                    component_id: ComponentId::from_str(&component_id).map_err(|_| {
+                        self.error(format!("{component_id:?} is not a component id"))
+                    })?,
+                }
+            }
+            "qualification" => {
+                self.expect("=")?;
+                ComponentFilter::QualificationStatus {
+                    status: self.choice(&[
+                        ("failure", QualificationSubCheckStatus::Failure),
+                        ("success", QualificationSubCheckStatus::Success),
+                        ("warning", QualificationSubCheckStatus::Warning),
+                    ])?,
+                }
+            }
+            "resource" => {
+                if self.keyword("exists") {
+                    ComponentFilter::ResourceExists
+                } else {
+                    self.expect("=")?;
+                    ComponentFilter::ResourceStatus {
+                        status: self.choice(&[
+                            ("error", ResourceStatus::Error),
+                            ("ok", ResourceStatus::Ok),
+                            ("warning", ResourceStatus::Warning),
+                        ])?,
+                    }
+                }
+            }
+            "schema" => {
+                self.expect("=")?;
+                ComponentFilter::Schema {
+                    name: self.string()?,
+                }
+            }
+            "tag" => ComponentFilter::Tag {
+                key: self.string()?,
+                predicate: self.predicate()?,
+            },
+            "variant" => {
+                self.expect("=")?;
+                ComponentFilter::SchemaVariant {
+                    name: self.string()?,
+                }
+            }
+            path => {
+                if !path.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '/') {
+                    self.position = start;
+                    return Err(self.error("expected a filter"));
+                }
+                ComponentFilter::Prop {
+                    path: path.to_owned(),
+                    predicate: self.predicate()?,
+                }
+            }
+        })
+    }
+
+    fn predicate(&mut self) -> QueryResult<PropPredicate> {
+        // The two character operators must be tried first.
+        Ok(if self.eat("!=") {
+            PropPredicate::NotEquals(self.value()?)
+        } else if self.eat("<=") {
+            PropPredicate::LessThanOrEqual(self.value()?)
+        } else if self.eat(">=") {
+            PropPredicate::GreaterThanOrEqual(self.value()?)
+        } else if self.eat("==") || self.eat("=") {
+            PropPredicate::Equals(self.value()?)
+        } else if self.eat("<") {
+            PropPredicate::LessThan(self.value()?)
+        } else if self.eat(">") {
+            PropPredicate::GreaterThan(self.value()?)
+        } else if self.keyword("contains") {
+            PropPredicate::Contains(self.value()?)
+        } else if self.keyword("endsWith") {
+            PropPredicate::EndsWith(self.string()?)
+        } else if self.keyword("exists") {
+            PropPredicate::Exists
+        } else if self.keyword("startsWith") {
+            PropPredicate::StartsWith(self.string()?)
+        } else {
+            return Err(self.error("expected a comparison"));
+        })
+    }
+
+    /// Parse one of the given words, returning what it stands for.
+    fn choice<T: Copy>(&mut self, choices: &[(&str, T)]) -> QueryResult<T> {
+        let start = self.position;
+        let word = self.word();
+        if let Some((_, choice)) = choices
+            .iter()
+            .find(|(name, _)| word.as_deref() == Some(*name))
+        {
+            return Ok(*choice);
+        }
+
+        self.position = start;
+        self.skip_whitespace();
+        let names: Vec<&str> = choices.iter().map(|(name, _)| *name).collect();
+        Err(self.error(format!("expected one of {}", names.join(", "))))
+    }
+
+    fn value(&mut self) -> QueryResult<Value> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"' | '\'') => Ok(Value::String(self.string()?)),
+            Some(c) if c.is_ascii_digit() || c == '-' => self.number(),
+            _ => {
+                let start = self.position;
+                match self.word().as_deref() {
+                    Some("true") => Ok(Value::Bool(true)),
+                    Some("false") => Ok(Value::Bool(false)),
+                    Some("null") => Ok(Value::Null),
+                    _ => {
+                        self.position = start;
+                        self.skip_whitespace();
+                        Err(self.error("expected a string, number, boolean or null"))
+                    }
+                }
+            }
+        }
+    }
+
+    fn string(&mut self) -> QueryResult<String> {
+        self.skip_whitespace();
+        let quote = match self.peek() {
+            Some(quote @ ('"' | '\'')) => quote,
+            _ => return Err(self.error("expected a quoted string")),
+        };
+        self.position += 1;
+
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some(c @ ('\\' | '"' | '\'')) => string.push(c),
+                    Some(c) => return Err(self.error(format!("unknown escape sequence \\{c}"))),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> QueryResult<Value> {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.'))
+        {
+            self.position += 1;
+        }
+
+        let text: String = self.chars[start..self.position].iter().collect();
+        match serde_json::from_str::<Value>(&text) {
+            Ok(number @ Value::Number(_)) => Ok(number),
+            _ => {
+                self.position = start;
+                Err(self.error(format!("{text} is not a valid number")))
+            }
+        }
+    }
+
+    /// Parse a keyword or a prop path, which may contain "/" between the names of props.
+    fn word(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let mut word = String::new();
+        while let Some(c) = self.peek().filter(|c| is_word_char(*c)) {
+            word.push(c);
+            self.position += 1;
+        }
+        if word.is_empty() {
+            None
+        } else {
+            Some(word)
+        }
+    }
+
+    /// Consume the keyword (after any whitespace) if it comes next as a whole word.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        self.skip_whitespace();
+        let length = keyword.chars().count();
+        let matches = keyword
+            .chars()
+            .enumerate()
+            .all(|(offset, c)| self.peek_at(offset) == Some(c))
+            && !self.peek_at(length).is_some_and(is_word_char);
+        if matches {
+            self.position += length;
+        }
+        matches
+    }
+
+    /// Consume the token (after any whitespace) if it comes next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let matches = token
+            .chars()
+            .enumerate()
+            .all(|(offset, c)| self.peek_at(offset) == Some(c));
+        if matches {
+            self.position += token.chars().count();
+        }
+        matches
+    }
+
+    fn expect(&mut self, token: &str) -> QueryResult<()> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected \"{token}\"")))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.position += 1;
+        }
+        c
+    }
+
+    fn error(&self, message: impl Into<String>) -> QueryError {
+        QueryError::Syntax {
+            position: self.position + 1,
+            message: message.into(),
+        }
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '/' | '.')
+}
@@ -0,0 +1,385 @@
+//! This module contains [`ResourceImport`], which turns the resources recorded in a Terraform
+//! state file or declared in a CloudFormation template into [`Components`](crate::Component), so
+//! that an existing estate doesn't have to be recreated by hand. Everything is read from the
+//! files themselves: nothing is fetched from the cloud providers.
+//!
+//! Resource types are matched to [`SchemaVariants`](crate::SchemaVariant) through
+//! [`ImportMappings`](ImportMapping), which also say which attributes of a resource go to which
+//! props. Resources whose type has no mapping are skipped. Connections are inferred from the
+//! references between resources (Terraform dependencies, and `Ref`, `Fn::GetAtt`, `Fn::Sub` and
+//! `DependsOn` in CloudFormation): an output socket of the referenced component is connected to
+//! the input socket with the same name on the referring component.
+//!
+//! An import is materialized as a [`Manifest`], with the address of each resource (or its
+//! logical id in CloudFormation) as the name of its [`Component`], so importing the same file
+//! again updates the [`Components`](Component) instead of duplicating them. Terraform state
+//! also records that the resources exist: the [`Components`](Component) made for them get no
+//! create actions, and once the change set they were imported into is applied, their mapped
+//! attributes are attached to them on head as their resources, with
+//! [`ResourceImportPlan::attach_resources`].
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_data_nats::NatsError;
+use si_data_pg::PgError;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use strum::{AsRefStr, Display, EnumString};
+use thiserror::Error;
+
+use crate::func::backend::js_action::ActionRunResult;
+use crate::manifest::{ManifestComponent, ManifestConnection};
+use crate::schema::variant::SchemaVariantError;
+use crate::socket::{SocketEdgeKind, SocketKind};
+use crate::{
+    Action, ActionError, ActionKind, Component, ComponentError, ComponentId, DalContext,
+    HistoryEventError, Manifest, ManifestError, ManifestPlan, SchemaVariant, SchemaVariantId,
+    StandardModel, StandardModelError, TransactionsError,
+};
+
+pub mod cloudformation;
+pub mod mapping;
+pub mod terraform;
+
+pub use mapping::{ImportAttributeMapping, ImportMapping, ImportMappingId, ImportMappingPk};
+
+/// Components are laid out in rows of this many.
+const COMPONENTS_PER_ROW: usize = 5;
+const COLUMN_WIDTH: i64 = 400;
+const ROW_HEIGHT: i64 = 300;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ResourceImportError {
+    #[error("action error: {0}")]
+    Action(#[from] ActionError),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("component not found for imported resource: {0}")]
+    ComponentNotFound(String),
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("import has changes that are not on head yet, apply its change set first")]
+    ImportNotApplied,
+    #[error("invalid attribute mapping, prop paths start with root: {0}")]
+    InvalidPropPath(String),
+    #[error("invalid cloudformation template: {0}")]
+    InvalidTemplate(String),
+    #[error("manifest error: {0}")]
+    Manifest(#[from] ManifestError),
+    #[error("nats txn error: {0}")]
+    Nats(#[from] NatsError),
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("resources can only be attached on head, once the import's change set is applied")]
+    ResourcesRequireHead,
+    #[error("schema not found for schema variant: {0}")]
+    SchemaNotFoundForVariant(SchemaVariantId),
+    #[error("schema variant error: {0}")]
+    SchemaVariant(#[from] SchemaVariantError),
+    #[error("schema variant not found: {0}")]
+    SchemaVariantNotFound(SchemaVariantId),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("error deserializing yaml: {0}")]
+    SerdeYaml(#[from] serde_yaml::Error),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("unsupported terraform state version: {0}")]
+    UnsupportedTerraformStateVersion(u64),
+}
+
+pub type ResourceImportResult<T> = Result<T, ResourceImportError>;
+
+/// Where the resources of a [`ResourceImport`] were read from.
+#[remain::sorted]
+#[derive(
+    AsRefStr, Clone, Copy, Debug, Deserialize, Display, EnumString, Eq, Hash, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ImportSource {
+    /// A CloudFormation template, in JSON or YAML.
+    CloudFormation,
+    /// A Terraform state file (`.tfstate`), version 4.
+    Terraform,
+}
+
+/// A resource read from a Terraform state file or a CloudFormation template.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedResource {
+    /// Identifies the resource within the import: the address of a Terraform resource instance,
+    /// like `aws_instance.web[0]`, or the logical id of a CloudFormation resource.
+    pub address: String,
+    /// Like `aws_instance` or `AWS::EC2::Instance`.
+    pub resource_type: String,
+    /// The attributes of a Terraform resource instance, or the properties of a CloudFormation
+    /// resource.
+    pub attributes: Value,
+    /// The addresses of the resources this one refers to, within the import.
+    pub references: Vec<String>,
+    /// Whether the resource exists already, which only Terraform state records. The mapped
+    /// attributes of such a resource are attached to its [`Component`] as its resource.
+    pub exists: bool,
+}
+
+/// A resource that was not imported, since there is no [`ImportMapping`] for its type.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedResource {
+    pub address: String,
+    pub resource_type: String,
+}
+
+/// The resources read from a file. See the [`module`](crate::resource_import) for more
+/// information.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceImport {
+    pub source: ImportSource,
+    pub resources: Vec<ImportedResource>,
+}
+
+/// What importing would change, as a [`ManifestPlan`], along with the [`Manifest`] it was
+/// planned from.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceImportPlan {
+    pub manifest: Manifest,
+    pub plan: ManifestPlan,
+    pub skipped: Vec<SkippedResource>,
+    /// The payloads to attach as resources, by component name. Only the mapped attributes of a
+    /// resource are in its payload.
+    pub payloads: BTreeMap<String, Value>,
+}
+
+impl ResourceImport {
+    pub fn from_terraform_state(text: &str) -> ResourceImportResult<Self> {
+        Ok(Self {
+            source: ImportSource::Terraform,
+            resources: terraform::parse_state(text)?,
+        })
+    }
+
+    pub fn from_cloudformation_template(text: &str) -> ResourceImportResult<Self> {
+        Ok(Self {
+            source: ImportSource::CloudFormation,
+            resources: cloudformation::parse_template(text)?,
+        })
+    }
+
+    pub fn parse(source: ImportSource, text: &str) -> ResourceImportResult<Self> {
+        match source {
+            ImportSource::CloudFormation => Self::from_cloudformation_template(text),
+            ImportSource::Terraform => Self::from_terraform_state(text),
+        }
+    }
+
+    /// Compares the imported resources with the [`Components`](Component) of the current
+    /// [`Visibility`](crate::Visibility).
+    pub async fn plan(&self, ctx: &DalContext) -> ResourceImportResult<ResourceImportPlan> {
+        let mut manifest = Manifest::default();
+        let mut skipped = Vec::new();
+        let mut payloads = BTreeMap::new();
+        let mut schema_variant_ids = HashMap::new();
+        for resource in &self.resources {
+            let mapping = match ImportMapping::find_for_resource_type(
+                ctx,
+                self.source,
+                &resource.resource_type,
+            )
+            .await?
+            {
+                Some(mapping) => mapping,
+                None => {
+                    skipped.push(SkippedResource {
+                        address: resource.address.clone(),
+                        resource_type: resource.resource_type.clone(),
+                    });
+                    continue;
+                }
+            };
+
+            let schema_variant_id = *mapping.schema_variant_id();
+            let schema_variant = SchemaVariant::get_by_id(ctx, &schema_variant_id)
+                .await?
+                .ok_or(ResourceImportError::SchemaVariantNotFound(
+                    schema_variant_id,
+                ))?;
+            let schema = schema_variant.schema(ctx).await?.ok_or(
+                ResourceImportError::SchemaNotFoundForVariant(schema_variant_id),
+            )?;
+
+            let position = manifest.components.len();
+            manifest.components.push(ManifestComponent {
+                name: resource.address.clone(),
+                schema_name: schema.name().to_owned(),
+                schema_variant_name: Some(schema_variant.name().to_owned()),
+                x: (position % COMPONENTS_PER_ROW) as i64 * COLUMN_WIDTH,
+                y: (position / COMPONENTS_PER_ROW) as i64 * ROW_HEIGHT,
+                values: mapping.values_for(&resource.attributes)?,
+            });
+            schema_variant_ids.insert(resource.address.as_str(), schema_variant_id);
+            if resource.exists {
+                payloads.insert(
+                    resource.address.clone(),
+                    mapping.payload_for(&resource.attributes),
+                );
+            }
+        }
+
+        let mut sockets = HashMap::new();
+        let mut connections = BTreeSet::new();
+        for resource in &self.resources {
+            let to_schema_variant_id = match schema_variant_ids.get(resource.address.as_str()) {
+                Some(schema_variant_id) => *schema_variant_id,
+                None => continue,
+            };
+            for reference in &resource.references {
+                let from_schema_variant_id = match schema_variant_ids.get(reference.as_str()) {
+                    Some(schema_variant_id) => *schema_variant_id,
+                    None => continue,
+                };
+                let (outputs, _) = socket_names(ctx, &mut sockets, from_schema_variant_id).await?;
+                let (_, inputs) = socket_names(ctx, &mut sockets, to_schema_variant_id).await?;
+                for name in outputs.intersection(&inputs) {
+                    connections.insert(ManifestConnection {
+                        from_component: reference.clone(),
+                        from_socket: name.clone(),
+                        to_component: resource.address.clone(),
+                        to_socket: name.clone(),
+                    });
+                }
+            }
+        }
+        manifest.connections = connections.into_iter().collect();
+
+        let plan = manifest.plan(ctx, false).await?;
+        Ok(ResourceImportPlan {
+            manifest,
+            plan,
+            skipped,
+            payloads,
+        })
+    }
+}
+
+impl ResourceImportPlan {
+    /// Makes the changes of the plan, returning the imported [`Components`](Component) by name.
+    /// Resources that exist already don't get create actions.
+    pub async fn apply(
+        &self,
+        ctx: &DalContext,
+    ) -> ResourceImportResult<BTreeMap<String, ComponentId>> {
+        let components = self.plan.apply(ctx).await?;
+
+        if !self.payloads.is_empty() && !ctx.visibility().is_head() {
+            let existing: BTreeSet<ComponentId> = self
+                .payloads
+                .keys()
+                .filter_map(|name| components.get(name).copied())
+                .collect();
+            for mut action in Action::find_for_change_set(ctx).await? {
+                if existing.contains(action.component_id())
+                    && *action.prototype(ctx).await?.kind() == ActionKind::Create
+                {
+                    action.delete_by_id(ctx).await?;
+                }
+            }
+        }
+
+        Ok(components)
+    }
+
+    /// Attaches the known resources to the imported [`Components`](Component), returning them
+    /// by name. Resources only exist on head, so this is planned and done on head, once the
+    /// change set the import was applied in has been applied too.
+    pub async fn attach_resources(
+        &self,
+        ctx: &DalContext,
+    ) -> ResourceImportResult<BTreeMap<String, ComponentId>> {
+        if !ctx.visibility().is_head() {
+            return Err(ResourceImportError::ResourcesRequireHead);
+        }
+        if !self.plan.is_empty() {
+            return Err(ResourceImportError::ImportNotApplied);
+        }
+
+        let mut components = BTreeMap::new();
+        for (name, payload) in &self.payloads {
+            let component_id = self
+                .plan
+                .components
+                .get(name)
+                .ok_or_else(|| ResourceImportError::ComponentNotFound(name.clone()))?;
+            let component = Component::get_by_id(ctx, component_id)
+                .await?
+                .ok_or_else(|| ResourceImportError::ComponentNotFound(name.clone()))?;
+            component
+                .set_resource(
+                    ctx,
+                    ActionRunResult {
+                        status: veritech_client::ResourceStatus::Ok,
+                        payload: Some(payload.clone()),
+                        message: None,
+                        logs: vec![],
+                        last_synced: None,
+                    },
+                )
+                .await?;
+            components.insert(name.clone(), *component_id);
+        }
+
+        Ok(components)
+    }
+}
+
+/// The names of the output and input configuration sockets of a [`SchemaVariant`]. Frame
+/// sockets are left out.
+async fn socket_names(
+    ctx: &DalContext,
+    cache: &mut HashMap<SchemaVariantId, (BTreeSet<String>, BTreeSet<String>)>,
+    schema_variant_id: SchemaVariantId,
+) -> ResourceImportResult<(BTreeSet<String>, BTreeSet<String>)> {
+    if let Some(names) = cache.get(&schema_variant_id) {
+        return Ok(names.clone());
+    }
+
+    let schema_variant = SchemaVariant::get_by_id(ctx, &schema_variant_id)
+        .await?
+        .ok_or(ResourceImportError::SchemaVariantNotFound(
+            schema_variant_id,
+        ))?;
+    let mut outputs = BTreeSet::new();
+    let mut inputs = BTreeSet::new();
+    for socket in schema_variant.sockets(ctx).await? {
+        if *socket.kind() == SocketKind::Frame {
+            continue;
+        }
+        match socket.edge_kind() {
+            SocketEdgeKind::ConfigurationOutput => outputs.insert(socket.name().to_owned()),
+            SocketEdgeKind::ConfigurationInput => inputs.insert(socket.name().to_owned()),
+        };
+    }
+
+    let names = (outputs, inputs);
+    cache.insert(schema_variant_id, names.clone());
+    Ok(names)
+}
+
+/// Whether the value is, or contains, a CloudFormation intrinsic function such as `Ref`, which
+/// can't be resolved from the template alone.
+pub(crate) fn contains_intrinsic(value: &Value) -> bool {
+    match value {
+        Value::Object(object) => {
+            object
+                .keys()
+                .any(|key| key == "Ref" || key.starts_with("Fn::"))
+                || object.values().any(contains_intrinsic)
+        }
+        Value::Array(array) => array.iter().any(contains_intrinsic),
+        _ => false,
+    }
+}
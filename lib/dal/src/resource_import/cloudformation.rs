@@ -0,0 +1,269 @@
+//! Reads the resources declared in a CloudFormation template, in JSON or YAML. The short forms
+//! of intrinsic functions in YAML (`!Ref`, `!GetAtt`, `!Sub` and so on) are read as their long
+//! forms, so that templates look the same whichever way they were written.
+
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use crate::resource_import::{ImportedResource, ResourceImportError, ResourceImportResult};
+
+/// Parses a template, with one [`ImportedResource`] per resource. Templates only declare
+/// resources, so none of them exist yet.
+pub fn parse_template(text: &str) -> ResourceImportResult<Vec<ImportedResource>> {
+    let template = if text.trim_start().starts_with('{') {
+        serde_json::from_str(text)?
+    } else {
+        yaml_to_json(serde_yaml::from_str(text)?)?
+    };
+
+    let declared = template
+        .get("Resources")
+        .and_then(Value::as_object)
+        .ok_or_else(|| ResourceImportError::InvalidTemplate("no Resources".to_owned()))?;
+
+    let mut resources = Vec::with_capacity(declared.len());
+    for (logical_id, declaration) in declared {
+        let resource_type = declaration
+            .get("Type")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                ResourceImportError::InvalidTemplate(format!("no Type for {logical_id}"))
+            })?;
+        let attributes = declaration
+            .get("Properties")
+            .cloned()
+            .unwrap_or_else(|| Value::Object(serde_json::Map::new()));
+
+        let mut references = BTreeSet::new();
+        collect_references(&attributes, &mut references);
+        match declaration.get("DependsOn") {
+            Some(Value::String(dependency)) => {
+                references.insert(dependency.clone());
+            }
+            Some(Value::Array(dependencies)) => {
+                references.extend(
+                    dependencies
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(ToOwned::to_owned),
+                );
+            }
+            _ => {}
+        }
+        // References to parameters and pseudo parameters (like `AWS::Region`) are not resources.
+        references.retain(|reference| reference != logical_id && declared.contains_key(reference));
+
+        resources.push(ImportedResource {
+            address: logical_id.clone(),
+            resource_type: resource_type.to_owned(),
+            attributes,
+            references: references.into_iter().collect(),
+            exists: false,
+        });
+    }
+
+    Ok(resources)
+}
+
+/// Collects the logical ids referred to by `Ref`, `Fn::GetAtt` and `Fn::Sub`.
+fn collect_references(value: &Value, references: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(logical_id)) = object.get("Ref") {
+                references.insert(logical_id.clone());
+            }
+            match object.get("Fn::GetAtt") {
+                Some(Value::Array(parts)) => {
+                    if let Some(Value::String(logical_id)) = parts.first() {
+                        references.insert(logical_id.clone());
+                    }
+                }
+                Some(Value::String(attribute)) => {
+                    if let Some((logical_id, _)) = attribute.split_once('.') {
+                        references.insert(logical_id.to_owned());
+                    }
+                }
+                _ => {}
+            }
+            let substituted = match object.get("Fn::Sub") {
+                Some(Value::String(string)) => Some(string),
+                Some(Value::Array(parts)) => parts.first().and_then(|part| match part {
+                    Value::String(string) => Some(string),
+                    _ => None,
+                }),
+                _ => None,
+            };
+            if let Some(string) = substituted {
+                collect_substitutions(string, references);
+            }
+
+            for child in object.values() {
+                collect_references(child, references);
+            }
+        }
+        Value::Array(array) => {
+            for child in array {
+                collect_references(child, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collects the logical ids of the `${LogicalId}` and `${LogicalId.Attribute}` variables of an
+/// `Fn::Sub` string. `${!Literal}` is not a variable.
+fn collect_substitutions(string: &str, references: &mut BTreeSet<String>) {
+    let mut rest = string;
+    while let Some(start) = rest.find("${") {
+        rest = &rest[start + 2..];
+        let end = match rest.find('}') {
+            Some(end) => end,
+            None => break,
+        };
+        let variable = &rest[..end];
+        if !variable.starts_with('!') {
+            let logical_id = variable.split('.').next().unwrap_or(variable);
+            references.insert(logical_id.to_owned());
+        }
+        rest = &rest[end + 1..];
+    }
+}
+
+fn yaml_to_json(value: serde_yaml::Value) -> ResourceImportResult<Value> {
+    Ok(match value {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(boolean) => Value::Bool(boolean),
+        serde_yaml::Value::Number(number) => serde_json::to_value(number)?,
+        serde_yaml::Value::String(string) => Value::String(string),
+        serde_yaml::Value::Sequence(sequence) => Value::Array(
+            sequence
+                .into_iter()
+                .map(yaml_to_json)
+                .collect::<ResourceImportResult<_>>()?,
+        ),
+        serde_yaml::Value::Mapping(mapping) => {
+            let mut object = serde_json::Map::with_capacity(mapping.len());
+            for (key, value) in mapping {
+                let key = match key {
+                    serde_yaml::Value::String(key) => key,
+                    serde_yaml::Value::Number(number) => number.to_string(),
+                    serde_yaml::Value::Bool(boolean) => boolean.to_string(),
+                    other => {
+                        return Err(ResourceImportError::InvalidTemplate(format!(
+                            "unsupported key: {other:?}"
+                        )))
+                    }
+                };
+                object.insert(key, yaml_to_json(value)?);
+            }
+            Value::Object(object)
+        }
+        serde_yaml::Value::Tagged(tagged) => {
+            let tag = tagged.tag.to_string();
+            let name = tag.trim_start_matches('!');
+            let value = yaml_to_json(tagged.value)?;
+            let (function, value) = match (name, value) {
+                ("Ref", value) => ("Ref".to_owned(), value),
+                // `!GetAtt Resource.Attribute` is the short form of a list of the two.
+                ("GetAtt", Value::String(attribute)) => (
+                    "Fn::GetAtt".to_owned(),
+                    match attribute.split_once('.') {
+                        Some((logical_id, attribute)) => {
+                            serde_json::json!([logical_id, attribute])
+                        }
+                        None => Value::String(attribute),
+                    },
+                ),
+                (name, value) => (format!("Fn::{name}"), value),
+            };
+            let mut object = serde_json::Map::with_capacity(1);
+            object.insert(function, value);
+            Value::Object(object)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const YAML: &str = r#"
+AWSTemplateFormatVersion: "2010-09-09"
+Parameters:
+  Size:
+    Type: String
+Resources:
+  Vpc:
+    Type: AWS::EC2::VPC
+    Properties:
+      CidrBlock: 10.0.0.0/16
+  Subnet:
+    Type: AWS::EC2::Subnet
+    Properties:
+      VpcId: !Ref Vpc
+      CidrBlock: 10.0.1.0/24
+      AvailabilityZone: !Select [0, !GetAZs ""]
+  Server:
+    Type: AWS::EC2::Instance
+    DependsOn: Subnet
+    Properties:
+      InstanceType: !Ref Size
+      UserData: !Sub "echo ${Vpc.CidrBlock} ${AWS::Region} ${!Literal}"
+      Tags:
+        - Key: Zone
+          Value: !GetAtt Subnet.AvailabilityZone
+"#;
+
+    #[test]
+    fn parse_yaml() {
+        let resources = parse_template(YAML).expect("could not parse template");
+        let addresses: Vec<&str> = resources
+            .iter()
+            .map(|resource| resource.address.as_str())
+            .collect();
+        assert_eq!(vec!["Server", "Subnet", "Vpc"], addresses);
+
+        let server = &resources[0];
+        assert_eq!("AWS::EC2::Instance", server.resource_type);
+        assert_eq!(
+            vec!["Subnet".to_owned(), "Vpc".to_owned()],
+            server.references
+        );
+        assert_eq!(
+            json!({ "Fn::GetAtt": ["Subnet", "AvailabilityZone"] }),
+            server.attributes["Tags"][0]["Value"]
+        );
+        assert!(!server.exists);
+
+        let subnet = &resources[1];
+        assert_eq!(vec!["Vpc".to_owned()], subnet.references);
+        assert_eq!(json!({ "Ref": "Vpc" }), subnet.attributes["VpcId"]);
+        assert_eq!(
+            json!({ "Fn::Select": [0, { "Fn::GetAZs": "" }] }),
+            subnet.attributes["AvailabilityZone"]
+        );
+    }
+
+    #[test]
+    fn parse_json_matches_yaml() {
+        let json = serde_json::to_string(
+            &yaml_to_json(serde_yaml::from_str(YAML).expect("could not parse yaml"))
+                .expect("could not convert yaml"),
+        )
+        .expect("could not serialize template");
+        assert_eq!(
+            parse_template(YAML).expect("could not parse yaml template"),
+            parse_template(&json).expect("could not parse json template"),
+        );
+    }
+
+    #[test]
+    fn missing_resources() {
+        assert!(matches!(
+            parse_template("AWSTemplateFormatVersion: \"2010-09-09\""),
+            Err(ResourceImportError::InvalidTemplate(_))
+        ));
+    }
+}
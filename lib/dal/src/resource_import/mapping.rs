@@ -0,0 +1,298 @@
+//! This module contains [`ImportMapping`], which says which [`SchemaVariant`](crate::SchemaVariant)
+//! a type of imported resource becomes, and where its attributes go.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use telemetry::prelude::*;
+
+use crate::resource_import::{ImportSource, ResourceImportError, ResourceImportResult};
+use crate::standard_model::TypeHint;
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor, DalContext, HistoryEvent,
+    SchemaVariantId, StandardModel, Tenancy, Timestamp, Visibility,
+};
+
+pk!(ImportMappingPk);
+pk!(ImportMappingId);
+
+/// Where an attribute of an imported resource goes.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportAttributeMapping {
+    /// A JSON pointer into the attributes of the resource, like `/instance_type` or
+    /// `/Tags/0/Value`.
+    pub source: String,
+    /// The names of the props from "root" to the [`Prop`](crate::Prop) the value is for.
+    pub path: Vec<String>,
+}
+
+/// Maps a resource type of an [`ImportSource`] to a [`SchemaVariant`](crate::SchemaVariant).
+/// There is at most one mapping per source and resource type: creating one that exists updates it.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ImportMapping {
+    pk: ImportMappingPk,
+    id: ImportMappingId,
+    schema_variant_id: SchemaVariantId,
+    source: ImportSource,
+    resource_type: String,
+    attributes: Vec<ImportAttributeMapping>,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: ImportMapping,
+    pk: ImportMappingPk,
+    id: ImportMappingId,
+    table_name: "import_mappings",
+    history_event_label_base: "import_mapping",
+    history_event_message_name: "Import Mapping"
+}
+
+impl ImportMapping {
+    #[instrument(skip_all)]
+    pub async fn new(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        source: ImportSource,
+        resource_type: impl AsRef<str>,
+        attributes: Vec<ImportAttributeMapping>,
+    ) -> ResourceImportResult<Self> {
+        validate_attributes(&attributes)?;
+
+        let resource_type = resource_type.as_ref();
+        let attributes = serde_json::to_value(&attributes)?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM import_mapping_create_v1($1, $2, $3, $4, $5, $6)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &schema_variant_id,
+                    &source.as_ref(),
+                    &resource_type,
+                    &attributes,
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    standard_model_accessor!(schema_variant_id, Pk(SchemaVariantId), ResourceImportResult);
+    standard_model_accessor!(source, Enum(ImportSource), ResourceImportResult);
+    standard_model_accessor!(resource_type, String, ResourceImportResult);
+
+    pub fn attributes(&self) -> &[ImportAttributeMapping] {
+        &self.attributes
+    }
+
+    #[instrument(skip_all)]
+    pub async fn set_attributes(
+        &mut self,
+        ctx: &DalContext,
+        attributes: Vec<ImportAttributeMapping>,
+    ) -> ResourceImportResult<()> {
+        validate_attributes(&attributes)?;
+
+        let value = serde_json::to_value(&attributes)?;
+        let updated_at = standard_model::update(
+            ctx,
+            Self::table_name(),
+            "attributes",
+            self.id(),
+            &value,
+            TypeHint::JsonB,
+        )
+        .await?;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            &Self::history_event_label(vec!["updated"]),
+            &Self::history_event_message("updated"),
+            &serde_json::json![{
+                "pk": self.pk,
+                "field": "attributes",
+                "value": &value,
+            }],
+        )
+        .await?;
+        self.timestamp.updated_at = updated_at;
+        self.attributes = attributes;
+
+        Ok(())
+    }
+
+    pub async fn find_for_resource_type(
+        ctx: &DalContext,
+        source: ImportSource,
+        resource_type: impl AsRef<str>,
+    ) -> ResourceImportResult<Option<Self>> {
+        let resource_type = resource_type.as_ref();
+        Ok(Self::find_by_attr(ctx, "resource_type", &resource_type)
+            .await?
+            .into_iter()
+            .find(|mapping| mapping.source == source))
+    }
+
+    pub async fn list_for_schema_variant(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+    ) -> ResourceImportResult<Vec<Self>> {
+        let mut mappings =
+            Self::find_by_attr(ctx, "schema_variant_id", &schema_variant_id.to_string()).await?;
+        mappings.sort_by(|a, b| a.resource_type.cmp(&b.resource_type));
+        Ok(mappings)
+    }
+
+    /// Creates the mapping for the source and resource type, or replaces the
+    /// [`SchemaVariant`](crate::SchemaVariant) and attributes of the existing one.
+    #[instrument(skip_all)]
+    pub async fn upsert(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        source: ImportSource,
+        resource_type: impl AsRef<str>,
+        attributes: Vec<ImportAttributeMapping>,
+    ) -> ResourceImportResult<Self> {
+        match Self::find_for_resource_type(ctx, source, resource_type.as_ref()).await? {
+            Some(mut mapping) => {
+                if mapping.schema_variant_id != schema_variant_id {
+                    mapping
+                        .set_schema_variant_id(ctx, schema_variant_id)
+                        .await?;
+                }
+                mapping.set_attributes(ctx, attributes).await?;
+                Ok(mapping)
+            }
+            None => Self::new(ctx, schema_variant_id, source, resource_type, attributes).await,
+        }
+    }
+
+    /// The values of a [`ManifestComponent`](crate::manifest::ManifestComponent) for a resource
+    /// with the given attributes. Attributes the resource doesn't have, or which are unresolved
+    /// CloudFormation intrinsic functions, are left out.
+    pub fn values_for(
+        &self,
+        attributes: &Value,
+    ) -> ResourceImportResult<serde_json::Map<String, Value>> {
+        let mut values = serde_json::Map::new();
+        for attribute in &self.attributes {
+            let value = match attributes.pointer(&attribute.source) {
+                Some(value) if !value.is_null() && !super::contains_intrinsic(value) => value,
+                _ => continue,
+            };
+
+            // The path was validated to start with "root" and have at least one more part.
+            let (last, parents) = match attribute.path[1..].split_last() {
+                Some(split) => split,
+                None => continue,
+            };
+            let mut object = &mut values;
+            for part in parents {
+                let entry = object
+                    .entry(part.clone())
+                    .or_insert_with(|| Value::Object(serde_json::Map::new()));
+                object = match entry {
+                    Value::Object(child) => child,
+                    _ => {
+                        return Err(ResourceImportError::InvalidPropPath(
+                            attribute.path.join("/"),
+                        ))
+                    }
+                };
+            }
+            object.insert(last.clone(), value.clone());
+        }
+
+        Ok(values)
+    }
+
+    /// The payload to attach as the resource of a [`Component`](crate::Component) imported from
+    /// a resource with the given attributes: the mapped attributes, where they are in the
+    /// resource. Unmapped attributes are left out, as they may be secrets.
+    pub fn payload_for(&self, attributes: &Value) -> Value {
+        let mut payload = Value::Object(serde_json::Map::new());
+        for attribute in &self.attributes {
+            if matches!(
+                attributes.pointer(&attribute.source),
+                None | Some(Value::Null)
+            ) {
+                continue;
+            }
+            let tokens: Vec<String> = attribute
+                .source
+                .split('/')
+                .skip(1)
+                .map(|token| token.replace("~1", "/").replace("~0", "~"))
+                .collect();
+            copy_pointed(attributes, &tokens, &mut payload);
+        }
+        payload
+    }
+}
+
+/// Copies the value the JSON pointer tokens point to into the same place in `to`, creating the
+/// objects and arrays on the way.
+fn copy_pointed(from: &Value, tokens: &[String], to: &mut Value) {
+    let (token, rest) = match tokens.split_first() {
+        Some(split) => split,
+        None => {
+            *to = from.clone();
+            return;
+        }
+    };
+
+    match from {
+        Value::Object(object) => {
+            let child = match object.get(token) {
+                Some(child) => child,
+                None => return,
+            };
+            if !to.is_object() {
+                *to = Value::Object(serde_json::Map::new());
+            }
+            if let Value::Object(target) = to {
+                let entry = target.entry(token.clone()).or_insert(Value::Null);
+                copy_pointed(child, rest, entry);
+            }
+        }
+        Value::Array(array) => {
+            let (index, child) = match token
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| Some((index, array.get(index)?)))
+            {
+                Some(found) => found,
+                None => return,
+            };
+            if !to.is_array() {
+                *to = Value::Array(Vec::new());
+            }
+            if let Value::Array(target) = to {
+                if target.len() <= index {
+                    target.resize(index + 1, Value::Null);
+                }
+                copy_pointed(child, rest, &mut target[index]);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn validate_attributes(attributes: &[ImportAttributeMapping]) -> ResourceImportResult<()> {
+    for attribute in attributes {
+        if attribute.path.len() < 2 || attribute.path[0] != "root" {
+            return Err(ResourceImportError::InvalidPropPath(
+                attribute.path.join("/"),
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,272 @@
+//! Reads the managed resources of a Terraform state file. Data sources are skipped, since they
+//! are not part of the estate.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::resource_import::{ImportedResource, ResourceImportError, ResourceImportResult};
+
+/// The only version of the state format written by Terraform since 0.12.
+const SUPPORTED_VERSION: u64 = 4;
+
+#[derive(Deserialize, Debug)]
+struct State {
+    version: u64,
+    #[serde(default)]
+    resources: Vec<StateResource>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StateResource {
+    module: Option<String>,
+    mode: String,
+    #[serde(rename = "type")]
+    resource_type: String,
+    name: String,
+    #[serde(default)]
+    instances: Vec<StateInstance>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StateInstance {
+    index_key: Option<Value>,
+    #[serde(default)]
+    attributes: Value,
+    #[serde(default)]
+    sensitive_attributes: Vec<Vec<PathStep>>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PathStep {
+    #[serde(rename = "type")]
+    kind: String,
+    value: Value,
+}
+
+/// Parses a state file, with one [`ImportedResource`] per resource instance. The attributes listed
+/// in `sensitive_attributes` are removed. That list only has the values marked sensitive in the
+/// configuration though: secrets the provider itself knows, like the `private_key_pem` of a
+/// `tls_private_key`, are kept in plain text, which is why only mapped attributes are imported.
+pub fn parse_state(text: &str) -> ResourceImportResult<Vec<ImportedResource>> {
+    let state: State = serde_json::from_str(text)?;
+    if state.version != SUPPORTED_VERSION {
+        return Err(ResourceImportError::UnsupportedTerraformStateVersion(
+            state.version,
+        ));
+    }
+
+    let mut instance_addresses: HashMap<String, Vec<String>> = HashMap::new();
+    let mut parsed = Vec::new();
+    for resource in state.resources {
+        if resource.mode != "managed" {
+            continue;
+        }
+
+        let resource_address = match &resource.module {
+            Some(module) => format!("{module}.{}.{}", resource.resource_type, resource.name),
+            None => format!("{}.{}", resource.resource_type, resource.name),
+        };
+        for instance in resource.instances {
+            let address = match &instance.index_key {
+                Some(Value::Number(index)) => format!("{resource_address}[{index}]"),
+                Some(Value::String(key)) => format!("{resource_address}[{key:?}]"),
+                _ => resource_address.clone(),
+            };
+            instance_addresses
+                .entry(resource_address.clone())
+                .or_default()
+                .push(address.clone());
+
+            let mut attributes = instance.attributes;
+            for path in &instance.sensitive_attributes {
+                remove_path(&mut attributes, path);
+            }
+            parsed.push((
+                ImportedResource {
+                    address,
+                    resource_type: resource.resource_type.clone(),
+                    attributes,
+                    exists: true,
+                    references: Vec::new(),
+                },
+                instance.dependencies,
+            ));
+        }
+    }
+
+    // Dependencies are recorded by resource, so they refer to every instance of the resource.
+    Ok(parsed
+        .into_iter()
+        .map(|(mut resource, dependencies)| {
+            let references: BTreeSet<String> = dependencies
+                .iter()
+                .flat_map(|dependency| {
+                    instance_addresses
+                        .get(dependency)
+                        .cloned()
+                        .unwrap_or_default()
+                })
+                .filter(|address| address != &resource.address)
+                .collect();
+            resource.references = references.into_iter().collect();
+            resource
+        })
+        .collect())
+}
+
+/// Removes the value at the path, given as the steps Terraform records sensitive attributes
+/// with.
+fn remove_path(value: &mut Value, path: &[PathStep]) {
+    let (last, parents) = match path.split_last() {
+        Some(split) => split,
+        None => return,
+    };
+
+    let mut current = value;
+    for step in parents {
+        current = match step_into(current, step) {
+            Some(next) => next,
+            None => return,
+        };
+    }
+
+    match (current, last.kind.as_str(), &last.value) {
+        (Value::Object(object), "get_attr", Value::String(name)) => {
+            object.remove(name);
+        }
+        (Value::Object(object), "index", index) => {
+            if let Some(Value::String(key)) = index.get("value") {
+                object.remove(key);
+            }
+        }
+        (Value::Array(array), "index", index) => {
+            if let Some(element) = index
+                .get("value")
+                .and_then(Value::as_u64)
+                .and_then(|index| array.get_mut(index as usize))
+            {
+                *element = Value::Null;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn step_into<'a>(value: &'a mut Value, step: &PathStep) -> Option<&'a mut Value> {
+    match (value, step.kind.as_str()) {
+        (Value::Object(object), "get_attr") => object.get_mut(step.value.as_str()?),
+        (Value::Object(object), "index") => object.get_mut(step.value.get("value")?.as_str()?),
+        (Value::Array(array), "index") => {
+            array.get_mut(step.value.get("value")?.as_u64()? as usize)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const STATE: &str = r#"{
+        "version": 4,
+        "terraform_version": "1.4.6",
+        "resources": [
+            {
+                "mode": "managed",
+                "type": "aws_vpc",
+                "name": "main",
+                "instances": [
+                    { "attributes": { "id": "vpc-1", "cidr_block": "10.0.0.0/16" } }
+                ]
+            },
+            {
+                "mode": "managed",
+                "type": "aws_subnet",
+                "name": "private",
+                "instances": [
+                    {
+                        "index_key": 0,
+                        "attributes": { "id": "subnet-1", "vpc_id": "vpc-1" },
+                        "dependencies": ["aws_vpc.main"]
+                    },
+                    {
+                        "index_key": 1,
+                        "attributes": { "id": "subnet-2", "vpc_id": "vpc-1" },
+                        "dependencies": ["aws_vpc.main"]
+                    }
+                ]
+            },
+            {
+                "module": "module.db",
+                "mode": "managed",
+                "type": "aws_db_instance",
+                "name": "this",
+                "instances": [
+                    {
+                        "index_key": "primary",
+                        "attributes": { "id": "db-1", "password": "hunter2", "tags": { "Name": "db" } },
+                        "sensitive_attributes": [[{ "type": "get_attr", "value": "password" }]],
+                        "dependencies": ["aws_subnet.private"]
+                    }
+                ]
+            },
+            {
+                "mode": "data",
+                "type": "aws_ami",
+                "name": "ubuntu",
+                "instances": [{ "attributes": { "id": "ami-1" } }]
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn parse() {
+        let resources = parse_state(STATE).expect("could not parse state");
+        let addresses: Vec<&str> = resources
+            .iter()
+            .map(|resource| resource.address.as_str())
+            .collect();
+        assert_eq!(
+            vec![
+                "aws_vpc.main",
+                "aws_subnet.private[0]",
+                "aws_subnet.private[1]",
+                "module.db.aws_db_instance.this[\"primary\"]",
+            ],
+            addresses
+        );
+
+        assert_eq!(vec!["aws_vpc.main".to_owned()], resources[1].references);
+        assert_eq!(
+            vec![
+                "aws_subnet.private[0]".to_owned(),
+                "aws_subnet.private[1]".to_owned()
+            ],
+            resources[3].references
+        );
+        assert!(resources.iter().all(|resource| resource.exists));
+    }
+
+    #[test]
+    fn sensitive_attributes_are_removed() {
+        let resources = parse_state(STATE).expect("could not parse state");
+        let db = &resources[3];
+        assert_eq!(
+            json!({ "id": "db-1", "tags": { "Name": "db" } }),
+            db.attributes
+        );
+    }
+
+    #[test]
+    fn unsupported_version() {
+        assert!(matches!(
+            parse_state(r#"{ "version": 3, "modules": [] }"#),
+            Err(ResourceImportError::UnsupportedTerraformStateVersion(3))
+        ));
+    }
+}
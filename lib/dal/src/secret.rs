@@ -20,8 +20,8 @@ use crate::{
     pk,
     standard_model::{self, TypeHint},
     standard_model_accessor, standard_model_accessor_ro, DalContext, HistoryEvent,
-    HistoryEventError, KeyPair, KeyPairError, StandardModel, StandardModelError, Timestamp,
-    Visibility,
+    HistoryEventError, KeyPair, KeyPairError, StandardModel, StandardModelError, Timestamp, User,
+    UserCapability, UserError, Visibility,
 };
 
 /// Error type for Secrets.
@@ -44,6 +44,8 @@ pub enum SecretError {
     StandardModelError(#[from] StandardModelError),
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
+    #[error("user error: {0}")]
+    User(#[from] UserError),
 }
 
 /// Result type for Secrets.
@@ -244,8 +246,10 @@ impl EncryptedSecret {
     standard_model_accessor_ro!(algorithm, SecretAlgorithm);
 
     /// Decrypts the encrypted secret with its associated [`KeyPair`] and returns a
-    /// [`DecryptedSecret`].
+    /// [`DecryptedSecret`]. Only [`Users`](User) allowed to
+    /// [`decrypt secrets`](UserCapability::DecryptSecrets) can do so.
     pub async fn decrypt(self, ctx: &DalContext) -> SecretResult<DecryptedSecret> {
+        User::ensure_authorized(ctx, UserCapability::DecryptSecrets).await?;
         let key_pair = self.key_pair(ctx).await?;
         self.into_decrypted(key_pair.public_key(), key_pair.secret_key())
     }
@@ -1,4 +1,4 @@
-use crate::{Tenancy, TransactionsError, UserError, UserPk};
+use crate::{ApiTokenError, Tenancy, TransactionsError, UserError, UserPk};
 use chrono::{DateTime, Utc};
 use postgres_types::ToSql;
 use serde::{de::DeserializeOwned, Serialize};
@@ -14,6 +14,8 @@ use crate::{DalContext, HistoryEvent, HistoryEventError, Timestamp, Visibility};
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum StandardModelError {
+    #[error("api token error: {0}")]
+    ApiToken(#[from] ApiTokenError),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     #[error("{0} id {1} is missing when one was expected; it does not exist, is not visible, or is not valid for this tenancy")]
@@ -607,14 +609,18 @@ pub async fn finish_create_from_row<Object: Send + Sync + DeserializeOwned + Sta
     row: PgRow,
 ) -> StandardModelResult<Object> {
     let json: serde_json::Value = row.try_get("object")?;
+    let object: Object = serde_json::from_value(json)?;
     let _history_event = HistoryEvent::new(
         ctx,
         Object::history_event_label(vec!["create"]),
         Object::history_event_message("created"),
-        &serde_json::json![{ "visibility": ctx.visibility() }],
+        &serde_json::json![{
+            "pk": object.pk(),
+            "id": object.id(),
+            "visibility": ctx.visibility(),
+        }],
     )
     .await?;
-    let object: Object = serde_json::from_value(json)?;
     Ok(object)
 }
 
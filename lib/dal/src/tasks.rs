@@ -4,7 +4,9 @@
 // This modules should remain private! Add "pub use" statements to use their contents.
 mod resource_scheduler;
 mod status_receiver;
+mod webhook_dispatcher;
 
 pub use resource_scheduler::{ResourceScheduler, ResourceSchedulerError};
 pub use status_receiver::client::StatusReceiverClient;
 pub use status_receiver::{StatusReceiver, StatusReceiverError, StatusReceiverRequest};
+pub use webhook_dispatcher::{WebhookDispatcher, WebhookDispatcherError};
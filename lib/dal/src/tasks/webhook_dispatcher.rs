@@ -0,0 +1,215 @@
+//! The [`WebhookDispatcher`] is a spawned, "long-running" [tokio](https://tokio.rs/) task that
+//! queues a [`WebhookDelivery`] for every [`WsEvent`] a [`Webhook`](crate::Webhook) is subscribed
+//! to, and posts the deliveries that are due.
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use nats_subscriber::{Subscriber, SubscriberError};
+use si_data_nats::NatsError;
+use si_data_pg::{PgError, PgPoolError};
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::{sync::broadcast, task::JoinHandle, time};
+
+use crate::{
+    DalContextBuilder, ServicesContext, TransactionsError, Webhook, WebhookClient, WebhookDelivery,
+    WebhookError, WsEvent,
+};
+
+/// The [NATS](https://nats.io) subject [`events`](WsEvent) are published on, for every
+/// [`Workspace`](crate::Workspace).
+const WEBHOOK_DISPATCHER_EVENT_SUBJECT: &str = "si.workspace_pk.*.event";
+/// The queue name for [NATS](https://nats.io), so that only one dispatcher queues deliveries for
+/// any given event.
+const WEBHOOK_DISPATCHER_QUEUE_NAME: &str = "webhookDispatcher";
+/// How often due deliveries are looked for.
+const WEBHOOK_DISPATCHER_INTERVAL: Duration = Duration::from_secs(5);
+/// How many due deliveries are claimed at a time.
+const WEBHOOK_DISPATCHER_BATCH_SIZE: usize = 50;
+/// How many deliveries of a batch are attempted at once. Each one briefly takes a database
+/// connection before and after posting, so this stays well below the size of the pool. A batch
+/// of slow webhooks still finishes well within the lease.
+const WEBHOOK_DISPATCHER_CONCURRENCY: usize = 8;
+/// How long a claimed delivery is left alone by other dispatchers, in case this one dies while
+/// attempting it.
+const WEBHOOK_DISPATCHER_LEASE: Duration = Duration::from_secs(60);
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WebhookDispatcherError {
+    #[error(transparent)]
+    Nats(#[from] NatsError),
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error(transparent)]
+    PgPool(#[from] PgPoolError),
+    #[error(transparent)]
+    Subscriber(#[from] SubscriberError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+    #[error(transparent)]
+    Webhook(#[from] WebhookError),
+}
+
+pub type WebhookDispatcherResult<T> = Result<T, WebhookDispatcherError>;
+
+/// Queues and posts [`webhook deliveries`](WebhookDelivery). See the
+/// [`module`](self) for more information.
+#[derive(Debug)]
+pub struct WebhookDispatcher {
+    /// The [`ServicesContext`](crate::ServicesContext) needed to assemble a
+    /// [`DalContext`](crate::DalContext).
+    services_context: ServicesContext,
+    /// A [NATS](https://nats.io) subscriber to listen for [`events`](WsEvent).
+    events: Subscriber<WsEvent>,
+    /// The client deliveries are posted with.
+    client: WebhookClient,
+}
+
+impl WebhookDispatcher {
+    /// Create a new [`WebhookDispatcher`].
+    pub async fn new(services_context: ServicesContext) -> WebhookDispatcherResult<Self> {
+        let nats = services_context.nats_conn();
+        let events: Subscriber<WsEvent> = Subscriber::create(WEBHOOK_DISPATCHER_EVENT_SUBJECT)
+            .queue_name(WEBHOOK_DISPATCHER_QUEUE_NAME)
+            .start(nats)
+            .await?;
+        Ok(Self {
+            services_context,
+            events,
+            client: WebhookClient::new(),
+        })
+    }
+
+    /// A _synchronous_ function that starts the [`dispatcher`](Self) in a new asynchronous task.
+    pub fn start(self, shutdown_broadcast_rx: broadcast::Receiver<()>) {
+        info!("starting webhook dispatcher");
+        tokio::spawn(self.start_task(shutdown_broadcast_rx));
+    }
+
+    /// The "inner" portion of [`Self::start()`] that contains the core loop.
+    ///
+    /// This should only be called by [`Self::start()`].
+    #[instrument(name = "webhook_dispatcher.start_task", skip_all, level = "debug")]
+    async fn start_task(mut self, mut shutdown_broadcast_rx: broadcast::Receiver<()>) {
+        let mut interval = time::interval(WEBHOOK_DISPATCHER_INTERVAL);
+        // Deliveries are dispatched in their own task, so that events keep being queued while
+        // they are attempted. A new batch is only claimed once the previous one is done.
+        let mut dispatching: Option<JoinHandle<()>> = None;
+        loop {
+            tokio::select! {
+                _ = shutdown_broadcast_rx.recv() => {
+                    trace!("the webhook dispatcher task received shutdown");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if dispatching.as_ref().map_or(true, JoinHandle::is_finished) {
+                        let ctx_builder = self.services_context.clone().into_builder(false);
+                        let client = self.client.clone();
+                        dispatching = Some(tokio::spawn(async move {
+                            if let Err(err) = Self::dispatch_due(ctx_builder, &client).await {
+                                error!(error = ?err, "webhook dispatcher failed to dispatch due deliveries");
+                            }
+                        }));
+                    }
+                }
+                event = self.events.next() => {
+                    match event {
+                        Some(Ok(event)) => {
+                            if let Err(err) = Self::enqueue(
+                                self.services_context.clone().into_builder(false),
+                                &event.payload,
+                            ).await {
+                                error!(error = ?err, "webhook dispatcher failed to queue deliveries");
+                            }
+                        }
+                        Some(Err(err)) => {
+                            warn!(error = ?err, "next webhook dispatcher event errored");
+                        }
+                        None => {
+                            trace!("webhook dispatcher events subscriber stream has closed");
+                            break;
+                        }
+                    }
+                }
+                else => {
+                    trace!("returning with all select arms closed");
+                    break
+                }
+            }
+        }
+
+        // Unsubscribe from subscriber without draining the channel
+        if let Err(e) = self.events.unsubscribe_after(0).await {
+            error!("could not unsubscribe from nats: {:?}", e);
+        }
+    }
+
+    async fn enqueue(
+        ctx_builder: DalContextBuilder,
+        event: &WsEvent,
+    ) -> WebhookDispatcherResult<()> {
+        let ctx = ctx_builder.build_default().await?;
+        let deliveries = Webhook::enqueue_for_event(&ctx, event).await?;
+        if !deliveries.is_empty() {
+            ctx.commit().await?;
+            debug!(count = deliveries.len(), "queued webhook deliveries");
+        }
+        Ok(())
+    }
+
+    /// Attempts every delivery that is due, a few at a time. No database connection is held
+    /// while posting, so a slow webhook never holds connections or locks. A delivery that can't be attempted is
+    /// logged and left for its lease to run out, without holding up the others.
+    async fn dispatch_due(
+        ctx_builder: DalContextBuilder,
+        client: &WebhookClient,
+    ) -> WebhookDispatcherResult<()> {
+        let ctx = ctx_builder.build_default().await?;
+        let deliveries = WebhookDelivery::claim_due(
+            &ctx,
+            WEBHOOK_DISPATCHER_BATCH_SIZE as i64,
+            WEBHOOK_DISPATCHER_LEASE,
+        )
+        .await?;
+        ctx.commit().await?;
+
+        futures::stream::iter(deliveries)
+            .for_each_concurrent(WEBHOOK_DISPATCHER_CONCURRENCY, |delivery| {
+                let ctx_builder = ctx_builder.clone();
+                async move {
+                    let pk = delivery.pk();
+                    if let Err(err) = Self::dispatch(ctx_builder, delivery, client).await {
+                        error!(error = ?err, webhook_delivery.pk = %pk, "webhook dispatcher failed to attempt delivery");
+                    }
+                }
+            })
+            .await;
+        Ok(())
+    }
+
+    async fn dispatch(
+        ctx_builder: DalContextBuilder,
+        mut delivery: WebhookDelivery,
+        client: &WebhookClient,
+    ) -> WebhookDispatcherResult<()> {
+        let webhook = {
+            let ctx = ctx_builder.build_default().await?;
+            let webhook = Webhook::get_by_pk_in_any_workspace(&ctx, delivery.webhook_pk()).await?;
+            ctx.rollback().await?;
+            match webhook {
+                Some(webhook) => webhook,
+                // The webhook was deleted along with its deliveries since they were claimed.
+                None => return Ok(()),
+            }
+        };
+
+        let attempt = delivery.post(&webhook, client).await?;
+
+        let ctx = ctx_builder.build_default().await?;
+        delivery.record(&ctx, attempt).await?;
+        ctx.commit().await?;
+        Ok(())
+    }
+}
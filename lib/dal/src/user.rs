@@ -1,26 +1,38 @@
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
 use si_data_pg::PgError;
+use strum::{AsRefStr, Display, EnumString};
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::task::JoinError;
 
 use crate::{
-    jwt_key::JwtKeyError, pk, standard_model_accessor_ro, DalContext, HistoryEvent,
-    HistoryEventError, JwtPublicSigningKey, Tenancy, Timestamp, TransactionsError, WorkspacePk,
+    jwt_key::JwtKeyError, pk, standard_model_accessor_ro, ApiToken, ApiTokenError, ApiTokenPk,
+    DalContext, HistoryActor, HistoryEvent, HistoryEventError, JwtPublicSigningKey, Tenancy,
+    Timestamp, TransactionsError, WorkspacePk,
 };
 
 const USER_GET_BY_PK: &str = include_str!("queries/user/get_by_pk.sql");
+const USER_GET_WORKSPACE_ROLE: &str = include_str!("queries/user/get_workspace_role.sql");
+const USER_LIST_FOR_WORKSPACE: &str = include_str!("queries/user/list_for_workspace.sql");
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum UserError {
+    #[error("api token error: {0}")]
+    ApiToken(#[from] Box<ApiTokenError>),
+    #[error("api token {0} is not allowed to {1}")]
+    ApiTokenUnauthorized(ApiTokenPk, UserCapability),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
+    #[error("invalid workspace role: {0}")]
+    InvalidWorkspaceRole(String),
     #[error("failed to join long lived async task; bug!")]
     Join(#[from] JoinError),
     #[error(transparent)]
     JwtKey(#[from] JwtKeyError),
+    #[error("workspace must keep at least one owner")]
+    LastOwner,
     #[error("nats txn error: {0}")]
     Nats(#[from] NatsError),
     #[error("user not found in tenancy: {0} {1:?}")]
@@ -33,12 +45,76 @@ pub enum UserError {
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
+    #[error("user {0} is not allowed to {1}")]
+    Unauthorized(UserPk, UserCapability),
 }
 
 pub type UserResult<T> = Result<T, UserError>;
 
 pk!(UserPk);
 
+/// The role of a [`User`] in a [`Workspace`](crate::Workspace), which decides what they are
+/// allowed to do in it. See [`WorkspaceRole::can`].
+#[remain::sorted]
+#[derive(
+    AsRefStr, Clone, Copy, Debug, Deserialize, Display, EnumString, Eq, Hash, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum WorkspaceRole {
+    /// Can do everything an editor can, and can also apply change sets, run fixes and read the
+    /// audit log.
+    Approver,
+    /// Can make changes in change sets and use secrets, but can't apply them to head.
+    Editor,
+    /// Can do everything, including managing the roles of the other members and the webhooks.
+    Owner,
+    /// Can only look.
+    Viewer,
+}
+
+impl WorkspaceRole {
+    pub fn can(&self, capability: UserCapability) -> bool {
+        match capability {
+            UserCapability::Read => true,
+            UserCapability::DecryptSecrets | UserCapability::Write => !matches!(self, Self::Viewer),
+            UserCapability::ApplyChangeSet
+            | UserCapability::ReadAuditLog
+            | UserCapability::RunFix => {
+                matches!(self, Self::Approver | Self::Owner)
+            }
+            UserCapability::ManageRoles | UserCapability::ManageWebhooks => {
+                matches!(self, Self::Owner)
+            }
+        }
+    }
+}
+
+/// Something a [`User`] may or may not be allowed to do in a [`Workspace`](crate::Workspace),
+/// depending on their [`WorkspaceRole`].
+#[remain::sorted]
+#[derive(AsRefStr, Clone, Copy, Debug, Deserialize, Display, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "snake_case")]
+pub enum UserCapability {
+    ApplyChangeSet,
+    DecryptSecrets,
+    ManageRoles,
+    ManageWebhooks,
+    Read,
+    ReadAuditLog,
+    RunFix,
+    Write,
+}
+
+/// A [`User`] that belongs to a [`Workspace`](crate::Workspace), along with their role in it.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMember {
+    pub user: User,
+    pub role: WorkspaceRole,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct User {
     pk: UserPk,
@@ -117,9 +193,185 @@ impl User {
         }
     }
 
-    pub async fn authorize(_ctx: &DalContext, _user_pk: &UserPk) -> UserResult<bool> {
-        // TODO(paulo,theo): implement capabilities through auth0
-        Ok(true)
+    /// Gets the [`User`] on whose behalf the [`HistoryActor`] acts: the [`User`] itself, or the
+    /// creator of the [`ApiToken`]. The system doesn't act on behalf of anyone.
+    pub async fn get_for_history_actor(
+        ctx: &DalContext,
+        history_actor: &HistoryActor,
+    ) -> UserResult<Option<Self>> {
+        match history_actor {
+            HistoryActor::ApiToken(api_token_pk) => {
+                match ApiToken::get_by_pk(ctx, *api_token_pk)
+                    .await
+                    .map_err(Box::new)?
+                {
+                    Some(api_token) => Self::get_by_pk(ctx, api_token.created_by_user_pk()).await,
+                    None => Ok(None),
+                }
+            }
+            HistoryActor::SystemInit => Ok(None),
+            HistoryActor::User(user_pk) => Self::get_by_pk(ctx, *user_pk).await,
+        }
+    }
+
+    /// Returns whether the [`User`] is allowed to do the given thing in the
+    /// [`Workspace`](crate::Workspace) of the [`Tenancy`] of the [`DalContext`]. Users that
+    /// don't belong to the [`Workspace`](crate::Workspace) aren't allowed to do anything.
+    pub async fn authorize(
+        ctx: &DalContext,
+        user_pk: &UserPk,
+        capability: UserCapability,
+    ) -> UserResult<bool> {
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(UserError::NoWorkspaceInTenancy)?;
+        Ok(Self::workspace_role(ctx, *user_pk, workspace_pk)
+            .await?
+            .map_or(false, |role| role.can(capability)))
+    }
+
+    /// Errors with [`UserError::Unauthorized`] if the [`HistoryActor`] of the [`DalContext`] is a
+    /// [`User`] that is not allowed to do the given thing, and with
+    /// [`UserError::ApiTokenUnauthorized`] if it is an [`ApiToken`] that is not. The system is
+    /// allowed to do anything.
+    pub async fn ensure_authorized(ctx: &DalContext, capability: UserCapability) -> UserResult<()> {
+        match ctx.history_actor() {
+            HistoryActor::SystemInit => Ok(()),
+            HistoryActor::ApiToken(api_token_pk) => {
+                let authorized = match ApiToken::get_by_pk(ctx, *api_token_pk)
+                    .await
+                    .map_err(Box::new)?
+                {
+                    Some(api_token) => api_token
+                        .authorize(ctx, capability)
+                        .await
+                        .map_err(Box::new)?,
+                    None => false,
+                };
+                if authorized {
+                    Ok(())
+                } else {
+                    Err(UserError::ApiTokenUnauthorized(*api_token_pk, capability))
+                }
+            }
+            HistoryActor::User(user_pk) => {
+                if Self::authorize(ctx, user_pk, capability).await? {
+                    Ok(())
+                } else {
+                    Err(UserError::Unauthorized(*user_pk, capability))
+                }
+            }
+        }
+    }
+
+    pub async fn workspace_role(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        workspace_pk: WorkspacePk,
+    ) -> UserResult<Option<WorkspaceRole>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(USER_GET_WORKSPACE_ROLE, &[&user_pk, &workspace_pk])
+            .await?;
+        match row {
+            Some(row) => {
+                let role: String = row.try_get("role")?;
+                Ok(Some(
+                    role.parse()
+                        .map_err(|_| UserError::InvalidWorkspaceRole(role))?,
+                ))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Lists every [`User`] that belongs to the [`Workspace`](crate::Workspace) of the
+    /// [`Tenancy`] of the [`DalContext`], sorted by name.
+    pub async fn list_members(ctx: &DalContext) -> UserResult<Vec<WorkspaceMember>> {
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(UserError::NoWorkspaceInTenancy)?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(USER_LIST_FOR_WORKSPACE, &[&workspace_pk])
+            .await?;
+
+        let mut members = Vec::with_capacity(rows.len());
+        for row in rows {
+            let json: serde_json::Value = row.try_get("object")?;
+            let role: String = row.try_get("role")?;
+            members.push(WorkspaceMember {
+                user: serde_json::from_value(json)?,
+                role: role
+                    .parse()
+                    .map_err(|_| UserError::InvalidWorkspaceRole(role))?,
+            });
+        }
+        Ok(members)
+    }
+
+    /// Changes the role of the [`User`] in the [`Workspace`](crate::Workspace) of the
+    /// [`Tenancy`] of the [`DalContext`]. Only owners can change roles, and the last owner of a
+    /// [`Workspace`](crate::Workspace) can't give up ownership.
+    #[instrument(skip_all)]
+    pub async fn set_workspace_role(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        role: WorkspaceRole,
+    ) -> UserResult<()> {
+        Self::ensure_authorized(ctx, UserCapability::ManageRoles).await?;
+
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(UserError::NoWorkspaceInTenancy)?;
+        let previous_role = Self::workspace_role(ctx, user_pk, workspace_pk)
+            .await?
+            .ok_or_else(|| UserError::NotFoundInTenancy(user_pk, *ctx.tenancy()))?;
+        if previous_role == role {
+            return Ok(());
+        }
+
+        if previous_role == WorkspaceRole::Owner {
+            let owners = Self::list_members(ctx)
+                .await?
+                .into_iter()
+                .filter(|member| member.role == WorkspaceRole::Owner)
+                .count();
+            if owners <= 1 {
+                return Err(UserError::LastOwner);
+            }
+        }
+
+        ctx.txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT user_set_workspace_role_v1($1, $2, $3)",
+                &[&user_pk, &workspace_pk, &role.as_ref()],
+            )
+            .await?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "user.role.update".to_owned(),
+            "User role updated".to_owned(),
+            &serde_json::json![{
+                "userPk": user_pk,
+                "workspacePk": workspace_pk,
+                "previousRole": previous_role,
+                "role": role,
+            }],
+        )
+        .await?;
+
+        Ok(())
     }
 
     pub async fn associate_workspace(
@@ -6,8 +6,13 @@
 //! multiple "fields" in a tree (abstractly defined here, usually corresponding to a
 //! [`SchemaVariant`](crate::SchemaVariant).
 //!
-//! In instances where there is a dependency on information from more than that single "field",
-//! then a ["qualification"](crate::qualification) is used instead of a "validation".
+//! A validation may also read other "fields" of the same [`Component`](crate::Component),
+//! either [`Props`](crate::Prop) or input [`Sockets`](crate::Socket), through the
+//! [`inputs`](crate::ValidationPrototypeInput) of its
+//! [`ValidationPrototype`](crate::ValidationPrototype). Such a validation is re-run whenever any
+//! of its inputs change and its failures are reported on every participating
+//! [`Prop`](crate::Prop). When the check is about the shape of the whole tree, a
+//! ["qualification"](crate::qualification) is used instead of a "validation".
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -18,6 +23,7 @@ use crate::{
     SchemaVariantId,
 };
 
+pub mod json_schema;
 pub mod prototype;
 pub mod resolver;
 
@@ -49,14 +55,44 @@ pub type ValidationConstructorResult<T> = Result<T, ValidationConstructorError>;
 #[remain::sorted]
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub enum Validation {
+    /// Validate that every item in the "value" array is distinct.
+    ArrayItemsAreUnique { value: Option<Vec<Value>> },
+    /// Validate that the length of the "value" array is within the (inclusive) bounds. A missing
+    /// bound is unbounded.
+    ArrayLengthIsBetween {
+        value: Option<Vec<Value>>,
+        lower_bound: Option<i64>,
+        upper_bound: Option<i64>,
+    },
     /// Validate that the "value" integer is between the lower and upper bound integers.
     IntegerIsBetweenTwoIntegers {
         value: Option<i64>,
         lower_bound: i64,
         upper_bound: i64,
     },
+    /// Validate that the "value" integer is within the (inclusive) bounds. A missing bound is
+    /// unbounded.
+    IntegerIsInRange {
+        value: Option<i64>,
+        lower_bound: Option<i64>,
+        upper_bound: Option<i64>,
+    },
     /// Validate that the "value" integer is not empty
     IntegerIsNotEmpty { value: Option<i64> },
+    /// Validate that every key of the "value" map matches the regular expression.
+    MapKeysMatchRegex {
+        value: Option<serde_json::Map<String, Value>>,
+        pattern: String,
+    },
+    /// Validate that the "value" conforms to the JSON schema. See
+    /// [`json_schema`](crate::validation::json_schema) for the supported keywords.
+    ObjectMatchesJsonSchema { value: Option<Value>, schema: Value },
+    /// Validate that the "value" CIDR block lies within the CIDR block (or any of the CIDR blocks)
+    /// provided by the named input, such as a subnet within the CIDR block of its VPC.
+    StringCidrIsWithinInput {
+        value: Option<String>,
+        input: String,
+    },
     /// Validate that the "value" string is the same as the expected string.
     StringEquals {
         value: Option<String>,
@@ -83,8 +119,39 @@ pub enum Validation {
     StringIsHexColor { value: Option<String> },
     /// Validate that the "value" string is not empty
     StringIsNotEmpty { value: Option<String> },
+    /// Validate that the "value" string is shaped like an AWS ARN, optionally for the given
+    /// service (e.g. "iam").
+    StringIsValidArn {
+        value: Option<String>,
+        service: Option<String>,
+    },
+    /// Validate that the "value" string is an IPv4 or IPv6 CIDR block (e.g. "10.0.0.0/16").
+    StringIsValidCidr { value: Option<String> },
+    /// Validate that the "value" string is a valid DNS hostname.
+    StringIsValidHostname { value: Option<String> },
     /// Validate that the "value" string is a valid [IpAddr](std::net::IpAddr).
     StringIsValidIpAddr { value: Option<String> },
+    /// Validate that the "value" string is an absolute [Url](url::Url).
+    StringIsValidUrl { value: Option<String> },
+    /// Validate that the length (in characters) of the "value" string is within the (inclusive)
+    /// bounds. A missing bound is unbounded.
+    StringLengthIsBetween {
+        value: Option<String>,
+        lower_bound: Option<i64>,
+        upper_bound: Option<i64>,
+    },
+    /// Validate that the "value" string matches the regular expression.
+    StringMatchesRegex {
+        value: Option<String>,
+        pattern: String,
+    },
+    /// Validate that the "value" is present whenever the named input equals the expected value,
+    /// such as "port" being required when "protocol" is "tcp".
+    ValueIsRequiredWhenInputEquals {
+        value: Option<Value>,
+        input: String,
+        expected: Value,
+    },
 }
 
 impl Validation {
@@ -92,6 +159,18 @@ impl Validation {
     /// remaining fields' values will be identical.
     pub fn update_value(self, value: &Option<Value>) -> ValidationConstructorResult<Self> {
         let validation = match self {
+            Validation::ArrayItemsAreUnique { value: _ } => Validation::ArrayItemsAreUnique {
+                value: Self::value_as_array(value)?,
+            },
+            Validation::ArrayLengthIsBetween {
+                value: _,
+                lower_bound,
+                upper_bound,
+            } => Validation::ArrayLengthIsBetween {
+                value: Self::value_as_array(value)?,
+                lower_bound,
+                upper_bound,
+            },
             Validation::IntegerIsBetweenTwoIntegers {
                 value: _,
                 lower_bound,
@@ -101,9 +180,34 @@ impl Validation {
                 lower_bound,
                 upper_bound,
             },
+            Validation::IntegerIsInRange {
+                value: _,
+                lower_bound,
+                upper_bound,
+            } => Validation::IntegerIsInRange {
+                value: Self::value_as_i64(value)?,
+                lower_bound,
+                upper_bound,
+            },
             Validation::IntegerIsNotEmpty { value: _ } => Validation::IntegerIsNotEmpty {
                 value: Self::value_as_i64(value)?,
             },
+            Validation::MapKeysMatchRegex { value: _, pattern } => Validation::MapKeysMatchRegex {
+                value: Self::value_as_map(value)?,
+                pattern,
+            },
+            Validation::ObjectMatchesJsonSchema { value: _, schema } => {
+                Validation::ObjectMatchesJsonSchema {
+                    value: value.clone(),
+                    schema,
+                }
+            }
+            Validation::StringCidrIsWithinInput { value: _, input } => {
+                Validation::StringCidrIsWithinInput {
+                    value: Self::value_as_string(value)?,
+                    input,
+                }
+            }
             Validation::StringEquals { value: _, expected } => Validation::StringEquals {
                 value: Self::value_as_string(value)?,
                 expected,
@@ -130,10 +234,62 @@ impl Validation {
             Validation::StringIsNotEmpty { value: _ } => Validation::StringIsNotEmpty {
                 value: Self::value_as_string(value)?,
             },
+            Validation::StringIsValidArn { value: _, service } => Validation::StringIsValidArn {
+                value: Self::value_as_string(value)?,
+                service,
+            },
+            Validation::StringIsValidCidr { value: _ } => Validation::StringIsValidCidr {
+                value: Self::value_as_string(value)?,
+            },
+            Validation::StringIsValidHostname { value: _ } => Validation::StringIsValidHostname {
+                value: Self::value_as_string(value)?,
+            },
+            Validation::StringIsValidUrl { value: _ } => Validation::StringIsValidUrl {
+                value: Self::value_as_string(value)?,
+            },
+            Validation::StringLengthIsBetween {
+                value: _,
+                lower_bound,
+                upper_bound,
+            } => Validation::StringLengthIsBetween {
+                value: Self::value_as_string(value)?,
+                lower_bound,
+                upper_bound,
+            },
+            Validation::StringMatchesRegex { value: _, pattern } => {
+                Validation::StringMatchesRegex {
+                    value: Self::value_as_string(value)?,
+                    pattern,
+                }
+            }
+            Validation::ValueIsRequiredWhenInputEquals {
+                value: _,
+                input,
+                expected,
+            } => Validation::ValueIsRequiredWhenInputEquals {
+                value: value.clone(),
+                input,
+                expected,
+            },
         };
         Ok(validation)
     }
 
+    /// Refuses a validation which would check less than it says it does, such as a
+    /// [`JSON schema`](Self::ObjectMatchesJsonSchema) using
+    /// [`unsupported keywords`](json_schema::unsupported_keywords).
+    pub fn ensure_supported(&self) -> prototype::ValidationPrototypeResult<()> {
+        if let Validation::ObjectMatchesJsonSchema { schema, .. } = self {
+            let unsupported = json_schema::unsupported_keywords(schema);
+            if !unsupported.is_empty() {
+                return Err(
+                    prototype::ValidationPrototypeError::UnsupportedJsonSchemaKeywords(unsupported),
+                );
+            }
+        }
+        Ok(())
+    }
+
     fn value_as_string(maybe_value: &Option<Value>) -> ValidationConstructorResult<Option<String>> {
         match maybe_value {
             Some(value) => match value.as_str() {
@@ -147,6 +303,36 @@ impl Validation {
         }
     }
 
+    fn value_as_array(
+        maybe_value: &Option<Value>,
+    ) -> ValidationConstructorResult<Option<Vec<Value>>> {
+        match maybe_value {
+            Some(value) => match value.as_array() {
+                Some(success_value) => Ok(Some(success_value.to_owned())),
+                None => Err(ValidationConstructorError::InvalidValueKind(
+                    "Array",
+                    value.clone(),
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn value_as_map(
+        maybe_value: &Option<Value>,
+    ) -> ValidationConstructorResult<Option<serde_json::Map<String, Value>>> {
+        match maybe_value {
+            Some(value) => match value.as_object() {
+                Some(success_value) => Ok(Some(success_value.to_owned())),
+                None => Err(ValidationConstructorError::InvalidValueKind(
+                    "Map",
+                    value.clone(),
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+
     fn value_as_i64(maybe_value: &Option<Value>) -> ValidationConstructorResult<Option<i64>> {
         match maybe_value {
             Some(value) => match value.as_i64() {
@@ -164,26 +350,54 @@ impl Validation {
 #[remain::sorted]
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub enum ValidationErrorKind {
+    ArrayItemsNotUnique,
+    ArrayLengthNotInRange,
+    CidrNotWithinInput,
     IntegerNotInBetweenTwoIntegers,
+    IntegerNotInRange,
+    InvalidArn,
+    InvalidCidr,
     InvalidHexString,
+    InvalidHostname,
     InvalidIpAddr,
+    InvalidRegex,
+    InvalidUrl,
     JsValidation,
+    JsonSchemaMismatch,
+    MapKeyDoesNotMatchRegex,
     StringDoesNotEqual,
     StringDoesNotHavePrefix,
+    StringDoesNotMatchRegex,
+    StringLengthNotInRange,
     StringNotInStringArray,
     ValueMustBePresent,
+    ValueRequiredByInput,
 }
 
 impl ValidationErrorKind {
     pub fn as_str(&self) -> &'static str {
         match self {
+            Self::ArrayItemsNotUnique => "ArrayItemsNotUnique",
+            Self::ArrayLengthNotInRange => "ArrayLengthNotInRange",
+            Self::CidrNotWithinInput => "CidrNotWithinInput",
             Self::IntegerNotInBetweenTwoIntegers => "IntegerNotInBetweenTwoIntegers",
+            Self::IntegerNotInRange => "IntegerNotInRange",
+            Self::InvalidArn => "InvalidArn",
+            Self::InvalidCidr => "InvalidCidr",
             Self::InvalidHexString => "InvalidHexString",
+            Self::InvalidHostname => "InvalidHostname",
             Self::InvalidIpAddr => "InvalidIpAddr",
+            Self::InvalidRegex => "InvalidRegex",
+            Self::InvalidUrl => "InvalidUrl",
+            Self::JsonSchemaMismatch => "JsonSchemaMismatch",
+            Self::MapKeyDoesNotMatchRegex => "MapKeyDoesNotMatchRegex",
             Self::StringDoesNotEqual => "StringDoesNotEqual",
             Self::StringDoesNotHavePrefix => "StringDoesNotHavePrefix",
+            Self::StringDoesNotMatchRegex => "StringDoesNotMatchRegex",
+            Self::StringLengthNotInRange => "StringLengthNotInRange",
             Self::StringNotInStringArray => "StringNotInStringArray",
             Self::ValueMustBePresent => "ValueMustBePresent",
+            Self::ValueRequiredByInput => "ValueRequiredByInput",
             Self::JsValidation => "JsValidation",
         }
     }
@@ -205,10 +419,13 @@ pub async fn create_validation(
     schema_variant_id: SchemaVariantId,
 ) -> prototype::ValidationPrototypeResult<prototype::ValidationPrototype> {
     let (validation_func_id, validation_args) = match validation_kind {
-        ValidationKind::Builtin(validation) => (
-            builtin_func_id,
-            serde_json::to_value(FuncBackendValidationArgs::new(validation))?,
-        ),
+        ValidationKind::Builtin(validation) => {
+            validation.ensure_supported()?;
+            (
+                builtin_func_id,
+                serde_json::to_value(FuncBackendValidationArgs::new(validation))?,
+            )
+        }
 
         ValidationKind::Custom(func_id) => (func_id, serde_json::json!(null)),
     };
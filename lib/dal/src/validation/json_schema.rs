@@ -0,0 +1,442 @@
+//! A small, dependency-free [JSON Schema](https://json-schema.org) checker used by
+//! [`Validation::ObjectMatchesJsonSchema`](crate::validation::Validation::ObjectMatchesJsonSchema).
+//!
+//! Only the keywords which describe the shape of a value are supported:
+//!
+//! - `type` (a single type name or an array of them)
+//! - `enum` and `const`
+//! - `properties`, `required` and `additionalProperties` (a boolean or a schema)
+//! - `items`, `minItems`, `maxItems` and `uniqueItems`
+//! - `minLength`, `maxLength` and `pattern`
+//! - `minimum`, `maximum`, `exclusiveMinimum` and `exclusiveMaximum`
+//! - `allOf`, `anyOf` and `not`
+//!
+//! Annotations (`title`, `description`, `default`, `examples`, `$comment`, `$schema` and `$id`)
+//! are allowed but have no effect. Any other keyword (such as `$ref`, `oneOf`,
+//! `patternProperties` or `format`) would not be checked, so [`unsupported_keywords`] reports
+//! them and validations using them are refused when they are created.
+
+use regex::Regex;
+use serde_json::Value;
+
+/// Checks "value" against "schema", returning a message for every violation found. Each message
+/// starts with the [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) of the offending value.
+pub fn validate(schema: &Value, value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(schema, value, "", &mut errors);
+    errors
+}
+
+/// Returns the [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) of every keyword in
+/// "schema" which [`validate`] does not check, so that a schema is never checked less strictly
+/// than it reads.
+pub fn unsupported_keywords(schema: &Value) -> Vec<String> {
+    let mut unsupported = Vec::new();
+    unsupported_keywords_at(schema, "", &mut unsupported);
+    unsupported
+}
+
+fn unsupported_keywords_at(schema: &Value, pointer: &str, unsupported: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Object(schema) => schema,
+        // Booleans are schemas too, anything else is not checked at all.
+        Value::Bool(_) => return,
+        _ => {
+            unsupported.push(display(pointer).to_owned());
+            return;
+        }
+    };
+
+    for (keyword, child) in schema {
+        let child_pointer = format!("{pointer}/{}", escape(keyword));
+        match keyword.as_str() {
+            "type" | "enum" | "const" | "required" | "minItems" | "maxItems" | "uniqueItems"
+            | "minLength" | "maxLength" | "pattern" | "minimum" | "maximum"
+            | "exclusiveMinimum" | "exclusiveMaximum" => {}
+            "title" | "description" | "default" | "examples" | "$comment" | "$schema" | "$id" => {}
+            "properties" => match child {
+                Value::Object(properties) => {
+                    for (key, property) in properties {
+                        unsupported_keywords_at(
+                            property,
+                            &format!("{child_pointer}/{}", escape(key)),
+                            unsupported,
+                        );
+                    }
+                }
+                _ => unsupported.push(child_pointer),
+            },
+            // Only the single schema form of "items" is checked, not the tuple form.
+            "additionalProperties" | "items" | "not" => {
+                unsupported_keywords_at(child, &child_pointer, unsupported)
+            }
+            "allOf" | "anyOf" => match child {
+                Value::Array(schemas) => {
+                    for (index, sub_schema) in schemas.iter().enumerate() {
+                        unsupported_keywords_at(
+                            sub_schema,
+                            &format!("{child_pointer}/{index}"),
+                            unsupported,
+                        );
+                    }
+                }
+                _ => unsupported.push(child_pointer),
+            },
+            _ => unsupported.push(child_pointer),
+        }
+    }
+}
+
+fn validate_at(schema: &Value, value: &Value, pointer: &str, errors: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(format!("{}: no value is allowed here", display(pointer)));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|kind| is_type(value, kind)) {
+            errors.push(format!(
+                "{}: expected {}, found {}",
+                display(pointer),
+                allowed.join(" or "),
+                type_name(value)
+            ));
+            // The remaining keywords all assume the type matched
+            return;
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            errors.push(format!(
+                "{}: {value} is not one of {}",
+                display(pointer),
+                Value::Array(options.clone())
+            ));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!("{}: expected {expected}", display(pointer)));
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(key) {
+                        errors.push(format!(
+                            "{}: missing required property {key}",
+                            display(pointer)
+                        ));
+                    }
+                }
+            }
+
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, child) in object {
+                let child_pointer = format!("{pointer}/{}", escape(key));
+                match properties.and_then(|properties| properties.get(key)) {
+                    Some(child_schema) => validate_at(child_schema, child, &child_pointer, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => errors.push(format!(
+                            "{}: property {key} is not allowed",
+                            display(pointer)
+                        )),
+                        Some(additional) => validate_at(additional, child, &child_pointer, errors),
+                        None => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            check_bounds(
+                items.len(),
+                schema.get("minItems"),
+                schema.get("maxItems"),
+                "items",
+                pointer,
+                errors,
+            );
+            if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+                for (index, item) in items.iter().enumerate() {
+                    if items[..index].contains(item) {
+                        errors.push(format!("{}: item {index} is a duplicate", display(pointer)));
+                    }
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_at(item_schema, item, &format!("{pointer}/{index}"), errors);
+                }
+            }
+        }
+        Value::String(string) => {
+            check_bounds(
+                string.chars().count(),
+                schema.get("minLength"),
+                schema.get("maxLength"),
+                "characters",
+                pointer,
+                errors,
+            );
+            if let Some(Value::String(pattern)) = schema.get("pattern") {
+                match Regex::new(pattern) {
+                    Ok(re) if re.is_match(string) => {}
+                    Ok(_) => errors.push(format!(
+                        "{}: {string:?} does not match pattern {pattern}",
+                        display(pointer)
+                    )),
+                    Err(err) => errors.push(format!(
+                        "{}: schema pattern {pattern} is invalid: {err}",
+                        display(pointer)
+                    )),
+                }
+            }
+        }
+        Value::Number(number) => {
+            if let Some(number) = number.as_f64() {
+                let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+                let checks = [
+                    ("minimum", bound("minimum").filter(|min| number < *min)),
+                    ("maximum", bound("maximum").filter(|max| number > *max)),
+                    (
+                        "exclusiveMinimum",
+                        bound("exclusiveMinimum").filter(|min| number <= *min),
+                    ),
+                    (
+                        "exclusiveMaximum",
+                        bound("exclusiveMaximum").filter(|max| number >= *max),
+                    ),
+                ];
+                for (keyword, violated) in checks {
+                    if let Some(limit) = violated {
+                        errors.push(format!(
+                            "{}: {number} violates {keyword} of {limit}",
+                            display(pointer)
+                        ));
+                    }
+                }
+            }
+        }
+        Value::Bool(_) | Value::Null => {}
+    }
+
+    if let Some(Value::Array(schemas)) = schema.get("allOf") {
+        for sub_schema in schemas {
+            validate_at(sub_schema, value, pointer, errors);
+        }
+    }
+    if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+        let matches_any = schemas
+            .iter()
+            .any(|sub_schema| validate(sub_schema, value).is_empty());
+        if !matches_any {
+            errors.push(format!(
+                "{}: value does not match any of the allowed schemas",
+                display(pointer)
+            ));
+        }
+    }
+    if let Some(sub_schema) = schema.get("not") {
+        if validate(sub_schema, value).is_empty() {
+            errors.push(format!(
+                "{}: value matches a schema it must not match",
+                display(pointer)
+            ));
+        }
+    }
+}
+
+fn check_bounds(
+    length: usize,
+    min: Option<&Value>,
+    max: Option<&Value>,
+    unit: &str,
+    pointer: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(min) = min.and_then(Value::as_u64) {
+        if (length as u64) < min {
+            errors.push(format!(
+                "{}: expected at least {min} {unit}, found {length}",
+                display(pointer)
+            ));
+        }
+    }
+    if let Some(max) = max.and_then(Value::as_u64) {
+        if (length as u64) > max {
+            errors.push(format!(
+                "{}: expected at most {max} {unit}, found {length}",
+                display(pointer)
+            ));
+        }
+    }
+}
+
+fn is_type(value: &Value, kind: &str) -> bool {
+    match kind {
+        "array" => value.is_array(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "object" => value.is_object(),
+        "string" => value.is_string(),
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Array(_) => "array",
+        Value::Bool(_) => "boolean",
+        Value::Null => "null",
+        Value::Number(_) => "number",
+        Value::Object(_) => "object",
+        Value::String(_) => "string",
+    }
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn display(pointer: &str) -> &str {
+    if pointer.is_empty() {
+        "/"
+    } else {
+        pointer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn object_matches() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": { "type": "string", "minLength": 1 },
+                "ports": {
+                    "type": "array",
+                    "items": { "type": "integer", "minimum": 1, "maximum": 65535 },
+                    "uniqueItems": true,
+                },
+            },
+            "additionalProperties": false,
+        });
+
+        assert!(validate(&schema, &json!({ "name": "web", "ports": [80, 443] })).is_empty());
+    }
+
+    #[test]
+    fn object_violations_are_reported_with_pointers() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "ports": {
+                    "type": "array",
+                    "items": { "type": "integer", "maximum": 65535 },
+                    "uniqueItems": true,
+                },
+                "tags": { "type": "object", "additionalProperties": { "type": "string" } },
+            },
+            "additionalProperties": false,
+        });
+
+        let errors = validate(
+            &schema,
+            &json!({
+                "ports": [80, 80, 70000],
+                "tags": { "a/b": 1 },
+                "extra": true,
+            }),
+        );
+
+        assert_eq!(
+            vec![
+                "/: missing required property name".to_string(),
+                "/ports: item 1 is a duplicate".to_string(),
+                "/ports/2: 70000 violates maximum of 65535".to_string(),
+                "/tags/a~1b: expected string, found number".to_string(),
+                "/: property extra is not allowed".to_string(),
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn combinators() {
+        let schema = json!({
+            "anyOf": [{ "type": "string", "pattern": "^arn:" }, { "type": "null" }],
+            "not": { "const": "arn:nope" },
+        });
+
+        assert!(validate(&schema, &json!("arn:aws:s3:::bucket")).is_empty());
+        assert!(validate(&schema, &Value::Null).is_empty());
+        assert_eq!(1, validate(&schema, &json!("bucket")).len());
+        assert_eq!(1, validate(&schema, &json!("arn:nope")).len());
+    }
+
+    #[test]
+    fn unsupported_keywords_are_reported_with_pointers() {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "subnet",
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "format": "hostname" },
+                "tags": { "patternProperties": { "^a": { "type": "string" } } },
+                "ports": { "items": [{ "type": "integer" }] },
+                "cidr": { "oneOf": [{ "$ref": "#/$defs/cidr" }, { "type": "null" }] },
+            },
+            "anyOf": [{ "required": ["name"] }, { "not": { "$ref": "#/$defs/empty" } }],
+        });
+
+        assert_eq!(
+            vec![
+                "/properties/name/format".to_string(),
+                "/properties/tags/patternProperties".to_string(),
+                "/properties/ports/items".to_string(),
+                "/properties/cidr/oneOf".to_string(),
+                "/anyOf/1/not/$ref".to_string(),
+            ],
+            unsupported_keywords(&schema)
+        );
+    }
+
+    #[test]
+    fn supported_schemas_have_no_unsupported_keywords() {
+        let schema = json!({
+            "type": "object",
+            "description": "a web server",
+            "required": ["name"],
+            "properties": {
+                "name": { "type": "string", "minLength": 1 },
+                "ports": { "type": "array", "items": { "type": "integer" } },
+            },
+            "additionalProperties": false,
+        });
+
+        assert!(unsupported_keywords(&schema).is_empty());
+        assert!(unsupported_keywords(&json!(true)).is_empty());
+    }
+}
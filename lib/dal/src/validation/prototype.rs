@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use si_data_nats::NatsError;
 use si_data_pg::PgError;
+use std::collections::HashSet;
 use telemetry::prelude::*;
 use thiserror::Error;
 
@@ -9,19 +10,19 @@ use crate::validation::prototype::context::ValidationPrototypeContextBuilder;
 use crate::{
     func::FuncId,
     impl_standard_model, pk,
-    standard_model::{self, objects_from_rows},
-    standard_model_accessor, DalContext, HistoryEventError, PropId, SchemaVariantId, StandardModel,
-    StandardModelError, Tenancy, Timestamp, Visibility,
+    standard_model::{self, objects_from_rows, TypeHint},
+    standard_model_accessor, DalContext, HistoryEvent, HistoryEventError, InternalProviderId,
+    PropId, SchemaVariantId, StandardModel, StandardModelError, Tenancy, Timestamp, Visibility,
 };
-use crate::{PropKind, SchemaId, TransactionsError, ValidationPrototypeContext};
+use crate::{SchemaId, TransactionsError, ValidationPrototypeContext};
 
 pub mod context;
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum ValidationPrototypeError {
-    #[error("prop for validation prototype context is not of primitive prop kind, found: {0:?}")]
-    ContextPropKindIsNotPrimitive(PropKind),
+    #[error("validation prototype input name used more than once: {0}")]
+    DuplicateInputName(String),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
     #[error("nats txn error: {0}")]
@@ -38,6 +39,8 @@ pub enum ValidationPrototypeError {
     StandardModelError(#[from] StandardModelError),
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
+    #[error("json schema uses keywords which are not checked: {0:?}")]
+    UnsupportedJsonSchemaKeywords(Vec<String>),
 }
 
 pub type ValidationPrototypeResult<T> = Result<T, ValidationPrototypeError>;
@@ -46,11 +49,41 @@ const LIST_FOR_PROP: &str = include_str!("../queries/validation_prototype/list_f
 const LIST_FOR_SCHEMA_VARIANT: &str =
     include_str!("../queries/validation_prototype/list_for_schema_variant.sql");
 const LIST_FOR_FUNC: &str = include_str!("../queries/validation_prototype/list_for_func.sql");
+const LIST_FOR_INPUT_SOCKET: &str =
+    include_str!("../queries/validation_prototype/list_for_input_socket.sql");
 const FIND_FOR_CONTEXT: &str = include_str!("../queries/validation_prototype/find_for_context.sql");
 
 pk!(ValidationPrototypePk);
 pk!(ValidationPrototypeId);
 
+/// A value, besides the one at the [`ValidationPrototype`]'s own [`Prop`](crate::Prop), that the
+/// validation needs to see. Inputs are passed to the validation func by name, and a failing
+/// validation is reported on every input [`Prop`](crate::Prop) as well as its own.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ValidationPrototypeInput {
+    /// The value of an input [`Socket`](crate::Socket) on the same
+    /// [`Component`](crate::Component), identified by its explicit
+    /// [`InternalProvider`](crate::InternalProvider).
+    #[serde(rename_all = "camelCase")]
+    InputSocket {
+        name: String,
+        internal_provider_id: InternalProviderId,
+    },
+    /// The value of another [`Prop`](crate::Prop) on the same [`Component`](crate::Component).
+    #[serde(rename_all = "camelCase")]
+    Prop { name: String, prop_id: PropId },
+}
+
+impl ValidationPrototypeInput {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::InputSocket { name, .. } | Self::Prop { name, .. } => name,
+        }
+    }
+}
+
 // An ValidationPrototype joins a `Func` to the context in which
 // the component that is created with it can use to generate a ValidationResolver.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
@@ -63,6 +96,9 @@ pub struct ValidationPrototype {
     prop_id: PropId,
     schema_id: SchemaId,
     schema_variant_id: SchemaVariantId,
+    /// Additional values the validation reads. Empty for validations of a single value.
+    #[serde(default)]
+    inputs: Vec<ValidationPrototypeInput>,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -120,6 +156,54 @@ impl ValidationPrototype {
         ValidationPrototypeResult
     );
 
+    pub fn inputs(&self) -> &[ValidationPrototypeInput] {
+        &self.inputs
+    }
+
+    /// Replace the [`inputs`](ValidationPrototypeInput) of [`Self`]. Input names must be unique,
+    /// since they become the keys of the "inputs" object passed to the validation func.
+    #[instrument(skip_all)]
+    pub async fn set_inputs(
+        &mut self,
+        ctx: &DalContext,
+        inputs: Vec<ValidationPrototypeInput>,
+    ) -> ValidationPrototypeResult<()> {
+        let mut names = HashSet::new();
+        for input in &inputs {
+            if !names.insert(input.name()) {
+                return Err(ValidationPrototypeError::DuplicateInputName(
+                    input.name().to_owned(),
+                ));
+            }
+        }
+
+        let value = serde_json::to_value(&inputs)?;
+        let updated_at = standard_model::update(
+            ctx,
+            Self::table_name(),
+            "inputs",
+            self.id(),
+            &value,
+            TypeHint::JsonB,
+        )
+        .await?;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            &Self::history_event_label(vec!["updated"]),
+            &Self::history_event_message("updated"),
+            &serde_json::json![{
+                "pk": self.pk,
+                "field": "inputs",
+                "value": &value,
+            }],
+        )
+        .await?;
+        self.timestamp.updated_at = updated_at;
+        self.inputs = inputs;
+
+        Ok(())
+    }
+
     pub fn context(&self) -> ValidationPrototypeContext {
         ValidationPrototypeContext::new_unchecked(
             self.prop_id,
@@ -167,6 +251,26 @@ impl ValidationPrototype {
         Ok(object)
     }
 
+    /// List all [`ValidationPrototypes`](Self) which read the input [`Socket`](crate::Socket) of
+    /// the given explicit [`InternalProvider`](crate::InternalProvider).
+    #[instrument(skip_all)]
+    pub async fn list_for_input_socket(
+        ctx: &DalContext,
+        internal_provider_id: InternalProviderId,
+    ) -> ValidationPrototypeResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                LIST_FOR_INPUT_SOCKET,
+                &[ctx.tenancy(), ctx.visibility(), &internal_provider_id],
+            )
+            .await?;
+
+        Ok(objects_from_rows(rows)?)
+    }
+
     /// List all [`ValidationPrototypes`](Self) for a [`Func`](crate::Func)
     #[instrument(skip_all)]
     pub async fn list_for_func(
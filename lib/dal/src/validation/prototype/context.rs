@@ -5,8 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::validation::prototype::ValidationPrototypeResult;
 use crate::{
-    DalContext, Prop, PropId, PropKind, SchemaId, SchemaVariantId, StandardModel,
-    ValidationPrototypeError,
+    DalContext, Prop, PropId, SchemaId, SchemaVariantId, StandardModel, ValidationPrototypeError,
 };
 
 /// The builder used to create a valid [`ValidationPrototypeContext`].
@@ -77,18 +76,11 @@ impl ValidationPrototypeContextBuilder {
             ));
         }
 
-        let prop = Prop::get_by_id(ctx, &self.prop_id)
+        // Validations may be attached to props of any kind: containers are validated against
+        // the full value built up from their children.
+        Prop::get_by_id(ctx, &self.prop_id)
             .await?
             .ok_or(ValidationPrototypeError::PropNotFound(self.prop_id))?;
-        let prop_kind = prop.kind();
-        if prop_kind != &PropKind::String
-            && prop_kind != &PropKind::Integer
-            && prop_kind != &PropKind::Boolean
-        {
-            return Err(ValidationPrototypeError::ContextPropKindIsNotPrimitive(
-                *prop_kind,
-            ));
-        }
 
         Ok(ValidationPrototypeContext {
             prop_id: self.prop_id,
@@ -54,6 +54,8 @@ pub type ValidationResolverResult<T> = Result<T, ValidationResolverError>;
 const FIND_STATUS: &str = include_str!("../queries/validation_resolver/find_status.sql");
 const FIND_FOR_ATTRIBUTE_VALUE_AND_FUNC_BINDING: &str =
     include_str!("../queries/validation_resolver/find_for_attribute_value_and_func_binding.sql");
+const FIND_FOR_ATTRIBUTE_VALUE_AND_PROTOTYPE: &str =
+    include_str!("../queries/validation_resolver/find_for_attribute_value_and_prototype.sql");
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ValidationStatus {
@@ -163,6 +165,33 @@ impl ValidationResolver {
         Ok(standard_model::objects_from_rows(rows)?)
     }
 
+    /// Find the resolvers for a given [`AttributeValue`](crate::AttributeValue) that were created
+    /// by a given [`ValidationPrototype`]. Cross-field validations use this, rather than
+    /// [`Self::find_for_attribute_value_and_validation_func`], since the same attribute value can
+    /// participate in several of them.
+    pub async fn find_for_attribute_value_and_prototype(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+        validation_prototype_id: ValidationPrototypeId,
+    ) -> ValidationResolverResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                FIND_FOR_ATTRIBUTE_VALUE_AND_PROTOTYPE,
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &attribute_value_id,
+                    &validation_prototype_id,
+                ],
+            )
+            .await?;
+
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
     /// Find the status of validation(s) for a given [`ComponentId`](crate::Component).
     pub async fn find_status(
         ctx: &DalContext,
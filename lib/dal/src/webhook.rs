@@ -0,0 +1,878 @@
+//! This module contains [`Webhook`], an HTTP endpoint that is told about what happens in a
+//! [`Workspace`](crate::Workspace), and [`WebhookDelivery`], the record of telling it.
+//!
+//! When a [`WsEvent`] of one of the [`kinds`](WebhookEventKind) a [`Webhook`] is subscribed to is
+//! published, a [`WebhookDelivery`] is queued for it. The
+//! [`WebhookDispatcher`](crate::tasks::WebhookDispatcher) then posts it to the webhook, signed
+//! with the secret of the webhook, retrying with backoff until it succeeds or runs out of
+//! attempts.
+//!
+//! Deliveries are only ever posted to public addresses: the host of the webhook is resolved
+//! right before each attempt, and loopback, private, link-local and unique-local addresses are
+//! refused. Redirects are not followed, since they could lead anywhere.
+
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use si_data_nats::NatsError;
+use si_data_pg::PgError;
+use sodiumoxide::crypto::auth::hmacsha256;
+use strum::{AsRefStr, Display, EnumString};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    pk, ChangeSetPk, DalContext, HistoryEvent, HistoryEventError, Timestamp, TransactionsError,
+    User, UserCapability, UserError, WorkspacePk, WsEvent, WsPayload,
+};
+
+const WEBHOOK_GET_BY_PK: &str = include_str!("queries/webhook/get_by_pk.sql");
+const WEBHOOK_LIST_DELIVERIES: &str = include_str!("queries/webhook/list_deliveries.sql");
+const WEBHOOK_LIST_FOR_WORKSPACE: &str = include_str!("queries/webhook/list_for_workspace.sql");
+const WEBHOOK_LIST_SUBSCRIBED: &str = include_str!("queries/webhook/list_subscribed.sql");
+
+/// The header carrying the hex encoded HMAC-SHA256 of the body of a delivery, keyed with the
+/// secret of the [`Webhook`] and prefixed with `sha256=`.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-SI-Signature";
+/// The header carrying the [`WebhookEventKind`] of a delivery.
+pub const WEBHOOK_EVENT_HEADER: &str = "X-SI-Event";
+/// The header carrying the [`WebhookDeliveryPk`] of a delivery, which stays the same across
+/// retries so receivers can ignore duplicates.
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-SI-Delivery";
+/// How many times a [`WebhookDelivery`] is attempted before it is given up on.
+pub const WEBHOOK_MAX_ATTEMPTS: i32 = 5;
+
+/// How long to wait before the first retry. Every retry after that waits twice as long as the
+/// previous one.
+const WEBHOOK_RETRY_BASE_SECONDS: i32 = 30;
+/// How long a single attempt can take before it counts as failed.
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a [`ping`](Webhook::ping) is left alone by the dispatcher, while the caller posts it.
+const WEBHOOK_PING_LEASE: Duration = Duration::from_secs(60);
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("webhook url is not a valid http(s) url: {0}")]
+    InvalidUrl(String),
+    #[error("nats txn error: {0}")]
+    Nats(#[from] NatsError),
+    #[error("webhook must subscribe to at least one event kind")]
+    NoEventKinds,
+    #[error("no workspace in tenancy")]
+    NoWorkspaceInTenancy,
+    #[error("pg error: {0}")]
+    Pg(#[from] PgError),
+    #[error("http client error: {0}")]
+    Reqwest(#[from] reqwest::Error),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("user error: {0}")]
+    User(#[from] UserError),
+}
+
+pub type WebhookResult<T> = Result<T, WebhookError>;
+
+pk!(WebhookPk);
+pk!(WebhookDeliveryPk);
+
+/// The kinds of [`WsEvent`] a [`Webhook`] can subscribe to.
+#[remain::sorted]
+#[derive(
+    AsRefStr, Clone, Copy, Debug, Deserialize, Display, EnumString, Eq, Hash, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum WebhookEventKind {
+    /// A change set was applied to head.
+    ChangeSetApplied,
+    /// The qualifications of a component were checked.
+    CheckedQualifications,
+    /// A batch of fixes finished running.
+    FixBatchReturn,
+    /// Sent when asked to test a [`Webhook`], whatever it is subscribed to.
+    Ping,
+    /// The resource of a component was refreshed.
+    ResourceRefreshed,
+}
+
+impl WebhookEventKind {
+    /// The kind of the given payload, if webhooks can subscribe to it.
+    pub fn for_payload(payload: &WsPayload) -> Option<Self> {
+        match payload {
+            WsPayload::ChangeSetApplied(_) => Some(Self::ChangeSetApplied),
+            WsPayload::CheckedQualifications(_) => Some(Self::CheckedQualifications),
+            WsPayload::FixBatchReturn(_) => Some(Self::FixBatchReturn),
+            WsPayload::ResourceRefreshed(_) => Some(Self::ResourceRefreshed),
+            _ => None,
+        }
+    }
+}
+
+/// An HTTP endpoint subscribed to events of a [`Workspace`](crate::Workspace). See the
+/// [`module`](crate::webhook) for more information.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    pk: WebhookPk,
+    workspace_pk: WorkspacePk,
+    url: String,
+    event_kinds: Vec<WebhookEventKind>,
+    #[serde(skip_serializing)]
+    secret: String,
+    enabled: bool,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+}
+
+impl Webhook {
+    /// Creates an enabled webhook for the [`Workspace`](crate::Workspace) of the
+    /// [`Tenancy`](crate::Tenancy) of the [`DalContext`], with a freshly generated secret.
+    #[instrument(skip_all)]
+    pub async fn new(
+        ctx: &DalContext,
+        url: impl AsRef<str>,
+        event_kinds: Vec<WebhookEventKind>,
+    ) -> WebhookResult<Self> {
+        User::ensure_authorized(ctx, UserCapability::ManageWebhooks).await?;
+
+        let url = validate_url(url.as_ref())?;
+        let event_kinds = validate_event_kinds(event_kinds)?;
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(WebhookError::NoWorkspaceInTenancy)?;
+        let secret = format!(
+            "whsec_{}",
+            hex::encode(rand::thread_rng().gen::<[u8; 32]>())
+        );
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM webhook_create_v1($1, $2, $3, $4)",
+                &[
+                    &workspace_pk,
+                    &url,
+                    &serde_json::to_value(&event_kinds)?,
+                    &secret,
+                ],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        let object: Self = serde_json::from_value(json)?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "webhook.create",
+            "Webhook created",
+            &serde_json::json![{ "pk": object.pk, "url": object.url, "eventKinds": object.event_kinds }],
+        )
+        .await?;
+
+        Ok(object)
+    }
+
+    pub fn pk(&self) -> WebhookPk {
+        self.pk
+    }
+
+    pub fn workspace_pk(&self) -> WorkspacePk {
+        self.workspace_pk
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn event_kinds(&self) -> &[WebhookEventKind] {
+        &self.event_kinds
+    }
+
+    /// The key deliveries are signed with. It is never serialized, so it has to be handed out
+    /// explicitly.
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Gets the webhook in the [`Workspace`](crate::Workspace) of the
+    /// [`Tenancy`](crate::Tenancy) of the [`DalContext`].
+    pub async fn get_by_pk(ctx: &DalContext, pk: WebhookPk) -> WebhookResult<Option<Self>> {
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(WebhookError::NoWorkspaceInTenancy)?;
+        Ok(Self::get_by_pk_in_any_workspace(ctx, pk)
+            .await?
+            .filter(|webhook| webhook.workspace_pk == workspace_pk))
+    }
+
+    /// Gets the webhook whatever its [`Workspace`](crate::Workspace) is, for the
+    /// [`WebhookDispatcher`](crate::tasks::WebhookDispatcher), which works across all of them.
+    pub(crate) async fn get_by_pk_in_any_workspace(
+        ctx: &DalContext,
+        pk: WebhookPk,
+    ) -> WebhookResult<Option<Self>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(WEBHOOK_GET_BY_PK, &[&pk])
+            .await?;
+        object_option_from_row(row)
+    }
+
+    /// Lists the webhooks of the [`Workspace`](crate::Workspace) of the
+    /// [`Tenancy`](crate::Tenancy) of the [`DalContext`], newest first.
+    pub async fn list(ctx: &DalContext) -> WebhookResult<Vec<Self>> {
+        let workspace_pk = ctx
+            .tenancy()
+            .workspace_pk()
+            .ok_or(WebhookError::NoWorkspaceInTenancy)?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(WEBHOOK_LIST_FOR_WORKSPACE, &[&workspace_pk])
+            .await?;
+        objects_from_rows(rows)
+    }
+
+    /// Changes where the webhook is posted to, what it is subscribed to and whether it is
+    /// enabled. Disabled webhooks don't get new deliveries, and their pending ones are failed
+    /// when they come due.
+    #[instrument(skip_all)]
+    pub async fn update(
+        &mut self,
+        ctx: &DalContext,
+        url: impl AsRef<str>,
+        event_kinds: Vec<WebhookEventKind>,
+        enabled: bool,
+    ) -> WebhookResult<()> {
+        User::ensure_authorized(ctx, UserCapability::ManageWebhooks).await?;
+
+        let url = validate_url(url.as_ref())?;
+        let event_kinds = validate_event_kinds(event_kinds)?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM webhook_update_v1($1, $2, $3, $4)",
+                &[
+                    &self.pk,
+                    &url,
+                    &serde_json::to_value(&event_kinds)?,
+                    &enabled,
+                ],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        *self = serde_json::from_value(json)?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "webhook.update",
+            "Webhook updated",
+            &serde_json::json![{ "pk": self.pk, "url": self.url, "eventKinds": self.event_kinds, "enabled": self.enabled }],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deletes the webhook, along with its deliveries.
+    #[instrument(skip_all)]
+    pub async fn delete(self, ctx: &DalContext) -> WebhookResult<()> {
+        User::ensure_authorized(ctx, UserCapability::ManageWebhooks).await?;
+
+        ctx.txns()
+            .await?
+            .pg()
+            .execute("DELETE FROM webhooks WHERE pk = $1", &[&self.pk])
+            .await?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "webhook.delete",
+            "Webhook deleted",
+            &serde_json::json![{ "pk": self.pk, "url": self.url }],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Lists the most recent deliveries of the webhook, newest first.
+    pub async fn deliveries(
+        &self,
+        ctx: &DalContext,
+        limit: i64,
+    ) -> WebhookResult<Vec<WebhookDelivery>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(WEBHOOK_LIST_DELIVERIES, &[&self.pk, &limit])
+            .await?;
+        objects_from_rows(rows)
+    }
+
+    /// Queues a delivery of the [`WsEvent`] to every enabled webhook of its
+    /// [`Workspace`](crate::Workspace) that is subscribed to it.
+    pub async fn enqueue_for_event(
+        ctx: &DalContext,
+        event: &WsEvent,
+    ) -> WebhookResult<Vec<WebhookDelivery>> {
+        let kind = match WebhookEventKind::for_payload(event.payload()) {
+            Some(kind) => kind,
+            None => return Ok(Vec::new()),
+        };
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                WEBHOOK_LIST_SUBSCRIBED,
+                &[&event.workspace_pk(), &kind.as_ref()],
+            )
+            .await?;
+        let webhooks: Vec<Self> = objects_from_rows(rows)?;
+
+        let data = serde_json::to_value(event.payload())?;
+        let mut deliveries = Vec::with_capacity(webhooks.len());
+        for webhook in webhooks {
+            deliveries.push(
+                webhook
+                    .enqueue(ctx, kind, event.change_set_pk(), data.clone())
+                    .await?,
+            );
+        }
+        Ok(deliveries)
+    }
+
+    /// Queues a [`WebhookEventKind::Ping`] delivery, to check that the webhook is reachable. The
+    /// delivery is leased to the caller, which is expected to [`post`](WebhookDelivery::post) it
+    /// right away, so the [`WebhookDispatcher`](crate::tasks::WebhookDispatcher) leaves it alone
+    /// unless the caller fails to record the attempt.
+    pub async fn ping(&self, ctx: &DalContext) -> WebhookResult<WebhookDelivery> {
+        let delivery = self
+            .enqueue(
+                ctx,
+                WebhookEventKind::Ping,
+                ctx.visibility().change_set_pk,
+                serde_json::json![{ "webhookPk": self.pk }],
+            )
+            .await?;
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "UPDATE webhook_deliveries
+                 SET next_attempt_at = CLOCK_TIMESTAMP() + make_interval(secs => $2)
+                 WHERE pk = $1
+                 RETURNING row_to_json(webhook_deliveries.*) AS object",
+                &[&delivery.pk, &(WEBHOOK_PING_LEASE.as_secs() as i32)],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        Ok(serde_json::from_value(json)?)
+    }
+
+    async fn enqueue(
+        &self,
+        ctx: &DalContext,
+        kind: WebhookEventKind,
+        change_set_pk: ChangeSetPk,
+        data: serde_json::Value,
+    ) -> WebhookResult<WebhookDelivery> {
+        let payload = serde_json::json![{
+            "kind": kind,
+            "workspacePk": self.workspace_pk,
+            "changeSetPk": change_set_pk,
+            "data": data,
+        }];
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM webhook_delivery_create_v1($1, $2, $3)",
+                &[&self.pk, &kind.as_ref(), &payload],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        Ok(serde_json::from_value(json)?)
+    }
+
+    /// Signs the body of a delivery with the secret of the webhook, returning the value of the
+    /// [`WEBHOOK_SIGNATURE_HEADER`].
+    pub fn sign(&self, body: &[u8]) -> String {
+        let mut state = hmacsha256::State::init(self.secret.as_bytes());
+        state.update(body);
+        format!("sha256={}", hex::encode(state.finalize().0))
+    }
+}
+
+/// Where a [`WebhookDelivery`] is at.
+#[remain::sorted]
+#[derive(
+    AsRefStr, Clone, Copy, Debug, Deserialize, Display, EnumString, Eq, Hash, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum WebhookDeliveryStatus {
+    /// Every attempt failed, or the webhook was disabled.
+    Failed,
+    /// Waiting for its next attempt.
+    Pending,
+    /// The webhook answered with a success status code.
+    Succeeded,
+}
+
+/// One event posted to a [`Webhook`], along with how it went. See the
+/// [`module`](crate::webhook) for more information.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    pk: WebhookDeliveryPk,
+    webhook_pk: WebhookPk,
+    event_kind: WebhookEventKind,
+    payload: serde_json::Value,
+    status: WebhookDeliveryStatus,
+    attempts: i32,
+    last_status_code: Option<i32>,
+    last_error: Option<String>,
+    next_attempt_at: DateTime<Utc>,
+    delivered_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+}
+
+impl WebhookDelivery {
+    pub fn pk(&self) -> WebhookDeliveryPk {
+        self.pk
+    }
+
+    pub fn webhook_pk(&self) -> WebhookPk {
+        self.webhook_pk
+    }
+
+    pub fn event_kind(&self) -> WebhookEventKind {
+        self.event_kind
+    }
+
+    pub fn payload(&self) -> &serde_json::Value {
+        &self.payload
+    }
+
+    pub fn status(&self) -> WebhookDeliveryStatus {
+        self.status
+    }
+
+    pub fn attempts(&self) -> i32 {
+        self.attempts
+    }
+
+    pub fn last_status_code(&self) -> Option<i32> {
+        self.last_status_code
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    pub fn next_attempt_at(&self) -> DateTime<Utc> {
+        self.next_attempt_at
+    }
+
+    pub fn delivered_at(&self) -> Option<DateTime<Utc>> {
+        self.delivered_at
+    }
+
+    /// Claims up to `limit` pending deliveries that are due, across every
+    /// [`Workspace`](crate::Workspace). They won't be claimed again for `lease` unless their
+    /// attempt is recorded first, so the transaction should be committed before attempting them.
+    pub(crate) async fn claim_due(
+        ctx: &DalContext,
+        limit: i64,
+        lease: Duration,
+    ) -> WebhookResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT object FROM webhook_deliveries_claim_due_v1($1, $2)",
+                &[&limit, &(lease.as_secs() as i32)],
+            )
+            .await?;
+        objects_from_rows(rows)
+    }
+
+    /// Posts the delivery to the [`Webhook`], without touching the database, so that no
+    /// connection or transaction is held while waiting on the webhook. The returned
+    /// [`WebhookAttempt`] is then stored with [`Self::record`].
+    #[instrument(skip_all, fields(webhook_delivery.pk = %self.pk))]
+    pub async fn post(
+        &self,
+        webhook: &Webhook,
+        client: &WebhookClient,
+    ) -> WebhookResult<WebhookAttempt> {
+        if !webhook.enabled() {
+            return Ok(WebhookAttempt::given_up("webhook is disabled"));
+        }
+
+        let http_client = match client.http_client_for(webhook.url()).await {
+            Ok(http_client) => http_client,
+            Err(WebhookDestinationError::NotPublic) => {
+                return Ok(WebhookAttempt::given_up(
+                    "webhook url does not resolve to a public address",
+                ));
+            }
+            Err(WebhookDestinationError::Unresolved(message)) => {
+                return Ok(WebhookAttempt::failed(None, message));
+            }
+        };
+
+        let body = serde_json::to_vec(&self.payload)?;
+        let response = http_client
+            .post(webhook.url())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_SIGNATURE_HEADER, webhook.sign(&body))
+            .header(WEBHOOK_EVENT_HEADER, self.event_kind.as_ref())
+            .header(WEBHOOK_DELIVERY_HEADER, self.pk.to_string())
+            .body(body)
+            .send()
+            .await;
+
+        Ok(match response {
+            Ok(response) if response.status().is_success() => WebhookAttempt {
+                succeeded: true,
+                status_code: Some(response.status().as_u16()),
+                error: None,
+                give_up: false,
+            },
+            Ok(response) => {
+                let status = response.status();
+                WebhookAttempt::failed(
+                    Some(status.as_u16()),
+                    format!("webhook responded with {status}"),
+                )
+            }
+            Err(err) => {
+                warn!(error = ?err, "webhook delivery attempt failed");
+                WebhookAttempt::failed(None, err.to_string())
+            }
+        })
+    }
+
+    /// Records how an attempt made with [`Self::post`] went. Failed attempts are retried later,
+    /// until [`WEBHOOK_MAX_ATTEMPTS`] is reached.
+    pub async fn record(&mut self, ctx: &DalContext, attempt: WebhookAttempt) -> WebhookResult<()> {
+        let retry_in_seconds = if attempt.succeeded || attempt.give_up {
+            None
+        } else {
+            retry_in_seconds(self.attempts + 1)
+        };
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM webhook_delivery_record_attempt_v1($1, $2, $3, $4, $5)",
+                &[
+                    &self.pk,
+                    &attempt.succeeded,
+                    &attempt.status_code.map(i32::from),
+                    &attempt.error,
+                    &retry_in_seconds,
+                ],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        *self = serde_json::from_value(json)?;
+        Ok(())
+    }
+}
+
+/// How posting a [`WebhookDelivery`] went, as returned by [`WebhookDelivery::post`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebhookAttempt {
+    succeeded: bool,
+    status_code: Option<u16>,
+    error: Option<String>,
+    /// Whether the delivery is failed for good rather than retried.
+    give_up: bool,
+}
+
+impl WebhookAttempt {
+    fn failed(status_code: Option<u16>, error: String) -> Self {
+        Self {
+            succeeded: false,
+            status_code,
+            error: Some(error),
+            give_up: false,
+        }
+    }
+
+    fn given_up(error: &str) -> Self {
+        Self {
+            succeeded: false,
+            status_code: None,
+            error: Some(error.to_owned()),
+            give_up: true,
+        }
+    }
+}
+
+/// Why a [`WebhookDelivery`] can't be posted to the url of its [`Webhook`].
+#[derive(Debug)]
+enum WebhookDestinationError {
+    /// The host is, or resolves to, an address that isn't public.
+    NotPublic,
+    /// The host could not be resolved, which may well succeed on a later attempt.
+    Unresolved(String),
+}
+
+/// Posts [`deliveries`](WebhookDelivery), only ever to public addresses. See the
+/// [`module`](crate::webhook) for more information.
+#[derive(Clone, Debug, Default)]
+pub struct WebhookClient {
+    allow_private_addresses: bool,
+}
+
+impl WebhookClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A client that also posts to loopback and private addresses, for tests that stand up a
+    /// webhook locally. Never use it for webhooks users configure.
+    pub fn allowing_private_addresses() -> Self {
+        Self {
+            allow_private_addresses: true,
+        }
+    }
+
+    /// Resolves the host of the url and builds an HTTP client pinned to the address it resolved
+    /// to, so that the address that was checked is the one that gets connected to.
+    async fn http_client_for(&self, url: &str) -> Result<reqwest::Client, WebhookDestinationError> {
+        let builder = reqwest::Client::builder()
+            .timeout(WEBHOOK_REQUEST_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent("si-webhooks");
+
+        let parsed = url::Url::parse(url)
+            .map_err(|err| WebhookDestinationError::Unresolved(err.to_string()))?;
+        let port = parsed.port_or_known_default().unwrap_or(443);
+        let builder = match parsed.host() {
+            Some(url::Host::Ipv4(ip)) => {
+                self.check(&[IpAddr::V4(ip)])?;
+                builder
+            }
+            Some(url::Host::Ipv6(ip)) => {
+                self.check(&[IpAddr::V6(ip)])?;
+                builder
+            }
+            Some(url::Host::Domain(domain)) => {
+                let addrs: Vec<SocketAddr> = match tokio::time::timeout(
+                    WEBHOOK_REQUEST_TIMEOUT,
+                    tokio::net::lookup_host((domain, port)),
+                )
+                .await
+                {
+                    Ok(Ok(addrs)) => addrs.collect(),
+                    Ok(Err(err)) => {
+                        return Err(WebhookDestinationError::Unresolved(format!(
+                            "could not resolve {domain}: {err}"
+                        )))
+                    }
+                    Err(_) => {
+                        return Err(WebhookDestinationError::Unresolved(format!(
+                            "timed out resolving {domain}"
+                        )))
+                    }
+                };
+                self.check(&addrs.iter().map(SocketAddr::ip).collect::<Vec<_>>())?;
+                match addrs.first() {
+                    Some(addr) => builder.resolve(domain, *addr),
+                    None => {
+                        return Err(WebhookDestinationError::Unresolved(format!(
+                            "could not resolve {domain}"
+                        )))
+                    }
+                }
+            }
+            None => return Err(WebhookDestinationError::NotPublic),
+        };
+
+        builder
+            .build()
+            .map_err(|err| WebhookDestinationError::Unresolved(err.to_string()))
+    }
+
+    /// Every address has to be public, since any of them could be the one connected to.
+    fn check(&self, ips: &[IpAddr]) -> Result<(), WebhookDestinationError> {
+        if self.allow_private_addresses || ips.iter().all(|ip| is_public(*ip)) {
+            Ok(())
+        } else {
+            Err(WebhookDestinationError::NotPublic)
+        }
+    }
+}
+
+/// Whether the address is reachable on the internet, as opposed to loopback, private (including
+/// carrier-grade NAT), link-local (which covers cloud metadata services), unique-local,
+/// multicast or otherwise reserved.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || first == 0
+                || (first == 100 && second & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
+
+/// How long to wait before attempting again after the given number of failed attempts, if at
+/// all.
+fn retry_in_seconds(attempts: i32) -> Option<i32> {
+    if attempts >= WEBHOOK_MAX_ATTEMPTS {
+        None
+    } else {
+        Some(WEBHOOK_RETRY_BASE_SECONDS * 2_i32.pow((attempts - 1).max(0) as u32))
+    }
+}
+
+fn validate_url(url: &str) -> WebhookResult<String> {
+    let url = url.trim();
+    match url::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(url.to_owned()),
+        _ => Err(WebhookError::InvalidUrl(url.to_owned())),
+    }
+}
+
+fn validate_event_kinds(
+    mut event_kinds: Vec<WebhookEventKind>,
+) -> WebhookResult<Vec<WebhookEventKind>> {
+    event_kinds.retain(|kind| *kind != WebhookEventKind::Ping);
+    event_kinds.sort_by_key(|kind| kind.to_string());
+    event_kinds.dedup();
+    if event_kinds.is_empty() {
+        return Err(WebhookError::NoEventKinds);
+    }
+    Ok(event_kinds)
+}
+
+fn object_option_from_row<T: serde::de::DeserializeOwned>(
+    row: Option<si_data_pg::PgRow>,
+) -> WebhookResult<Option<T>> {
+    match row {
+        Some(row) => {
+            let json: serde_json::Value = row.try_get("object")?;
+            Ok(Some(serde_json::from_value(json)?))
+        }
+        None => Ok(None),
+    }
+}
+
+fn objects_from_rows<T: serde::de::DeserializeOwned>(
+    rows: Vec<si_data_pg::PgRow>,
+) -> WebhookResult<Vec<T>> {
+    let mut objects = Vec::with_capacity(rows.len());
+    for row in rows {
+        let json: serde_json::Value = row.try_get("object")?;
+        objects.push(serde_json::from_value(json)?);
+    }
+    Ok(objects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_back_off_then_give_up() {
+        assert_eq!(retry_in_seconds(1), Some(30));
+        assert_eq!(retry_in_seconds(2), Some(60));
+        assert_eq!(retry_in_seconds(4), Some(240));
+        assert_eq!(retry_in_seconds(WEBHOOK_MAX_ATTEMPTS), None);
+    }
+
+    #[test]
+    fn only_public_addresses_are_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(
+                !is_public(ip.parse().expect("bad ip")),
+                "{ip} is not public"
+            );
+        }
+        for ip in ["1.1.1.1", "8.8.8.8", "2606:4700:4700::1111"] {
+            assert!(is_public(ip.parse().expect("bad ip")), "{ip} is public");
+        }
+    }
+
+    #[tokio::test]
+    async fn private_hosts_are_refused() {
+        let client = WebhookClient::new();
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/hook",
+            "http://localhost/hook",
+        ] {
+            assert!(matches!(
+                client.http_client_for(url).await,
+                Err(WebhookDestinationError::NotPublic)
+            ));
+        }
+        assert!(WebhookClient::allowing_private_addresses()
+            .http_client_for("http://127.0.0.1:8080/hook")
+            .await
+            .is_ok());
+    }
+}
@@ -163,6 +163,8 @@ impl Workspace {
             None::<&str>,
         )
         .await?;
+        // The user signing up becomes the owner of the new workspace.
+        user.associate_workspace(ctx, *workspace.pk()).await?;
         ctx.update_history_actor(HistoryActor::User(user.pk()));
 
         ctx.import_builtins().await?;
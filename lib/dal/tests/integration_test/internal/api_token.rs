@@ -0,0 +1,143 @@
+use chrono::{Duration, Utc};
+use dal::{
+    ApiToken, ApiTokenError, DalContext, HistoryActor, User, UserCapability, UserError,
+    WorkspaceRole, WorkspaceSignup,
+};
+use dal_test::test;
+
+#[test]
+async fn create_find_and_revoke(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let ctx = ctx.clone_with_new_history_actor(HistoryActor::User(nw.user.pk()));
+
+    let (token, secret) = ApiToken::new(&ctx, "ci", WorkspaceRole::Editor, None)
+        .await
+        .expect("could not create api token");
+    assert!(secret.starts_with(dal::API_TOKEN_PREFIX));
+    assert_eq!(nw.user.pk(), token.created_by_user_pk());
+    assert!(token.is_active());
+
+    let found = ApiToken::find_by_secret(&ctx, &secret)
+        .await
+        .expect("could not find api token")
+        .expect("api token not found by its secret");
+    assert_eq!(token, found);
+    assert!(ApiToken::find_by_secret(&ctx, "si_nope")
+        .await
+        .expect("could not find api token")
+        .is_none());
+
+    let tokens = ApiToken::list(&ctx)
+        .await
+        .expect("could not list api tokens");
+    assert_eq!(vec![token.clone()], tokens);
+
+    let mut token = token;
+    token
+        .revoke(&ctx)
+        .await
+        .expect("could not revoke api token");
+    assert!(!token.is_active());
+    assert!(!token
+        .authorize(&ctx, UserCapability::Read)
+        .await
+        .expect("could not authorize api token"));
+}
+
+#[test]
+async fn tokens_are_hashed_like_before(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let ctx = ctx.clone_with_new_history_actor(HistoryActor::User(nw.user.pk()));
+
+    let (token, secret) = ApiToken::new(&ctx, "ci", WorkspaceRole::Editor, None)
+        .await
+        .expect("could not create api token");
+
+    // Tokens created while Postgres hashed them have to keep working.
+    let row = ctx
+        .txns()
+        .await
+        .expect("could not get transactions")
+        .pg()
+        .query_one(
+            "SELECT token_hash = ENCODE(DIGEST($1, 'sha256'), 'hex') AS matches
+             FROM api_tokens
+             WHERE pk = $2",
+            &[&secret, &token.pk()],
+        )
+        .await
+        .expect("could not query api token");
+    assert!(row
+        .try_get::<_, bool>("matches")
+        .expect("could not get matches"));
+}
+
+#[test]
+async fn only_users_create_tokens(ctx: &DalContext) {
+    let result = ApiToken::new(ctx, "ci", WorkspaceRole::Editor, None).await;
+    assert!(matches!(result, Err(ApiTokenError::CreatorNotUser)));
+}
+
+#[test]
+async fn expiring_tokens(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let ctx = ctx.clone_with_new_history_actor(HistoryActor::User(nw.user.pk()));
+
+    let result = ApiToken::new(
+        &ctx,
+        "ci",
+        WorkspaceRole::Editor,
+        Some(Utc::now() - Duration::hours(1)),
+    )
+    .await;
+    assert!(matches!(result, Err(ApiTokenError::ExpiresInPast(_))));
+
+    let (token, _) = ApiToken::new(
+        &ctx,
+        "ci",
+        WorkspaceRole::Editor,
+        Some(Utc::now() + Duration::hours(1)),
+    )
+    .await
+    .expect("could not create api token");
+    assert!(token.is_active());
+}
+
+#[test]
+async fn tokens_act_within_their_role(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let owner_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(nw.user.pk()));
+    let (token, _) = ApiToken::new(&owner_ctx, "ci", WorkspaceRole::Editor, None)
+        .await
+        .expect("could not create api token");
+
+    let token_ctx = ctx.clone_with_new_history_actor(HistoryActor::ApiToken(token.pk()));
+    User::ensure_authorized(&token_ctx, UserCapability::Write)
+        .await
+        .expect("editor tokens can write");
+    let result = User::ensure_authorized(&token_ctx, UserCapability::ApplyChangeSet).await;
+    assert!(matches!(
+        result,
+        Err(UserError::ApiTokenUnauthorized(
+            _,
+            UserCapability::ApplyChangeSet
+        ))
+    ));
+
+    let creator = User::get_for_history_actor(&token_ctx, token_ctx.history_actor())
+        .await
+        .expect("could not get user for history actor")
+        .expect("no user for api token");
+    assert_eq!(nw.user.pk(), creator.pk());
+
+    // Tokens never do more than their creator currently can.
+    let member = dal_test::helpers::create_user(ctx).await;
+    member
+        .associate_workspace(ctx, *nw.workspace.pk())
+        .await
+        .expect("could not associate workspace");
+    let member_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(member.pk()));
+    let (member_token, _) = ApiToken::new(&member_ctx, "ci", WorkspaceRole::Owner, None)
+        .await
+        .expect("could not create api token");
+    assert!(!member_token
+        .authorize(ctx, UserCapability::ApplyChangeSet)
+        .await
+        .expect("could not authorize api token"));
+}
@@ -1,10 +1,12 @@
 use dal::{
     attribute::context::AttributeContextBuilder, component::view::ComponentView, generate_name,
-    AttributeContext, AttributeReadContext, AttributeValue, Component, DalContext, Prop, PropKind,
-    StandardModel,
+    property_editor::values::PropertyEditorValues, AttributeContext, AttributePrototypeArgument,
+    AttributeReadContext, AttributeValue, AttributeValueId, Component, ComponentId, DalContext,
+    InternalProvider, Prop, PropId, PropKind, StandardModel,
 };
 use dal_test::helpers::component_bag::ComponentBagger;
 use dal_test::{
+    helpers::setup_identity_func,
     test,
     test_harness::{create_schema, create_schema_variant_with_root},
 };
@@ -730,3 +732,207 @@ async fn list_payload(ctx: &DalContext) {
     assert_eq!(found_name.replace('"', ""), name);
     assert_eq!(si_name_value, domain_name_value);
 }
+
+#[test]
+async fn override_reset_and_lock(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root_prop) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let schema_variant_id = *schema_variant.id();
+
+    // domain: Object
+    // ├─ source: String
+    // └─ destination: String
+    let source_prop = Prop::new(
+        ctx,
+        "source",
+        PropKind::String,
+        None,
+        schema_variant_id,
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    let destination_prop = Prop::new(
+        ctx,
+        "destination",
+        PropKind::String,
+        None,
+        schema_variant_id,
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+
+    // The destination is computed from the source with the identity function.
+    let mut destination_attribute_prototype = AttributeValue::find_for_context(
+        ctx,
+        AttributeReadContext::default_with_prop(*destination_prop.id()),
+    )
+    .await
+    .expect("cannot get attribute value")
+    .expect("attribute value not found")
+    .attribute_prototype(ctx)
+    .await
+    .expect("cannot find attribute prototype")
+    .expect("attribute prototype not found");
+    let (identity_func_id, _, _, identity_func_identity_argument_id) =
+        setup_identity_func(ctx).await;
+    let source_internal_provider = InternalProvider::find_for_prop(ctx, *source_prop.id())
+        .await
+        .expect("could not get internal provider")
+        .expect("internal provider not found");
+    destination_attribute_prototype
+        .set_func_id(ctx, identity_func_id)
+        .await
+        .expect("could not set func id on attribute prototype");
+    AttributePrototypeArgument::new_for_intra_component(
+        ctx,
+        *destination_attribute_prototype.id(),
+        identity_func_identity_argument_id,
+        *source_internal_provider.id(),
+    )
+    .await
+    .expect("could not create attribute prototype argument");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let (component, _) =
+        Component::new_for_default_variant_from_schema(ctx, "arrakis", *schema.id())
+            .await
+            .expect("unable to create component");
+    let component_id = *component.id();
+
+    update_domain_value(ctx, component_id, *source_prop.id(), "spice").await;
+    assert_eq!(
+        serde_json::json!["spice"],
+        destination_value(ctx, component_id).await
+    );
+
+    // Setting the destination by hand overrides the computed value, which upstream changes no
+    // longer touch.
+    let destination_attribute_value_id =
+        update_domain_value(ctx, component_id, *destination_prop.id(), "water").await;
+    update_domain_value(ctx, component_id, *source_prop.id(), "sand").await;
+    assert_eq!(
+        serde_json::json!["water"],
+        destination_value(ctx, component_id).await
+    );
+    let mut destination_attribute_value =
+        AttributeValue::get_by_id(ctx, &destination_attribute_value_id)
+            .await
+            .expect("cannot get attribute value")
+            .expect("attribute value not found");
+    assert!(destination_attribute_value.is_overridden());
+
+    let property_editor_values = PropertyEditorValues::for_component(ctx, component_id)
+        .await
+        .expect("could not get property editor values");
+    let property_editor_value = property_editor_values
+        .values
+        .get(&destination_attribute_value_id.into())
+        .expect("property editor value not found");
+    assert!(property_editor_value.is_overridden());
+    assert!(!property_editor_value.is_locked());
+
+    // Resetting goes back to the computed value, and upstream changes apply again.
+    destination_attribute_value
+        .reset_to_computed(ctx)
+        .await
+        .expect("could not reset attribute value");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+    assert!(!destination_attribute_value.is_overridden());
+    assert_eq!(
+        serde_json::json!["sand"],
+        destination_value(ctx, component_id).await
+    );
+    update_domain_value(ctx, component_id, *source_prop.id(), "worm").await;
+    assert_eq!(
+        serde_json::json!["worm"],
+        destination_value(ctx, component_id).await
+    );
+
+    // A locked value keeps what it had until it is unlocked.
+    destination_attribute_value
+        .set_locked(ctx, true)
+        .await
+        .expect("could not lock attribute value");
+    update_domain_value(ctx, component_id, *source_prop.id(), "stillsuit").await;
+    assert_eq!(
+        serde_json::json!["worm"],
+        destination_value(ctx, component_id).await
+    );
+
+    destination_attribute_value
+        .set_locked(ctx, false)
+        .await
+        .expect("could not unlock attribute value");
+    update_domain_value(ctx, component_id, *source_prop.id(), "thumper").await;
+    assert_eq!(
+        serde_json::json!["thumper"],
+        destination_value(ctx, component_id).await
+    );
+}
+
+/// Sets the value of a [`Prop`] directly under "/root/domain" for the given
+/// [`Component`](dal::Component) and runs the jobs it enqueues.
+async fn update_domain_value(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    prop_id: PropId,
+    value: &str,
+) -> AttributeValueId {
+    let read_context = AttributeReadContext {
+        prop_id: Some(prop_id),
+        component_id: Some(component_id),
+        ..AttributeReadContext::default()
+    };
+    let attribute_value = AttributeValue::find_for_context(ctx, read_context)
+        .await
+        .expect("cannot get attribute value")
+        .expect("attribute value not found");
+    let parent_attribute_value = attribute_value
+        .parent_attribute_value(ctx)
+        .await
+        .expect("cannot get parent attribute value")
+        .expect("parent attribute value not found");
+    let context = AttributeContextBuilder::from(read_context)
+        .to_context()
+        .expect("could not convert builder to attribute context");
+    let (_, attribute_value_id) = AttributeValue::update_for_context(
+        ctx,
+        *attribute_value.id(),
+        Some(*parent_attribute_value.id()),
+        context,
+        Some(serde_json::json![value]),
+        None,
+    )
+    .await
+    .expect("cannot update value for context");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    attribute_value_id
+}
+
+async fn destination_value(ctx: &DalContext, component_id: ComponentId) -> serde_json::Value {
+    ComponentView::new(ctx, component_id)
+        .await
+        .expect("cannot get component view")
+        .properties["domain"]["destination"]
+        .clone()
+}
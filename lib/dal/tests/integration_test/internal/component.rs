@@ -19,7 +19,9 @@ use dal_test::{
 use pretty_assertions_sorted::assert_eq;
 use veritech_client::ResourceStatus;
 
+mod bulk;
 mod code;
+mod duplicate;
 mod qualification;
 mod resource;
 mod validation;
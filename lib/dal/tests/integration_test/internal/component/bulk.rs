@@ -0,0 +1,72 @@
+use dal::prop::PropPath;
+use dal::{Component, ComponentSelection, DalContext};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn bulk_update_prop_value(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let first_bag = bagger.create_component(ctx, "first", "starfield").await;
+    let second_bag = bagger.create_component(ctx, "second", "starfield").await;
+    let third_bag = bagger.create_component(ctx, "third", "starfield").await;
+    let freestar = PropPath::new(["root", "domain", "freestar"]);
+
+    // Update every "starfield" component at once.
+    let updated = Component::bulk_update_prop_value(
+        ctx,
+        &ComponentSelection::SchemaVariant {
+            schema_variant_id: first_bag.schema_variant_id,
+        },
+        &freestar,
+        Some(serde_json::json!["crimson fleet"]),
+    )
+    .await
+    .expect("could not bulk update prop value");
+    assert_eq!(3, updated.len());
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    for bag in [&first_bag, &second_bag, &third_bag] {
+        assert_eq!(
+            serde_json::json!["crimson fleet"], // expected
+            bag.component_view_properties_raw(ctx).await["domain"]["freestar"], // actual
+        );
+    }
+
+    // Now, only update a subset of them.
+    let updated = Component::bulk_update_prop_value(
+        ctx,
+        &ComponentSelection::Components {
+            component_ids: vec![
+                first_bag.component_id,
+                third_bag.component_id,
+                first_bag.component_id,
+            ],
+        },
+        &freestar,
+        Some(serde_json::json!["ryujin"]),
+    )
+    .await
+    .expect("could not bulk update prop value");
+    assert_eq!(2, updated.len());
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert_eq!(
+        serde_json::json!["ryujin"], // expected
+        first_bag.component_view_properties_raw(ctx).await["domain"]["freestar"], // actual
+    );
+    assert_eq!(
+        serde_json::json!["crimson fleet"], // expected
+        second_bag.component_view_properties_raw(ctx).await["domain"]["freestar"], // actual
+    );
+    assert_eq!(
+        serde_json::json!["ryujin"], // expected
+        third_bag.component_view_properties_raw(ctx).await["domain"]["freestar"], // actual
+    );
+}
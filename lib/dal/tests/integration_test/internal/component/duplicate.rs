@@ -0,0 +1,112 @@
+use dal::edge::EdgeKind;
+use dal::socket::SocketEdgeKind;
+use dal::{Component, ComponentView, Connection, DalContext, Edge, Socket, StandardModel};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn duplicate(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "tail", "fallout").await;
+    let starfield_bag = bagger.create_component(ctx, "head", "starfield").await;
+
+    let freestar_prop = starfield_bag
+        .find_prop(ctx, &["root", "domain", "freestar"])
+        .await;
+    starfield_bag
+        .update_attribute_value_for_prop(
+            ctx,
+            *freestar_prop.id(),
+            Some(serde_json::json!["crimson fleet"]),
+        )
+        .await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        fallout_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        starfield_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    Connection::new(
+        ctx,
+        fallout_bag.node_id,
+        *output_socket.id(),
+        starfield_bag.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+    )
+    .await
+    .expect("could not create connection");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let duplicates = Component::duplicate(
+        ctx,
+        &[fallout_bag.component_id, starfield_bag.component_id],
+        50,
+        50,
+    )
+    .await
+    .expect("could not duplicate components");
+    assert_eq!(2, duplicates.len());
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let starfield_duplicate = duplicates
+        .iter()
+        .find(|duplicate| duplicate.original_component_id == starfield_bag.component_id)
+        .expect("could not find starfield duplicate");
+    assert_ne!(starfield_bag.component_id, starfield_duplicate.component_id);
+
+    let component = Component::get_by_id(ctx, &starfield_duplicate.component_id)
+        .await
+        .expect("could not get component")
+        .expect("component not found");
+    assert_eq!(
+        "head - Copy",                                          // expected
+        component.name(ctx).await.expect("could not get name"), // actual
+    );
+
+    let view = ComponentView::new(ctx, starfield_duplicate.component_id)
+        .await
+        .expect("could not get component view");
+    assert_eq!(
+        Some(&serde_json::json!["crimson fleet"]),   // expected
+        view.properties.pointer("/domain/freestar"), // actual
+    );
+
+    // The edge between the originals is recreated between the duplicates.
+    let fallout_duplicate = duplicates
+        .iter()
+        .find(|duplicate| duplicate.original_component_id == fallout_bag.component_id)
+        .expect("could not find fallout duplicate");
+    let configuration_edges: Vec<Edge> =
+        Edge::list_for_component(ctx, starfield_duplicate.component_id)
+            .await
+            .expect("could not list edges")
+            .into_iter()
+            .filter(|edge| *edge.kind() == EdgeKind::Configuration)
+            .collect();
+    assert_eq!(1, configuration_edges.len());
+    assert_eq!(
+        fallout_duplicate.node_id,             // expected
+        configuration_edges[0].tail_node_id(), // actual
+    );
+}
@@ -4,8 +4,8 @@ use dal::{
     validation::{Validation, ValidationError, ValidationErrorKind},
     AttributeReadContext, AttributeValue, AttributeValueId, Component, ComponentId, ComponentView,
     DalContext, Func, FuncBackendKind, FuncBackendResponseType, Prop, PropId, PropKind,
-    StandardModel, ValidationPrototype, ValidationPrototypeContext, ValidationResolver,
-    ValidationStatus,
+    StandardModel, ValidationPrototype, ValidationPrototypeContext, ValidationPrototypeInput,
+    ValidationResolver, ValidationStatus,
 };
 use dal_test::helpers::component_bag::ComponentBagger;
 use dal_test::{
@@ -647,6 +647,178 @@ async fn ensure_validations_are_sourced_correctly(ctx: &DalContext) {
     assert!(status.errors.is_empty());
 }
 
+#[test]
+async fn check_cross_field_validations_for_component(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root_prop) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let schema_variant_id = *schema_variant.id();
+
+    let protocol_prop = Prop::new(
+        ctx,
+        "protocol",
+        PropKind::String,
+        None,
+        schema_variant_id,
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    let port_prop = Prop::new(
+        ctx,
+        "port",
+        PropKind::Integer,
+        None,
+        schema_variant_id,
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+
+    // The port is required when the protocol is "tcp".
+    let func = Func::find_by_attr(ctx, "name", &"si:validation".to_string())
+        .await
+        .expect("could not perform find by attr")
+        .pop()
+        .expect("could not find func");
+    let mut builder = ValidationPrototypeContext::builder();
+    builder
+        .set_prop_id(*port_prop.id())
+        .set_schema_id(*schema.id())
+        .set_schema_variant_id(schema_variant_id);
+    let args = serde_json::to_value(FuncBackendValidationArgs::new(
+        Validation::ValueIsRequiredWhenInputEquals {
+            value: None,
+            input: "protocol".to_string(),
+            expected: serde_json::json!("tcp"),
+        },
+    ))
+    .expect("could not convert args to Value");
+    let mut prototype = ValidationPrototype::new(
+        ctx,
+        *func.id(),
+        args,
+        builder
+            .to_context(ctx)
+            .await
+            .expect("could not convert builder to context"),
+    )
+    .await
+    .expect("could not create validation prototype");
+    prototype
+        .set_inputs(
+            ctx,
+            vec![ValidationPrototypeInput::Prop {
+                name: "protocol".to_string(),
+                prop_id: *protocol_prop.id(),
+            }],
+        )
+        .await
+        .expect("could not set validation prototype inputs");
+
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let (component, _) = Component::new(ctx, "ssh", schema_variant_id)
+        .await
+        .expect("could not create component");
+    let base_attribute_read_context = AttributeReadContext {
+        component_id: Some(*component.id()),
+        ..AttributeReadContext::default()
+    };
+    let domain_attribute_value = AttributeValue::find_for_context(
+        ctx,
+        AttributeReadContext {
+            prop_id: Some(root_prop.domain_prop_id),
+            ..base_attribute_read_context
+        },
+    )
+    .await
+    .expect("could not perform find for context")
+    .expect("could not find attribute value");
+
+    // Setting the protocol, but not the port, fails the validation on both props.
+    let mut attribute_value_ids = HashMap::new();
+    for (prop_id, value) in [
+        (*protocol_prop.id(), serde_json::json!("tcp")),
+        (*port_prop.id(), Value::Null),
+    ] {
+        let attribute_value = AttributeValue::find_for_context(
+            ctx,
+            AttributeReadContext {
+                prop_id: Some(prop_id),
+                ..base_attribute_read_context
+            },
+        )
+        .await
+        .expect("could not perform find for context")
+        .expect("could not find attribute value");
+        let (_, attribute_value_id) = AttributeValue::update_for_context(
+            ctx,
+            *attribute_value.id(),
+            Some(*domain_attribute_value.id()),
+            AttributeContextBuilder::from(base_attribute_read_context)
+                .set_prop_id(prop_id)
+                .to_context()
+                .expect("could not convert builder to attribute context"),
+            (!value.is_null()).then_some(value),
+            None,
+        )
+        .await
+        .expect("could not update attribute value");
+        attribute_value_ids.insert(prop_id, attribute_value_id);
+    }
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    for prop_id in [*protocol_prop.id(), *port_prop.id()] {
+        let status =
+            find_validation_status(ctx, attribute_value_ids[&prop_id], *component.id(), None).await;
+        assert_eq!(
+            vec![ValidationErrorKind::ValueRequiredByInput],
+            status
+                .errors
+                .into_iter()
+                .map(|error| error.kind)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    // Setting the port makes both props valid again.
+    let (_, port_attribute_value_id) = AttributeValue::update_for_context(
+        ctx,
+        attribute_value_ids[port_prop.id()],
+        Some(*domain_attribute_value.id()),
+        AttributeContextBuilder::from(base_attribute_read_context)
+            .set_prop_id(*port_prop.id())
+            .to_context()
+            .expect("could not convert builder to attribute context"),
+        Some(serde_json::json!(22)),
+        None,
+    )
+    .await
+    .expect("could not update attribute value");
+    attribute_value_ids.insert(*port_prop.id(), port_attribute_value_id);
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    for prop_id in [*protocol_prop.id(), *port_prop.id()] {
+        let status =
+            find_validation_status(ctx, attribute_value_ids[&prop_id], *component.id(), None).await;
+        assert!(status.errors.is_empty());
+    }
+}
+
 /// Finds exactly one [`ValidationStatus`](dal::ValidationStatus) for a given
 /// [`AttributeValueId`](dal::AttributeValue) corresponding to a [`Prop`](dal::Prop) and
 /// [`Component`](dal::Component).
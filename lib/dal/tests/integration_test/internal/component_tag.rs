@@ -0,0 +1,243 @@
+use dal::edge::EdgeKind;
+use dal::node::NodeId;
+use dal::socket::SocketEdgeKind;
+use dal::{
+    Component, ComponentFilter, ComponentTag, ComponentTagInheritance, Connection, DalContext,
+    Socket, StandardModel,
+};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use std::collections::{BTreeMap, HashSet};
+
+async fn place_in_frame(ctx: &DalContext, child_node_id: NodeId, frame_node_id: NodeId) {
+    let child_socket =
+        Socket::find_frame_socket_for_node(ctx, child_node_id, SocketEdgeKind::ConfigurationOutput)
+            .await
+            .expect("could not find child frame socket");
+    let frame_socket =
+        Socket::find_frame_socket_for_node(ctx, frame_node_id, SocketEdgeKind::ConfigurationInput)
+            .await
+            .expect("could not find parent frame socket");
+    Connection::new(
+        ctx,
+        child_node_id,
+        *child_socket.id(),
+        frame_node_id,
+        *frame_socket.id(),
+        EdgeKind::Symbolic,
+    )
+    .await
+    .expect("could not connect component to frame");
+}
+
+#[test]
+async fn upsert_and_remove(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let bag = bagger.create_component(ctx, "starfield", "starfield").await;
+
+    ComponentTag::upsert(
+        ctx,
+        bag.component_id,
+        "team",
+        "platform",
+        ComponentTagInheritance::None,
+    )
+    .await
+    .expect("could not set tag");
+    let tag = ComponentTag::upsert(
+        ctx,
+        bag.component_id,
+        "team",
+        "constellation",
+        ComponentTagInheritance::Children,
+    )
+    .await
+    .expect("could not update tag");
+    assert_eq!("constellation", tag.value());
+    assert_eq!(ComponentTagInheritance::Children, *tag.inheritance());
+
+    let tags = ComponentTag::list_for_component(ctx, bag.component_id)
+        .await
+        .expect("could not list tags");
+    assert_eq!(1, tags.len());
+
+    assert!(ComponentTag::remove(ctx, bag.component_id, "team")
+        .await
+        .expect("could not remove tag"));
+    assert!(!ComponentTag::remove(ctx, bag.component_id, "team")
+        .await
+        .expect("could not remove tag"));
+    assert!(ComponentTag::list_for_component(ctx, bag.component_id)
+        .await
+        .expect("could not list tags")
+        .is_empty());
+}
+
+#[test]
+async fn one_tag_per_key(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let bag = bagger.create_component(ctx, "starfield", "starfield").await;
+
+    let tag = ComponentTag::new(
+        ctx,
+        bag.component_id,
+        "team",
+        "platform",
+        ComponentTagInheritance::None,
+    )
+    .await
+    .expect("could not create tag");
+    // Creating the same tag again, as a racing upsert would, updates it instead.
+    let again = ComponentTag::new(
+        ctx,
+        bag.component_id,
+        " team ",
+        "constellation",
+        ComponentTagInheritance::Descendants,
+    )
+    .await
+    .expect("could not create tag again");
+    assert_eq!(tag.id(), again.id());
+
+    let tags = ComponentTag::list_for_component(ctx, bag.component_id)
+        .await
+        .expect("could not list tags");
+    assert_eq!(1, tags.len());
+    assert_eq!("constellation", tags[0].value());
+    assert_eq!(ComponentTagInheritance::Descendants, *tags[0].inheritance());
+}
+
+#[test]
+async fn deleted_and_restored_with_component(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let bag = bagger.create_component(ctx, "starfield", "starfield").await;
+
+    ComponentTag::upsert(
+        ctx,
+        bag.component_id,
+        "team",
+        "platform",
+        ComponentTagInheritance::None,
+    )
+    .await
+    .expect("could not set tag");
+
+    let mut component = Component::get_by_id(ctx, &bag.component_id)
+        .await
+        .expect("could not get component")
+        .expect("component not found");
+    component
+        .delete_and_propagate(ctx)
+        .await
+        .expect("could not delete component");
+    assert!(ComponentTag::list_for_component(ctx, bag.component_id)
+        .await
+        .expect("could not list tags")
+        .is_empty());
+
+    Component::restore_and_propagate(ctx, bag.component_id)
+        .await
+        .expect("could not restore component");
+    let tags = ComponentTag::list_for_component(ctx, bag.component_id)
+        .await
+        .expect("could not list tags");
+    assert_eq!(
+        vec![("team", "platform")], // expected
+        tags.iter()
+            .map(|tag| (tag.key(), tag.value()))
+            .collect::<Vec<_>>(), // actual
+    );
+}
+
+#[test]
+async fn inherited_from_frames(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let grandparent_bag = bagger
+        .create_component(ctx, "grandparent", "starfield")
+        .await;
+    let parent_bag = bagger.create_component(ctx, "parent", "starfield").await;
+    let child_bag = bagger.create_component(ctx, "child", "fallout").await;
+    place_in_frame(ctx, parent_bag.node_id, grandparent_bag.node_id).await;
+    place_in_frame(ctx, child_bag.node_id, parent_bag.node_id).await;
+
+    for (component_id, key, value, inheritance) in [
+        (
+            grandparent_bag.component_id,
+            "env",
+            "prod",
+            ComponentTagInheritance::Descendants,
+        ),
+        (
+            grandparent_bag.component_id,
+            "team",
+            "infra",
+            ComponentTagInheritance::Children,
+        ),
+        (
+            grandparent_bag.component_id,
+            "secret",
+            "shh",
+            ComponentTagInheritance::None,
+        ),
+        (
+            parent_bag.component_id,
+            "team",
+            "platform",
+            ComponentTagInheritance::Children,
+        ),
+        (
+            child_bag.component_id,
+            "cost center",
+            "R&D",
+            ComponentTagInheritance::None,
+        ),
+    ] {
+        ComponentTag::new(ctx, component_id, key, value, inheritance)
+            .await
+            .expect("could not create tag");
+    }
+
+    let effective = |component_id| async move {
+        ComponentTag::effective_for_component(ctx, component_id)
+            .await
+            .expect("could not get effective tags")
+            .into_iter()
+            .map(|(key, tag)| (key, (tag.value, tag.inherited)))
+            .collect::<BTreeMap<_, _>>()
+    };
+
+    assert_eq!(
+        BTreeMap::from([
+            ("env".to_owned(), ("prod".to_owned(), true)),
+            ("team".to_owned(), ("platform".to_owned(), false)),
+        ]), // expected
+        effective(parent_bag.component_id).await, // actual
+    );
+    // Tags inherited by children only do not skip a level, and the closest frame wins.
+    assert_eq!(
+        BTreeMap::from([
+            ("cost center".to_owned(), ("R&D".to_owned(), false)),
+            ("env".to_owned(), ("prod".to_owned(), true)),
+            ("team".to_owned(), ("platform".to_owned(), true)),
+        ]), // expected
+        effective(child_bag.component_id).await, // actual
+    );
+
+    let matches: HashSet<_> = ComponentFilter::parse(r#"tag "env" = "prod""#)
+        .expect("could not parse query")
+        .execute(ctx, &[])
+        .await
+        .expect("could not execute query")
+        .into_iter()
+        .map(|query_match| query_match.component_id)
+        .collect();
+    assert_eq!(
+        HashSet::from([
+            grandparent_bag.component_id,
+            parent_bag.component_id,
+            child_bag.component_id,
+        ]), // expected
+        matches, // actual
+    );
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use dal::edge::EdgeKind;
+use dal::node_menu::GenerateMenuItem;
+use dal::socket::SocketEdgeKind;
+use dal::{
+    ComponentTemplate, ComponentTemplateParameter, ComponentView, Connection, DalContext, Edge,
+    Socket, StandardModel,
+};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn capture_and_instantiate(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "tail", "fallout").await;
+    let starfield_bag = bagger.create_component(ctx, "head", "starfield").await;
+
+    let freestar_prop = starfield_bag
+        .find_prop(ctx, &["root", "domain", "freestar"])
+        .await;
+    starfield_bag
+        .update_attribute_value_for_prop(
+            ctx,
+            *freestar_prop.id(),
+            Some(serde_json::json!["t2.micro"]),
+        )
+        .await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        fallout_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        starfield_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    Connection::new(
+        ctx,
+        fallout_bag.node_id,
+        *output_socket.id(),
+        starfield_bag.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+    )
+    .await
+    .expect("could not create connection");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let template = ComponentTemplate::capture(
+        ctx,
+        "game night",
+        Some("a pair of games"),
+        &[fallout_bag.component_id, starfield_bag.component_id],
+        vec![ComponentTemplateParameter {
+            name: "size".to_owned(),
+            description: None,
+            default_value: Some(serde_json::json!["t2.micro"]),
+        }],
+    )
+    .await
+    .expect("could not capture template");
+
+    let definition = template.definition();
+    assert_eq!(2, definition.components.len());
+    assert_eq!(1, definition.edges.len());
+    assert_eq!("bethesda", definition.edges[0].from_socket);
+
+    // The template shows up in the node add menu.
+    let menu = GenerateMenuItem::new(ctx, true)
+        .await
+        .expect("could not generate menu");
+    assert!(menu
+        .raw_templates
+        .iter()
+        .any(|item| item.template_id == *template.id()));
+
+    let instances = template
+        .instantiate(
+            ctx,
+            500,
+            500,
+            HashMap::from([("size".to_owned(), serde_json::json!["m5.large"])]),
+        )
+        .await
+        .expect("could not instantiate template");
+    assert_eq!(2, instances.len());
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let starfield_instance = instances
+        .iter()
+        .find(|instance| instance.key == starfield_bag.component_id.to_string())
+        .expect("could not find starfield instance");
+    let view = ComponentView::new(ctx, starfield_instance.component_id)
+        .await
+        .expect("could not get component view");
+    assert_eq!(
+        Some(&serde_json::json!["m5.large"]),        // expected
+        view.properties.pointer("/domain/freestar"), // actual
+    );
+
+    let configuration_edges: Vec<Edge> =
+        Edge::list_for_component(ctx, starfield_instance.component_id)
+            .await
+            .expect("could not list edges")
+            .into_iter()
+            .filter(|edge| *edge.kind() == EdgeKind::Configuration)
+            .collect();
+    assert_eq!(1, configuration_edges.len());
+}
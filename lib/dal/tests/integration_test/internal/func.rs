@@ -106,6 +106,31 @@ async fn func_binding_execute_unset(ctx: &DalContext) {
     assert_eq!(return_value.unprocessed_value(), None,);
 }
 
+#[test]
+async fn func_binding_execute_expression(ctx: &DalContext) {
+    let name = dal_test::test_harness::generate_fake_name();
+    let mut func = Func::new(
+        ctx,
+        name,
+        FuncBackendKind::Expression,
+        FuncBackendResponseType::String,
+    )
+    .await
+    .expect("cannot create func");
+    func.set_code_plaintext(ctx, Some("`${lower(name)}-${port ?? 80}`"))
+        .await
+        .expect("cannot set code");
+    let args = serde_json::json!({ "name": "Web", "port": null });
+
+    let func_binding = create_func_binding(ctx, args, *func.id(), *func.backend_kind()).await;
+
+    let return_value = func_binding
+        .execute(ctx)
+        .await
+        .expect("failed to execute func binding");
+    assert_eq!(return_value.value(), Some(&serde_json::json!("web-80")));
+}
+
 #[test]
 async fn func_argument_new(ctx: &DalContext) {
     let func_id = FuncId::generate();
@@ -1,4 +1,4 @@
-use dal::{DalContext, HistoryActor, HistoryEvent};
+use dal::{DalContext, HistoryActor, HistoryEvent, HistoryEventFilter, WorkspaceSignup};
 use dal_test::test;
 
 #[test]
@@ -17,3 +17,72 @@ async fn new(ctx: &DalContext) {
     assert_eq!(&history_event.data, &serde_json::json!({}));
     assert_eq!(&history_event.tenancy, ctx.tenancy());
 }
+
+#[test]
+async fn list(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let user_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(nw.user.pk()));
+    for label in ["audit_test.first", "audit_test.second", "audit_test.third"] {
+        HistoryEvent::new(
+            &user_ctx,
+            label,
+            "audit test",
+            &serde_json::json!({ "nested": { "id": label } }),
+        )
+        .await
+        .expect("cannot create a new history event");
+    }
+    HistoryEvent::new(
+        ctx,
+        "audit_test.system",
+        "audit test",
+        &serde_json::json!({}),
+    )
+    .await
+    .expect("cannot create a new history event");
+
+    let by_prefix = HistoryEventFilter {
+        label_prefix: Some("audit_test.*".to_owned()),
+        ..Default::default()
+    };
+    let page = HistoryEvent::list(ctx, &by_prefix, None, 2)
+        .await
+        .expect("cannot list history events");
+    assert_eq!(
+        vec!["audit_test.system", "audit_test.third"],
+        page.events
+            .iter()
+            .map(|event| event.label.as_str())
+            .collect::<Vec<_>>()
+    );
+    let page = HistoryEvent::list(ctx, &by_prefix, page.next_cursor, 2)
+        .await
+        .expect("cannot list history events");
+    assert_eq!(
+        vec!["audit_test.second", "audit_test.first"],
+        page.events
+            .iter()
+            .map(|event| event.label.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert!(page.next_cursor.is_none());
+
+    let by_actor = HistoryEventFilter {
+        actor: Some(HistoryActor::User(nw.user.pk())),
+        label_prefix: Some("audit_test.".to_owned()),
+        ..Default::default()
+    };
+    let page = HistoryEvent::list(ctx, &by_actor, None, 10)
+        .await
+        .expect("cannot list history events");
+    assert_eq!(3, page.events.len());
+
+    let by_object = HistoryEventFilter {
+        object_id: Some("audit_test.second".to_owned()),
+        ..Default::default()
+    };
+    let page = HistoryEvent::list(ctx, &by_object, None, 10)
+        .await
+        .expect("cannot list history events");
+    assert_eq!(1, page.events.len());
+    assert_eq!("audit_test.second", page.events[0].label);
+}
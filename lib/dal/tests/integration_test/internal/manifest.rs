@@ -0,0 +1,240 @@
+use dal::edge::EdgeKind;
+use dal::manifest::ManifestConnection;
+use dal::{
+    Component, ComponentType, ComponentView, DalContext, Edge, Manifest, ManifestChange,
+    ManifestError, StandardModel,
+};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+const MANIFEST: &str = r#"
+components:
+  - name: tail
+    schemaName: fallout
+  - name: head
+    schemaName: starfield
+    values:
+      domain:
+        freestar: t2.micro
+connections:
+  - fromComponent: tail
+    fromSocket: bethesda
+    toComponent: head
+    toSocket: bethesda
+"#;
+
+#[test]
+async fn plan_and_apply(ctx: &DalContext) {
+    let manifest = Manifest::parse(MANIFEST).expect("could not parse manifest");
+
+    let plan = manifest.plan(ctx, false).await.expect("could not plan");
+    assert!(plan.components.is_empty());
+    assert_eq!(
+        2,
+        plan.changes
+            .iter()
+            .filter(|change| matches!(change, ManifestChange::CreateComponent { .. }))
+            .count()
+    );
+    assert!(plan
+        .changes
+        .contains(&ManifestChange::CreateConnection(ManifestConnection {
+            from_component: "tail".to_owned(),
+            from_socket: "bethesda".to_owned(),
+            to_component: "head".to_owned(),
+            to_socket: "bethesda".to_owned(),
+        })));
+
+    let components = plan.apply(ctx).await.expect("could not apply plan");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let head_id = *components.get("head").expect("head was not created");
+    let view = ComponentView::new(ctx, head_id)
+        .await
+        .expect("could not get component view");
+    assert_eq!(
+        Some(&serde_json::json!["t2.micro"]),        // expected
+        view.properties.pointer("/domain/freestar"), // actual
+    );
+    let configuration_edges = Edge::list_for_component(ctx, head_id)
+        .await
+        .expect("could not list edges")
+        .into_iter()
+        .filter(|edge| *edge.kind() == EdgeKind::Configuration)
+        .count();
+    assert_eq!(1, configuration_edges);
+
+    // Once applied, the workspace matches the manifest.
+    let plan = manifest.plan(ctx, false).await.expect("could not plan");
+    assert!(plan.is_empty());
+    assert_eq!(2, plan.components.len());
+}
+
+#[test]
+async fn plan_updates_and_deletes(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let tail_bag = bagger.create_component(ctx, "tail", "fallout").await;
+    let head_bag = bagger.create_component(ctx, "head", "starfield").await;
+    let stray_bag = bagger.create_component(ctx, "stray", "fallout").await;
+    let frame_bag = bagger.create_component(ctx, "frame", "fallout").await;
+    frame_bag
+        .component(ctx)
+        .await
+        .set_type(ctx, ComponentType::ConfigurationFrame)
+        .await
+        .expect("could not set component type");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let mut manifest = Manifest::parse(MANIFEST).expect("could not parse manifest");
+    manifest.connections.clear();
+
+    let plan = manifest.plan(ctx, true).await.expect("could not plan");
+    assert_eq!(
+        Some(&head_bag.component_id), // expected
+        plan.components.get("head"),  // actual
+    );
+    assert_eq!(
+        Some(&tail_bag.component_id), // expected
+        plan.components.get("tail"),  // actual
+    );
+    assert!(plan.changes.iter().any(|change| matches!(
+        change,
+        ManifestChange::SetValue { component, path, .. }
+            if component == "head" && path.last().map(String::as_str) == Some("freestar")
+    )));
+    assert!(plan.changes.contains(&ManifestChange::DeleteComponent {
+        name: "stray".to_owned(),
+        component_id: stray_bag.component_id,
+    }));
+    // Frames aren't described by manifests, so they are never pruned.
+    assert!(!plan.changes.iter().any(|change| matches!(
+        change,
+        ManifestChange::DeleteComponent { component_id, .. }
+            if *component_id == frame_bag.component_id
+    )));
+
+    plan.apply(ctx).await.expect("could not apply plan");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert!(Component::get_by_id(ctx, &stray_bag.component_id)
+        .await
+        .expect("could not get component")
+        .is_none());
+    assert!(Component::get_by_id(ctx, &frame_bag.component_id)
+        .await
+        .expect("could not get component")
+        .is_some());
+    let plan = manifest.plan(ctx, true).await.expect("could not plan");
+    assert!(plan.is_empty());
+}
+
+#[test]
+async fn plan_deletes_connections(ctx: &DalContext) {
+    let manifest = Manifest::parse(MANIFEST).expect("could not parse manifest");
+    let components = manifest
+        .plan(ctx, false)
+        .await
+        .expect("could not plan")
+        .apply(ctx)
+        .await
+        .expect("could not apply plan");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+    let head_id = *components.get("head").expect("head was not created");
+
+    let mut manifest = manifest;
+    let connection = manifest
+        .connections
+        .pop()
+        .expect("manifest has a connection");
+    let plan = manifest.plan(ctx, false).await.expect("could not plan");
+    let edge_id = match plan.changes.as_slice() {
+        [ManifestChange::DeleteConnection {
+            edge_id,
+            connection: deleted,
+        }] => {
+            assert_eq!(&connection, deleted);
+            *edge_id
+        }
+        changes => panic!("expected a single deleted connection, got {changes:?}"),
+    };
+
+    plan.apply(ctx).await.expect("could not apply plan");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert!(Edge::get_by_id(ctx, &edge_id)
+        .await
+        .expect("could not get edge")
+        .is_none());
+    let configuration_edges = Edge::list_for_component(ctx, head_id)
+        .await
+        .expect("could not list edges")
+        .into_iter()
+        .filter(|edge| *edge.kind() == EdgeKind::Configuration)
+        .count();
+    assert_eq!(0, configuration_edges);
+    let plan = manifest.plan(ctx, false).await.expect("could not plan");
+    assert!(plan.is_empty());
+}
+
+#[test]
+async fn plan_rejects_replacing_elements(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let head_bag = bagger.create_component(ctx, "head", "starfield").await;
+    let galaxies_prop = head_bag
+        .find_prop(ctx, &["root", "domain", "universe", "galaxies"])
+        .await;
+    let galaxy_prop = head_bag
+        .find_prop(ctx, &["root", "domain", "universe", "galaxies", "galaxy"])
+        .await;
+    head_bag
+        .insert_array_object_element(ctx, *galaxies_prop.id(), *galaxy_prop.id())
+        .await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let manifest = Manifest::parse(
+        r#"
+components:
+  - name: head
+    schemaName: starfield
+    values:
+      domain:
+        universe:
+          galaxies:
+            - sun: sol
+              planets: 8
+"#,
+    )
+    .expect("could not parse manifest");
+    let result = manifest.plan(ctx, false).await;
+    assert!(matches!(
+        result,
+        Err(ManifestError::ContainerNotEmpty(name, path))
+            if name == "head" && path == "root/domain/universe/galaxies"
+    ));
+}
+
+#[test]
+async fn plan_rejects_schema_mismatch(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    bagger.create_component(ctx, "head", "fallout").await;
+
+    let manifest = Manifest::parse(MANIFEST).expect("could not parse manifest");
+    let result = manifest.plan(ctx, false).await;
+    assert!(matches!(
+        result,
+        Err(ManifestError::ComponentSchemaMismatch(name, _, _)) if name == "head"
+    ));
+}
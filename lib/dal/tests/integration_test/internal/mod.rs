@@ -1,7 +1,10 @@
 mod action_prototype;
+mod api_token;
 mod attribute;
 mod change_set;
 mod component;
+mod component_tag;
+mod component_template;
 mod diagram;
 mod edge;
 mod func;
@@ -9,6 +12,7 @@ mod func_execution;
 mod graph;
 mod history_event;
 mod key_pair;
+mod manifest;
 mod node;
 mod node_menu;
 mod pkg;
@@ -16,6 +20,8 @@ mod prop;
 mod prop_tree;
 mod property_editor;
 mod provider;
+mod query;
+mod resource_import;
 mod schema;
 mod secret;
 mod socket;
@@ -26,4 +32,5 @@ mod user;
 mod validation_prototype;
 mod validation_resolver;
 mod visibility;
+mod webhook;
 mod workspace;
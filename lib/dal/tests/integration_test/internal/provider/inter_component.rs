@@ -1,7 +1,8 @@
 use dal::{
     socket::SocketArity, AttributeContext, AttributePrototypeArgument, AttributeReadContext,
-    AttributeValue, Component, ComponentView, DalContext, Edge, ExternalProvider, InternalProvider,
-    Prop, PropId, PropKind, StandardModel,
+    AttributeValue, AttributeValueLocation, AttributeValueProvenance,
+    AttributeValueProvenanceSource, Component, ComponentView, DalContext, Edge, ExternalProvider,
+    InternalProvider, Prop, PropId, PropKind, StandardModel,
 };
 use dal_test::{
     helpers::{component_bag::ComponentBag, setup_identity_func},
@@ -308,6 +309,89 @@ async fn inter_component_identity_update(ctx: &DalContext) {
         }], // expected
         swings_bag.component_view_properties_raw(ctx).await // actual
     );
+
+    // Walk from "destination" back across the edge to the "source" value set on "esp".
+    let destination_provenance = AttributeValue::find_for_context(
+        ctx,
+        swings_bag.attribute_read_context_with_prop(destination_prop_id),
+    )
+    .await
+    .expect("cannot find attribute value")
+    .expect("attribute value not found")
+    .provenance(ctx)
+    .await
+    .expect("could not get provenance");
+    assert_eq!(
+        AttributeValueLocation::Prop {
+            prop_id: destination_prop_id,
+            path: "/root/domain/destination".to_string(),
+        },
+        destination_provenance.location
+    );
+    assert_eq!(Some(serde_json::json!["two"]), destination_provenance.value);
+
+    let input_socket_provenance = single_argument_source(&destination_provenance);
+    assert_eq!(
+        swings_bag.component_id,
+        input_socket_provenance.component_id
+    );
+    assert_eq!(
+        AttributeValueLocation::InternalProvider {
+            internal_provider_id: *swings_explicit_internal_provider.id(),
+            name: "swings".to_string(),
+            prop_id: None,
+        },
+        input_socket_provenance.location
+    );
+
+    let output_socket_provenance = single_argument_source(input_socket_provenance);
+    assert_eq!(esp_bag.component_id, output_socket_provenance.component_id);
+    assert_eq!(
+        Some("esp"),
+        output_socket_provenance.component_name.as_deref()
+    );
+    assert_eq!(
+        AttributeValueLocation::ExternalProvider {
+            external_provider_id: *esp_external_provider.id(),
+            name: "output".to_string(),
+        },
+        output_socket_provenance.location
+    );
+
+    let intermediate_provenance = match &single_argument_source(output_socket_provenance).source {
+        AttributeValueProvenanceSource::Prop { source } => source,
+        source => panic!("expected the implicit internal provider of a prop, found: {source:?}"),
+    };
+    assert_eq!(
+        AttributeValueLocation::Prop {
+            prop_id: intermediate_prop_id,
+            path: "/root/domain/object/intermediate".to_string(),
+        },
+        intermediate_provenance.location
+    );
+
+    let source_provenance = match &single_argument_source(intermediate_provenance).source {
+        AttributeValueProvenanceSource::Prop { source } => source,
+        source => panic!("expected the implicit internal provider of a prop, found: {source:?}"),
+    };
+    assert_eq!(Some(serde_json::json!["two"]), source_provenance.value);
+    assert!(matches!(
+        source_provenance.source,
+        AttributeValueProvenanceSource::Manual { .. }
+    ));
+}
+
+/// Expect a value computed by the identity func and return where its single argument came from.
+fn single_argument_source(provenance: &AttributeValueProvenance) -> &AttributeValueProvenance {
+    match &provenance.source {
+        AttributeValueProvenanceSource::Computed { func, arguments } => {
+            assert_eq!("si:identity", func.name);
+            assert_eq!(1, arguments.len());
+            assert_eq!(1, arguments[0].sources.len());
+            &arguments[0].sources[0]
+        }
+        source => panic!("expected a computed value, found: {source:?}"),
+    }
 }
 
 // 38.805354552534816, -77.05091482877533
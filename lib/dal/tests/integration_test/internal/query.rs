@@ -0,0 +1,134 @@
+use dal::edge::EdgeKind;
+use dal::socket::SocketEdgeKind;
+use dal::{ComponentFilter, ComponentId, Connection, DalContext, Socket, StandardModel};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+use std::collections::HashSet;
+
+async fn query(ctx: &DalContext, source: &str) -> HashSet<ComponentId> {
+    ComponentFilter::parse(source)
+        .expect("could not parse query")
+        .execute(ctx, &[])
+        .await
+        .expect("could not execute query")
+        .into_iter()
+        .map(|query_match| query_match.component_id)
+        .collect()
+}
+
+#[test]
+async fn execute(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "tail", "fallout").await;
+    let alpha_bag = bagger.create_component(ctx, "alpha", "starfield").await;
+    let beta_bag = bagger.create_component(ctx, "beta", "starfield").await;
+
+    let freestar_prop = alpha_bag
+        .find_prop(ctx, &["root", "domain", "freestar"])
+        .await;
+    alpha_bag
+        .update_attribute_value_for_prop(
+            ctx,
+            *freestar_prop.id(),
+            Some(serde_json::json!["t2.micro"]),
+        )
+        .await;
+    beta_bag
+        .update_attribute_value_for_prop(
+            ctx,
+            *freestar_prop.id(),
+            Some(serde_json::json!["m5.large"]),
+        )
+        .await;
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        fallout_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        alpha_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    Connection::new(
+        ctx,
+        fallout_bag.node_id,
+        *output_socket.id(),
+        alpha_bag.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+    )
+    .await
+    .expect("could not create connection");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    assert_eq!(
+        HashSet::from([alpha_bag.component_id, beta_bag.component_id]), // expected
+        query(ctx, r#"schema = "starfield""#).await,                    // actual
+    );
+    assert_eq!(
+        HashSet::from([fallout_bag.component_id]),       // expected
+        query(ctx, r#"not schema = "starfield""#).await, // actual
+    );
+    assert_eq!(
+        HashSet::from([alpha_bag.component_id]), // expected
+        query(
+            ctx,
+            r#"schema = "starfield" and domain/freestar startsWith "t2""#
+        )
+        .await, // actual
+    );
+    assert_eq!(
+        HashSet::from([fallout_bag.component_id, beta_bag.component_id]), // expected
+        query(
+            ctx,
+            r#"domain/freestar = "m5.large" or not schema = "starfield""#
+        )
+        .await, // actual
+    );
+    assert_eq!(
+        HashSet::from([alpha_bag.component_id]), // expected
+        query(
+            ctx,
+            &format!(r#"connectedTo "{}""#, fallout_bag.component_id)
+        )
+        .await, // actual
+    );
+    assert_eq!(
+        HashSet::from([
+            fallout_bag.component_id,
+            alpha_bag.component_id,
+            beta_bag.component_id
+        ]), // expected
+        query(ctx, "change = added").await, // actual
+    );
+
+    // The values of the prop paths used by the query and the selected ones are returned.
+    let matches = ComponentFilter::parse(r#"domain/freestar endsWith "large""#)
+        .expect("could not parse query")
+        .execute(ctx, &["si/name".to_owned()])
+        .await
+        .expect("could not execute query");
+    assert_eq!(1, matches.len());
+    assert_eq!(beta_bag.component_id, matches[0].component_id);
+    assert_eq!(
+        serde_json::json!({
+            "domain/freestar": "m5.large",
+            "si/name": "beta",
+        }), // expected
+        serde_json::to_value(&matches[0].values).expect("could not serialize values"), // actual
+    );
+}
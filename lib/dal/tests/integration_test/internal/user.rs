@@ -1,4 +1,8 @@
-use dal::{DalContext, User, UserPk, WorkspaceSignup};
+use dal::{
+    DalContext, HistoryActor, User, UserCapability, UserError, UserPk, WorkspaceRole,
+    WorkspaceSignup,
+};
+use dal_test::helpers::{create_change_set, create_user};
 use dal_test::test;
 
 #[test]
@@ -16,26 +20,93 @@ async fn new(ctx: &DalContext) {
 
 #[test]
 async fn authorize(ctx: &DalContext, nw: &WorkspaceSignup) {
-    let worked = User::authorize(ctx, &nw.user.pk())
+    let worked = User::authorize(ctx, &nw.user.pk(), UserCapability::ManageRoles)
         .await
-        .expect("admin group user should be authorized");
-    assert!(worked, "authorized admin group user returns true");
+        .expect("could not authorize user");
+    assert!(worked, "the user who signed up owns the workspace");
 
-    // TODO(theo,paulo): re-enable that when capabilities are back
-    /*
-    let user_no_group = User::new(
-        ctx,
-        UserPk::generate(),
-        "funky",
-        "bobotclown@systeminit.com",
-    )
-    .await
-    .expect("cannot create user");
+    let outsider = create_user(ctx).await;
+    let worked = User::authorize(ctx, &outsider.pk(), UserCapability::Read)
+        .await
+        .expect("could not authorize user");
+    assert!(!worked, "users outside of the workspace can't do anything");
 
-    let f = User::authorize(ctx, &user_no_group.pk()).await;
+    let member = create_user(ctx).await;
+    member
+        .associate_workspace(ctx, *nw.workspace.pk())
+        .await
+        .expect("could not associate workspace");
+    assert_eq!(
+        Some(WorkspaceRole::Editor),
+        User::workspace_role(ctx, member.pk(), *nw.workspace.pk())
+            .await
+            .expect("could not get workspace role"),
+    );
+    assert!(User::authorize(ctx, &member.pk(), UserCapability::Write)
+        .await
+        .expect("could not authorize user"));
     assert!(
-        f.is_err(),
-        "user that is not in the admin group should fail"
+        !User::authorize(ctx, &member.pk(), UserCapability::ApplyChangeSet)
+            .await
+            .expect("could not authorize user")
     );
-    */
+}
+
+#[test]
+async fn set_workspace_role(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let member = create_user(ctx).await;
+    member
+        .associate_workspace(ctx, *nw.workspace.pk())
+        .await
+        .expect("could not associate workspace");
+
+    // Only owners can change roles.
+    let member_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(member.pk()));
+    let result = User::set_workspace_role(&member_ctx, member.pk(), WorkspaceRole::Owner).await;
+    assert!(matches!(
+        result,
+        Err(UserError::Unauthorized(_, UserCapability::ManageRoles))
+    ));
+
+    let owner_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(nw.user.pk()));
+    User::set_workspace_role(&owner_ctx, member.pk(), WorkspaceRole::Viewer)
+        .await
+        .expect("could not set workspace role");
+
+    let members = User::list_members(ctx)
+        .await
+        .expect("could not list members");
+    assert_eq!(2, members.len());
+    assert!(members
+        .iter()
+        .any(|m| m.user.pk() == nw.user.pk() && m.role == WorkspaceRole::Owner));
+    assert!(members
+        .iter()
+        .any(|m| m.user.pk() == member.pk() && m.role == WorkspaceRole::Viewer));
+
+    // The last owner can't give up ownership.
+    let result = User::set_workspace_role(&owner_ctx, nw.user.pk(), WorkspaceRole::Editor).await;
+    assert!(matches!(result, Err(UserError::LastOwner)));
+}
+
+#[test]
+async fn viewers_cannot_apply_change_sets(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let viewer = create_user(ctx).await;
+    viewer
+        .associate_workspace(ctx, *nw.workspace.pk())
+        .await
+        .expect("could not associate workspace");
+    User::set_workspace_role(ctx, viewer.pk(), WorkspaceRole::Viewer)
+        .await
+        .expect("could not set workspace role");
+
+    let mut change_set = create_change_set(ctx).await;
+    let mut viewer_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(viewer.pk()));
+    assert!(change_set.apply(&mut viewer_ctx).await.is_err());
+
+    let mut owner_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(nw.user.pk()));
+    change_set
+        .apply(&mut owner_ctx)
+        .await
+        .expect("owners can apply change sets");
 }
@@ -0,0 +1,245 @@
+use dal::{
+    ChangeSetPk, DalContext, HistoryActor, User, UserCapability, UserError, Webhook, WebhookClient,
+    WebhookDeliveryStatus, WebhookError, WebhookEventKind, WorkspaceRole, WorkspaceSignup, WsEvent,
+};
+use dal_test::helpers::create_user;
+use dal_test::test;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// A stub HTTP server that answers a single request with the given status line, and hands back
+/// the request headers (lowercased) and body.
+async fn stub_server(status: &'static str) -> (String, JoinHandle<(String, Vec<u8>)>) {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("could not bind stub server");
+    let url = format!(
+        "http://{}/hook",
+        listener.local_addr().expect("stub server has no address")
+    );
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.expect("could not accept");
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        let (headers, body_start) = loop {
+            let read = stream.read(&mut buf).await.expect("could not read");
+            request.extend_from_slice(&buf[..read]);
+            if let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                break (
+                    String::from_utf8_lossy(&request[..end]).to_lowercase(),
+                    end + 4,
+                );
+            }
+        };
+        let content_length: usize = headers
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .map(|value| value.trim().parse().expect("bad content length"))
+            .unwrap_or(0);
+        while request.len() < body_start + content_length {
+            let read = stream.read(&mut buf).await.expect("could not read");
+            request.extend_from_slice(&buf[..read]);
+        }
+        stream
+            .write_all(format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\n\r\n").as_bytes())
+            .await
+            .expect("could not write");
+        (headers, request[body_start..].to_vec())
+    });
+
+    (url, handle)
+}
+
+#[test]
+async fn ping_is_signed_and_delivered(ctx: &DalContext) {
+    let (url, stub) = stub_server("200 OK").await;
+    let webhook = Webhook::new(ctx, &url, vec![WebhookEventKind::ChangeSetApplied])
+        .await
+        .expect("could not create webhook");
+    assert!(webhook.enabled());
+
+    let mut delivery = webhook.ping(ctx).await.expect("could not ping webhook");
+    assert_eq!(WebhookDeliveryStatus::Pending, delivery.status());
+    // Pings are leased to whoever posts them, so the dispatcher doesn't post them too.
+    assert!(delivery.next_attempt_at() > chrono::Utc::now());
+    let attempt = delivery
+        .post(&webhook, &WebhookClient::allowing_private_addresses())
+        .await
+        .expect("could not post delivery");
+    delivery
+        .record(ctx, attempt)
+        .await
+        .expect("could not record attempt");
+
+    assert_eq!(WebhookDeliveryStatus::Succeeded, delivery.status());
+    assert_eq!(1, delivery.attempts());
+    assert_eq!(Some(200), delivery.last_status_code());
+    assert!(delivery.delivered_at().is_some());
+
+    let (headers, body) = stub.await.expect("stub server panicked");
+    let signature = webhook.sign(&body).to_lowercase();
+    assert!(headers.contains(&format!("x-si-signature: {signature}")));
+    assert!(headers.contains("x-si-event: ping"));
+    let body: serde_json::Value = serde_json::from_slice(&body).expect("body is not json");
+    assert_eq!(&serde_json::json!("ping"), &body["kind"]);
+
+    let deliveries = webhook
+        .deliveries(ctx, 10)
+        .await
+        .expect("could not list deliveries");
+    assert_eq!(vec![delivery], deliveries);
+}
+
+#[test]
+async fn failed_deliveries_are_retried(ctx: &DalContext) {
+    let (url, stub) = stub_server("500 Internal Server Error").await;
+    let webhook = Webhook::new(ctx, &url, vec![WebhookEventKind::FixBatchReturn])
+        .await
+        .expect("could not create webhook");
+
+    let mut delivery = webhook.ping(ctx).await.expect("could not ping webhook");
+    let attempt = delivery
+        .post(&webhook, &WebhookClient::allowing_private_addresses())
+        .await
+        .expect("could not post delivery");
+    delivery
+        .record(ctx, attempt)
+        .await
+        .expect("could not record attempt");
+    stub.await.expect("stub server panicked");
+
+    assert_eq!(WebhookDeliveryStatus::Pending, delivery.status());
+    assert_eq!(1, delivery.attempts());
+    assert_eq!(Some(500), delivery.last_status_code());
+    assert!(delivery.next_attempt_at() > chrono::Utc::now());
+}
+
+#[test]
+async fn private_addresses_are_refused(ctx: &DalContext) {
+    let (url, _stub) = stub_server("200 OK").await;
+    let webhook = Webhook::new(ctx, &url, vec![WebhookEventKind::FixBatchReturn])
+        .await
+        .expect("could not create webhook");
+
+    let mut delivery = webhook.ping(ctx).await.expect("could not ping webhook");
+    let attempt = delivery
+        .post(&webhook, &WebhookClient::new())
+        .await
+        .expect("could not post delivery");
+    delivery
+        .record(ctx, attempt)
+        .await
+        .expect("could not record attempt");
+
+    assert_eq!(WebhookDeliveryStatus::Failed, delivery.status());
+    assert_eq!(None, delivery.last_status_code());
+    assert_eq!(
+        Some("webhook url does not resolve to a public address"),
+        delivery.last_error()
+    );
+}
+
+#[test]
+async fn events_are_queued_for_subscribed_webhooks(ctx: &DalContext) {
+    let mut subscribed = Webhook::new(
+        ctx,
+        "https://example.com/applied",
+        vec![WebhookEventKind::ChangeSetApplied],
+    )
+    .await
+    .expect("could not create webhook");
+    let _other = Webhook::new(
+        ctx,
+        "https://example.com/fixes",
+        vec![WebhookEventKind::FixBatchReturn],
+    )
+    .await
+    .expect("could not create webhook");
+
+    let applied = WsEvent::change_set_applied(ctx, ChangeSetPk::generate())
+        .await
+        .expect("could not create event");
+    let deliveries = Webhook::enqueue_for_event(ctx, &applied)
+        .await
+        .expect("could not queue deliveries");
+    assert_eq!(1, deliveries.len());
+    assert_eq!(subscribed.pk(), deliveries[0].webhook_pk());
+    assert_eq!(
+        WebhookEventKind::ChangeSetApplied,
+        deliveries[0].event_kind()
+    );
+
+    let created = WsEvent::change_set_created(ctx, ChangeSetPk::generate())
+        .await
+        .expect("could not create event");
+    assert!(Webhook::enqueue_for_event(ctx, &created)
+        .await
+        .expect("could not queue deliveries")
+        .is_empty());
+
+    subscribed
+        .update(
+            ctx,
+            subscribed.url().to_owned(),
+            subscribed.event_kinds().to_vec(),
+            false,
+        )
+        .await
+        .expect("could not disable webhook");
+    assert!(Webhook::enqueue_for_event(ctx, &applied)
+        .await
+        .expect("could not queue deliveries")
+        .is_empty());
+}
+
+#[test]
+async fn invalid_webhooks(ctx: &DalContext) {
+    let result = Webhook::new(
+        ctx,
+        "ftp://example.com",
+        vec![WebhookEventKind::FixBatchReturn],
+    )
+    .await;
+    assert!(matches!(result, Err(WebhookError::InvalidUrl(_))));
+
+    let result = Webhook::new(ctx, "https://example.com", vec![WebhookEventKind::Ping]).await;
+    assert!(matches!(result, Err(WebhookError::NoEventKinds)));
+}
+
+#[test]
+async fn only_owners_manage_webhooks(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let editor = create_user(ctx).await;
+    editor
+        .associate_workspace(ctx, *nw.workspace.pk())
+        .await
+        .expect("could not associate workspace");
+    User::set_workspace_role(ctx, editor.pk(), WorkspaceRole::Editor)
+        .await
+        .expect("could not set workspace role");
+
+    let editor_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(editor.pk()));
+    let result = Webhook::new(
+        &editor_ctx,
+        "https://example.com",
+        vec![WebhookEventKind::ChangeSetApplied],
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(WebhookError::User(UserError::Unauthorized(
+            _,
+            UserCapability::ManageWebhooks
+        )))
+    ));
+
+    let owner_ctx = ctx.clone_with_new_history_actor(HistoryActor::User(nw.user.pk()));
+    Webhook::new(
+        &owner_ctx,
+        "https://example.com",
+        vec![WebhookEventKind::ChangeSetApplied],
+    )
+    .await
+    .expect("owners can create webhooks");
+}
@@ -1,8 +1,19 @@
+use std::collections::HashSet;
+
+use si_pkg::LintReport;
 use ulid::Ulid;
 use url::Url;
 
-use crate::types::ModuleRejectionResponse;
-use crate::{IndexClientResult, ModuleDetailsResponse};
+use crate::types::{LintErrorResponse, ModuleRejectionResponse};
+use crate::{
+    content_checksum, CreateWorkspaceBackupRequest, IndexClientError, IndexClientResult,
+    MissingBackupNodesRequest, MissingBackupNodesResponse, ModuleDetailsResponse,
+    ModuleVersionsResponse, WorkspaceBackupResponse, WorkspaceBackupsResponse,
+    MAX_BACKUP_NODE_HASHES_PER_REQUEST,
+};
+
+// Keep each batch of backup node uploads comfortably below the module index's upload limit
+const MAX_BACKUP_NODE_UPLOAD_BYTES: usize = 1024 * 1024 * 8;
 
 #[derive(Debug, Clone)]
 pub struct IndexClient {
@@ -57,13 +68,43 @@ impl IndexClient {
             .multipart(reqwest::multipart::Form::new().part("module bundle", module_upload_part))
             .bearer_auth(&self.auth_token)
             .send()
+            .await?;
+        if upload_response.status() == reqwest::StatusCode::UNPROCESSABLE_ENTITY {
+            let issues = upload_response
+                .json::<LintErrorResponse>()
+                .await?
+                .error
+                .lint_issues;
+            return Err(IndexClientError::Lint(LintReport { issues }));
+        }
+        let upload_response = upload_response.error_for_status()?;
+
+        Ok(upload_response.json::<ModuleDetailsResponse>().await?)
+    }
+
+    pub async fn get_module_details(
+        &self,
+        module_id: Ulid,
+    ) -> IndexClientResult<ModuleDetailsResponse> {
+        let details_url = self
+            .base_url
+            .join("modules/")?
+            .join(&module_id.to_string())?;
+        let response = reqwest::Client::new()
+            .get(details_url)
+            .bearer_auth(&self.auth_token)
+            .send()
             .await?
             .error_for_status()?;
 
-        Ok(upload_response.json::<ModuleDetailsResponse>().await?)
+        Ok(response.json::<ModuleDetailsResponse>().await?)
     }
 
+    /// Downloads the latest version of a module. The module index may redirect to the storage
+    /// backend, so the bundle is checked against the checksum in the module details here.
     pub async fn download_module(&self, module_id: Ulid) -> IndexClientResult<Vec<u8>> {
+        let details = self.get_module_details(module_id).await?;
+
         let download_url = dbg!(self
             .base_url
             .join("modules/")?
@@ -79,6 +120,216 @@ impl IndexClient {
         let bytes = response.bytes().await?;
         dbg!(&bytes.len());
 
+        verify_checksum(&bytes, details.content_checksum.as_deref())?;
         Ok(bytes.to_vec())
     }
+
+    pub async fn list_module_versions(
+        &self,
+        module_id: Ulid,
+    ) -> IndexClientResult<ModuleVersionsResponse> {
+        let versions_url = self
+            .base_url
+            .join("modules/")?
+            .join(&format!("{}/", module_id))?
+            .join("versions")?;
+        let response = reqwest::Client::new()
+            .get(versions_url)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(response.json::<ModuleVersionsResponse>().await?)
+    }
+
+    /// Downloads a version of a module, checked against the checksum listed for the version.
+    pub async fn download_module_version(
+        &self,
+        module_id: Ulid,
+        version: &str,
+    ) -> IndexClientResult<Vec<u8>> {
+        let expected_checksum = self
+            .list_module_versions(module_id)
+            .await?
+            .versions
+            .into_iter()
+            .find(|details| details.version == version)
+            .and_then(|details| details.content_checksum);
+
+        let mut download_url = self
+            .base_url
+            .join("modules/")?
+            .join(&format!("{}/", module_id))?;
+        download_url
+            .path_segments_mut()
+            .map_err(|_| url::ParseError::RelativeUrlWithCannotBeABaseBase)?
+            .pop_if_empty()
+            .extend(["versions", version, "download"]);
+        let response = reqwest::Client::new()
+            .get(download_url)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let bytes = response.bytes().await?;
+        verify_checksum(&bytes, expected_checksum.as_deref())?;
+        Ok(bytes.to_vec())
+    }
+
+    /// Returns the hashes, out of those given, of backup nodes which are not yet stored in the
+    /// module index.
+    pub async fn missing_backup_nodes(&self, hashes: &[String]) -> IndexClientResult<Vec<String>> {
+        let missing_url = self.base_url.join("backups/nodes/missing")?;
+
+        let mut missing = Vec::new();
+        for chunk in hashes.chunks(MAX_BACKUP_NODE_HASHES_PER_REQUEST) {
+            let response = reqwest::Client::new()
+                .post(missing_url.clone())
+                .json(&MissingBackupNodesRequest {
+                    hashes: chunk.to_vec(),
+                })
+                .bearer_auth(&self.auth_token)
+                .send()
+                .await?
+                .error_for_status()?;
+
+            missing.extend(response.json::<MissingBackupNodesResponse>().await?.missing);
+        }
+
+        Ok(missing)
+    }
+
+    /// Uploads serialized backup nodes, keyed by their hashes, in a single request.
+    pub async fn upload_backup_nodes(
+        &self,
+        nodes: Vec<(String, Vec<u8>)>,
+    ) -> IndexClientResult<()> {
+        let upload_url = self.base_url.join("backups/nodes")?;
+
+        let mut form = reqwest::multipart::Form::new();
+        for (hash, bytes) in nodes {
+            form = form.part(
+                hash.clone(),
+                reqwest::multipart::Part::bytes(bytes).file_name(hash),
+            );
+        }
+
+        reqwest::Client::new()
+            .post(upload_url)
+            .multipart(form)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    pub async fn create_workspace_backup(
+        &self,
+        request: &CreateWorkspaceBackupRequest,
+    ) -> IndexClientResult<WorkspaceBackupResponse> {
+        let create_url = self.base_url.join("backups")?;
+        let response = reqwest::Client::new()
+            .post(create_url)
+            .json(request)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(response.json::<WorkspaceBackupResponse>().await?)
+    }
+
+    /// Uploads a workspace backup incrementally: only the nodes which the module index does not
+    /// already hold are uploaded, after which a manifest for the backup is recorded.
+    ///
+    /// The nodes must be ordered with children before parents, as returned by
+    /// `SiPkg::serialized_nodes`.
+    pub async fn upload_workspace_backup(
+        &self,
+        workspace_name: &str,
+        created_by_display_name: Option<String>,
+        root_hash: &str,
+        nodes: Vec<(String, Vec<u8>)>,
+    ) -> IndexClientResult<WorkspaceBackupResponse> {
+        let node_hashes: Vec<String> = nodes.iter().map(|(hash, _)| hash.to_owned()).collect();
+        let missing: HashSet<String> = self
+            .missing_backup_nodes(&node_hashes)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut batch = Vec::new();
+        let mut batch_bytes = 0;
+        for (hash, bytes) in nodes {
+            if !missing.contains(&hash) {
+                continue;
+            }
+
+            if !batch.is_empty() && batch_bytes + bytes.len() > MAX_BACKUP_NODE_UPLOAD_BYTES {
+                self.upload_backup_nodes(std::mem::take(&mut batch)).await?;
+                batch_bytes = 0;
+            }
+            batch_bytes += bytes.len();
+            batch.push((hash, bytes));
+        }
+        if !batch.is_empty() {
+            self.upload_backup_nodes(batch).await?;
+        }
+
+        self.create_workspace_backup(&CreateWorkspaceBackupRequest {
+            workspace_name: workspace_name.to_owned(),
+            root_hash: root_hash.to_owned(),
+            node_hashes,
+            created_by_display_name,
+        })
+        .await
+    }
+
+    pub async fn list_workspace_backups(&self) -> IndexClientResult<WorkspaceBackupsResponse> {
+        let list_url = self.base_url.join("backups")?;
+        let response = reqwest::Client::new()
+            .get(list_url)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(response.json::<WorkspaceBackupsResponse>().await?)
+    }
+
+    /// Downloads a workspace backup, reassembled from its stored nodes as a module bundle.
+    pub async fn download_workspace_backup(&self, backup_id: Ulid) -> IndexClientResult<Vec<u8>> {
+        let download_url = self
+            .base_url
+            .join("backups/")?
+            .join(&format!("{}/", backup_id))?
+            .join("download")?;
+        let response = reqwest::Client::new()
+            .get(download_url)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+/// Confirms downloaded module contents match the checksum recorded at upload time. Modules
+/// uploaded before checksums were recorded have no checksum and are not verified.
+fn verify_checksum(bytes: &[u8], expected_checksum: Option<&str>) -> IndexClientResult<()> {
+    if let Some(expected_checksum) = expected_checksum {
+        let actual_checksum = content_checksum(bytes);
+        if actual_checksum != expected_checksum {
+            return Err(IndexClientError::IntegrityCheck(
+                expected_checksum.to_owned(),
+                actual_checksum,
+            ));
+        }
+    }
+    Ok(())
 }
@@ -2,6 +2,19 @@ pub mod client;
 pub mod types;
 
 pub use client::IndexClient;
-pub use types::{FuncMetadata, IndexClientError, IndexClientResult, ModuleDetailsResponse};
+pub use types::{
+    CreateWorkspaceBackupRequest, FuncMetadata, IndexClientError, IndexClientResult,
+    MissingBackupNodesRequest, MissingBackupNodesResponse, ModuleDetailsResponse,
+    ModuleVersionsResponse, WorkspaceBackupResponse, WorkspaceBackupsResponse,
+};
 
 pub const DEFAULT_URL: &str = "http://localhost:5157";
+
+/// The most node hashes which may be checked in a single request for missing backup nodes.
+pub const MAX_BACKUP_NODE_HASHES_PER_REQUEST: usize = 10_000;
+
+/// Computes the checksum recorded when a module bundle is uploaded and verified when it is
+/// downloaded.
+pub fn content_checksum(bytes: &[u8]) -> String {
+    blake3::hash(bytes).to_hex().to_string()
+}
@@ -1,12 +1,17 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use si_pkg::{LintIssue, LintReport};
 use thiserror::Error;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum IndexClientError {
+    #[error("module contents failed integrity check; expected={0}, actual={1}")]
+    IntegrityCheck(String, String),
     #[error("Request error: {0}")]
     InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
+    #[error("module failed lint: {0}")]
+    Lint(LintReport),
     #[error("Request error: {0}")]
     Request(#[from] reqwest::Error),
     #[error("Upload error: {0}")]
@@ -17,6 +22,19 @@ pub enum IndexClientError {
 
 pub type IndexClientResult<T> = Result<T, IndexClientError>;
 
+/// The error body the module index responds with when an uploaded module fails lint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LintErrorResponse {
+    pub error: LintErrorResponseBody,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LintErrorResponseBody {
+    pub lint_issues: Vec<LintIssue>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModuleRejectionResponse {}
@@ -33,6 +51,21 @@ pub struct ModuleDetailsResponse {
     pub latest_hash: String,
     pub latest_hash_created_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The checksum of the module bundle, see [`content_checksum`](crate::content_checksum).
+    /// Modules uploaded before checksums were recorded have none.
+    #[serde(default)]
+    pub content_checksum: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleVersionsResponse {
+    pub name: String,
+    pub versions: Vec<ModuleDetailsResponse>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,3 +75,45 @@ pub struct FuncMetadata {
     pub display_name: Option<String>,
     pub description: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingBackupNodesRequest {
+    pub hashes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingBackupNodesResponse {
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWorkspaceBackupRequest {
+    pub workspace_name: String,
+    pub root_hash: String,
+    /// The hashes of every node in the backup, ordered with children before parents.
+    pub node_hashes: Vec<String>,
+    pub created_by_display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceBackupResponse {
+    pub id: String,
+    pub workspace_pk: String,
+    pub workspace_name: String,
+    pub root_hash: String,
+    pub parent_id: Option<String>,
+    pub node_count: i32,
+    pub new_node_count: i32,
+    pub created_by_display_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceBackupsResponse {
+    pub backups: Vec<WorkspaceBackupResponse>,
+}
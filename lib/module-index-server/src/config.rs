@@ -12,7 +12,10 @@ use thiserror::Error;
 pub use si_settings::{StandardConfig, StandardConfigFile};
 use ulid::Ulid;
 
-use crate::s3::S3Config;
+use crate::{
+    s3::S3Config,
+    storage::{LocalStorageConfig, StorageBackend},
+};
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -55,7 +58,13 @@ pub struct Config {
     #[builder(default = "PosthogConfig::default()")]
     posthog: PosthogConfig,
 
+    #[builder(default)]
+    storage_backend: StorageBackend,
+
     s3: S3Config,
+
+    #[builder(default)]
+    local_storage: LocalStorageConfig,
 }
 
 impl StandardConfig for Config {
@@ -91,11 +100,23 @@ impl Config {
         &self.posthog
     }
 
+    /// Gets the storage backend that module bundles are kept in
+    #[must_use]
+    pub fn storage_backend(&self) -> StorageBackend {
+        self.storage_backend
+    }
+
     /// Gets a config's s3 details
     #[must_use]
     pub fn s3(&self) -> &S3Config {
         &self.s3
     }
+
+    /// Gets a config's local filesystem storage details
+    #[must_use]
+    pub fn local_storage(&self) -> &LocalStorageConfig {
+        &self.local_storage
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -111,7 +132,11 @@ pub struct ConfigFile {
     #[serde(default)]
     pub posthog: PosthogConfig,
     #[serde(default)]
+    pub storage_backend: StorageBackend,
+    #[serde(default)]
     pub s3: S3Config,
+    #[serde(default)]
+    pub local_storage: LocalStorageConfig,
 }
 
 impl Default for ConfigFile {
@@ -128,7 +153,9 @@ impl Default for ConfigFile {
             instance_id: random_instance_id(),
             jwt_signing_public_key_path: default_jwt_signing_public_key_path(),
             posthog: Default::default(),
+            storage_backend: Default::default(),
             s3: Default::default(),
+            local_storage: Default::default(),
         }
     }
 }
@@ -149,7 +176,9 @@ impl TryFrom<ConfigFile> for Config {
         config.instance_id(value.instance_id);
         config.jwt_signing_public_key_path(value.jwt_signing_public_key_path.try_into()?);
         config.posthog(value.posthog);
+        config.storage_backend(value.storage_backend);
         config.s3(value.s3);
+        config.local_storage(value.local_storage);
         config.build().map_err(Into::into)
     }
 }
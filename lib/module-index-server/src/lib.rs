@@ -6,6 +6,8 @@ mod models;
 mod routes;
 mod s3;
 pub mod server;
+mod storage;
+mod version;
 mod whoami;
 
 pub use crate::{
@@ -14,4 +16,5 @@ pub use crate::{
         StandardConfig, StandardConfigFile,
     },
     server::{Server, ServerError},
+    storage::{LocalStorageConfig, StorageBackend},
 };
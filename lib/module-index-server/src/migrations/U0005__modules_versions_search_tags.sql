@@ -0,0 +1,19 @@
+ALTER TABLE modules
+    ADD version text NOT NULL DEFAULT '',
+    ADD tags jsonb NOT NULL DEFAULT '[]'::jsonb;
+
+UPDATE modules
+SET version = COALESCE(metadata ->> 'version', '');
+
+ALTER TABLE modules
+    ADD search_vector tsvector GENERATED ALWAYS AS (
+        to_tsvector('simple',
+            name || ' ' ||
+            COALESCE(description, '') || ' ' ||
+            COALESCE((metadata -> 'schemas')::text, '')
+        )
+    ) STORED;
+
+CREATE INDEX modules_name_version_idx ON modules (name, version);
+CREATE INDEX modules_search_vector_idx ON modules USING GIN (search_vector);
+CREATE INDEX modules_tags_idx ON modules USING GIN (tags);
@@ -0,0 +1,2 @@
+ALTER TABLE modules
+    ADD content_checksum text;
@@ -0,0 +1,23 @@
+CREATE TABLE backup_nodes
+(
+    hash                        char(64) primary key,
+    size_bytes                  bigint                   NOT NULL,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE TABLE workspace_backups
+(
+    id                          ident primary key default ident_create_v1(),
+    workspace_pk                ident                    NOT NULL,
+    workspace_name              text                     NOT NULL,
+    root_hash                   char(64)                 NOT NULL,
+    parent_id                   ident REFERENCES workspace_backups (id),
+    node_hashes                 jsonb                    NOT NULL,
+    node_count                  integer                  NOT NULL,
+    new_node_count              integer                  NOT NULL,
+    created_by_user_id          ident                    NOT NULL,
+    created_by_display_name     text,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+CREATE INDEX workspace_backups_workspace_pk_created_at_idx ON workspace_backups (workspace_pk, created_at DESC);
@@ -0,0 +1,13 @@
+CREATE TABLE backup_node_workspaces
+(
+    hash                        char(64)                 NOT NULL REFERENCES backup_nodes (hash),
+    workspace_pk                ident                    NOT NULL,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    PRIMARY KEY (workspace_pk, hash)
+);
+
+-- Nodes already listed in a workspace's backups belong to that workspace
+INSERT INTO backup_node_workspaces (hash, workspace_pk)
+SELECT DISTINCT jsonb_array_elements_text(node_hashes), workspace_pk
+FROM workspace_backups
+ON CONFLICT DO NOTHING;
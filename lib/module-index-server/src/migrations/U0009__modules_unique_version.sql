@@ -0,0 +1,19 @@
+-- Versions uploaded again before uploads were checked against each other are rejected, so that
+-- only the first upload of a version stays installable.
+UPDATE modules
+SET rejected_at = CLOCK_TIMESTAMP()
+WHERE id IN (SELECT id
+             FROM (SELECT id,
+                          ROW_NUMBER() OVER (
+                              PARTITION BY name, kind, owner_user_id, version
+                              ORDER BY created_at
+                              ) AS position
+                   FROM modules
+                   WHERE rejected_at IS NULL
+                     AND kind = 'module') AS ranked
+             WHERE position > 1);
+
+-- Workspace backups are versioned by the time they are taken, so only modules are held to this.
+CREATE UNIQUE INDEX modules_name_kind_owner_version_idx
+    ON modules (name, kind, owner_user_id, version)
+    WHERE rejected_at IS NULL AND kind = 'module';
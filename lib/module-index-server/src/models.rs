@@ -1 +1,4 @@
+pub mod backup_node;
+pub mod backup_node_workspace;
 pub mod si_module;
+pub mod workspace_backup;
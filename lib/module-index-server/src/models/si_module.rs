@@ -95,6 +95,10 @@ pub struct Model {
     pub rejected_at: Option<DateTimeWithTimeZone>,
     pub rejected_by_display_name: Option<String>,
     pub kind: ModuleKind,
+    #[sea_orm(column_type = "Text")]
+    pub version: String,
+    pub tags: Json,
+    pub content_checksum: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
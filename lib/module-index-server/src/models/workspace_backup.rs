@@ -0,0 +1,57 @@
+use module_index_client::WorkspaceBackupResponse;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A manifest recording one backup of a workspace: the root hash of the backup's object tree and
+/// the hashes of every node in it. Each backup points at the previous backup of the same
+/// workspace, forming a chain from which any backup point can be restored.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "workspace_backups")]
+pub struct Model {
+    #[sea_orm(
+        primary_key,
+        auto_increment = false,
+        column_type = r##"custom("ident")"##
+    )]
+    pub id: String,
+    pub workspace_pk: String,
+    #[sea_orm(column_type = "Text")]
+    pub workspace_name: String,
+    pub root_hash: String,
+    pub parent_id: Option<String>,
+    pub node_hashes: Json,
+    pub node_count: i32,
+    pub new_node_count: i32,
+    pub created_by_user_id: String,
+    pub created_by_display_name: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// Returns the hashes of every node in the backup, children before parents.
+    pub fn node_hashes(&self) -> Result<Vec<String>, serde_json::Error> {
+        serde_json::from_value(self.node_hashes.clone())
+    }
+}
+
+impl From<Model> for WorkspaceBackupResponse {
+    fn from(value: Model) -> Self {
+        Self {
+            id: value.id,
+            workspace_pk: value.workspace_pk,
+            workspace_name: value.workspace_name,
+            root_hash: value.root_hash,
+            parent_id: value.parent_id,
+            node_count: value.node_count,
+            new_node_count: value.new_node_count,
+            created_by_display_name: value.created_by_display_name,
+            created_at: value.created_at.into(),
+        }
+    }
+}
@@ -11,10 +11,18 @@ use si_data_pg::PgError;
 use thiserror::Error;
 use tower_http::cors::CorsLayer;
 
+mod create_workspace_backup_route;
 mod download_module_route;
+mod download_module_version_route;
+mod download_workspace_backup_route;
 mod get_module_details_route;
+mod list_module_versions_route;
 mod list_modules_route;
+mod list_tags_route;
+mod list_workspace_backups_route;
+mod missing_backup_nodes_route;
 pub(crate) mod reject_module_route;
+mod upload_backup_nodes_route;
 pub(crate) mod upsert_module_route;
 
 use super::{app_state::AppState, server::ServerError};
@@ -37,10 +45,39 @@ pub fn routes(state: AppState) -> Router {
             "/modules/:module_id/download",
             get(download_module_route::download_module_route),
         )
+        .route(
+            "/modules/:module_id/versions",
+            get(list_module_versions_route::list_module_versions_route),
+        )
+        .route(
+            "/modules/:module_id/versions/:version/download",
+            get(download_module_version_route::download_module_version_route),
+        )
+        .route("/tags", get(list_tags_route::list_tags_route))
         .route(
             "/modules/:module_id/reject",
             post(reject_module_route::reject_module),
         )
+        .route(
+            "/backups",
+            get(list_workspace_backups_route::list_workspace_backups_route),
+        )
+        .route(
+            "/backups",
+            post(create_workspace_backup_route::create_workspace_backup_route),
+        )
+        .route(
+            "/backups/nodes",
+            post(upload_backup_nodes_route::upload_backup_nodes_route),
+        )
+        .route(
+            "/backups/nodes/missing",
+            post(missing_backup_nodes_route::missing_backup_nodes_route),
+        )
+        .route(
+            "/backups/:backup_id/download",
+            get(download_workspace_backup_route::download_workspace_backup_route),
+        )
         .layer(CorsLayer::permissive())
         .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES));
 
@@ -0,0 +1,149 @@
+use std::collections::HashSet;
+
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use hyper::StatusCode;
+use module_index_client::{
+    CreateWorkspaceBackupRequest, WorkspaceBackupResponse, MAX_BACKUP_NODE_HASHES_PER_REQUEST,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    extract::{Authorization, DbConnection},
+    models::{backup_node, backup_node_workspace, workspace_backup},
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum CreateWorkspaceBackupError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error("invalid backup node hash: {0}")]
+    InvalidHash(String),
+    #[error("{0} backup nodes have not been uploaded")]
+    MissingNodes(usize),
+    #[error("backup root node {0} is not one of the backup's nodes")]
+    RootNotInNodes(String),
+    #[error("JSON serialization/deserialization error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("backup node count is too large: {0}")]
+    TooManyNodes(usize),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for CreateWorkspaceBackupError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::InvalidHash(_) | Self::RootNotInNodes(_) | Self::TooManyNodes(_) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
+            Self::MissingNodes(_) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// Records a manifest for a backup of the caller's workspace whose nodes have already been
+/// uploaded by that workspace. The new backup is chained to the workspace's most recent backup.
+pub async fn create_workspace_backup_route(
+    Authorization { user_claim, .. }: Authorization,
+    DbConnection(txn): DbConnection,
+    Json(request): Json<CreateWorkspaceBackupRequest>,
+) -> Result<Json<WorkspaceBackupResponse>, CreateWorkspaceBackupError> {
+    // The same sub-tree can appear more than once in a tree, but only needs to be listed once.
+    // Keeping the first occurrence preserves the children-before-parents ordering.
+    let mut seen = HashSet::new();
+    let mut node_hashes = Vec::with_capacity(request.node_hashes.len());
+    for hash in request.node_hashes {
+        if !backup_node::is_valid_hash(&hash) {
+            return Err(CreateWorkspaceBackupError::InvalidHash(hash));
+        }
+        if seen.insert(hash.clone()) {
+            node_hashes.push(hash);
+        }
+    }
+
+    if !seen.contains(&request.root_hash) {
+        return Err(CreateWorkspaceBackupError::RootNotInNodes(
+            request.root_hash,
+        ));
+    }
+
+    let node_count = i32::try_from(node_hashes.len())
+        .map_err(|_| CreateWorkspaceBackupError::TooManyNodes(node_hashes.len()))?;
+
+    let workspace_pk = user_claim.workspace_pk.to_string();
+
+    // Only nodes this workspace uploaded count, so that a backup can't point at the nodes of
+    // another workspace's backups.
+    let mut stored_count = 0;
+    for chunk in node_hashes.chunks(MAX_BACKUP_NODE_HASHES_PER_REQUEST) {
+        stored_count += backup_node_workspace::Entity::find()
+            .filter(backup_node_workspace::Column::WorkspacePk.eq(&workspace_pk))
+            .filter(backup_node_workspace::Column::Hash.is_in(chunk.iter().cloned()))
+            .count(&txn)
+            .await?;
+    }
+    let missing_count = node_hashes
+        .len()
+        .saturating_sub(usize::try_from(stored_count).unwrap_or(usize::MAX));
+    if missing_count > 0 {
+        return Err(CreateWorkspaceBackupError::MissingNodes(missing_count));
+    }
+
+    let parent = workspace_backup::Entity::find()
+        .filter(workspace_backup::Column::WorkspacePk.eq(&workspace_pk))
+        .order_by_desc(workspace_backup::Column::CreatedAt)
+        .one(&txn)
+        .await?;
+
+    let new_node_count = match &parent {
+        Some(parent) => {
+            let parent_hashes: HashSet<String> = parent.node_hashes()?.into_iter().collect();
+            node_hashes
+                .iter()
+                .filter(|hash| !parent_hashes.contains(*hash))
+                .count()
+        }
+        None => node_hashes.len(),
+    };
+
+    let new_backup = workspace_backup::ActiveModel {
+        workspace_pk: Set(workspace_pk),
+        workspace_name: Set(request.workspace_name),
+        root_hash: Set(request.root_hash),
+        parent_id: Set(parent.map(|parent| parent.id)),
+        node_hashes: Set(serde_json::to_value(&node_hashes)?),
+        node_count: Set(node_count),
+        // Bounded by `node_count`, which fits
+        new_node_count: Set(i32::try_from(new_node_count).unwrap_or(node_count)),
+        created_by_user_id: Set(user_claim.user_pk.to_string()),
+        created_by_display_name: Set(request.created_by_display_name),
+        ..Default::default() // all other attributes are `NotSet`
+    };
+
+    let new_backup = new_backup.insert(&txn).await?;
+
+    txn.commit().await?;
+
+    info!(
+        backup_id = %new_backup.id,
+        node_count = new_backup.node_count,
+        new_node_count = new_backup.new_node_count,
+        "recorded workspace backup"
+    );
+
+    Ok(Json(new_backup.into()))
+}
@@ -3,14 +3,14 @@ use axum::{
     response::{IntoResponse, Redirect, Response},
     Json,
 };
-use hyper::StatusCode;
-use s3::error::S3Error;
+use hyper::{header, StatusCode};
 use sea_orm::{DbErr, EntityTrait};
 use thiserror::Error;
 
 use crate::{
-    extract::{Authorization, DbConnection, ExtractedS3Bucket},
+    extract::{Authorization, DbConnection, ExtractedStorage},
     models::si_module::{self, ModuleId},
+    storage::{get_verified, ObjectKind, StorageError},
 };
 
 #[remain::sorted]
@@ -20,8 +20,8 @@ pub enum DownloadModuleError {
     DbErr(#[from] DbErr),
     #[error(r#"Module "{0}" not found"#)]
     NotFound(ModuleId),
-    #[error("s3 error: {0}")]
-    S3Error(#[from] S3Error),
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
 }
 
 // TODO: figure out how to not keep this serialization logic here
@@ -43,16 +43,28 @@ impl IntoResponse for DownloadModuleError {
 pub async fn download_module_route(
     Path(module_id): Path<ModuleId>,
     Authorization { .. }: Authorization,
-    ExtractedS3Bucket(s3_bucket): ExtractedS3Bucket,
+    ExtractedStorage(storage): ExtractedStorage,
     DbConnection(txn): DbConnection,
-) -> Result<Redirect, DownloadModuleError> {
+) -> Result<Response, DownloadModuleError> {
     let module = match si_module::Entity::find_by_id(module_id).one(&txn).await? {
         Some(module) => module,
         _ => return Err(DownloadModuleError::NotFound(module_id)),
     };
 
-    let download_url =
-        s3_bucket.presign_get(format!("{}.sipkg", module.latest_hash), 60 * 5, None)?;
+    // Backends that can hand out a download url are downloaded from directly
+    if let Some(download_url) = storage
+        .presigned_url(ObjectKind::Module, &module.latest_hash)
+        .await?
+    {
+        return Ok(Redirect::temporary(&download_url).into_response());
+    }
+
+    let bytes = get_verified(
+        storage.as_ref(),
+        &module.latest_hash,
+        module.content_checksum.as_deref(),
+    )
+    .await?;
 
-    Ok(Redirect::temporary(&download_url))
+    Ok(([(header::CONTENT_TYPE, "application/octet-stream")], bytes).into_response())
 }
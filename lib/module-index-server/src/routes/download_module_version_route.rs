@@ -0,0 +1,88 @@
+use axum::{
+    extract::Path,
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use hyper::{header, StatusCode};
+use sea_orm::{DbErr, EntityTrait};
+use thiserror::Error;
+
+use crate::{
+    extract::{Authorization, DbConnection, ExtractedStorage},
+    models::si_module::{self, ModuleId},
+    routes::list_module_versions_route::module_versions,
+    storage::{get_verified, ObjectKind, StorageError},
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum DownloadModuleVersionError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error(r#"Module "{0}" not found"#)]
+    NotFound(ModuleId),
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error(r#"Module "{0}" has no version "{1}""#)]
+    VersionNotFound(ModuleId, String),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for DownloadModuleVersionError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::NotFound(_) | Self::VersionNotFound(_, _) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+pub async fn download_module_version_route(
+    Path((module_id, version)): Path<(ModuleId, String)>,
+    Authorization { .. }: Authorization,
+    ExtractedStorage(storage): ExtractedStorage,
+    DbConnection(txn): DbConnection,
+) -> Result<Response, DownloadModuleVersionError> {
+    let module = match si_module::Entity::find_by_id(module_id).one(&txn).await? {
+        Some(module) => module,
+        _ => return Err(DownloadModuleVersionError::NotFound(module_id)),
+    };
+
+    let module_version = match module_versions(&txn, &module)
+        .await?
+        .into_iter()
+        .find(|candidate| candidate.version == version)
+    {
+        Some(module_version) => module_version,
+        None => {
+            return Err(DownloadModuleVersionError::VersionNotFound(
+                module_id, version,
+            ))
+        }
+    };
+
+    // Backends that can hand out a download url are downloaded from directly
+    if let Some(download_url) = storage
+        .presigned_url(ObjectKind::Module, &module_version.latest_hash)
+        .await?
+    {
+        return Ok(Redirect::temporary(&download_url).into_response());
+    }
+
+    let bytes = get_verified(
+        storage.as_ref(),
+        &module_version.latest_hash,
+        module_version.content_checksum.as_deref(),
+    )
+    .await?;
+
+    Ok(([(header::CONTENT_TYPE, "application/octet-stream")], bytes).into_response())
+}
@@ -0,0 +1,93 @@
+use std::str::FromStr;
+
+use axum::{
+    extract::Path,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures::{StreamExt, TryStreamExt};
+use hyper::{header, StatusCode};
+use object_tree::{Hash, HashParseError, SerializedNode, TarWriter, TarWriterError};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+use thiserror::Error;
+use ulid::Ulid;
+
+use crate::{
+    extract::{Authorization, DbConnection, ExtractedStorage},
+    models::workspace_backup,
+    storage::{ObjectKind, StorageError},
+};
+
+// The number of backup nodes fetched from storage concurrently
+const NODE_FETCH_CONCURRENCY: usize = 32;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum DownloadWorkspaceBackupError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error("invalid backup node hash: {0}")]
+    Hash(#[from] HashParseError),
+    #[error(r#"Workspace backup "{0}" not found"#)]
+    NotFound(Ulid),
+    #[error("JSON serialization/deserialization error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("failed to assemble backup: {0}")]
+    TarWriter(#[from] TarWriterError),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for DownloadWorkspaceBackupError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::NotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// Reassembles a backup of the caller's workspace from its stored nodes, returning it as a
+/// module bundle. Every node is verified against its hash as it is read back from storage.
+pub async fn download_workspace_backup_route(
+    Path(backup_id): Path<Ulid>,
+    Authorization { user_claim, .. }: Authorization,
+    ExtractedStorage(storage): ExtractedStorage,
+    DbConnection(txn): DbConnection,
+) -> Result<Response, DownloadWorkspaceBackupError> {
+    let backup = match workspace_backup::Entity::find_by_id(backup_id.to_string())
+        .filter(workspace_backup::Column::WorkspacePk.eq(user_claim.workspace_pk.to_string()))
+        .one(&txn)
+        .await?
+    {
+        Some(backup) => backup,
+        None => return Err(DownloadWorkspaceBackupError::NotFound(backup_id)),
+    };
+
+    let nodes: Vec<SerializedNode> = futures::stream::iter(backup.node_hashes()?)
+        .map(|hash| {
+            let storage = storage.clone();
+            async move {
+                let bytes = storage.get_object(ObjectKind::BackupNode, &hash).await?;
+                Ok::<_, DownloadWorkspaceBackupError>(SerializedNode::new(
+                    Hash::from_str(&hash)?,
+                    bytes,
+                )?)
+            }
+        })
+        .buffered(NODE_FETCH_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    let bytes =
+        TarWriter::from_serialized_nodes(Hash::from_str(&backup.root_hash)?, nodes)?.bytes();
+
+    Ok(([(header::CONTENT_TYPE, "application/octet-stream")], bytes).into_response())
+}
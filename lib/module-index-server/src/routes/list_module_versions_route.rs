@@ -0,0 +1,83 @@
+use axum::{
+    extract::Path,
+    response::{IntoResponse, Response},
+    Json,
+};
+use hyper::StatusCode;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    extract::{Authorization, DbConnection},
+    models::si_module::{self, ModuleId},
+    version::newest_first,
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ListModuleVersionsError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error(r#"Module "{0}" not found"#)]
+    NotFound(ModuleId),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for ListModuleVersionsError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::NotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListModuleVersionsResponse {
+    name: String,
+    versions: Vec<si_module::Model>,
+}
+
+/// Finds every non-rejected version of the module with the given id, that is every module of
+/// the same kind with the same name and owner, ordered from newest to oldest version.
+pub(crate) async fn module_versions(
+    txn: &impl sea_orm::ConnectionTrait,
+    module: &si_module::Model,
+) -> Result<Vec<si_module::Model>, DbErr> {
+    let mut versions = si_module::Entity::find()
+        .filter(si_module::Column::Name.eq(module.name.as_str()))
+        .filter(si_module::Column::Kind.eq(module.kind.to_db_kind()))
+        .filter(si_module::Column::OwnerUserId.eq(module.owner_user_id.as_str()))
+        .filter(si_module::Column::RejectedAt.is_null())
+        .all(txn)
+        .await?;
+    versions.sort_by(newest_first);
+
+    Ok(versions)
+}
+
+pub async fn list_module_versions_route(
+    Path(module_id): Path<ModuleId>,
+    Authorization { .. }: Authorization,
+    DbConnection(txn): DbConnection,
+) -> Result<Json<ListModuleVersionsResponse>, ListModuleVersionsError> {
+    let module = match si_module::Entity::find_by_id(module_id).one(&txn).await? {
+        Some(module) => module,
+        _ => return Err(ListModuleVersionsError::NotFound(module_id)),
+    };
+
+    let versions = module_versions(&txn, &module).await?;
+
+    Ok(Json(ListModuleVersionsResponse {
+        name: module.name,
+        versions,
+    }))
+}
@@ -4,7 +4,9 @@ use axum::{
     Json,
 };
 use hyper::StatusCode;
-use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -37,18 +39,32 @@ impl IntoResponse for ListModulesError {
     }
 }
 
+const DEFAULT_PAGE_SIZE: u64 = 50;
+const MAX_PAGE_SIZE: u64 = 500;
+
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ListModulesRequest {
     pub name: Option<String>,
     pub kind: Option<si_module::ModuleKind>,
     pub su: Option<bool>,
+    /// Full-text search over module names, descriptions and schema names
+    pub q: Option<String>,
+    /// Only return modules tagged with this category
+    pub tag: Option<String>,
+    /// Zero-based page number
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ListModulesResponse {
     modules: Vec<si_module::Model>,
+    page: u64,
+    page_size: u64,
+    total_items: u64,
+    total_pages: u64,
 }
 
 pub async fn list_module_route(
@@ -83,13 +99,59 @@ pub async fn list_module_route(
     } else {
         query
     };
+    let query = match request.q.as_deref().map(str::trim) {
+        Some(search) if !search.is_empty() => query.filter(Expr::cust_with_values(
+            "search_vector @@ plainto_tsquery('simple', $1)",
+            [search.to_owned()],
+        )),
+        _ => query,
+    };
+    let query = if let Some(tag) = request.tag {
+        query.filter(Expr::cust_with_values(
+            "tags @> $1::jsonb",
+            [serde_json::json!([tag]).to_string()],
+        ))
+    } else {
+        query
+    };
 
     // ordering
     let query = query
         .order_by_desc(si_module::Column::OwnerUserId)
         .order_by_desc(si_module::Column::CreatedAt);
 
-    let modules: Vec<si_module::Model> = query.all(&txn).await?;
+    // pagination is opt-in so that existing callers continue to receive every module
+    let (modules, page, page_size, total_items, total_pages) =
+        if request.page.is_some() || request.page_size.is_some() {
+            let page = request.page.unwrap_or(0);
+            let page_size = request
+                .page_size
+                .unwrap_or(DEFAULT_PAGE_SIZE)
+                .clamp(1, MAX_PAGE_SIZE);
+
+            let paginator = query.paginate(&txn, page_size);
+            let totals = paginator.num_items_and_pages().await?;
+            let modules: Vec<si_module::Model> = paginator.fetch_page(page).await?;
+
+            (
+                modules,
+                page,
+                page_size,
+                totals.number_of_items,
+                totals.number_of_pages,
+            )
+        } else {
+            let modules: Vec<si_module::Model> = query.all(&txn).await?;
+            let total_items = modules.len() as u64;
+
+            (modules, 0, total_items, total_items, 1)
+        };
 
-    Ok(Json(ListModulesResponse { modules }))
+    Ok(Json(ListModulesResponse {
+        modules,
+        page,
+        page_size,
+        total_items,
+        total_pages,
+    }))
 }
@@ -0,0 +1,62 @@
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use hyper::StatusCode;
+use sea_orm::{DbBackend, DbErr, FromQueryResult, Statement};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::extract::{Authorization, DbConnection};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ListTagsError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for ListTagsError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, FromQueryResult)]
+#[serde(rename_all = "camelCase")]
+pub struct TagView {
+    tag: String,
+    module_count: i64,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTagsResponse {
+    tags: Vec<TagView>,
+}
+
+pub async fn list_tags_route(
+    Authorization { .. }: Authorization,
+    DbConnection(txn): DbConnection,
+) -> Result<Json<ListTagsResponse>, ListTagsError> {
+    let tags = TagView::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        r#"SELECT tag, COUNT(*) AS module_count
+           FROM modules, jsonb_array_elements_text(modules.tags) AS tag
+           WHERE modules.rejected_at IS NULL AND modules.kind = 'module'
+           GROUP BY tag
+           ORDER BY tag"#
+            .to_owned(),
+    ))
+    .all(&txn)
+    .await?;
+
+    Ok(Json(ListTagsResponse { tags }))
+}
@@ -0,0 +1,50 @@
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use hyper::StatusCode;
+use module_index_client::WorkspaceBackupsResponse;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use thiserror::Error;
+
+use crate::{
+    extract::{Authorization, DbConnection},
+    models::workspace_backup,
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ListWorkspaceBackupsError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for ListWorkspaceBackupsError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// Lists the backup chain of the caller's workspace, newest backup first.
+pub async fn list_workspace_backups_route(
+    Authorization { user_claim, .. }: Authorization,
+    DbConnection(txn): DbConnection,
+) -> Result<Json<WorkspaceBackupsResponse>, ListWorkspaceBackupsError> {
+    let backups = workspace_backup::Entity::find()
+        .filter(workspace_backup::Column::WorkspacePk.eq(user_claim.workspace_pk.to_string()))
+        .order_by_desc(workspace_backup::Column::CreatedAt)
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(Json(WorkspaceBackupsResponse { backups }))
+}
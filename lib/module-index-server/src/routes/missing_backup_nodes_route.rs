@@ -0,0 +1,82 @@
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use hyper::StatusCode;
+use module_index_client::{
+    MissingBackupNodesRequest, MissingBackupNodesResponse, MAX_BACKUP_NODE_HASHES_PER_REQUEST,
+};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+use std::collections::HashSet;
+use thiserror::Error;
+
+use crate::{
+    extract::{Authorization, DbConnection},
+    models::{backup_node, backup_node_workspace},
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum MissingBackupNodesError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error("invalid backup node hash: {0}")]
+    InvalidHash(String),
+    #[error("too many hashes in one request: {0} (max {MAX_BACKUP_NODE_HASHES_PER_REQUEST})")]
+    TooManyHashes(usize),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for MissingBackupNodesError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::InvalidHash(_) | Self::TooManyHashes(_) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// Lists which of the given nodes the caller's workspace has not uploaded. Nodes uploaded only by
+/// other workspaces are reported as missing too, so that nothing is revealed about them.
+pub async fn missing_backup_nodes_route(
+    Authorization { user_claim, .. }: Authorization,
+    DbConnection(txn): DbConnection,
+    Json(request): Json<MissingBackupNodesRequest>,
+) -> Result<Json<MissingBackupNodesResponse>, MissingBackupNodesError> {
+    if request.hashes.len() > MAX_BACKUP_NODE_HASHES_PER_REQUEST {
+        return Err(MissingBackupNodesError::TooManyHashes(request.hashes.len()));
+    }
+    if let Some(invalid) = request
+        .hashes
+        .iter()
+        .find(|hash| !backup_node::is_valid_hash(hash))
+    {
+        return Err(MissingBackupNodesError::InvalidHash(invalid.to_owned()));
+    }
+
+    let stored: HashSet<String> = backup_node_workspace::Entity::find()
+        .filter(backup_node_workspace::Column::WorkspacePk.eq(user_claim.workspace_pk.to_string()))
+        .filter(backup_node_workspace::Column::Hash.is_in(request.hashes.iter().cloned()))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|node| node.hash)
+        .collect();
+
+    let mut seen = HashSet::new();
+    let missing = request
+        .hashes
+        .into_iter()
+        .filter(|hash| !stored.contains(hash) && seen.insert(hash.to_owned()))
+        .collect();
+
+    Ok(Json(MissingBackupNodesResponse { missing }))
+}
@@ -13,7 +13,7 @@ use crate::app_state::AppState;
 use crate::routes::upsert_module_route::UpsertModuleError;
 use crate::whoami::{is_systeminit_auth_token, WhoamiError};
 use crate::{
-    extract::{Authorization, DbConnection, ExtractedS3Bucket},
+    extract::{Authorization, DbConnection},
     models::si_module::{self, ModuleId},
 };
 
@@ -50,7 +50,6 @@ pub async fn reject_module(
         user_claim: _user_claim,
         auth_token,
     }: Authorization,
-    ExtractedS3Bucket(_s3_bucket): ExtractedS3Bucket,
     DbConnection(txn): DbConnection,
     State(state): State<AppState>,
     mut multipart: Multipart,
@@ -89,6 +88,9 @@ pub async fn reject_module(
         ))),
         rejected_by_display_name: Set(Some(data)),
         kind: Set(module.kind),
+        version: Set(module.version),
+        tags: Set(module.tags),
+        content_checksum: Set(module.content_checksum),
     };
 
     let updated_module: si_module::Model = dbg!(active_module.update(&txn).await)?;
@@ -0,0 +1,107 @@
+use axum::{
+    extract::{multipart::MultipartError, Multipart},
+    response::{IntoResponse, Response},
+    Json,
+};
+use hyper::StatusCode;
+use sea_orm::{ConnectionTrait, DbBackend, DbErr, Statement};
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    extract::{Authorization, DbConnection, ExtractedStorage},
+    models::backup_node,
+    storage::{content_checksum, ObjectKind, StorageError},
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum UploadBackupNodesError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error("backup node failed integrity check; expected={0}, actual={1}")]
+    IntegrityCheck(String, String),
+    #[error("invalid backup node hash: {0}")]
+    InvalidHash(String),
+    #[error("multipart decode error: {0}")]
+    Multipart(#[from] MultipartError),
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for UploadBackupNodesError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::IntegrityCheck(_, _) | Self::InvalidHash(_) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadBackupNodesResponse {
+    stored: usize,
+}
+
+/// Stores backup nodes uploaded as multipart fields, each named by the hash of its contents, and
+/// records that the caller's workspace uploaded them.
+pub async fn upload_backup_nodes_route(
+    Authorization { user_claim, .. }: Authorization,
+    ExtractedStorage(storage): ExtractedStorage,
+    DbConnection(txn): DbConnection,
+    mut multipart: Multipart,
+) -> Result<Json<UploadBackupNodesResponse>, UploadBackupNodesError> {
+    let workspace_pk = user_claim.workspace_pk.to_string();
+    let mut stored = 0;
+
+    while let Some(field) = multipart.next_field().await? {
+        let hash = field.name().unwrap_or_default().to_owned();
+        if !backup_node::is_valid_hash(&hash) {
+            return Err(UploadBackupNodesError::InvalidHash(hash));
+        }
+
+        let data = field.bytes().await?;
+
+        // Nodes are content-addressed, so the hash of a node's bytes must be its key
+        let checksum = content_checksum(&data);
+        if checksum != hash {
+            return Err(UploadBackupNodesError::IntegrityCheck(hash, checksum));
+        }
+
+        storage
+            .put_object(ObjectKind::BackupNode, &hash, &data)
+            .await?;
+
+        txn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO backup_nodes (hash, size_bytes) VALUES ($1, $2) ON CONFLICT (hash) DO NOTHING",
+            [hash.clone().into(), (data.len() as i64).into()],
+        ))
+        .await?;
+        txn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO backup_node_workspaces (hash, workspace_pk) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+            [hash.into(), workspace_pk.clone().into()],
+        ))
+        .await?;
+
+        stored += 1;
+    }
+
+    txn.commit().await?;
+
+    debug!(stored, "stored backup nodes");
+
+    Ok(Json(UploadBackupNodesResponse { stored }))
+}
@@ -6,16 +6,18 @@ use axum::{
 use chrono::{DateTime, FixedOffset, Offset, Utc};
 use hyper::StatusCode;
 use module_index_client::{FuncMetadata, ModuleDetailsResponse};
-use s3::error::S3Error;
-use sea_orm::{ActiveModelTrait, DbErr, Set};
+use sea_orm::{
+    sqlx, ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, RuntimeErr, Set,
+};
 use serde::{Deserialize, Serialize};
-use si_pkg::{SiPkg, SiPkgError, SiPkgKind};
+use si_pkg::{LintReport, SiPkg, SiPkgError, SiPkgKind};
 use telemetry::prelude::*;
 use thiserror::Error;
 
 use crate::{
-    extract::{Authorization, DbConnection, ExtractedS3Bucket},
+    extract::{Authorization, DbConnection, ExtractedStorage},
     models::si_module,
+    storage::{put_with_checksum, StorageError},
 };
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -31,26 +33,41 @@ pub enum UpsertModuleError {
     DbErr(#[from] DbErr),
     #[error("file upload error: {0}")]
     IoError(#[from] std::io::Error),
+    #[error("module failed lint: {0}")]
+    Lint(LintReport),
     #[error("multipart decode error: {0}")]
     Multipart(#[from] MultipartError),
-    #[error("s3 error: {0}")]
-    S3Error(#[from] S3Error),
     #[error("JSON serialization/deserialization error: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("module parsing error: {0}")]
     SiPkgError(#[from] SiPkgError),
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
     #[error("upload is required")]
     UploadRequiredError,
+    #[error(r#"Module "{0}" already has a version "{1}""#)]
+    VersionAlreadyExists(String, String),
 }
 
 // TODO: figure out how to not keep this serialization logic here
 impl IntoResponse for UpsertModuleError {
     fn into_response(self) -> Response {
-        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
-
-        let body = Json(
-            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
-        );
+        let (status, error_message) = match self {
+            Self::Lint(_) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
+            Self::VersionAlreadyExists(_, _) => (StatusCode::CONFLICT, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+        let lint_issues = match self {
+            Self::Lint(lint_report) => Some(lint_report.issues),
+            _ => None,
+        };
+
+        let mut error = serde_json::json!({ "message": error_message, "code": 42, "statusCode": status.as_u16() });
+        // Lint issues are returned as they are, so that clients can point at each of them.
+        if let Some(lint_issues) = lint_issues {
+            error["lintIssues"] = serde_json::json!(lint_issues);
+        }
+        let body = Json(serde_json::json!({ "error": error }));
 
         (status, body).into_response()
     }
@@ -59,7 +76,7 @@ impl IntoResponse for UpsertModuleError {
 // #[debug_handler]
 pub async fn upsert_module_route(
     Authorization { user_claim, .. }: Authorization,
-    ExtractedS3Bucket(s3_bucket): ExtractedS3Bucket,
+    ExtractedStorage(storage): ExtractedStorage,
     DbConnection(txn): DbConnection,
     mut multipart: Multipart,
 ) -> Result<Json<ModuleDetailsResponse>, UpsertModuleError> {
@@ -82,11 +99,44 @@ pub async fn upsert_module_route(
         SiPkgKind::Module => si_module::ModuleKind::Module,
     };
 
-    let schemas: Vec<String> = loaded_module
-        .schemas()?
+    // Versions belong to the module of the same name and owner, see `module_versions`. The
+    // unique index on them still refuses an upload which races past this check.
+    if let si_module::ModuleKind::Module = module_kind {
+        let existing_version = si_module::Entity::find()
+            .filter(si_module::Column::Name.eq(module_metadata.name()))
+            .filter(si_module::Column::Version.eq(&version))
+            .filter(si_module::Column::Kind.eq(module_kind.to_db_kind()))
+            .filter(si_module::Column::OwnerUserId.eq(user_claim.user_pk.to_string()))
+            .filter(si_module::Column::RejectedAt.is_null())
+            .one(&txn)
+            .await?;
+        if existing_version.is_some() {
+            return Err(UpsertModuleError::VersionAlreadyExists(
+                module_metadata.name().to_owned(),
+                version,
+            ));
+        }
+    }
+
+    // Modules are installed by other workspaces, so refuse any which could not install cleanly
+    if let si_module::ModuleKind::Module = module_kind {
+        let lint_report = loaded_module.to_spec().await?.lint();
+        if lint_report.has_errors() {
+            return Err(UpsertModuleError::Lint(lint_report));
+        }
+    }
+
+    let pkg_schemas = loaded_module.schemas()?;
+    let schemas: Vec<String> = pkg_schemas.iter().map(|s| s.name().to_owned()).collect();
+
+    // Tags are the distinct categories of the module's schemas, e.g. "AWS EC2"
+    let mut tags: Vec<String> = pkg_schemas
         .iter()
-        .map(|s| s.name().to_owned())
+        .filter_map(|s| s.data().map(|data| data.category().to_owned()))
+        .filter(|category| !category.is_empty())
         .collect();
+    tags.sort();
+    tags.dedup();
     let funcs: Vec<FuncMetadata> = loaded_module
         .funcs()?
         .iter()
@@ -97,42 +147,101 @@ pub async fn upsert_module_route(
         })
         .collect();
 
+    // The checksum is of the bundle that ends up stored, which is not the one uploaded if a
+    // bundle with the same package hash was uploaded before
+    let hash = module_metadata.hash().to_string();
+    let recorded_checksum = si_module::Entity::find()
+        .filter(si_module::Column::LatestHash.eq(&hash))
+        .filter(si_module::Column::ContentChecksum.is_not_null())
+        .one(&txn)
+        .await?
+        .and_then(|module| module.content_checksum);
+    let content_checksum =
+        put_with_checksum(storage.as_ref(), &hash, &data, recorded_checksum).await?;
+
     let new_module = si_module::ActiveModel {
         name: Set(module_metadata.name().to_owned()),
         description: Set(Some(module_metadata.description().to_owned())),
         owner_user_id: Set(user_claim.user_pk.to_string()),
         owner_display_name: Set(Some(module_metadata.created_by().to_owned())),
-        latest_hash: Set(module_metadata.hash().to_string()),
+        latest_hash: Set(hash),
         // maybe use db's `CLOCK_TIMESTAMP()`?
         latest_hash_created_at: Set(DateTime::<FixedOffset>::from_utc(
             Utc::now().naive_utc(),
             Utc.fix(),
         )),
         metadata: Set(serde_json::to_value(ExtraMetadata {
-            version,
+            version: version.clone(),
             schemas,
             funcs,
         })?),
         kind: Set(module_kind),
+        version: Set(version.clone()),
+        tags: Set(serde_json::to_value(tags)?),
+        content_checksum: Set(Some(content_checksum)),
         ..Default::default() // all other attributes are `NotSet`
     };
 
-    // TODO: put below
-    // upload to s3
-    s3_bucket
-        .put_object(format!("{}.sipkg", module_metadata.hash()), &data)
-        .await?;
-
-    let new_module: si_module::Model = dbg!(new_module.insert(&txn).await)?;
+    let new_module: si_module::Model = match dbg!(new_module.insert(&txn).await) {
+        Ok(new_module) => new_module,
+        Err(err) if is_unique_violation(&err) => {
+            return Err(UpsertModuleError::VersionAlreadyExists(
+                module_metadata.name().to_owned(),
+                version,
+            ))
+        }
+        Err(err) => return Err(err.into()),
+    };
 
     txn.commit().await?;
 
     Ok(dbg!(Json(new_module.try_into()?)))
 }
 
+/// Whether postgres refused the statement because it would break a unique index.
+fn is_unique_violation(err: &DbErr) -> bool {
+    match err {
+        DbErr::Exec(RuntimeErr::SqlxError(sqlx::Error::Database(err)))
+        | DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(err))) => {
+            err.code().as_deref() == Some("23505")
+        }
+        _ => false,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtraMetadata {
     pub version: String,
     pub schemas: Vec<String>,
     pub funcs: Vec<FuncMetadata>,
 }
+
+#[cfg(test)]
+mod tests {
+    use si_pkg::{LintIssue, LintKind, LintSeverity};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn lint_errors_list_their_issues() {
+        let issue = LintIssue {
+            severity: LintSeverity::Error,
+            kind: LintKind::DanglingFuncReference,
+            location: "schemas[starfield]/variants[v0]".to_owned(),
+            message: "func missing is not in the package".to_owned(),
+        };
+        let response = UpsertModuleError::Lint(LintReport {
+            issues: vec![issue.clone()],
+        })
+        .into_response();
+        assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .expect("failed to read body");
+        let body: serde_json::Value = serde_json::from_slice(&body).expect("body is not json");
+        let issues: Vec<LintIssue> = serde_json::from_value(body["error"]["lintIssues"].clone())
+            .expect("lint issues are not listed");
+        assert_eq!(vec![issue], issues);
+    }
+}
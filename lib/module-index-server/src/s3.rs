@@ -1,4 +1,12 @@
+use async_trait::async_trait;
+use s3::{creds::Credentials as AwsCredentials, Bucket as S3Bucket, Region as AwsRegion};
 use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+use crate::storage::{object_key, ModuleStorage, ObjectKind, StorageError, StorageResult};
+
+/// How long a presigned download url stays valid for, in seconds.
+const PRESIGNED_URL_EXPIRY_SECONDS: u32 = 60 * 5;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(default)]
@@ -22,3 +30,69 @@ impl Default for S3Config {
         }
     }
 }
+
+/// Stores module bundles in an S3 bucket.
+#[derive(Clone, Debug)]
+pub struct S3Storage {
+    bucket: S3Bucket,
+}
+
+impl S3Storage {
+    pub fn new(config: &S3Config, creds: AwsCredentials) -> StorageResult<Self> {
+        let region = config
+            .region
+            .parse::<AwsRegion>()
+            .map_err(|err| StorageError::S3Config(err.to_string()))?;
+        let bucket = S3Bucket::new(&config.bucket, region, creds)?;
+
+        Ok(Self { bucket })
+    }
+}
+
+#[async_trait]
+impl ModuleStorage for S3Storage {
+    async fn put_object(&self, kind: ObjectKind, hash: &str, bytes: &[u8]) -> StorageResult<()> {
+        if self.object_exists(kind, hash).await? {
+            debug!(hash, ?kind, "object already present in s3, skipping upload");
+            return Ok(());
+        }
+
+        let response = self
+            .bucket
+            .put_object(object_key(kind, hash)?, bytes)
+            .await?;
+        match response.status_code() {
+            200..=299 => Ok(()),
+            code => Err(StorageError::S3Status(
+                code,
+                String::from_utf8_lossy(response.bytes()).to_string(),
+            )),
+        }
+    }
+
+    async fn get_object(&self, kind: ObjectKind, hash: &str) -> StorageResult<Vec<u8>> {
+        let response = self.bucket.get_object(object_key(kind, hash)?).await?;
+        match response.status_code() {
+            200..=299 => Ok(response.bytes().to_vec()),
+            404 => Err(StorageError::NotFound(hash.to_owned())),
+            code => Err(StorageError::S3Status(
+                code,
+                String::from_utf8_lossy(response.bytes()).to_string(),
+            )),
+        }
+    }
+
+    async fn object_exists(&self, kind: ObjectKind, hash: &str) -> StorageResult<bool> {
+        let (_, code) = self.bucket.head_object(object_key(kind, hash)?).await?;
+
+        Ok((200..=299).contains(&code))
+    }
+
+    async fn presigned_url(&self, kind: ObjectKind, hash: &str) -> StorageResult<Option<String>> {
+        Ok(Some(self.bucket.presign_get(
+            object_key(kind, hash)?,
+            PRESIGNED_URL_EXPIRY_SECONDS,
+            None,
+        )?))
+    }
+}
@@ -1,4 +1,4 @@
-use std::{io, net::SocketAddr, path::Path, time::Duration};
+use std::{io, net::SocketAddr, path::Path, sync::Arc, time::Duration};
 
 use super::routes;
 
@@ -21,7 +21,7 @@ use tower_http::trace::{DefaultMakeSpan, TraceLayer};
 use crate::{
     app_state::{AppState, ShutdownSource},
     jwt_key::{JwtKeyError, JwtPublicSigningKey},
-    s3::S3Config,
+    storage::{DynModuleStorage, LocalStorage, S3Storage, StorageBackend, StorageError},
     Config,
 };
 
@@ -52,6 +52,8 @@ pub enum ServerError {
     SerdeJson(#[from] serde_json::Error),
     #[error("failed to setup signal handler")]
     Signal(#[source] io::Error),
+    #[error("module storage error: {0}")]
+    Storage(#[from] StorageError),
 }
 
 impl From<PgPoolError> for ServerError {
@@ -79,28 +81,38 @@ impl Server<(), ()> {
     ) -> Result<(Server<AddrIncoming, SocketAddr>, broadcast::Receiver<()>)> {
         // socket_addr
 
-        // try to load aws creds from a few different places
-        let aws_creds = match (&config.s3().access_key_id, &config.s3().secret_access_key) {
-            (Some(aws_key), Some(aws_secret)) => {
-                AwsCredentials::new(Some(aws_key), Some(aws_secret), None, None, None)?
+        let storage: DynModuleStorage = match config.storage_backend() {
+            StorageBackend::S3 => {
+                // try to load aws creds from a few different places
+                let aws_creds = match (&config.s3().access_key_id, &config.s3().secret_access_key) {
+                    (Some(aws_key), Some(aws_secret)) => {
+                        AwsCredentials::new(Some(aws_key), Some(aws_secret), None, None, None)?
+                    }
+                    (None, None) => match AwsCredentials::from_env() {
+                        Ok(creds) => creds,
+                        Err(CredentialsError::MissingEnvVar(_, _)) => {
+                            AwsCredentials::from_profile(None)?
+                        }
+                        Err(err) => return Err(err.into()),
+                    },
+                    _ => {
+                        return Err(ServerError::AwsConfigError);
+                    }
+                };
+
+                Arc::new(S3Storage::new(config.s3(), aws_creds)?)
             }
-            (None, None) => match AwsCredentials::from_env() {
-                Ok(creds) => creds,
-                Err(CredentialsError::MissingEnvVar(_, _)) => AwsCredentials::from_profile(None)?,
-                Err(err) => return Err(err.into()),
-            },
-            _ => {
-                return Err(ServerError::AwsConfigError);
+            StorageBackend::Local => {
+                info!(
+                    path = %config.local_storage().path.display(),
+                    "storing modules on the local filesystem",
+                );
+                Arc::new(LocalStorage::new(config.local_storage())?)
             }
         };
 
-        let (service, shutdown_rx, shutdown_broadcast_rx) = build_service(
-            pg_pool,
-            jwt_public_signing_key,
-            posthog_client,
-            aws_creds,
-            config.s3().clone(),
-        )?;
+        let (service, shutdown_rx, shutdown_broadcast_rx) =
+            build_service(pg_pool, jwt_public_signing_key, posthog_client, storage)?;
 
         info!(
             "binding to HTTP socket; socket_addr={}",
@@ -212,8 +224,7 @@ pub fn build_service(
     pg_pool: DatabaseConnection,
     jwt_public_signing_key: JwtPublicSigningKey,
     posthog_client: PosthogClient,
-    aws_creds: AwsCredentials,
-    s3_config: S3Config,
+    storage: DynModuleStorage,
 ) -> Result<(Router, oneshot::Receiver<()>, broadcast::Receiver<()>)> {
     let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
     let (shutdown_broadcast_tx, shutdown_broadcast_rx) = broadcast::channel(1);
@@ -222,8 +233,7 @@ pub fn build_service(
         pg_pool,
         jwt_public_signing_key,
         posthog_client,
-        aws_creds,
-        s3_config,
+        storage,
         shutdown_broadcast_tx.clone(),
         shutdown_tx,
     );
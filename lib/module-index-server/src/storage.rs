@@ -0,0 +1,167 @@
+use std::{fmt, path::PathBuf, sync::Arc};
+
+use async_trait::async_trait;
+use s3::error::S3Error;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub use self::local::{LocalStorage, LocalStorageConfig};
+pub use crate::s3::S3Storage;
+pub use module_index_client::content_checksum;
+
+mod local;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("module contents failed integrity check; expected={0}, actual={1}")]
+    IntegrityCheck(String, String),
+    #[error("invalid module object key: {0}")]
+    InvalidKey(String),
+    #[error("storage io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("module object not found in storage: {0}")]
+    NotFound(String),
+    #[error("s3 error: {0}")]
+    S3(#[from] S3Error),
+    #[error("invalid s3 config: {0}")]
+    S3Config(String),
+    #[error("s3 request failed with status {0}: {1}")]
+    S3Status(u16, String),
+}
+
+pub type StorageResult<T> = Result<T, StorageError>;
+
+/// The storage backends that can hold module bundles.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    Local,
+    #[default]
+    S3,
+}
+
+/// The kinds of objects held in module storage, each kept under its own key space.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ObjectKind {
+    /// A single serialized node of a workspace backup's object tree, keyed by the node's hash.
+    BackupNode,
+    /// A complete module bundle, keyed by the module's package hash.
+    Module,
+}
+
+/// A content-addressed store of module bundles, keyed by the module's package hash.
+///
+/// Storing a bundle under a key that is already present is a no-op, so uploading the same module
+/// more than once only stores its contents once.
+#[async_trait]
+pub trait ModuleStorage: fmt::Debug + Send + Sync {
+    /// Stores an object's bytes under the given hash, unless already present.
+    async fn put_object(&self, kind: ObjectKind, hash: &str, bytes: &[u8]) -> StorageResult<()>;
+
+    /// Fetches the object bytes stored under the given hash.
+    async fn get_object(&self, kind: ObjectKind, hash: &str) -> StorageResult<Vec<u8>>;
+
+    /// Returns `true` if an object is stored under the given hash.
+    async fn object_exists(&self, kind: ObjectKind, hash: &str) -> StorageResult<bool>;
+
+    /// Returns a short-lived url the object can be downloaded from directly, for backends that
+    /// can hand one out, so that downloads don't have to be proxied through the module index.
+    async fn presigned_url(&self, _kind: ObjectKind, _hash: &str) -> StorageResult<Option<String>> {
+        Ok(None)
+    }
+
+    /// Stores the module bundle bytes under the given package hash, unless already present.
+    async fn put(&self, hash: &str, bytes: &[u8]) -> StorageResult<()> {
+        self.put_object(ObjectKind::Module, hash, bytes).await
+    }
+
+    /// Fetches the module bundle bytes stored under the given package hash.
+    async fn get(&self, hash: &str) -> StorageResult<Vec<u8>> {
+        self.get_object(ObjectKind::Module, hash).await
+    }
+
+    /// Returns `true` if a module bundle is stored under the given package hash.
+    async fn exists(&self, hash: &str) -> StorageResult<bool> {
+        self.object_exists(ObjectKind::Module, hash).await
+    }
+}
+
+pub type DynModuleStorage = Arc<dyn ModuleStorage>;
+
+/// Stores a module bundle under its package hash and returns the checksum of the bundle that is
+/// kept. Since storing is a no-op when the hash is already present, a re-upload of the same
+/// package hash keeps the original bytes, and so must record their checksum rather than the
+/// checksum of the bytes that were just uploaded: that is the checksum already recorded for the
+/// package hash, if any. Only bundles stored before checksums were recorded are fetched to
+/// compute theirs.
+pub async fn put_with_checksum(
+    storage: &dyn ModuleStorage,
+    hash: &str,
+    bytes: &[u8],
+    recorded_checksum: Option<String>,
+) -> StorageResult<String> {
+    if storage.exists(hash).await? {
+        return match recorded_checksum {
+            Some(recorded_checksum) => Ok(recorded_checksum),
+            None => Ok(content_checksum(&storage.get(hash).await?)),
+        };
+    }
+
+    storage.put(hash, bytes).await?;
+    Ok(content_checksum(bytes))
+}
+
+/// Fetches a module bundle and confirms its contents match the checksum recorded at upload time.
+///
+/// Modules uploaded before checksums were recorded have no checksum and are returned unverified.
+pub async fn get_verified(
+    storage: &dyn ModuleStorage,
+    hash: &str,
+    expected_checksum: Option<&str>,
+) -> StorageResult<Vec<u8>> {
+    let bytes = storage.get(hash).await?;
+
+    if let Some(expected_checksum) = expected_checksum {
+        let actual_checksum = content_checksum(&bytes);
+        if actual_checksum != expected_checksum {
+            return Err(StorageError::IntegrityCheck(
+                expected_checksum.to_owned(),
+                actual_checksum,
+            ));
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Returns the object key for a stored object, rejecting anything which is not a hex encoded hash
+/// so that keys can never escape the storage root.
+pub(crate) fn object_key(kind: ObjectKind, hash: &str) -> StorageResult<String> {
+    if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(StorageError::InvalidKey(hash.to_owned()));
+    }
+
+    Ok(match kind {
+        ObjectKind::BackupNode => format!("nodes/{hash}"),
+        ObjectKind::Module => format!("{hash}.sipkg"),
+    })
+}
+
+/// Returns the path of an object under a local storage root, fanned out by the first two
+/// characters of the hash to keep directories small.
+fn local_object_path(
+    root: &std::path::Path,
+    kind: ObjectKind,
+    hash: &str,
+) -> StorageResult<PathBuf> {
+    let key = object_key(kind, hash)?;
+    let prefix = hash.get(..2).unwrap_or(hash);
+
+    Ok(match kind {
+        ObjectKind::BackupNode => root.join("nodes").join(prefix).join(hash),
+        ObjectKind::Module => root.join(prefix).join(key),
+    })
+}
@@ -0,0 +1,209 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+use tokio::{fs, io::AsyncWriteExt};
+use ulid::Ulid;
+
+use super::{local_object_path, ModuleStorage, ObjectKind, StorageError, StorageResult};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalStorageConfig {
+    pub path: PathBuf,
+}
+
+impl Default for LocalStorageConfig {
+    fn default() -> Self {
+        LocalStorageConfig {
+            path: PathBuf::from("/var/lib/si-module-index/modules"),
+        }
+    }
+}
+
+/// Stores module bundles on the local filesystem, for development and air-gapped installations.
+#[derive(Clone, Debug)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(config: &LocalStorageConfig) -> StorageResult<Self> {
+        std::fs::create_dir_all(&config.path)?;
+
+        Ok(Self {
+            root: config.path.clone(),
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+#[async_trait]
+impl ModuleStorage for LocalStorage {
+    async fn put_object(&self, kind: ObjectKind, hash: &str, bytes: &[u8]) -> StorageResult<()> {
+        let object_path = local_object_path(&self.root, kind, hash)?;
+        if fs::try_exists(&object_path).await? {
+            debug!(
+                hash,
+                ?kind,
+                "object already present in local storage, skipping write"
+            );
+            return Ok(());
+        }
+
+        let parent = object_path
+            .parent()
+            .ok_or_else(|| StorageError::InvalidKey(hash.to_owned()))?;
+        fs::create_dir_all(parent).await?;
+
+        // Write to a temporary file first so that a partially written bundle is never visible
+        // under its final name
+        let tmp_path = parent.join(format!(".{}.tmp", Ulid::new()));
+        let mut file = fs::File::create(&tmp_path).await?;
+        file.write_all(bytes).await?;
+        file.sync_all().await?;
+        drop(file);
+        fs::rename(&tmp_path, &object_path).await?;
+
+        Ok(())
+    }
+
+    async fn get_object(&self, kind: ObjectKind, hash: &str) -> StorageResult<Vec<u8>> {
+        let object_path = local_object_path(&self.root, kind, hash)?;
+
+        match fs::read(&object_path).await {
+            Ok(bytes) => Ok(bytes),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                Err(StorageError::NotFound(hash.to_owned()))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn object_exists(&self, kind: ObjectKind, hash: &str) -> StorageResult<bool> {
+        let object_path = local_object_path(&self.root, kind, hash)?;
+
+        Ok(fs::try_exists(&object_path).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{content_checksum, get_verified, put_with_checksum};
+
+    const HASH: &str = "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262";
+
+    fn storage(dir: &tempfile::TempDir) -> LocalStorage {
+        LocalStorage::new(&LocalStorageConfig {
+            path: dir.path().to_path_buf(),
+        })
+        .expect("failed to create local storage")
+    }
+
+    #[tokio::test]
+    async fn put_and_get() {
+        let dir = tempfile::tempdir().expect("failed to create tempdir");
+        let storage = storage(&dir);
+
+        assert!(!storage.exists(HASH).await.expect("failed to check"));
+        storage.put(HASH, b"module").await.expect("failed to put");
+        assert!(storage.exists(HASH).await.expect("failed to check"));
+        assert_eq!(
+            b"module".to_vec(),
+            storage.get(HASH).await.expect("failed to get")
+        );
+    }
+
+    #[tokio::test]
+    async fn put_with_checksum_returns_the_kept_checksum() {
+        let dir = tempfile::tempdir().expect("failed to create tempdir");
+        let storage = storage(&dir);
+
+        let checksum = put_with_checksum(&storage, HASH, b"module", None)
+            .await
+            .expect("failed to put");
+        assert_eq!(content_checksum(b"module"), checksum);
+
+        // Without a recorded checksum, the kept bundle is checksummed
+        let checksum = put_with_checksum(&storage, HASH, b"other", None)
+            .await
+            .expect("failed to put");
+        assert_eq!(content_checksum(b"module"), checksum);
+
+        // Otherwise the recorded checksum is kept
+        let checksum = put_with_checksum(&storage, HASH, b"other", Some("recorded".to_owned()))
+            .await
+            .expect("failed to put");
+        assert_eq!("recorded", checksum);
+    }
+
+    #[tokio::test]
+    async fn put_is_deduplicated() {
+        let dir = tempfile::tempdir().expect("failed to create tempdir");
+        let storage = storage(&dir);
+
+        storage.put(HASH, b"module").await.expect("failed to put");
+        storage.put(HASH, b"other").await.expect("failed to put");
+
+        assert_eq!(
+            b"module".to_vec(),
+            storage.get(HASH).await.expect("failed to get")
+        );
+    }
+
+    #[tokio::test]
+    async fn object_kinds_are_kept_apart() {
+        let dir = tempfile::tempdir().expect("failed to create tempdir");
+        let storage = storage(&dir);
+
+        storage
+            .put_object(ObjectKind::BackupNode, HASH, b"node")
+            .await
+            .expect("failed to put");
+
+        assert!(!storage.exists(HASH).await.expect("failed to check"));
+        assert_eq!(
+            b"node".to_vec(),
+            storage
+                .get_object(ObjectKind::BackupNode, HASH)
+                .await
+                .expect("failed to get")
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_keys() {
+        let dir = tempfile::tempdir().expect("failed to create tempdir");
+        let storage = storage(&dir);
+
+        assert!(matches!(
+            storage.put("../../etc/passwd", b"nope").await,
+            Err(StorageError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            storage.get(HASH).await,
+            Err(StorageError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn verifies_checksum() {
+        let dir = tempfile::tempdir().expect("failed to create tempdir");
+        let storage = storage(&dir);
+        storage.put(HASH, b"module").await.expect("failed to put");
+
+        let checksum = content_checksum(b"module");
+        get_verified(&storage, HASH, Some(&checksum))
+            .await
+            .expect("checksum should match");
+        assert!(matches!(
+            get_verified(&storage, HASH, Some(&content_checksum(b"other"))).await,
+            Err(StorageError::IntegrityCheck(_, _))
+        ));
+    }
+}
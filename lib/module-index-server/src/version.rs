@@ -0,0 +1,182 @@
+//! Ordering of module version strings.
+//!
+//! Module versions are free-form strings provided by module authors. When a version parses as a
+//! [Semantic Version][semver] it is ordered by semver precedence; versions which do not parse are
+//! considered older than any semantic version and are ordered by their creation time instead.
+//!
+//! [semver]: https://semver.org/
+
+use std::cmp::Ordering;
+
+use crate::models::si_module;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SemanticVersion {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Vec<PreReleaseIdentifier>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PreReleaseIdentifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl SemanticVersion {
+    /// Parses a version string such as `1.2.3`, `v1.2.3-beta.1` or `1.2.3+build.5`. Build metadata
+    /// is ignored as it has no bearing on precedence.
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let version = match version.split_once('+') {
+            Some((version, _build)) => version,
+            None => version,
+        };
+        let (core, pre) = match version.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (version, None),
+        };
+
+        let mut parts = core.split('.');
+        let major = parse_numeric(parts.next()?)?;
+        let minor = parse_numeric(parts.next()?)?;
+        let patch = parse_numeric(parts.next()?)?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let pre = match pre {
+            Some(pre) => pre
+                .split('.')
+                .map(|ident| {
+                    if ident.is_empty() {
+                        None
+                    } else if let Some(numeric) = parse_numeric(ident) {
+                        Some(PreReleaseIdentifier::Numeric(numeric))
+                    } else if ident.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                        Some(PreReleaseIdentifier::AlphaNumeric(ident.to_owned()))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Option<Vec<_>>>()?,
+            None => vec![],
+        };
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+}
+
+fn parse_numeric(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) || (s.len() > 1 && s.starts_with('0'))
+    {
+        return None;
+    }
+    s.parse().ok()
+}
+
+impl Ord for PreReleaseIdentifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Numeric(a), Self::Numeric(b)) => a.cmp(b),
+            (Self::AlphaNumeric(a), Self::AlphaNumeric(b)) => a.cmp(b),
+            // Numeric identifiers always have lower precedence than alphanumeric ones
+            (Self::Numeric(_), Self::AlphaNumeric(_)) => Ordering::Less,
+            (Self::AlphaNumeric(_), Self::Numeric(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for PreReleaseIdentifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SemanticVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then(self.minor.cmp(&other.minor))
+            .then(self.patch.cmp(&other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                // A pre-release version has lower precedence than its associated normal version
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialOrd for SemanticVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares two module versions, with the newest version ordered first.
+pub fn newest_first(a: &si_module::Model, b: &si_module::Model) -> Ordering {
+    match (
+        SemanticVersion::parse(&a.version),
+        SemanticVersion::parse(&b.version),
+    ) {
+        (Some(a_version), Some(b_version)) => b_version
+            .cmp(&a_version)
+            .then(b.created_at.cmp(&a.created_at)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => b.created_at.cmp(&a.created_at),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(version: &str) -> SemanticVersion {
+        SemanticVersion::parse(version).expect("failed to parse version")
+    }
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(parse("1.2.3"), parse("v1.2.3"));
+        assert_eq!(parse("1.2.3"), parse("1.2.3+build.7"));
+        assert!(SemanticVersion::parse("1.2").is_none());
+        assert!(SemanticVersion::parse("1.2.3.4").is_none());
+        assert!(SemanticVersion::parse("01.2.3").is_none());
+        assert!(SemanticVersion::parse("2023-07-21").is_none());
+        assert!(SemanticVersion::parse("1.2.3-").is_none());
+    }
+
+    #[test]
+    fn orders_by_precedence() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.2.0",
+            "1.10.0",
+            "2.0.0",
+        ];
+
+        for pair in ordered.windows(2) {
+            if let [lower, higher] = pair {
+                assert!(parse(lower) < parse(higher), "{lower} < {higher}");
+            }
+        }
+    }
+}
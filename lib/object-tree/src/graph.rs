@@ -162,7 +162,7 @@ pub enum NodeKind {
 
 /// A node entry is a representation of a child node in a parent node's serialized representation.
 #[derive(Clone, Debug)]
-pub(crate) struct NodeEntry {
+pub struct NodeEntry {
     kind: NodeKind,
     hash: Hash,
     name: String,
@@ -177,10 +177,23 @@ impl NodeEntry {
         }
     }
 
+    /// Returns the [`NodeKind`] of the child node.
     #[must_use]
-    pub(crate) fn hash(&self) -> Hash {
+    pub fn kind(&self) -> NodeKind {
+        self.kind
+    }
+
+    /// Returns the [`struct@Hash`] of the child node.
+    #[must_use]
+    pub fn hash(&self) -> Hash {
         self.hash
     }
+
+    /// Returns the name of the child node.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 impl WriteBytes for NodeEntry {
@@ -80,12 +80,12 @@ mod hash;
 mod tar;
 
 pub use crate::tar::{
-    read::TarReadError,
-    write::{TarWriter, TarWriterError},
+    read::{TarIndexReader, TarReadError},
+    write::{SerializedNode, TarWriter, TarWriterError},
 };
 pub use graph::{
     read_key_value_line, read_key_value_line_opt, write_key_value_line, write_key_value_line_opt,
-    GraphError, HashedNode, NameStr, NodeChild, NodeKind, NodeWithChildren, ObjectTree, ReadBytes,
-    WriteBytes,
+    GraphError, HashedNode, NameStr, NodeChild, NodeEntry, NodeKind, NodeWithChildren, ObjectTree,
+    ReadBytes, WriteBytes,
 };
 pub use hash::{Hash, HashParseError};
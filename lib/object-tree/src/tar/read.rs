@@ -1,10 +1,21 @@
-use std::{collections::HashMap, io::Read, path::PathBuf, str::FromStr, string::FromUtf8Error};
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Seek, SeekFrom},
+    num::TryFromIntError,
+    path::{Path, PathBuf},
+    str::FromStr,
+    string::FromUtf8Error,
+    sync::Mutex,
+};
 
 use petgraph::prelude::*;
 use thiserror::Error;
 
 use crate::{
-    graph::{GraphError, HashedNodeWithEntries, NodeWithEntries, ObjectTree, ReadBytes},
+    graph::{
+        GraphError, HashedNode, HashedNodeWithEntries, NodeEntry, NodeWithEntries, ObjectTree,
+        ReadBytes,
+    },
     hash::{Hash, HashParseError},
     tar::{object_path, ref_path},
 };
@@ -12,6 +23,9 @@ use crate::{
 /// Errors that can occur when reading a module bundle from a tar file
 #[derive(Debug, Error)]
 pub enum TarReadError {
+    /// When the size of a `tar` entry cannot be represented as a `usize`
+    #[error("tar entry size is too large: {0}")]
+    EntrySize(#[from] TryFromIntError),
     /// When an error occurs creating a [`struct@Hash`] from the given
     /// [`String`]
     #[error("Error parsing hash: {0}")]
@@ -19,6 +33,9 @@ pub enum TarReadError {
     /// When an error occurs while reading bytes
     #[error("io error when reading: {0}")]
     IoRead(#[from] std::io::Error),
+    /// When the lock over the underlying reader has been poisoned by a panicking thread
+    #[error("reader lock poisoned")]
+    LockPoisoned,
     /// When the given entry is not found in what was read from the `tar`
     #[error("Node entry not found: {0:?}")]
     NodeNotFound(PathBuf),
@@ -51,22 +68,150 @@ impl<T> ObjectTree<T> {
     where
         N: ReadBytes,
     {
-        let mut graph = Graph::new();
-        let mut root_idx: Option<NodeIndex> = None;
+        TarIndexReader::new(Cursor::new(tar_data))?.read_tree()
+    }
+}
+
+/// The location of a node entry's data in the underlying reader of a `tar`.
+#[derive(Clone, Copy, Debug)]
+struct TarEntryLocation {
+    offset: u64,
+    size: u64,
+}
+
+/// A reader over a `tar` bundle of an [`ObjectTree`] which loads nodes on demand.
+///
+/// On creation, the `tar` headers are scanned once to build an index of node entries keyed by
+/// their [`struct@Hash`], but no node data is read. Individual nodes (or whole sub-trees) can then
+/// be read from the index, meaning that callers interested in only a small part of a large tree
+/// never have to hold the entire tree in memory.
+#[derive(Debug)]
+pub struct TarIndexReader<R> {
+    reader: Mutex<R>,
+    index: HashMap<Hash, TarEntryLocation>,
+    root_hash: Hash,
+}
+
+impl<R> TarIndexReader<R>
+where
+    R: Read + Seek,
+{
+    /// Creates a new reader, indexing all node entries found in the `tar`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if:
+    ///
+    /// - An I/O error occurs while reading the `tar` headers
+    /// - A node entry's path cannot be parsed as a [`struct@Hash`]
+    /// - The root ref is missing or is not a valid [`struct@Hash`]
+    pub fn new(mut reader: R) -> Result<Self, TarReadError> {
+        let objects_path = Path::new("objects");
+        let root_ref_path = ref_path("root");
 
-        let mut unpacked_tar = ::tar::Archive::new(tar_data.as_slice());
-        let mut tar_data = HashMap::new();
-        for maybe_tar_entry in unpacked_tar.entries()? {
+        let mut index = HashMap::new();
+        let mut root_hash = None;
+
+        let mut archive = ::tar::Archive::new(&mut reader);
+        for maybe_tar_entry in archive.entries_with_seek()? {
             let mut tar_entry = maybe_tar_entry?;
             let entry_path = tar_entry.path()?.into_owned();
-            let mut entry_data = Vec::new();
-            tar_entry.read_to_end(&mut entry_data)?;
 
-            tar_data.insert(entry_path, entry_data);
+            if entry_path == root_ref_path {
+                let mut buf = Vec::new();
+                tar_entry.read_to_end(&mut buf)?;
+                root_hash = Some(Hash::from_str(&String::from_utf8(buf)?)?);
+            } else if let Ok(hash_path) = entry_path.strip_prefix(objects_path) {
+                let hash = Hash::from_str(&hash_path.to_string_lossy())?;
+                index.insert(
+                    hash,
+                    TarEntryLocation {
+                        offset: tar_entry.raw_file_position(),
+                        size: tar_entry.size(),
+                    },
+                );
+            }
         }
 
-        let root_hash = get_root_ref(&mut tar_data)?;
-        let root_node = get_node(&mut tar_data, root_hash)?.ok_or(TarReadError::RootNodeError)?;
+        let root_hash = root_hash.ok_or(TarReadError::NodeNotFound(root_ref_path))?;
+
+        Ok(Self {
+            reader: Mutex::new(reader),
+            index,
+            root_hash,
+        })
+    }
+
+    /// Returns the [`struct@Hash`] of the root node of the tree.
+    pub fn root_hash(&self) -> Hash {
+        self.root_hash
+    }
+
+    /// Returns `true` if a node with the given [`struct@Hash`] is present in the index.
+    pub fn contains(&self, hash: Hash) -> bool {
+        self.index.contains_key(&hash)
+    }
+
+    /// Returns the number of node entries in the index.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if the index contains no node entries.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Reads a single node for the given [`struct@Hash`], returning it along with the
+    /// [`NodeEntry`] items which describe its children. The children themselves are not read.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the hash is not in the index, if an I/O error occurs or if the node fails
+    /// to be parsed.
+    pub fn read_node<N>(
+        &self,
+        hash: Hash,
+    ) -> Result<Option<(HashedNode<N>, Vec<NodeEntry>)>, TarReadError>
+    where
+        N: ReadBytes,
+    {
+        Ok(self.read_node_with_entries(hash)?.map(Into::into))
+    }
+
+    /// Reads and returns the full [`ObjectTree`] bundled in the `tar`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` for the same reasons as [`TarIndexReader::read_subtree`].
+    pub fn read_tree<N>(&self) -> Result<ObjectTree<N>, TarReadError>
+    where
+        N: ReadBytes,
+    {
+        self.read_subtree(self.root_hash)
+    }
+
+    /// Reads and returns an [`ObjectTree`] whose root is the node for the given
+    /// [`struct@Hash`]. Only the nodes of that sub-tree are read.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if:
+    ///
+    /// - An I/O error occurs while reading from the underlying reader
+    /// - A node is not present in the index
+    /// - A node fails to be correctly parsed
+    /// - The resulting tree structure has no root node or multiple root nodes
+    pub fn read_subtree<N>(&self, hash: Hash) -> Result<ObjectTree<N>, TarReadError>
+    where
+        N: ReadBytes,
+    {
+        let mut graph = Graph::new();
+        let mut root_idx: Option<NodeIndex> = None;
+
+        let root_node = self
+            .read_node_with_entries(hash)?
+            .ok_or(TarReadError::RootNodeError)?;
 
         let mut stack: Vec<(HashedNodeWithEntries<N>, Option<NodeIndex>)> = vec![(root_node, None)];
 
@@ -88,7 +233,7 @@ impl<T> ObjectTree<T> {
             };
 
             for child_entry in child_entries.into_iter().rev() {
-                if let Some(child_node) = get_node(&mut tar_data, child_entry.hash())? {
+                if let Some(child_node) = self.read_node_with_entries(child_entry.hash())? {
                     stack.push((child_node, Some(node_idx)));
                 }
             }
@@ -99,35 +244,30 @@ impl<T> ObjectTree<T> {
             None => Err(TarReadError::ReadTree(GraphError::MissingRootNode)),
         }
     }
-}
 
-fn get_node<N>(
-    tar_data: &mut HashMap<PathBuf, Vec<u8>>,
-    hash: Hash,
-) -> Result<Option<HashedNodeWithEntries<N>>, TarReadError>
-where
-    N: ReadBytes,
-{
-    let dst_path = object_path(&hash);
-    let buf = tar_data
-        .get(&dst_path)
-        .ok_or_else(|| TarReadError::NodeNotFound(dst_path))?;
-
-    let node_with_entries: Option<NodeWithEntries<N>> =
-        NodeWithEntries::from_bytes(buf.clone()).map_err(TarReadError::NodeWithEntriesParse)?;
+    fn read_node_with_entries<N>(
+        &self,
+        hash: Hash,
+    ) -> Result<Option<HashedNodeWithEntries<N>>, TarReadError>
+    where
+        N: ReadBytes,
+    {
+        let location = self
+            .index
+            .get(&hash)
+            .ok_or_else(|| TarReadError::NodeNotFound(object_path(&hash)))?;
 
-    Ok(node_with_entries
-        .map(|nwe| HashedNodeWithEntries::from_node_with_entries_and_hash(nwe, hash)))
-}
+        let mut buf = vec![0; usize::try_from(location.size)?];
+        {
+            let mut reader = self.reader.lock().map_err(|_| TarReadError::LockPoisoned)?;
+            reader.seek(SeekFrom::Start(location.offset))?;
+            reader.read_exact(&mut buf)?;
+        }
 
-fn get_root_ref(tar_data: &mut HashMap<PathBuf, Vec<u8>>) -> Result<Hash, TarReadError> {
-    let dst_path = ref_path("root");
-    let buf = String::from_utf8(
-        tar_data
-            .get(&dst_path)
-            .cloned()
-            .ok_or(TarReadError::NodeNotFound(dst_path))?,
-    )?;
+        let node_with_entries: Option<NodeWithEntries<N>> =
+            NodeWithEntries::from_bytes(buf).map_err(TarReadError::NodeWithEntriesParse)?;
 
-    Hash::from_str(&buf).map_err(Into::into)
+        Ok(node_with_entries
+            .map(|nwe| HashedNodeWithEntries::from_node_with_entries_and_hash(nwe, hash)))
+    }
 }
@@ -7,7 +7,7 @@ use thiserror::Error;
 use crate::{
     graph::{HashedNodeWithEntries, NodeEntry},
     tar::{object_path, ref_path},
-    GraphError, NameStr, ObjectTree, WriteBytes,
+    GraphError, Hash, NameStr, ObjectTree, WriteBytes,
 };
 
 /// Errors that can occur when creating a tar bundle of the object tree
@@ -25,6 +25,48 @@ pub enum TarWriterError {
     TryFromInt(#[from] TryFromIntError),
 }
 
+/// A node of an [`ObjectTree`] in its serialized form, as it is stored in a `tar` bundle.
+///
+/// As a node's [`struct@Hash`] is computed over exactly these bytes, serialized nodes can be
+/// stored, compared and de-duplicated by hash independently of the tree they came from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SerializedNode {
+    hash: Hash,
+    bytes: Vec<u8>,
+}
+
+impl SerializedNode {
+    /// Creates a serialized node from previously stored bytes, verifying that the bytes match the
+    /// given [`struct@Hash`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the hash computed over the bytes does not match the given hash.
+    pub fn new(hash: Hash, bytes: Vec<u8>) -> Result<Self, TarWriterError> {
+        let computed = Hash::new(&bytes);
+        if computed != hash {
+            return Err(GraphError::Verify(hash, computed).into());
+        }
+
+        Ok(Self { hash, bytes })
+    }
+
+    /// Returns the [`struct@Hash`] of the node.
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Returns the serialized bytes of the node.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consumes the node, returning its serialized bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
 /// Create a tar from an [`ObjectTree`]
 pub struct TarWriter {
     bytes: Vec<u8>,
@@ -37,8 +79,53 @@ impl TarWriter {
         T: Clone + NameStr + WriteBytes + Send + Sync + 'static,
     {
         let (graph, root_idx) = tree.as_petgraph();
+        let root_hash = graph
+            .node_weight(root_idx)
+            .ok_or(GraphError::NodeWeightNotFound(
+                root_idx.index(),
+                "tar writer: could not find root node for index",
+            ))?
+            .hash();
+
+        Self::from_serialized_nodes(root_hash, Self::serialized_nodes(tree)?)
+    }
+
+    /// Return a [`TarWriter`] populated from already serialized nodes, such as nodes which were
+    /// stored individually by their [`struct@Hash`].
+    ///
+    /// The nodes are written in the order given and are expected to include every node reachable
+    /// from the root node.
+    pub fn from_serialized_nodes(
+        root_hash: Hash,
+        nodes: impl IntoIterator<Item = SerializedNode>,
+    ) -> Result<Self, TarWriterError> {
         let mut tar_builder = Builder::new(Vec::new());
 
+        for node in nodes {
+            write_tar_entry(&mut tar_builder, object_path(&node.hash), &node.bytes)?;
+        }
+
+        write_tar_entry(
+            &mut tar_builder,
+            ref_path("root"),
+            root_hash.to_string().as_bytes(),
+        )?;
+        tar_builder.finish()?;
+
+        Ok(Self {
+            bytes: tar_builder.into_inner()?,
+        })
+    }
+
+    /// Returns every node of the provided [`ObjectTree`] in its serialized form, ordered so that
+    /// children always come before their parents (i.e. a depth-first post-order traversal).
+    pub fn serialized_nodes<T>(tree: &ObjectTree<T>) -> Result<Vec<SerializedNode>, TarWriterError>
+    where
+        T: Clone + NameStr + WriteBytes,
+    {
+        let (graph, root_idx) = tree.as_petgraph();
+        let mut nodes = Vec::new();
+
         let mut dfspo = DfsPostOrder::new(graph, root_idx);
 
         while let Some(node_idx) = dfspo.next(graph) {
@@ -67,29 +154,13 @@ impl TarWriter {
             }
 
             let tar_entry = HashedNodeWithEntries::new(node, entries);
-            write_tar_entry(
-                &mut tar_builder,
-                object_path(&tar_entry.hash()),
-                &tar_entry.to_bytes()?,
-            )?;
+            nodes.push(SerializedNode {
+                hash: tar_entry.hash(),
+                bytes: tar_entry.to_bytes()?,
+            });
         }
 
-        let root_node = graph
-            .node_weight(root_idx)
-            .ok_or(GraphError::NodeWeightNotFound(
-                root_idx.index(),
-                "tar writer: could not find root node for index",
-            ))?;
-        write_tar_entry(
-            &mut tar_builder,
-            ref_path("root"),
-            root_node.hash().to_string().as_bytes(),
-        )?;
-        tar_builder.finish()?;
-
-        Ok(Self {
-            bytes: tar_builder.into_inner()?,
-        })
+        Ok(nodes)
     }
 
     /// Return the tar as a `Vec<u8>`
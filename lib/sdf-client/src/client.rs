@@ -0,0 +1,400 @@
+use reqwest::{RequestBuilder, Response};
+use sdf_core::{
+    change_set::{
+        AddActionRequest, ApplyChangeSetRequest, ApplyChangeSetResponse, CreateChangeSetRequest,
+        CreateChangeSetResponse, GetChangeSetRequest, GetChangeSetResponse, GetStatsRequest,
+        GetStatsResponse, ListOpenChangeSetsResponse, RemoveActionRequest,
+    },
+    component::{
+        GetCodeRequest, GetCodeResponse, GetPropertyEditorSchemaRequest,
+        GetPropertyEditorSchemaResponse, GetPropertyEditorValuesRequest,
+        GetPropertyEditorValuesResponse, InsertPropertyEditorValueRequest,
+        ListQualificationsRequest, QualificationResponse, RefreshRequest, RefreshResponse,
+        ResetPropertyEditorValueRequest, SetTypeRequest, UpdatePropertyEditorValueRequest,
+    },
+    diagram::{
+        ApplyManifestRequest, ApplyManifestResponse, AttachImportedResourcesRequest,
+        AttachImportedResourcesResponse, CreateConnectionRequest, CreateConnectionResponse,
+        CreateNodeRequest, CreateNodeResponse, DeleteComponentsRequest, DeleteConnectionRequest,
+        GetDiagramRequest, GetDiagramResponse, ImportResourcesRequest, ImportResourcesResponse,
+        ListSchemaVariantsRequest, ListSchemaVariantsResponse, PlanManifestRequest,
+        PlanManifestResponse, PlanResourceImportRequest, PlanResourceImportResponse,
+        RestoreComponentsRequest, SetNodePositionRequest, SetNodePositionResponse,
+        UndeleteConnectionRequest,
+    },
+    fix::{FixesRunRequest, FixesRunResponse, ListFixesRequest, ListFixesResponse},
+    func::{ExecuteRequest, ExecuteResponse, ListFuncsRequest, ListFuncsResponse},
+    pkg::{InstallPkgRequest, InstallPkgResponse, PkgListRequest, PkgListResponse},
+    ws::ResumeFrom,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio_tungstenite::tungstenite::{
+    client::IntoClientRequest,
+    http::{header::AUTHORIZATION, HeaderValue},
+};
+use url::Url;
+
+use crate::{
+    types::ApiErrorBody,
+    ws::{self, WsEventStream},
+    SdfClientError, SdfClientResult,
+};
+
+/// A client for the sdf api, authenticated as a single user of a single workspace.
+///
+/// Every method maps to one route of sdf and takes the same request the route does, so what the
+/// change set and the rest of the visibility is comes from the request, like for the web app.
+#[derive(Debug, Clone)]
+pub struct SdfClient {
+    base_url: Url,
+    auth_token: String,
+    http: reqwest::Client,
+}
+
+impl SdfClient {
+    /// Creates a client for the sdf api found at `base_url`, for example
+    /// `http://localhost:5156/api/`. `auth_token` is either the token returned when connecting
+    /// a session or an api token.
+    pub fn new(mut base_url: Url, auth_token: &str) -> Self {
+        // Routes are joined onto the base url, which only keeps its last segment with a trailing
+        // slash.
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+        Self {
+            base_url,
+            auth_token: auth_token.to_owned(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    // Change sets
+
+    pub async fn list_open_change_sets(&self) -> SdfClientResult<ListOpenChangeSetsResponse> {
+        let url = self.base_url.join("change_set/list_open_change_sets")?;
+        let response = self.send(self.http.get(url)).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn create_change_set(
+        &self,
+        request: &CreateChangeSetRequest,
+    ) -> SdfClientResult<CreateChangeSetResponse> {
+        self.post("change_set/create_change_set", request).await
+    }
+
+    pub async fn get_change_set(
+        &self,
+        request: &GetChangeSetRequest,
+    ) -> SdfClientResult<GetChangeSetResponse> {
+        self.get("change_set/get_change_set", request).await
+    }
+
+    pub async fn get_change_set_stats(
+        &self,
+        request: &GetStatsRequest,
+    ) -> SdfClientResult<GetStatsResponse> {
+        self.get("change_set/get_stats", request).await
+    }
+
+    pub async fn apply_change_set(
+        &self,
+        request: &ApplyChangeSetRequest,
+    ) -> SdfClientResult<ApplyChangeSetResponse> {
+        self.post("change_set/apply_change_set", request).await
+    }
+
+    pub async fn add_action(&self, request: &AddActionRequest) -> SdfClientResult<()> {
+        self.post_empty("change_set/add_action", request).await
+    }
+
+    pub async fn remove_action(&self, request: &RemoveActionRequest) -> SdfClientResult<()> {
+        self.post_empty("change_set/remove_action", request).await
+    }
+
+    // Components
+
+    pub async fn list_qualifications(
+        &self,
+        request: &ListQualificationsRequest,
+    ) -> SdfClientResult<QualificationResponse> {
+        self.get("component/list_qualifications", request).await
+    }
+
+    pub async fn get_code(&self, request: &GetCodeRequest) -> SdfClientResult<GetCodeResponse> {
+        self.get("component/get_code", request).await
+    }
+
+    pub async fn get_property_editor_schema(
+        &self,
+        request: &GetPropertyEditorSchemaRequest,
+    ) -> SdfClientResult<GetPropertyEditorSchemaResponse> {
+        self.get("component/get_property_editor_schema", request)
+            .await
+    }
+
+    pub async fn get_property_editor_values(
+        &self,
+        request: &GetPropertyEditorValuesRequest,
+    ) -> SdfClientResult<GetPropertyEditorValuesResponse> {
+        self.get("component/get_property_editor_values", request)
+            .await
+    }
+
+    pub async fn update_property_editor_value(
+        &self,
+        request: &UpdatePropertyEditorValueRequest,
+    ) -> SdfClientResult<()> {
+        self.post_empty("component/update_property_editor_value", request)
+            .await
+    }
+
+    pub async fn insert_property_editor_value(
+        &self,
+        request: &InsertPropertyEditorValueRequest,
+    ) -> SdfClientResult<()> {
+        self.post_empty("component/insert_property_editor_value", request)
+            .await
+    }
+
+    pub async fn reset_property_editor_value(
+        &self,
+        request: &ResetPropertyEditorValueRequest,
+    ) -> SdfClientResult<()> {
+        self.post_empty("component/reset_property_editor_value", request)
+            .await
+    }
+
+    pub async fn set_component_type(&self, request: &SetTypeRequest) -> SdfClientResult<()> {
+        self.post_empty("component/set_type", request).await
+    }
+
+    pub async fn refresh(&self, request: &RefreshRequest) -> SdfClientResult<RefreshResponse> {
+        self.post("component/refresh", request).await
+    }
+
+    // Diagram
+
+    pub async fn get_diagram(
+        &self,
+        request: &GetDiagramRequest,
+    ) -> SdfClientResult<GetDiagramResponse> {
+        self.get("diagram/get_diagram", request).await
+    }
+
+    pub async fn list_schema_variants(
+        &self,
+        request: &ListSchemaVariantsRequest,
+    ) -> SdfClientResult<ListSchemaVariantsResponse> {
+        self.get("diagram/list_schema_variants", request).await
+    }
+
+    pub async fn create_node(
+        &self,
+        request: &CreateNodeRequest,
+    ) -> SdfClientResult<CreateNodeResponse> {
+        self.post("diagram/create_node", request).await
+    }
+
+    pub async fn set_node_position(
+        &self,
+        request: &SetNodePositionRequest,
+    ) -> SdfClientResult<SetNodePositionResponse> {
+        self.post("diagram/set_node_position", request).await
+    }
+
+    pub async fn create_connection(
+        &self,
+        request: &CreateConnectionRequest,
+    ) -> SdfClientResult<CreateConnectionResponse> {
+        self.post("diagram/create_connection", request).await
+    }
+
+    pub async fn delete_connection(
+        &self,
+        request: &DeleteConnectionRequest,
+    ) -> SdfClientResult<()> {
+        self.post_empty("diagram/delete_connection", request).await
+    }
+
+    pub async fn restore_connection(
+        &self,
+        request: &UndeleteConnectionRequest,
+    ) -> SdfClientResult<()> {
+        self.post_empty("diagram/restore_connection", request).await
+    }
+
+    pub async fn delete_components(
+        &self,
+        request: &DeleteComponentsRequest,
+    ) -> SdfClientResult<()> {
+        self.post_empty("diagram/delete_components", request).await
+    }
+
+    pub async fn restore_components(
+        &self,
+        request: &RestoreComponentsRequest,
+    ) -> SdfClientResult<()> {
+        self.post_empty("diagram/restore_components", request).await
+    }
+
+    pub async fn plan_manifest(
+        &self,
+        request: &PlanManifestRequest,
+    ) -> SdfClientResult<PlanManifestResponse> {
+        self.post("diagram/plan_manifest", request).await
+    }
+
+    pub async fn apply_manifest(
+        &self,
+        request: &ApplyManifestRequest,
+    ) -> SdfClientResult<ApplyManifestResponse> {
+        self.post("diagram/apply_manifest", request).await
+    }
+
+    pub async fn plan_resource_import(
+        &self,
+        request: &PlanResourceImportRequest,
+    ) -> SdfClientResult<PlanResourceImportResponse> {
+        self.post("diagram/plan_resource_import", request).await
+    }
+
+    pub async fn import_resources(
+        &self,
+        request: &ImportResourcesRequest,
+    ) -> SdfClientResult<ImportResourcesResponse> {
+        self.post("diagram/import_resources", request).await
+    }
+
+    pub async fn attach_imported_resources(
+        &self,
+        request: &AttachImportedResourcesRequest,
+    ) -> SdfClientResult<AttachImportedResourcesResponse> {
+        self.post("diagram/attach_imported_resources", request)
+            .await
+    }
+
+    // Funcs
+
+    pub async fn list_funcs(
+        &self,
+        request: &ListFuncsRequest,
+    ) -> SdfClientResult<ListFuncsResponse> {
+        self.get("func/list_funcs", request).await
+    }
+
+    pub async fn execute_func(&self, request: &ExecuteRequest) -> SdfClientResult<ExecuteResponse> {
+        self.post("func/execute", request).await
+    }
+
+    // Fixes
+
+    pub async fn list_fixes(
+        &self,
+        request: &ListFixesRequest,
+    ) -> SdfClientResult<ListFixesResponse> {
+        self.get("fix/list", request).await
+    }
+
+    pub async fn run_fixes(&self, request: &FixesRunRequest) -> SdfClientResult<FixesRunResponse> {
+        self.post("fix/run", request).await
+    }
+
+    // Packages
+
+    pub async fn list_pkgs(&self, request: &PkgListRequest) -> SdfClientResult<PkgListResponse> {
+        self.get("pkg/list_pkgs", request).await
+    }
+
+    pub async fn install_pkg(
+        &self,
+        request: &InstallPkgRequest,
+    ) -> SdfClientResult<InstallPkgResponse> {
+        self.post("pkg/install_pkg", request).await
+    }
+
+    // Websocket
+
+    /// Subscribes to the events of the workspace. Passing where a previous stream left off, as
+    /// returned by [`WsEventStream::resume_from`], replays what was missed in between.
+    pub async fn workspace_updates(
+        &self,
+        resume_from: Option<&ResumeFrom>,
+    ) -> SdfClientResult<WsEventStream> {
+        let mut url = self.base_url.join("ws/workspace_updates")?;
+        let scheme = match url.scheme() {
+            "http" => "ws",
+            "https" => "wss",
+            other => return Err(SdfClientError::UnsupportedScheme(other.to_owned())),
+        };
+        url.set_scheme(scheme)
+            .map_err(|()| SdfClientError::UnsupportedScheme(url.scheme().to_owned()))?;
+        if let Some(resume_from) = resume_from {
+            url.query_pairs_mut()
+                .append_pair("resumeEpoch", &resume_from.epoch)
+                .append_pair("resumeAfter", &resume_from.sequence.to_string());
+        }
+
+        let mut request = url.as_str().into_client_request()?;
+        request.headers_mut().insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", self.auth_token))?,
+        );
+
+        ws::connect(request).await
+    }
+
+    async fn get<Req: Serialize, Res: DeserializeOwned>(
+        &self,
+        path: &str,
+        request: &Req,
+    ) -> SdfClientResult<Res> {
+        let mut url = self.base_url.join(path)?;
+        let params = serde_url_params::to_string(request)?;
+        if !params.is_empty() {
+            url.set_query(Some(&params));
+        }
+        let response = self.send(self.http.get(url)).await?;
+        Ok(response.json::<Res>().await?)
+    }
+
+    async fn post<Req: Serialize, Res: DeserializeOwned>(
+        &self,
+        path: &str,
+        request: &Req,
+    ) -> SdfClientResult<Res> {
+        let url = self.base_url.join(path)?;
+        let response = self.send(self.http.post(url).json(request)).await?;
+        Ok(response.json::<Res>().await?)
+    }
+
+    /// Posts to a route which responds with an empty body.
+    async fn post_empty<Req: Serialize>(&self, path: &str, request: &Req) -> SdfClientResult<()> {
+        let url = self.base_url.join(path)?;
+        self.send(self.http.post(url).json(request)).await?;
+        Ok(())
+    }
+
+    async fn send(&self, request: RequestBuilder) -> SdfClientResult<Response> {
+        let response = request.bearer_auth(&self.auth_token).send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await?;
+        let message = match serde_json::from_str::<ApiErrorBody>(&body) {
+            Ok(body) => body.error.message,
+            Err(_) => body,
+        };
+        Err(SdfClientError::Api {
+            status: status.as_u16(),
+            message,
+        })
+    }
+}
@@ -0,0 +1,19 @@
+//! This crate provides [`SdfClient`], a typed client for the sdf api to drive a workspace from
+//! Rust: change sets, components and their properties, the diagram, funcs, fixes and packages,
+//! along with [`WsEventStream`] to follow the events of the workspace.
+//!
+//! Requests and responses are the types of [`sdf_core`], re-exported here, so using the client
+//! requires neither sdf nor the dal.
+
+pub mod client;
+pub mod types;
+pub mod ws;
+
+pub use client::SdfClient;
+pub use sdf_core::{
+    change_set, component, diagram, fix, func, pkg, ws::WsEvent, ActionId, ActionPrototypeId,
+    AttributeValueId, ChangeSetPk, ComponentId, EdgeId, FixBatchId, FixId, FuncId, NodeId, PropId,
+    SchemaId, SchemaVariantId, SocketId, Visibility, WorkspacePk,
+};
+pub use types::{SdfClientError, SdfClientResult};
+pub use ws::{WsEventStream, WsUpdate};
@@ -0,0 +1,36 @@
+use serde::Deserialize;
+use thiserror::Error;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum SdfClientError {
+    #[error("sdf returned {status}: {message}")]
+    Api { status: u16, message: String },
+    #[error("unexpected websocket message: {0}")]
+    InvalidWsMessage(String),
+    #[error("Request error: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("serde json error: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("cannot connect a websocket to a {0} url")]
+    UnsupportedScheme(String),
+    #[error("url params error: {0}")]
+    UrlParams(#[from] serde_url_params::Error),
+    #[error("Url parse error: {0}")]
+    UrlParse(#[from] url::ParseError),
+    #[error("websocket error: {0}")]
+    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+}
+
+pub type SdfClientResult<T> = Result<T, SdfClientError>;
+
+/// The body sdf responds with when a request fails.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ApiErrorBody {
+    pub error: ApiError,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ApiError {
+    pub message: String,
+}
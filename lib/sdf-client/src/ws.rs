@@ -0,0 +1,111 @@
+//! This module contains [`WsEventStream`], the events sdf sends down the websocket of a
+//! workspace, parsed into [`WsEvent`]s.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt};
+use sdf_core::ws::{ResumeFrom, StreamStatus, WsEvent, WsPayload};
+use serde::Deserialize;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    tungstenite::{handshake::client::Request, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+
+use crate::{SdfClientError, SdfClientResult};
+
+/// What is received on the websocket of a workspace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WsUpdate {
+    /// An event published for the workspace, with the sequence sdf stamped it with.
+    Event { sequence: u64, event: Box<WsEvent> },
+    /// Where the stream is at. Sent first, and again whenever the stream falls behind. When
+    /// `gap` is set events were missed and whatever was built from them should be reloaded.
+    Status(StreamStatus),
+}
+
+/// The events of a workspace, as a [`Stream`]. The stream ends when sdf closes the websocket.
+#[derive(Debug)]
+pub struct WsEventStream {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    epoch: Option<String>,
+    last_sequence: u64,
+}
+
+pub(crate) async fn connect(request: Request) -> SdfClientResult<WsEventStream> {
+    let (socket, _) = tokio_tungstenite::connect_async(request).await?;
+    Ok(WsEventStream {
+        socket,
+        epoch: None,
+        last_sequence: 0,
+    })
+}
+
+impl WsEventStream {
+    /// Where this stream left off, to give to
+    /// [`SdfClient::workspace_updates`](crate::SdfClient::workspace_updates) when reconnecting.
+    /// This is only known once the first status has been received.
+    pub fn resume_from(&self) -> Option<ResumeFrom> {
+        self.epoch.as_ref().map(|epoch| ResumeFrom {
+            epoch: epoch.clone(),
+            sequence: self.last_sequence,
+        })
+    }
+
+    /// Closes the websocket.
+    pub async fn close(mut self) -> SdfClientResult<()> {
+        self.socket.close(None).await?;
+        Ok(())
+    }
+
+    fn parse(&mut self, text: &str) -> SdfClientResult<WsUpdate> {
+        #[derive(Deserialize)]
+        struct Envelope {
+            sequence: Option<u64>,
+            payload: WsPayload,
+        }
+
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        let envelope = Envelope::deserialize(&value)?;
+        if envelope.payload.kind == "StreamStatus" {
+            let status: StreamStatus = envelope.payload.data_as()?;
+            self.epoch = Some(status.epoch.clone());
+            self.last_sequence = status.sequence;
+            return Ok(WsUpdate::Status(status));
+        }
+
+        let sequence = envelope
+            .sequence
+            .ok_or_else(|| SdfClientError::InvalidWsMessage(text.to_owned()))?;
+        let event: WsEvent = serde_json::from_value(value)?;
+        self.last_sequence = sequence;
+        Ok(WsUpdate::Event {
+            sequence,
+            event: Box::new(event),
+        })
+    }
+}
+
+impl Stream for WsEventStream {
+    type Item = SdfClientResult<WsUpdate>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match self.socket.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(message))) => message,
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err.into()))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            match message {
+                Message::Text(text) => return Poll::Ready(Some(self.parse(&text))),
+                Message::Close(_) => return Poll::Ready(None),
+                // Pings are answered by tungstenite itself, and sdf sends nothing else.
+                Message::Binary(_) | Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,3 @@
+const TEST_PG_DBNAME: &str = "si_test_sdf_client";
+
+mod integration_test;
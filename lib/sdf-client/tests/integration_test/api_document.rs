@@ -0,0 +1,460 @@
+//! Checks the requests and responses of sdf-core against the OpenAPI document sdf builds from its
+//! own handlers, so that the two can't drift apart. Every request and response is read from a
+//! sample of its schema with every property set and written back: sdf-core must be able to read
+//! what sdf sends, and must only send what sdf knows, including everything sdf requires.
+
+use sdf_client::{change_set, component, diagram, fix, func, pkg};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Used for the strings of samples, since most of them are ids.
+const SAMPLE_ULID: &str = "01H9ZQD35JPMBGHH69BT0Q79VY";
+
+struct Document(Value);
+
+impl Document {
+    fn new() -> Self {
+        Self(sdf_server::api_document())
+    }
+
+    fn operation(&self, method: &str, path: &str) -> &Value {
+        let operation = &self.0["paths"][format!("/api/{path}")][method];
+        assert!(
+            operation.is_object(),
+            "sdf has no route {method} /api/{path}"
+        );
+        operation
+    }
+
+    /// The schema of the request of a route, with query parameters gathered into an object.
+    fn request(&self, method: &str, path: &str) -> Value {
+        let operation = self.operation(method, path);
+        match operation.get("parameters").and_then(Value::as_array) {
+            Some(parameters) => {
+                let mut properties = Map::new();
+                let mut required = Vec::new();
+                for parameter in parameters {
+                    let name = parameter["name"].as_str().unwrap_or_default().to_owned();
+                    if parameter["required"] == true {
+                        required.push(json!(name));
+                    }
+                    properties.insert(name, parameter["schema"].clone());
+                }
+                json!({ "type": "object", "properties": properties, "required": required })
+            }
+            None => operation["requestBody"]["content"]["application/json"]["schema"].clone(),
+        }
+    }
+
+    fn response(&self, method: &str, path: &str) -> Value {
+        self.operation(method, path)["responses"]["200"]["content"]["application/json"]["schema"]
+            .clone()
+    }
+
+    /// Follows references, and merges the parts of `allOf` into one schema.
+    fn normalize(&self, schema: &Value) -> Value {
+        if let Some(name) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix("#/components/schemas/"))
+        {
+            return self.normalize(&self.0["components"]["schemas"][name]);
+        }
+
+        let parts = match schema.get("allOf").and_then(Value::as_array) {
+            Some(parts) => parts,
+            None => return schema.clone(),
+        };
+        let mut merged = schema.clone();
+        if let Some(object) = merged.as_object_mut() {
+            object.remove("allOf");
+        }
+        for part in parts {
+            let part = self.normalize(part);
+            for (key, value) in part.as_object().into_iter().flatten() {
+                match (key.as_str(), merged.get_mut(key)) {
+                    ("properties", Some(Value::Object(properties))) => {
+                        properties.extend(value.as_object().cloned().unwrap_or_default());
+                    }
+                    ("required", Some(Value::Array(required))) => {
+                        required.extend(value.as_array().cloned().unwrap_or_default());
+                    }
+                    (_, Some(_)) => {}
+                    (_, None) => merged[key] = value.clone(),
+                }
+            }
+        }
+        merged
+    }
+
+    /// A value of the schema with every property set. Recursive schemas stop at empty arrays.
+    fn sample(&self, schema: &Value, visiting: &mut Vec<String>) -> Value {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            if visiting.iter().any(|visited| visited == reference) {
+                return Value::Null;
+            }
+            visiting.push(reference.to_owned());
+            let sample = self.sample(&self.normalize(schema), visiting);
+            visiting.pop();
+            return sample;
+        }
+
+        let schema = self.normalize(schema);
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            return Value::Object(
+                properties
+                    .iter()
+                    .map(|(name, property)| (name.clone(), self.sample(property, visiting)))
+                    .collect(),
+            );
+        }
+        if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+            return variants.first().cloned().unwrap_or_default();
+        }
+        match schema.get("type").and_then(Value::as_str) {
+            Some("object") => match schema.get("additionalProperties") {
+                Some(values) => json!({ SAMPLE_ULID: self.sample(values, visiting) }),
+                None => json!({}),
+            },
+            Some("array") => match self.sample(&schema["items"], visiting) {
+                Value::Null => json!([]),
+                item => json!([item]),
+            },
+            Some("string") => match schema.get("format").and_then(Value::as_str) {
+                Some("date-time") => json!("2023-09-01T12:00:00Z"),
+                Some("uri") => json!("http://localhost:5156/"),
+                _ => json!(SAMPLE_ULID),
+            },
+            Some("integer") => json!(1),
+            Some("number") => json!(1.5),
+            Some("boolean") => json!(true),
+            // Schemas any value matches, and enums whose variants carry data.
+            _ => Value::Null,
+        }
+    }
+
+    /// Checks that a value written by sdf-core only has the properties the schema knows, with
+    /// values it allows, and, with `all_required`, every property it requires.
+    fn check(
+        &self,
+        schema: &Value,
+        value: &Value,
+        location: &str,
+        all_required: bool,
+        errors: &mut Vec<String>,
+    ) {
+        if value.is_null() {
+            return;
+        }
+
+        let schema = self.normalize(schema);
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            let object = match value.as_object() {
+                Some(object) => object,
+                None => {
+                    errors.push(format!("{location}: expected an object, got {value}"));
+                    return;
+                }
+            };
+            for (name, child) in object {
+                match properties.get(name) {
+                    Some(property) => self.check(
+                        property,
+                        child,
+                        &format!("{location}/{name}"),
+                        all_required,
+                        errors,
+                    ),
+                    None => errors.push(format!("{location}/{name}: unknown to sdf")),
+                }
+            }
+            if all_required {
+                for name in schema["required"].as_array().into_iter().flatten() {
+                    let name = name.as_str().unwrap_or_default();
+                    if object.get(name).map_or(true, Value::is_null) {
+                        errors.push(format!("{location}/{name}: required by sdf"));
+                    }
+                }
+            }
+            return;
+        }
+        if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+            if !variants.contains(value) {
+                errors.push(format!("{location}: {value} is not one of {variants:?}"));
+            }
+            return;
+        }
+        match (schema.get("type").and_then(Value::as_str), value) {
+            (Some("array"), Value::Array(items)) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.check(
+                        &schema["items"],
+                        item,
+                        &format!("{location}/{index}"),
+                        all_required,
+                        errors,
+                    );
+                }
+            }
+            (Some("object"), Value::Object(object)) => {
+                if let Some(values) = schema.get("additionalProperties") {
+                    for (key, child) in object {
+                        self.check(
+                            values,
+                            child,
+                            &format!("{location}/{key}"),
+                            all_required,
+                            errors,
+                        );
+                    }
+                }
+            }
+            (Some("array"), _) | (Some("object"), _) => {
+                errors.push(format!("{location}: {value} does not match {schema}"));
+            }
+            _ => {}
+        }
+    }
+
+    fn round_trip<T: Serialize + DeserializeOwned>(
+        &self,
+        schema: &Value,
+        all_required: bool,
+        route: &str,
+    ) -> Vec<String> {
+        let sample = self.sample(schema, &mut Vec::new());
+        let read: T = match serde_json::from_value(sample) {
+            Ok(read) => read,
+            Err(error) => return vec![format!("{route}: sdf-core can't read it: {error}")],
+        };
+        let written = serde_json::to_value(read).expect("cannot serialize sdf-core value");
+
+        let mut errors = Vec::new();
+        self.check(schema, &written, route, all_required, &mut errors);
+        errors
+    }
+
+    /// Checks the request of a route sdf-client sends.
+    fn request_matches<Req: Serialize + DeserializeOwned>(
+        &self,
+        method: &str,
+        path: &str,
+        errors: &mut Vec<String>,
+    ) {
+        let schema = self.request(method, path);
+        errors.extend(self.round_trip::<Req>(&schema, true, &format!("{method} {path} request")));
+    }
+
+    /// Checks the request and the response of a route sdf-client sends and reads.
+    fn route_matches<Req: Serialize + DeserializeOwned, Res: Serialize + DeserializeOwned>(
+        &self,
+        method: &str,
+        path: &str,
+        errors: &mut Vec<String>,
+    ) {
+        self.request_matches::<Req>(method, path, errors);
+        let schema = self.response(method, path);
+        errors.extend(self.round_trip::<Res>(&schema, false, &format!("{method} {path} response")));
+    }
+}
+
+#[test]
+fn sdf_core_matches_the_api_document() {
+    let document = Document::new();
+    let mut errors = Vec::new();
+
+    errors.extend(
+        document.round_trip::<change_set::ListOpenChangeSetsResponse>(
+            &document.response("get", "change_set/list_open_change_sets"),
+            false,
+            "get change_set/list_open_change_sets response",
+        ),
+    );
+    document
+        .route_matches::<change_set::CreateChangeSetRequest, change_set::CreateChangeSetResponse>(
+            "post",
+            "change_set/create_change_set",
+            &mut errors,
+        );
+    document.route_matches::<change_set::GetChangeSetRequest, change_set::GetChangeSetResponse>(
+        "get",
+        "change_set/get_change_set",
+        &mut errors,
+    );
+    document.route_matches::<change_set::GetStatsRequest, change_set::GetStatsResponse>(
+        "get",
+        "change_set/get_stats",
+        &mut errors,
+    );
+    document
+        .route_matches::<change_set::ApplyChangeSetRequest, change_set::ApplyChangeSetResponse>(
+            "post",
+            "change_set/apply_change_set",
+            &mut errors,
+        );
+    document.request_matches::<change_set::AddActionRequest>(
+        "post",
+        "change_set/add_action",
+        &mut errors,
+    );
+    document.request_matches::<change_set::RemoveActionRequest>(
+        "post",
+        "change_set/remove_action",
+        &mut errors,
+    );
+
+    document
+        .route_matches::<component::ListQualificationsRequest, component::QualificationResponse>(
+            "get",
+            "component/list_qualifications",
+            &mut errors,
+        );
+    document.route_matches::<component::GetCodeRequest, component::GetCodeResponse>(
+        "get",
+        "component/get_code",
+        &mut errors,
+    );
+    document.route_matches::<
+        component::GetPropertyEditorSchemaRequest,
+        component::GetPropertyEditorSchemaResponse,
+    >("get", "component/get_property_editor_schema", &mut errors);
+    document.route_matches::<
+        component::GetPropertyEditorValuesRequest,
+        component::GetPropertyEditorValuesResponse,
+    >("get", "component/get_property_editor_values", &mut errors);
+    document.request_matches::<component::UpdatePropertyEditorValueRequest>(
+        "post",
+        "component/update_property_editor_value",
+        &mut errors,
+    );
+    document.request_matches::<component::InsertPropertyEditorValueRequest>(
+        "post",
+        "component/insert_property_editor_value",
+        &mut errors,
+    );
+    document.request_matches::<component::ResetPropertyEditorValueRequest>(
+        "post",
+        "component/reset_property_editor_value",
+        &mut errors,
+    );
+    document.request_matches::<component::SetTypeRequest>(
+        "post",
+        "component/set_type",
+        &mut errors,
+    );
+    document.route_matches::<component::RefreshRequest, component::RefreshResponse>(
+        "post",
+        "component/refresh",
+        &mut errors,
+    );
+
+    document.route_matches::<diagram::GetDiagramRequest, diagram::GetDiagramResponse>(
+        "get",
+        "diagram/get_diagram",
+        &mut errors,
+    );
+    document
+        .route_matches::<diagram::ListSchemaVariantsRequest, diagram::ListSchemaVariantsResponse>(
+            "get",
+            "diagram/list_schema_variants",
+            &mut errors,
+        );
+    document.route_matches::<diagram::CreateNodeRequest, diagram::CreateNodeResponse>(
+        "post",
+        "diagram/create_node",
+        &mut errors,
+    );
+    document.route_matches::<diagram::SetNodePositionRequest, diagram::SetNodePositionResponse>(
+        "post",
+        "diagram/set_node_position",
+        &mut errors,
+    );
+    document.route_matches::<diagram::CreateConnectionRequest, diagram::CreateConnectionResponse>(
+        "post",
+        "diagram/create_connection",
+        &mut errors,
+    );
+    document.request_matches::<diagram::DeleteConnectionRequest>(
+        "post",
+        "diagram/delete_connection",
+        &mut errors,
+    );
+    document.request_matches::<diagram::UndeleteConnectionRequest>(
+        "post",
+        "diagram/restore_connection",
+        &mut errors,
+    );
+    document.request_matches::<diagram::DeleteComponentsRequest>(
+        "post",
+        "diagram/delete_components",
+        &mut errors,
+    );
+    document.request_matches::<diagram::RestoreComponentsRequest>(
+        "post",
+        "diagram/restore_components",
+        &mut errors,
+    );
+    document.route_matches::<diagram::PlanManifestRequest, diagram::PlanManifestResponse>(
+        "post",
+        "diagram/plan_manifest",
+        &mut errors,
+    );
+    document.route_matches::<diagram::ApplyManifestRequest, diagram::ApplyManifestResponse>(
+        "post",
+        "diagram/apply_manifest",
+        &mut errors,
+    );
+    document
+        .route_matches::<diagram::PlanResourceImportRequest, diagram::PlanResourceImportResponse>(
+            "post",
+            "diagram/plan_resource_import",
+            &mut errors,
+        );
+    document.route_matches::<diagram::ImportResourcesRequest, diagram::ImportResourcesResponse>(
+        "post",
+        "diagram/import_resources",
+        &mut errors,
+    );
+    document.route_matches::<
+        diagram::AttachImportedResourcesRequest,
+        diagram::AttachImportedResourcesResponse,
+    >("post", "diagram/attach_imported_resources", &mut errors);
+
+    document.route_matches::<func::ListFuncsRequest, func::ListFuncsResponse>(
+        "get",
+        "func/list_funcs",
+        &mut errors,
+    );
+    document.route_matches::<func::ExecuteRequest, func::ExecuteResponse>(
+        "post",
+        "func/execute",
+        &mut errors,
+    );
+
+    document.route_matches::<fix::ListFixesRequest, fix::ListFixesResponse>(
+        "get",
+        "fix/list",
+        &mut errors,
+    );
+    document.route_matches::<fix::FixesRunRequest, fix::FixesRunResponse>(
+        "post",
+        "fix/run",
+        &mut errors,
+    );
+
+    document.route_matches::<pkg::PkgListRequest, pkg::PkgListResponse>(
+        "get",
+        "pkg/list_pkgs",
+        &mut errors,
+    );
+    document.route_matches::<pkg::InstallPkgRequest, pkg::InstallPkgResponse>(
+        "post",
+        "pkg/install_pkg",
+        &mut errors,
+    );
+
+    assert!(
+        errors.is_empty(),
+        "sdf-core does not match sdf: {errors:#?}"
+    );
+}
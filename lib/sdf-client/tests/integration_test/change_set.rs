@@ -0,0 +1,64 @@
+use axum::Router;
+use dal_test::{sdf_test, AuthTokenRef, DalContextHead};
+use sdf_client::{
+    change_set::{CreateChangeSetRequest, GetChangeSetRequest, GetStatsRequest},
+    SdfClientError, Visibility,
+};
+
+use crate::integration_test::serve;
+
+#[sdf_test]
+async fn create_and_get_change_set(
+    DalContextHead(ctx): DalContextHead,
+    app: Router,
+    AuthTokenRef(auth_token): AuthTokenRef<'_>,
+) {
+    ctx.commit().await.expect("cannot commit transaction");
+    let client = serve(app, auth_token);
+
+    let created = client
+        .create_change_set(&CreateChangeSetRequest {
+            change_set_name: "mastodon".to_string(),
+        })
+        .await
+        .expect("cannot create change set");
+    assert_eq!("mastodon", &created.change_set.name);
+
+    let fetched = client
+        .get_change_set(&GetChangeSetRequest {
+            pk: created.change_set.pk,
+        })
+        .await
+        .expect("cannot get change set");
+    assert_eq!(created.change_set, fetched.change_set);
+
+    let open = client
+        .list_open_change_sets()
+        .await
+        .expect("cannot list open change sets");
+    assert!(open
+        .iter()
+        .any(|change_set| change_set.pk == created.change_set.pk));
+
+    // Queries carry the flattened visibility.
+    client
+        .get_change_set_stats(&GetStatsRequest {
+            visibility: Visibility::new_change_set(created.change_set.pk),
+        })
+        .await
+        .expect("cannot get change set stats");
+}
+
+#[sdf_test]
+async fn errors_carry_the_sdf_message(DalContextHead(ctx): DalContextHead, app: Router) {
+    ctx.commit().await.expect("cannot commit transaction");
+    let client = serve(app, "not a token");
+
+    match client.list_open_change_sets().await {
+        Err(SdfClientError::Api { status, message }) => {
+            assert_eq!(401, status);
+            assert_eq!("unauthorized", message);
+        }
+        other => panic!("expected an api error, got {other:?}"),
+    }
+}
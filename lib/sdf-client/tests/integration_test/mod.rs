@@ -0,0 +1,22 @@
+use std::net::TcpListener;
+
+use axum::Router;
+use sdf_client::SdfClient;
+use url::Url;
+
+mod api_document;
+mod change_set;
+mod ws;
+
+/// Serves the app on a free local port, since the client talks to sdf over the network.
+pub fn serve(app: Router, auth_token: &str) -> SdfClient {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("cannot bind a local port");
+    let address = listener.local_addr().expect("cannot get the local address");
+    let server = axum::Server::from_tcp(listener)
+        .expect("cannot serve on the local port")
+        .serve(app.into_make_service());
+    tokio::spawn(server);
+
+    let base_url = Url::parse(&format!("http://{address}/api")).expect("invalid base url");
+    SdfClient::new(base_url, auth_token)
+}
@@ -0,0 +1,68 @@
+use axum::Router;
+use dal_test::{sdf_test, AuthTokenRef, DalContextHead};
+use futures::StreamExt;
+use sdf_client::{change_set::CreateChangeSetRequest, ChangeSetPk, WsEventStream, WsUpdate};
+
+use crate::integration_test::serve;
+
+async fn next_update(stream: &mut WsEventStream) -> WsUpdate {
+    tokio::time::timeout(std::time::Duration::from_secs(10), stream.next())
+        .await
+        .expect("timed out waiting for an update")
+        .expect("stream ended")
+        .expect("cannot read update")
+}
+
+#[sdf_test]
+async fn workspace_updates(
+    DalContextHead(ctx): DalContextHead,
+    app: Router,
+    AuthTokenRef(auth_token): AuthTokenRef<'_>,
+) {
+    ctx.commit().await.expect("cannot commit transaction");
+    let client = serve(app, auth_token);
+
+    let mut stream = client
+        .workspace_updates(None)
+        .await
+        .expect("cannot subscribe to workspace updates");
+    let status = match next_update(&mut stream).await {
+        WsUpdate::Status(status) => status,
+        other => panic!("expected a status first, got {other:?}"),
+    };
+    assert!(!status.gap);
+
+    let created = client
+        .create_change_set(&CreateChangeSetRequest {
+            change_set_name: "mastodon".to_string(),
+        })
+        .await
+        .expect("cannot create change set");
+
+    let sequence = loop {
+        if let WsUpdate::Event { sequence, event } = next_update(&mut stream).await {
+            if event.payload.kind == "ChangeSetCreated"
+                && event.payload.data_as::<ChangeSetPk>().ok() == Some(created.change_set.pk)
+            {
+                break sequence;
+            }
+        }
+    };
+    let resume_from = stream.resume_from().expect("stream has no position");
+    assert_eq!(status.epoch, resume_from.epoch);
+    assert_eq!(sequence, resume_from.sequence);
+    stream.close().await.expect("cannot close stream");
+
+    // Resuming from where the stream left off replays nothing and reports no gap.
+    let mut stream = client
+        .workspace_updates(Some(&resume_from))
+        .await
+        .expect("cannot resubscribe to workspace updates");
+    match next_update(&mut stream).await {
+        WsUpdate::Status(status) => {
+            assert!(!status.gap);
+            assert_eq!(resume_from.epoch, status.epoch);
+        }
+        other => panic!("expected a status first, got {other:?}"),
+    }
+}
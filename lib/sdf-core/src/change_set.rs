@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{ActionId, ActionPrototypeId, ChangeSetPk, ComponentId, FixBatchId, Visibility};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ChangeSetStatus {
+    Abandoned,
+    Applied,
+    Closed,
+    Failed,
+    Open,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ChangeSet {
+    pub pk: ChangeSetPk,
+    pub name: String,
+    pub note: Option<String>,
+    pub status: ChangeSetStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionView {
+    pub id: ActionId,
+    pub action_prototype_id: ActionPrototypeId,
+    pub name: String,
+    pub component_id: ComponentId,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ChangeSetView {
+    pub pk: ChangeSetPk,
+    pub name: String,
+    pub status: ChangeSetStatus,
+    pub actions: Vec<ActionView>,
+}
+
+pub type ListOpenChangeSetsResponse = Vec<ChangeSetView>;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateChangeSetRequest {
+    pub change_set_name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateChangeSetResponse {
+    pub change_set: ChangeSet,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChangeSetRequest {
+    pub pk: ChangeSetPk,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChangeSetResponse {
+    pub change_set: ChangeSet,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyChangeSetRequest {
+    pub change_set_pk: ChangeSetPk,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyChangeSetResponse {
+    pub change_set: ChangeSet,
+    /// The batch of fixes started for the actions of the change set, if it had any.
+    pub fix_batch_id: Option<FixBatchId>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetStatsRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeStatus {
+    Added,
+    Deleted,
+    Modified,
+    Unmodified,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentChangeStatusGroup {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub component_status: ChangeStatus,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ComponentChangeStatus {
+    pub stats: Vec<ComponentChangeStatusGroup>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetStatsResponse {
+    pub component_stats: ComponentChangeStatus,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddActionRequest {
+    pub prototype_id: ActionPrototypeId,
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveActionRequest {
+    pub id: ActionId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{AttributeValueId, ComponentId, PropId, Visibility};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResourceStatus {
+    Error,
+    Ok,
+    Warning,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceView {
+    pub status: ResourceStatus,
+    pub message: Option<String>,
+    pub data: Option<Value>,
+    pub logs: Vec<String>,
+    pub last_synced: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPropertyEditorSchemaRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PropertyEditorPropKind {
+    Array,
+    Boolean,
+    Integer,
+    Map,
+    Object,
+    String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyEditorProp {
+    pub id: PropId,
+    pub name: String,
+    pub kind: PropertyEditorPropKind,
+    pub doc_link: Option<String>,
+}
+
+/// The props of a component, as a tree starting at `root_prop_id`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPropertyEditorSchemaResponse {
+    pub root_prop_id: PropId,
+    pub props: HashMap<PropId, PropertyEditorProp>,
+    pub child_props: HashMap<PropId, Vec<PropId>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPropertyEditorValuesRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyEditorValue {
+    pub id: AttributeValueId,
+    pub prop_id: PropId,
+    pub key: Option<String>,
+    pub value: Value,
+    pub is_from_external_source: bool,
+    pub is_overridden: bool,
+    pub is_locked: bool,
+}
+
+/// The values of a component, as a tree starting at `root_value_id`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPropertyEditorValuesResponse {
+    pub root_value_id: AttributeValueId,
+    pub values: HashMap<AttributeValueId, PropertyEditorValue>,
+    pub child_values: HashMap<AttributeValueId, Vec<AttributeValueId>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePropertyEditorValueRequest {
+    pub attribute_value_id: AttributeValueId,
+    pub parent_attribute_value_id: Option<AttributeValueId>,
+    pub prop_id: PropId,
+    pub component_id: ComponentId,
+    pub value: Option<Value>,
+    pub key: Option<String>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsertPropertyEditorValueRequest {
+    pub parent_attribute_value_id: AttributeValueId,
+    pub prop_id: PropId,
+    pub component_id: ComponentId,
+    pub value: Option<Value>,
+    pub key: Option<String>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetPropertyEditorValueRequest {
+    pub attribute_value_id: AttributeValueId,
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTypeRequest {
+    pub component_id: ComponentId,
+    pub value: Option<Value>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshRequest {
+    /// Every component is refreshed when none is given.
+    pub component_id: Option<ComponentId>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshResponse {
+    pub success: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCodeRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CodeLanguage {
+    Diff,
+    Json,
+    Unknown,
+    Yaml,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CodeView {
+    pub language: CodeLanguage,
+    pub code: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCodeResponse {
+    pub code_views: Vec<CodeView>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListQualificationsRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QualificationSubCheckStatus {
+    Failure,
+    Success,
+    Unknown,
+    Warning,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct QualificationSubCheck {
+    pub description: String,
+    pub status: QualificationSubCheckStatus,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct QualificationResult {
+    pub status: QualificationSubCheckStatus,
+    pub title: Option<String>,
+    pub link: Option<String>,
+    pub sub_checks: Vec<QualificationSubCheck>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct QualificationOutputStreamView {
+    pub stream: String,
+    pub line: String,
+    pub level: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualificationView {
+    pub title: String,
+    pub output: Vec<QualificationOutputStreamView>,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub result: Option<QualificationResult>,
+    pub qualification_name: String,
+}
+
+pub type QualificationResponse = Vec<QualificationView>;
@@ -0,0 +1,369 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    change_set::ChangeStatus, component::ResourceView, ChangeSetPk, ComponentId, EdgeId, NodeId,
+    SchemaId, SchemaVariantId, SocketId, Visibility,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDiagramRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ComponentType {
+    AggregationFrame,
+    Component,
+    ConfigurationFrame,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct GridPoint {
+    pub x: isize,
+    pub y: isize,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Size2D {
+    pub width: isize,
+    pub height: isize,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SocketDirection {
+    Bidirectional,
+    Input,
+    Output,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SocketView {
+    /// The [`SocketId`], as a string.
+    pub id: String,
+    pub label: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub direction: SocketDirection,
+    pub max_connections: Option<usize>,
+    pub is_required: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagramComponentView {
+    pub id: ComponentId,
+    pub node_id: NodeId,
+    pub display_name: Option<String>,
+    pub parent_node_id: Option<NodeId>,
+    pub child_node_ids: Vec<NodeId>,
+    pub schema_name: String,
+    /// The [`SchemaId`], as a string.
+    pub schema_id: String,
+    /// The [`SchemaVariantId`], as a string.
+    pub schema_variant_id: String,
+    pub schema_variant_name: String,
+    pub schema_category: Option<String>,
+    pub sockets: Option<Vec<SocketView>>,
+    pub position: GridPoint,
+    pub size: Option<Size2D>,
+    pub color: Option<String>,
+    pub node_type: ComponentType,
+    pub change_status: ChangeStatus,
+    pub resource: ResourceView,
+    pub tags: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagramEdgeView {
+    /// The [`EdgeId`], as a string.
+    pub id: String,
+    pub from_node_id: String,
+    pub from_socket_id: String,
+    pub to_node_id: String,
+    pub to_socket_id: String,
+    pub change_status: ChangeStatus,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct GetDiagramResponse {
+    pub components: Vec<DiagramComponentView>,
+    pub edges: Vec<DiagramEdgeView>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSchemaVariantsRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SchemaVariantSocketView {
+    pub id: SocketId,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaVariantView {
+    pub id: SchemaVariantId,
+    pub builtin: bool,
+    pub name: String,
+    pub schema_name: String,
+    pub schema_id: SchemaId,
+    /// Whether this is the variant components of the schema are created from.
+    pub is_default: bool,
+    pub color: String,
+    pub input_sockets: Vec<SchemaVariantSocketView>,
+    pub output_sockets: Vec<SchemaVariantSocketView>,
+}
+
+pub type ListSchemaVariantsResponse = Vec<SchemaVariantView>;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateNodeRequest {
+    pub schema_id: SchemaId,
+    /// The frame to put the node in, if any.
+    pub parent_id: Option<NodeId>,
+    pub x: String,
+    pub y: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateNodeResponse {
+    pub component_id: ComponentId,
+    pub node_id: NodeId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetNodePositionRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+    pub node_id: NodeId,
+    pub x: String,
+    pub y: String,
+    pub width: Option<String>,
+    pub height: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Node {
+    pub id: NodeId,
+    pub x: String,
+    pub y: String,
+    pub width: Option<String>,
+    pub height: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetNodePositionResponse {
+    pub node: Node,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateConnectionRequest {
+    pub from_node_id: NodeId,
+    pub from_socket_id: SocketId,
+    pub to_node_id: NodeId,
+    pub to_socket_id: SocketId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Vertex {
+    pub node_id: NodeId,
+    pub socket_id: SocketId,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Connection {
+    pub id: EdgeId,
+    pub source: Vertex,
+    pub destination: Vertex,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateConnectionResponse {
+    pub connection: Connection,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteConnectionRequest {
+    pub edge_id: EdgeId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndeleteConnectionRequest {
+    pub edge_id: EdgeId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteComponentsRequest {
+    pub component_ids: Vec<ComponentId>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreComponentsRequest {
+    pub component_ids: Vec<ComponentId>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// The changes needed for a workspace to match a manifest, in the order they are made.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestPlan {
+    /// Each change is an object tagged by its `kind`, like `createComponent` or `setValue`.
+    pub changes: Vec<Value>,
+    /// The existing components described by the manifest, by name.
+    pub components: BTreeMap<String, ComponentId>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanManifestRequest {
+    /// The manifest, written in YAML or JSON.
+    pub manifest: String,
+    /// Whether components the manifest does not describe are deleted.
+    pub prune: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanManifestResponse {
+    pub plan: ManifestPlan,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyManifestRequest {
+    /// The manifest, written in YAML or JSON.
+    pub manifest: String,
+    /// Whether components the manifest does not describe are deleted.
+    pub prune: bool,
+    /// Whether the change set the plan was made in is applied afterwards.
+    pub apply: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyManifestResponse {
+    pub plan: ManifestPlan,
+    /// The change set the plan was made in, unless there was nothing to change.
+    pub change_set_pk: Option<ChangeSetPk>,
+    /// The components described by the manifest, by name.
+    pub components: BTreeMap<String, ComponentId>,
+    pub applied: bool,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportSource {
+    /// A CloudFormation template, in JSON or YAML.
+    CloudFormation,
+    /// A Terraform state file (`.tfstate`), version 4.
+    Terraform,
+}
+
+/// A resource that was not imported, since there is no import mapping for its type.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedResource {
+    pub address: String,
+    pub resource_type: String,
+}
+
+/// What importing would change, along with the manifest it was planned from.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceImportPlan {
+    pub manifest: Value,
+    pub plan: ManifestPlan,
+    pub skipped: Vec<SkippedResource>,
+    /// The payloads to attach as resources, by component name.
+    pub payloads: BTreeMap<String, Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanResourceImportRequest {
+    pub source: ImportSource,
+    /// The contents of a Terraform state file or a CloudFormation template.
+    pub content: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanResourceImportResponse {
+    pub plan: ResourceImportPlan,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResourcesRequest {
+    pub source: ImportSource,
+    /// The contents of a Terraform state file or a CloudFormation template.
+    pub content: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResourcesResponse {
+    /// The change set the resources were imported into, or none if there was nothing to change.
+    pub change_set_pk: Option<ChangeSetPk>,
+    /// The imported components, by resource address.
+    pub components: BTreeMap<String, ComponentId>,
+    pub skipped: Vec<SkippedResource>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachImportedResourcesRequest {
+    pub source: ImportSource,
+    /// The contents of the Terraform state file the resources were imported from.
+    pub content: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachImportedResourcesResponse {
+    /// The components resources were attached to, by resource address.
+    pub components: BTreeMap<String, ComponentId>,
+}
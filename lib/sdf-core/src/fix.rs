@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    component::ResourceView, ActionPrototypeId, ComponentId, FixBatchId, FixId, Visibility,
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ActionKind {
+    Create,
+    Delete,
+    Other,
+    Refresh,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FixCompletionStatus {
+    Error,
+    Failure,
+    Success,
+    Unstarted,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFixesRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixHistoryView {
+    pub id: FixId,
+    pub status: FixCompletionStatus,
+    pub action_kind: ActionKind,
+    pub display_name: String,
+    pub schema_name: String,
+    pub component_name: String,
+    pub component_id: ComponentId,
+    pub provider: Option<String>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub resource: Option<ResourceView>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchHistoryView {
+    pub id: FixBatchId,
+    pub status: Option<FixCompletionStatus>,
+    pub author: String,
+    pub fixes: Vec<FixHistoryView>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+pub type ListFixesResponse = Vec<BatchHistoryView>;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixRunRequest {
+    pub component_id: ComponentId,
+    pub action_prototype_id: ActionPrototypeId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixesRunRequest {
+    pub list: Vec<FixRunRequest>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixesRunResponse {
+    pub id: FixBatchId,
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{FuncId, Visibility};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum FuncVariant {
+    Action,
+    Attribute,
+    CodeGeneration,
+    Qualification,
+    Reconciliation,
+    Validation,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFuncsRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListedFuncView {
+    pub id: FuncId,
+    pub handler: Option<String>,
+    pub variant: FuncVariant,
+    pub name: String,
+    pub display_name: Option<String>,
+    pub is_builtin: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFuncsResponse {
+    pub funcs: Vec<ListedFuncView>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecuteRequest {
+    pub id: FuncId,
+    pub args: Value,
+    pub execution_key: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct OutputStream {
+    pub stream: String,
+    pub execution_id: String,
+    pub level: String,
+    pub group: Option<String>,
+    pub message: String,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecuteResponse {
+    pub id: FuncId,
+    pub args: Value,
+    pub output: Value,
+    pub execution_key: String,
+    pub logs: Vec<OutputStream>,
+}
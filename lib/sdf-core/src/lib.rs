@@ -0,0 +1,108 @@
+//! This crate contains the requests and responses of the sdf api as they are sent over the wire,
+//! so that talking to sdf, as [`sdf-client`] does, requires neither sdf nor the dal it is built
+//! on. The messages of the websocket stream are also used by sdf itself.
+//!
+//! Ids are [`Ulid`]s, serialized the same way the ids of the dal are, and the parts of responses
+//! which clients have no use for are left out.
+//!
+//! [`sdf-client`]: ../sdf_client/index.html
+
+#![warn(
+    clippy::unwrap_in_result,
+    clippy::unwrap_used,
+    clippy::panic,
+    clippy::missing_panics_doc,
+    clippy::panic_in_result_fn
+)]
+#![allow(clippy::module_name_repetitions)]
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+
+pub mod change_set;
+pub mod component;
+pub mod diagram;
+pub mod fix;
+pub mod func;
+pub mod pkg;
+pub mod ws;
+
+macro_rules! id {
+    (
+        $(#[$($attrs:tt)*])*
+        $name:ident
+    ) => {
+        $(#[$($attrs)*])*
+        #[derive(
+            Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+        )]
+        pub struct $name(pub Ulid);
+
+        impl $name {
+            /// An unset id value.
+            pub const NONE: Self = Self(Ulid::nil());
+        }
+
+        impl From<Ulid> for $name {
+            fn from(ulid: Ulid) -> Self {
+                Self(ulid)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ulid::DecodeError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Self(Ulid::from_string(s)?))
+            }
+        }
+    };
+}
+
+id!(ActionId);
+id!(ActionPrototypeId);
+id!(AttributeValueId);
+id!(ChangeSetPk);
+id!(ComponentId);
+id!(EdgeId);
+id!(FixBatchId);
+id!(FixId);
+id!(FuncId);
+id!(NodeId);
+id!(PropId);
+id!(SchemaId);
+id!(SchemaVariantId);
+id!(SocketId);
+id!(WorkspacePk);
+
+/// Which [`change set`](change_set) a request reads and writes, flattened into the request.
+/// Requests made on head use [`Visibility::head`].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Visibility {
+    #[serde(rename = "visibility_change_set_pk")]
+    pub change_set_pk: ChangeSetPk,
+    #[serde(rename = "visibility_deleted_at")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl Visibility {
+    pub fn head() -> Self {
+        Self::new_change_set(ChangeSetPk::NONE)
+    }
+
+    pub fn new_change_set(change_set_pk: ChangeSetPk) -> Self {
+        Self {
+            change_set_pk,
+            deleted_at: None,
+        }
+    }
+}
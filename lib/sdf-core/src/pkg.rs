@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+
+use crate::Visibility;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PkgListRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PkgView {
+    pub name: String,
+    pub installed: bool,
+    pub hash: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PkgListResponse {
+    pub pkgs: Vec<PkgView>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallPkgRequest {
+    /// The id of the module in the module index.
+    pub id: Ulid,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallPkgResponse {
+    pub success: bool,
+}
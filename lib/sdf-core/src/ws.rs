@@ -0,0 +1,50 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{ChangeSetPk, WorkspacePk};
+
+/// Where a client left off, as given when it reconnects.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeFrom {
+    pub epoch: String,
+    /// The last sequence the client saw.
+    pub sequence: u64,
+}
+
+/// Sent to the client when it connects, and whenever it falls behind.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamStatus {
+    pub epoch: String,
+    /// The sequence of the last message sent before this status.
+    pub sequence: u64,
+    /// Whether messages were missed, in which case the client should reload what it shows.
+    pub gap: bool,
+}
+
+/// An event published for a workspace.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WsEvent {
+    pub version: i64,
+    pub workspace_pk: WorkspacePk,
+    pub change_set_pk: ChangeSetPk,
+    pub payload: WsPayload,
+}
+
+/// What happened, such as `ChangeSetWritten`, along with whatever data comes with that kind of
+/// event.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WsPayload {
+    pub kind: String,
+    #[serde(default)]
+    pub data: Value,
+}
+
+impl WsPayload {
+    /// Reads the data of the payload, for example as a [`ChangeSetPk`] for the events about
+    /// change sets.
+    pub fn data_as<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        T::deserialize(&self.data)
+    }
+}
@@ -0,0 +1,289 @@
+//! This crate provides the `ApiSchema` derive used by `sdf-server` to describe its request and
+//! response types in its OpenAPI document.
+//!
+//! The derive reads the same `#[serde(...)]` attributes serde does (`rename_all`, `rename`,
+//! `flatten`, `skip`, `skip_serializing`, `default`), so the schema describes what goes over the
+//! wire rather than the Rust fields, and turns doc comments into descriptions.
+
+extern crate proc_macro;
+
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Lit, LitStr, Meta, Type,
+};
+
+/// Implements `ApiSchema` (from `sdf-server`) for a struct or an enum.
+#[proc_macro_derive(ApiSchema, attributes(serde))]
+pub fn derive_api_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let container = SerdeAttrs::parse(&input.attrs)?;
+    let description = doc_comment(&input.attrs);
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let mut statements = Vec::new();
+                for field in &fields.named {
+                    let attrs = SerdeAttrs::parse(&field.attrs)?;
+                    if attrs.skip {
+                        continue;
+                    }
+                    let ty = &field.ty;
+                    let schema = quote! {
+                        (&crate::server::openapi::SchemaOf::<#ty>::new()).api_schema_of(components)
+                    };
+                    if attrs.flatten {
+                        statements.push(quote! {
+                            let schema = #schema;
+                            object.flatten(components, schema);
+                        });
+                        continue;
+                    }
+                    let field_name = field
+                        .ident
+                        .as_ref()
+                        .map(|ident| ident.to_string().trim_start_matches("r#").to_owned())
+                        .unwrap_or_default();
+                    let field_name = match attrs.rename {
+                        Some(rename) => rename,
+                        None => container.rename_field(&field_name),
+                    };
+                    let required = !attrs.default && !container.default && !is_option(ty);
+                    let field_description = option_tokens(doc_comment(&field.attrs));
+                    statements.push(quote! {
+                        object.property(#field_name, #schema, #required, #field_description);
+                    });
+                }
+                let description = option_tokens(description);
+                quote! {
+                    #[allow(unused_mut)]
+                    let mut object = crate::server::openapi::ObjectSchema::new(#description);
+                    #(#statements)*
+                    object.into_value()
+                }
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                quote! {
+                    (&crate::server::openapi::SchemaOf::<#ty>::new()).api_schema_of(components)
+                }
+            }
+            Fields::Unnamed(fields) => {
+                let items = fields.unnamed.iter().map(|field| {
+                    let ty = &field.ty;
+                    quote! {
+                        (&crate::server::openapi::SchemaOf::<#ty>::new()).api_schema_of(components)
+                    }
+                });
+                let len = fields.unnamed.len();
+                quote! {
+                    serde_json::json!({
+                        "type": "array",
+                        "items": { "anyOf": [#(#items),*] },
+                        "minItems": #len,
+                        "maxItems": #len,
+                    })
+                }
+            }
+            Fields::Unit => quote! { crate::server::openapi::null_schema() },
+        },
+        Data::Enum(data) => {
+            if data
+                .variants
+                .iter()
+                .all(|variant| matches!(variant.fields, Fields::Unit))
+            {
+                let mut names = Vec::new();
+                for variant in &data.variants {
+                    let attrs = SerdeAttrs::parse(&variant.attrs)?;
+                    if attrs.skip {
+                        continue;
+                    }
+                    names.push(match attrs.rename {
+                        Some(rename) => rename,
+                        None => container.rename_variant(&variant.ident.to_string()),
+                    });
+                }
+                let description = option_tokens(description);
+                quote! {
+                    crate::server::openapi::string_enum(&[#(#names),*], #description)
+                }
+            } else {
+                // Enums carrying data are tagged in too many ways to describe them precisely,
+                // so they are documented by listing the possible variants.
+                let variants = data
+                    .variants
+                    .iter()
+                    .map(|variant| container.rename_variant(&variant.ident.to_string()));
+                let description = option_tokens(description);
+                quote! {
+                    crate::server::openapi::opaque_enum(&[#(#variants),*], #description)
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "ApiSchema cannot be derived for unions",
+            ))
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics crate::server::openapi::ApiSchema for #ident #ty_generics #where_clause {
+            fn api_schema(
+                components: &mut crate::server::openapi::ApiComponents,
+            ) -> serde_json::Value {
+                #[allow(unused_imports)]
+                use crate::server::openapi::{ViaApiSchema as _, ViaFallback as _};
+                components.register::<Self>(#name, |components| {
+                    let _ = &components;
+                    #body
+                })
+            }
+        }
+    })
+}
+
+/// The `#[serde(...)]` attributes that change the shape of what is serialized.
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    flatten: bool,
+    skip: bool,
+    default: bool,
+}
+
+impl SerdeAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    if meta.input.peek(syn::Token![=]) {
+                        parsed.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                    } else {
+                        skip_nested(&meta)?;
+                    }
+                } else if meta.path.is_ident("rename_all") {
+                    if meta.input.peek(syn::Token![=]) {
+                        parsed.rename_all = Some(meta.value()?.parse::<LitStr>()?.value());
+                    } else {
+                        skip_nested(&meta)?;
+                    }
+                } else if meta.path.is_ident("flatten") {
+                    parsed.flatten = true;
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                    // Fields that are only skipped when deserializing are still sent in
+                    // responses, so they stay in the schema.
+                    parsed.skip = true;
+                } else if meta.path.is_ident("default") {
+                    parsed.default = true;
+                    if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<Expr>()?;
+                    }
+                } else if meta.path.is_ident("skip_serializing_if") {
+                    parsed.default = true;
+                    meta.value()?.parse::<Expr>()?;
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    skip_nested(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+
+    fn rename_field(&self, name: &str) -> String {
+        match self.rename_all.as_deref() {
+            Some(rule) => apply_rule(rule, name, Case::Snake),
+            None => name.to_owned(),
+        }
+    }
+
+    fn rename_variant(&self, name: &str) -> String {
+        match self.rename_all.as_deref() {
+            Some(rule) => apply_rule(rule, name, Case::Pascal),
+            None => name.to_owned(),
+        }
+    }
+}
+
+fn skip_nested(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    meta.parse_nested_meta(|nested| {
+        if nested.input.peek(syn::Token![=]) {
+            nested.value()?.parse::<Expr>()?;
+        }
+        Ok(())
+    })
+}
+
+/// Applies a serde `rename_all` rule to a name written in the given case.
+fn apply_rule(rule: &str, name: &str, from: Case) -> String {
+    let name = name.from_case(from);
+    match rule {
+        "lowercase" => name.to_case(Case::Flat),
+        "UPPERCASE" => name.to_case(Case::UpperFlat),
+        "PascalCase" => name.to_case(Case::Pascal),
+        "camelCase" => name.to_case(Case::Camel),
+        "snake_case" => name.to_case(Case::Snake),
+        "SCREAMING_SNAKE_CASE" => name.to_case(Case::ScreamingSnake),
+        "kebab-case" => name.to_case(Case::Kebab),
+        "SCREAMING-KEBAB-CASE" => name.to_case(Case::Cobol),
+        _ => name.to_case(from),
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => {
+            matches!(path.path.segments.last(), Some(segment) if segment.ident == "Option")
+        }
+        _ => false,
+    }
+}
+
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(name_value) if name_value.path.is_ident("doc") => {
+                match &name_value.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(doc), ..
+                    }) => Some(doc.value().trim().to_owned()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n").trim().to_owned())
+    }
+}
+
+fn option_tokens(value: Option<String>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
@@ -14,8 +14,8 @@
 
 mod server;
 pub use server::{
-    build_service, build_service_for_tests, detect_and_configure_development,
-    job_processor::JobProcessorClientCloser, job_processor::JobProcessorConnector, service, Config,
-    ConfigError, ConfigFile, IncomingStream, JobQueueProcessor, MigrationMode, NatsProcessor,
-    Server, StandardConfig, StandardConfigFile,
+    api_document, build_service, build_service_for_tests, detect_and_configure_development,
+    job_processor::JobProcessorClientCloser, job_processor::JobProcessorConnector, openapi,
+    service, Config, ConfigError, ConfigFile, IncomingStream, JobQueueProcessor, MigrationMode,
+    NatsProcessor, Server, StandardConfig, StandardConfigFile,
 };
@@ -3,7 +3,7 @@ pub use config::{
     IncomingStream, StandardConfig, StandardConfigFile,
 };
 pub use dal::{JobQueueProcessor, MigrationMode, NatsProcessor};
-pub use routes::{routes, AppError};
+pub use routes::{api_document, routes, AppError};
 pub use server::{build_service, build_service_for_tests, Server};
 pub use uds::{UdsIncomingStream, UdsIncomingStreamError};
 
@@ -11,6 +11,7 @@ mod config;
 pub(crate) mod extract;
 mod feature_flags;
 pub(crate) mod job_processor;
+pub mod openapi;
 mod routes;
 mod server;
 pub mod service;
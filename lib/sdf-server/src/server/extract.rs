@@ -3,12 +3,12 @@ use std::{collections::HashMap, fmt};
 use axum::{
     async_trait,
     extract::{FromRequestParts, Query},
-    http::request::Parts,
+    http::{request::Parts, Method},
     Json,
 };
 use dal::{
     context::{self, DalContextBuilder},
-    User, UserClaim,
+    ApiToken, ApiTokenPk, DalContext, User, UserCapability, UserClaim, API_TOKEN_PREFIX,
 };
 use hyper::StatusCode;
 
@@ -27,10 +27,13 @@ impl FromRequestParts<AppState> for AccessBuilder {
         let Authorization(claim) = Authorization::from_request_parts(parts, state).await?;
         let Tenancy(tenancy) = tenancy_from_claim(&claim).await?;
 
-        Ok(Self(context::AccessBuilder::new(
-            tenancy,
-            dal::HistoryActor::from(claim.user_pk),
-        )))
+        // Requests made with an api token are recorded as made by the token, not its creator.
+        let history_actor = match parts.extensions.get::<ApiTokenAuthorization>() {
+            Some(ApiTokenAuthorization(api_token_pk)) => dal::HistoryActor::ApiToken(*api_token_pk),
+            None => dal::HistoryActor::from(claim.user_pk),
+        };
+
+        Ok(Self(context::AccessBuilder::new(tenancy, history_actor)))
     }
 }
 
@@ -132,14 +135,17 @@ impl FromRequestParts<AppState> for Authorization {
         let authorization = authorization_header_value
             .to_str()
             .map_err(internal_error)?;
-        let claim = UserClaim::from_bearer_token(jwt_public_signing_key, authorization)
-            .await
-            .map_err(|_| unauthorized_error())?;
-        ctx.update_tenancy(dal::Tenancy::new(claim.workspace_pk));
 
-        User::authorize(&ctx, &claim.user_pk)
-            .await
-            .map_err(|_| unauthorized_error())?;
+        // Anyone in the workspace can look, but only some roles can make changes.
+        let capability = match parts.method {
+            Method::GET | Method::HEAD | Method::OPTIONS => UserCapability::Read,
+            _ => UserCapability::Write,
+        };
+        let (claim, api_token_pk) =
+            authenticate(&mut ctx, jwt_public_signing_key, authorization, capability).await?;
+        if let Some(api_token_pk) = api_token_pk {
+            parts.extensions.insert(ApiTokenAuthorization(api_token_pk));
+        }
 
         Ok(Self(claim))
     }
@@ -159,19 +165,27 @@ impl FromRequestParts<AppState> for WsAuthorization {
         let mut ctx = builder.build_default().await.map_err(internal_error)?;
         let jwt_public_signing_key = state.jwt_public_signing_key().clone();
 
-        let query: Query<HashMap<String, String>> = Query::from_request_parts(parts, state)
-            .await
-            .map_err(|_| unauthorized_error())?;
-        let authorization = query.get("token").ok_or_else(unauthorized_error)?;
-
-        let claim = UserClaim::from_bearer_token(jwt_public_signing_key, authorization)
-            .await
-            .map_err(|_| unauthorized_error())?;
-        ctx.update_tenancy(dal::Tenancy::new(claim.workspace_pk));
-
-        User::authorize(&ctx, &claim.user_pk)
-            .await
-            .map_err(|_| unauthorized_error())?;
+        // Browsers can't set headers on websockets, so they pass the token in the query instead.
+        let authorization = match parts.headers.get("Authorization") {
+            Some(header_value) => header_value.to_str().map_err(internal_error)?.to_owned(),
+            None => {
+                let query: Query<HashMap<String, String>> = Query::from_request_parts(parts, state)
+                    .await
+                    .map_err(|_| unauthorized_error())?;
+                query
+                    .get("token")
+                    .ok_or_else(unauthorized_error)?
+                    .to_owned()
+            }
+        };
+
+        let (claim, _) = authenticate(
+            &mut ctx,
+            jwt_public_signing_key,
+            &authorization,
+            UserCapability::Read,
+        )
+        .await?;
 
         Ok(Self(claim))
     }
@@ -198,6 +212,56 @@ async fn tenancy_from_claim(
     Ok(Tenancy(dal::Tenancy::new(claim.workspace_pk)))
 }
 
+/// Set on the request by [`Authorization`] when it was made with an [`ApiToken`].
+#[derive(Clone, Copy, Debug)]
+struct ApiTokenAuthorization(ApiTokenPk);
+
+/// Checks the bearer token, which is either a session token or an [`ApiToken`], and whether it is
+/// allowed to do the given thing. Returns the claim it acts with, along with the [`ApiToken`] it
+/// is, if it is one. The [`DalContext`] is scoped to the workspace of the claim.
+async fn authenticate(
+    ctx: &mut DalContext,
+    jwt_public_signing_key: dal::JwtPublicSigningKey,
+    authorization: &str,
+    capability: UserCapability,
+) -> Result<(UserClaim, Option<ApiTokenPk>), (StatusCode, Json<serde_json::Value>)> {
+    let token = authorization
+        .strip_prefix("Bearer ")
+        .unwrap_or(authorization);
+
+    if token.starts_with(API_TOKEN_PREFIX) {
+        let api_token = ApiToken::find_by_secret(ctx, token)
+            .await
+            .map_err(internal_error)?
+            .filter(ApiToken::is_active)
+            .ok_or_else(unauthorized_error)?;
+        let claim = UserClaim::new(api_token.created_by_user_pk(), api_token.workspace_pk());
+        ctx.update_tenancy(dal::Tenancy::new(claim.workspace_pk));
+
+        let authorized = api_token
+            .authorize(ctx, capability)
+            .await
+            .map_err(|_| unauthorized_error())?;
+        if !authorized {
+            return Err(forbidden_error());
+        }
+        return Ok((claim, Some(api_token.pk())));
+    }
+
+    let claim = UserClaim::from_bearer_token(jwt_public_signing_key, authorization)
+        .await
+        .map_err(|_| unauthorized_error())?;
+    ctx.update_tenancy(dal::Tenancy::new(claim.workspace_pk));
+
+    let authorized = User::authorize(ctx, &claim.user_pk, capability)
+        .await
+        .map_err(|_| unauthorized_error())?;
+    if !authorized {
+        return Err(forbidden_error());
+    }
+    Ok((claim, None))
+}
+
 fn internal_error(message: impl fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
     let status_code = StatusCode::INTERNAL_SERVER_ERROR;
     (
@@ -225,3 +289,17 @@ fn unauthorized_error() -> (StatusCode, Json<serde_json::Value>) {
         })),
     )
 }
+
+fn forbidden_error() -> (StatusCode, Json<serde_json::Value>) {
+    let status_code = StatusCode::FORBIDDEN;
+    (
+        status_code,
+        Json(serde_json::json!({
+            "error": {
+                "message": "forbidden",
+                "statusCode": status_code.as_u16(),
+                "code": 42,
+            },
+        })),
+    )
+}
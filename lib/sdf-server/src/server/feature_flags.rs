@@ -1,5 +1,5 @@
 use super::state::PosthogClient;
-use dal::{DalContext, HistoryActor};
+use dal::{DalContext, HistoryActor, User};
 use si_posthog::FeatureFlag;
 
 pub async fn feature_is_enabled(
@@ -8,6 +8,16 @@ pub async fn feature_is_enabled(
     feature: FeatureFlag,
 ) -> bool {
     match ctx.history_actor() {
+        // API tokens get the flags of the user who created them.
+        HistoryActor::ApiToken(_) => {
+            match User::get_for_history_actor(ctx, ctx.history_actor()).await {
+                Ok(Some(user)) => posthog_client
+                    .check_feature_flag(feature, user.pk().to_string())
+                    .await
+                    .unwrap_or(false),
+                _ => false,
+            }
+        }
         HistoryActor::SystemInit => false,
         HistoryActor::User(user_pk) => posthog_client
             .check_feature_flag(feature, user_pk.to_string())
@@ -0,0 +1,710 @@
+//! This module describes the sdf API as an [OpenAPI 3](https://spec.openapis.org/oas/v3.0.3)
+//! document, served at `/api/openapi.json`.
+//!
+//! Every service declares its routes in [`ApiRoutes`], with their handlers and the
+//! [`schemas`](ApiSchema) of their requests and responses, and both the router of sdf and this
+//! document are built from that one declaration. Request and response types get their schemas by
+//! deriving [`ApiSchema`], which follows their serde attributes. The types of other crates that
+//! sdf sends as they are, mostly from `dal`, have theirs listed by hand in `remote`, except for
+//! their ids, which are described as strings.
+
+use std::{
+    any::type_name,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+};
+
+use axum::{
+    handler::Handler,
+    routing::{delete, get, post, MethodRouter},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use convert_case::{Case, Casing};
+use serde_json::{json, Map, Value};
+use ulid::Ulid;
+
+use super::state::AppState;
+
+pub use sdf_openapi_macros::ApiSchema;
+
+mod remote;
+
+/// Builds the schema of a type, calling [`ApiSchema::api_schema`] when the type implements it and
+/// falling back to a schema guessed from the name of the type otherwise.
+macro_rules! schema_of {
+    ($ty:ty) => {
+        |components: &mut $crate::server::openapi::ApiComponents| {
+            #[allow(unused_imports)]
+            use $crate::server::openapi::{ViaApiSchema as _, ViaFallback as _};
+            (&$crate::server::openapi::SchemaOf::<$ty>::new()).api_schema_of(components)
+        }
+    };
+}
+
+pub(crate) use schema_of;
+
+/// A function that builds the schema of a type, as returned by `schema_of!`.
+pub type SchemaFn = fn(&mut ApiComponents) -> Value;
+
+/// Describes how a type is serialized, as a JSON schema object. Usually derived.
+pub trait ApiSchema {
+    fn api_schema(components: &mut ApiComponents) -> Value;
+}
+
+/// Used as the response of routes that answer with an empty body.
+#[derive(Debug)]
+pub struct NoContent;
+
+impl ApiSchema for NoContent {
+    fn api_schema(_components: &mut ApiComponents) -> Value {
+        Value::Null
+    }
+}
+
+/// Carries a type so that `(&SchemaOf::<T>::new()).api_schema_of(components)` resolves to
+/// [`ViaApiSchema`] when `T` implements [`ApiSchema`] and to [`ViaFallback`] when it does not.
+#[derive(Debug)]
+pub struct SchemaOf<T: ?Sized>(std::marker::PhantomData<T>);
+
+impl<T: ?Sized> SchemaOf<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+pub trait ViaApiSchema {
+    fn api_schema_of(&self, components: &mut ApiComponents) -> Value;
+}
+
+impl<T: ApiSchema + ?Sized> ViaApiSchema for SchemaOf<T> {
+    fn api_schema_of(&self, components: &mut ApiComponents) -> Value {
+        T::api_schema(components)
+    }
+}
+
+pub trait ViaFallback {
+    fn api_schema_of(&self, components: &mut ApiComponents) -> Value;
+}
+
+impl<T: ?Sized> ViaFallback for &SchemaOf<T> {
+    fn api_schema_of(&self, _components: &mut ApiComponents) -> Value {
+        fallback_schema(type_name::<T>())
+    }
+}
+
+/// The named schemas referenced from the rest of the document.
+#[derive(Debug, Default)]
+pub struct ApiComponents {
+    schemas: BTreeMap<String, Value>,
+    names: HashMap<&'static str, String>,
+}
+
+impl ApiComponents {
+    /// Registers the schema of `T` under `name` the first time it is seen, and returns a reference
+    /// to it. Types with the same name in different modules are told apart by their module.
+    pub fn register<T: ?Sized>(
+        &mut self,
+        name: &str,
+        build: impl FnOnce(&mut Self) -> Value,
+    ) -> Value {
+        let type_name = type_name::<T>();
+        if let Some(name) = self.names.get(type_name) {
+            return reference(name);
+        }
+
+        let mut unique_name = name.to_owned();
+        if self.schemas.contains_key(&unique_name) {
+            let module = type_name
+                .rsplit("::")
+                .nth(1)
+                .unwrap_or_default()
+                .to_case(Case::Pascal);
+            unique_name = format!("{module}{name}");
+            let mut suffix = 2;
+            while self.schemas.contains_key(&unique_name) {
+                unique_name = format!("{module}{name}{suffix}");
+                suffix += 1;
+            }
+        }
+
+        // Registered before being built, so that recursive types refer to themselves.
+        self.names.insert(type_name, unique_name.clone());
+        self.schemas.insert(unique_name.clone(), Value::Null);
+        let schema = build(self);
+        self.schemas.insert(unique_name.clone(), schema);
+        reference(&unique_name)
+    }
+
+    /// Follows a reference to a registered schema, if the schema is one.
+    pub fn resolve<'a>(&'a self, schema: &'a Value) -> &'a Value {
+        schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|path| path.strip_prefix("#/components/schemas/"))
+            .and_then(|name| self.schemas.get(name))
+            .unwrap_or(schema)
+    }
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+/// The schema of a struct with named fields, as built by the derive.
+#[derive(Debug)]
+pub struct ObjectSchema {
+    description: Option<&'static str>,
+    properties: Map<String, Value>,
+    required: Vec<String>,
+    all_of: Vec<Value>,
+}
+
+impl ObjectSchema {
+    pub fn new(description: Option<&'static str>) -> Self {
+        Self {
+            description,
+            properties: Map::new(),
+            required: Vec::new(),
+            all_of: Vec::new(),
+        }
+    }
+
+    pub fn property(
+        &mut self,
+        name: &str,
+        schema: Value,
+        required: bool,
+        description: Option<&'static str>,
+    ) {
+        self.properties
+            .insert(name.to_owned(), with_description(schema, description));
+        if required {
+            self.required.push(name.to_owned());
+        }
+    }
+
+    /// Adds the properties of a `#[serde(flatten)]` field.
+    pub fn flatten(&mut self, components: &ApiComponents, schema: Value) {
+        let resolved = components.resolve(&schema).clone();
+        match resolved.get("properties").and_then(Value::as_object) {
+            Some(properties) => {
+                for (name, property) in properties {
+                    self.properties.insert(name.clone(), property.clone());
+                }
+                if let Some(required) = resolved.get("required").and_then(Value::as_array) {
+                    self.required.extend(
+                        required
+                            .iter()
+                            .filter_map(Value::as_str)
+                            .map(ToOwned::to_owned),
+                    );
+                }
+            }
+            None => self.all_of.push(schema),
+        }
+    }
+
+    pub fn into_value(self) -> Value {
+        let mut object = json!({ "type": "object", "properties": self.properties });
+        if !self.required.is_empty() {
+            object["required"] = json!(self.required);
+        }
+        if let Some(description) = self.description {
+            object["description"] = json!(description);
+        }
+        if self.all_of.is_empty() {
+            object
+        } else {
+            let mut all_of = self.all_of;
+            all_of.push(object);
+            json!({ "allOf": all_of })
+        }
+    }
+}
+
+fn with_description(schema: Value, description: Option<&str>) -> Value {
+    match description {
+        None => schema,
+        // Siblings of a reference are ignored, so the reference gets wrapped.
+        Some(description) if schema.get("$ref").is_some() => {
+            json!({ "allOf": [schema], "description": description })
+        }
+        Some(description) => {
+            let mut schema = schema;
+            schema["description"] = json!(description);
+            schema
+        }
+    }
+}
+
+/// The schema of an enum whose variants carry no data.
+pub fn string_enum(variants: &[&str], description: Option<&'static str>) -> Value {
+    with_description(json!({ "type": "string", "enum": variants }), description)
+}
+
+/// The schema of an enum whose variants carry data, which only lists the variants.
+pub fn opaque_enum(variants: &[&str], description: Option<&'static str>) -> Value {
+    let variants = format!("One of: {}.", variants.join(", "));
+    let description = match description {
+        Some(description) => format!("{description}\n\n{variants}"),
+        None => variants,
+    };
+    json!({ "description": description })
+}
+
+/// The schema of a value that is always `null`.
+pub fn null_schema() -> Value {
+    json!({ "nullable": true, "enum": [null] })
+}
+
+/// Guesses a schema from the name of a type that does not implement [`ApiSchema`], looking through
+/// the usual containers.
+fn fallback_schema(type_name: &str) -> Value {
+    let (path, args) = match type_name.split_once('<') {
+        Some((path, args)) => (path, split_type_args(&args[..args.len() - 1])),
+        None => (type_name, Vec::new()),
+    };
+    let name = path.rsplit("::").next().unwrap_or(path);
+
+    match (name, args.as_slice()) {
+        ("Option", [inner]) => nullable(fallback_schema(inner)),
+        ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", [inner, ..]) => {
+            json!({ "type": "array", "items": fallback_schema(inner) })
+        }
+        ("HashMap" | "BTreeMap", [_, value, ..]) => {
+            json!({ "type": "object", "additionalProperties": fallback_schema(value) })
+        }
+        ("Box" | "Arc" | "Rc", [inner]) => fallback_schema(inner),
+        _ if name.ends_with("Pk") || name.ends_with("Id") => {
+            json!({ "type": "string", "x-rust-type": name })
+        }
+        _ => json!({ "x-rust-type": name }),
+    }
+}
+
+/// Splits the arguments of a generic type on the commas that are not nested in other arguments.
+fn split_type_args(args: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, character) in args.char_indices() {
+        match character {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(args[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(args[start..].trim());
+    split
+}
+
+fn nullable(schema: Value) -> Value {
+    if schema.get("$ref").is_some() {
+        json!({ "allOf": [schema], "nullable": true })
+    } else {
+        let mut schema = schema;
+        if let Some(object) = schema.as_object_mut() {
+            object.insert("nullable".to_owned(), json!(true));
+        }
+        schema
+    }
+}
+
+macro_rules! impl_api_schema {
+    ($schema:tt for $($ty:ty),+) => {
+        $(
+            impl ApiSchema for $ty {
+                fn api_schema(_components: &mut ApiComponents) -> Value {
+                    json!($schema)
+                }
+            }
+        )+
+    };
+}
+
+impl_api_schema!({ "type": "boolean" } for bool);
+impl_api_schema!({ "type": "integer", "format": "int32" } for i8, i16, i32, u8, u16);
+impl_api_schema!({ "type": "integer", "format": "int64" } for i64, isize, u32);
+impl_api_schema!({ "type": "integer", "format": "int64", "minimum": 0 } for u64, usize);
+impl_api_schema!({ "type": "number", "format": "float" } for f32);
+impl_api_schema!({ "type": "number", "format": "double" } for f64);
+impl_api_schema!({ "type": "string" } for String, str, char, Ulid);
+impl_api_schema!({ "type": "string", "format": "date-time" } for DateTime<Utc>);
+impl_api_schema!({ "type": "string", "format": "uri" } for url::Url);
+impl_api_schema!({} for Value);
+impl_api_schema!({ "type": "object" } for Map<String, Value>);
+
+impl ApiSchema for () {
+    fn api_schema(_components: &mut ApiComponents) -> Value {
+        null_schema()
+    }
+}
+
+impl<T: ApiSchema + ?Sized> ApiSchema for &T {
+    fn api_schema(components: &mut ApiComponents) -> Value {
+        T::api_schema(components)
+    }
+}
+
+impl<T: ApiSchema + ?Sized> ApiSchema for Box<T> {
+    fn api_schema(components: &mut ApiComponents) -> Value {
+        T::api_schema(components)
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Option<T> {
+    fn api_schema(components: &mut ApiComponents) -> Value {
+        nullable(T::api_schema(components))
+    }
+}
+
+macro_rules! impl_api_schema_for_sequence {
+    ($($ty:ident),+) => {
+        $(
+            impl<T: ApiSchema> ApiSchema for $ty<T> {
+                fn api_schema(components: &mut ApiComponents) -> Value {
+                    json!({ "type": "array", "items": T::api_schema(components) })
+                }
+            }
+        )+
+    };
+}
+
+impl_api_schema_for_sequence!(Vec, HashSet, BTreeSet);
+
+impl<T: ApiSchema> ApiSchema for [T] {
+    fn api_schema(components: &mut ApiComponents) -> Value {
+        json!({ "type": "array", "items": T::api_schema(components) })
+    }
+}
+
+impl<K, V: ApiSchema> ApiSchema for HashMap<K, V> {
+    fn api_schema(components: &mut ApiComponents) -> Value {
+        json!({ "type": "object", "additionalProperties": V::api_schema(components) })
+    }
+}
+
+impl<K, V: ApiSchema> ApiSchema for BTreeMap<K, V> {
+    fn api_schema(components: &mut ApiComponents) -> Value {
+        json!({ "type": "object", "additionalProperties": V::api_schema(components) })
+    }
+}
+
+/// [`Visibility`](dal::Visibility) is flattened into most requests, as query parameters or as
+/// part of the body.
+impl ApiSchema for dal::Visibility {
+    fn api_schema(components: &mut ApiComponents) -> Value {
+        components.register::<Self>("Visibility", |_| {
+            json!({
+                "type": "object",
+                "properties": {
+                    "visibility_change_set_pk": {
+                        "type": "string",
+                        "description": "The change set to work in, `0` for head.",
+                    },
+                    "visibility_deleted_at": {
+                        "type": "string",
+                        "format": "date-time",
+                        "nullable": true,
+                    },
+                },
+                "required": ["visibility_change_set_pk"],
+            })
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ApiMethod {
+    Delete,
+    Get,
+    Post,
+    WebSocket,
+}
+
+#[derive(Debug)]
+struct ApiOperation {
+    method: ApiMethod,
+    path: String,
+    tag: Option<String>,
+    request: SchemaFn,
+    response: SchemaFn,
+}
+
+/// The routes of a service, with their handlers and the schemas of their requests and responses.
+/// Both the [`Router`] of the service and its part of the OpenAPI document are built from it, so
+/// that every route is documented.
+#[derive(Debug)]
+pub struct ApiRoutes {
+    router: Router<AppState>,
+    operations: Vec<ApiOperation>,
+}
+
+impl Default for ApiRoutes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ApiRoutes {
+    pub fn new() -> Self {
+        Self {
+            router: Router::new(),
+            operations: Vec::new(),
+        }
+    }
+
+    /// A `GET` route, whose request is passed as query parameters.
+    pub fn get<H, T>(self, path: &str, handler: H, request: SchemaFn, response: SchemaFn) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.route(ApiMethod::Get, path, get(handler), request, response)
+    }
+
+    /// A `GET` route served by a [`MethodRouter`] built by hand, for instance to add a layer to
+    /// it, whose request is passed as query parameters.
+    pub fn get_with(
+        self,
+        path: &str,
+        method_router: MethodRouter<AppState>,
+        request: SchemaFn,
+        response: SchemaFn,
+    ) -> Self {
+        self.route(ApiMethod::Get, path, method_router, request, response)
+    }
+
+    /// A `POST` route, whose request is passed as a json body.
+    pub fn post<H, T>(self, path: &str, handler: H, request: SchemaFn, response: SchemaFn) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.route(ApiMethod::Post, path, post(handler), request, response)
+    }
+
+    /// A `DELETE` route, whose request is passed as a json body.
+    pub fn delete<H, T>(self, path: &str, handler: H, request: SchemaFn, response: SchemaFn) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.route(ApiMethod::Delete, path, delete(handler), request, response)
+    }
+
+    /// A `GET` route that upgrades to a websocket, whose request is passed as query parameters.
+    pub fn websocket<H, T>(self, path: &str, handler: H, request: SchemaFn) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.route(ApiMethod::WebSocket, path, get(handler), request, |_| {
+            Value::Null
+        })
+    }
+
+    /// Adds the routes of another service under `prefix`, tagging them with the name of the
+    /// service.
+    pub fn nest(mut self, prefix: &str, routes: ApiRoutes) -> Self {
+        let tag = prefix
+            .trim_matches('/')
+            .trim_start_matches("api/")
+            .to_owned();
+        for mut operation in routes.operations {
+            operation.path = join_path(prefix, &operation.path);
+            operation.tag.get_or_insert_with(|| tag.clone());
+            self.operations.push(operation);
+        }
+        self.router = self.router.nest(prefix, routes.router);
+        self
+    }
+
+    /// The [`Router`] serving every route.
+    pub fn into_router(self) -> Router<AppState> {
+        self.router
+    }
+
+    fn route(
+        mut self,
+        method: ApiMethod,
+        path: &str,
+        method_router: MethodRouter<AppState>,
+        request: SchemaFn,
+        response: SchemaFn,
+    ) -> Self {
+        // Routing a path again adds the method to the ones it already has.
+        self.router = self.router.route(path, method_router);
+        self.operations.push(ApiOperation {
+            method,
+            path: path.to_owned(),
+            tag: None,
+            request,
+            response,
+        });
+        self
+    }
+
+    /// Builds the OpenAPI document for every route.
+    pub fn document(&self) -> Value {
+        let mut components = ApiComponents::default();
+        let mut paths = Map::new();
+
+        for operation in &self.operations {
+            let request = (operation.request)(&mut components);
+            let response = (operation.response)(&mut components);
+
+            let mut object = json!({
+                "responses": {
+                    "default": { "$ref": "#/components/responses/Error" },
+                },
+            });
+            if let Some(tag) = &operation.tag {
+                object["tags"] = json!([tag]);
+            }
+
+            let method = match operation.method {
+                ApiMethod::Get | ApiMethod::WebSocket => {
+                    object["parameters"] = json!(query_parameters(&components, &request));
+                    "get"
+                }
+                ApiMethod::Post | ApiMethod::Delete => {
+                    object["requestBody"] = json!({
+                        "required": true,
+                        "content": { "application/json": { "schema": request } },
+                    });
+                    if operation.method == ApiMethod::Post {
+                        "post"
+                    } else {
+                        "delete"
+                    }
+                }
+            };
+
+            let shared_path = self
+                .operations
+                .iter()
+                .filter(|other| other.path == operation.path)
+                .count()
+                > 1;
+            object["operationId"] = json!(operation_id(method, &operation.path, shared_path));
+
+            let (status, response) = match (operation.method, response) {
+                (ApiMethod::WebSocket, _) => {
+                    ("101", json!({ "description": "Switching Protocols" }))
+                }
+                (_, Value::Null) => ("200", json!({ "description": "OK" })),
+                (_, schema) => (
+                    "200",
+                    json!({
+                        "description": "OK",
+                        "content": { "application/json": { "schema": schema } },
+                    }),
+                ),
+            };
+            object["responses"][status] = response;
+
+            let path = paths
+                .entry(operation.path.clone())
+                .or_insert_with(|| json!({}));
+            path[method] = object;
+        }
+
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "System Initiative sdf API",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "paths": paths,
+            "security": [{ "bearerAuth": [] }],
+            "components": {
+                "schemas": components.schemas,
+                "securitySchemes": {
+                    "bearerAuth": { "type": "http", "scheme": "bearer" },
+                },
+                "responses": {
+                    "Error": {
+                        "description": "The request failed.",
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": {
+                                        "error": {
+                                            "type": "object",
+                                            "properties": {
+                                                "message": { "type": "string" },
+                                                "code": { "type": "integer" },
+                                                "statusCode": { "type": "integer" },
+                                            },
+                                            "required": ["message", "code", "statusCode"],
+                                        },
+                                    },
+                                    "required": ["error"],
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+        })
+    }
+}
+
+/// Joins a nested path the way [`Router::nest`](axum::Router::nest) does.
+fn join_path(prefix: &str, path: &str) -> String {
+    if path == "/" {
+        prefix.to_owned()
+    } else {
+        format!("{}{path}", prefix.trim_end_matches('/'))
+    }
+}
+
+/// Names an operation after its path, and after its method too when the path has several.
+fn operation_id(method: &str, path: &str, shared_path: bool) -> String {
+    let path = path.trim_matches('/');
+    let id = path
+        .strip_prefix("api/")
+        .unwrap_or(path)
+        .replace(|character: char| !character.is_ascii_alphanumeric(), "_");
+    if shared_path {
+        format!("{method}_{id}")
+    } else {
+        id
+    }
+}
+
+/// Turns the properties of a request into query parameters.
+fn query_parameters(components: &ApiComponents, request: &Value) -> Vec<Value> {
+    let request = components.resolve(request);
+    let mut schemas = vec![request];
+    if let Some(all_of) = request.get("allOf").and_then(Value::as_array) {
+        schemas.extend(all_of.iter().map(|schema| components.resolve(schema)));
+    }
+
+    let mut parameters = Vec::new();
+    for schema in schemas {
+        let required: BTreeSet<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (name, property) in properties {
+                parameters.push(json!({
+                    "name": name,
+                    "in": "query",
+                    "required": required.contains(name.as_str()),
+                    "schema": property,
+                }));
+            }
+        }
+    }
+    parameters
+}
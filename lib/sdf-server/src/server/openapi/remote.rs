@@ -0,0 +1,1626 @@
+//! Schemas for the types of other crates that sdf sends and receives as they are, mostly from
+//! `dal`. These can't derive [`ApiSchema`] here, so their fields are listed by hand, under the
+//! names they are serialized with. The tests round-trip a sample of every type described here
+//! through serde and compare the result with its schema, so they fail when the two drift apart.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use dal::{
+    property_editor::{self, PropertyEditorPropId, PropertyEditorValueId},
+    status::AttributeValueMetadata,
+};
+use serde_json::{json, Value};
+
+use super::{
+    opaque_enum, schema_of, string_enum, ApiComponents, ApiSchema, ObjectSchema, SchemaFn,
+};
+
+/// Implements [`ApiSchema`] for a struct of another crate, from the fields it serializes. Fields
+/// that may be missing are marked `= optional`, and the types of flattened fields are listed
+/// after the fields.
+macro_rules! remote_object {
+    (@required) => {
+        true
+    };
+    (@required optional) => {
+        false
+    };
+    (
+        $ty:ty as $name:literal {
+            $($field:literal: $field_ty:ty $(= $optional:ident)?),* $(,)?
+        } $(flatten [$($flatten_ty:ty),+ $(,)?])?
+    ) => {
+        impl ApiSchema for $ty {
+            fn api_schema(components: &mut ApiComponents) -> Value {
+                components.register::<Self>($name, |components| {
+                    let mut object = ObjectSchema::new(None);
+                    $(
+                        let schema: SchemaFn = schema_of!($field_ty);
+                        let schema = schema(components);
+                        let required = remote_object!(@required $($optional)?);
+                        object.property($field, schema, required, None);
+                    )*
+                    $($(
+                        let schema: SchemaFn = schema_of!($flatten_ty);
+                        let schema = schema(components);
+                        object.flatten(components, schema);
+                    )+)?
+                    object.into_value()
+                })
+            }
+        }
+    };
+}
+
+/// Implements [`ApiSchema`] for an enum of another crate whose variants carry no data, from the
+/// names its variants are serialized with.
+macro_rules! remote_string_enum {
+    ($ty:ty as $name:literal [$($variant:literal),+ $(,)?]) => {
+        impl ApiSchema for $ty {
+            fn api_schema(components: &mut ApiComponents) -> Value {
+                components.register::<Self>($name, |_| string_enum(&[$($variant),+], None))
+            }
+        }
+    };
+}
+
+/// Implements [`ApiSchema`] for an enum of another crate whose variants carry data, listing its
+/// variants the way the derive does.
+macro_rules! remote_opaque_enum {
+    ($ty:ty as $name:literal [$($variant:literal),+ $(,)?]) => {
+        impl ApiSchema for $ty {
+            fn api_schema(components: &mut ApiComponents) -> Value {
+                components.register::<Self>($name, |_| opaque_enum(&[$($variant),+], None))
+            }
+        }
+    };
+}
+
+remote_string_enum!(dal::ActionKind as "ActionKind" ["create", "delete", "other", "refresh"]);
+
+remote_object! {
+    dal::ActionPrototypeView as "ActionPrototypeView" {
+        "id": dal::ActionPrototypeId,
+        "name": String,
+        "displayName": Option<String> = optional,
+    }
+}
+
+remote_opaque_enum!(dal::ActorView as "ActorView" ["apiToken", "system", "user"]);
+
+remote_object! {
+    dal::ApiToken as "ApiToken" {
+        "pk": dal::ApiTokenPk,
+        "workspace_pk": dal::WorkspacePk,
+        "created_by_user_pk": dal::UserPk,
+        "name": String,
+        "role": dal::WorkspaceRole,
+        "expires_at": Option<chrono::DateTime<chrono::Utc>> = optional,
+        "revoked_at": Option<chrono::DateTime<chrono::Utc>> = optional,
+    } flatten [dal::Timestamp]
+}
+
+remote_opaque_enum!(
+    dal::AttributeValueLocation as "AttributeValueLocation" [
+        "externalProvider",
+        "internalProvider",
+        "prop",
+    ]
+);
+
+remote_object! {
+    dal::AttributeValueProvenance as "AttributeValueProvenance" {
+        "attributeValueId": dal::AttributeValueId,
+        "componentId": dal::ComponentId,
+        "componentName": Option<String> = optional,
+        "location": dal::AttributeValueLocation,
+        "value": Option<serde_json::Value> = optional,
+        "source": dal::AttributeValueProvenanceSource,
+    }
+}
+
+remote_opaque_enum!(
+    dal::AttributeValueProvenanceSource as "AttributeValueProvenanceSource" [
+        "computed",
+        "default",
+        "manual",
+        "parentFunc",
+        "prop",
+        "repeated",
+        "unset",
+    ]
+);
+
+remote_object! {
+    dal::ChangeSet as "ChangeSet" {
+        "pk": dal::ChangeSetPk,
+        "name": String,
+        "note": Option<String> = optional,
+        "status": dal::ChangeSetStatus,
+    } flatten [dal::Tenancy, dal::Timestamp]
+}
+
+remote_string_enum!(
+    dal::ChangeSetStatus as "ChangeSetStatus" [
+        "Abandoned",
+        "Applied",
+        "Closed",
+        "Failed",
+        "Open",
+    ]
+);
+
+remote_string_enum!(dal::CodeLanguage as "CodeLanguage" ["diff", "json", "unknown", "yaml"]);
+
+remote_object! {
+    dal::CodeView as "CodeView" {
+        "language": dal::CodeLanguage,
+        "code": Option<String> = optional,
+    }
+}
+
+remote_object! {
+    dal::ComponentQueryMatch as "ComponentQueryMatch" {
+        "componentId": dal::ComponentId,
+        "values": BTreeMap<String, serde_json::Value>,
+    }
+}
+
+remote_opaque_enum!(
+    dal::ComponentSelection as "ComponentSelection" [
+        "components",
+        "schemaVariant",
+    ]
+);
+
+remote_string_enum!(
+    dal::ComponentTagInheritance as "ComponentTagInheritance" [
+        "children",
+        "descendants",
+        "none",
+    ]
+);
+
+remote_object! {
+    dal::ComponentTemplateParameter as "ComponentTemplateParameter" {
+        "name": String,
+        "description": Option<String> = optional,
+        "defaultValue": Option<serde_json::Value> = optional,
+    }
+}
+
+remote_string_enum!(
+    dal::ComponentType as "ComponentType" [
+        "aggregationFrame",
+        "component",
+        "configurationFrame",
+    ]
+);
+
+remote_object! {
+    dal::Connection as "Connection" {
+        "id": dal::edge::EdgeId,
+        "classification": dal::edge::EdgeKind,
+        "source": dal::diagram::connection::Vertex,
+        "destination": dal::diagram::connection::Vertex,
+        "createdBy": Option<dal::User> = optional,
+        "deletedBy": Option<dal::User> = optional,
+    }
+}
+
+remote_object! {
+    dal::Diagram as "Diagram" {
+        "components": Vec<dal::diagram::node::DiagramComponentView>,
+        "edges": Vec<dal::DiagramEdgeView>,
+    }
+}
+
+remote_object! {
+    dal::DiagramEdgeView as "DiagramEdgeView" {
+        "id": String,
+        "fromNodeId": String,
+        "fromSocketId": String,
+        "toNodeId": String,
+        "toSocketId": String,
+        "changeStatus": dal::change_status::ChangeStatus,
+        "createdInfo": Option<dal::diagram::node::HistoryEventMetadata> = optional,
+        "deletedInfo": Option<dal::diagram::node::HistoryEventMetadata> = optional,
+    }
+}
+
+remote_string_enum!(dal::DiagramKind as "DiagramKind" ["configuration"]);
+
+remote_object! {
+    dal::DuplicatedComponent as "DuplicatedComponent" {
+        "originalComponentId": dal::ComponentId,
+        "componentId": dal::ComponentId,
+        "nodeId": dal::NodeId,
+        "parentNodeId": Option<dal::NodeId> = optional,
+    }
+}
+
+remote_object! {
+    dal::ExternalProvider as "ExternalProvider" {
+        "pk": dal::provider::external::ExternalProviderPk,
+        "id": dal::ExternalProviderId,
+        "schema_id": dal::SchemaId,
+        "schema_variant_id": dal::SchemaVariantId,
+        "attribute_prototype_id": Option<dal::AttributePrototypeId> = optional,
+        "name": String,
+        "type_definition": Option<String> = optional,
+    } flatten [dal::Tenancy, dal::Visibility, dal::Timestamp]
+}
+
+remote_string_enum!(
+    dal::FixCompletionStatus as "FixCompletionStatus" [
+        "error",
+        "failure",
+        "success",
+        "unstarted",
+    ]
+);
+
+remote_opaque_enum!(dal::HistoryActor as "HistoryActor" ["ApiToken", "SystemInit", "User"]);
+
+remote_object! {
+    dal::HistoryEvent as "HistoryEvent" {
+        "pk": dal::HistoryEventPk,
+        "label": String,
+        "actor": dal::HistoryActor,
+        "message": String,
+        "data": serde_json::Value,
+    } flatten [dal::Tenancy, dal::Timestamp]
+}
+
+remote_object! {
+    dal::HistoryEventPage as "HistoryEventPage" {
+        "events": Vec<dal::HistoryEvent>,
+        "nextCursor": Option<dal::HistoryEventPk> = optional,
+    }
+}
+
+remote_string_enum!(dal::ImportSource as "ImportSource" ["cloudFormation", "terraform"]);
+
+remote_object! {
+    dal::InternalProvider as "InternalProvider" {
+        "pk": dal::provider::internal::InternalProviderPk,
+        "id": dal::InternalProviderId,
+        "prop_id": dal::PropId,
+        "schema_variant_id": dal::SchemaVariantId,
+        "attribute_prototype_id": Option<dal::AttributePrototypeId> = optional,
+        "name": String,
+        "inbound_type_definition": Option<String> = optional,
+        "outbound_type_definition": Option<String> = optional,
+    } flatten [dal::Tenancy, dal::Visibility, dal::Timestamp]
+}
+
+remote_object! {
+    dal::Manifest as "Manifest" {
+        "components": Vec<dal::manifest::ManifestComponent> = optional,
+        "connections": Vec<dal::manifest::ManifestConnection> = optional,
+    }
+}
+
+remote_opaque_enum!(
+    dal::ManifestChange as "ManifestChange" [
+        "createComponent",
+        "setValue",
+        "createConnection",
+        "deleteConnection",
+        "deleteComponent",
+    ]
+);
+
+remote_object! {
+    dal::ManifestPlan as "ManifestPlan" {
+        "changes": Vec<dal::ManifestChange>,
+        "components": BTreeMap<String, dal::ComponentId>,
+    }
+}
+
+remote_object! {
+    dal::Node as "Node" {
+        "pk": dal::node::NodePk,
+        "id": dal::NodeId,
+        "kind": dal::NodeKind,
+        "x": String,
+        "y": String,
+        "width": Option<String> = optional,
+        "height": Option<String> = optional,
+    } flatten [dal::Tenancy, dal::Timestamp, dal::Visibility]
+}
+
+remote_string_enum!(dal::NodeKind as "NodeKind" ["configuration"]);
+
+remote_string_enum!(
+    dal::PropKind as "PropKind" [
+        "array",
+        "boolean",
+        "integer",
+        "map",
+        "object",
+        "string",
+    ]
+);
+
+remote_object! {
+    dal::PublicKey as "PublicKey" {
+        "pk": dal::key_pair::KeyPairPk,
+        "name": String,
+        "public_key": String,
+        "created_lamport_clock": u64,
+    } flatten [dal::Timestamp]
+}
+
+remote_object! {
+    dal::QualificationView as "QualificationView" {
+        "title": String,
+        "output": Vec<dal::qualification::QualificationOutputStreamView>,
+        "description": Option<String> = optional,
+        "link": Option<String> = optional,
+        "result": Option<dal::qualification::QualificationResult> = optional,
+        "qualificationName": String,
+    }
+}
+
+remote_object! {
+    dal::ResourceImportPlan as "ResourceImportPlan" {
+        "manifest": dal::Manifest,
+        "plan": dal::ManifestPlan,
+        "skipped": Vec<dal::resource_import::SkippedResource>,
+        "payloads": BTreeMap<String, serde_json::Value>,
+    }
+}
+
+remote_object! {
+    dal::ResourceView as "ResourceView" {
+        "status": veritech_client::ResourceStatus,
+        "message": Option<String> = optional,
+        "data": Option<serde_json::Value> = optional,
+        "logs": Vec<String>,
+        "lastSynced": Option<String> = optional,
+    }
+}
+
+remote_object! {
+    dal::Schema as "Schema" {
+        "pk": dal::SchemaPk,
+        "id": dal::SchemaId,
+        "name": String,
+        "ui_hidden": bool,
+        "default_schema_variant_id": Option<dal::SchemaVariantId> = optional,
+        "component_kind": dal::component::ComponentKind,
+    } flatten [dal::Tenancy, dal::Timestamp, dal::Visibility]
+}
+
+remote_object! {
+    dal::Tenancy as "Tenancy" {
+        "tenancy_workspace_pk": Option<dal::WorkspacePk> = optional,
+    }
+}
+
+remote_object! {
+    dal::Timestamp as "Timestamp" {
+        "created_at": chrono::DateTime<chrono::Utc>,
+        "updated_at": chrono::DateTime<chrono::Utc>,
+    }
+}
+
+remote_object! {
+    dal::User as "User" {
+        "pk": dal::UserPk,
+        "name": String,
+        "email": String,
+        "picture_url": Option<String> = optional,
+    } flatten [dal::Timestamp]
+}
+
+remote_object! {
+    dal::Webhook as "Webhook" {
+        "pk": dal::WebhookPk,
+        "workspace_pk": dal::WorkspacePk,
+        "url": String,
+        "event_kinds": Vec<dal::WebhookEventKind>,
+        "enabled": bool,
+    } flatten [dal::Timestamp]
+}
+
+remote_object! {
+    dal::WebhookDelivery as "WebhookDelivery" {
+        "pk": dal::WebhookDeliveryPk,
+        "webhook_pk": dal::WebhookPk,
+        "event_kind": dal::WebhookEventKind,
+        "payload": serde_json::Value,
+        "status": dal::WebhookDeliveryStatus,
+        "attempts": i32,
+        "last_status_code": Option<i32> = optional,
+        "last_error": Option<String> = optional,
+        "next_attempt_at": chrono::DateTime<chrono::Utc>,
+        "delivered_at": Option<chrono::DateTime<chrono::Utc>> = optional,
+    } flatten [dal::Timestamp]
+}
+
+remote_string_enum!(
+    dal::WebhookDeliveryStatus as "WebhookDeliveryStatus" [
+        "failed",
+        "pending",
+        "succeeded",
+    ]
+);
+
+remote_string_enum!(
+    dal::WebhookEventKind as "WebhookEventKind" [
+        "changeSetApplied",
+        "checkedQualifications",
+        "fixBatchReturn",
+        "ping",
+        "resourceRefreshed",
+    ]
+);
+
+remote_object! {
+    dal::Workspace as "Workspace" {
+        "pk": dal::WorkspacePk,
+        "name": String,
+    } flatten [dal::Timestamp]
+}
+
+remote_object! {
+    dal::WorkspaceMember as "WorkspaceMember" {
+        "user": dal::User,
+        "role": dal::WorkspaceRole,
+    }
+}
+
+remote_string_enum!(
+    dal::WorkspaceRole as "WorkspaceRole" [
+        "approver",
+        "editor",
+        "owner",
+        "viewer",
+    ]
+);
+
+remote_string_enum!(
+    dal::change_status::ChangeStatus as "ChangeStatus" [
+        "added",
+        "deleted",
+        "modified",
+        "unmodified",
+    ]
+);
+
+remote_object! {
+    dal::change_status::ComponentChangeStatus as "ComponentChangeStatus" {
+        "stats": Vec<dal::change_status::ComponentChangeStatusGroup>,
+    }
+}
+
+remote_object! {
+    dal::change_status::ComponentChangeStatusGroup as "ComponentChangeStatusGroup" {
+        "componentId": dal::ComponentId,
+        "componentName": String,
+        "componentStatus": dal::change_status::ChangeStatus,
+    }
+}
+
+remote_string_enum!(dal::component::ComponentKind as "ComponentKind" ["credential", "standard"]);
+
+remote_object! {
+    dal::component::diff::ComponentDiff as "ComponentDiff" {
+        "component_id": dal::ComponentId,
+        "current": dal::CodeView,
+        "diffs": Vec<dal::CodeView>,
+    }
+}
+
+remote_object! {
+    dal::diagram::connection::Vertex as "Vertex" {
+        "nodeId": dal::NodeId,
+        "socketId": dal::SocketId,
+    }
+}
+
+remote_object! {
+    dal::diagram::node::DiagramComponentView as "DiagramComponentView" {
+        "id": dal::ComponentId,
+        "nodeId": dal::NodeId,
+        "displayName": Option<String> = optional,
+        "parentNodeId": Option<dal::NodeId> = optional,
+        "childNodeIds": Vec<dal::NodeId>,
+        "schemaName": String,
+        "schemaId": String,
+        "schemaVariantId": String,
+        "schemaVariantName": String,
+        "schemaCategory": Option<String> = optional,
+        "actions": Vec<dal::ActionPrototypeView>,
+        "sockets": Option<Vec<dal::diagram::node::SocketView>> = optional,
+        "position": dal::diagram::node::GridPoint,
+        "size": Option<dal::diagram::node::Size2D> = optional,
+        "color": Option<String> = optional,
+        "nodeType": dal::ComponentType,
+        "changeStatus": dal::change_status::ChangeStatus,
+        "resource": dal::ResourceView,
+        "tags": BTreeMap<String, String>,
+        "createdInfo": dal::diagram::node::HistoryEventMetadata,
+        "updatedInfo": dal::diagram::node::HistoryEventMetadata,
+        "deletedInfo": Option<dal::diagram::node::HistoryEventMetadata> = optional,
+    }
+}
+
+remote_object! {
+    dal::diagram::node::GridPoint as "GridPoint" {
+        "x": isize,
+        "y": isize,
+    }
+}
+
+remote_object! {
+    dal::diagram::node::HistoryEventMetadata as "HistoryEventMetadata" {
+        "actor": dal::ActorView,
+        "timestamp": chrono::DateTime<chrono::Utc>,
+    }
+}
+
+remote_string_enum!(dal::diagram::node::NodeSide as "NodeSide" ["left", "right"]);
+
+remote_object! {
+    dal::diagram::node::Size2D as "Size2D" {
+        "width": isize,
+        "height": isize,
+    }
+}
+
+remote_string_enum!(
+    dal::diagram::node::SocketDirection as "SocketDirection" [
+        "bidirectional",
+        "input",
+        "output",
+    ]
+);
+
+remote_object! {
+    dal::diagram::node::SocketView as "SocketView" {
+        "id": String,
+        "label": String,
+        "type": String,
+        "direction": dal::diagram::node::SocketDirection,
+        "maxConnections": Option<usize> = optional,
+        "isRequired": Option<bool> = optional,
+        "nodeSide": dal::diagram::node::NodeSide,
+    }
+}
+
+remote_string_enum!(dal::edge::EdgeKind as "EdgeKind" ["configuration", "symbolic"]);
+
+remote_object! {
+    dal::fix::FixHistoryView as "FixHistoryView" {
+        "id": dal::FixId,
+        "status": dal::FixCompletionStatus,
+        "actionKind": dal::ActionKind,
+        "displayName": String,
+        "schemaName": String,
+        "componentName": String,
+        "componentId": dal::ComponentId,
+        "provider": Option<String> = optional,
+        "startedAt": Option<String> = optional,
+        "finishedAt": Option<String> = optional,
+        "resource": Option<dal::ResourceView> = optional,
+    }
+}
+
+remote_object! {
+    dal::func::backend::js_reconciliation::ReconciliationDiff as "ReconciliationDiff" {
+        "normalizedResource": Option<serde_json::Value> = optional,
+        "resource": serde_json::Value,
+        "domain": dal::func::backend::js_reconciliation::ReconciliationDiffDomain,
+    }
+}
+
+remote_object! {
+    dal::func::backend::js_reconciliation::ReconciliationDiffDomain as "ReconciliationDiffDomain" {
+        "id": dal::AttributeValueId,
+        "value": serde_json::Value,
+    }
+}
+
+remote_object! {
+    dal::func::backend::js_reconciliation::ReconciliationResult as "ReconciliationResult" {
+        "updates": HashMap<dal::AttributeValueId, serde_json::Value>,
+        "actions": Vec<String>,
+        "message": Option<String> = optional,
+    }
+}
+
+remote_string_enum!(
+    dal::func::execution::FuncExecutionState as "FuncExecutionState" [
+        "Create",
+        "Dispatch",
+        "Failure",
+        "Run",
+        "Start",
+        "Success",
+    ]
+);
+
+remote_object! {
+    dal::manifest::ManifestComponent as "ManifestComponent" {
+        "name": String,
+        "schemaName": String,
+        "schemaVariantName": Option<String> = optional,
+        "x": i64 = optional,
+        "y": i64 = optional,
+        "values": serde_json::Map<String, serde_json::Value> = optional,
+    }
+}
+
+remote_object! {
+    dal::manifest::ManifestConnection as "ManifestConnection" {
+        "fromComponent": String,
+        "fromSocket": String,
+        "toComponent": String,
+        "toSocket": String,
+    }
+}
+
+remote_object! {
+    property_editor::schema::PropertyEditorProp as "PropertyEditorProp" {
+        "id": PropertyEditorPropId,
+        "name": String,
+        "kind": property_editor::schema::PropertyEditorPropKind,
+        "widgetKind": property_editor::schema::PropertyEditorPropWidgetKind,
+        "docLink": Option<String> = optional,
+    }
+}
+
+remote_string_enum!(
+    property_editor::schema::PropertyEditorPropKind as "PropertyEditorPropKind" [
+        "array",
+        "boolean",
+        "integer",
+        "map",
+        "object",
+        "string",
+    ]
+);
+
+remote_opaque_enum!(
+    property_editor::schema::PropertyEditorPropWidgetKind as "PropertyEditorPropWidgetKind" [
+        "array",
+        "checkbox",
+        "color",
+        "comboBox",
+        "header",
+        "map",
+        "secret",
+        "select",
+        "text",
+        "textArea",
+    ]
+);
+
+remote_object! {
+    property_editor::schema::PropertyEditorSchema as "PropertyEditorSchema" {
+        "rootPropId": PropertyEditorPropId,
+        "props": HashMap<PropertyEditorPropId, property_editor::schema::PropertyEditorProp>,
+        "childProps": HashMap<PropertyEditorPropId, Vec<PropertyEditorPropId>>,
+    }
+}
+
+remote_object! {
+    property_editor::validations::PropertyEditorValidation as "PropertyEditorValidation" {
+        "valueId": PropertyEditorValueId,
+        "valid": bool,
+        "errors": Vec<property_editor::validations::PropertyEditorValidationError>,
+    }
+}
+
+remote_object! {
+    property_editor::validations::PropertyEditorValidationError as "PropertyEditorValidationError" {
+        "message": String,
+        "level": Option<String> = optional,
+        "kind": Option<String> = optional,
+        "link": Option<String> = optional,
+    }
+}
+
+remote_object! {
+    property_editor::validations::PropertyEditorValidations as "PropertyEditorValidations" {
+        "validations": Vec<property_editor::validations::PropertyEditorValidation>,
+    }
+}
+
+remote_object! {
+    property_editor::values::PropertyEditorValue as "PropertyEditorValue" {
+        "id": PropertyEditorValueId,
+        "propId": PropertyEditorPropId,
+        "key": Option<String> = optional,
+        "value": serde_json::Value,
+        "isFromExternalSource": bool,
+        "isOverridden": bool,
+        "isLocked": bool,
+    }
+}
+
+remote_object! {
+    property_editor::values::PropertyEditorValues as "PropertyEditorValues" {
+        "rootValueId": PropertyEditorValueId,
+        "values": HashMap<PropertyEditorValueId, property_editor::values::PropertyEditorValue>,
+        "childValues": HashMap<PropertyEditorValueId, Vec<PropertyEditorValueId>>,
+    }
+}
+
+remote_object! {
+    dal::qualification::QualificationOutputStreamView as "QualificationOutputStreamView" {
+        "stream": String,
+        "line": String,
+        "level": String,
+    }
+}
+
+remote_object! {
+    dal::qualification::QualificationResult as "QualificationResult" {
+        "status": dal::qualification::QualificationSubCheckStatus,
+        "title": Option<String> = optional,
+        "link": Option<String> = optional,
+        "sub_checks": Vec<dal::qualification::QualificationSubCheck>,
+    }
+}
+
+remote_object! {
+    dal::qualification::QualificationSubCheck as "QualificationSubCheck" {
+        "description": String,
+        "status": dal::qualification::QualificationSubCheckStatus,
+    }
+}
+
+remote_string_enum!(
+    dal::qualification::QualificationSubCheckStatus as "QualificationSubCheckStatus" [
+        "failure",
+        "success",
+        "unknown",
+        "warning",
+    ]
+);
+
+remote_object! {
+    dal::qualification::QualificationSummary as "QualificationSummary" {
+        "total": i64,
+        "succeeded": i64,
+        "warned": i64,
+        "failed": i64,
+        "components": Vec<dal::qualification::QualificationSummaryForComponent>,
+    }
+}
+
+remote_object! {
+    dal::qualification::QualificationSummaryForComponent as "QualificationSummaryForComponent" {
+        "componentId": dal::ComponentId,
+        "componentName": String,
+        "total": i64,
+        "warned": i64,
+        "succeeded": i64,
+        "failed": i64,
+    }
+}
+
+remote_object! {
+    dal::resource_import::SkippedResource as "SkippedResource" {
+        "address": String,
+        "resourceType": String,
+    }
+}
+
+remote_object! {
+    dal::resource_import::mapping::ImportAttributeMapping as "ImportAttributeMapping" {
+        "source": String,
+        "path": Vec<String>,
+    }
+}
+
+remote_object! {
+    dal::status::StatusUpdateData as "StatusUpdateData" {
+        "actor": dal::ActorView,
+        "dependent_values_metadata": HashMap<dal::AttributeValueId, AttributeValueMetadata>,
+        "queued_dependent_value_ids": HashSet<dal::AttributeValueId>,
+        "running_dependent_value_ids": HashSet<dal::AttributeValueId>,
+        "completed_dependent_value_ids": HashSet<dal::AttributeValueId>,
+    }
+}
+
+remote_object! {
+    si_pkg::ActionFuncSpec as "ActionFuncSpec" {
+        "funcUniqueId": String,
+        "name": Option<String> = optional,
+        "kind": si_pkg::ActionFuncSpecKind,
+        "uniqueId": Option<String> = optional,
+        "deleted": bool = optional,
+    }
+}
+
+remote_string_enum!(
+    si_pkg::ActionFuncSpecKind as "ActionFuncSpecKind" [
+        "create",
+        "refresh",
+        "other",
+        "delete",
+    ]
+);
+
+remote_opaque_enum!(
+    si_pkg::AttrFuncInputSpec as "AttrFuncInputSpec" [
+        "inputSocket",
+        "outputSocket",
+        "prop",
+    ]
+);
+
+remote_object! {
+    si_pkg::ChangeSetSpec as "ChangeSetSpec" {
+        "name": String,
+        "basedOnChangeSet": Option<String> = optional,
+        "status": si_pkg::ChangeSetSpecStatus,
+        "schemas": Vec<si_pkg::SchemaSpec> = optional,
+        "funcs": Vec<si_pkg::FuncSpec> = optional,
+    }
+}
+
+remote_string_enum!(
+    si_pkg::ChangeSetSpecStatus as "ChangeSetSpecStatus" [
+        "Abandoned",
+        "Applied",
+        "Closed",
+        "Failed",
+        "Open",
+    ]
+);
+
+remote_object! {
+    si_pkg::ComponentTemplateSpec as "ComponentTemplateSpec" {
+        "name": String,
+        "description": Option<String> = optional,
+        "definition": serde_json::Value,
+    }
+}
+
+remote_string_enum!(
+    si_pkg::FuncArgumentKind as "FuncArgumentKind" [
+        "any",
+        "array",
+        "boolean",
+        "integer",
+        "map",
+        "object",
+        "string",
+    ]
+);
+
+remote_object! {
+    si_pkg::FuncArgumentSpec as "FuncArgumentSpec" {
+        "name": String,
+        "kind": si_pkg::FuncArgumentKind,
+        "elementKind": Option<si_pkg::FuncArgumentKind> = optional,
+        "uniqueId": Option<String> = optional,
+        "deleted": bool = optional,
+    }
+}
+
+remote_object! {
+    si_pkg::FuncSpec as "FuncSpec" {
+        "name": String,
+        "uniqueId": String,
+        "data": Option<si_pkg::FuncSpecData> = optional,
+        "deleted": bool = optional,
+        "arguments": Vec<si_pkg::FuncArgumentSpec>,
+    }
+}
+
+remote_string_enum!(
+    si_pkg::FuncSpecBackendKind as "FuncSpecBackendKind" [
+        "array",
+        "boolean",
+        "diff",
+        "expression",
+        "identity",
+        "integer",
+        "jsAction",
+        "jsAttribute",
+        "jsReconciliation",
+        "jsSchemaVariantDefinition",
+        "jsValidation",
+        "map",
+        "object",
+        "string",
+        "unset",
+        "validation",
+    ]
+);
+
+remote_string_enum!(
+    si_pkg::FuncSpecBackendResponseType as "FuncSpecBackendResponseType" [
+        "action",
+        "array",
+        "boolean",
+        "codeGeneration",
+        "identity",
+        "integer",
+        "json",
+        "map",
+        "object",
+        "qualification",
+        "reconciliation",
+        "schemaVariantDefinition",
+        "string",
+        "unset",
+        "validation",
+    ]
+);
+
+remote_object! {
+    si_pkg::FuncSpecData as "FuncSpecData" {
+        "name": String,
+        "displayName": Option<String> = optional,
+        "description": Option<String> = optional,
+        "handler": String,
+        "codeBase64": String,
+        "backendKind": si_pkg::FuncSpecBackendKind,
+        "responseType": si_pkg::FuncSpecBackendResponseType,
+        "hidden": bool,
+        "link": Option<url::Url> = optional,
+    }
+}
+
+remote_object! {
+    si_pkg::LeafFunctionSpec as "LeafFunctionSpec" {
+        "funcUniqueId": String,
+        "leafKind": si_pkg::LeafKind,
+        "uniqueId": Option<String> = optional,
+        "deleted": bool = optional,
+        "inputs": Vec<si_pkg::LeafInputLocation>,
+    }
+}
+
+remote_string_enum!(
+    si_pkg::LeafInputLocation as "LeafInputLocation" [
+        "code",
+        "deletedAt",
+        "domain",
+        "resource",
+    ]
+);
+
+remote_string_enum!(si_pkg::LeafKind as "LeafKind" ["codeGeneration", "qualification"]);
+
+remote_object! {
+    si_pkg::PkgSpec as "PkgSpec" {
+        "kind": si_pkg::SiPkgKind,
+        "name": String,
+        "version": String,
+        "description": String,
+        "createdAt": chrono::DateTime<chrono::Utc>,
+        "createdBy": String,
+        "defaultChangeSet": Option<String> = optional,
+        "workspacePk": Option<String> = optional,
+        "workspaceName": Option<String> = optional,
+        "schemas": Vec<si_pkg::SchemaSpec> = optional,
+        "funcs": Vec<si_pkg::FuncSpec> = optional,
+        "changeSets": Vec<si_pkg::ChangeSetSpec> = optional,
+        "componentTemplates": Vec<si_pkg::ComponentTemplateSpec> = optional,
+    }
+}
+
+remote_opaque_enum!(
+    si_pkg::PropSpec as "PropSpec" [
+        "array",
+        "boolean",
+        "map",
+        "number",
+        "object",
+        "string",
+    ]
+);
+
+remote_object! {
+    si_pkg::SchemaSpec as "SchemaSpec" {
+        "name": String,
+        "data": Option<si_pkg::SchemaSpecData> = optional,
+        "uniqueId": Option<String> = optional,
+        "deleted": bool = optional,
+        "variants": Vec<si_pkg::SchemaVariantSpec>,
+    }
+}
+
+remote_object! {
+    si_pkg::SchemaSpecData as "SchemaSpecData" {
+        "name": String,
+        "category": String,
+        "categoryName": Option<String> = optional,
+        "uiHidden": bool,
+        "defaultSchemaVariant": Option<String> = optional,
+    }
+}
+
+remote_object! {
+    si_pkg::SchemaVariantSpec as "SchemaVariantSpec" {
+        "name": String,
+        "data": Option<si_pkg::SchemaVariantSpecData> = optional,
+        "uniqueId": Option<String> = optional,
+        "deleted": bool = optional,
+        "actionFuncs": Vec<si_pkg::ActionFuncSpec>,
+        "leafFunctions": Vec<si_pkg::LeafFunctionSpec>,
+        "sockets": Vec<si_pkg::SocketSpec>,
+        "siPropFuncs": Vec<si_pkg::SiPropFuncSpec>,
+        "domain": si_pkg::PropSpec,
+        "secrets": si_pkg::PropSpec,
+        "secretDefinition": Option<si_pkg::PropSpec> = optional,
+        "resourceValue": si_pkg::PropSpec,
+    }
+}
+
+remote_string_enum!(
+    si_pkg::SchemaVariantSpecComponentType as "SchemaVariantSpecComponentType" [
+        "aggregationFrame",
+        "component",
+        "configurationFrame",
+    ]
+);
+
+remote_object! {
+    si_pkg::SchemaVariantSpecData as "SchemaVariantSpecData" {
+        "name": String,
+        "link": Option<url::Url> = optional,
+        "color": Option<String> = optional,
+        "componentType": si_pkg::SchemaVariantSpecComponentType,
+        "funcUniqueId": String,
+    }
+}
+
+remote_string_enum!(si_pkg::SiPkgKind as "SiPkgKind" ["module", "workspaceBackup"]);
+
+remote_object! {
+    si_pkg::SiPropFuncSpec as "SiPropFuncSpec" {
+        "kind": si_pkg::SiPropFuncSpecKind,
+        "funcUniqueId": String,
+        "uniqueId": Option<String> = optional,
+        "deleted": bool = optional,
+        "inputs": Vec<si_pkg::AttrFuncInputSpec>,
+    }
+}
+
+remote_string_enum!(
+    si_pkg::SiPropFuncSpecKind as "SiPropFuncSpecKind" [
+        "color",
+        "name",
+        "resourcePayload",
+    ]
+);
+
+remote_object! {
+    si_pkg::SocketSpec as "SocketSpec" {
+        "name": String,
+        "data": Option<si_pkg::SocketSpecData> = optional,
+        "inputs": Vec<si_pkg::AttrFuncInputSpec>,
+        "uniqueId": Option<String> = optional,
+    }
+}
+
+remote_string_enum!(si_pkg::SocketSpecArity as "SocketSpecArity" ["many", "one"]);
+
+remote_object! {
+    si_pkg::SocketSpecData as "SocketSpecData" {
+        "funcUniqueId": Option<String> = optional,
+        "kind": si_pkg::SocketSpecKind,
+        "name": String,
+        "arity": si_pkg::SocketSpecArity,
+        "uiHidden": bool,
+    }
+}
+
+remote_string_enum!(si_pkg::SocketSpecKind as "SocketSpecKind" ["input", "output"]);
+
+remote_object! {
+    veritech_client::OutputStream as "OutputStream" {
+        "stream": String,
+        "execution_id": String,
+        "level": String,
+        "group": Option<String> = optional,
+        "message": String,
+        "timestamp": u64,
+    }
+}
+
+remote_string_enum!(veritech_client::ResourceStatus as "ResourceStatus" ["error", "ok", "warning"]);
+
+remote_object! {
+    module_index_client::WorkspaceBackupResponse as "WorkspaceBackupResponse" {
+        "id": String,
+        "workspacePk": String,
+        "workspaceName": String,
+        "rootHash": String,
+        "parentId": Option<String> = optional,
+        "nodeCount": i32,
+        "newNodeCount": i32,
+        "createdByDisplayName": Option<String> = optional,
+        "createdAt": chrono::DateTime<chrono::Utc>,
+    }
+}
+
+/// The kind of value is a private enum of `dal`, tagged by `kind`, with the id of its prop or
+/// socket, if any, in `id`.
+impl ApiSchema for AttributeValueMetadata {
+    fn api_schema(components: &mut ApiComponents) -> Value {
+        components.register::<Self>("AttributeValueMetadata", |components| {
+            let mut object = ObjectSchema::new(None);
+            let schema: SchemaFn = schema_of!(dal::AttributeValueId);
+            let schema = schema(components);
+            object.property("valueId", schema, true, None);
+            let schema: SchemaFn = schema_of!(dal::ComponentId);
+            let schema = schema(components);
+            object.property("componentId", schema, true, None);
+            object.property(
+                "valueKind",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "kind": {
+                            "type": "string",
+                            "enum": [
+                                "attribute",
+                                "codeGen",
+                                "inputSocket",
+                                "internal",
+                                "outputSocket",
+                                "qualification",
+                            ],
+                        },
+                        "id": { "type": "string" },
+                    },
+                    "required": ["kind"],
+                }),
+                true,
+                None,
+            );
+            object.into_value()
+        })
+    }
+}
+
+/// The error is a type of `cyclone-core`, which sdf doesn't depend on.
+impl ApiSchema for veritech_client::FunctionResultFailure {
+    fn api_schema(components: &mut ApiComponents) -> Value {
+        components.register::<Self>("FunctionResultFailure", |components| {
+            let mut object = ObjectSchema::new(None);
+            object.property("execution_id", json!({ "type": "string" }), true, None);
+            object.property(
+                "error",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "kind": { "type": "string" },
+                        "message": { "type": "string" },
+                    },
+                    "required": ["kind", "message"],
+                }),
+                true,
+                None,
+            );
+            let schema: SchemaFn = schema_of!(u64);
+            let schema = schema(components);
+            object.property("timestamp", schema, true, None);
+            object.into_value()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::type_name;
+
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::Map;
+
+    use super::*;
+
+    const ULID: &str = "01H9ZQCBJ3E6M0YVDQ0DTH5SJ3";
+
+    /// Values for the enums that are only described by the names of their variants.
+    fn opaque_sample(name: &str) -> Option<Value> {
+        Some(match name {
+            "ActorView" => json!({ "kind": "system", "label": "System Initiative" }),
+            "AttrFuncInputSpec" => json!({
+                "kind": "prop",
+                "name": "identity",
+                "prop_path": "/root/si/name",
+                "unique_id": null,
+                "deleted": false,
+            }),
+            "AttributeValueLocation" => json!({
+                "kind": "externalProvider",
+                "externalProviderId": ULID,
+                "name": "bethesda",
+            }),
+            "AttributeValueProvenanceSource" => json!({ "kind": "unset" }),
+            "ComponentSelection" => json!({ "kind": "components", "componentIds": [ULID] }),
+            "HistoryActor" => json!("SystemInit"),
+            "ManifestChange" => json!({
+                "kind": "deleteComponent",
+                "name": "stray",
+                "componentId": ULID,
+            }),
+            "PropSpec" => json!({
+                "kind": "boolean",
+                "name": "enabled",
+                "data": null,
+                "uniqueId": null,
+            }),
+            "PropertyEditorPropWidgetKind" => json!({ "kind": "text" }),
+            _ => return None,
+        })
+    }
+
+    fn reference_name(schema: &Value) -> Option<&str> {
+        schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|path| path.strip_prefix("#/components/schemas/"))
+    }
+
+    /// Follows references and merges `allOf`, keeping whether the schema is nullable.
+    fn resolved(components: &ApiComponents, schema: &Value) -> Value {
+        let mut schema = components.resolve(schema).clone();
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array).cloned() {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            let mut merged = Value::Null;
+            for part in &all_of {
+                let part = resolved(components, part);
+                match part.get("properties").and_then(Value::as_object) {
+                    Some(part_properties) => {
+                        properties.extend(part_properties.clone());
+                        if let Some(part_required) = part.get("required").and_then(Value::as_array)
+                        {
+                            required.extend(part_required.iter().cloned());
+                        }
+                    }
+                    None => merged = part,
+                }
+            }
+            if !properties.is_empty() {
+                merged =
+                    json!({ "type": "object", "properties": properties, "required": required });
+            }
+            if schema.get("nullable") == Some(&json!(true)) {
+                merged["nullable"] = json!(true);
+            }
+            schema = merged;
+        }
+        schema
+    }
+
+    /// The variants listed by the description of an opaque enum.
+    fn opaque_variants(schema: &Value) -> Option<Vec<&str>> {
+        let description = schema.get("description").and_then(Value::as_str)?;
+        let variants = description.rsplit("One of: ").next()?.strip_suffix('.')?;
+        Some(variants.split(", ").collect())
+    }
+
+    /// Builds a value matching the schema, with every property set and one element in every
+    /// array.
+    fn sample(components: &ApiComponents, schema: &Value) -> Value {
+        let unwrapped = match schema.get("allOf").and_then(Value::as_array) {
+            Some(all_of) if all_of.len() == 1 => &all_of[0],
+            _ => schema,
+        };
+        if let Some(sample) = reference_name(unwrapped).and_then(opaque_sample) {
+            return sample;
+        }
+
+        let schema = resolved(components, schema);
+        if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+            return variants.first().cloned().unwrap_or_default();
+        }
+        match schema.get("type").and_then(Value::as_str) {
+            Some("object") => match schema.get("properties").and_then(Value::as_object) {
+                Some(properties) => Value::Object(
+                    properties
+                        .iter()
+                        .map(|(name, property)| (name.clone(), sample(components, property)))
+                        .collect(),
+                ),
+                None => json!({}),
+            },
+            Some("array") => json!([sample(components, &schema["items"])]),
+            Some("string") => match schema.get("format").and_then(Value::as_str) {
+                Some("date-time") => json!("2023-09-01T12:00:00Z"),
+                Some("uri") => json!("https://systeminit.com/"),
+                _ => json!(ULID),
+            },
+            Some("integer") => json!(1),
+            Some("number") => json!(1.5),
+            Some("boolean") => json!(true),
+            _ => Value::Null,
+        }
+    }
+
+    /// Checks a serialized value against the schema, collecting what doesn't match.
+    fn check(
+        components: &ApiComponents,
+        schema: &Value,
+        value: &Value,
+        location: &str,
+        errors: &mut Vec<String>,
+    ) {
+        let schema = resolved(components, schema);
+        if value.is_null() {
+            if schema.get("type").is_some() && schema.get("nullable") != Some(&json!(true)) {
+                errors.push(format!("{location}: null, but not nullable"));
+            }
+            return;
+        }
+        if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+            if !variants.contains(value) {
+                errors.push(format!("{location}: {value} is not one of {variants:?}"));
+            }
+            return;
+        }
+
+        match schema.get("type").and_then(Value::as_str) {
+            Some("object") => {
+                let object = match value.as_object() {
+                    Some(object) => object,
+                    None => {
+                        errors.push(format!("{location}: {value} is not an object"));
+                        return;
+                    }
+                };
+                if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                    for (key, child) in object {
+                        match properties.get(key) {
+                            Some(property) => check(
+                                components,
+                                property,
+                                child,
+                                &format!("{location}.{key}"),
+                                errors,
+                            ),
+                            None => errors.push(format!("{location}: unexpected key {key}")),
+                        }
+                    }
+                    for required in schema
+                        .get("required")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                    {
+                        if !object.contains_key(required) {
+                            errors.push(format!("{location}: missing key {required}"));
+                        }
+                    }
+                } else if let Some(additional) = schema.get("additionalProperties") {
+                    for (key, child) in object {
+                        check(
+                            components,
+                            additional,
+                            child,
+                            &format!("{location}.{key}"),
+                            errors,
+                        );
+                    }
+                }
+            }
+            Some("array") => match value.as_array() {
+                Some(array) => {
+                    for (index, child) in array.iter().enumerate() {
+                        check(
+                            components,
+                            &schema["items"],
+                            child,
+                            &format!("{location}[{index}]"),
+                            errors,
+                        );
+                    }
+                }
+                None => errors.push(format!("{location}: {value} is not an array")),
+            },
+            Some(kind) => {
+                let matches = match kind {
+                    "string" => value.is_string(),
+                    "integer" => value.is_i64() || value.is_u64(),
+                    "number" => value.is_number(),
+                    "boolean" => value.is_boolean(),
+                    _ => true,
+                };
+                if !matches {
+                    errors.push(format!("{location}: {value} is not a {kind}"));
+                }
+            }
+            None => {
+                if let Some(variants) = opaque_variants(&schema) {
+                    let tag = match value {
+                        Value::String(tag) => Some(tag.as_str()),
+                        Value::Object(object) => match object.get("kind") {
+                            Some(kind) => kind.as_str(),
+                            None if object.len() == 1 => object.keys().next().map(String::as_str),
+                            None => None,
+                        },
+                        _ => None,
+                    };
+                    if !tag.map_or(false, |tag| variants.contains(&tag)) {
+                        errors.push(format!("{location}: {value} is not one of {variants:?}"));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Deserializes samples of the schema of `T` (every variant, for enums), serializes them
+    /// again and checks the result against the schema.
+    fn round_trip<T: ApiSchema + Serialize + DeserializeOwned>(errors: &mut Vec<String>) {
+        let location = type_name::<T>();
+        let mut components = ApiComponents::default();
+        let schema = T::api_schema(&mut components);
+
+        let samples = match resolved(&components, &schema)
+            .get("enum")
+            .and_then(Value::as_array)
+        {
+            Some(variants) => variants.clone(),
+            None => vec![sample(&components, &schema)],
+        };
+        for sample in samples {
+            let value = match serde_json::from_value::<T>(sample.clone()) {
+                Ok(value) => serde_json::to_value(value).expect("could not serialize sample"),
+                Err(err) => {
+                    errors.push(format!("{location}: could not deserialize {sample}: {err}"));
+                    continue;
+                }
+            };
+            if reference_name(&schema).and_then(opaque_sample).is_some() && value != sample {
+                errors.push(format!("{location}: {sample} serialized as {value}"));
+            }
+            check(&components, &schema, &value, location, errors);
+        }
+    }
+
+    macro_rules! round_trip_all {
+        ($errors:expr; $($ty:ty),+ $(,)?) => {
+            $(round_trip::<$ty>($errors);)+
+        };
+    }
+
+    #[test]
+    fn schemas_match_serialization() {
+        let mut errors = Vec::new();
+        // Every type described in this file.
+        round_trip_all!(
+            &mut errors;
+            AttributeValueMetadata,
+            dal::ActionKind,
+            dal::ActionPrototypeView,
+            dal::ActorView,
+            dal::ApiToken,
+            dal::AttributeValueLocation,
+            dal::AttributeValueProvenance,
+            dal::AttributeValueProvenanceSource,
+            dal::ChangeSet,
+            dal::ChangeSetStatus,
+            dal::CodeLanguage,
+            dal::CodeView,
+            dal::ComponentQueryMatch,
+            dal::ComponentSelection,
+            dal::ComponentTagInheritance,
+            dal::ComponentTemplateParameter,
+            dal::ComponentType,
+            dal::Connection,
+            dal::Diagram,
+            dal::DiagramEdgeView,
+            dal::DiagramKind,
+            dal::DuplicatedComponent,
+            dal::ExternalProvider,
+            dal::FixCompletionStatus,
+            dal::HistoryActor,
+            dal::HistoryEvent,
+            dal::HistoryEventPage,
+            dal::ImportSource,
+            dal::InternalProvider,
+            dal::Manifest,
+            dal::ManifestChange,
+            dal::ManifestPlan,
+            dal::Node,
+            dal::NodeKind,
+            dal::PropKind,
+            dal::PublicKey,
+            dal::QualificationView,
+            dal::ResourceImportPlan,
+            dal::ResourceView,
+            dal::Schema,
+            dal::Tenancy,
+            dal::Timestamp,
+            dal::User,
+            dal::Webhook,
+            dal::WebhookDelivery,
+            dal::WebhookDeliveryStatus,
+            dal::WebhookEventKind,
+            dal::Workspace,
+            dal::WorkspaceMember,
+            dal::WorkspaceRole,
+            dal::change_status::ChangeStatus,
+            dal::change_status::ComponentChangeStatus,
+            dal::change_status::ComponentChangeStatusGroup,
+            dal::component::ComponentKind,
+            dal::component::diff::ComponentDiff,
+            dal::diagram::connection::Vertex,
+            dal::diagram::node::DiagramComponentView,
+            dal::diagram::node::GridPoint,
+            dal::diagram::node::HistoryEventMetadata,
+            dal::diagram::node::NodeSide,
+            dal::diagram::node::Size2D,
+            dal::diagram::node::SocketDirection,
+            dal::diagram::node::SocketView,
+            dal::edge::EdgeKind,
+            dal::fix::FixHistoryView,
+            dal::func::backend::js_reconciliation::ReconciliationDiff,
+            dal::func::backend::js_reconciliation::ReconciliationDiffDomain,
+            dal::func::backend::js_reconciliation::ReconciliationResult,
+            dal::func::execution::FuncExecutionState,
+            dal::manifest::ManifestComponent,
+            dal::manifest::ManifestConnection,
+            dal::qualification::QualificationOutputStreamView,
+            dal::qualification::QualificationResult,
+            dal::qualification::QualificationSubCheck,
+            dal::qualification::QualificationSubCheckStatus,
+            dal::qualification::QualificationSummary,
+            dal::qualification::QualificationSummaryForComponent,
+            dal::resource_import::SkippedResource,
+            dal::resource_import::mapping::ImportAttributeMapping,
+            dal::status::StatusUpdateData,
+            module_index_client::WorkspaceBackupResponse,
+            property_editor::schema::PropertyEditorProp,
+            property_editor::schema::PropertyEditorPropKind,
+            property_editor::schema::PropertyEditorPropWidgetKind,
+            property_editor::schema::PropertyEditorSchema,
+            property_editor::validations::PropertyEditorValidation,
+            property_editor::validations::PropertyEditorValidationError,
+            property_editor::validations::PropertyEditorValidations,
+            property_editor::values::PropertyEditorValue,
+            property_editor::values::PropertyEditorValues,
+            si_pkg::ActionFuncSpec,
+            si_pkg::ActionFuncSpecKind,
+            si_pkg::AttrFuncInputSpec,
+            si_pkg::ChangeSetSpec,
+            si_pkg::ChangeSetSpecStatus,
+            si_pkg::ComponentTemplateSpec,
+            si_pkg::FuncArgumentKind,
+            si_pkg::FuncArgumentSpec,
+            si_pkg::FuncSpec,
+            si_pkg::FuncSpecBackendKind,
+            si_pkg::FuncSpecBackendResponseType,
+            si_pkg::FuncSpecData,
+            si_pkg::LeafFunctionSpec,
+            si_pkg::LeafInputLocation,
+            si_pkg::LeafKind,
+            si_pkg::PkgSpec,
+            si_pkg::PropSpec,
+            si_pkg::SchemaSpec,
+            si_pkg::SchemaSpecData,
+            si_pkg::SchemaVariantSpec,
+            si_pkg::SchemaVariantSpecComponentType,
+            si_pkg::SchemaVariantSpecData,
+            si_pkg::SiPkgKind,
+            si_pkg::SiPropFuncSpec,
+            si_pkg::SiPropFuncSpecKind,
+            si_pkg::SocketSpec,
+            si_pkg::SocketSpecArity,
+            si_pkg::SocketSpecData,
+            si_pkg::SocketSpecKind,
+            veritech_client::FunctionResultFailure,
+            veritech_client::OutputStream,
+            veritech_client::ResourceStatus,
+        );
+        assert!(
+            errors.is_empty(),
+            "schemas that don't match serde: {errors:#?}"
+        );
+    }
+}
@@ -5,22 +5,63 @@ use axum::{
     Router,
 };
 use hyper::StatusCode;
+use once_cell::sync::Lazy;
 use serde_json::{json, Value};
 use si_data_nats::NatsError;
 use si_data_pg::PgError;
 use thiserror::Error;
 use tower_http::cors::CorsLayer;
 
-use super::{server::ServerError, state::AppState};
+use super::{
+    openapi::{schema_of, ApiRoutes},
+    server::ServerError,
+    state::AppState,
+};
 
 #[allow(clippy::too_many_arguments)]
 pub fn routes(state: AppState) -> Router {
-    let mut router: Router<AppState> = Router::new();
-    router = router
+    let mut router: Router<AppState> = api_routes().into_router();
+
+    // Load dev routes if we are in dev mode (decided by "opt-level" at the moment).
+    router = dev_routes(router);
+
+    router.with_state(state)
+}
+
+async fn system_status_route() -> Json<Value> {
+    Json(json!({ "ok": true }))
+}
+
+static API_DOCUMENT: Lazy<Value> = Lazy::new(api_document);
+
+async fn openapi_route() -> Json<Value> {
+    Json(API_DOCUMENT.clone())
+}
+
+/// Every route of sdf but the dev routes. [`routes`] serves them and [`api_document`] documents
+/// them.
+pub fn api_routes() -> ApiRoutes {
+    ApiRoutes::new()
         // root health route is currently pinged by auth portal to check if backend is up and running so we need permissive CORS headers
         .nest(
             "/api/",
-            Router::new().route("/", get(system_status_route).layer(CorsLayer::permissive())),
+            ApiRoutes::new()
+                .get_with(
+                    "/",
+                    get(system_status_route).layer(CorsLayer::permissive()),
+                    schema_of!(()),
+                    schema_of!(Value),
+                )
+                .get(
+                    "/openapi.json",
+                    openapi_route,
+                    schema_of!(()),
+                    schema_of!(Value),
+                ),
+        )
+        .nest(
+            "/api/audit_log",
+            crate::server::service::audit_log::routes(),
         )
         .nest(
             "/api/change_set",
@@ -43,20 +84,18 @@ pub fn routes(state: AppState) -> Router {
         .nest("/api/secret", crate::server::service::secret::routes())
         .nest("/api/session", crate::server::service::session::routes())
         .nest("/api/status", crate::server::service::status::routes())
+        .nest("/api/token", crate::server::service::token::routes())
         .nest(
             "/api/variant_def",
             crate::server::service::variant_definition::routes(),
         )
-        .nest("/api/ws", crate::server::service::ws::routes());
-
-    // Load dev routes if we are in dev mode (decided by "opt-level" at the moment).
-    router = dev_routes(router);
-
-    router.with_state(state)
+        .nest("/api/webhook", crate::server::service::webhook::routes())
+        .nest("/api/ws", crate::server::service::ws::routes())
 }
 
-async fn system_status_route() -> Json<Value> {
-    Json(json!({ "ok": true }))
+/// The OpenAPI document served at `/api/openapi.json`.
+pub fn api_document() -> Value {
+    api_routes().document()
 }
 
 #[cfg(debug_assertions)]
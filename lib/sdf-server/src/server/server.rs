@@ -3,7 +3,7 @@ use std::{io, net::SocketAddr, path::Path, path::PathBuf, sync::Arc};
 use crate::server::config::CycloneKeyPair;
 use axum::routing::IntoMakeService;
 use axum::Router;
-use dal::tasks::{StatusReceiver, StatusReceiverError};
+use dal::tasks::{StatusReceiver, StatusReceiverError, WebhookDispatcher, WebhookDispatcherError};
 use dal::JwtPublicSigningKey;
 use dal::{
     cyclone_key_pair::CycloneKeyPairError, job::processor::JobQueueProcessor,
@@ -60,6 +60,8 @@ pub enum ServerError {
     StatusReceiver(#[from] StatusReceiverError),
     #[error(transparent)]
     Uds(#[from] UdsIncomingStreamError),
+    #[error(transparent)]
+    WebhookDispatcher(#[from] WebhookDispatcherError),
     #[error("wrong incoming stream for {0} server: {1:?}")]
     WrongIncomingStream(&'static str, IncomingStream),
 }
@@ -286,6 +288,30 @@ impl Server<(), ()> {
         Ok(())
     }
 
+    /// Start the dispatcher posting webhook deliveries
+    pub async fn start_webhook_dispatcher(
+        pg: PgPool,
+        nats: NatsClient,
+        job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
+        veritech: VeritechClient,
+        encryption_key: EncryptionKey,
+        shutdown_broadcast_rx: broadcast::Receiver<()>,
+    ) -> Result<()> {
+        let services_context = ServicesContext::new(
+            pg,
+            nats,
+            job_processor,
+            veritech,
+            Arc::new(encryption_key),
+            None,
+            None,
+        );
+        WebhookDispatcher::new(services_context)
+            .await?
+            .start(shutdown_broadcast_rx);
+        Ok(())
+    }
+
     #[instrument(name = "sdf.init.create_pg_pool", skip_all)]
     pub async fn create_pg_pool(pg_pool_config: &PgPoolConfig) -> Result<PgPool> {
         let pool = PgPool::new(pg_pool_config).await?;
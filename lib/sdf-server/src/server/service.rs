@@ -1,3 +1,4 @@
+pub mod audit_log;
 pub mod change_set;
 pub mod component;
 pub mod diagram;
@@ -10,7 +11,9 @@ pub mod schema;
 pub mod secret;
 pub mod session;
 pub mod status;
+pub mod token;
 pub mod variant_definition;
+pub mod webhook;
 pub mod ws;
 
 /// A module containing dev routes for local development only.
@@ -0,0 +1,105 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use dal::{HistoryActor, HistoryEventError, HistoryEventFilter, TransactionsError, UserError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::server::openapi::{schema_of, ApiRoutes, ApiSchema};
+
+pub mod export_events;
+pub mod list_events;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum AuditLogError {
+    #[error(transparent)]
+    ContextTransactions(#[from] TransactionsError),
+    #[error(transparent)]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error(transparent)]
+    Hyper(#[from] hyper::http::Error),
+    #[error("invalid actor: {0}")]
+    InvalidActor(String),
+    #[error("json serialize failed")]
+    JSONSerialize(#[from] serde_json::Error),
+    #[error(transparent)]
+    User(#[from] UserError),
+}
+
+pub type AuditLogResult<T> = std::result::Result<T, AuditLogError>;
+
+impl IntoResponse for AuditLogError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            AuditLogError::InvalidActor(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            AuditLogError::User(
+                UserError::ApiTokenUnauthorized(_, _) | UserError::Unauthorized(_, _),
+            ) => (StatusCode::FORBIDDEN, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// The filters shared by every audit log route, as query parameters.
+#[derive(Deserialize, Serialize, Debug, Default, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogFilterRequest {
+    /// Either `system`, `user:<pk>` or `apiToken:<pk>`.
+    pub actor: Option<String>,
+    /// Such as `change_set.*`.
+    pub label_prefix: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub object_id: Option<String>,
+}
+
+impl AuditLogFilterRequest {
+    fn into_filter(self) -> AuditLogResult<HistoryEventFilter> {
+        let actor = match self.actor.as_deref() {
+            None | Some("") => None,
+            Some("system") => Some(HistoryActor::SystemInit),
+            Some(actor) => {
+                let invalid = || AuditLogError::InvalidActor(actor.to_owned());
+                match actor.split_once(':').ok_or_else(invalid)? {
+                    ("user", pk) => Some(HistoryActor::User(pk.parse().map_err(|_| invalid())?)),
+                    ("apiToken", pk) => {
+                        Some(HistoryActor::ApiToken(pk.parse().map_err(|_| invalid())?))
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+        };
+
+        Ok(HistoryEventFilter {
+            actor,
+            label_prefix: self.label_prefix.filter(|prefix| !prefix.is_empty()),
+            since: self.since,
+            until: self.until,
+            object_id: self.object_id.filter(|object_id| !object_id.is_empty()),
+        })
+    }
+}
+
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .get(
+            "/list_events",
+            list_events::list_events,
+            schema_of!(list_events::ListEventsRequest),
+            schema_of!(list_events::ListEventsResponse),
+        )
+        .get(
+            "/export_events",
+            export_events::export_events,
+            schema_of!(export_events::ExportEventsRequest),
+            schema_of!(Vec<dal::HistoryEvent>),
+        )
+}
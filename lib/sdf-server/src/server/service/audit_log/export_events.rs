@@ -0,0 +1,120 @@
+use axum::body::StreamBody;
+use axum::extract::Query;
+use axum::response::{IntoResponse, Response};
+use dal::{
+    context, history_event::HISTORY_EVENT_PAGE_SIZE_MAX, DalContextBuilder, HistoryEvent,
+    HistoryEventFilter, HistoryEventPk, User, UserCapability,
+};
+use futures::stream;
+use serde::{Deserialize, Serialize};
+
+use super::{AuditLogError, AuditLogFilterRequest, AuditLogResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    /// A single JSON array.
+    #[default]
+    Json,
+    /// One JSON object per line.
+    Ndjson,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportEventsRequest {
+    #[serde(flatten)]
+    pub filter: AuditLogFilterRequest,
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Where an export is at, between the pages it reads. No context is kept between pages, so
+/// that a slow download doesn't hold a connection and its transaction open.
+struct ExportState {
+    builder: DalContextBuilder,
+    access_builder: context::AccessBuilder,
+    filter: HistoryEventFilter,
+    format: ExportFormat,
+    cursor: Option<HistoryEventPk>,
+    /// Whether the JSON array has been opened, which happens with the first event.
+    opened: bool,
+}
+
+/// Exports every event that matches the filter, newest first, as a file download. Events are
+/// read and sent a page at a time, so exports of any size are never held in memory.
+pub async fn export_events(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<ExportEventsRequest>,
+) -> AuditLogResult<impl IntoResponse> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    User::ensure_authorized(&ctx, UserCapability::ReadAuditLog).await?;
+
+    let state = ExportState {
+        builder,
+        access_builder,
+        filter: request.filter.into_filter()?,
+        format: request.format,
+        cursor: None,
+        opened: false,
+    };
+    let chunks = stream::try_unfold(Some(state), |state| async move {
+        let mut state = match state {
+            Some(state) => state,
+            None => return Ok::<_, AuditLogError>(None),
+        };
+
+        // Each page is read on its own, after the cursor of the one before it.
+        let ctx = state.builder.build_head(state.access_builder).await?;
+        let page = HistoryEvent::list(
+            &ctx,
+            &state.filter,
+            state.cursor,
+            HISTORY_EVENT_PAGE_SIZE_MAX,
+        )
+        .await?;
+        ctx.rollback().await?;
+        let last_page = page.next_cursor.is_none();
+
+        let mut chunk = String::new();
+        for event in &page.events {
+            match state.format {
+                ExportFormat::Json => {
+                    chunk.push(if state.opened { ',' } else { '[' });
+                    state.opened = true;
+                    chunk.push_str(&serde_json::to_string(event)?);
+                }
+                ExportFormat::Ndjson => {
+                    chunk.push_str(&serde_json::to_string(event)?);
+                    chunk.push('\n');
+                }
+            }
+        }
+        if state.format == ExportFormat::Json && last_page {
+            if !state.opened {
+                chunk.push('[');
+            }
+            chunk.push(']');
+        }
+
+        state.cursor = page.next_cursor;
+        Ok(Some((chunk, (!last_page).then_some(state))))
+    });
+
+    let (content_type, extension) = match request.format {
+        ExportFormat::Json => ("application/json", "json"),
+        ExportFormat::Ndjson => ("application/x-ndjson", "ndjson"),
+    };
+
+    Ok(Response::builder()
+        .header("Content-Type", content_type)
+        .header(
+            "Content-Disposition",
+            format!("attachment; filename=\"audit-log.{extension}\""),
+        )
+        .body(StreamBody::new(chunks))?)
+}
@@ -0,0 +1,42 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{HistoryEvent, HistoryEventPage, HistoryEventPk, User, UserCapability};
+use serde::{Deserialize, Serialize};
+
+use super::{AuditLogFilterRequest, AuditLogResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListEventsRequest {
+    #[serde(flatten)]
+    pub filter: AuditLogFilterRequest,
+    pub cursor: Option<HistoryEventPk>,
+    pub page_size: Option<usize>,
+}
+
+pub type ListEventsResponse = HistoryEventPage;
+
+pub async fn list_events(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<ListEventsRequest>,
+) -> AuditLogResult<Json<ListEventsResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    User::ensure_authorized(&ctx, UserCapability::ReadAuditLog).await?;
+
+    let filter = request.filter.into_filter()?;
+    let page = HistoryEvent::list(
+        &ctx,
+        &filter,
+        request.cursor,
+        request.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+    )
+    .await?;
+
+    Ok(Json(page))
+}
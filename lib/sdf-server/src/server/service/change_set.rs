@@ -1,19 +1,19 @@
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::{get, post},
-    Json, Router,
+    Json,
 };
 use dal::{
-    change_status::ChangeStatusError, ActionError, ActionId, ChangeSetError as DalChangeSetError,
-    ComponentError as DalComponentError, FixError, StandardModelError, TransactionsError,
-    UserError, UserPk, WsEventError,
+    change_status::ChangeStatusError, ActionError, ActionId, ApiTokenPk,
+    ChangeSetError as DalChangeSetError, ComponentError as DalComponentError, FixError,
+    StandardModelError, TransactionsError, UserError, UserPk, WsEventError,
 };
 use module_index_client::IndexClientError;
 use telemetry::prelude::*;
 use thiserror::Error;
 
-use crate::{server::state::AppState, service::pkg::PkgError};
+use crate::server::openapi::{schema_of, ApiRoutes};
+use crate::service::pkg::PkgError;
 
 pub mod add_action;
 pub mod apply_change_set;
@@ -47,6 +47,8 @@ pub enum ChangeSetError {
     Fix(#[from] FixError),
     #[error(transparent)]
     IndexClient(#[from] IndexClientError),
+    #[error("invalid api token {0}")]
+    InvalidApiToken(ApiTokenPk),
     #[error("invalid user {0}")]
     InvalidUser(UserPk),
     #[error("invalid user system init")]
@@ -73,6 +75,9 @@ impl IntoResponse for ChangeSetError {
     fn into_response(self) -> Response {
         let (status, error_message) = match self {
             ChangeSetError::ChangeSetNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            ChangeSetError::ChangeSet(DalChangeSetError::User(UserError::Unauthorized(_, _))) => {
+                (StatusCode::FORBIDDEN, self.to_string())
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -84,26 +89,54 @@ impl IntoResponse for ChangeSetError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route(
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .get(
             "/list_open_change_sets",
-            get(list_open_change_sets::list_open_change_sets),
+            list_open_change_sets::list_open_change_sets,
+            schema_of!(()),
+            schema_of!(list_open_change_sets::ListOpenChangeSetsResponse),
         )
-        .route("/remove_action", post(remove_action::remove_action))
-        .route("/add_action", post(add_action::add_action))
-        .route(
+        .post(
+            "/remove_action",
+            remove_action::remove_action,
+            schema_of!(remove_action::RemoveActionRequest),
+            schema_of!(()),
+        )
+        .post(
+            "/add_action",
+            add_action::add_action,
+            schema_of!(add_action::AddActionRequest),
+            schema_of!(()),
+        )
+        .post(
             "/create_change_set",
-            post(create_change_set::create_change_set),
+            create_change_set::create_change_set,
+            schema_of!(create_change_set::CreateChangeSetRequest),
+            schema_of!(create_change_set::CreateChangeSetResponse),
+        )
+        .get(
+            "/get_change_set",
+            get_change_set::get_change_set,
+            schema_of!(get_change_set::GetChangeSetRequest),
+            schema_of!(get_change_set::GetChangeSetResponse),
+        )
+        .get(
+            "/get_stats",
+            get_stats::get_stats,
+            schema_of!(get_stats::GetStatsRequest),
+            schema_of!(get_stats::GetStatsResponse),
         )
-        .route("/get_change_set", get(get_change_set::get_change_set))
-        .route("/get_stats", get(get_stats::get_stats))
-        .route(
+        .post(
             "/apply_change_set",
-            post(apply_change_set::apply_change_set),
+            apply_change_set::apply_change_set,
+            schema_of!(apply_change_set::ApplyChangeSetRequest),
+            schema_of!(apply_change_set::ApplyChangeSetResponse),
         )
-        .route(
+        .post(
             "/update_selected_change_set",
-            post(update_selected_change_set::update_selected_change_set),
+            update_selected_change_set::update_selected_change_set,
+            schema_of!(update_selected_change_set::UpdateSelectedChangeSetRequest),
+            schema_of!(update_selected_change_set::UpdateSelectedChangeSetResponse),
         )
 }
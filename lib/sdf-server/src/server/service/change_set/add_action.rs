@@ -1,11 +1,12 @@
 use super::{ChangeSetError, ChangeSetResult};
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use axum::extract::{Json, OriginalUri};
 use dal::{Action, ActionPrototypeId, ChangeSet, ComponentId, StandardModel, Visibility, WsEvent};
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AddActionRequest {
     pub prototype_id: ActionPrototypeId,
@@ -1,24 +1,32 @@
 use super::ChangeSetResult;
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::service::change_set::ChangeSetError;
+use crate::server::state::PosthogClient as PosthogClientState;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
+use axum::http::Uri;
 use axum::Json;
 use dal::job::definition::{FixItem, FixesJob};
-use dal::{ChangeSet, ChangeSetPk, Fix, FixBatch, HistoryActor, StandardModel, User};
+use dal::{
+    ChangeSet, ChangeSetPk, DalContext, Fix, FixBatch, FixBatchId, HistoryActor, StandardModel,
+    User,
+};
 use serde::{Deserialize, Serialize};
 //use telemetry::tracing::{info_span, Instrument, log::warn};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApplyChangeSetRequest {
     pub change_set_pk: ChangeSetPk,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApplyChangeSetResponse {
     pub change_set: ChangeSet,
+    /// The batch of fixes started for the actions of the change set, if it had any.
+    pub fix_batch_id: Option<FixBatchId>,
 }
 
 pub async fn apply_change_set(
@@ -30,39 +38,76 @@ pub async fn apply_change_set(
 ) -> ChangeSetResult<Json<ApplyChangeSetResponse>> {
     let mut ctx = builder.build_head(access_builder).await?;
 
-    let mut change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
+    let (change_set, fix_batch_id) = apply_and_run_fixes(
+        &mut ctx,
+        &posthog_client,
+        &original_uri,
+        request.change_set_pk,
+    )
+    .await?;
+
+    // If anything fails with uploading the workspace backup module, just log it. We shouldn't
+    // have the change set apply itself fail because of this.
+    /*
+    tokio::task::spawn(
+        super::upload_workspace_backup_module(ctx, raw_access_token)
+            .instrument(info_span!("Workspace backup module upload")),
+    );
+    */
+
+    Ok(Json(ApplyChangeSetResponse {
+        change_set,
+        fix_batch_id,
+    }))
+}
+
+/// Applies the change set from a head context, then starts the fixes for its actions as the user
+/// the context acts as, returning the batch they were started in. Commits the context.
+pub(crate) async fn apply_and_run_fixes(
+    ctx: &mut DalContext,
+    posthog_client: &PosthogClientState,
+    original_uri: &Uri,
+    change_set_pk: ChangeSetPk,
+) -> ChangeSetResult<(ChangeSet, Option<FixBatchId>)> {
+    let mut change_set = ChangeSet::get_by_pk(ctx, &change_set_pk)
         .await?
         .ok_or(ChangeSetError::ChangeSetNotFound)?;
-    let actions = change_set.actions(&ctx).await?;
-    change_set.apply(&mut ctx).await?;
+    let actions = change_set.actions(ctx).await?;
+    change_set.apply(ctx).await?;
 
     track(
-        &posthog_client,
-        &ctx,
-        &original_uri,
+        posthog_client,
+        ctx,
+        original_uri,
         "apply_change_set",
         serde_json::json!({
-            "merged_change_set": request.change_set_pk,
+            "merged_change_set": change_set_pk,
         }),
     );
 
     ctx.blocking_commit().await?;
 
     let user = match ctx.history_actor() {
-        HistoryActor::User(user_pk) => User::get_by_pk(&ctx, *user_pk)
+        HistoryActor::User(user_pk) => User::get_by_pk(ctx, *user_pk)
             .await?
             .ok_or(ChangeSetError::InvalidUser(*user_pk))?,
 
+        HistoryActor::ApiToken(api_token_pk) => {
+            User::get_for_history_actor(ctx, ctx.history_actor())
+                .await?
+                .ok_or(ChangeSetError::InvalidApiToken(*api_token_pk))?
+        }
         HistoryActor::SystemInit => return Err(ChangeSetError::InvalidUserSystemInit),
     };
 
+    let mut fix_batch_id = None;
     if !actions.is_empty() {
-        let batch = FixBatch::new(&ctx, user.email()).await?;
+        let batch = FixBatch::new(ctx, user.email()).await?;
         let mut fixes = Vec::with_capacity(actions.len());
 
         for action in actions {
             let fix = Fix::new(
-                &ctx,
+                ctx,
                 *batch.id(),
                 *action.component_id(),
                 *action.action_prototype_id(),
@@ -77,9 +122,9 @@ pub async fn apply_change_set(
         }
 
         track(
-            &posthog_client,
-            &ctx,
-            &original_uri,
+            posthog_client,
+            ctx,
+            original_uri,
             "apply_fix",
             serde_json::json!({
                 "fix_batch_id": batch.id(),
@@ -88,20 +133,12 @@ pub async fn apply_change_set(
             }),
         );
 
-        ctx.enqueue_job(FixesJob::new(&ctx, fixes, *batch.id()))
+        ctx.enqueue_job(FixesJob::new(ctx, fixes, *batch.id()))
             .await?;
+        fix_batch_id = Some(*batch.id());
     }
 
     ctx.commit().await?;
 
-    // If anything fails with uploading the workspace backup module, just log it. We shouldn't
-    // have the change set apply itself fail because of this.
-    /*
-    tokio::task::spawn(
-        super::upload_workspace_backup_module(ctx, raw_access_token)
-            .instrument(info_span!("Workspace backup module upload")),
-    );
-    */
-
-    Ok(Json(ApplyChangeSetResponse { change_set }))
+    Ok((change_set, fix_batch_id))
 }
@@ -5,15 +5,16 @@ use serde::{Deserialize, Serialize};
 
 use super::ChangeSetResult;
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateChangeSetRequest {
     pub change_set_name: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateChangeSetResponse {
     pub change_set: ChangeSet,
@@ -1,17 +1,18 @@
 use super::{ChangeSetError, ChangeSetResult};
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use axum::extract::Query;
 use axum::Json;
 use dal::{ChangeSet, ChangeSetPk};
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetChangeSetRequest {
     pub pk: ChangeSetPk,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetChangeSetResponse {
     pub change_set: ChangeSet,
@@ -1,5 +1,6 @@
 use super::ChangeSetResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
 use axum::extract::Query;
 use axum::Json;
@@ -7,14 +8,14 @@ use dal::change_status::ComponentChangeStatus;
 use dal::Visibility;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetStatsRequest {
     #[serde(flatten)]
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetStatsResponse {
     pub component_stats: ComponentChangeStatus,
@@ -1,5 +1,6 @@
 use super::ChangeSetResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use axum::Json;
 use dal::{
     ActionId, ActionKind, ActionPrototypeId, ChangeSet, ChangeSetPk, ChangeSetStatus, ComponentId,
@@ -7,7 +8,7 @@ use dal::{
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionView {
     pub id: ActionId,
@@ -16,7 +17,7 @@ pub struct ActionView {
     pub component_id: ComponentId,
 }
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, ApiSchema)]
 pub struct ChangeSetView {
     pub pk: ChangeSetPk,
     pub name: String,
@@ -1,11 +1,12 @@
 use super::ChangeSetResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use crate::server::service::change_set::ChangeSetError;
 use axum::Json;
 use dal::{Action, ActionId, StandardModel, Visibility, WsEvent};
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoveActionRequest {
     pub id: ActionId,
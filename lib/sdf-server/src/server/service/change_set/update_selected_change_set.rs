@@ -4,14 +4,15 @@ use serde::{Deserialize, Serialize};
 
 use super::{ChangeSetError, ChangeSetResult};
 use crate::server::extract::{AccessBuilder, Authorization, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateSelectedChangeSetRequest {
     pub next_change_set_pk: ChangeSetPk,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateSelectedChangeSetResponse {
     pub change_set: ChangeSet,
@@ -1,23 +1,26 @@
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::{get, post},
-    Json, Router,
+    Json,
 };
 use dal::change_status::ChangeStatusError;
 use dal::{
     node::NodeError, property_editor::PropertyEditorError, AttributeContextBuilderError,
     AttributePrototypeArgumentError, AttributePrototypeError, AttributeValueError, ChangeSetError,
-    ComponentError as DalComponentError, ComponentId, DiagramError, ExternalProviderError,
-    FuncBindingError, FuncError, InternalProviderError, PropId, ReconciliationPrototypeError,
-    SchemaError as DalSchemaError, StandardModelError, TransactionsError, WsEventError,
+    ComponentError as DalComponentError, ComponentId, ComponentTagError, DiagramError,
+    ExternalProviderError, FuncBindingError, FuncError, InternalProviderError, PropError, PropId,
+    QueryError, ReconciliationPrototypeError, SchemaError as DalSchemaError, StandardModelError,
+    TransactionsError, WsEventError,
 };
 use thiserror::Error;
 
-use crate::{server::state::AppState, service::schema::SchemaError};
+use crate::server::openapi::{schema_of, ApiRoutes, NoContent};
+use crate::service::schema::SchemaError;
 
 pub mod alter_simulation;
+pub mod bulk_update_property_value;
 pub mod debug;
+pub mod get_attribute_value_provenance;
 pub mod get_code;
 pub mod get_components_metadata;
 pub mod get_diff;
@@ -27,8 +30,14 @@ pub mod get_property_editor_values;
 pub mod insert_property_editor_value;
 pub mod list_qualifications;
 pub mod list_resources;
+pub mod list_tags;
+pub mod query;
 pub mod refresh;
+pub mod remove_tag;
+pub mod reset_property_editor_value;
 pub mod resource_domain_diff;
+pub mod set_property_editor_value_locked;
+pub mod set_tag;
 pub mod set_type;
 pub mod update_property_editor_value;
 
@@ -59,6 +68,8 @@ pub enum ComponentError {
     ComponentNameNotFound,
     #[error("component not found for id: {0}")]
     ComponentNotFound(ComponentId),
+    #[error("component tag error: {0}")]
+    ComponentTag(#[from] ComponentTagError),
     #[error("dal schema error: {0}")]
     DalSchema(#[from] DalSchemaError),
     #[error("diagram error: {0}")]
@@ -85,10 +96,14 @@ pub enum ComponentError {
     Node(#[from] NodeError),
     #[error(transparent)]
     Pg(#[from] si_data_pg::PgError),
+    #[error("prop error: {0}")]
+    Prop(#[from] PropError),
     #[error("property editor error: {0}")]
     PropertyEditor(#[from] PropertyEditorError),
     #[error("prop not found for id: {0}")]
     PropNotFound(PropId),
+    #[error("query error: {0}")]
+    Query(#[from] QueryError),
     #[error("reconciliation prototype: {0}")]
     ReconciliationPrototype(#[from] ReconciliationPrototypeError),
     #[error("schema error: {0}")]
@@ -103,6 +118,8 @@ pub enum ComponentError {
     StandardModel(#[from] StandardModelError),
     #[error("system id is required: ident_nil_v1() was provided")]
     SystemIdRequired,
+    #[error("tag {1} not found for component {0}")]
+    TagNotFound(ComponentId, String),
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
     #[error("ws event error: {0}")]
@@ -116,6 +133,13 @@ impl IntoResponse for ComponentError {
         let (status, error_message) = match self {
             ComponentError::SchemaNotFound => (StatusCode::NOT_FOUND, self.to_string()),
             ComponentError::InvalidVisibility => (StatusCode::NOT_FOUND, self.to_string()),
+            ComponentError::TagNotFound(..) => (StatusCode::NOT_FOUND, self.to_string()),
+            ComponentError::ComponentTag(ComponentTagError::EmptyKey) => {
+                (StatusCode::BAD_REQUEST, self.to_string())
+            }
+            ComponentError::Query(
+                QueryError::Syntax { .. } | QueryError::TooDeep | QueryError::TooLong,
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -127,45 +151,144 @@ impl IntoResponse for ComponentError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route(
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .get(
             "/get_components_metadata",
-            get(get_components_metadata::get_components_metadata),
+            get_components_metadata::get_components_metadata,
+            schema_of!(get_components_metadata::GetComponentsMetadataRequest),
+            schema_of!(get_components_metadata::GetComponentsMetadataResponse),
         )
-        .route(
+        .get(
             "/list_qualifications",
-            get(list_qualifications::list_qualifications),
+            list_qualifications::list_qualifications,
+            schema_of!(list_qualifications::ListQualificationsRequest),
+            schema_of!(list_qualifications::QualificationResponse),
         )
-        .route("/list_resources", get(list_resources::list_resources))
-        .route("/get_code", get(get_code::get_code))
-        .route("/get_diff", get(get_diff::get_diff))
-        .route(
+        .get(
+            "/list_resources",
+            list_resources::list_resources,
+            schema_of!(list_resources::ListResourcesRequest),
+            schema_of!(list_resources::ListResourcesResponse),
+        )
+        .get(
+            "/query",
+            query::query,
+            schema_of!(query::QueryRequest),
+            schema_of!(query::QueryResponse),
+        )
+        .get(
+            "/get_code",
+            get_code::get_code,
+            schema_of!(get_code::GetCodeRequest),
+            schema_of!(get_code::GetCodeResponse),
+        )
+        .get(
+            "/get_diff",
+            get_diff::get_diff,
+            schema_of!(get_diff::GetDiffRequest),
+            schema_of!(get_diff::GetDiffResponse),
+        )
+        .get(
             "/get_property_editor_schema",
-            get(get_property_editor_schema::get_property_editor_schema),
+            get_property_editor_schema::get_property_editor_schema,
+            schema_of!(get_property_editor_schema::GetPropertyEditorSchemaRequest),
+            schema_of!(get_property_editor_schema::GetPropertyEditorSchemaResponse),
         )
-        .route(
+        .get(
             "/get_property_editor_values",
-            get(get_property_editor_values::get_property_editor_values),
+            get_property_editor_values::get_property_editor_values,
+            schema_of!(get_property_editor_values::GetPropertyEditorValuesRequest),
+            schema_of!(get_property_editor_values::GetPropertyEditorValuesResponse),
         )
-        .route(
+        .post(
             "/update_property_editor_value",
-            post(update_property_editor_value::update_property_editor_value),
+            update_property_editor_value::update_property_editor_value,
+            schema_of!(update_property_editor_value::UpdatePropertyEditorValueRequest),
+            schema_of!(NoContent),
         )
-        .route(
+        .post(
+            "/bulk_update_property_value",
+            bulk_update_property_value::bulk_update_property_value,
+            schema_of!(bulk_update_property_value::BulkUpdatePropertyValueRequest),
+            schema_of!(bulk_update_property_value::BulkUpdatePropertyValueResponse),
+        )
+        .post(
             "/insert_property_editor_value",
-            post(insert_property_editor_value::insert_property_editor_value),
+            insert_property_editor_value::insert_property_editor_value,
+            schema_of!(insert_property_editor_value::InsertPropertyEditorValueRequest),
+            schema_of!(NoContent),
+        )
+        .post(
+            "/reset_property_editor_value",
+            reset_property_editor_value::reset_property_editor_value,
+            schema_of!(reset_property_editor_value::ResetPropertyEditorValueRequest),
+            schema_of!(NoContent),
         )
-        .route(
+        .post(
+            "/set_property_editor_value_locked",
+            set_property_editor_value_locked::set_property_editor_value_locked,
+            schema_of!(set_property_editor_value_locked::SetPropertyEditorValueLockedRequest),
+            schema_of!(NoContent),
+        )
+        .get(
             "/get_property_editor_validations",
-            get(get_property_editor_validations::get_property_editor_validations),
+            get_property_editor_validations::get_property_editor_validations,
+            schema_of!(get_property_editor_validations::GetPropertyEditorValidationsRequest),
+            schema_of!(get_property_editor_validations::GetPropertyEditorValidationsResponse),
+        )
+        .post(
+            "/set_type",
+            set_type::set_type,
+            schema_of!(set_type::SetTypeRequest),
+            schema_of!(NoContent),
         )
-        .route("/set_type", post(set_type::set_type))
-        .route("/refresh", post(refresh::refresh))
-        .route("/resource_domain_diff", get(resource_domain_diff::get_diff))
-        .route(
+        .post(
+            "/refresh",
+            refresh::refresh,
+            schema_of!(refresh::RefreshRequest),
+            schema_of!(refresh::RefreshResponse),
+        )
+        .get(
+            "/resource_domain_diff",
+            resource_domain_diff::get_diff,
+            schema_of!(resource_domain_diff::GetResourceDomainDiffRequest),
+            schema_of!(resource_domain_diff::GetResourceDomainDiffResponse),
+        )
+        .post(
             "/alter_simulation",
-            post(alter_simulation::alter_simulation),
+            alter_simulation::alter_simulation,
+            schema_of!(alter_simulation::AlterSimulationRequest),
+            schema_of!(alter_simulation::AlterSimulationResponse),
+        )
+        .get(
+            "/debug",
+            debug::debug_component,
+            schema_of!(debug::DebugComponentRequest),
+            schema_of!(debug::DebugComponentResponse),
+        )
+        .get(
+            "/get_attribute_value_provenance",
+            get_attribute_value_provenance::get_attribute_value_provenance,
+            schema_of!(get_attribute_value_provenance::GetAttributeValueProvenanceRequest),
+            schema_of!(get_attribute_value_provenance::GetAttributeValueProvenanceResponse),
+        )
+        .get(
+            "/list_tags",
+            list_tags::list_tags,
+            schema_of!(list_tags::ListTagsRequest),
+            schema_of!(list_tags::ListTagsResponse),
+        )
+        .post(
+            "/set_tag",
+            set_tag::set_tag,
+            schema_of!(set_tag::SetTagRequest),
+            schema_of!(NoContent),
+        )
+        .post(
+            "/remove_tag",
+            remove_tag::remove_tag,
+            schema_of!(remove_tag::RemoveTagRequest),
+            schema_of!(NoContent),
         )
-        .route("/debug", get(debug::debug_component))
 }
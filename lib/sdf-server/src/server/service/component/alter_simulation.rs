@@ -7,8 +7,9 @@ use std::collections::HashMap;
 
 use super::ComponentResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AlterSimulationRequest {
     pub attribute_values: HashMap<AttributeValueId, serde_json::Value>,
@@ -16,7 +17,7 @@ pub struct AlterSimulationRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AlterSimulationResponse {
     success: bool,
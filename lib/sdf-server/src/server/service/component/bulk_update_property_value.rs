@@ -0,0 +1,73 @@
+use axum::{response::IntoResponse, Json};
+use dal::{
+    prop::PropPath, AttributeValueId, ChangeSet, Component, ComponentSelection, Visibility, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdatePropertyValueRequest {
+    pub selection: ComponentSelection,
+    /// The path of the prop, such as "/root/domain/name".
+    pub prop_path: String,
+    pub value: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdatePropertyValueResponse {
+    pub attribute_value_ids: Vec<AttributeValueId>,
+}
+
+pub async fn bulk_update_property_value(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<BulkUpdatePropertyValueRequest>,
+) -> ComponentResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut force_changeset_pk = None;
+    if ctx.visibility().is_head() {
+        let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+        let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+        ctx.update_visibility(new_visibility);
+
+        force_changeset_pk = Some(change_set.pk);
+
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    };
+
+    let prop_path = PropPath::new(request.prop_path.split('/').filter(|part| !part.is_empty()));
+    let attribute_value_ids =
+        Component::bulk_update_prop_value(&ctx, &request.selection, &prop_path, request.value)
+            .await?;
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    response = response.header("Content-Type", "application/json");
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(
+        response.body(serde_json::to_string(&BulkUpdatePropertyValueResponse {
+            attribute_value_ids,
+        })?)?,
+    )
+}
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use super::{ComponentError, ComponentResult};
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use dal::{
     socket::SocketEdgeKind, AttributePrototypeArgument, AttributeReadContext, AttributeValue,
     AttributeValueId, AttributeView, Component, ComponentId, DalContext, ExternalProviderId, Func,
@@ -14,7 +15,7 @@ use dal::{
     StandardModel, Visibility,
 };
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ComponentDebugView {
     attributes: Vec<AttributeDebugView>,
@@ -22,7 +23,7 @@ pub struct ComponentDebugView {
     output_sockets: Vec<AttributeDebugView>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AttributeDebugView {
     name: String,
@@ -30,7 +31,7 @@ pub struct AttributeDebugView {
     debug_data: AttributeMetadataView,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AttributeMetadataView {
     pub value_id: AttributeValueId,
@@ -43,7 +44,7 @@ pub struct AttributeMetadataView {
     pub value: Option<serde_json::Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DebugComponentRequest {
     pub component_id: ComponentId,
@@ -51,7 +52,7 @@ pub struct DebugComponentRequest {
     pub visibility: Visibility,
 }
 
-type DebugComponentResponse = ComponentDebugView;
+pub type DebugComponentResponse = ComponentDebugView;
 
 pub async fn debug_component(
     HandlerContext(builder): HandlerContext,
@@ -0,0 +1,69 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{
+    prop::PropPath, AttributeReadContext, AttributeValue, AttributeValueProvenance, Component,
+    ComponentId, ExternalProviderId, InternalProviderId, Prop, StandardModel, Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{ComponentError, ComponentResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAttributeValueProvenanceRequest {
+    pub component_id: ComponentId,
+    /// The path of the prop, such as "/root/domain/name".
+    pub prop_path: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAttributeValueProvenanceResponse {
+    /// One entry per value of the prop, since props within arrays and maps have a value per
+    /// element.
+    pub provenance: Vec<AttributeValueProvenance>,
+}
+
+pub async fn get_attribute_value_provenance(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetAttributeValueProvenanceRequest>,
+) -> ComponentResult<Json<GetAttributeValueProvenanceResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let component = Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .ok_or(ComponentError::ComponentNotFound(request.component_id))?;
+    let schema_variant = component
+        .schema_variant(&ctx)
+        .await?
+        .ok_or(ComponentError::SchemaVariantNotFound)?;
+
+    let prop_path = PropPath::new(request.prop_path.split('/').filter(|part| !part.is_empty()));
+    let prop = Prop::find_prop_by_path(&ctx, *schema_variant.id(), &prop_path).await?;
+
+    let attribute_values = AttributeValue::list_for_context(
+        &ctx,
+        AttributeReadContext {
+            prop_id: Some(*prop.id()),
+            internal_provider_id: Some(InternalProviderId::NONE),
+            external_provider_id: Some(ExternalProviderId::NONE),
+            component_id: Some(request.component_id),
+        },
+    )
+    .await?;
+    if attribute_values.is_empty() {
+        return Err(ComponentError::AttributeValueNotFound);
+    }
+
+    let mut provenance = Vec::with_capacity(attribute_values.len());
+    for attribute_value in attribute_values {
+        provenance.push(attribute_value.provenance(&ctx).await?);
+    }
+
+    Ok(Json(GetAttributeValueProvenanceResponse { provenance }))
+}
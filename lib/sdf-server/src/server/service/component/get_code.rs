@@ -4,8 +4,9 @@ use serde::{Deserialize, Serialize};
 
 use super::ComponentResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetCodeRequest {
     pub component_id: ComponentId,
@@ -13,7 +14,7 @@ pub struct GetCodeRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetCodeResponse {
     pub code_views: Vec<CodeView>,
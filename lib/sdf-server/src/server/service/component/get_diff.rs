@@ -5,8 +5,9 @@ use serde::{Deserialize, Serialize};
 
 use super::ComponentResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetDiffRequest {
     pub component_id: ComponentId,
@@ -14,7 +15,7 @@ pub struct GetDiffRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetDiffResponse {
     pub component_diff: ComponentDiff,
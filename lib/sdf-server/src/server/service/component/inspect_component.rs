@@ -4,10 +4,11 @@ use axum::Json;
 use serde::{Deserialize, Serialize};
 
 use super::{ComponentError, ComponentResult};
+use crate::server::openapi::ApiSchema;
 use crate::server::extract::{AccessBuilder, HandlerContext};
 use dal::{Component, ComponentId, ComponentView, StandardModel, Visibility};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InspectComponentRequest {
     pub component_id: ComponentId,
@@ -6,8 +6,9 @@ use std::collections::HashMap;
 
 use super::ComponentResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListResourcesRequest {
     #[serde(flatten)]
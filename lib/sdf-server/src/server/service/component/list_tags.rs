@@ -0,0 +1,75 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{ComponentId, ComponentTag, ComponentTagInheritance, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTagsRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TagView {
+    pub key: String,
+    pub value: String,
+    pub inheritance: ComponentTagInheritance,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveTagView {
+    pub key: String,
+    pub value: String,
+    pub source_component_id: ComponentId,
+    pub inherited: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTagsResponse {
+    /// The tags set on the component itself.
+    pub tags: Vec<TagView>,
+    /// Every tag that applies to the component, including those inherited from frames.
+    pub effective_tags: Vec<EffectiveTagView>,
+}
+
+pub async fn list_tags(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListTagsRequest>,
+) -> ComponentResult<Json<ListTagsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let tags = ComponentTag::list_for_component(&ctx, request.component_id)
+        .await?
+        .into_iter()
+        .map(|tag| TagView {
+            key: tag.key().to_owned(),
+            value: tag.value().to_owned(),
+            inheritance: *tag.inheritance(),
+        })
+        .collect();
+    let effective_tags = ComponentTag::effective_for_component(&ctx, request.component_id)
+        .await?
+        .into_iter()
+        .map(|(key, tag)| EffectiveTagView {
+            key,
+            value: tag.value,
+            source_component_id: tag.source_component_id,
+            inherited: tag.inherited,
+        })
+        .collect();
+
+    Ok(Json(ListTagsResponse {
+        tags,
+        effective_tags,
+    }))
+}
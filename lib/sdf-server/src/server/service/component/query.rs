@@ -0,0 +1,47 @@
+use axum::{extract::Query, Json};
+use dal::{ComponentFilter, ComponentQueryMatch, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRequest {
+    /// The query, such as `schema = "EC2 Instance" and domain/InstanceType startsWith "t2"`.
+    pub query: String,
+    /// Comma separated prop paths whose values should be returned for every match, on top of
+    /// those used by the query.
+    pub select: Option<String>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResponse {
+    pub matches: Vec<ComponentQueryMatch>,
+}
+
+pub async fn query(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<QueryRequest>,
+) -> ComponentResult<Json<QueryResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let filter = ComponentFilter::parse(&request.query)?;
+    let select: Vec<String> = request
+        .select
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(ToOwned::to_owned)
+        .collect();
+    let matches = filter.execute(&ctx, &select).await?;
+
+    Ok(Json(QueryResponse { matches }))
+}
@@ -0,0 +1,77 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+
+use dal::{ChangeSet, ComponentId, ComponentTag, Visibility, WsEvent};
+use serde::{Deserialize, Serialize};
+
+use super::ComponentResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+use crate::service::component::ComponentError;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveTagRequest {
+    pub component_id: ComponentId,
+    pub key: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub async fn remove_tag(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RemoveTagRequest>,
+) -> ComponentResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut force_changeset_pk = None;
+    if ctx.visibility().is_head() {
+        let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+        let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+        ctx.update_visibility(new_visibility);
+
+        force_changeset_pk = Some(change_set.pk);
+
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    };
+
+    if !ComponentTag::remove(&ctx, request.component_id, &request.key).await? {
+        return Err(ComponentError::TagNotFound(
+            request.component_id,
+            request.key,
+        ));
+    }
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "remove_component_tag",
+        serde_json::json!({
+                    "component_id": request.component_id,
+                    "tag_key": &request.key,
+        }),
+    );
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response.body(axum::body::Empty::new())?)
+}
@@ -13,29 +13,30 @@ use telemetry::prelude::*;
 
 use super::ComponentResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use crate::service::component::ComponentError;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetResourceDomainDiffRequest {
     #[serde(flatten)]
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug, Default)]
+#[derive(Deserialize, Serialize, Debug, Default, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ResourceDomainDiff {
     diff: HashMap<String, ReconciliationDiff>,
     reconciliation: Option<ReconciliationResult>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Default)]
+#[derive(Deserialize, Serialize, Debug, Default, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetResourceDomainDiffResponse {
     diffs: HashMap<ComponentId, ResourceDomainDiff>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Default)]
+#[derive(Deserialize, Serialize, Debug, Default, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 struct DiffValue {
     diff: bool,
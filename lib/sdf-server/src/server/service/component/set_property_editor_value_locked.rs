@@ -0,0 +1,63 @@
+use axum::{response::IntoResponse, Json};
+use dal::{
+    AttributeValue, AttributeValueId, ChangeSet, ComponentId, StandardModel, Visibility, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{ComponentError, ComponentResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPropertyEditorValueLockedRequest {
+    pub attribute_value_id: AttributeValueId,
+    pub component_id: ComponentId,
+    pub locked: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// Locks (or unlocks) a value, so that it is not recomputed when the values it depends on change.
+pub async fn set_property_editor_value_locked(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<SetPropertyEditorValueLockedRequest>,
+) -> ComponentResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut force_changeset_pk = None;
+    if ctx.visibility().is_head() {
+        let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+        let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+        ctx.update_visibility(new_visibility);
+
+        force_changeset_pk = Some(change_set.pk);
+
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    };
+
+    let mut attribute_value = AttributeValue::get_by_id(&ctx, &request.attribute_value_id)
+        .await?
+        .filter(|attribute_value| attribute_value.context.component_id() == request.component_id)
+        .ok_or(ComponentError::AttributeValueNotFound)?;
+    attribute_value.set_locked(&ctx, request.locked).await?;
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(response.body(axum::body::Empty::new())?)
+}
@@ -1,33 +1,43 @@
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
-use axum::routing::{get, post};
 use axum::Json;
-use axum::Router;
 use dal::provider::external::ExternalProviderError as DalExternalProviderError;
 use dal::socket::{SocketError, SocketId};
 use dal::{
     node::NodeId, schema::variant::SchemaVariantError, ActionError, ActionPrototypeError,
-    AttributeValueError, ChangeSetError, ComponentError, ComponentType,
-    DiagramError as DalDiagramError, EdgeError, InternalProviderError, NodeError, NodeKind,
-    NodeMenuError, SchemaError as DalSchemaError, SchemaVariantId, StandardModelError,
-    TransactionsError,
+    AttributeValueError, ChangeSetError, ComponentError, ComponentTemplateError,
+    ComponentTemplateId, ComponentType, DiagramError as DalDiagramError, EdgeError,
+    InternalProviderError, ManifestError, NodeError, NodeKind, NodeMenuError, ResourceImportError,
+    SchemaError as DalSchemaError, SchemaVariantId, StandardModelError, TransactionsError,
 };
 use dal::{AttributeReadContext, WsEventError};
 use thiserror::Error;
 
-use crate::server::state::AppState;
+use crate::server::openapi::{schema_of, ApiRoutes, NoContent};
+use crate::service::change_set::ChangeSetError as ChangeSetServiceError;
 use crate::service::schema::SchemaError;
 
+pub mod apply_manifest;
+pub mod attach_imported_resources;
 mod connect_component_to_frame;
 pub mod create_connection;
 pub mod create_node;
 pub mod delete_component;
 pub mod delete_connection;
+pub mod duplicate_components;
 pub mod get_diagram;
 pub mod get_node_add_menu;
+pub mod import_resources;
+pub mod instantiate_component_template;
+pub mod list_component_templates;
+pub mod list_import_mappings;
 pub mod list_schema_variants;
+pub mod plan_manifest;
+pub mod plan_resource_import;
 mod restore_component;
 pub mod restore_connection;
+pub mod save_component_template;
+pub mod save_import_mapping;
 pub mod set_node_position;
 
 #[remain::sorted]
@@ -43,12 +53,18 @@ pub enum DiagramError {
     AttributeValueNotFoundForContext(AttributeReadContext),
     #[error("changeset error: {0}")]
     ChangeSet(#[from] ChangeSetError),
+    #[error("change set apply error: {0}")]
+    ChangeSetApply(#[from] ChangeSetServiceError),
     #[error("change set not found")]
     ChangeSetNotFound,
     #[error("component error: {0}")]
     Component(#[from] ComponentError),
     #[error("component not found")]
     ComponentNotFound,
+    #[error("component template error: {0}")]
+    ComponentTemplate(#[from] ComponentTemplateError),
+    #[error("component template not found: {0}")]
+    ComponentTemplateNotFound(ComponentTemplateId),
     #[error(transparent)]
     ContextTransaction(#[from] TransactionsError),
     #[error("dal schema error: {0}")]
@@ -83,6 +99,8 @@ pub enum DiagramError {
     InvalidSystem,
     #[error(transparent)]
     Nats(#[from] si_data_nats::NatsError),
+    #[error("manifest error: {0}")]
+    Manifest(#[from] ManifestError),
     #[error("node error: {0}")]
     Node(#[from] NodeError),
     #[error("node menu error: {0}")]
@@ -97,6 +115,8 @@ pub enum DiagramError {
     Pg(#[from] si_data_pg::PgError),
     #[error(transparent)]
     PgPool(#[from] si_data_pg::PgPoolError),
+    #[error("resource import error: {0}")]
+    ResourceImport(#[from] ResourceImportError),
     #[error("schema error: {0}")]
     Schema(#[from] SchemaError),
     #[error("schema not found")]
@@ -122,7 +142,31 @@ pub type DiagramResult<T> = Result<T, DiagramError>;
 impl IntoResponse for DiagramError {
     fn into_response(self) -> Response {
         let (status, error_message) = match self {
-            DiagramError::SchemaNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            DiagramError::SchemaNotFound | DiagramError::ComponentTemplateNotFound(_) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            DiagramError::Manifest(
+                ManifestError::AmbiguousComponentName(_)
+                | ManifestError::ComponentSchemaMismatch(_, _, _)
+                | ManifestError::ContainerNotEmpty(_, _)
+                | ManifestError::DuplicateComponentName(_)
+                | ManifestError::InvalidValue(_, _)
+                | ManifestError::PropNotFound(_)
+                | ManifestError::SchemaVariantNotFound(_, _)
+                | ManifestError::SerdeJson(_)
+                | ManifestError::SerdeYaml(_)
+                | ManifestError::SocketNotFound(_, _)
+                | ManifestError::UnknownComponent(_),
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            DiagramError::ResourceImport(
+                ResourceImportError::InvalidPropPath(_)
+                | ResourceImportError::InvalidTemplate(_)
+                | ResourceImportError::ImportNotApplied
+                | ResourceImportError::ResourcesRequireHead
+                | ResourceImportError::SerdeJson(_)
+                | ResourceImportError::SerdeYaml(_)
+                | ResourceImportError::UnsupportedTerraformStateVersion(_),
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -134,52 +178,150 @@ impl IntoResponse for DiagramError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route("/get_diagram", get(get_diagram::get_diagram))
-        .route(
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .get(
+            "/get_diagram",
+            get_diagram::get_diagram,
+            schema_of!(get_diagram::GetDiagramRequest),
+            schema_of!(get_diagram::GetDiagramResponse),
+        )
+        .post(
             "/get_node_add_menu",
-            post(get_node_add_menu::get_node_add_menu),
+            get_node_add_menu::get_node_add_menu,
+            schema_of!(get_node_add_menu::GetNodeAddMenuRequest),
+            schema_of!(get_node_add_menu::GetNodeAddMenuResponse),
+        )
+        .post(
+            "/create_node",
+            create_node::create_node,
+            schema_of!(create_node::CreateNodeRequest),
+            schema_of!(create_node::CreateNodeResponse),
         )
-        .route("/create_node", post(create_node::create_node))
-        .route(
+        .post(
             "/set_node_position",
-            post(set_node_position::set_node_position),
+            set_node_position::set_node_position,
+            schema_of!(set_node_position::SetNodePositionRequest),
+            schema_of!(set_node_position::SetNodePositionResponse),
         )
-        .route(
+        .post(
             "/create_connection",
-            post(create_connection::create_connection),
+            create_connection::create_connection,
+            schema_of!(create_connection::CreateConnectionRequest),
+            schema_of!(create_connection::CreateConnectionResponse),
         )
-        .route(
+        .post(
             "/delete_connection",
-            post(delete_connection::delete_connection),
+            delete_connection::delete_connection,
+            schema_of!(delete_connection::DeleteConnectionRequest),
+            schema_of!(NoContent),
         )
-        .route(
+        .post(
             "/restore_connection",
-            post(restore_connection::restore_connection),
+            restore_connection::restore_connection,
+            schema_of!(restore_connection::UndeleteConnectionRequest),
+            schema_of!(NoContent),
         )
-        .route(
+        .post(
             "/delete_component",
-            post(delete_component::delete_component),
+            delete_component::delete_component,
+            schema_of!(delete_component::DeleteComponentRequest),
+            schema_of!(NoContent),
         )
-        .route(
+        .post(
             "/delete_components",
-            post(delete_component::delete_components),
+            delete_component::delete_components,
+            schema_of!(delete_component::DeleteComponentsRequest),
+            schema_of!(NoContent),
         )
-        .route(
+        .post(
+            "/duplicate_components",
+            duplicate_components::duplicate_components,
+            schema_of!(duplicate_components::DuplicateComponentsRequest),
+            schema_of!(duplicate_components::DuplicateComponentsResponse),
+        )
+        .post(
             "/restore_component",
-            post(restore_component::restore_component),
+            restore_component::restore_component,
+            schema_of!(restore_component::RestoreComponentRequest),
+            schema_of!(NoContent),
         )
-        .route(
+        .post(
             "/restore_components",
-            post(restore_component::restore_components),
+            restore_component::restore_components,
+            schema_of!(restore_component::RestoreComponentsRequest),
+            schema_of!(NoContent),
         )
-        .route(
+        .post(
             "/connect_component_to_frame",
-            post(connect_component_to_frame::connect_component_to_frame),
+            connect_component_to_frame::connect_component_to_frame,
+            schema_of!(connect_component_to_frame::CreateFrameConnectionRequest),
+            schema_of!(connect_component_to_frame::CreateFrameConnectionResponse),
         )
-        .route(
+        .get(
             "/list_schema_variants",
-            get(list_schema_variants::list_schema_variants),
+            list_schema_variants::list_schema_variants,
+            schema_of!(list_schema_variants::ListSchemaVariantsRequest),
+            schema_of!(list_schema_variants::ListSchemaVariantsResponse),
+        )
+        .post(
+            "/save_component_template",
+            save_component_template::save_component_template,
+            schema_of!(save_component_template::SaveComponentTemplateRequest),
+            schema_of!(save_component_template::SaveComponentTemplateResponse),
+        )
+        .get(
+            "/list_component_templates",
+            list_component_templates::list_component_templates,
+            schema_of!(list_component_templates::ListComponentTemplatesRequest),
+            schema_of!(list_component_templates::ListComponentTemplatesResponse),
+        )
+        .post(
+            "/instantiate_component_template",
+            instantiate_component_template::instantiate_component_template,
+            schema_of!(instantiate_component_template::InstantiateComponentTemplateRequest),
+            schema_of!(instantiate_component_template::InstantiateComponentTemplateResponse),
+        )
+        .post(
+            "/plan_manifest",
+            plan_manifest::plan_manifest,
+            schema_of!(plan_manifest::PlanManifestRequest),
+            schema_of!(plan_manifest::PlanManifestResponse),
+        )
+        .post(
+            "/apply_manifest",
+            apply_manifest::apply_manifest,
+            schema_of!(apply_manifest::ApplyManifestRequest),
+            schema_of!(apply_manifest::ApplyManifestResponse),
+        )
+        .post(
+            "/save_import_mapping",
+            save_import_mapping::save_import_mapping,
+            schema_of!(save_import_mapping::SaveImportMappingRequest),
+            schema_of!(save_import_mapping::SaveImportMappingResponse),
+        )
+        .get(
+            "/list_import_mappings",
+            list_import_mappings::list_import_mappings,
+            schema_of!(list_import_mappings::ListImportMappingsRequest),
+            schema_of!(list_import_mappings::ListImportMappingsResponse),
+        )
+        .post(
+            "/plan_resource_import",
+            plan_resource_import::plan_resource_import,
+            schema_of!(plan_resource_import::PlanResourceImportRequest),
+            schema_of!(plan_resource_import::PlanResourceImportResponse),
+        )
+        .post(
+            "/import_resources",
+            import_resources::import_resources,
+            schema_of!(import_resources::ImportResourcesRequest),
+            schema_of!(import_resources::ImportResourcesResponse),
+        )
+        .post(
+            "/attach_imported_resources",
+            attach_imported_resources::attach_imported_resources,
+            schema_of!(attach_imported_resources::AttachImportedResourcesRequest),
+            schema_of!(attach_imported_resources::AttachImportedResourcesResponse),
         )
 }
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use dal::{ChangeSet, ChangeSetPk, ComponentId, Manifest, ManifestPlan, Visibility, WsEvent};
+
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+use crate::service::change_set::apply_change_set::apply_and_run_fixes;
+use crate::service::diagram::{DiagramError, DiagramResult};
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyManifestRequest {
+    /// The manifest, written in YAML or JSON.
+    pub manifest: String,
+    /// Whether components the manifest does not describe are deleted.
+    #[serde(default)]
+    pub prune: bool,
+    /// Whether the change set the plan was made in is applied afterwards.
+    #[serde(default)]
+    pub apply: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyManifestResponse {
+    pub plan: ManifestPlan,
+    /// The change set the plan was made in, unless there was nothing to change.
+    pub change_set_pk: Option<ChangeSetPk>,
+    /// The components described by the manifest, by name.
+    pub components: BTreeMap<String, ComponentId>,
+    pub applied: bool,
+}
+
+/// Makes the changes a manifest needs in a change set, creating one when called on head, and
+/// applies the change set if asked to.
+pub async fn apply_manifest(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ApplyManifestRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let manifest = Manifest::parse(&request.manifest)?;
+    let plan = manifest.plan(&ctx, request.prune).await?;
+
+    let mut force_changeset_pk = None;
+    let mut change_set_pk = None;
+    let mut components = plan.components.clone();
+    let mut applied = false;
+    if !plan.is_empty() {
+        if ctx.visibility().is_head() {
+            let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+            let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+            ctx.update_visibility(new_visibility);
+
+            force_changeset_pk = Some(change_set.pk);
+
+            WsEvent::change_set_created(&ctx, change_set.pk)
+                .await?
+                .publish_on_commit(&ctx)
+                .await?;
+        };
+        change_set_pk = Some(ctx.visibility().change_set_pk);
+
+        components = plan.apply(&ctx).await?;
+
+        let change_set = ChangeSet::get_by_pk(&ctx, &ctx.visibility().change_set_pk)
+            .await?
+            .ok_or(DiagramError::ChangeSetNotFound)?;
+        change_set.sort_actions(&ctx).await?;
+
+        WsEvent::change_set_written(&ctx)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+
+        track(
+            &posthog_client,
+            &ctx,
+            &original_uri,
+            "manifest_applied",
+            serde_json::json!({
+                "change_count": plan.changes.len(),
+                "component_count": components.len(),
+                "prune": request.prune,
+            }),
+        );
+
+        if request.apply {
+            // Dependent values have to be settled before the change set is merged.
+            ctx.blocking_commit().await?;
+
+            ctx.update_visibility(Visibility::new_head(false));
+            apply_and_run_fixes(&mut ctx, &posthog_client, &original_uri, change_set.pk).await?;
+            applied = true;
+            force_changeset_pk = None;
+        } else {
+            ctx.commit().await?;
+        }
+    }
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    response = response.header("content-type", "application/json");
+    Ok(response.body(serde_json::to_string(&ApplyManifestResponse {
+        plan,
+        change_set_pk,
+        components,
+        applied,
+    })?)?)
+}
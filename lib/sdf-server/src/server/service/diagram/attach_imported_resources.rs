@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+
+use axum::extract::OriginalUri;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use dal::{ComponentId, ImportSource, ResourceImport, WsEvent};
+
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+use crate::service::diagram::DiagramResult;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachImportedResourcesRequest {
+    pub source: ImportSource,
+    /// The contents of the Terraform state file the resources were imported from.
+    pub content: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachImportedResourcesResponse {
+    /// The components resources were attached to, by resource address.
+    pub components: BTreeMap<String, ComponentId>,
+}
+
+/// Attaches the resources recorded in a Terraform state file to the components it was imported
+/// as. Resources only exist on head, so this is done on head, once the change set the import was
+/// made in has been applied.
+pub async fn attach_imported_resources(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<AttachImportedResourcesRequest>,
+) -> DiagramResult<Json<AttachImportedResourcesResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let import = ResourceImport::parse(request.source, &request.content)?;
+    let plan = import.plan(&ctx).await?;
+    let components = plan.attach_resources(&ctx).await?;
+
+    for component_id in components.values() {
+        WsEvent::resource_refreshed(&ctx, *component_id)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    }
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "imported_resources_attached",
+        serde_json::json!({
+            "source": request.source,
+            "resource_count": components.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(AttachImportedResourcesResponse { components }))
+}
@@ -12,11 +12,12 @@ use dal::{ComponentType, Socket};
 use serde::{Deserialize, Serialize};
 
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 
 use super::{DiagramError, DiagramResult};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateFrameConnectionRequest {
     pub child_node_id: NodeId,
@@ -25,7 +26,7 @@ pub struct CreateFrameConnectionRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateFrameConnectionResponse {
     pub connection: Connection,
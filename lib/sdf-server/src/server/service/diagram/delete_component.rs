@@ -9,9 +9,10 @@ use serde::{Deserialize, Serialize};
 
 use super::{DiagramError, DiagramResult};
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteComponentRequest {
     pub component_id: ComponentId,
@@ -142,7 +143,7 @@ pub async fn delete_component(
     Ok(response.body(axum::body::Empty::new())?)
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteComponentsRequest {
     pub component_ids: Vec<ComponentId>,
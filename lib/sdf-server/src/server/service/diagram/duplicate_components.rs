@@ -0,0 +1,104 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use dal::{ChangeSet, Component, ComponentId, DuplicatedComponent, Visibility, WsEvent};
+
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+use crate::service::diagram::connect_component_to_frame::connect_component_sockets_to_frame;
+use crate::service::diagram::{DiagramError, DiagramResult};
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateComponentsRequest {
+    pub component_ids: Vec<ComponentId>,
+    pub offset_x: String,
+    pub offset_y: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateComponentsResponse {
+    pub components: Vec<DuplicatedComponent>,
+}
+
+pub async fn duplicate_components(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<DuplicateComponentsRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let offset_x = request
+        .offset_x
+        .parse::<f64>()
+        .map_err(|_| DiagramError::InvalidRequest)? as i64;
+    let offset_y = request
+        .offset_y
+        .parse::<f64>()
+        .map_err(|_| DiagramError::InvalidRequest)? as i64;
+
+    let mut force_changeset_pk = None;
+    if ctx.visibility().is_head() {
+        let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+        let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+        ctx.update_visibility(new_visibility);
+
+        force_changeset_pk = Some(change_set.pk);
+
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    };
+
+    let duplicates = Component::duplicate(&ctx, &request.component_ids, offset_x, offset_y).await?;
+
+    for duplicate in &duplicates {
+        if let Some(parent_node_id) = duplicate.parent_node_id {
+            connect_component_sockets_to_frame(&ctx, parent_node_id, duplicate.node_id).await?;
+        }
+    }
+
+    let change_set = ChangeSet::get_by_pk(&ctx, &ctx.visibility().change_set_pk)
+        .await?
+        .ok_or(DiagramError::ChangeSetNotFound)?;
+    change_set.sort_actions(&ctx).await?;
+
+    WsEvent::component_created(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "components_duplicated",
+        serde_json::json!({
+                    "component_ids": &request.component_ids,
+                    "component_count": duplicates.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    response = response.header("content-type", "application/json");
+    Ok(
+        response.body(serde_json::to_string(&DuplicateComponentsResponse {
+            components: duplicates,
+        })?)?,
+    )
+}
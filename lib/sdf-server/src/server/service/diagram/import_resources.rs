@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use dal::resource_import::SkippedResource;
+use dal::{ChangeSet, ChangeSetPk, ComponentId, ImportSource, ResourceImport, Visibility, WsEvent};
+
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+use crate::service::diagram::{DiagramError, DiagramResult};
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResourcesRequest {
+    pub source: ImportSource,
+    /// The contents of a Terraform state file or a CloudFormation template.
+    pub content: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResourcesResponse {
+    /// The change set the resources were imported into, or none if there was nothing to change.
+    pub change_set_pk: Option<ChangeSetPk>,
+    /// The imported components, by resource address.
+    pub components: BTreeMap<String, ComponentId>,
+    pub skipped: Vec<SkippedResource>,
+}
+
+/// Imports the resources of a Terraform state file or a CloudFormation template into a change set,
+/// creating one when called on head. The resources recorded in Terraform state are attached with
+/// [`attach_imported_resources`](super::attach_imported_resources::attach_imported_resources)
+/// once the change set is applied.
+pub async fn import_resources(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ImportResourcesRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let import = ResourceImport::parse(request.source, &request.content)?;
+    let mut plan = import.plan(&ctx).await?;
+
+    let mut force_changeset_pk = None;
+    let mut change_set_pk = None;
+    let mut components = plan.plan.components.clone();
+    if !plan.plan.is_empty() {
+        if ctx.visibility().is_head() {
+            let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+            let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+            ctx.update_visibility(new_visibility);
+
+            force_changeset_pk = Some(change_set.pk);
+
+            WsEvent::change_set_created(&ctx, change_set.pk)
+                .await?
+                .publish_on_commit(&ctx)
+                .await?;
+
+            // Planned on head, so plan again where the changes are made.
+            plan = import.plan(&ctx).await?;
+        };
+
+        components = plan.apply(&ctx).await?;
+
+        let change_set = ChangeSet::get_by_pk(&ctx, &ctx.visibility().change_set_pk)
+            .await?
+            .ok_or(DiagramError::ChangeSetNotFound)?;
+        change_set.sort_actions(&ctx).await?;
+        change_set_pk = Some(change_set.pk);
+
+        WsEvent::change_set_written(&ctx)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+
+        track(
+            &posthog_client,
+            &ctx,
+            &original_uri,
+            "resources_imported",
+            serde_json::json!({
+                "source": request.source,
+                "component_count": components.len(),
+                "resource_count": plan.payloads.len(),
+                "skipped_count": plan.skipped.len(),
+            }),
+        );
+
+        ctx.commit().await?;
+    }
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    response = response.header("content-type", "application/json");
+    Ok(
+        response.body(serde_json::to_string(&ImportResourcesResponse {
+            change_set_pk,
+            components,
+            skipped: plan.skipped,
+        })?)?,
+    )
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use dal::node::NodeId;
+use dal::{
+    ChangeSet, ComponentId, ComponentTemplate, ComponentTemplateId, StandardModel, Visibility,
+    WsEvent,
+};
+
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+use crate::service::diagram::connect_component_to_frame::connect_component_sockets_to_frame;
+use crate::service::diagram::{DiagramError, DiagramResult};
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateComponentTemplateRequest {
+    pub template_id: ComponentTemplateId,
+    pub x: String,
+    pub y: String,
+    #[serde(default)]
+    pub parameters: HashMap<String, serde_json::Value>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiatedComponentView {
+    pub key: String,
+    pub component_id: ComponentId,
+    pub node_id: NodeId,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateComponentTemplateResponse {
+    pub components: Vec<InstantiatedComponentView>,
+}
+
+pub async fn instantiate_component_template(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<InstantiateComponentTemplateRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let x = request
+        .x
+        .parse::<f64>()
+        .map_err(|_| DiagramError::InvalidRequest)? as i64;
+    let y = request
+        .y
+        .parse::<f64>()
+        .map_err(|_| DiagramError::InvalidRequest)? as i64;
+
+    let mut force_changeset_pk = None;
+    if ctx.visibility().is_head() {
+        let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+        let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+        ctx.update_visibility(new_visibility);
+
+        force_changeset_pk = Some(change_set.pk);
+
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    };
+
+    let template = ComponentTemplate::get_by_id(&ctx, &request.template_id)
+        .await?
+        .ok_or(DiagramError::ComponentTemplateNotFound(request.template_id))?;
+
+    let instances = template.instantiate(&ctx, x, y, request.parameters).await?;
+
+    // Frame edges only record the parent, so the sockets of each child still need to be wired
+    // up to the frame the same way they would be when dragging a component into it.
+    for instance in &instances {
+        if let Some(parent_node_id) = instance.parent_node_id {
+            connect_component_sockets_to_frame(&ctx, parent_node_id, instance.node_id).await?;
+        }
+    }
+
+    let change_set = ChangeSet::get_by_pk(&ctx, &ctx.visibility().change_set_pk)
+        .await?
+        .ok_or(DiagramError::ChangeSetNotFound)?;
+    change_set.sort_actions(&ctx).await?;
+
+    WsEvent::component_created(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "component_template_instantiated",
+        serde_json::json!({
+                    "template_id": template.id(),
+                    "template_name": template.name(),
+                    "component_count": instances.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    response = response.header("content-type", "application/json");
+    Ok(response.body(serde_json::to_string(
+        &InstantiateComponentTemplateResponse {
+            components: instances
+                .into_iter()
+                .map(|instance| InstantiatedComponentView {
+                    key: instance.key,
+                    component_id: instance.component_id,
+                    node_id: instance.node_id,
+                })
+                .collect(),
+        },
+    )?)?)
+}
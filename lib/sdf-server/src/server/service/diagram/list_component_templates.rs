@@ -0,0 +1,52 @@
+use axum::extract::{Json, Query};
+use dal::{
+    ComponentTemplate, ComponentTemplateId, ComponentTemplateParameter, StandardModel, Visibility,
+};
+use serde::{Deserialize, Serialize};
+
+use super::DiagramResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListComponentTemplatesRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentTemplateView {
+    id: ComponentTemplateId,
+    name: String,
+    description: Option<String>,
+    component_count: usize,
+    parameters: Vec<ComponentTemplateParameter>,
+}
+
+pub type ListComponentTemplatesResponse = Vec<ComponentTemplateView>;
+
+pub async fn list_component_templates(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListComponentTemplatesRequest>,
+) -> DiagramResult<Json<ListComponentTemplatesResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut templates = ComponentTemplate::list(&ctx).await?;
+    templates.sort_by(|a, b| a.name().cmp(b.name()));
+
+    let views = templates
+        .into_iter()
+        .map(|template| ComponentTemplateView {
+            id: *template.id(),
+            name: template.name().to_owned(),
+            description: template.description().map(ToOwned::to_owned),
+            component_count: template.definition().components.len(),
+            parameters: template.definition().parameters.clone(),
+        })
+        .collect();
+
+    Ok(Json(views))
+}
@@ -0,0 +1,48 @@
+use axum::extract::{Json, Query};
+use dal::resource_import::{ImportAttributeMapping, ImportMappingId};
+use dal::{ImportMapping, ImportSource, SchemaVariantId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::DiagramResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListImportMappingsRequest {
+    pub schema_variant_id: SchemaVariantId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportMappingView {
+    id: ImportMappingId,
+    source: ImportSource,
+    resource_type: String,
+    attributes: Vec<ImportAttributeMapping>,
+}
+
+pub type ListImportMappingsResponse = Vec<ImportMappingView>;
+
+pub async fn list_import_mappings(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<ListImportMappingsRequest>,
+) -> DiagramResult<Json<ListImportMappingsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let views = ImportMapping::list_for_schema_variant(&ctx, request.schema_variant_id)
+        .await?
+        .into_iter()
+        .map(|mapping| ImportMappingView {
+            id: *mapping.id(),
+            source: *mapping.source(),
+            resource_type: mapping.resource_type().to_owned(),
+            attributes: mapping.attributes().to_vec(),
+        })
+        .collect();
+
+    Ok(Json(views))
+}
@@ -8,8 +8,9 @@ use serde::{Deserialize, Serialize};
 
 use super::{DiagramError, DiagramResult};
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListSchemaVariantsRequest {
     #[serde(flatten)]
@@ -18,14 +19,14 @@ pub struct ListSchemaVariantsRequest {
 
 pub type ProviderMetadata = String;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OutputProviderView {
     id: ExternalProviderId,
     ty: ProviderMetadata,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OutputSocketView {
     id: SocketId,
@@ -34,14 +35,14 @@ pub struct OutputSocketView {
     provider: OutputProviderView,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InputProviderView {
     id: InternalProviderId,
     ty: ProviderMetadata,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InputSocketView {
     id: SocketId,
@@ -50,7 +51,7 @@ pub struct InputSocketView {
     provider: InputProviderView,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SchemaVariantView {
     id: SchemaVariantId,
@@ -58,10 +59,13 @@ pub struct SchemaVariantView {
     name: String,
     schema_name: String,
     schema_id: SchemaId,
+    /// Whether this is the variant components of the schema are created from.
+    is_default: bool,
     color: String,
     input_sockets: Vec<InputSocketView>,
     output_sockets: Vec<OutputSocketView>,
 }
+
 pub type ListSchemaVariantsResponse = Vec<SchemaVariantView>;
 
 pub async fn list_schema_variants(
@@ -130,6 +134,7 @@ pub async fn list_schema_variants(
             name: variant.name().to_owned(),
             schema_id: *schema.id(),
             schema_name: schema.name().to_owned(),
+            is_default: schema.default_schema_variant_id() == Some(variant.id()),
             color: variant
                 .color(&ctx)
                 .await?
@@ -0,0 +1,38 @@
+use axum::Json;
+use dal::{Manifest, ManifestPlan, Visibility};
+use serde::{Deserialize, Serialize};
+
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+use crate::service::diagram::DiagramResult;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanManifestRequest {
+    /// The manifest, written in YAML or JSON.
+    pub manifest: String,
+    /// Whether components the manifest does not describe are deleted.
+    #[serde(default)]
+    pub prune: bool,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanManifestResponse {
+    pub plan: ManifestPlan,
+}
+
+pub async fn plan_manifest(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<PlanManifestRequest>,
+) -> DiagramResult<Json<PlanManifestResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let manifest = Manifest::parse(&request.manifest)?;
+    let plan = manifest.plan(&ctx, request.prune).await?;
+
+    Ok(Json(PlanManifestResponse { plan }))
+}
@@ -0,0 +1,36 @@
+use axum::Json;
+use dal::{ImportSource, ResourceImport, ResourceImportPlan, Visibility};
+use serde::{Deserialize, Serialize};
+
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+use crate::service::diagram::DiagramResult;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanResourceImportRequest {
+    pub source: ImportSource,
+    /// The contents of a Terraform state file or a CloudFormation template.
+    pub content: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanResourceImportResponse {
+    pub plan: ResourceImportPlan,
+}
+
+pub async fn plan_resource_import(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<PlanResourceImportRequest>,
+) -> DiagramResult<Json<PlanResourceImportResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let import = ResourceImport::parse(request.source, &request.content)?;
+    let plan = import.plan(&ctx).await?;
+
+    Ok(Json(PlanResourceImportResponse { plan }))
+}
@@ -0,0 +1,96 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use dal::{
+    ChangeSet, ComponentId, ComponentTemplate, ComponentTemplateId, ComponentTemplateParameter,
+    StandardModel, Visibility, WsEvent,
+};
+
+use super::DiagramResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveComponentTemplateRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub component_ids: Vec<ComponentId>,
+    #[serde(default)]
+    pub parameters: Vec<ComponentTemplateParameter>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveComponentTemplateResponse {
+    pub template_id: ComponentTemplateId,
+}
+
+pub async fn save_component_template(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<SaveComponentTemplateRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut force_changeset_pk = None;
+    if ctx.visibility().is_head() {
+        let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+        let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+        ctx.update_visibility(new_visibility);
+
+        force_changeset_pk = Some(change_set.pk);
+
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    };
+
+    let template = ComponentTemplate::capture(
+        &ctx,
+        &request.name,
+        request.description.as_deref(),
+        &request.component_ids,
+        request.parameters,
+    )
+    .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "component_template_saved",
+        serde_json::json!({
+                    "template_id": template.id(),
+                    "template_name": template.name(),
+                    "component_count": request.component_ids.len(),
+        }),
+    );
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    response = response.header("content-type", "application/json");
+    Ok(
+        response.body(serde_json::to_string(&SaveComponentTemplateResponse {
+            template_id: *template.id(),
+        })?)?,
+    )
+}
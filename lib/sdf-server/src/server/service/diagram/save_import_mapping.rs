@@ -0,0 +1,56 @@
+use axum::Json;
+use dal::resource_import::{ImportAttributeMapping, ImportMappingId};
+use dal::{ImportMapping, ImportSource, SchemaVariantId, StandardModel, Visibility, WsEvent};
+use serde::{Deserialize, Serialize};
+
+use super::DiagramResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveImportMappingRequest {
+    pub schema_variant_id: SchemaVariantId,
+    pub source: ImportSource,
+    /// Like `aws_instance` or `AWS::EC2::Instance`.
+    pub resource_type: String,
+    #[serde(default)]
+    pub attributes: Vec<ImportAttributeMapping>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveImportMappingResponse {
+    pub mapping_id: ImportMappingId,
+}
+
+/// Creates the mapping for the source and resource type, or replaces the existing one.
+pub async fn save_import_mapping(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<SaveImportMappingRequest>,
+) -> DiagramResult<Json<SaveImportMappingResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mapping = ImportMapping::upsert(
+        &ctx,
+        request.schema_variant_id,
+        request.source,
+        &request.resource_type,
+        request.attributes,
+    )
+    .await?;
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(SaveImportMappingResponse {
+        mapping_id: *mapping.id(),
+    }))
+}
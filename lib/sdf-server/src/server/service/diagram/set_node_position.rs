@@ -1,5 +1,6 @@
 use super::DiagramResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use crate::service::diagram::DiagramError;
 use axum::Json;
 use dal::node::NodeId;
@@ -7,7 +8,7 @@ use dal::socket::SocketEdgeKind;
 use dal::{Node, StandardModel, Visibility, WsEvent};
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SetNodePositionRequest {
     #[serde(flatten)]
@@ -19,7 +20,7 @@ pub struct SetNodePositionRequest {
     pub height: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SetNodePositionResponse {
     pub node: Node,
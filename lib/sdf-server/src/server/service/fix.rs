@@ -1,19 +1,18 @@
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::{get, post},
-    Json, Router,
+    Json,
 };
 use thiserror::Error;
 
 use dal::fix::FixError as DalFixError;
 use dal::schema::SchemaError as DalSchemaError;
 use dal::{
-    ComponentError, ComponentId, FixResolverError, FuncBindingReturnValueError, StandardModelError,
-    TransactionsError, UserError, UserPk,
+    ApiTokenPk, ComponentError, ComponentId, FixResolverError, FuncBindingReturnValueError,
+    StandardModelError, TransactionsError, UserError, UserPk,
 };
 
-use crate::server::state::AppState;
+use crate::server::openapi::{schema_of, ApiRoutes};
 
 pub mod list;
 pub mod run;
@@ -33,6 +32,8 @@ pub enum FixError {
     FixResolver(#[from] FixResolverError),
     #[error(transparent)]
     FuncBindingReturnValue(#[from] FuncBindingReturnValueError),
+    #[error("invalid api token {0}")]
+    InvalidApiToken(ApiTokenPk),
     #[error("invalid user {0}")]
     InvalidUser(UserPk),
     #[error("invalid user system init")]
@@ -53,7 +54,12 @@ pub type FixResult<T> = std::result::Result<T, FixError>;
 
 impl IntoResponse for FixError {
     fn into_response(self) -> Response {
-        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+        let (status, error_message) = match self {
+            FixError::User(UserError::Unauthorized(_, _)) => {
+                (StatusCode::FORBIDDEN, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
 
         let body = Json(
             serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
@@ -63,8 +69,18 @@ impl IntoResponse for FixError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route("/list", get(list::list))
-        .route("/run", post(run::run))
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .get(
+            "/list",
+            list::list,
+            schema_of!(list::ListFixesRequest),
+            schema_of!(list::ListFixesResponse),
+        )
+        .post(
+            "/run",
+            run::run,
+            schema_of!(run::FixesRunRequest),
+            schema_of!(run::FixesRunResponse),
+        )
 }
@@ -7,15 +7,16 @@ use serde::{Deserialize, Serialize};
 
 use super::FixResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListFixesRequest {
     #[serde(flatten)]
     pub visibility: Visibility,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchHistoryView {
     pub id: FixBatchId,
@@ -4,21 +4,22 @@ use serde::{Deserialize, Serialize};
 
 use super::{FixError, FixResult};
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use dal::job::definition::{FixItem, FixesJob};
 use dal::{
     ActionPrototypeId, ComponentId, Fix, FixBatch, FixBatchId, HistoryActor, StandardModel, User,
-    Visibility,
+    UserCapability, Visibility,
 };
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FixRunRequest {
     pub component_id: ComponentId,
     pub action_prototype_id: ActionPrototypeId,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FixesRunRequest {
     pub list: Vec<FixRunRequest>,
@@ -26,7 +27,7 @@ pub struct FixesRunRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FixesRunResponse {
     pub id: FixBatchId,
@@ -41,11 +42,19 @@ pub async fn run(
 ) -> FixResult<Json<FixesRunResponse>> {
     let ctx = builder.build(request_ctx.build(request.visibility)).await?;
 
+    // Fixes run in a job, so we refuse them here rather than have the job fail.
+    User::ensure_authorized(&ctx, UserCapability::RunFix).await?;
+
     let user = match ctx.history_actor() {
         HistoryActor::User(user_pk) => User::get_by_pk(&ctx, *user_pk)
             .await?
             .ok_or(FixError::InvalidUser(*user_pk))?,
 
+        HistoryActor::ApiToken(api_token_pk) => {
+            User::get_for_history_actor(&ctx, ctx.history_actor())
+                .await?
+                .ok_or(FixError::InvalidApiToken(*api_token_pk))?
+        }
         HistoryActor::SystemInit => return Err(FixError::InvalidUserSystemInit),
     };
     let batch = FixBatch::new(&ctx, user.email()).await?;
@@ -1,10 +1,9 @@
-use crate::server::{impl_default_error_into_response, state::AppState};
-use crate::service::func::get_func::GetFuncResponse;
-use axum::{
-    response::Response,
-    routing::{get, post},
-    Json, Router,
+use crate::server::{
+    impl_default_error_into_response,
+    openapi::{schema_of, ApiRoutes, ApiSchema},
 };
+use crate::service::func::get_func::GetFuncResponse;
+use axum::{response::Response, Json};
 use dal::func::execution::FuncExecutionError;
 use dal::{
     attribute::context::{AttributeContextBuilder, AttributeContextBuilderError},
@@ -194,7 +193,7 @@ impl_default_error_into_response!(FuncError);
 // Variants don't map 1:1 onto FuncBackendKind, since some JsAttribute functions
 // are a special case (Qualification, CodeGeneration etc)
 #[remain::sorted]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Copy, ApiSchema)]
 pub enum FuncVariant {
     Action,
     Attribute,
@@ -235,7 +234,7 @@ impl TryFrom<&Func> for FuncVariant {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AttributePrototypeArgumentView {
     func_argument_id: FuncArgumentId,
@@ -244,7 +243,7 @@ pub struct AttributePrototypeArgumentView {
     internal_provider_id: Option<InternalProviderId>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AttributePrototypeView {
     id: AttributePrototypeId,
@@ -271,7 +270,7 @@ impl AttributePrototypeView {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ValidationPrototypeView {
     schema_variant_id: SchemaVariantId,
@@ -279,7 +278,7 @@ pub struct ValidationPrototypeView {
 }
 
 #[remain::sorted]
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, ApiSchema)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum FuncAssociations {
     #[serde(rename_all = "camelCase")]
@@ -314,7 +313,7 @@ pub enum FuncAssociations {
     },
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FuncArgumentView {
     pub id: FuncArgumentId,
@@ -875,22 +874,66 @@ fn langjs_types() -> &'static str {
 }"
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route("/list_funcs", get(list_funcs::list_funcs))
-        .route("/get_func", get(get_func::get_func))
-        .route(
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .get(
+            "/list_funcs",
+            list_funcs::list_funcs,
+            schema_of!(list_funcs::ListFuncsRequest),
+            schema_of!(list_funcs::ListFuncsResponse),
+        )
+        .get(
+            "/get_func",
+            get_func::get_func,
+            schema_of!(get_func::GetFuncRequest),
+            schema_of!(get_func::GetFuncResponse),
+        )
+        .get(
             "/get_func_last_execution",
-            get(get_func::get_latest_func_execution),
+            get_func::get_latest_func_execution,
+            schema_of!(get_func::GetLatestFuncExecutionRequest),
+            schema_of!(get_func::GetLatestFuncExecutionResponse),
+        )
+        .post(
+            "/create_func",
+            create_func::create_func,
+            schema_of!(create_func::CreateFuncRequest),
+            schema_of!(create_func::CreateFuncResponse),
+        )
+        .post(
+            "/save_func",
+            save_func::save_func,
+            schema_of!(save_func::SaveFuncRequest),
+            schema_of!(save_func::SaveFuncResponse),
+        )
+        .post(
+            "/delete_func",
+            delete_func::delete_func,
+            schema_of!(delete_func::DeleteFuncRequest),
+            schema_of!(delete_func::DeleteFuncResponse),
+        )
+        .post(
+            "/save_and_exec",
+            save_and_exec::save_and_exec,
+            schema_of!(save_func::SaveFuncRequest),
+            schema_of!(save_func::SaveFuncResponse),
+        )
+        .post(
+            "/execute",
+            execute::execute,
+            schema_of!(execute::ExecuteRequest),
+            schema_of!(execute::ExecuteResponse),
+        )
+        .post(
+            "/revert_func",
+            revert_func::revert_func,
+            schema_of!(revert_func::RevertFuncRequest),
+            schema_of!(revert_func::RevertFuncResponse),
         )
-        .route("/create_func", post(create_func::create_func))
-        .route("/save_func", post(save_func::save_func))
-        .route("/delete_func", post(delete_func::delete_func))
-        .route("/save_and_exec", post(save_and_exec::save_and_exec))
-        .route("/execute", post(execute::execute))
-        .route("/revert_func", post(revert_func::revert_func))
-        .route(
+        .get(
             "/list_input_sources",
-            get(list_input_sources::list_input_sources),
+            list_input_sources::list_input_sources,
+            schema_of!(list_input_sources::ListInputSourcesRequest),
+            schema_of!(list_input_sources::ListInputSourcesResponse),
         )
 }
@@ -1,5 +1,6 @@
 use super::{FuncResult, FuncVariant};
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use crate::service::func::FuncError;
 use axum::extract::OriginalUri;
@@ -14,7 +15,7 @@ use dal::{
 use serde::{Deserialize, Serialize};
 
 #[remain::sorted]
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, ApiSchema)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum AttributeOutputLocation {
     #[serde(rename_all = "camelCase")]
@@ -26,7 +27,7 @@ pub enum AttributeOutputLocation {
 }
 
 #[remain::sorted]
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, ApiSchema)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum CreateFuncOptions {
     #[serde(rename_all = "camelCase")]
@@ -50,7 +51,7 @@ pub enum CreateFuncOptions {
     },
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateFuncRequest {
     variant: FuncVariant,
@@ -60,7 +61,7 @@ pub struct CreateFuncRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateFuncResponse {
     pub id: FuncId,
@@ -1,5 +1,6 @@
 use super::FuncResult;
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use crate::service::func::{get_func_view, FuncAssociations, FuncError};
 use axum::extract::OriginalUri;
@@ -7,7 +8,7 @@ use axum::{response::IntoResponse, Json};
 use dal::{ChangeSet, Func, FuncId, StandardModel, Visibility, WsEvent};
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteFuncRequest {
     pub id: FuncId,
@@ -15,7 +16,7 @@ pub struct DeleteFuncRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteFuncResponse {
     pub success: bool,
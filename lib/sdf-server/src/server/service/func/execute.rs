@@ -1,5 +1,6 @@
 use super::FuncResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use axum::Json;
 use dal::{
     func::binding::FuncBindingResult, func::binding::LogLinePayload, DalContext, Func, FuncBinding,
@@ -8,7 +9,7 @@ use dal::{
 use serde::{Deserialize, Serialize};
 use veritech_client::OutputStream;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteRequest {
     pub id: FuncId,
@@ -18,7 +19,7 @@ pub struct ExecuteRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteResponse {
     pub id: FuncId,
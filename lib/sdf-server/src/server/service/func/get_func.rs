@@ -1,12 +1,13 @@
 use super::{FuncAssociations, FuncError, FuncResult, FuncVariant};
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use axum::{extract::Query, Json};
 use dal::func::execution::{FuncExecution, FuncExecutionState};
 use dal::{Func, FuncId, StandardModel, Visibility};
 use serde::{Deserialize, Serialize};
 use veritech_client::{FunctionResultFailure, OutputStream};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetLatestFuncExecutionRequest {
     pub id: FuncId,
@@ -14,7 +15,7 @@ pub struct GetLatestFuncExecutionRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetLatestFuncExecutionResponse {
     pub id: FuncId,
@@ -24,7 +25,7 @@ pub struct GetLatestFuncExecutionResponse {
     pub function_failure: Option<FunctionResultFailure>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFuncRequest {
     pub id: FuncId,
@@ -32,7 +33,7 @@ pub struct GetFuncRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFuncResponse {
     pub id: FuncId,
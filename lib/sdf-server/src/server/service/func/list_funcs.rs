@@ -1,17 +1,18 @@
 use super::{FuncError, FuncResult, FuncVariant};
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use axum::{extract::Query, Json};
 use dal::{Func, FuncBackendKind, FuncId, StandardModel, Visibility};
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListFuncsRequest {
     #[serde(flatten)]
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListedFuncView {
     pub id: FuncId,
@@ -22,7 +23,7 @@ pub struct ListedFuncView {
     pub is_builtin: bool,
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListFuncsResponse {
     pub funcs: Vec<ListedFuncView>,
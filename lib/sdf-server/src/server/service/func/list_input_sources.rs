@@ -1,5 +1,6 @@
 use super::FuncResult;
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use axum::{extract::Query, Json};
 use dal::{
     prop_tree::PropTree, ExternalProvider, ExternalProviderId, InternalProvider,
@@ -8,7 +9,7 @@ use dal::{
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InputSourceSocket {
     pub schema_variant_id: SchemaVariantId,
@@ -16,7 +17,7 @@ pub struct InputSourceSocket {
     pub name: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OutputSocket {
     pub schema_variant_id: SchemaVariantId,
@@ -24,7 +25,7 @@ pub struct OutputSocket {
     pub name: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InputSourceProp {
     pub schema_variant_id: SchemaVariantId,
@@ -35,7 +36,7 @@ pub struct InputSourceProp {
     pub path: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListInputSourcesRequest {
     schema_variant_id: Option<SchemaVariantId>,
@@ -43,7 +44,7 @@ pub struct ListInputSourcesRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListInputSourcesResponse {
     pub input_sockets: Vec<InputSourceSocket>,
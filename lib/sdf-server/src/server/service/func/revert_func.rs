@@ -8,8 +8,9 @@ use serde::{Deserialize, Serialize};
 
 use super::{FuncError, FuncResult};
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RevertFuncRequest {
     pub id: FuncId,
@@ -17,7 +18,7 @@ pub struct RevertFuncRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RevertFuncResponse {
     pub success: bool,
@@ -9,6 +9,7 @@ use super::{
     FuncError, FuncResult,
 };
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use dal::{
     attribute::context::AttributeContextBuilder,
@@ -22,7 +23,7 @@ use dal::{
 };
 use dal::{FuncBackendResponseType, PropKind, SchemaVariant, ValidationPrototype};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveFuncRequest {
     pub id: FuncId,
@@ -36,7 +37,7 @@ pub struct SaveFuncRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveFuncResponse {
     pub associations: Option<FuncAssociations>,
@@ -1,8 +1,8 @@
-use crate::server::{impl_default_error_into_response, state::AppState};
+use crate::server::openapi::{schema_of, ApiRoutes, ApiSchema};
 use axum::{
-    response::Response,
-    routing::{get, post},
-    Json, Router,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
 };
 use convert_case::{Case, Casing};
 use dal::{
@@ -11,7 +11,7 @@ use dal::{
     UserError, WorkspaceError, WorkspacePk, WsEventError,
 };
 use serde::{Deserialize, Serialize};
-use si_pkg::{SiPkg, SiPkgError};
+use si_pkg::{LintReport, SiPkg, SiPkgError};
 use si_settings::{safe_canonically_join, CanonicalFileError};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -20,13 +20,16 @@ use tokio::fs::read_dir;
 const PKG_EXTENSION: &str = "sipkg";
 const MAX_NAME_SEARCH_ATTEMPTS: usize = 100;
 
+pub mod backup_workspace;
 pub mod export_pkg;
 pub mod export_workspace;
 pub mod get_pkg;
 pub mod install_pkg;
 pub mod list_pkgs;
+pub mod list_workspace_backups;
 mod reject_pkg;
 pub mod remote_module_spec;
+pub mod restore_workspace_backup;
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -58,6 +61,8 @@ pub enum PkgError {
     PackageAlreadyOnDisk(String),
     #[error("No schema variants added to package export")]
     PackageExportEmpty,
+    #[error("Package failed lint: {0}")]
+    PackageLint(LintReport),
     #[error("Package name required")]
     PackageNameEmpty,
     #[error("Package could not be found: {0}")]
@@ -98,9 +103,33 @@ pub enum PkgError {
 
 pub type PkgResult<T> = Result<T, PkgError>;
 
-impl_default_error_into_response!(PkgError);
+impl IntoResponse for PkgError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            PkgError::ModuleIndex(module_index_client::IndexClientError::Lint(_))
+            | PkgError::PackageLint(_) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
+            PkgError::User(
+                UserError::ApiTokenUnauthorized(_, _) | UserError::Unauthorized(_, _),
+            ) => (StatusCode::FORBIDDEN, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+        let lint_issues = match self {
+            PkgError::ModuleIndex(module_index_client::IndexClientError::Lint(lint_report))
+            | PkgError::PackageLint(lint_report) => Some(lint_report.issues),
+            _ => None,
+        };
+
+        let mut error = serde_json::json!({ "message": error_message, "code": 42, "statusCode": status.as_u16() });
+        if let Some(lint_issues) = lint_issues {
+            error["lintIssues"] = serde_json::json!(lint_issues);
+        }
+        let body = Json(serde_json::json!({ "error": error }));
 
-#[derive(Deserialize, Serialize, Debug)]
+        (status, body).into_response()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PkgView {
     name: String,
@@ -188,19 +217,66 @@ pub async fn pkg_open(builder: &DalContextBuilder, file_name: &str) -> PkgResult
     Ok(SiPkg::load_from_file(&real_pkg_path).await?)
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route("/export_pkg", post(export_pkg::export_pkg))
-        .route(
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .post(
+            "/backup_workspace",
+            backup_workspace::backup_workspace,
+            schema_of!(backup_workspace::BackupWorkspaceRequest),
+            schema_of!(backup_workspace::BackupWorkspaceResponse),
+        )
+        .post(
+            "/export_pkg",
+            export_pkg::export_pkg,
+            schema_of!(export_pkg::ExportPkgRequest),
+            schema_of!(export_pkg::ExportPkgResponse),
+        )
+        .post(
             "/export_workspace",
-            post(export_workspace::export_workspace),
+            export_workspace::export_workspace,
+            schema_of!(export_workspace::ExportWorkspaceRequest),
+            schema_of!(export_workspace::ExportWorkspaceResponse),
+        )
+        .get(
+            "/get_module_by_hash",
+            get_pkg::get_module_by_hash,
+            schema_of!(get_pkg::PkgGetRequest),
+            schema_of!(get_pkg::PkgGetResponse),
+        )
+        .post(
+            "/install_pkg",
+            install_pkg::install_pkg,
+            schema_of!(install_pkg::InstallPkgRequest),
+            schema_of!(install_pkg::InstallPkgResponse),
+        )
+        .get(
+            "/list_pkgs",
+            list_pkgs::list_pkgs,
+            schema_of!(list_pkgs::PkgListRequest),
+            schema_of!(list_pkgs::PkgListResponse),
         )
-        .route("/get_module_by_hash", get(get_pkg::get_module_by_hash))
-        .route("/install_pkg", post(install_pkg::install_pkg))
-        .route("/list_pkgs", get(list_pkgs::list_pkgs))
-        .route(
+        .get(
+            "/list_workspace_backups",
+            list_workspace_backups::list_workspace_backups,
+            schema_of!(list_workspace_backups::ListWorkspaceBackupsRequest),
+            schema_of!(list_workspace_backups::ListWorkspaceBackupsResponse),
+        )
+        .get(
             "/remote_module_spec",
-            get(remote_module_spec::remote_module_spec),
+            remote_module_spec::remote_module_spec,
+            schema_of!(remote_module_spec::RemoteModuleDetailsRequest),
+            schema_of!(remote_module_spec::RemoteModuleDetailsResponse),
+        )
+        .post(
+            "/reject_pkg",
+            reject_pkg::reject_pkg,
+            schema_of!(reject_pkg::RejectPkgRequest),
+            schema_of!(reject_pkg::RejectPkgResponse),
+        )
+        .post(
+            "/restore_workspace_backup",
+            restore_workspace_backup::restore_workspace_backup,
+            schema_of!(restore_workspace_backup::RestoreWorkspaceBackupRequest),
+            schema_of!(restore_workspace_backup::RestoreWorkspaceBackupResponse),
         )
-        .route("/reject_pkg", post(reject_pkg::reject_pkg))
 }
@@ -0,0 +1,105 @@
+use super::{PkgError, PkgResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RawAccessToken};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+use axum::extract::OriginalUri;
+use axum::Json;
+use chrono::Utc;
+use dal::{HistoryActor, User, Visibility, Workspace, WorkspacePk};
+use module_index_client::{IndexClient, WorkspaceBackupResponse};
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupWorkspaceRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupWorkspaceResponse {
+    pub backup: WorkspaceBackupResponse,
+}
+
+/// Takes an incremental backup of the current workspace. Only the parts of the workspace which
+/// have changed since they were last backed up are uploaded to the module index.
+pub async fn backup_workspace(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    RawAccessToken(raw_access_token): RawAccessToken,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<BackupWorkspaceRequest>,
+) -> PkgResult<Json<BackupWorkspaceResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let user = match ctx.history_actor() {
+        HistoryActor::User(user_pk) => User::get_by_pk(&ctx, *user_pk).await?,
+        _ => None,
+    };
+
+    let (created_by_name, created_by_email) = user
+        .map(|user| (user.name().to_owned(), user.email().to_owned()))
+        .unwrap_or((
+            "unauthenticated user name".into(),
+            "unauthenticated user email".into(),
+        ));
+
+    info!("Backing up workspace");
+
+    let workspace_pk = ctx.tenancy().workspace_pk().unwrap_or(WorkspacePk::NONE);
+    let workspace = Workspace::get_by_pk(&ctx, &workspace_pk)
+        .await?
+        .ok_or(PkgError::WorkspaceNotFound(workspace_pk))?;
+
+    let version = Utc::now().format("%Y-%m-%d_%H:%M:%S").to_string();
+    let description = "workspace backup";
+
+    let mut exporter = dal::pkg::PkgExporter::new_workspace_exporter(
+        workspace.name().as_str(),
+        &created_by_email,
+        &version,
+        description,
+    );
+
+    let pkg = exporter.export(&ctx).await?;
+    let root_hash = pkg.hash()?.to_string();
+    let nodes = pkg
+        .serialized_nodes()?
+        .into_iter()
+        .map(|node| (node.hash().to_string(), node.into_bytes()))
+        .collect();
+
+    let module_index_url = match ctx.module_index_url() {
+        Some(url) => url,
+        None => return Err(PkgError::ModuleIndexNotConfigured),
+    };
+
+    let index_client = IndexClient::new(module_index_url.try_into()?, &raw_access_token);
+    let backup = index_client
+        .upload_workspace_backup(
+            workspace.name().as_str(),
+            Some(created_by_name.clone()),
+            &root_hash,
+            nodes,
+        )
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "backup_workspace",
+        serde_json::json!({
+                    "backup_id": backup.id,
+                    "backup_node_count": backup.node_count,
+                    "backup_new_node_count": backup.new_node_count,
+                    "backup_created_by_name": created_by_name,
+                    "backup_created_by_email": created_by_email,
+        }),
+    );
+
+    Ok(Json(BackupWorkspaceResponse { backup }))
+}
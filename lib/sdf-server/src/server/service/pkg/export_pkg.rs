@@ -1,13 +1,15 @@
 use super::{PkgError, PkgResult};
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RawAccessToken};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::Json;
 use dal::{HistoryActor, SchemaVariant, SchemaVariantId, StandardModel, User, Visibility, WsEvent};
 use serde::{Deserialize, Serialize};
+use si_pkg::SiPkg;
 use telemetry::prelude::*;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportPkgRequest {
     pub name: String,
@@ -18,7 +20,7 @@ pub struct ExportPkgRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportPkgResponse {
     pub success: bool,
@@ -86,7 +88,17 @@ pub async fn export_pkg(
         schema_ids,
     );
 
-    let module_payload = exporter.export_as_bytes(&ctx).await?;
+    let spec = exporter.export_as_spec(&ctx).await?;
+
+    let lint_report = spec.lint();
+    for warning in lint_report.warnings() {
+        warn!("{}", warning);
+    }
+    if lint_report.has_errors() {
+        return Err(PkgError::PackageLint(lint_report));
+    }
+
+    let module_payload = SiPkg::load_from_spec(spec)?.write_to_bytes()?;
 
     let index_client =
         module_index_client::IndexClient::new(module_index_url.try_into()?, &raw_access_token);
@@ -1,5 +1,6 @@
 use super::{PkgError, PkgResult};
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RawAccessToken};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::Json;
@@ -8,14 +9,14 @@ use dal::{HistoryActor, User, Visibility, Workspace, WorkspacePk, WsEvent};
 use serde::{Deserialize, Serialize};
 use telemetry::prelude::*;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportWorkspaceRequest {
     #[serde(flatten)]
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportWorkspaceResponse {
     pub success: bool,
@@ -6,10 +6,11 @@ use std::cmp::{Ord, PartialOrd};
 
 use super::{pkg_open, PkgError, PkgResult};
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PkgGetRequest {
     pub hash: String,
@@ -17,7 +18,7 @@ pub struct PkgGetRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PkgFuncView {
     pub name: String,
@@ -37,7 +38,7 @@ impl PartialOrd for PkgFuncView {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PkgGetResponse {
     pub name: String,
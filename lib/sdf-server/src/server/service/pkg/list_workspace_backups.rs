@@ -0,0 +1,39 @@
+use super::{PkgError, PkgResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, RawAccessToken};
+use crate::server::openapi::ApiSchema;
+use axum::{extract::Query, Json};
+use dal::Visibility;
+use module_index_client::{IndexClient, WorkspaceBackupResponse};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWorkspaceBackupsRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWorkspaceBackupsResponse {
+    pub backups: Vec<WorkspaceBackupResponse>,
+}
+
+pub async fn list_workspace_backups(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    RawAccessToken(raw_access_token): RawAccessToken,
+    Query(request): Query<ListWorkspaceBackupsRequest>,
+) -> PkgResult<Json<ListWorkspaceBackupsResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let module_index_url = match ctx.module_index_url() {
+        Some(url) => url,
+        None => return Err(PkgError::ModuleIndexNotConfigured),
+    };
+
+    let index_client = IndexClient::new(module_index_url.try_into()?, &raw_access_token);
+    let backups = index_client.list_workspace_backups().await?.backups;
+
+    Ok(Json(ListWorkspaceBackupsResponse { backups }))
+}
@@ -1,5 +1,6 @@
 use super::PkgResult;
 use crate::server::extract::RawAccessToken;
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use crate::{
     server::extract::{AccessBuilder, HandlerContext, PosthogClient},
@@ -12,7 +13,7 @@ use module_index_client::IndexClient;
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RejectPkgRequest {
     pub id: Ulid,
@@ -20,7 +21,7 @@ pub struct RejectPkgRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RejectPkgResponse {
     pub success: bool,
@@ -1,5 +1,6 @@
 use super::PkgResult;
 use crate::server::extract::RawAccessToken;
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use crate::{
     server::extract::{AccessBuilder, HandlerContext, PosthogClient},
@@ -13,7 +14,7 @@ use serde::{Deserialize, Serialize};
 use si_pkg::SiPkg;
 use ulid::Ulid;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoteModuleDetailsRequest {
     pub id: Ulid,
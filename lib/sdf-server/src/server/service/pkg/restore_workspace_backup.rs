@@ -0,0 +1,80 @@
+use std::io::Cursor;
+
+use super::{PkgError, PkgResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RawAccessToken};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{
+    pkg::restore_workspace_backup as restore_backup, User, UserCapability, Visibility, WsEvent,
+};
+use module_index_client::IndexClient;
+use serde::{Deserialize, Serialize};
+use si_pkg::SiPkg;
+use telemetry::prelude::*;
+use ulid::Ulid;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreWorkspaceBackupRequest {
+    pub backup_id: Ulid,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreWorkspaceBackupResponse {
+    pub success: bool,
+}
+
+/// Restores the current workspace, including its change sets, to the chosen backup point. Since
+/// this replaces everything in the workspace, only those who manage it can do so.
+pub async fn restore_workspace_backup(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    RawAccessToken(raw_access_token): RawAccessToken,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RestoreWorkspaceBackupRequest>,
+) -> PkgResult<Json<RestoreWorkspaceBackupResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    User::ensure_authorized(&ctx, UserCapability::ManageRoles).await?;
+
+    let module_index_url = match ctx.module_index_url() {
+        Some(url) => url,
+        None => return Err(PkgError::ModuleIndexNotConfigured),
+    };
+
+    info!(backup_id = %request.backup_id, "Restoring workspace backup");
+
+    let index_client = IndexClient::new(module_index_url.try_into()?, &raw_access_token);
+    let backup_data = index_client
+        .download_workspace_backup(request.backup_id)
+        .await?;
+
+    // Backups hold every change set of the workspace, so their nodes are only parsed as the
+    // restore gets to them.
+    let pkg = SiPkg::load_from_reader(Cursor::new(backup_data))?;
+    restore_backup(&ctx, &pkg).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "restore_workspace_backup",
+        serde_json::json!({
+                    "backup_id": request.backup_id,
+        }),
+    );
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+    ctx.commit().await?;
+
+    Ok(Json(RestoreWorkspaceBackupResponse { success: true }))
+}
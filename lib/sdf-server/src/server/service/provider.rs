@@ -1,12 +1,12 @@
 use axum::response::Response;
-use axum::{http::StatusCode, response::IntoResponse, routing::get, Json, Router};
+use axum::{http::StatusCode, response::IntoResponse, Json};
 use dal::provider::external::ExternalProviderError;
 use dal::provider::internal::InternalProviderError;
 use dal::{StandardModelError, TransactionsError};
 
 use thiserror::Error;
 
-use crate::server::state::AppState;
+use crate::server::openapi::{schema_of, ApiRoutes};
 
 pub mod list_all_providers;
 
@@ -47,9 +47,11 @@ impl IntoResponse for ProviderError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new().route(
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new().get(
         "/list_all_providers",
-        get(list_all_providers::list_all_providers),
+        list_all_providers::list_all_providers,
+        schema_of!(list_all_providers::ListAllProviderRequest),
+        schema_of!(list_all_providers::ListAllProviderResponse),
     )
 }
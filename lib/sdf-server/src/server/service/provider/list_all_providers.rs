@@ -4,9 +4,10 @@ use dal::{ExternalProvider, InternalProvider, SchemaVariantId, Visibility};
 use serde::{Deserialize, Serialize};
 
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use crate::service::provider::ProviderResult;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListAllProviderRequest {
     pub schema_variant_id: SchemaVariantId,
@@ -14,7 +15,7 @@ pub struct ListAllProviderRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListAllProviderResponse {
     pub internal_providers: Vec<InternalProvider>,
@@ -3,8 +3,7 @@ use std::string::FromUtf8Error;
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::get,
-    Json, Router,
+    Json,
 };
 
 use thiserror::Error;
@@ -15,7 +14,7 @@ use dal::{
     StandardModelError, TenancyError, TransactionsError,
 };
 
-use crate::server::state::AppState;
+use crate::server::openapi::{schema_of, ApiRoutes};
 
 pub mod get_summary;
 
@@ -85,6 +84,11 @@ impl IntoResponse for QualificationError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new().route("/get_summary", get(get_summary::get_summary))
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new().get(
+        "/get_summary",
+        get_summary::get_summary,
+        schema_of!(get_summary::GetSummaryRequest),
+        schema_of!(get_summary::GetSummaryResponse),
+    )
 }
@@ -6,9 +6,10 @@ use dal::qualification::QualificationSummary;
 use dal::Visibility;
 
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 use crate::service::qualification::QualificationResult;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSummaryRequest {
     #[serde(flatten)]
@@ -1,12 +1,10 @@
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
-use axum::routing::{get, post};
 use axum::Json;
-use axum::Router;
 use dal::{SchemaError as DalSchemaError, StandardModelError, TransactionsError, WsEventError};
 use thiserror::Error;
 
-use crate::server::state::AppState;
+use crate::server::openapi::{schema_of, ApiRoutes};
 
 pub mod create_schema;
 pub mod get_schema;
@@ -48,9 +46,24 @@ impl IntoResponse for SchemaError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route("/create_schema", post(create_schema::create_schema))
-        .route("/list_schemas", get(list_schemas::list_schemas))
-        .route("/get_schema", get(get_schema::get_schema))
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .post(
+            "/create_schema",
+            create_schema::create_schema,
+            schema_of!(create_schema::CreateSchemaRequest),
+            schema_of!(create_schema::CreateSchemaResponse),
+        )
+        .get(
+            "/list_schemas",
+            list_schemas::list_schemas,
+            schema_of!(list_schemas::ListSchemaRequest),
+            schema_of!(list_schemas::ListSchemaResponse),
+        )
+        .get(
+            "/get_schema",
+            get_schema::get_schema,
+            schema_of!(get_schema::GetSchemaRequest),
+            schema_of!(get_schema::GetSchemaResponse),
+        )
 }
@@ -4,8 +4,9 @@ use serde::{Deserialize, Serialize};
 
 use super::{SchemaError, SchemaResult};
 use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSchemaRequest {
     pub schema_id: SchemaId,
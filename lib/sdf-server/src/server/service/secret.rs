@@ -2,9 +2,7 @@ use std::collections::HashMap;
 
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
-use axum::routing::{delete, get, post};
 use axum::Json;
-use axum::Router;
 use chrono::Utc;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
@@ -18,7 +16,7 @@ use dal::{
 };
 
 use crate::server::extract::{AccessBuilder, HandlerContext};
-use crate::server::state::AppState;
+use crate::server::openapi::{schema_of, ApiRoutes, ApiSchema};
 
 pub mod get_public_key;
 
@@ -50,7 +48,7 @@ pub enum SecretError {
 pub type SecretResult<T> = std::result::Result<T, SecretError>;
 
 // NOTE(victor): This is a temporary struct created only for the static array storage to work
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Secret {
     id: SecretId,
@@ -81,15 +79,35 @@ impl IntoResponse for SecretError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route("/get_public_key", get(get_public_key::get_public_key))
-        .route("/", post(create_secret))
-        .route("/", get(list_secrets))
-        .route("/", delete(delete_secrets))
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .get(
+            "/get_public_key",
+            get_public_key::get_public_key,
+            schema_of!(()),
+            schema_of!(get_public_key::GetPublicKeyResponse),
+        )
+        .post(
+            "/",
+            create_secret,
+            schema_of!(CreateSecretRequest),
+            schema_of!(CreateSecretResponse),
+        )
+        .get(
+            "/",
+            list_secrets,
+            schema_of!(()),
+            schema_of!(ListSecretResponse),
+        )
+        .delete(
+            "/",
+            delete_secrets,
+            schema_of!(DeleteSecretRequest),
+            schema_of!(()),
+        )
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateSecretRequest {
     name: String,
@@ -144,7 +162,7 @@ pub async fn list_secrets() -> SecretResult<Json<ListSecretResponse>> {
     Ok(Json((*SECRETOS.lock().await).clone()))
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteSecretRequest {
     id: SecretId,
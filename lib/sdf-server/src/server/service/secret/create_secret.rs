@@ -5,11 +5,12 @@ use dal::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::server::openapi::ApiSchema;
 use crate::server::extract::{AccessBuilder, HandlerContext};
 
 use super::SecretResult;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateSecretRequest {
     pub name: String,
@@ -23,7 +24,7 @@ pub struct CreateSecretRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateSecretResponse {
     pub secret: Secret,
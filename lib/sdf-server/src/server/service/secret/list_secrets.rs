@@ -4,16 +4,17 @@ use dal::{secret::SecretView, Secret, StandardModel, Visibility};
 use serde::{Deserialize, Serialize};
 
 use super::SecretResult;
+use crate::server::openapi::ApiSchema;
 use crate::server::extract::{AccessBuilder, HandlerContext};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListSecretRequest {
     #[serde(flatten)]
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ListSecretResponse {
     pub list: Vec<SecretView>,
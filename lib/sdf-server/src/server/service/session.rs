@@ -1,19 +1,19 @@
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
-use axum::routing::{get, post};
 use axum::Json;
-use axum::Router;
 use dal::{
     KeyPairError, StandardModelError, TransactionsError, UserError, UserPk, WorkspaceError,
     WorkspacePk,
 };
 use thiserror::Error;
 
-use crate::server::state::AppState;
+use crate::server::openapi::{schema_of, ApiRoutes};
 
 pub mod auth_connect;
+pub mod list_members;
 pub mod load_workspace;
 pub mod restore_authentication;
+pub mod set_member_role;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -57,6 +57,10 @@ impl IntoResponse for SessionError {
                 Some("WORKSPACE_NOT_INITIALIZED"),
                 None,
             ),
+            SessionError::User(UserError::Unauthorized(_, _)) => {
+                (StatusCode::FORBIDDEN, None, None)
+            }
+            SessionError::User(UserError::LastOwner) => (StatusCode::CONFLICT, None, None),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, None, None),
         };
 
@@ -72,13 +76,42 @@ impl IntoResponse for SessionError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route("/connect", post(auth_connect::auth_connect))
-        .route("/reconnect", get(auth_connect::auth_reconnect))
-        .route(
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .post(
+            "/connect",
+            auth_connect::auth_connect,
+            schema_of!(auth_connect::AuthConnectRequest),
+            schema_of!(auth_connect::AuthConnectResponse),
+        )
+        .get(
+            "/reconnect",
+            auth_connect::auth_reconnect,
+            schema_of!(()),
+            schema_of!(auth_connect::AuthReconnectResponse),
+        )
+        .get(
             "/restore_authentication",
-            get(restore_authentication::restore_authentication),
+            restore_authentication::restore_authentication,
+            schema_of!(()),
+            schema_of!(restore_authentication::RestoreAuthenticationResponse),
+        )
+        .get(
+            "/load_workspace",
+            load_workspace::load_workspace,
+            schema_of!(()),
+            schema_of!(load_workspace::LoadWorkspaceResponse),
+        )
+        .get(
+            "/list_members",
+            list_members::list_members,
+            schema_of!(()),
+            schema_of!(list_members::ListMembersResponse),
+        )
+        .post(
+            "/set_member_role",
+            set_member_role::set_member_role,
+            schema_of!(set_member_role::SetMemberRoleRequest),
+            schema_of!(()),
         )
-        .route("/load_workspace", get(load_workspace::load_workspace))
 }
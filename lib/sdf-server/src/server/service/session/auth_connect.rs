@@ -1,17 +1,18 @@
 use super::{SessionError, SessionResult};
 use crate::server::extract::{HandlerContext, RawAccessToken};
+use crate::server::openapi::ApiSchema;
 use axum::Json;
 use dal::{DalContext, HistoryActor, KeyPair, Tenancy, User, UserPk, Workspace, WorkspacePk};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthConnectRequest {
     pub code: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthConnectResponse {
     pub user: User,
@@ -19,20 +20,20 @@ pub struct AuthConnectResponse {
     pub token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthReconnectResponse {
     pub user: User,
     pub workspace: Workspace,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ApiSchema)]
 struct AuthApiErrBody {
     pub kind: String,
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthApiUser {
     // probably dont really care about anything here but the id
@@ -45,7 +46,7 @@ pub struct AuthApiUser {
     pub email: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthApiWorkspace {
     pub id: WorkspacePk,
@@ -56,7 +57,7 @@ pub struct AuthApiWorkspace {
     pub instance_env_type: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthApiConnectResponse {
     pub user: AuthApiUser,
@@ -64,7 +65,7 @@ pub struct AuthApiConnectResponse {
     pub token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthApiReconnectResponse {
     pub user: AuthApiUser,
@@ -0,0 +1,24 @@
+use axum::Json;
+use dal::{User, WorkspaceMember};
+use serde::{Deserialize, Serialize};
+
+use super::SessionResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListMembersResponse {
+    pub members: Vec<WorkspaceMember>,
+}
+
+pub async fn list_members(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> SessionResult<Json<ListMembersResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let members = User::list_members(&ctx).await?;
+
+    Ok(Json(ListMembersResponse { members }))
+}
@@ -1,12 +1,13 @@
 use super::{SessionError, SessionResult};
 use crate::server::extract::{AccessBuilder, Authorization, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::Json;
 use dal::Workspace;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LoadWorkspaceResponse {
     pub workspace: Workspace,
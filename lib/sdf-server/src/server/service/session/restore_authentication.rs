@@ -1,15 +1,17 @@
 use axum::Json;
-use dal::{User, Workspace};
+use dal::{User, Workspace, WorkspaceRole};
 use serde::{Deserialize, Serialize};
 
 use super::{SessionError, SessionResult};
 use crate::server::extract::{AccessBuilder, Authorization, HandlerContext};
+use crate::server::openapi::ApiSchema;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RestoreAuthenticationResponse {
     pub user: User,
     pub workspace: Workspace,
+    pub role: Option<WorkspaceRole>,
 }
 
 pub async fn restore_authentication(
@@ -27,7 +29,13 @@ pub async fn restore_authentication(
         .await?
         .ok_or(SessionError::InvalidUser(claim.user_pk))?;
 
-    let reply = RestoreAuthenticationResponse { user, workspace };
+    let role = User::workspace_role(&ctx, claim.user_pk, claim.workspace_pk).await?;
+
+    let reply = RestoreAuthenticationResponse {
+        user,
+        workspace,
+        role,
+    };
 
     Ok(Json(reply))
 }
@@ -0,0 +1,43 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{User, UserPk, WorkspaceRole};
+use serde::{Deserialize, Serialize};
+
+use super::SessionResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMemberRoleRequest {
+    pub user_pk: UserPk,
+    pub role: WorkspaceRole,
+}
+
+pub async fn set_member_role(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<SetMemberRoleRequest>,
+) -> SessionResult<Json<()>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    User::set_workspace_role(&ctx, request.user_pk, request.role).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "set_member_role",
+        serde_json::json!({
+            "user_pk": request.user_pk,
+            "role": request.role,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(()))
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use dal::Workspace;
 use telemetry::prelude::*;
 
+use crate::server::openapi::ApiSchema;
 use crate::{
     server::extract::{HandlerContext, SignupSecret},
     service::signup::SignupError,
@@ -11,7 +12,7 @@ use crate::{
 
 use super::SignupResult;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateAccountRequest {
     pub workspace_name: String,
@@ -21,7 +22,7 @@ pub struct CreateAccountRequest {
     pub signup_secret: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateAccountResponse {
     pub success: bool,
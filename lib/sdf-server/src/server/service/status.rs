@@ -1,13 +1,12 @@
 use axum::{
     response::{IntoResponse, Response},
-    routing::get,
-    Json, Router,
+    Json,
 };
 use dal::{StatusUpdateError, TransactionsError};
 use hyper::StatusCode;
 use thiserror::Error;
 
-use crate::server::state::AppState;
+use crate::server::openapi::{schema_of, ApiRoutes};
 
 pub mod list_active_statuses;
 
@@ -34,9 +33,11 @@ impl IntoResponse for StatusError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new().route(
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new().get(
         "/list-active-statuses",
-        get(list_active_statuses::list_active_statuses),
+        list_active_statuses::list_active_statuses,
+        schema_of!(list_active_statuses::ListActiveStatusesRequest),
+        schema_of!(list_active_statuses::ListActiveStatusesResponse),
     )
 }
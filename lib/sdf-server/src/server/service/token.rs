@@ -0,0 +1,71 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use dal::{ApiTokenError, ApiTokenPk, TransactionsError, UserError};
+use thiserror::Error;
+
+use crate::server::openapi::{schema_of, ApiRoutes};
+
+pub mod create_token;
+pub mod list_tokens;
+pub mod revoke_token;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum TokenError {
+    #[error(transparent)]
+    ApiToken(#[from] ApiTokenError),
+    #[error(transparent)]
+    ContextTransactions(#[from] TransactionsError),
+    #[error("api token not found: {0}")]
+    TokenNotFound(ApiTokenPk),
+    #[error(transparent)]
+    User(#[from] UserError),
+}
+
+pub type TokenResult<T> = std::result::Result<T, TokenError>;
+
+impl IntoResponse for TokenError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            TokenError::TokenNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            TokenError::ApiToken(
+                ApiTokenError::CreatorNotUser
+                | ApiTokenError::EmptyName
+                | ApiTokenError::ExpiresInPast(_),
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            TokenError::User(
+                UserError::ApiTokenUnauthorized(_, _) | UserError::Unauthorized(_, _),
+            ) => (StatusCode::FORBIDDEN, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .get(
+            "/list_tokens",
+            list_tokens::list_tokens,
+            schema_of!(()),
+            schema_of!(list_tokens::ListTokensResponse),
+        )
+        .post(
+            "/create_token",
+            create_token::create_token,
+            schema_of!(create_token::CreateTokenRequest),
+            schema_of!(create_token::CreateTokenResponse),
+        )
+        .post(
+            "/revoke_token",
+            revoke_token::revoke_token,
+            schema_of!(revoke_token::RevokeTokenRequest),
+            schema_of!(revoke_token::RevokeTokenResponse),
+        )
+}
@@ -0,0 +1,55 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use dal::{ApiToken, WorkspaceRole};
+use serde::{Deserialize, Serialize};
+
+use super::TokenResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTokenRequest {
+    pub name: String,
+    pub role: WorkspaceRole,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTokenResponse {
+    pub token: ApiToken,
+    /// The secret to authenticate with. It is only ever returned here.
+    pub secret: String,
+}
+
+pub async fn create_token(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<CreateTokenRequest>,
+) -> TokenResult<Json<CreateTokenResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let (token, secret) =
+        ApiToken::new(&ctx, &request.name, request.role, request.expires_at).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "create_api_token",
+        serde_json::json!({
+            "api_token_pk": token.pk(),
+            "role": token.role(),
+            "expires_at": token.expires_at(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(CreateTokenResponse { token, secret }))
+}
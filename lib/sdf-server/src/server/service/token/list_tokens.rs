@@ -0,0 +1,42 @@
+use axum::Json;
+use dal::{ApiToken, HistoryActor, User, UserCapability};
+use serde::{Deserialize, Serialize};
+
+use super::TokenResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTokensResponse {
+    pub tokens: Vec<ApiToken>,
+}
+
+pub async fn list_tokens(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> TokenResult<Json<ListTokensResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    // Whoever manages the workspace sees every token, everyone else only the ones they created.
+    let tokens = ApiToken::list(&ctx).await?;
+    let tokens = match ctx.history_actor() {
+        HistoryActor::User(user_pk) => {
+            if User::authorize(&ctx, user_pk, UserCapability::ManageRoles).await? {
+                tokens
+            } else {
+                let user_pk = *user_pk;
+                tokens
+                    .into_iter()
+                    .filter(|token| token.created_by_user_pk() == user_pk)
+                    .collect()
+            }
+        }
+        HistoryActor::ApiToken(_) | HistoryActor::SystemInit => {
+            User::ensure_authorized(&ctx, UserCapability::ManageRoles).await?;
+            tokens
+        }
+    };
+
+    Ok(Json(ListTokensResponse { tokens }))
+}
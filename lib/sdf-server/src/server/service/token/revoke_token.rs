@@ -0,0 +1,60 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{ApiToken, ApiTokenPk, HistoryActor, User, UserCapability};
+use serde::{Deserialize, Serialize};
+
+use super::{TokenError, TokenResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeTokenRequest {
+    pub pk: ApiTokenPk,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeTokenResponse {
+    pub token: ApiToken,
+}
+
+pub async fn revoke_token(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RevokeTokenRequest>,
+) -> TokenResult<Json<RevokeTokenResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut token = ApiToken::get_by_pk(&ctx, request.pk)
+        .await?
+        .ok_or(TokenError::TokenNotFound(request.pk))?;
+
+    // Tokens can be revoked by whoever created them, and by whoever manages the workspace.
+    let is_creator = matches!(
+        ctx.history_actor(),
+        HistoryActor::User(user_pk) if *user_pk == token.created_by_user_pk()
+    );
+    if !is_creator {
+        User::ensure_authorized(&ctx, UserCapability::ManageRoles).await?;
+    }
+
+    token.revoke(&ctx).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "revoke_api_token",
+        serde_json::json!({
+            "api_token_pk": token.pk(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(RevokeTokenResponse { token }))
+}
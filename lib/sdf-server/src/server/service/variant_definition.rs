@@ -1,8 +1,7 @@
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::{get, post},
-    Json, Router,
+    Json,
 };
 use strum::IntoEnumIterator;
 use thiserror::Error;
@@ -24,7 +23,7 @@ use dal::{
 };
 use si_pkg::{SiPkgError, SpecError};
 
-use crate::server::state::AppState;
+use crate::server::openapi::{schema_of, ApiRoutes};
 use crate::service::func::FuncError as SdfFuncError;
 
 use self::save_variant_def::SaveVariantDefRequest;
@@ -349,27 +348,42 @@ pub async fn maybe_delete_schema_variant_connected_to_variant_def(
     Ok((maybe_previous_schema_variant_id, leaf_func_migrations))
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new()
-        .route(
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .get(
             "/list_variant_defs",
-            get(list_variant_defs::list_variant_defs),
+            list_variant_defs::list_variant_defs,
+            schema_of!(list_variant_defs::ListVariantDefsRequest),
+            schema_of!(list_variant_defs::ListVariantDefsResponse),
         )
-        .route("/get_variant_def", get(get_variant_def::get_variant_def))
-        .route(
+        .get(
+            "/get_variant_def",
+            get_variant_def::get_variant_def,
+            schema_of!(get_variant_def::GetVariantDefRequest),
+            schema_of!(get_variant_def::GetVariantDefResponse),
+        )
+        .post(
             "/save_variant_def",
-            post(save_variant_def::save_variant_def),
+            save_variant_def::save_variant_def,
+            schema_of!(save_variant_def::SaveVariantDefRequest),
+            schema_of!(save_variant_def::SaveVariantDefResponse),
         )
-        .route(
+        .post(
             "/create_variant_def",
-            post(create_variant_def::create_variant_def),
+            create_variant_def::create_variant_def,
+            schema_of!(create_variant_def::CreateVariantDefRequest),
+            schema_of!(create_variant_def::CreateVariantDefResponse),
         )
-        .route(
+        .post(
             "/exec_variant_def",
-            post(exec_variant_def::exec_variant_def),
+            exec_variant_def::exec_variant_def,
+            schema_of!(exec_variant_def::ExecVariantDefRequest),
+            schema_of!(exec_variant_def::ExecVariantDefResponse),
         )
-        .route(
+        .post(
             "/clone_variant_def",
-            post(clone_variant_def::clone_variant_def),
+            clone_variant_def::clone_variant_def,
+            schema_of!(clone_variant_def::CloneVariantDefRequest),
+            schema_of!(clone_variant_def::CloneVariantDefResponse),
         )
 }
@@ -1,5 +1,6 @@
 use super::SchemaVariantDefinitionResult;
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::{response::IntoResponse, Json};
@@ -15,7 +16,7 @@ const DEFAULT_ASSET_CODE: &str = r#"function createAsset() {
   return asset.build()
 }"#;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateVariantDefRequest {
     pub name: String,
@@ -28,7 +29,7 @@ pub struct CreateVariantDefRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateVariantDefResponse {
     pub id: SchemaVariantDefinitionId,
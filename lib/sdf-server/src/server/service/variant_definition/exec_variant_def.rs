@@ -4,6 +4,7 @@ use super::{
     SchemaVariantDefinitionResult,
 };
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::{response::IntoResponse, Json};
@@ -26,7 +27,7 @@ use std::collections::HashMap;
 
 pub type ExecVariantDefRequest = super::save_variant_def::SaveVariantDefRequest;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecVariantDefResponse {
     pub success: bool,
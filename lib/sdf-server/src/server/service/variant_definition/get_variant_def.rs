@@ -1,6 +1,7 @@
 use super::{is_variant_def_locked, SchemaVariantDefinitionError, SchemaVariantDefinitionResult};
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
 use crate::server::feature_flags::feature_is_enabled;
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use crate::service::func::list_funcs::ListedFuncView;
 use crate::service::func::{compile_return_types, compile_return_types_2};
@@ -13,7 +14,7 @@ use dal::{
 use serde::{Deserialize, Serialize};
 use si_posthog::FeatureFlag;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetVariantDefRequest {
     pub id: SchemaVariantDefinitionId,
@@ -21,7 +22,7 @@ pub struct GetVariantDefRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetVariantDefResponse {
     pub id: SchemaVariantDefinitionId,
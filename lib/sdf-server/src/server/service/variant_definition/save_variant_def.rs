@@ -1,5 +1,6 @@
 use super::SchemaVariantDefinitionResult;
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::{response::IntoResponse, Json};
@@ -7,7 +8,7 @@ use dal::ComponentType;
 use dal::{schema::variant::definition::SchemaVariantDefinitionId, ChangeSet, Visibility, WsEvent};
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveVariantDefRequest {
     pub id: SchemaVariantDefinitionId,
@@ -24,7 +25,7 @@ pub struct SaveVariantDefRequest {
     pub visibility: Visibility,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveVariantDefResponse {
     pub success: bool,
@@ -0,0 +1,93 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use dal::{TransactionsError, UserError, WebhookError, WebhookPk};
+use thiserror::Error;
+
+use crate::server::openapi::{schema_of, ApiRoutes};
+
+pub mod create_webhook;
+pub mod delete_webhook;
+pub mod list_deliveries;
+pub mod list_webhooks;
+pub mod test_webhook;
+pub mod update_webhook;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum WebhookServiceError {
+    #[error(transparent)]
+    ContextTransactions(#[from] TransactionsError),
+    #[error(transparent)]
+    User(#[from] UserError),
+    #[error(transparent)]
+    Webhook(#[from] WebhookError),
+    #[error("webhook not found: {0}")]
+    WebhookNotFound(WebhookPk),
+}
+
+pub type WebhookServiceResult<T> = std::result::Result<T, WebhookServiceError>;
+
+impl IntoResponse for WebhookServiceError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            WebhookServiceError::WebhookNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            WebhookServiceError::Webhook(
+                WebhookError::InvalidUrl(_) | WebhookError::NoEventKinds,
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            WebhookServiceError::User(
+                UserError::ApiTokenUnauthorized(_, _) | UserError::Unauthorized(_, _),
+            )
+            | WebhookServiceError::Webhook(WebhookError::User(
+                UserError::ApiTokenUnauthorized(_, _) | UserError::Unauthorized(_, _),
+            )) => (StatusCode::FORBIDDEN, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new()
+        .get(
+            "/list_webhooks",
+            list_webhooks::list_webhooks,
+            schema_of!(()),
+            schema_of!(list_webhooks::ListWebhooksResponse),
+        )
+        .post(
+            "/create_webhook",
+            create_webhook::create_webhook,
+            schema_of!(create_webhook::CreateWebhookRequest),
+            schema_of!(create_webhook::CreateWebhookResponse),
+        )
+        .post(
+            "/update_webhook",
+            update_webhook::update_webhook,
+            schema_of!(update_webhook::UpdateWebhookRequest),
+            schema_of!(update_webhook::UpdateWebhookResponse),
+        )
+        .post(
+            "/delete_webhook",
+            delete_webhook::delete_webhook,
+            schema_of!(delete_webhook::DeleteWebhookRequest),
+            schema_of!(()),
+        )
+        .get(
+            "/list_deliveries",
+            list_deliveries::list_deliveries,
+            schema_of!(list_deliveries::ListDeliveriesRequest),
+            schema_of!(list_deliveries::ListDeliveriesResponse),
+        )
+        .post(
+            "/test_webhook",
+            test_webhook::test_webhook,
+            schema_of!(test_webhook::TestWebhookRequest),
+            schema_of!(test_webhook::TestWebhookResponse),
+        )
+}
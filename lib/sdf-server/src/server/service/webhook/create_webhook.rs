@@ -0,0 +1,52 @@
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{Webhook, WebhookEventKind};
+use serde::{Deserialize, Serialize};
+
+use super::WebhookServiceResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::openapi::ApiSchema;
+use crate::server::tracking::track;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub event_kinds: Vec<WebhookEventKind>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookResponse {
+    pub webhook: Webhook,
+    /// The secret deliveries are signed with. It is only ever returned here.
+    pub secret: String,
+}
+
+pub async fn create_webhook(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<CreateWebhookRequest>,
+) -> WebhookServiceResult<Json<CreateWebhookResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let webhook = Webhook::new(&ctx, &request.url, request.event_kinds).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "create_webhook",
+        serde_json::json!({
+            "webhook_pk": webhook.pk(),
+            "event_kinds": webhook.event_kinds(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let secret = webhook.secret().to_owned();
+    Ok(Json(CreateWebhookResponse { webhook, secret }))
+}
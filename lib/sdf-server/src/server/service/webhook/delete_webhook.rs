@@ -0,0 +1,30 @@
+use axum::Json;
+use dal::{Webhook, WebhookPk};
+use serde::{Deserialize, Serialize};
+
+use super::{WebhookServiceError, WebhookServiceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteWebhookRequest {
+    pub pk: WebhookPk,
+}
+
+pub async fn delete_webhook(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Json(request): Json<DeleteWebhookRequest>,
+) -> WebhookServiceResult<Json<()>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let webhook = Webhook::get_by_pk(&ctx, request.pk)
+        .await?
+        .ok_or(WebhookServiceError::WebhookNotFound(request.pk))?;
+    webhook.delete(&ctx).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(()))
+}
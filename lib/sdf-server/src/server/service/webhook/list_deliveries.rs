@@ -0,0 +1,43 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{User, UserCapability, Webhook, WebhookDelivery, WebhookPk};
+use serde::{Deserialize, Serialize};
+
+use super::{WebhookServiceError, WebhookServiceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+/// How many deliveries are listed when the request doesn't say.
+const DEFAULT_LIMIT: i64 = 50;
+/// The most deliveries that can be listed at once.
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDeliveriesRequest {
+    pub pk: WebhookPk,
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDeliveriesResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+pub async fn list_deliveries(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<ListDeliveriesRequest>,
+) -> WebhookServiceResult<Json<ListDeliveriesResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    User::ensure_authorized(&ctx, UserCapability::ManageWebhooks).await?;
+    let webhook = Webhook::get_by_pk(&ctx, request.pk)
+        .await?
+        .ok_or(WebhookServiceError::WebhookNotFound(request.pk))?;
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let deliveries = webhook.deliveries(&ctx, limit).await?;
+
+    Ok(Json(ListDeliveriesResponse { deliveries }))
+}
@@ -0,0 +1,25 @@
+use axum::Json;
+use dal::{User, UserCapability, Webhook};
+use serde::{Deserialize, Serialize};
+
+use super::WebhookServiceResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhooksResponse {
+    pub webhooks: Vec<Webhook>,
+}
+
+pub async fn list_webhooks(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> WebhookServiceResult<Json<ListWebhooksResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    User::ensure_authorized(&ctx, UserCapability::ManageWebhooks).await?;
+    let webhooks = Webhook::list(&ctx).await?;
+
+    Ok(Json(ListWebhooksResponse { webhooks }))
+}
@@ -0,0 +1,49 @@
+use axum::Json;
+use dal::{User, UserCapability, Webhook, WebhookClient, WebhookDelivery, WebhookPk};
+use serde::{Deserialize, Serialize};
+
+use super::{WebhookServiceError, WebhookServiceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestWebhookRequest {
+    pub pk: WebhookPk,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestWebhookResponse {
+    pub delivery: WebhookDelivery,
+}
+
+/// Posts a ping to the webhook right away, rather than waiting for the dispatcher, and returns
+/// how it went. Like any other delivery, it is retried later if it fails.
+pub async fn test_webhook(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Json(request): Json<TestWebhookRequest>,
+) -> WebhookServiceResult<Json<TestWebhookResponse>> {
+    let (webhook, mut delivery) = {
+        let ctx = builder.build_head(access_builder).await?;
+
+        User::ensure_authorized(&ctx, UserCapability::ManageWebhooks).await?;
+        let webhook = Webhook::get_by_pk(&ctx, request.pk)
+            .await?
+            .ok_or(WebhookServiceError::WebhookNotFound(request.pk))?;
+        let delivery = webhook.ping(&ctx).await?;
+
+        ctx.commit().await?;
+        (webhook, delivery)
+    };
+
+    // The webhook is posted to without holding a connection, and the attempt recorded after.
+    let attempt = delivery.post(&webhook, &WebhookClient::new()).await?;
+
+    let ctx = builder.build_head(access_builder).await?;
+    delivery.record(&ctx, attempt).await?;
+    ctx.commit().await?;
+
+    Ok(Json(TestWebhookResponse { delivery }))
+}
@@ -0,0 +1,41 @@
+use axum::Json;
+use dal::{Webhook, WebhookEventKind, WebhookPk};
+use serde::{Deserialize, Serialize};
+
+use super::{WebhookServiceError, WebhookServiceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::server::openapi::ApiSchema;
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhookRequest {
+    pub pk: WebhookPk,
+    pub url: String,
+    pub event_kinds: Vec<WebhookEventKind>,
+    pub enabled: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhookResponse {
+    pub webhook: Webhook,
+}
+
+pub async fn update_webhook(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Json(request): Json<UpdateWebhookRequest>,
+) -> WebhookServiceResult<Json<UpdateWebhookResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut webhook = Webhook::get_by_pk(&ctx, request.pk)
+        .await?
+        .ok_or(WebhookServiceError::WebhookNotFound(request.pk))?;
+    webhook
+        .update(&ctx, &request.url, request.event_kinds, request.enabled)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(UpdateWebhookResponse { webhook }))
+}
@@ -1,11 +1,9 @@
-use axum::{
-    http::StatusCode, response::IntoResponse, response::Response, routing::get, Json, Router,
-};
+use axum::{http::StatusCode, response::IntoResponse, response::Response, Json};
 use dal::TransactionsError;
 use si_data_pg::{PgError, PgPoolError};
 use thiserror::Error;
 
-use crate::server::state::AppState;
+use crate::server::openapi::{schema_of, ApiRoutes};
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -18,6 +16,7 @@ pub enum WsError {
     Transactions(#[from] TransactionsError),
 }
 
+pub mod event_log;
 pub mod workspace_updates;
 
 impl IntoResponse for WsError {
@@ -36,9 +35,10 @@ impl IntoResponse for WsError {
     }
 }
 
-pub fn routes() -> Router<AppState> {
-    Router::new().route(
+pub fn routes() -> ApiRoutes {
+    ApiRoutes::new().websocket(
         "/workspace_updates",
-        get(workspace_updates::workspace_updates),
+        workspace_updates::workspace_updates,
+        schema_of!(workspace_updates::WorkspaceUpdatesRequest),
     )
 }
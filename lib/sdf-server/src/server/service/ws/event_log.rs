@@ -0,0 +1,375 @@
+//! This module contains [`WsEventLog`], which stamps the messages published for a workspace with
+//! a sequence number and keeps the most recent ones around, so that a websocket that reconnects
+//! can catch up on what it missed instead of going stale.
+//!
+//! Sequences are kept in memory, per instance: every time the log of a workspace is (re)created it
+//! gets a new epoch, and sequences only mean something within an epoch. A client resuming from
+//! another epoch, or from further back than the log goes, is told there is a gap so it can reload.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use dal::WorkspacePk;
+use futures::StreamExt;
+use si_data_nats::{NatsClient, NatsError, Subscriber};
+use telemetry::prelude::*;
+use tokio::sync::broadcast;
+use ulid::Ulid;
+
+pub use sdf_core::ws::{ResumeFrom, StreamStatus};
+
+/// How many messages are kept for each workspace.
+const WS_EVENT_LOG_CAPACITY: usize = 1024;
+/// How long the log of a workspace is kept once no websocket is connected for it.
+const WS_EVENT_LOG_IDLE_TTL: Duration = Duration::from_secs(5 * 60);
+/// How often idle logs are looked for.
+const WS_EVENT_LOG_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A message published for a workspace, with the sequence it was stamped with.
+#[derive(Clone, Debug)]
+pub struct SequencedMessage {
+    pub sequence: u64,
+    pub text: Arc<str>,
+}
+
+/// The result of [`WsEventLog::attach`].
+#[derive(Debug)]
+pub struct Attached {
+    pub status: StreamStatus,
+    /// The messages the client missed, oldest first.
+    pub replay: Vec<SequencedMessage>,
+    /// Every message published from now on.
+    pub live: broadcast::Receiver<SequencedMessage>,
+}
+
+#[derive(Debug)]
+struct WorkspaceLog {
+    epoch: String,
+    last_sequence: u64,
+    messages: VecDeque<SequencedMessage>,
+    sender: broadcast::Sender<SequencedMessage>,
+    last_attached_at: Instant,
+}
+
+impl WorkspaceLog {
+    fn new() -> Self {
+        let (sender, _) = broadcast::channel(WS_EVENT_LOG_CAPACITY);
+        Self {
+            epoch: Ulid::new().to_string(),
+            last_sequence: 0,
+            messages: VecDeque::with_capacity(WS_EVENT_LOG_CAPACITY),
+            sender,
+            last_attached_at: Instant::now(),
+        }
+    }
+
+    fn push(&mut self, payload: &[u8]) {
+        self.last_sequence += 1;
+        let sequence = self.last_sequence;
+
+        // Messages are json objects, which get the sequence added to them. Anything else is
+        // passed along untouched.
+        let text = match serde_json::from_slice::<serde_json::Value>(payload) {
+            Ok(serde_json::Value::Object(mut object)) => {
+                object.insert("sequence".to_owned(), sequence.into());
+                serde_json::Value::Object(object).to_string()
+            }
+            _ => String::from_utf8_lossy(payload).to_string(),
+        };
+        let message = SequencedMessage {
+            sequence,
+            text: text.into(),
+        };
+
+        if self.messages.len() == WS_EVENT_LOG_CAPACITY {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(message.clone());
+        // Nobody listening is fine, the message is kept for when somebody reconnects.
+        let _ = self.sender.send(message);
+    }
+
+    fn attach(&mut self, resume_from: Option<&ResumeFrom>) -> Attached {
+        self.last_attached_at = Instant::now();
+        let live = self.sender.subscribe();
+
+        let (replay, gap) = match resume_from {
+            None => (Vec::new(), false),
+            Some(resume_from) => {
+                let oldest_kept = self
+                    .messages
+                    .front()
+                    .map_or(self.last_sequence + 1, |message| message.sequence);
+                if resume_from.epoch == self.epoch
+                    && resume_from.sequence <= self.last_sequence
+                    && resume_from.sequence + 1 >= oldest_kept
+                {
+                    let replay = self
+                        .messages
+                        .iter()
+                        .filter(|message| message.sequence > resume_from.sequence)
+                        .cloned()
+                        .collect();
+                    (replay, false)
+                } else {
+                    (Vec::new(), true)
+                }
+            }
+        };
+
+        Attached {
+            status: StreamStatus {
+                epoch: self.epoch.clone(),
+                sequence: self.last_sequence,
+                gap,
+            },
+            replay,
+            live,
+        }
+    }
+}
+
+/// The logs of every workspace with a websocket connected to this instance. See the
+/// [`module`](self) for more information.
+#[derive(Clone, Debug, Default)]
+pub struct WsEventLog {
+    workspaces: Arc<Mutex<HashMap<WorkspacePk, WorkspaceLog>>>,
+}
+
+impl WsEventLog {
+    /// Starts following the messages of the workspace, unless they already are, and returns what
+    /// the client missed since `resume_from` along with a receiver for the messages to come.
+    pub async fn attach(
+        &self,
+        nats: &NatsClient,
+        workspace_pk: WorkspacePk,
+        resume_from: Option<&ResumeFrom>,
+        shutdown: broadcast::Receiver<()>,
+    ) -> Result<Attached, NatsError> {
+        {
+            let mut workspaces = self.lock();
+            if let Some(log) = workspaces.get_mut(&workspace_pk) {
+                return Ok(log.attach(resume_from));
+            }
+        }
+
+        // Subscribe without holding the lock, so it is never held across an await.
+        let subscriber = nats
+            .subscribe(format!("si.workspace_pk.{workspace_pk}.>"))
+            .await?;
+
+        let mut workspaces = self.lock();
+        if let Some(log) = workspaces.get_mut(&workspace_pk) {
+            // Somebody else started following the workspace in the meantime.
+            return Ok(log.attach(resume_from));
+        }
+        let mut log = WorkspaceLog::new();
+        let attached = log.attach(resume_from);
+        workspaces.insert(workspace_pk, log);
+        drop(workspaces);
+
+        tokio::spawn(self.clone().follow(workspace_pk, subscriber, shutdown));
+        Ok(attached)
+    }
+
+    /// Pushes the messages of the workspace into its log until nobody has been connected for it
+    /// for a while.
+    async fn follow(
+        self,
+        workspace_pk: WorkspacePk,
+        mut subscriber: Subscriber,
+        mut shutdown: broadcast::Receiver<()>,
+    ) {
+        let mut sweep = tokio::time::interval(WS_EVENT_LOG_SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => {
+                    trace!("ws event log received shutdown");
+                    break;
+                }
+                _ = sweep.tick() => {
+                    let mut workspaces = self.lock();
+                    let idle = match workspaces.get_mut(&workspace_pk) {
+                        Some(log) if log.sender.receiver_count() > 0 => {
+                            log.last_attached_at = Instant::now();
+                            false
+                        }
+                        Some(log) => log.last_attached_at.elapsed() > WS_EVENT_LOG_IDLE_TTL,
+                        None => true,
+                    };
+                    if idle {
+                        trace!(%workspace_pk, "ws event log is idle, dropping it");
+                        workspaces.remove(&workspace_pk);
+                        break;
+                    }
+                }
+                message = subscriber.next() => {
+                    match message {
+                        Some(message) => {
+                            let mut workspaces = self.lock();
+                            if let Some(log) = workspaces.get_mut(&workspace_pk) {
+                                log.push(message.payload());
+                            }
+                        }
+                        None => {
+                            trace!("ws event log subscriber stream has closed");
+                            self.lock().remove(&workspace_pk);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Err(err) = subscriber.unsubscribe().await {
+            warn!(error = ?err, "could not unsubscribe ws event log from nats");
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<WorkspacePk, WorkspaceLog>> {
+        self.workspaces
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_with_messages(count: u64) -> WorkspaceLog {
+        let mut log = WorkspaceLog::new();
+        for index in 0..count {
+            log.push(format!(r#"{{"index":{index}}}"#).as_bytes());
+        }
+        log
+    }
+
+    fn sequences(messages: &[SequencedMessage]) -> Vec<u64> {
+        messages.iter().map(|message| message.sequence).collect()
+    }
+
+    #[test]
+    fn push_stamps_json_objects_with_their_sequence() {
+        let mut log = WorkspaceLog::new();
+        log.push(br#"{"kind":"ChangeSetWritten"}"#);
+        log.push(b"not json");
+
+        let messages: Vec<_> = log.messages.iter().cloned().collect();
+        assert_eq!(vec![1, 2], sequences(&messages));
+        assert_eq!(
+            serde_json::json!({ "kind": "ChangeSetWritten", "sequence": 1 }),
+            serde_json::from_str::<serde_json::Value>(&messages[0].text).expect("not json")
+        );
+        assert_eq!("not json", &*messages[1].text);
+    }
+
+    #[test]
+    fn attach_without_resuming_replays_nothing() {
+        let mut log = log_with_messages(3);
+
+        let attached = log.attach(None);
+        assert!(attached.replay.is_empty());
+        assert_eq!(
+            StreamStatus {
+                epoch: log.epoch.clone(),
+                sequence: 3,
+                gap: false,
+            },
+            attached.status
+        );
+    }
+
+    #[test]
+    fn attach_replays_what_was_missed_within_the_window() {
+        let mut log = log_with_messages(5);
+        let resume_from = ResumeFrom {
+            epoch: log.epoch.clone(),
+            sequence: 2,
+        };
+
+        let attached = log.attach(Some(&resume_from));
+        assert!(!attached.status.gap);
+        assert_eq!(5, attached.status.sequence);
+        assert_eq!(vec![3, 4, 5], sequences(&attached.replay));
+
+        // Resuming from the last message replays nothing, and is not a gap either.
+        let resume_from = ResumeFrom {
+            epoch: log.epoch.clone(),
+            sequence: 5,
+        };
+        let attached = log.attach(Some(&resume_from));
+        assert!(!attached.status.gap);
+        assert!(attached.replay.is_empty());
+    }
+
+    #[test]
+    fn attach_sends_what_is_pushed_afterwards() {
+        let mut log = log_with_messages(1);
+        let mut attached = log.attach(None);
+
+        log.push(br#"{"index":1}"#);
+        let message = attached.live.try_recv().expect("nothing was sent");
+        assert_eq!(2, message.sequence);
+    }
+
+    #[test]
+    fn attach_from_another_epoch_is_a_gap() {
+        let mut log = log_with_messages(3);
+        let resume_from = ResumeFrom {
+            epoch: Ulid::new().to_string(),
+            sequence: 1,
+        };
+
+        let attached = log.attach(Some(&resume_from));
+        assert!(attached.status.gap);
+        assert!(attached.replay.is_empty());
+        assert_eq!(log.epoch, attached.status.epoch);
+        assert_eq!(3, attached.status.sequence);
+    }
+
+    #[test]
+    fn attach_from_a_sequence_not_yet_pushed_is_a_gap() {
+        let mut log = log_with_messages(3);
+        let resume_from = ResumeFrom {
+            epoch: log.epoch.clone(),
+            sequence: 7,
+        };
+
+        let attached = log.attach(Some(&resume_from));
+        assert!(attached.status.gap);
+        assert!(attached.replay.is_empty());
+    }
+
+    #[test]
+    fn attach_from_before_the_oldest_kept_message_is_a_gap() {
+        let overflow = 10;
+        let mut log = log_with_messages(WS_EVENT_LOG_CAPACITY as u64 + overflow);
+        assert_eq!(WS_EVENT_LOG_CAPACITY, log.messages.len());
+
+        // The messages up to `overflow` were dropped, so the one right after is missing.
+        let resume_from = ResumeFrom {
+            epoch: log.epoch.clone(),
+            sequence: overflow - 1,
+        };
+        let attached = log.attach(Some(&resume_from));
+        assert!(attached.status.gap);
+        assert!(attached.replay.is_empty());
+
+        // Everything after `overflow` is still around.
+        let resume_from = ResumeFrom {
+            epoch: log.epoch.clone(),
+            sequence: overflow,
+        };
+        let attached = log.attach(Some(&resume_from));
+        assert!(!attached.status.gap);
+        assert_eq!(WS_EVENT_LOG_CAPACITY, attached.replay.len());
+        assert_eq!(
+            Some(overflow + 1),
+            attached.replay.first().map(|m| m.sequence)
+        );
+    }
+}
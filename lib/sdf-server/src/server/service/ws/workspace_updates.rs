@@ -1,34 +1,51 @@
+use super::event_log::{ResumeFrom, WsEventLog};
 use super::WsError;
 use axum::{
-    extract::{ws::WebSocket, State, WebSocketUpgrade},
+    extract::{ws::WebSocket, Query, State, WebSocketUpgrade},
     response::IntoResponse,
 };
 use dal::WorkspacePk;
+use serde::{Deserialize, Serialize};
 use si_data_nats::NatsClient;
 use telemetry::prelude::*;
 use tokio::sync::broadcast;
 
+use crate::server::openapi::ApiSchema;
 use crate::server::{
     extract::{Nats, WsAuthorization},
     state::ShutdownBroadcast,
 };
 
-#[instrument(skip(wsu, nats))]
+/// To catch up on what was missed while disconnected, clients pass the epoch and the last
+/// sequence they saw. See [`WsEventLog`] for more information.
+#[derive(Deserialize, Serialize, Debug, ApiSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceUpdatesRequest {
+    pub resume_epoch: Option<String>,
+    pub resume_after: Option<u64>,
+}
+
+#[instrument(skip(wsu, nats, ws_event_log))]
 #[allow(clippy::unused_async)]
 pub async fn workspace_updates(
     wsu: WebSocketUpgrade,
     Nats(nats): Nats,
     WsAuthorization(claim): WsAuthorization,
+    Query(request): Query<WorkspaceUpdatesRequest>,
     State(shutdown_broadcast): State<ShutdownBroadcast>,
+    State(ws_event_log): State<WsEventLog>,
 ) -> Result<impl IntoResponse, WsError> {
     async fn handle_socket(
         socket: WebSocket,
         nats: NatsClient,
-        mut shutdown: broadcast::Receiver<()>,
+        ws_event_log: WsEventLog,
+        resume_from: Option<ResumeFrom>,
+        shutdown_broadcast: ShutdownBroadcast,
         workspace_pk: WorkspacePk,
     ) {
+        let mut shutdown = shutdown_broadcast.subscribe();
         tokio::select! {
-            _ = run_workspace_updates_proto(socket, nats, workspace_pk) => {
+            _ = run_workspace_updates_proto(socket, nats, ws_event_log, resume_from, shutdown_broadcast.subscribe(), workspace_pk) => {
                 trace!("finished workspace_updates proto");
             }
             _ = shutdown.recv() => {
@@ -40,16 +57,34 @@ pub async fn workspace_updates(
         }
     }
 
-    let shutdown = shutdown_broadcast.subscribe();
-    Ok(wsu.on_upgrade(move |socket| handle_socket(socket, nats, shutdown, claim.workspace_pk)))
+    let resume_from = match (request.resume_epoch, request.resume_after) {
+        (Some(epoch), Some(sequence)) => Some(ResumeFrom { epoch, sequence }),
+        _ => None,
+    };
+    Ok(wsu.on_upgrade(move |socket| {
+        handle_socket(
+            socket,
+            nats,
+            ws_event_log,
+            resume_from,
+            shutdown_broadcast,
+            claim.workspace_pk,
+        )
+    }))
 }
 
 async fn run_workspace_updates_proto(
     mut socket: WebSocket,
     nats: NatsClient,
+    ws_event_log: WsEventLog,
+    resume_from: Option<ResumeFrom>,
+    shutdown: broadcast::Receiver<()>,
     workspace_pk: WorkspacePk,
 ) {
-    let proto = match workspace_updates::run(nats, workspace_pk).start().await {
+    let proto = match workspace_updates::run(nats, ws_event_log, workspace_pk)
+        .start(resume_from.as_ref(), shutdown)
+        .await
+    {
         Ok(started) => started,
         Err(err) => {
             // This is likely due to nats failing to subscribe to the required topic, which is
@@ -78,14 +113,24 @@ mod workspace_updates {
 
     use axum::extract::ws::{self, WebSocket};
     use dal::WorkspacePk;
-    use futures::StreamExt;
-    use si_data_nats::{NatsClient, NatsError, Subscriber};
+    use si_data_nats::{NatsClient, NatsError};
     use telemetry::prelude::*;
     use thiserror::Error;
+    use tokio::sync::broadcast::{self, error::RecvError};
     use tokio_tungstenite::tungstenite;
 
-    pub fn run(nats: NatsClient, workspace_pk: WorkspacePk) -> WorkspaceUpdates {
-        WorkspaceUpdates { nats, workspace_pk }
+    use super::super::event_log::{ResumeFrom, SequencedMessage, StreamStatus, WsEventLog};
+
+    pub fn run(
+        nats: NatsClient,
+        ws_event_log: WsEventLog,
+        workspace_pk: WorkspacePk,
+    ) -> WorkspaceUpdates {
+        WorkspaceUpdates {
+            nats,
+            ws_event_log,
+            workspace_pk,
+        }
     }
 
     #[remain::sorted]
@@ -93,8 +138,8 @@ mod workspace_updates {
     pub enum WorkspaceUpdatesError {
         #[error("axum error: {0}")]
         Axum(#[from] axum::Error),
-        #[error("failed to subscribe to subject {1}")]
-        Subscribe(#[source] NatsError, String),
+        #[error("failed to subscribe to workspace {1}")]
+        Subscribe(#[source] NatsError, WorkspacePk),
         #[error("error when closing websocket")]
         WsClose(#[source] axum::Error),
         #[error("error when sending websocket message")]
@@ -106,31 +151,54 @@ mod workspace_updates {
     #[derive(Debug)]
     pub struct WorkspaceUpdates {
         nats: NatsClient,
+        ws_event_log: WsEventLog,
         workspace_pk: WorkspacePk,
     }
 
     impl WorkspaceUpdates {
-        pub async fn start(self) -> Result<WorkspaceUpdatesStarted> {
-            let subject = format!("si.workspace_pk.{}.>", self.workspace_pk);
-            let subscriber = self
-                .nats
-                .subscribe(&subject)
+        pub async fn start(
+            self,
+            resume_from: Option<&ResumeFrom>,
+            shutdown: broadcast::Receiver<()>,
+        ) -> Result<WorkspaceUpdatesStarted> {
+            let attached = self
+                .ws_event_log
+                .attach(&self.nats, self.workspace_pk, resume_from, shutdown)
                 .await
-                .map_err(|err| WorkspaceUpdatesError::Subscribe(err, subject))?;
+                .map_err(|err| WorkspaceUpdatesError::Subscribe(err, self.workspace_pk))?;
 
-            Ok(WorkspaceUpdatesStarted { subscriber })
+            Ok(WorkspaceUpdatesStarted {
+                workspace_pk: self.workspace_pk,
+                status: attached.status,
+                replay: attached.replay,
+                live: attached.live,
+            })
         }
     }
 
     #[derive(Debug)]
     pub struct WorkspaceUpdatesStarted {
-        subscriber: Subscriber,
+        workspace_pk: WorkspacePk,
+        status: StreamStatus,
+        replay: Vec<SequencedMessage>,
+        live: broadcast::Receiver<SequencedMessage>,
     }
 
     impl WorkspaceUpdatesStarted {
         pub async fn process(mut self, ws: &mut WebSocket) -> Result<WorkspaceUpdatesClosing> {
+            // Tell the client where the stream is at, then catch it up on what it missed
+            let status = self.status_message(&self.status);
+            if let Some(closing) = send(ws, status).await? {
+                return Ok(closing);
+            }
+            for message in std::mem::take(&mut self.replay) {
+                if let Some(closing) = send(ws, message.text.to_string()).await? {
+                    return Ok(closing);
+                }
+            }
+
             // Send all messages down the WebSocket until and unless an error is encountered, the
-            // client websocket connection is closed, or the nats subscriber naturally closes
+            // client websocket connection is closed, or the log of the workspace goes away
             loop {
                 tokio::select! {
                     msg = ws.recv() => {
@@ -140,29 +208,28 @@ mod workspace_updates {
                             None => return Ok(WorkspaceUpdatesClosing { ws_is_closed: true }),
                         }
                     }
-                    Some(nats_msg) = self.subscriber.next() => {
-                        let msg = ws::Message::Text(String::from_utf8_lossy(nats_msg.payload()).to_string());
-
-                        if let Err(err) = ws.send(msg).await {
-                            match err
-                                .source()
-                                .and_then(|err| err.downcast_ref::<tungstenite::Error>())
-                            {
-                                Some(ws_err) => match ws_err {
-                                    // If the websocket has cleanly closed, we should cleanly finish as
-                                    // well--this is not an error condition
-                                    tungstenite::Error::ConnectionClosed
-                                    | tungstenite::Error::AlreadyClosed => {
-                                        trace!("websocket has cleanly closed, ending");
-                                        return Ok(WorkspaceUpdatesClosing { ws_is_closed: true });
-                                    }
-                                    _ => return Err(WorkspaceUpdatesError::WsSendIo(err)),
-                                },
-                                None => return Err(WorkspaceUpdatesError::WsSendIo(err)),
+                    message = self.live.recv() => {
+                        let text = match message {
+                            Ok(message) => {
+                                self.status.sequence = message.sequence;
+                                message.text.to_string()
+                            }
+                            Err(RecvError::Lagged(skipped)) => {
+                                // The client is reading slower than messages are published and
+                                // missed some of them, which it needs to know about
+                                debug!(skipped, "workspace_updates lagged behind");
+                                let status = StreamStatus {
+                                    gap: true,
+                                    ..self.status.clone()
+                                };
+                                self.status_message(&status)
                             }
+                            Err(RecvError::Closed) => break,
+                        };
+                        if let Some(closing) = send(ws, text).await? {
+                            return Ok(closing);
                         }
                     }
-                    else => break,
                 }
             }
 
@@ -170,6 +237,39 @@ mod workspace_updates {
                 ws_is_closed: false,
             })
         }
+
+        /// Wraps the status like a `WsEvent`, so that clients can handle it like any other
+        /// message.
+        fn status_message(&self, status: &StreamStatus) -> String {
+            serde_json::json!({
+                "version": 1,
+                "workspace_pk": self.workspace_pk,
+                "payload": { "kind": "StreamStatus", "data": status },
+            })
+            .to_string()
+        }
+    }
+
+    /// Sends the message, returning how to close if the websocket has closed.
+    async fn send(ws: &mut WebSocket, text: String) -> Result<Option<WorkspaceUpdatesClosing>> {
+        if let Err(err) = ws.send(ws::Message::Text(text)).await {
+            match err
+                .source()
+                .and_then(|err| err.downcast_ref::<tungstenite::Error>())
+            {
+                Some(ws_err) => match ws_err {
+                    // If the websocket has cleanly closed, we should cleanly finish as
+                    // well--this is not an error condition
+                    tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed => {
+                        trace!("websocket has cleanly closed, ending");
+                        return Ok(Some(WorkspaceUpdatesClosing { ws_is_closed: true }));
+                    }
+                    _ => return Err(WorkspaceUpdatesError::WsSendIo(err)),
+                },
+                None => return Err(WorkspaceUpdatesError::WsSendIo(err)),
+            }
+        }
+        Ok(None)
     }
 
     #[derive(Debug)]
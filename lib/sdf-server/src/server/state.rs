@@ -6,6 +6,7 @@ use si_std::SensitiveString;
 use tokio::sync::{broadcast, mpsc};
 
 use super::server::ShutdownSource;
+use super::service::ws::event_log::WsEventLog;
 
 #[derive(Clone, FromRef)]
 pub struct AppState {
@@ -14,6 +15,7 @@ pub struct AppState {
     jwt_public_signing_key: JwtPublicSigningKey,
     posthog_client: PosthogClient,
     shutdown_broadcast: ShutdownBroadcast,
+    ws_event_log: WsEventLog,
     for_tests: bool,
 
     // TODO(fnichol): we're likely going to use this, but we can't allow it to be dropped because
@@ -38,6 +40,7 @@ impl AppState {
             jwt_public_signing_key: jwt_public_signing_key.into(),
             posthog_client: posthog_client.into(),
             shutdown_broadcast: ShutdownBroadcast(shutdown_broadcast_tx),
+            ws_event_log: WsEventLog::default(),
             for_tests,
             _tmp_shutdown_tx: Arc::new(tmp_shutdown_tx),
         }
@@ -0,0 +1,66 @@
+use axum::{http::Method, Router};
+use dal::{Component, Manifest, StandardModel, Visibility};
+use dal_test::{sdf_test, AuthTokenRef, DalContextHead};
+use sdf_server::service::diagram::apply_manifest::{ApplyManifestRequest, ApplyManifestResponse};
+
+use crate::service_tests::api_request_auth_json_body;
+
+const MANIFEST: &str = r#"
+components:
+  - name: tail
+    schemaName: fallout
+  - name: head
+    schemaName: starfield
+    values:
+      domain:
+        freestar: t2.micro
+connections:
+  - fromComponent: tail
+    fromSocket: bethesda
+    toComponent: head
+    toSocket: bethesda
+"#;
+
+#[sdf_test]
+async fn apply_manifest_and_its_change_set(
+    DalContextHead(ctx): DalContextHead,
+    app: Router,
+    AuthTokenRef(auth_token): AuthTokenRef<'_>,
+) {
+    let request = ApplyManifestRequest {
+        manifest: MANIFEST.to_owned(),
+        prune: false,
+        apply: true,
+        visibility: Visibility::new_head(false),
+    };
+
+    let response: ApplyManifestResponse = api_request_auth_json_body(
+        app,
+        Method::POST,
+        "/api/diagram/apply_manifest",
+        auth_token,
+        &request,
+    )
+    .await;
+    assert!(response.applied);
+    assert!(response.change_set_pk.is_some());
+    assert!(!response.plan.is_empty());
+
+    // The change set was applied, so head matches the manifest.
+    for name in ["tail", "head"] {
+        let component_id = response
+            .components
+            .get(name)
+            .unwrap_or_else(|| panic!("{name} was not created"));
+        assert!(Component::get_by_id(&ctx, component_id)
+            .await
+            .expect("could not get component")
+            .is_some());
+    }
+    let plan = Manifest::parse(MANIFEST)
+        .expect("could not parse manifest")
+        .plan(&ctx, false)
+        .await
+        .expect("could not plan");
+    assert!(plan.is_empty());
+}
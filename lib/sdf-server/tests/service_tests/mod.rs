@@ -8,6 +8,8 @@ use tower::ServiceExt;
 
 mod change_set;
 mod component;
+mod diagram;
+mod openapi;
 mod scenario;
 mod schema;
 mod secret;
@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+/// Collects the schemas that were guessed from the name of a Rust type without finding out what
+/// it is, along with where they are in the document.
+fn opaque_schemas(value: &Value, location: &str, opaque: &mut Vec<String>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(rust_type) = object.get("x-rust-type") {
+                if !object.contains_key("type") {
+                    opaque.push(format!("{location}: {rust_type}"));
+                }
+            }
+            for (key, child) in object {
+                opaque_schemas(child, &format!("{location}/{key}"), opaque);
+            }
+        }
+        Value::Array(array) => {
+            for (index, child) in array.iter().enumerate() {
+                opaque_schemas(child, &format!("{location}/{index}"), opaque);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn every_schema_is_described() {
+    let document = sdf_server::api_document();
+
+    let mut opaque = Vec::new();
+    opaque_schemas(&document, "#", &mut opaque);
+    assert!(
+        opaque.is_empty(),
+        "schemas of types that implement neither ApiSchema nor are described in openapi/remote.rs: {opaque:#?}"
+    );
+
+    // Types from dal are described by their fields.
+    let change_set = &document["components"]["schemas"]["ChangeSet"];
+    assert_eq!(
+        serde_json::json!(["pk", "name", "status", "created_at", "updated_at"]),
+        change_set["required"]
+    );
+}
+
+#[test]
+fn document_is_well_formed() {
+    let document = sdf_server::api_document();
+    assert_eq!("3.0.3", document["openapi"]);
+
+    let schemas = document["components"]["schemas"]
+        .as_object()
+        .expect("document has no schemas");
+    let text = document.to_string();
+    for reference in text.split("\"$ref\":\"").skip(1) {
+        let reference = reference.split('"').next().unwrap_or_default();
+        if let Some(name) = reference.strip_prefix("#/components/schemas/") {
+            assert!(schemas.contains_key(name), "dangling reference {reference}");
+        }
+    }
+
+    // Requests flatten the visibility into their query parameters.
+    let parameters = document["paths"]["/api/change_set/get_stats"]["get"]["parameters"]
+        .as_array()
+        .expect("get_stats has no parameters");
+    assert!(parameters
+        .iter()
+        .any(|parameter| parameter["name"] == "visibility_change_set_pk"
+            && parameter["required"] == true));
+
+    // Fields follow the serde renames of their types.
+    let create_webhook = &schemas["CreateWebhookRequest"];
+    assert_eq!(
+        serde_json::json!(["url", "eventKinds"]),
+        create_webhook["required"]
+    );
+}
@@ -1,5 +1,6 @@
 use color_eyre::eyre::ErrReport;
 use color_eyre::Result;
+use sdf_client::{ComponentId, SdfClientError};
 use std::env::VarError;
 use thiserror::Error;
 
@@ -7,6 +8,7 @@ pub mod cmd;
 pub mod engine;
 mod key_management;
 pub mod state;
+pub mod workspace;
 
 pub const CONTAINER_NAMES: &[&str] = &[
     "jaeger", "postgres", "nats", "otelcol", "council", "veritech", "pinga", "sdf", "web",
@@ -15,6 +17,8 @@ pub const CONTAINER_NAMES: &[&str] = &[
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum SiCliError {
+    #[error("component not found: {0}")]
+    ComponentNotFound(ComponentId),
     #[error("unable to connect to the container engine")]
     ContainerEngine,
     #[error("ctrl+c")]
@@ -31,6 +35,10 @@ pub enum SiCliError {
     IncorrectInstallMode(String),
     #[error("aborting installation")]
     Installation,
+    #[error("invalid id: {0}")]
+    InvalidId(String),
+    #[error("invalid import source: {0}")]
+    InvalidImportSource(String),
     #[error("io: {0}")]
     Io(#[from] std::io::Error),
     #[error("join: {0}")]
@@ -39,8 +47,18 @@ pub enum SiCliError {
     MissingDataDir(),
     #[error("podman api: {0}")]
     Podman(#[from] podman_api::Error),
+    #[error("prop not found: {0}")]
+    PropNotFound(String),
     #[error("reqwest: {0}")]
     Reqwest(#[from] reqwest::Error),
+    #[error("schema not found: {0}")]
+    SchemaNotFound(String),
+    #[error("sdf: {0}")]
+    SdfClient(#[from] SdfClientError),
+    #[error("serde json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("socket {0} not found on component {1}")]
+    SocketNotFound(String, ComponentId),
     #[error("toml deserialize error: {0}")]
     TomlDeserialize(#[from] toml::de::Error),
     #[error("unable to download update, status = {0}")]
@@ -51,6 +69,8 @@ pub enum SiCliError {
     UnableToFetchSiUpdate(u16),
     #[error("unsupported operating system: {0}")]
     UnsupportedOperatingSystem(String),
+    #[error("url parse: {0}")]
+    UrlParse(#[from] url::ParseError),
     #[error("env var: {0}")]
     Var(#[from] VarError),
     #[error("web portal is currently offline - please check that the system is running")]
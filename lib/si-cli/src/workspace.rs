@@ -0,0 +1,652 @@
+//! This module contains [`Workspace`], which drives a workspace through a running sdf rather than
+//! managing the local containers. Everything it prints is json, one document per line, so that
+//! it can be scripted, for example from CI.
+
+use std::{path::Path, str::FromStr};
+
+use futures::StreamExt;
+use sdf_client::{
+    change_set::{ApplyChangeSetRequest, CreateChangeSetRequest},
+    component::{
+        GetPropertyEditorSchemaRequest, GetPropertyEditorSchemaResponse,
+        GetPropertyEditorValuesRequest, GetPropertyEditorValuesResponse, ListQualificationsRequest,
+        PropertyEditorValue, UpdatePropertyEditorValueRequest,
+    },
+    diagram::{
+        ApplyManifestRequest, AttachImportedResourcesRequest, CreateConnectionRequest,
+        CreateNodeRequest, GetDiagramRequest, ImportResourcesRequest, ImportSource,
+        ListSchemaVariantsRequest, PlanManifestRequest, PlanResourceImportRequest, SocketDirection,
+    },
+    AttributeValueId, ChangeSetPk, ComponentId, FixBatchId, SdfClient, SocketId, Visibility,
+    WsEventStream, WsUpdate,
+};
+use serde::Serialize;
+use url::Url;
+
+use crate::{CliResult, SiCliError};
+
+/// A workspace of a running sdf, reached as the user the api token belongs to.
+#[derive(Debug)]
+pub struct Workspace {
+    client: SdfClient,
+}
+
+impl Workspace {
+    pub fn new(sdf_url: &str, api_token: &str) -> CliResult<Self> {
+        Ok(Self {
+            client: SdfClient::new(Url::parse(sdf_url)?, api_token),
+        })
+    }
+
+    pub async fn list_change_sets(&self) -> CliResult<()> {
+        emit(&self.client.list_open_change_sets().await?)
+    }
+
+    pub async fn create_change_set(&self, name: &str) -> CliResult<()> {
+        let response = self
+            .client
+            .create_change_set(&CreateChangeSetRequest {
+                change_set_name: name.to_owned(),
+            })
+            .await?;
+        emit(&response.change_set)
+    }
+
+    /// Creates a component of the default variant of the schema with the given name, and names
+    /// it if a name is given.
+    pub async fn create_component(
+        &self,
+        change_set_pk: &str,
+        schema_name: &str,
+        name: Option<&str>,
+        x: isize,
+        y: isize,
+    ) -> CliResult<()> {
+        let visibility = visibility(change_set_pk)?;
+        let variants = self
+            .client
+            .list_schema_variants(&ListSchemaVariantsRequest { visibility })
+            .await?;
+        let schema_id = variants
+            .iter()
+            .find(|variant| variant.schema_name == schema_name && variant.is_default)
+            .map(|variant| variant.schema_id)
+            .ok_or_else(|| SiCliError::SchemaNotFound(schema_name.to_owned()))?;
+
+        let response = self
+            .client
+            .create_node(&CreateNodeRequest {
+                schema_id,
+                parent_id: None,
+                x: x.to_string(),
+                y: y.to_string(),
+                visibility,
+            })
+            .await?;
+        if let Some(name) = name {
+            self.update_prop(
+                visibility,
+                response.component_id,
+                "/root/si/name",
+                serde_json::Value::String(name.to_owned()),
+            )
+            .await?;
+        }
+        emit(&response)
+    }
+
+    /// Sets the value of the prop at `path`, like `/root/domain/region`. Entries of maps are
+    /// reached by their key. The value is parsed as json, and taken as a string if it isn't.
+    pub async fn set_prop(
+        &self,
+        change_set_pk: &str,
+        component_id: &str,
+        path: &str,
+        value: &str,
+    ) -> CliResult<()> {
+        let visibility = visibility(change_set_pk)?;
+        let component_id = parse_id::<ComponentId>(component_id)?;
+        let value = serde_json::from_str(value)
+            .unwrap_or_else(|_| serde_json::Value::String(value.to_owned()));
+
+        self.update_prop(visibility, component_id, path, value.clone())
+            .await?;
+        emit(&serde_json::json!({
+            "componentId": component_id,
+            "path": path,
+            "value": value,
+        }))
+    }
+
+    async fn update_prop(
+        &self,
+        visibility: Visibility,
+        component_id: ComponentId,
+        path: &str,
+        value: serde_json::Value,
+    ) -> CliResult<()> {
+        let schema = self
+            .client
+            .get_property_editor_schema(&GetPropertyEditorSchemaRequest {
+                component_id,
+                visibility,
+            })
+            .await?;
+        let values = self
+            .client
+            .get_property_editor_values(&GetPropertyEditorValuesRequest {
+                component_id,
+                visibility,
+            })
+            .await?;
+
+        let (parent, target) = find_value(&schema, &values, path)?;
+
+        self.client
+            .update_property_editor_value(&UpdatePropertyEditorValueRequest {
+                attribute_value_id: target.id,
+                parent_attribute_value_id: parent,
+                prop_id: target.prop_id,
+                component_id,
+                value: Some(value),
+                key: target.key.clone(),
+                visibility,
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Connects an output socket of a component to an input socket of another, by their names.
+    pub async fn connect(
+        &self,
+        change_set_pk: &str,
+        from_component_id: &str,
+        from_socket: &str,
+        to_component_id: &str,
+        to_socket: &str,
+    ) -> CliResult<()> {
+        let visibility = visibility(change_set_pk)?;
+        let from_component_id = parse_id::<ComponentId>(from_component_id)?;
+        let to_component_id = parse_id::<ComponentId>(to_component_id)?;
+
+        let diagram = self
+            .client
+            .get_diagram(&GetDiagramRequest { visibility })
+            .await?;
+        let find_socket = |component_id: ComponentId, name: &str, unwanted: SocketDirection| {
+            let component = diagram
+                .components
+                .iter()
+                .find(|component| component.id == component_id)
+                .ok_or(SiCliError::ComponentNotFound(component_id))?;
+            let socket = component
+                .sockets
+                .iter()
+                .flatten()
+                .find(|socket| socket.label == name && socket.direction != unwanted)
+                .ok_or_else(|| SiCliError::SocketNotFound(name.to_owned(), component_id))?;
+            Ok::<_, SiCliError>((component.node_id, parse_id::<SocketId>(&socket.id)?))
+        };
+        let (from_node_id, from_socket_id) =
+            find_socket(from_component_id, from_socket, SocketDirection::Input)?;
+        let (to_node_id, to_socket_id) =
+            find_socket(to_component_id, to_socket, SocketDirection::Output)?;
+
+        let response = self
+            .client
+            .create_connection(&CreateConnectionRequest {
+                from_node_id,
+                from_socket_id,
+                to_node_id,
+                to_socket_id,
+                visibility,
+            })
+            .await?;
+        emit(&response.connection)
+    }
+
+    /// Prints the changes needed for the workspace to match the manifest in the file, as seen
+    /// from the change set, or from head if none is given. With `prune`, the components the
+    /// manifest does not describe are deleted.
+    pub async fn plan_manifest(
+        &self,
+        change_set_pk: Option<&str>,
+        path: &Path,
+        prune: bool,
+    ) -> CliResult<()> {
+        let response = self
+            .client
+            .plan_manifest(&PlanManifestRequest {
+                manifest: std::fs::read_to_string(path)?,
+                prune,
+                visibility: optional_visibility(change_set_pk)?,
+            })
+            .await?;
+        emit(&response.plan)
+    }
+
+    /// Makes the changes needed for the workspace to match the manifest in the file in the
+    /// change set, or in a new one if none is given, and applies it with `apply`.
+    pub async fn apply_manifest(
+        &self,
+        change_set_pk: Option<&str>,
+        path: &Path,
+        prune: bool,
+        apply: bool,
+    ) -> CliResult<()> {
+        let response = self
+            .client
+            .apply_manifest(&ApplyManifestRequest {
+                manifest: std::fs::read_to_string(path)?,
+                prune,
+                apply,
+                visibility: optional_visibility(change_set_pk)?,
+            })
+            .await?;
+        emit(&response)
+    }
+
+    /// Prints what importing the Terraform state file or CloudFormation template would change,
+    /// as seen from the change set, or from head if none is given.
+    pub async fn plan_import(
+        &self,
+        change_set_pk: Option<&str>,
+        source: &str,
+        path: &Path,
+    ) -> CliResult<()> {
+        let response = self
+            .client
+            .plan_resource_import(&PlanResourceImportRequest {
+                source: parse_import_source(source)?,
+                content: std::fs::read_to_string(path)?,
+                visibility: optional_visibility(change_set_pk)?,
+            })
+            .await?;
+        emit(&response.plan)
+    }
+
+    /// Imports the resources of the Terraform state file or CloudFormation template into the
+    /// change set, or a new one if none is given.
+    pub async fn import(
+        &self,
+        change_set_pk: Option<&str>,
+        source: &str,
+        path: &Path,
+    ) -> CliResult<()> {
+        let response = self
+            .client
+            .import_resources(&ImportResourcesRequest {
+                source: parse_import_source(source)?,
+                content: std::fs::read_to_string(path)?,
+                visibility: optional_visibility(change_set_pk)?,
+            })
+            .await?;
+        emit(&response)
+    }
+
+    /// Attaches the resources recorded in the Terraform state file to the components it was
+    /// imported as, once the change set of the import has been applied.
+    pub async fn attach_resources(&self, path: &Path) -> CliResult<()> {
+        let response = self
+            .client
+            .attach_imported_resources(&AttachImportedResourcesRequest {
+                source: ImportSource::Terraform,
+                content: std::fs::read_to_string(path)?,
+            })
+            .await?;
+        emit(&response)
+    }
+
+    /// Prints the results of the qualifications of a component. Qualifications run again
+    /// whenever the values of the component change.
+    pub async fn qualifications(&self, change_set_pk: &str, component_id: &str) -> CliResult<()> {
+        let response = self
+            .client
+            .list_qualifications(&ListQualificationsRequest {
+                component_id: parse_id(component_id)?,
+                visibility: visibility(change_set_pk)?,
+            })
+            .await?;
+        emit(&response)
+    }
+
+    /// Applies the change set. With `tail`, the results of the fixes its actions start are
+    /// printed as they come in, until they have all run.
+    pub async fn apply_change_set(&self, change_set_pk: &str, tail: bool) -> CliResult<()> {
+        let change_set_pk = parse_id::<ChangeSetPk>(change_set_pk)?;
+
+        // Subscribe before applying, so that no result is missed.
+        let fixes = if tail {
+            Some(self.client.workspace_updates(None).await?)
+        } else {
+            None
+        };
+
+        let response = self
+            .client
+            .apply_change_set(&ApplyChangeSetRequest { change_set_pk })
+            .await?;
+        emit(&response.change_set)?;
+
+        if let Some(stream) = fixes {
+            match response.fix_batch_id {
+                Some(batch_id) => self.tail(stream, TailUntil::BatchDone(batch_id)).await?,
+                // The change set had no actions, so there is nothing to wait for.
+                None => stream.close().await?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Prints the results of fixes as they come in, until the first batch of fixes has run if
+    /// `until_batch_done` is set, or forever otherwise.
+    pub async fn tail_fixes(&self, until_batch_done: bool) -> CliResult<()> {
+        let stream = self.client.workspace_updates(None).await?;
+        let until = if until_batch_done {
+            TailUntil::AnyBatchDone
+        } else {
+            TailUntil::Forever
+        };
+        self.tail(stream, until).await
+    }
+
+    async fn tail(&self, mut stream: WsEventStream, until: TailUntil) -> CliResult<()> {
+        loop {
+            let update = match stream.next().await {
+                Some(update) => update,
+                None => {
+                    // sdf closed the websocket, pick up where it left off.
+                    let resume_from = stream.resume_from();
+                    stream = self.client.workspace_updates(resume_from.as_ref()).await?;
+                    continue;
+                }
+            };
+            match update? {
+                WsUpdate::Event { event, .. } => match event.payload.kind.as_str() {
+                    "FixReturn" if until.includes(&event.payload.data, "batchId") => {
+                        emit(&event.payload)?
+                    }
+                    "FixBatchReturn" if until.includes(&event.payload.data, "id") => {
+                        emit(&event.payload)?;
+                        if until != TailUntil::Forever {
+                            break;
+                        }
+                    }
+                    _ => {}
+                },
+                // Results were missed, which whoever reads them needs to know.
+                WsUpdate::Status(status) if status.gap => emit(&serde_json::json!({
+                    "kind": "StreamStatus",
+                    "data": status,
+                }))?,
+                WsUpdate::Status(_) => {}
+            }
+        }
+        stream.close().await?;
+        Ok(())
+    }
+}
+
+/// When [`Workspace::tail`] stops printing the results of fixes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TailUntil {
+    /// Never, until the command is stopped.
+    Forever,
+    /// Once a batch of fixes has run.
+    AnyBatchDone,
+    /// Once the given batch of fixes has run. The results of other batches are left out.
+    BatchDone(FixBatchId),
+}
+
+impl TailUntil {
+    /// Whether the results in the data of a fix event are printed, going by the batch id found
+    /// in `field`.
+    fn includes(&self, data: &serde_json::Value, field: &str) -> bool {
+        match self {
+            Self::BatchDone(batch_id) => data.get(field) == Some(&serde_json::json!(batch_id)),
+            Self::AnyBatchDone | Self::Forever => true,
+        }
+    }
+}
+
+/// Finds the value of the prop at `path` among the values of a component, along with the id of
+/// the value of its parent, if it has one.
+fn find_value<'a>(
+    schema: &GetPropertyEditorSchemaResponse,
+    values: &'a GetPropertyEditorValuesResponse,
+    path: &str,
+) -> CliResult<(Option<AttributeValueId>, &'a PropertyEditorValue)> {
+    let mut segments = path.trim_matches('/').split('/').peekable();
+    if segments.peek() == Some(&"root") {
+        segments.next();
+    }
+    let mut parent = None;
+    let mut current = values.root_value_id;
+    for segment in segments {
+        let children = values
+            .child_values
+            .get(&current)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let child = children
+            .iter()
+            .find(|child| {
+                values.values.get(child).map_or(false, |value| {
+                    value.key.as_deref() == Some(segment)
+                        || schema
+                            .props
+                            .get(&value.prop_id)
+                            .map_or(false, |prop| prop.name == segment)
+                })
+            })
+            .ok_or_else(|| SiCliError::PropNotFound(path.to_owned()))?;
+        parent = Some(current);
+        current = *child;
+    }
+    let target = values
+        .values
+        .get(&current)
+        .ok_or_else(|| SiCliError::PropNotFound(path.to_owned()))?;
+    Ok((parent, target))
+}
+
+fn emit(value: &impl Serialize) -> CliResult<()> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
+
+fn parse_id<T: FromStr>(id: &str) -> CliResult<T> {
+    T::from_str(id).map_err(|_| SiCliError::InvalidId(id.to_owned()))
+}
+
+fn parse_import_source(source: &str) -> CliResult<ImportSource> {
+    match source {
+        "cloudFormation" => Ok(ImportSource::CloudFormation),
+        "terraform" => Ok(ImportSource::Terraform),
+        _ => Err(SiCliError::InvalidImportSource(source.to_owned())),
+    }
+}
+
+fn visibility(change_set_pk: &str) -> CliResult<Visibility> {
+    Ok(Visibility::new_change_set(parse_id(change_set_pk)?))
+}
+
+fn optional_visibility(change_set_pk: Option<&str>) -> CliResult<Visibility> {
+    match change_set_pk {
+        Some(change_set_pk) => visibility(change_set_pk),
+        None => Ok(Visibility::head()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use sdf_client::{
+        component::{PropertyEditorProp, PropertyEditorPropKind},
+        PropId,
+    };
+
+    use super::*;
+
+    fn id<T: FromStr>(number: u8) -> T {
+        parse_id(&format!("{number:0>26}")).expect("could not make id")
+    }
+
+    /// A component with `/root/si/name` set, and a `/root/domain/tags` map with "team" and "env"
+    /// entries.
+    fn component() -> (
+        GetPropertyEditorSchemaResponse,
+        GetPropertyEditorValuesResponse,
+    ) {
+        let props = [
+            (1, "root", PropertyEditorPropKind::Object),
+            (2, "si", PropertyEditorPropKind::Object),
+            (3, "name", PropertyEditorPropKind::String),
+            (4, "domain", PropertyEditorPropKind::Object),
+            (5, "tags", PropertyEditorPropKind::Map),
+            (6, "tag", PropertyEditorPropKind::String),
+        ];
+        let schema = GetPropertyEditorSchemaResponse {
+            root_prop_id: id(1),
+            props: props
+                .into_iter()
+                .map(|(number, name, kind)| {
+                    (
+                        id(number),
+                        PropertyEditorProp {
+                            id: id(number),
+                            name: name.to_owned(),
+                            kind,
+                            doc_link: None,
+                        },
+                    )
+                })
+                .collect(),
+            child_props: HashMap::from([
+                (id(1), vec![id(2), id(4)]),
+                (id(2), vec![id(3)]),
+                (id(4), vec![id(5)]),
+                (id(5), vec![id(6)]),
+            ]),
+        };
+
+        // Values are numbered like the props they are for, from 11 on, except for the entries
+        // of the map.
+        let values = [
+            (11, 1, None),
+            (12, 2, None),
+            (13, 3, None),
+            (14, 4, None),
+            (15, 5, None),
+            (16, 6, Some("team")),
+            (17, 6, Some("env")),
+        ];
+        let values = GetPropertyEditorValuesResponse {
+            root_value_id: id(11),
+            values: values
+                .into_iter()
+                .map(|(number, prop_number, key): (u8, u8, Option<&str>)| {
+                    (
+                        id(number),
+                        PropertyEditorValue {
+                            id: id(number),
+                            prop_id: id(prop_number),
+                            key: key.map(ToOwned::to_owned),
+                            value: serde_json::Value::Null,
+                            is_from_external_source: false,
+                            is_overridden: false,
+                            is_locked: false,
+                        },
+                    )
+                })
+                .collect(),
+            child_values: HashMap::from([
+                (id(11), vec![id(12), id(14)]),
+                (id(12), vec![id(13)]),
+                (id(14), vec![id(15)]),
+                (id(15), vec![id(16), id(17)]),
+            ]),
+        };
+
+        (schema, values)
+    }
+
+    fn found(path: &str) -> (Option<AttributeValueId>, PropId) {
+        let (schema, values) = component();
+        let (parent, value) = find_value(&schema, &values, path).expect("could not find value");
+        (parent, value.prop_id)
+    }
+
+    #[test]
+    fn finds_props_by_name() {
+        assert_eq!((Some(id(12)), id(3)), found("/root/si/name"));
+        assert_eq!((Some(id(14)), id(5)), found("/root/domain/tags"));
+        assert_eq!((None, id(1)), found("/root"));
+    }
+
+    #[test]
+    fn root_and_slashes_are_optional() {
+        assert_eq!((Some(id(12)), id(3)), found("si/name"));
+        assert_eq!((Some(id(12)), id(3)), found("/si/name/"));
+    }
+
+    #[test]
+    fn finds_map_entries_by_key() {
+        let (schema, values) = component();
+        let (parent, value) =
+            find_value(&schema, &values, "/root/domain/tags/env").expect("could not find entry");
+        assert_eq!(Some(id(15)), parent);
+        assert_eq!(id::<AttributeValueId>(17), value.id);
+        assert_eq!(Some("env"), value.key.as_deref());
+    }
+
+    #[test]
+    fn missing_props_are_errors() {
+        let (schema, values) = component();
+        for path in [
+            "/root/domain/region",
+            "/root/domain/tags/owner",
+            "/root/si/name/first",
+        ] {
+            match find_value(&schema, &values, path) {
+                Err(SiCliError::PropNotFound(missing)) => assert_eq!(path, missing),
+                other => panic!("expected {path} to be missing, found {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn import_sources() {
+        assert_eq!(
+            ImportSource::CloudFormation,
+            parse_import_source("cloudFormation").expect("could not parse source")
+        );
+        assert_eq!(
+            ImportSource::Terraform,
+            parse_import_source("terraform").expect("could not parse source")
+        );
+        for source in ["Terraform", "cloudformation", ""] {
+            match parse_import_source(source) {
+                Err(SiCliError::InvalidImportSource(invalid)) => assert_eq!(source, invalid),
+                other => panic!("expected {source:?} to be invalid, found {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn tails_only_the_batch_of_the_apply() {
+        let until = TailUntil::BatchDone(id(1));
+        assert!(until.includes(
+            &serde_json::json!({ "batchId": id::<FixBatchId>(1) }),
+            "batchId"
+        ));
+        assert!(!until.includes(
+            &serde_json::json!({ "batchId": id::<FixBatchId>(2) }),
+            "batchId"
+        ));
+        assert!(!until.includes(&serde_json::json!({ "id": id::<FixBatchId>(1) }), "batchId"));
+        assert!(TailUntil::AnyBatchDone
+            .includes(&serde_json::json!({ "id": id::<FixBatchId>(2) }), "id"));
+    }
+}
@@ -13,7 +13,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let buf = fs::read(&path).await?;
     let pkg = SiPkg::load_from_bytes(buf)?;
 
-    let (graph, _root_idx) = pkg.as_petgraph();
+    let (graph, _root_idx) = pkg.as_petgraph()?;
     println!(
         "\n---- snip ----\n{:?}\n---- snip ----\n",
         Dot::with_config(graph, &[Config::EdgeNoLabel])
@@ -11,7 +11,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("--- Reading object tree from file: {path}");
     let pkg = SiPkg::load_from_file(path).await?;
 
-    let (graph, _root_idx) = pkg.as_petgraph();
+    let (graph, _root_idx) = pkg.as_petgraph()?;
     println!(
         "\n---- snip ----\n{:?}\n---- snip ----\n",
         Dot::with_config(graph, &[Config::EdgeNoLabel])
@@ -1,25 +1,29 @@
+mod lint;
 pub(crate) mod node;
 mod pkg;
 mod spec;
 
+pub use lint::{lint, LintIssue, LintKind, LintReport, LintSeverity};
 pub use pkg::{
-    SiPkg, SiPkgActionFunc, SiPkgAttrFuncInput, SiPkgAttrFuncInputView, SiPkgChangeSet, SiPkgError,
-    SiPkgFunc, SiPkgFuncArgument, SiPkgFuncData, SiPkgKind, SiPkgLeafFunction, SiPkgMapKeyFunc,
-    SiPkgMetadata, SiPkgProp, SiPkgPropData, SiPkgSchema, SiPkgSchemaData, SiPkgSchemaVariant,
-    SiPkgSchemaVariantData, SiPkgSocket, SiPkgSocketData, SiPkgValidation,
+    SiPkg, SiPkgActionFunc, SiPkgAttrFuncInput, SiPkgAttrFuncInputView, SiPkgChangeSet,
+    SiPkgComponentTemplate, SiPkgError, SiPkgFunc, SiPkgFuncArgument, SiPkgFuncData, SiPkgKind,
+    SiPkgLeafFunction, SiPkgMapKeyFunc, SiPkgMetadata, SiPkgProp, SiPkgPropData, SiPkgSchema,
+    SiPkgSchemaData, SiPkgSchemaVariant, SiPkgSchemaVariantData, SiPkgSocket, SiPkgSocketData,
+    SiPkgValidation,
 };
 pub use spec::{
     ActionFuncSpec, ActionFuncSpecBuilder, ActionFuncSpecKind, AttrFuncInputSpec,
     AttrFuncInputSpecKind, ChangeSetSpec, ChangeSetSpecBuilder, ChangeSetSpecStatus,
-    FuncArgumentKind, FuncArgumentSpec, FuncArgumentSpecBuilder, FuncSpec, FuncSpecBackendKind,
-    FuncSpecBackendResponseType, FuncSpecData, FuncSpecDataBuilder, LeafFunctionSpec,
-    LeafFunctionSpecBuilder, LeafInputLocation, LeafKind, MapKeyFuncSpec, MapKeyFuncSpecBuilder,
-    PkgSpec, PkgSpecBuilder, PropSpec, PropSpecBuilder, PropSpecKind, PropSpecWidgetKind,
-    SchemaSpec, SchemaSpecBuilder, SchemaSpecData, SchemaSpecDataBuilder, SchemaVariantSpec,
-    SchemaVariantSpecBuilder, SchemaVariantSpecComponentType, SchemaVariantSpecData,
-    SchemaVariantSpecPropRoot, SiPropFuncSpec, SiPropFuncSpecBuilder, SiPropFuncSpecKind,
-    SocketSpec, SocketSpecArity, SocketSpecData, SocketSpecDataBuilder, SocketSpecKind, SpecError,
-    ValidationSpec, ValidationSpecKind,
+    ComponentTemplateSpec, ComponentTemplateSpecBuilder, FuncArgumentKind, FuncArgumentSpec,
+    FuncArgumentSpecBuilder, FuncSpec, FuncSpecBackendKind, FuncSpecBackendResponseType,
+    FuncSpecData, FuncSpecDataBuilder, LeafFunctionSpec, LeafFunctionSpecBuilder,
+    LeafInputLocation, LeafKind, MapKeyFuncSpec, MapKeyFuncSpecBuilder, PkgSpec, PkgSpecBuilder,
+    PropSpec, PropSpecBuilder, PropSpecKind, PropSpecWidgetKind, SchemaSpec, SchemaSpecBuilder,
+    SchemaSpecData, SchemaSpecDataBuilder, SchemaVariantSpec, SchemaVariantSpecBuilder,
+    SchemaVariantSpecComponentType, SchemaVariantSpecData, SchemaVariantSpecPropRoot,
+    SiPropFuncSpec, SiPropFuncSpecBuilder, SiPropFuncSpecKind, SocketSpec, SocketSpecArity,
+    SocketSpecData, SocketSpecDataBuilder, SocketSpecKind, SpecError, ValidationSpec,
+    ValidationSpecKind,
 };
 
 #[cfg(test)]
@@ -51,7 +55,7 @@ mod tests {
 
         let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
 
-        let (graph, _root_idx) = pkg.as_petgraph();
+        let (graph, _root_idx) = pkg.as_petgraph().expect("failed to get graph");
 
         let funcs = pkg.funcs().expect("failed to get funcs");
         assert_eq!(2, funcs.len());
@@ -87,6 +91,255 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn pkg_lazy_reader_round_trip() {
+        let spec: PkgSpec = serde_json::from_str(PACKAGE_JSON).unwrap();
+        let description = spec.description.to_owned();
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+
+        let pkg_data = pkg.write_to_bytes().expect("failed to serialize pkg");
+
+        let lazy_pkg = SiPkg::load_from_reader(std::io::Cursor::new(pkg_data))
+            .expect("failed to load pkg from reader");
+        let metadata = lazy_pkg.metadata().expect("get metadata");
+
+        assert_eq!(description, metadata.description());
+        assert_eq!(pkg.hash().expect("get hash"), metadata.hash());
+
+        let schemas = lazy_pkg.schemas().expect("get schemas");
+        assert_eq!(pkg.schemas().expect("get schemas").len(), schemas.len());
+
+        let funcs = lazy_pkg.funcs().expect("get funcs");
+        assert_eq!(2, funcs.len());
+        assert!(lazy_pkg.change_sets().expect("get change sets").is_empty());
+
+        let rewritten_pkg = SiPkg::load_from_bytes(
+            lazy_pkg
+                .write_to_bytes()
+                .expect("failed to serialize lazy pkg"),
+        )
+        .expect("failed to load pkg from bytes");
+        assert_eq!(
+            metadata.hash(),
+            rewritten_pkg.hash().expect("get rewritten hash")
+        );
+    }
+
+    #[tokio::test]
+    async fn pkg_serialized_nodes_round_trip() {
+        let spec: PkgSpec = serde_json::from_str(WORKSPACE_JSON).unwrap();
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+        let root_hash = pkg.hash().expect("get hash");
+
+        let nodes = pkg.serialized_nodes().expect("failed to serialize nodes");
+        assert_eq!(
+            Some(root_hash),
+            nodes.last().map(|node| node.hash()),
+            "root node should be serialized last"
+        );
+
+        // Nodes which were stored individually can be verified and bundled back up
+        let stored_nodes = nodes
+            .into_iter()
+            .map(|node| object_tree::SerializedNode::new(node.hash(), node.into_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to verify nodes");
+        let bytes = object_tree::TarWriter::from_serialized_nodes(root_hash, stored_nodes)
+            .expect("failed to write nodes")
+            .bytes();
+
+        let restored_pkg = SiPkg::load_from_bytes(bytes).expect("failed to load pkg from bytes");
+        assert_eq!(root_hash, restored_pkg.hash().expect("get restored hash"));
+        assert_eq!(
+            pkg.change_sets().expect("get change sets").len(),
+            restored_pkg.change_sets().expect("get change sets").len()
+        );
+
+        assert!(object_tree::SerializedNode::new(root_hash, b"tampered".to_vec()).is_err());
+    }
+
+    #[test]
+    fn lint_complex_pkg() {
+        let spec: PkgSpec = serde_json::from_str(PACKAGE_JSON).unwrap();
+        let report = spec.lint();
+
+        assert!(report.is_empty(), "unexpected lint issues: {report}");
+    }
+
+    #[test]
+    fn lint_finds_broken_references() {
+        let spec: PkgSpec = serde_json::from_value(serde_json::json!({
+            "kind": "module",
+            "name": "broken",
+            "version": "0.1.0",
+            "description": "",
+            "createdAt": "2023-02-28T00:19:25Z",
+            "createdBy": "nick",
+            "funcs": [
+                { "name": "unused", "uniqueId": "unused", "arguments": [] },
+                { "name": "si:identity", "uniqueId": "identity", "arguments": [] },
+            ],
+            "schemas": [{
+                "name": "thing",
+                "uniqueId": "dupe",
+                "variants": [{
+                    "name": "v0",
+                    "uniqueId": "dupe",
+                    "actionFuncs": [],
+                    "leafFunctions": [],
+                    "siPropFuncs": [],
+                    "sockets": [{
+                        "name": "out",
+                        "data": {
+                            "name": "out",
+                            "kind": "output",
+                            "arity": "many",
+                            "uiHidden": false,
+                            "funcUniqueId": "identity",
+                        },
+                        "inputs": [{
+                            "kind": "inputSocket",
+                            "name": "identity",
+                            "socket_name": "missing",
+                        }],
+                    }],
+                    "domain": {
+                        "kind": "object",
+                        "name": "domain",
+                        "entries": [{
+                            "kind": "number",
+                            "name": "port",
+                            "data": {
+                                "name": "port",
+                                "defaultValue": "eighty",
+                                "validations": [{ "kind": "stringIsHexColor" }],
+                                "funcUniqueId": "nope",
+                                "inputs": [
+                                    {
+                                        "kind": "prop",
+                                        "name": "identity",
+                                        "prop_path": "root\u{0B}domain\u{0B}host",
+                                    },
+                                    {
+                                        "kind": "prop",
+                                        "name": "identity",
+                                        "prop_path": "root\u{0B}si\u{0B}name",
+                                    },
+                                ],
+                            },
+                        }],
+                    },
+                    "secrets": { "kind": "object", "name": "secrets", "entries": [] },
+                    "resourceValue": { "kind": "object", "name": "value", "entries": [] },
+                }],
+            }],
+        }))
+        .expect("failed to deserialize spec");
+
+        let report = lint(&spec);
+        let kinds: Vec<LintKind> = report.issues.iter().map(|issue| issue.kind).collect();
+
+        assert_eq!(
+            vec![
+                LintKind::DuplicateUniqueId,
+                LintKind::DanglingSocket,
+                LintKind::DanglingFuncReference,
+                LintKind::DanglingPropPath,
+                LintKind::DefaultValueKindMismatch,
+                LintKind::ValidationOnWrongPropKind,
+                LintKind::UnusedFunc,
+            ],
+            kinds
+        );
+        assert!(report.has_errors());
+        assert_eq!(2, report.warnings().count());
+        assert_eq!(
+            "schemas[thing]/variants[v0]/domain/port/validations[0]",
+            report.issues[5].location
+        );
+    }
+
+    #[tokio::test]
+    async fn pkg_validations_round_trip() {
+        let validations = serde_json::json!([
+            { "kind": "stringMatchesRegex", "pattern": "^[a-z]+$" },
+            { "kind": "stringLengthIsBetween", "lower_bound": 1 },
+            { "kind": "stringIsValidCidr" },
+            { "kind": "stringIsValidArn", "service": "iam" },
+            { "kind": "stringIsValidArn" },
+        ]);
+        let spec: PkgSpec = serde_json::from_value(serde_json::json!({
+            "kind": "module",
+            "name": "validations",
+            "version": "0.1.0",
+            "description": "",
+            "createdAt": "2023-02-28T00:19:25Z",
+            "createdBy": "nick",
+            "funcs": [],
+            "schemas": [{
+                "name": "thing",
+                "variants": [{
+                    "name": "v0",
+                    "actionFuncs": [],
+                    "leafFunctions": [],
+                    "siPropFuncs": [],
+                    "sockets": [],
+                    "domain": {
+                        "kind": "object",
+                        "name": "domain",
+                        "entries": [
+                            {
+                                "kind": "string",
+                                "name": "name",
+                                "data": { "name": "name", "validations": validations },
+                            },
+                            {
+                                "kind": "object",
+                                "name": "config",
+                                "data": {
+                                    "name": "config",
+                                    "validations": [{
+                                        "kind": "objectMatchesJsonSchema",
+                                        "schema": { "type": "object", "required": ["port"] },
+                                    }],
+                                },
+                                "entries": [],
+                            },
+                        ],
+                    },
+                    "secrets": { "kind": "object", "name": "secrets", "entries": [] },
+                    "resourceValue": { "kind": "object", "name": "value", "entries": [] },
+                }],
+            }],
+        }))
+        .expect("failed to deserialize spec");
+
+        let pkg = SiPkg::load_from_spec(spec.clone()).expect("failed to load spec");
+        let read_pkg = SiPkg::load_from_bytes(pkg.write_to_bytes().expect("failed to write pkg"))
+            .expect("failed to read pkg");
+        let read_spec = read_pkg
+            .to_spec()
+            .await
+            .expect("failed to convert pkg to spec");
+
+        let prop_validations = |spec: &PkgSpec| match &spec.schemas[0].variants[0].domain {
+            PropSpec::Object { entries, .. } => entries
+                .iter()
+                .map(|entry| match entry {
+                    PropSpec::Object { data, .. } | PropSpec::String { data, .. } => data
+                        .as_ref()
+                        .and_then(|data| data.validations.clone())
+                        .unwrap_or_default(),
+                    _ => vec![],
+                })
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+
+        assert_eq!(prop_validations(&spec), prop_validations(&read_spec));
+        assert!(spec.lint().is_empty());
+    }
+
     #[tokio::test]
     async fn pkg_bytes_round_trip() {
         let spec: PkgSpec = serde_json::from_str(PACKAGE_JSON).unwrap();
@@ -0,0 +1,724 @@
+//! Checks a [`PkgSpec`] for problems which would otherwise only be found when the package is
+//! installed, such as references to funcs, props or sockets which do not exist in the package.
+//!
+//! Linting produces a [`LintReport`] of [`LintIssue`]s. Issues with a severity of
+//! [`LintSeverity::Error`] describe a package which cannot be installed correctly and should
+//! prevent the package from being exported or published. Issues with a severity of
+//! [`LintSeverity::Warning`] describe a package which is probably not what its author intended.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display};
+
+use crate::{
+    AttrFuncInputSpec, FuncSpec, PkgSpec, PropSpec, SchemaSpec, SchemaVariantSpec,
+    SchemaVariantSpecPropRoot, SiPkgKind, SocketSpecKind, ValidationSpec,
+};
+
+/// The separator between the parts of a prop path, as used by [`AttrFuncInputSpec::Prop`].
+const PROP_PATH_SEPARATOR: &str = "\x0B";
+
+/// Sub-trees of the root prop which are created for every schema variant when it is installed and
+/// so are never described by a package.
+const BUILTIN_ROOT_PROPS: &[&str] = &["si", "resource", "code", "qualification", "deleted_at"];
+
+#[remain::sorted]
+#[derive(AsRefStr, Clone, Copy, Debug, Deserialize, Display, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+#[remain::sorted]
+#[derive(AsRefStr, Clone, Copy, Debug, Deserialize, Display, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintKind {
+    /// A func is referenced by unique id but is not in the package.
+    DanglingFuncReference,
+    /// An attribute func input refers to a prop path which does not exist in the schema variant.
+    DanglingPropPath,
+    /// An attribute func input refers to a socket which does not exist in the schema variant.
+    DanglingSocket,
+    /// A prop's default value does not match the kind of the prop.
+    DefaultValueKindMismatch,
+    /// More than one item in the same change set shares a unique id.
+    DuplicateUniqueId,
+    /// A func is included in the package but nothing uses it.
+    UnusedFunc,
+    /// A validation can never apply to the kind of prop it is attached to.
+    ValidationOnWrongPropKind,
+}
+
+impl LintKind {
+    pub fn severity(&self) -> LintSeverity {
+        match self {
+            Self::DanglingFuncReference
+            | Self::DanglingPropPath
+            | Self::DanglingSocket
+            | Self::DuplicateUniqueId
+            | Self::ValidationOnWrongPropKind => LintSeverity::Error,
+            Self::DefaultValueKindMismatch | Self::UnusedFunc => LintSeverity::Warning,
+        }
+    }
+}
+
+/// A single problem found in a package, along with where in the package it was found.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintIssue {
+    pub severity: LintSeverity,
+    pub kind: LintKind,
+    /// The location of the problem, e.g. `schemas[Docker Image]/variants[v0]/domain/image`.
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] at {}: {}",
+            self.severity, self.kind, self.location, self.message
+        )
+    }
+}
+
+/// The result of linting a package.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    pub fn errors(&self) -> impl Iterator<Item = &LintIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == LintSeverity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &LintIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == LintSeverity::Warning)
+    }
+
+    /// Returns `true` if any issue should prevent the package from being exported or published.
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for LintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} error(s), {} warning(s)",
+            self.errors().count(),
+            self.warnings().count()
+        )?;
+        for issue in self.errors() {
+            write!(f, "; {issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PkgSpec {
+    /// Lints the package spec. See [`lint`].
+    pub fn lint(&self) -> LintReport {
+        lint(self)
+    }
+}
+
+/// Lints a package spec, returning every problem found.
+///
+/// Modules are linted as a single unit. Workspace backups are linted one change set at a time,
+/// where each change set may also refer to the funcs of the default change set.
+pub fn lint(spec: &PkgSpec) -> LintReport {
+    let mut linter = Linter::default();
+
+    match spec.kind {
+        SiPkgKind::Module => {
+            let mut scope = Scope::new("", &spec.funcs, &[]);
+            linter.lint_scope(&mut scope, &spec.schemas);
+
+            for func in &spec.funcs {
+                if !func.deleted
+                    && !func.name.starts_with("si:")
+                    && !scope.referenced.contains(func.unique_id.as_str())
+                {
+                    linter.push(
+                        LintKind::UnusedFunc,
+                        format!("funcs[{}]", func.name),
+                        format!("func {} is not used by any schema variant", func.name),
+                    );
+                }
+            }
+        }
+        SiPkgKind::WorkspaceBackup => {
+            let default_change_set = spec
+                .default_change_set
+                .as_deref()
+                .and_then(|name| spec.change_sets.iter().find(|cs| cs.name == name));
+            let default_funcs = default_change_set
+                .map(|cs| cs.funcs.as_slice())
+                .unwrap_or(&[]);
+
+            if !spec.funcs.is_empty() || !spec.schemas.is_empty() {
+                let mut scope = Scope::new("", &spec.funcs, &[]);
+                linter.lint_scope(&mut scope, &spec.schemas);
+            }
+
+            for change_set in &spec.change_sets {
+                let is_default = default_change_set
+                    .map(|default| std::ptr::eq(default, change_set))
+                    .unwrap_or(false);
+                let fallback_funcs: &[FuncSpec] = if is_default { &[] } else { default_funcs };
+
+                let mut scope = Scope::new(
+                    &format!("changeSets[{}]/", change_set.name),
+                    &change_set.funcs,
+                    fallback_funcs,
+                );
+                linter.lint_scope(&mut scope, &change_set.schemas);
+            }
+        }
+    }
+
+    LintReport {
+        issues: linter.issues,
+    }
+}
+
+/// A set of funcs and schemas which are installed together, within which references must resolve.
+struct Scope<'a> {
+    prefix: String,
+    funcs: HashSet<&'a str>,
+    referenced: HashSet<String>,
+    unique_ids: HashMap<String, String>,
+}
+
+impl<'a> Scope<'a> {
+    fn new(prefix: &str, funcs: &'a [FuncSpec], fallback_funcs: &'a [FuncSpec]) -> Self {
+        Self {
+            prefix: prefix.to_owned(),
+            funcs: funcs
+                .iter()
+                .chain(fallback_funcs)
+                .map(|func| func.unique_id.as_str())
+                .collect(),
+            referenced: HashSet::new(),
+            unique_ids: HashMap::new(),
+        }
+    }
+
+    fn location(&self, location: impl AsRef<str>) -> String {
+        format!("{}{}", self.prefix, location.as_ref())
+    }
+}
+
+/// The props and sockets of a schema variant which attribute func inputs can refer to.
+#[derive(Default)]
+struct VariantTargets {
+    prop_paths: HashSet<String>,
+    input_sockets: HashSet<String>,
+    output_sockets: HashSet<String>,
+}
+
+#[derive(Default)]
+struct Linter {
+    issues: Vec<LintIssue>,
+}
+
+impl Linter {
+    fn push(&mut self, kind: LintKind, location: String, message: impl Into<String>) {
+        self.issues.push(LintIssue {
+            severity: kind.severity(),
+            kind,
+            location,
+            message: message.into(),
+        });
+    }
+
+    fn lint_scope(&mut self, scope: &mut Scope<'_>, schemas: &[SchemaSpec]) {
+        for schema in schemas {
+            let schema_location = format!("schemas[{}]", schema.name);
+            self.unique_id(scope, schema.unique_id.as_deref(), &schema_location);
+
+            for variant in &schema.variants {
+                let variant_location = format!("{schema_location}/variants[{}]", variant.name);
+                self.lint_variant(scope, variant, &variant_location, schema.deleted);
+            }
+        }
+    }
+
+    fn lint_variant(
+        &mut self,
+        scope: &mut Scope<'_>,
+        variant: &SchemaVariantSpec,
+        location: &str,
+        schema_deleted: bool,
+    ) {
+        self.unique_id(scope, variant.unique_id.as_deref(), location);
+
+        // References from deleted items are never resolved on install, so they can't dangle
+        let skip_references = schema_deleted || variant.deleted;
+
+        if let Some(data) = &variant.data {
+            self.func_reference(
+                scope,
+                &data.func_unique_id,
+                &format!("{location}/data"),
+                skip_references,
+            );
+        }
+
+        let targets = variant_targets(variant);
+
+        for (idx, action_func) in variant.action_funcs.iter().enumerate() {
+            let action_location = format!("{location}/actionFuncs[{idx}]");
+            self.unique_id(scope, action_func.unique_id.as_deref(), &action_location);
+            self.func_reference(
+                scope,
+                &action_func.func_unique_id,
+                &action_location,
+                skip_references || action_func.deleted,
+            );
+        }
+
+        for (idx, leaf_function) in variant.leaf_functions.iter().enumerate() {
+            let leaf_location = format!("{location}/leafFunctions[{idx}]");
+            self.unique_id(scope, leaf_function.unique_id.as_deref(), &leaf_location);
+            self.func_reference(
+                scope,
+                &leaf_function.func_unique_id,
+                &leaf_location,
+                skip_references || leaf_function.deleted,
+            );
+        }
+
+        for socket in &variant.sockets {
+            let socket_location = format!("{location}/sockets[{}]", socket.name);
+            self.unique_id(scope, socket.unique_id.as_deref(), &socket_location);
+            if let Some(func_unique_id) = socket
+                .data
+                .as_ref()
+                .and_then(|data| data.func_unique_id.as_deref())
+            {
+                self.func_reference(scope, func_unique_id, &socket_location, skip_references);
+            }
+            self.inputs(
+                scope,
+                &targets,
+                &socket.inputs,
+                &socket_location,
+                skip_references,
+            );
+        }
+
+        for si_prop_func in &variant.si_prop_funcs {
+            let si_prop_location = format!("{location}/siPropFuncs[{}]", si_prop_func.kind);
+            self.unique_id(scope, si_prop_func.unique_id.as_deref(), &si_prop_location);
+            let skip = skip_references || si_prop_func.deleted;
+            self.func_reference(scope, &si_prop_func.func_unique_id, &si_prop_location, skip);
+            self.inputs(
+                scope,
+                &targets,
+                &si_prop_func.inputs,
+                &si_prop_location,
+                skip,
+            );
+        }
+
+        for (root, prop) in variant_prop_roots(variant) {
+            let root_location = format!("{location}/{}", root.path_parts()[1..].join("/"));
+            self.lint_prop_tree(scope, &targets, prop, &root_location, skip_references);
+        }
+    }
+
+    fn lint_prop_tree(
+        &mut self,
+        scope: &mut Scope<'_>,
+        targets: &VariantTargets,
+        root_prop: &PropSpec,
+        root_location: &str,
+        skip_references: bool,
+    ) {
+        let mut stack = vec![(root_prop, root_location.to_owned())];
+
+        while let Some((prop, location)) = stack.pop() {
+            self.lint_prop(scope, targets, prop, &location, skip_references);
+
+            match prop {
+                PropSpec::Array { type_prop, .. } | PropSpec::Map { type_prop, .. } => {
+                    let child_location = format!("{location}/{}", prop_name(type_prop));
+                    stack.push((type_prop.as_ref(), child_location));
+                }
+                PropSpec::Object { entries, .. } => {
+                    for entry in entries.iter().rev() {
+                        let child_location = format!("{location}/{}", prop_name(entry));
+                        stack.push((entry, child_location));
+                    }
+                }
+                PropSpec::Boolean { .. } | PropSpec::Number { .. } | PropSpec::String { .. } => {}
+            }
+        }
+    }
+
+    fn lint_prop(
+        &mut self,
+        scope: &mut Scope<'_>,
+        targets: &VariantTargets,
+        prop: &PropSpec,
+        location: &str,
+        skip_references: bool,
+    ) {
+        let (unique_id, data) = match prop {
+            PropSpec::Array {
+                unique_id, data, ..
+            }
+            | PropSpec::Boolean {
+                unique_id, data, ..
+            }
+            | PropSpec::Map {
+                unique_id, data, ..
+            }
+            | PropSpec::Number {
+                unique_id, data, ..
+            }
+            | PropSpec::Object {
+                unique_id, data, ..
+            }
+            | PropSpec::String {
+                unique_id, data, ..
+            } => (unique_id.as_deref(), data.as_ref()),
+        };
+        self.unique_id(scope, unique_id, location);
+
+        if let PropSpec::Map {
+            map_key_funcs: Some(map_key_funcs),
+            ..
+        } = prop
+        {
+            for map_key_func in map_key_funcs {
+                let key_location = format!("{location}/mapKeyFuncs[{}]", map_key_func.key);
+                self.func_reference(
+                    scope,
+                    &map_key_func.func_unique_id,
+                    &key_location,
+                    skip_references,
+                );
+                self.inputs(
+                    scope,
+                    targets,
+                    &map_key_func.inputs,
+                    &key_location,
+                    skip_references,
+                );
+            }
+        }
+
+        let data = match data {
+            Some(data) => data,
+            None => return,
+        };
+
+        if let Some(func_unique_id) = &data.func_unique_id {
+            self.func_reference(scope, func_unique_id, location, skip_references);
+        }
+        if let Some(inputs) = &data.inputs {
+            self.inputs(scope, targets, inputs, location, skip_references);
+        }
+
+        if let Some(default_value) = &data.default_value {
+            let matches = match prop {
+                PropSpec::Array { .. } => default_value.is_array(),
+                PropSpec::Boolean { .. } => default_value.is_boolean(),
+                PropSpec::Map { .. } | PropSpec::Object { .. } => default_value.is_object(),
+                PropSpec::Number { .. } => default_value.is_number(),
+                PropSpec::String { .. } => default_value.is_string(),
+            };
+            if !matches && !default_value.is_null() {
+                self.push(
+                    LintKind::DefaultValueKindMismatch,
+                    scope.location(location),
+                    format!(
+                        "default value {default_value} does not match a {} prop",
+                        prop_kind_name(prop)
+                    ),
+                );
+            }
+        }
+
+        for (idx, validation) in data.validations.iter().flatten().enumerate() {
+            let validation_location = format!("{location}/validations[{idx}]");
+            self.unique_id(scope, validation.unique_id(), &validation_location);
+            if validation.deleted() {
+                continue;
+            }
+
+            let applies = match validation {
+                ValidationSpec::CustomValidation { func_unique_id, .. } => {
+                    self.func_reference(
+                        scope,
+                        func_unique_id,
+                        &validation_location,
+                        skip_references,
+                    );
+                    true
+                }
+                ValidationSpec::ArrayItemsAreUnique { .. }
+                | ValidationSpec::ArrayLengthIsBetween { .. } => {
+                    matches!(prop, PropSpec::Array { .. })
+                }
+                ValidationSpec::IntegerIsBetweenTwoIntegers { .. }
+                | ValidationSpec::IntegerIsInRange { .. }
+                | ValidationSpec::IntegerIsNotEmpty { .. } => {
+                    matches!(prop, PropSpec::Number { .. })
+                }
+                ValidationSpec::MapKeysMatchRegex { .. } => matches!(prop, PropSpec::Map { .. }),
+                ValidationSpec::ObjectMatchesJsonSchema { .. } => {
+                    matches!(prop, PropSpec::Map { .. } | PropSpec::Object { .. })
+                }
+                ValidationSpec::StringEquals { .. }
+                | ValidationSpec::StringHasPrefix { .. }
+                | ValidationSpec::StringInStringArray { .. }
+                | ValidationSpec::StringIsHexColor { .. }
+                | ValidationSpec::StringIsNotEmpty { .. }
+                | ValidationSpec::StringIsValidArn { .. }
+                | ValidationSpec::StringIsValidCidr { .. }
+                | ValidationSpec::StringIsValidHostname { .. }
+                | ValidationSpec::StringIsValidIpAddr { .. }
+                | ValidationSpec::StringIsValidUrl { .. }
+                | ValidationSpec::StringLengthIsBetween { .. }
+                | ValidationSpec::StringMatchesRegex { .. } => {
+                    matches!(prop, PropSpec::String { .. })
+                }
+            };
+            if !applies {
+                self.push(
+                    LintKind::ValidationOnWrongPropKind,
+                    scope.location(&validation_location),
+                    format!(
+                        "validation {} cannot apply to a {} prop",
+                        validation.kind(),
+                        prop_kind_name(prop)
+                    ),
+                );
+            }
+        }
+    }
+
+    fn inputs(
+        &mut self,
+        scope: &mut Scope<'_>,
+        targets: &VariantTargets,
+        inputs: &[AttrFuncInputSpec],
+        location: &str,
+        skip_references: bool,
+    ) {
+        for input in inputs {
+            let (name, unique_id, deleted) = match input {
+                AttrFuncInputSpec::InputSocket {
+                    name,
+                    unique_id,
+                    deleted,
+                    ..
+                }
+                | AttrFuncInputSpec::OutputSocket {
+                    name,
+                    unique_id,
+                    deleted,
+                    ..
+                }
+                | AttrFuncInputSpec::Prop {
+                    name,
+                    unique_id,
+                    deleted,
+                    ..
+                } => (name, unique_id.as_deref(), *deleted),
+            };
+            let input_location = format!("{location}/inputs[{name}]");
+            self.unique_id(scope, unique_id, &input_location);
+
+            if skip_references || deleted {
+                continue;
+            }
+
+            match input {
+                AttrFuncInputSpec::InputSocket { socket_name, .. } => {
+                    if !targets.input_sockets.contains(socket_name) {
+                        self.push(
+                            LintKind::DanglingSocket,
+                            scope.location(&input_location),
+                            format!("input socket {socket_name} does not exist"),
+                        );
+                    }
+                }
+                AttrFuncInputSpec::OutputSocket { socket_name, .. } => {
+                    if !targets.output_sockets.contains(socket_name) {
+                        self.push(
+                            LintKind::DanglingSocket,
+                            scope.location(&input_location),
+                            format!("output socket {socket_name} does not exist"),
+                        );
+                    }
+                }
+                AttrFuncInputSpec::Prop { prop_path, .. } => {
+                    if !targets.has_prop_path(prop_path) {
+                        self.push(
+                            LintKind::DanglingPropPath,
+                            scope.location(&input_location),
+                            format!(
+                                "prop {} does not exist",
+                                prop_path.replace(PROP_PATH_SEPARATOR, "/")
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn func_reference(
+        &mut self,
+        scope: &mut Scope<'_>,
+        func_unique_id: &str,
+        location: &str,
+        skip_check: bool,
+    ) {
+        scope.referenced.insert(func_unique_id.to_owned());
+
+        if !skip_check && !scope.funcs.contains(func_unique_id) {
+            self.push(
+                LintKind::DanglingFuncReference,
+                scope.location(location),
+                format!("func with unique id {func_unique_id} is not in the package"),
+            );
+        }
+    }
+
+    fn unique_id(&mut self, scope: &mut Scope<'_>, unique_id: Option<&str>, location: &str) {
+        let unique_id = match unique_id {
+            Some(unique_id) => unique_id,
+            None => return,
+        };
+
+        let location = scope.location(location);
+        match scope.unique_ids.get(unique_id) {
+            Some(first_location) => {
+                let message = format!("unique id {unique_id} is already used by {first_location}");
+                self.push(LintKind::DuplicateUniqueId, location, message);
+            }
+            None => {
+                scope.unique_ids.insert(unique_id.to_owned(), location);
+            }
+        }
+    }
+}
+
+impl VariantTargets {
+    fn has_prop_path(&self, prop_path: &str) -> bool {
+        if self.prop_paths.contains(prop_path) {
+            return true;
+        }
+
+        let mut parts = prop_path.split(PROP_PATH_SEPARATOR);
+        match (parts.next(), parts.next()) {
+            (Some("root"), None) => true,
+            (Some("root"), Some(child)) => BUILTIN_ROOT_PROPS.contains(&child),
+            _ => false,
+        }
+    }
+}
+
+fn variant_prop_roots(
+    variant: &SchemaVariantSpec,
+) -> impl Iterator<Item = (SchemaVariantSpecPropRoot, &PropSpec)> {
+    [
+        (SchemaVariantSpecPropRoot::Domain, Some(&variant.domain)),
+        (SchemaVariantSpecPropRoot::Secrets, Some(&variant.secrets)),
+        (
+            SchemaVariantSpecPropRoot::SecretDefinition,
+            variant.secret_definition.as_ref(),
+        ),
+        (
+            SchemaVariantSpecPropRoot::ResourceValue,
+            Some(&variant.resource_value),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(root, prop)| prop.map(|prop| (root, prop)))
+}
+
+fn variant_targets(variant: &SchemaVariantSpec) -> VariantTargets {
+    let mut targets = VariantTargets::default();
+
+    for socket in &variant.sockets {
+        match socket.data.as_ref().map(|data| data.kind) {
+            Some(SocketSpecKind::Input) => {
+                targets.input_sockets.insert(socket.name.to_owned());
+            }
+            Some(SocketSpecKind::Output) => {
+                targets.output_sockets.insert(socket.name.to_owned());
+            }
+            None => {}
+        }
+    }
+
+    for (root, root_prop) in variant_prop_roots(variant) {
+        // The root props are named differently in a spec than in an installed schema variant,
+        // e.g. the resource value root is named "value" but lives at /root/resource_value
+        let mut stack = vec![(root_prop, root.path_parts().join(PROP_PATH_SEPARATOR))];
+
+        while let Some((prop, path)) = stack.pop() {
+            match prop {
+                PropSpec::Array { type_prop, .. } | PropSpec::Map { type_prop, .. } => {
+                    let child_path = format!("{path}{PROP_PATH_SEPARATOR}{}", prop_name(type_prop));
+                    stack.push((type_prop.as_ref(), child_path));
+                }
+                PropSpec::Object { entries, .. } => {
+                    for entry in entries {
+                        let child_path = format!("{path}{PROP_PATH_SEPARATOR}{}", prop_name(entry));
+                        stack.push((entry, child_path));
+                    }
+                }
+                PropSpec::Boolean { .. } | PropSpec::Number { .. } | PropSpec::String { .. } => {}
+            }
+            targets.prop_paths.insert(path);
+        }
+    }
+
+    targets
+}
+
+fn prop_name(prop: &PropSpec) -> &str {
+    match prop {
+        PropSpec::Array { name, .. }
+        | PropSpec::Boolean { name, .. }
+        | PropSpec::Map { name, .. }
+        | PropSpec::Number { name, .. }
+        | PropSpec::Object { name, .. }
+        | PropSpec::String { name, .. } => name,
+    }
+}
+
+fn prop_kind_name(prop: &PropSpec) -> &'static str {
+    match prop {
+        PropSpec::Array { .. } => "array",
+        PropSpec::Boolean { .. } => "boolean",
+        PropSpec::Map { .. } => "map",
+        PropSpec::Number { .. } => "number",
+        PropSpec::Object { .. } => "object",
+        PropSpec::String { .. } => "string",
+    }
+}
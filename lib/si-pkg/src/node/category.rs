@@ -6,11 +6,12 @@ use object_tree::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{ChangeSetSpec, FuncSpec, SchemaSpec};
+use crate::{ChangeSetSpec, ComponentTemplateSpec, FuncSpec, SchemaSpec};
 
 use super::PkgNode;
 
 const CATEGORY_TYPE_CHANGE_SETS: &str = "change_sets";
+const CATEGORY_TYPE_COMPONENT_TEMPLATES: &str = "component_templates";
 const CATEGORY_TYPE_SCHEMAS: &str = "schemas";
 const CATEGORY_TYPE_FUNCS: &str = "funcs";
 
@@ -21,6 +22,7 @@ const KEY_KIND_STR: &str = "kind";
 #[serde(rename_all = "camelCase")]
 pub enum PackageCategory {
     ChangeSets(Vec<ChangeSetSpec>),
+    ComponentTemplates(Vec<ComponentTemplateSpec>),
     Funcs(Vec<FuncSpec>),
     Schemas(Vec<SchemaSpec>),
 }
@@ -29,6 +31,7 @@ pub enum PackageCategory {
 #[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
 pub enum CategoryNode {
     ChangeSets,
+    ComponentTemplates,
     Funcs,
     Schemas,
 }
@@ -37,6 +40,7 @@ impl CategoryNode {
     pub fn kind_str(&self) -> &'static str {
         match self {
             Self::ChangeSets => CATEGORY_TYPE_CHANGE_SETS,
+            Self::ComponentTemplates => CATEGORY_TYPE_COMPONENT_TEMPLATES,
             Self::Funcs => CATEGORY_TYPE_FUNCS,
             Self::Schemas => CATEGORY_TYPE_SCHEMAS,
         }
@@ -47,6 +51,7 @@ impl NameStr for CategoryNode {
     fn name(&self) -> &str {
         match self {
             Self::ChangeSets => CATEGORY_TYPE_CHANGE_SETS,
+            Self::ComponentTemplates => CATEGORY_TYPE_COMPONENT_TEMPLATES,
             Self::Schemas => CATEGORY_TYPE_SCHEMAS,
             Self::Funcs => CATEGORY_TYPE_FUNCS,
         }
@@ -69,6 +74,7 @@ impl ReadBytes for CategoryNode {
 
         let node = match kind_str.as_str() {
             CATEGORY_TYPE_CHANGE_SETS => Self::ChangeSets,
+            CATEGORY_TYPE_COMPONENT_TEMPLATES => Self::ComponentTemplates,
             CATEGORY_TYPE_FUNCS => Self::Funcs,
             CATEGORY_TYPE_SCHEMAS => Self::Schemas,
             invalid_kind => {
@@ -96,6 +102,16 @@ impl NodeChild for PackageCategory {
                     .map(|cs| Box::new(cs.clone()) as Box<dyn NodeChild<NodeType = Self::NodeType>>)
                     .collect(),
             ),
+            Self::ComponentTemplates(entries) => NodeWithChildren::new(
+                NodeKind::Tree,
+                Self::NodeType::Category(CategoryNode::ComponentTemplates),
+                entries
+                    .iter()
+                    .map(|template| {
+                        Box::new(template.clone()) as Box<dyn NodeChild<NodeType = Self::NodeType>>
+                    })
+                    .collect(),
+            ),
             Self::Funcs(entries) => NodeWithChildren::new(
                 NodeKind::Tree,
                 Self::NodeType::Category(CategoryNode::Funcs),
@@ -0,0 +1,84 @@
+use std::io::{BufRead, Write};
+
+use object_tree::{
+    read_key_value_line, write_key_value_line, GraphError, NameStr, NodeChild, NodeKind,
+    NodeWithChildren, ReadBytes, WriteBytes,
+};
+
+use super::PkgNode;
+use crate::spec::ComponentTemplateSpec;
+
+const KEY_NAME_STR: &str = "name";
+const KEY_DESCRIPTION_STR: &str = "description";
+const KEY_DEFINITION_STR: &str = "definition";
+
+#[derive(Clone, Debug)]
+pub struct ComponentTemplateNode {
+    pub name: String,
+    pub description: Option<String>,
+    pub definition: serde_json::Value,
+}
+
+impl NameStr for ComponentTemplateNode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl WriteBytes for ComponentTemplateNode {
+    fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<(), GraphError> {
+        write_key_value_line(writer, KEY_NAME_STR, self.name())?;
+        write_key_value_line(
+            writer,
+            KEY_DESCRIPTION_STR,
+            self.description.as_deref().unwrap_or(""),
+        )?;
+        // Serialized without pretty printing, so that the definition stays on a single line.
+        write_key_value_line(
+            writer,
+            KEY_DEFINITION_STR,
+            serde_json::to_string(&self.definition).map_err(GraphError::parse)?,
+        )?;
+
+        Ok(())
+    }
+}
+
+impl ReadBytes for ComponentTemplateNode {
+    fn read_bytes<R: BufRead>(reader: &mut R) -> Result<Option<Self>, GraphError>
+    where
+        Self: std::marker::Sized,
+    {
+        let name = read_key_value_line(reader, KEY_NAME_STR)?;
+        let description_str = read_key_value_line(reader, KEY_DESCRIPTION_STR)?;
+        let description = if description_str.is_empty() {
+            None
+        } else {
+            Some(description_str)
+        };
+        let definition_str = read_key_value_line(reader, KEY_DEFINITION_STR)?;
+        let definition = serde_json::from_str(&definition_str).map_err(GraphError::parse)?;
+
+        Ok(Some(Self {
+            name,
+            description,
+            definition,
+        }))
+    }
+}
+
+impl NodeChild for ComponentTemplateSpec {
+    type NodeType = PkgNode;
+
+    fn as_node_with_children(&self) -> NodeWithChildren<Self::NodeType> {
+        NodeWithChildren::new(
+            NodeKind::Leaf,
+            Self::NodeType::ComponentTemplate(ComponentTemplateNode {
+                name: self.name.to_owned(),
+                description: self.description.to_owned(),
+                definition: self.definition.to_owned(),
+            }),
+            vec![],
+        )
+    }
+}
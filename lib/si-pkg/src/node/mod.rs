@@ -13,6 +13,7 @@ mod attr_func_input;
 mod category;
 mod change_set;
 mod change_set_child;
+mod component_template;
 mod func;
 mod func_argument;
 mod leaf_function;
@@ -33,6 +34,7 @@ pub(crate) use self::{
     category::CategoryNode,
     change_set::ChangeSetNode,
     change_set_child::{ChangeSetChild, ChangeSetChildNode},
+    component_template::ComponentTemplateNode,
     func::FuncNode,
     func_argument::FuncArgumentNode,
     leaf_function::LeafFunctionNode,
@@ -53,6 +55,7 @@ const NODE_KIND_ATTR_FUNC_INPUT: &str = "attr_func_input";
 const NODE_KIND_CATEGORY: &str = "category";
 const NODE_KIND_CHANGE_SET: &str = "change_set";
 const NODE_KIND_CHANGE_SET_CHILD: &str = "change_set_child";
+const NODE_KIND_COMPONENT_TEMPLATE: &str = "component_template";
 const NODE_KIND_FUNC: &str = "func";
 const NODE_KIND_FUNC_ARGUMENT: &str = "func_argument";
 const NODE_KIND_LEAF_FUNCTION: &str = "leaf_function";
@@ -118,6 +121,7 @@ pub enum PkgNode {
     Category(CategoryNode),
     ChangeSet(ChangeSetNode),
     ChangeSetChild(ChangeSetChildNode),
+    ComponentTemplate(ComponentTemplateNode),
     Func(FuncNode),
     FuncArgument(FuncArgumentNode),
     LeafFunction(LeafFunctionNode),
@@ -139,6 +143,7 @@ impl PkgNode {
     pub const CATEGORY_KIND_STR: &str = NODE_KIND_CATEGORY;
     pub const CHANGE_SET_KIND_STR: &str = NODE_KIND_CHANGE_SET;
     pub const CHANGE_SET_CHILD_KIND_STR: &str = NODE_KIND_CHANGE_SET_CHILD;
+    pub const COMPONENT_TEMPLATE_KIND_STR: &str = NODE_KIND_COMPONENT_TEMPLATE;
     pub const FUNC_KIND_STR: &str = NODE_KIND_FUNC;
     pub const FUNC_ARGUMENT_KIND_STR: &str = NODE_KIND_FUNC_ARGUMENT;
     pub const LEAF_FUNCTION_KIND_STR: &str = NODE_KIND_LEAF_FUNCTION;
@@ -159,6 +164,7 @@ impl PkgNode {
             Self::Category(_) => NODE_KIND_CATEGORY,
             Self::ChangeSet(_) => NODE_KIND_CHANGE_SET,
             Self::ChangeSetChild(_) => NODE_KIND_CHANGE_SET_CHILD,
+            Self::ComponentTemplate(_) => NODE_KIND_COMPONENT_TEMPLATE,
             Self::ActionFunc(_) => NODE_KIND_ACTION_FUNC,
             Self::Func(_) => NODE_KIND_FUNC,
             Self::FuncArgument(_) => NODE_KIND_FUNC_ARGUMENT,
@@ -184,6 +190,7 @@ impl NameStr for PkgNode {
             Self::Category(node) => node.name(),
             Self::ChangeSet(node) => node.name(),
             Self::ChangeSetChild(node) => node.name(),
+            Self::ComponentTemplate(node) => node.name(),
             Self::ActionFunc(_) => NODE_KIND_ACTION_FUNC,
             Self::Func(node) => node.name(),
             Self::FuncArgument(node) => node.name(),
@@ -211,6 +218,7 @@ impl WriteBytes for PkgNode {
             Self::Category(node) => node.write_bytes(writer)?,
             Self::ChangeSet(node) => node.write_bytes(writer)?,
             Self::ChangeSetChild(node) => node.write_bytes(writer)?,
+            Self::ComponentTemplate(node) => node.write_bytes(writer)?,
             Self::ActionFunc(node) => node.write_bytes(writer)?,
             Self::Func(node) => node.write_bytes(writer)?,
             Self::FuncArgument(node) => node.write_bytes(writer)?,
@@ -248,6 +256,9 @@ impl ReadBytes for PkgNode {
             NODE_KIND_CHANGE_SET_CHILD => {
                 ChangeSetChildNode::read_bytes(reader)?.map(Self::ChangeSetChild)
             }
+            NODE_KIND_COMPONENT_TEMPLATE => {
+                ComponentTemplateNode::read_bytes(reader)?.map(Self::ComponentTemplate)
+            }
             NODE_KIND_FUNC => FuncNode::read_bytes(reader)?.map(Self::Func),
             NODE_KIND_FUNC_ARGUMENT => {
                 FuncArgumentNode::read_bytes(reader)?.map(Self::FuncArgument)
@@ -129,7 +129,16 @@ impl NodeChild for PkgSpec {
                             as Box<dyn NodeChild<NodeType = Self::NodeType>>,
                     ]
                 }
-            },
+            }
+            .into_iter()
+            // Only added when there are templates, so that the hashes of packages without them
+            // do not change.
+            .chain((!self.component_templates.is_empty()).then(|| {
+                Box::new(PackageCategory::ComponentTemplates(
+                    self.component_templates.clone(),
+                )) as Box<dyn NodeChild<NodeType = Self::NodeType>>
+            }))
+            .collect(),
         )
     }
 }
@@ -22,6 +22,8 @@ const KEY_WIDGET_KIND_STR: &str = "widget_kind";
 const KEY_WIDGET_OPTIONS_STR: &str = "widget_options";
 const KEY_HIDDEN_STR: &str = "hidden";
 const KEY_DOC_LINK_STR: &str = "doc_link";
+const KEY_OVERRIDDEN_STR: &str = "overridden";
+const KEY_LOCKED_STR: &str = "locked";
 const KEY_UNIQUE_ID_STR: &str = "unique_id";
 
 const PROP_TY_STRING: &str = "string";
@@ -40,6 +42,8 @@ pub struct PropNodeData {
     pub widget_options: Option<serde_json::Value>,
     pub doc_link: Option<Url>,
     pub hidden: bool,
+    pub overridden: bool,
+    pub locked: bool,
 }
 
 #[remain::sorted]
@@ -152,6 +156,15 @@ impl WriteBytes for PropNode {
                 KEY_DOC_LINK_STR,
                 data.doc_link.as_ref().map(|l| l.as_str()).unwrap_or(""),
             )?;
+
+            // These are only written when set so that the bytes (and therefore hashes) of
+            // existing props don't change
+            if data.overridden {
+                write_key_value_line(writer, KEY_OVERRIDDEN_STR, data.overridden)?;
+            }
+            if data.locked {
+                write_key_value_line(writer, KEY_LOCKED_STR, data.locked)?;
+            }
         }
 
         if let Some(unique_id) = match &self {
@@ -213,6 +226,17 @@ impl ReadBytes for PropNode {
                     Some(Url::parse(&doc_link_str).map_err(GraphError::parse)?)
                 };
 
+                let overridden = match read_key_value_line_opt(reader, KEY_OVERRIDDEN_STR)? {
+                    Some(overridden_str) => {
+                        bool::from_str(&overridden_str).map_err(GraphError::parse)?
+                    }
+                    None => false,
+                };
+                let locked = match read_key_value_line_opt(reader, KEY_LOCKED_STR)? {
+                    Some(locked_str) => bool::from_str(&locked_str).map_err(GraphError::parse)?,
+                    None => false,
+                };
+
                 Some(PropNodeData {
                     name: name.to_owned(),
                     func_unique_id,
@@ -221,6 +245,8 @@ impl ReadBytes for PropNode {
                     widget_options,
                     doc_link,
                     hidden,
+                    overridden,
+                    locked,
                 })
             }
         };
@@ -317,6 +343,8 @@ impl NodeChild for PropSpec {
                          widget_options,
                          hidden,
                          doc_link,
+                         overridden,
+                         locked,
                          ..
                      }| PropNodeData {
                         name,
@@ -326,6 +354,8 @@ impl NodeChild for PropSpec {
                         widget_options,
                         hidden: hidden.unwrap_or(false),
                         doc_link,
+                        overridden: overridden.unwrap_or(false),
+                        locked: locked.unwrap_or(false),
                     },
                 ),
                 unique_id.to_owned(),
@@ -19,6 +19,7 @@ const KEY_EXPECTED_STRING_STR: &str = "expected_string";
 const KEY_EXPECTED_STRING_ARRAY_STR: &str = "expected_string_array";
 const KEY_DISPLAY_EXPECTED_STR: &str = "display_expected";
 const KEY_FUNC_UNIQUE_ID_STR: &str = "func_unique_id";
+const KEY_JSON_SCHEMA_STR: &str = "json_schema";
 
 #[derive(Clone, Debug)]
 pub struct ValidationNode {
@@ -29,6 +30,7 @@ pub struct ValidationNode {
     pub expected_string_array: Option<Vec<String>>,
     pub display_expected: Option<bool>,
     pub func_unique_id: Option<String>,
+    pub json_schema: Option<serde_json::Value>,
     pub unique_id: Option<String>,
     pub deleted: bool,
 }
@@ -43,6 +45,7 @@ impl Default for ValidationNode {
             expected_string_array: None,
             display_expected: None,
             func_unique_id: None,
+            json_schema: None,
             unique_id: None,
             deleted: false,
         }
@@ -70,13 +73,43 @@ impl WriteBytes for ValidationNode {
                         .unwrap_or("".to_string()),
                 )?;
             }
-            ValidationSpecKind::StringEquals | ValidationSpecKind::StringHasPrefix => {
+            ValidationSpecKind::ArrayLengthIsBetween
+            | ValidationSpecKind::IntegerIsInRange
+            | ValidationSpecKind::StringLengthIsBetween => {
                 write_key_value_line(
                     writer,
-                    KEY_EXPECTED_STRING_STR,
-                    self.expected_string.clone().unwrap_or("".to_string()),
-                )?
+                    KEY_UPPER_BOUND_STR,
+                    self.upper_bound
+                        .map(|i| i.to_string())
+                        .unwrap_or("".to_string()),
+                )?;
+                write_key_value_line(
+                    writer,
+                    KEY_LOWER_BOUND_STR,
+                    self.lower_bound
+                        .map(|i| i.to_string())
+                        .unwrap_or("".to_string()),
+                )?;
             }
+            ValidationSpecKind::MapKeysMatchRegex
+            | ValidationSpecKind::StringEquals
+            | ValidationSpecKind::StringHasPrefix
+            | ValidationSpecKind::StringIsValidArn
+            | ValidationSpecKind::StringMatchesRegex => write_key_value_line(
+                writer,
+                KEY_EXPECTED_STRING_STR,
+                self.expected_string.clone().unwrap_or("".to_string()),
+            )?,
+            ValidationSpecKind::ObjectMatchesJsonSchema => write_key_value_line(
+                writer,
+                KEY_JSON_SCHEMA_STR,
+                serde_json::to_string(
+                    self.json_schema
+                        .as_ref()
+                        .unwrap_or(&serde_json::Value::Null),
+                )
+                .map_err(GraphError::parse)?,
+            )?,
             ValidationSpecKind::StringInStringArray => {
                 write_key_value_line(
                     writer,
@@ -100,10 +133,14 @@ impl WriteBytes for ValidationNode {
                     .map(|id| id.to_owned())
                     .unwrap_or("".to_string()),
             )?,
-            ValidationSpecKind::IntegerIsNotEmpty
-            | ValidationSpecKind::StringIsValidIpAddr
+            ValidationSpecKind::ArrayItemsAreUnique
+            | ValidationSpecKind::IntegerIsNotEmpty
             | ValidationSpecKind::StringIsHexColor
-            | ValidationSpecKind::StringIsNotEmpty => {}
+            | ValidationSpecKind::StringIsNotEmpty
+            | ValidationSpecKind::StringIsValidCidr
+            | ValidationSpecKind::StringIsValidHostname
+            | ValidationSpecKind::StringIsValidIpAddr
+            | ValidationSpecKind::StringIsValidUrl => {}
         }
 
         write_common_fields(writer, self.unique_id.as_deref(), self.deleted)?;
@@ -125,6 +162,7 @@ impl ReadBytes for ValidationNode {
         let mut expected_string_array = None;
         let mut display_expected = None;
         let mut func_unique_id = None;
+        let mut json_schema = None;
 
         match kind {
             ValidationSpecKind::IntegerIsBetweenTwoIntegers => {
@@ -134,7 +172,24 @@ impl ReadBytes for ValidationNode {
                 let lower_bound_str = read_key_value_line(reader, KEY_LOWER_BOUND_STR)?;
                 lower_bound = Some(i64::from_str(&lower_bound_str).map_err(GraphError::parse)?);
             }
-            ValidationSpecKind::StringEquals | ValidationSpecKind::StringHasPrefix => {
+            ValidationSpecKind::ArrayLengthIsBetween
+            | ValidationSpecKind::IntegerIsInRange
+            | ValidationSpecKind::StringLengthIsBetween => {
+                let upper_bound_str = read_key_value_line(reader, KEY_UPPER_BOUND_STR)?;
+                if !upper_bound_str.is_empty() {
+                    upper_bound = Some(i64::from_str(&upper_bound_str).map_err(GraphError::parse)?);
+                }
+
+                let lower_bound_str = read_key_value_line(reader, KEY_LOWER_BOUND_STR)?;
+                if !lower_bound_str.is_empty() {
+                    lower_bound = Some(i64::from_str(&lower_bound_str).map_err(GraphError::parse)?);
+                }
+            }
+            ValidationSpecKind::MapKeysMatchRegex
+            | ValidationSpecKind::StringEquals
+            | ValidationSpecKind::StringHasPrefix
+            | ValidationSpecKind::StringIsValidArn
+            | ValidationSpecKind::StringMatchesRegex => {
                 let expected_string_str = read_key_value_line(reader, KEY_EXPECTED_STRING_STR)?;
                 if !expected_string_str.is_empty() {
                     expected_string = Some(expected_string_str);
@@ -158,10 +213,19 @@ impl ReadBytes for ValidationNode {
             ValidationSpecKind::CustomValidation => {
                 func_unique_id = Some(read_key_value_line(reader, KEY_FUNC_UNIQUE_ID_STR)?);
             }
-            ValidationSpecKind::IntegerIsNotEmpty
-            | ValidationSpecKind::StringIsValidIpAddr
+            ValidationSpecKind::ObjectMatchesJsonSchema => {
+                let json_schema_str = read_key_value_line(reader, KEY_JSON_SCHEMA_STR)?;
+                json_schema =
+                    Some(serde_json::from_str(&json_schema_str).map_err(GraphError::parse)?);
+            }
+            ValidationSpecKind::ArrayItemsAreUnique
+            | ValidationSpecKind::IntegerIsNotEmpty
             | ValidationSpecKind::StringIsHexColor
-            | ValidationSpecKind::StringIsNotEmpty => {}
+            | ValidationSpecKind::StringIsNotEmpty
+            | ValidationSpecKind::StringIsValidCidr
+            | ValidationSpecKind::StringIsValidHostname
+            | ValidationSpecKind::StringIsValidIpAddr
+            | ValidationSpecKind::StringIsValidUrl => {}
         }
 
         let (unique_id, deleted) = read_common_fields(reader)?;
@@ -174,6 +238,7 @@ impl ReadBytes for ValidationNode {
             expected_string_array,
             display_expected,
             func_unique_id,
+            json_schema,
             unique_id,
             deleted,
         }))
@@ -259,6 +324,113 @@ impl NodeChild for ValidationSpec {
                     deleted: *deleted,
                     ..ValidationNode::default()
                 },
+                ValidationSpec::ArrayItemsAreUnique { unique_id, deleted } => ValidationNode {
+                    kind: ValidationSpecKind::ArrayItemsAreUnique,
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::ArrayLengthIsBetween {
+                    lower_bound,
+                    upper_bound,
+                    unique_id,
+                    deleted,
+                } => ValidationNode {
+                    kind: ValidationSpecKind::ArrayLengthIsBetween,
+                    upper_bound: *upper_bound,
+                    lower_bound: *lower_bound,
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::IntegerIsInRange {
+                    lower_bound,
+                    upper_bound,
+                    unique_id,
+                    deleted,
+                } => ValidationNode {
+                    kind: ValidationSpecKind::IntegerIsInRange,
+                    upper_bound: *upper_bound,
+                    lower_bound: *lower_bound,
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::MapKeysMatchRegex {
+                    pattern,
+                    unique_id,
+                    deleted,
+                } => ValidationNode {
+                    kind: ValidationSpecKind::MapKeysMatchRegex,
+                    expected_string: Some(pattern.clone()),
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::ObjectMatchesJsonSchema {
+                    schema,
+                    unique_id,
+                    deleted,
+                } => ValidationNode {
+                    kind: ValidationSpecKind::ObjectMatchesJsonSchema,
+                    json_schema: Some(schema.clone()),
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringIsValidArn {
+                    service,
+                    unique_id,
+                    deleted,
+                } => ValidationNode {
+                    kind: ValidationSpecKind::StringIsValidArn,
+                    expected_string: service.clone(),
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringIsValidCidr { unique_id, deleted } => ValidationNode {
+                    kind: ValidationSpecKind::StringIsValidCidr,
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringIsValidHostname { unique_id, deleted } => ValidationNode {
+                    kind: ValidationSpecKind::StringIsValidHostname,
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringIsValidUrl { unique_id, deleted } => ValidationNode {
+                    kind: ValidationSpecKind::StringIsValidUrl,
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringLengthIsBetween {
+                    lower_bound,
+                    upper_bound,
+                    unique_id,
+                    deleted,
+                } => ValidationNode {
+                    kind: ValidationSpecKind::StringLengthIsBetween,
+                    upper_bound: *upper_bound,
+                    lower_bound: *lower_bound,
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringMatchesRegex {
+                    pattern,
+                    unique_id,
+                    deleted,
+                } => ValidationNode {
+                    kind: ValidationSpecKind::StringMatchesRegex,
+                    expected_string: Some(pattern.clone()),
+                    unique_id: unique_id.clone(),
+                    deleted: *deleted,
+                    ..ValidationNode::default()
+                },
                 ValidationSpec::CustomValidation {
                     func_unique_id,
                     unique_id,
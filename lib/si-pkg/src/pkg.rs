@@ -1,10 +1,16 @@
 use core::fmt;
-use std::{collections::HashMap, convert::Infallible, path::Path, sync::Arc};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    io::{BufReader, Read, Seek},
+    path::Path,
+    sync::{Arc, OnceLock},
+};
 
 use chrono::{DateTime, Utc};
 use object_tree::{
-    GraphError, Hash, HashedNode, NameStr, NodeChild, ObjectTree, TarReadError, TarWriter,
-    TarWriterError,
+    GraphError, Hash, HashedNode, NameStr, NodeChild, NodeEntry, ObjectTree, SerializedNode,
+    TarIndexReader, TarReadError, TarWriter, TarWriterError,
 };
 use petgraph::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -14,6 +20,7 @@ use thiserror::Error;
 mod action_func;
 mod attr_func_input;
 mod change_set;
+mod component_template;
 mod func;
 mod leaf_function;
 mod map_key_func;
@@ -25,13 +32,14 @@ mod validation;
 mod variant;
 
 pub use {
-    action_func::*, attr_func_input::*, change_set::*, func::*, leaf_function::*, map_key_func::*,
-    prop::*, schema::*, si_prop_func::*, socket::*, validation::*, variant::*,
+    action_func::*, attr_func_input::*, change_set::*, component_template::*, func::*,
+    leaf_function::*, map_key_func::*, prop::*, schema::*, si_prop_func::*, socket::*,
+    validation::*, variant::*,
 };
 
 use crate::{
     node::{CategoryNode, PkgNode},
-    spec::{FuncSpec, PkgSpec, SchemaVariantSpecPropRoot, SpecError},
+    spec::{ComponentTemplateSpec, FuncSpec, PkgSpec, SchemaVariantSpecPropRoot, SpecError},
 };
 
 #[remain::sorted]
@@ -91,7 +99,102 @@ impl From<Infallible> for SiPkgError {
 
 #[derive(Clone, Debug)]
 pub struct SiPkg {
-    tree: Arc<ObjectTree<PkgNode>>,
+    tree: PkgTree,
+}
+
+#[derive(Clone, Debug)]
+enum PkgTree {
+    Loaded(Arc<ObjectTree<PkgNode>>),
+    Lazy(Arc<LazyPkgTree>),
+}
+
+trait ReadSeek: Read + Seek + Send {}
+
+impl<T> ReadSeek for T where T: Read + Seek + Send {}
+
+/// A package tree whose nodes are read from a tar on demand, one category at a time, so that
+/// answering questions such as the package metadata or its schemas does not require reading
+/// every func (and its code) into memory.
+struct LazyPkgTree {
+    reader: TarIndexReader<Box<dyn ReadSeek>>,
+    root: OnceLock<HashedNode<PkgNode>>,
+    root_entries: OnceLock<Vec<NodeEntry>>,
+    change_sets: OnceLock<Option<ObjectTree<PkgNode>>>,
+    component_templates: OnceLock<Option<ObjectTree<PkgNode>>>,
+    funcs: OnceLock<Option<ObjectTree<PkgNode>>>,
+    schemas: OnceLock<Option<ObjectTree<PkgNode>>>,
+    full: OnceLock<ObjectTree<PkgNode>>,
+}
+
+impl LazyPkgTree {
+    fn new(reader: TarIndexReader<Box<dyn ReadSeek>>) -> Self {
+        Self {
+            reader,
+            root: OnceLock::new(),
+            root_entries: OnceLock::new(),
+            change_sets: OnceLock::new(),
+            component_templates: OnceLock::new(),
+            funcs: OnceLock::new(),
+            schemas: OnceLock::new(),
+            full: OnceLock::new(),
+        }
+    }
+
+    fn root(&self) -> PkgResult<(&HashedNode<PkgNode>, &[NodeEntry])> {
+        if let (Some(root), Some(entries)) = (self.root.get(), self.root_entries.get()) {
+            return Ok((root, entries));
+        }
+
+        let root_hash = self.reader.root_hash();
+        let (root, entries) = self
+            .reader
+            .read_node(root_hash)?
+            .ok_or(SiPkgError::NodeWithHashNotFound(root_hash))?;
+
+        Ok((
+            self.root.get_or_init(|| root),
+            self.root_entries.get_or_init(|| entries),
+        ))
+    }
+
+    fn category_tree(&self, category: CategoryNode) -> PkgResult<Option<&ObjectTree<PkgNode>>> {
+        let cell = match category {
+            CategoryNode::ChangeSets => &self.change_sets,
+            CategoryNode::ComponentTemplates => &self.component_templates,
+            CategoryNode::Funcs => &self.funcs,
+            CategoryNode::Schemas => &self.schemas,
+        };
+        if let Some(tree) = cell.get() {
+            return Ok(tree.as_ref());
+        }
+
+        let (_, entries) = self.root()?;
+        let tree = match entries.iter().find(|entry| entry.name() == category.name()) {
+            Some(entry) => Some(self.reader.read_subtree(entry.hash())?),
+            None => None,
+        };
+
+        Ok(cell.get_or_init(|| tree).as_ref())
+    }
+
+    fn full_tree(&self) -> PkgResult<&ObjectTree<PkgNode>> {
+        if let Some(tree) = self.full.get() {
+            return Ok(tree);
+        }
+
+        let tree = self.reader.read_tree()?;
+
+        Ok(self.full.get_or_init(|| tree))
+    }
+}
+
+impl fmt::Debug for LazyPkgTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyPkgTree")
+            .field("root_hash", &self.reader.root_hash())
+            .field("indexed_nodes", &self.reader.len())
+            .finish()
+    }
 }
 
 impl SiPkg {
@@ -100,11 +203,34 @@ impl SiPkg {
         Self::load_from_bytes(file_data)
     }
 
+    /// Opens a package file without reading it into memory. Nodes are read from the file as they
+    /// are needed, see [`SiPkg::load_from_reader`].
+    pub fn load_from_file_lazily(path: impl AsRef<Path>) -> PkgResult<Self> {
+        let file = std::fs::File::open(path)?;
+        Self::load_from_reader(BufReader::new(file))
+    }
+
     pub fn load_from_bytes(bytes: Vec<u8>) -> PkgResult<Self> {
         let tree: ObjectTree<PkgNode> = ObjectTree::<PkgNode>::read_from_tar(bytes)?;
 
         Ok(Self {
-            tree: Arc::new(tree),
+            tree: PkgTree::Loaded(Arc::new(tree)),
+        })
+    }
+
+    /// Loads a package from a reader over its tar representation, indexing the nodes it contains
+    /// without parsing them. The metadata, funcs, schemas and change sets of the package are each
+    /// only read when first asked for, which keeps memory usage low for large packages (such as
+    /// workspace backups) when only part of the package is of interest.
+    pub fn load_from_reader<R>(reader: R) -> PkgResult<Self>
+    where
+        R: Read + Seek + Send + 'static,
+    {
+        let reader: Box<dyn ReadSeek> = Box::new(reader);
+        let reader = TarIndexReader::new(reader)?;
+
+        Ok(Self {
+            tree: PkgTree::Lazy(Arc::new(LazyPkgTree::new(reader))),
         })
     }
 
@@ -117,18 +243,30 @@ impl SiPkg {
         let tree = ObjectTree::create_from_root(spec.as_node_with_children())?;
 
         Ok(Self {
-            tree: Arc::new(tree),
+            tree: PkgTree::Loaded(Arc::new(tree)),
         })
     }
 
     pub fn write_to_bytes(&self) -> PkgResult<Vec<u8>> {
-        Ok(TarWriter::new(&self.tree)?.bytes())
+        Ok(TarWriter::new(self.tree()?)?.bytes())
+    }
+
+    /// Returns every node of the package in its serialized form, with children ordered before
+    /// their parents. Nodes are keyed by their [`Hash`], so unchanged parts of a package produce
+    /// identical nodes and only need to be stored once.
+    pub fn serialized_nodes(&self) -> PkgResult<Vec<SerializedNode>> {
+        Ok(TarWriter::serialized_nodes(self.tree()?)?)
     }
 
     pub fn metadata(&self) -> PkgResult<SiPkgMetadata> {
-        let (graph, root_idx) = self.as_petgraph();
+        match &self.tree {
+            PkgTree::Loaded(tree) => {
+                let (graph, root_idx) = tree.as_petgraph();
 
-        SiPkgMetadata::from_graph(graph, root_idx)
+                SiPkgMetadata::from_graph(graph, root_idx)
+            }
+            PkgTree::Lazy(lazy) => SiPkgMetadata::from_hashed_node(lazy.root()?.0),
+        }
     }
 
     pub fn hash(&self) -> PkgResult<Hash> {
@@ -146,9 +284,11 @@ impl SiPkg {
     }
 
     pub fn funcs(&self) -> PkgResult<Vec<SiPkgFunc>> {
-        let (graph, root_idx) = self.as_petgraph();
+        let Some((graph, category_idx)) = self.category_graph(CategoryNode::Funcs)? else {
+            return Ok(vec![]);
+        };
 
-        let node_idxs = func_node_idxs(graph, root_idx)?;
+        let node_idxs = category_child_idxs(graph, category_idx);
         let mut funcs = Vec::with_capacity(node_idxs.len());
         for node_idx in node_idxs {
             funcs.push(SiPkgFunc::from_graph(graph, node_idx)?);
@@ -158,9 +298,11 @@ impl SiPkg {
     }
 
     pub fn schemas(&self) -> PkgResult<Vec<SiPkgSchema>> {
-        let (graph, root_idx) = self.as_petgraph();
+        let Some((graph, category_idx)) = self.category_graph(CategoryNode::Schemas)? else {
+            return Ok(vec![]);
+        };
 
-        let node_idxs = schema_node_idxs(graph, root_idx)?;
+        let node_idxs = category_child_idxs(graph, category_idx);
         let mut schemas = Vec::with_capacity(node_idxs.len());
 
         for node_idx in node_idxs {
@@ -171,9 +313,11 @@ impl SiPkg {
     }
 
     pub fn change_sets(&self) -> PkgResult<Vec<SiPkgChangeSet>> {
-        let (graph, root_idx) = self.as_petgraph();
+        let Some((graph, category_idx)) = self.category_graph(CategoryNode::ChangeSets)? else {
+            return Ok(vec![]);
+        };
 
-        let node_idxs = category_node_idxs(CategoryNode::ChangeSets, graph, root_idx)?;
+        let node_idxs = category_child_idxs(graph, category_idx);
 
         let mut change_sets = Vec::with_capacity(node_idxs.len());
 
@@ -184,24 +328,76 @@ impl SiPkg {
         Ok(change_sets)
     }
 
+    pub fn component_templates(&self) -> PkgResult<Vec<SiPkgComponentTemplate>> {
+        let Some((graph, category_idx)) = self.category_graph(CategoryNode::ComponentTemplates)? else {
+            return Ok(vec![]);
+        };
+
+        let node_idxs = category_child_idxs(graph, category_idx);
+        let mut component_templates = Vec::with_capacity(node_idxs.len());
+        for node_idx in node_idxs {
+            component_templates.push(SiPkgComponentTemplate::from_graph(graph, node_idx)?);
+        }
+
+        Ok(component_templates)
+    }
+
     pub fn schema_by_name(&self, name: impl AsRef<str>) -> PkgResult<SiPkgSchema> {
-        let (graph, root_idx) = self.as_petgraph();
+        let name = name.as_ref();
+        let Some((graph, category_idx)) = self.category_graph(CategoryNode::Schemas)? else {
+            return Err(SiPkgError::NodeWithNameNotFound(name.to_string()));
+        };
 
-        let node_idx = idx_for_name(graph, schema_node_idxs(graph, root_idx)?.into_iter(), name)?;
+        let node_idx = idx_for_name(
+            graph,
+            category_child_idxs(graph, category_idx).into_iter(),
+            name,
+        )?;
 
         SiPkgSchema::from_graph(graph, node_idx)
     }
 
     pub fn schema_by_hash(&self, hash: Hash) -> PkgResult<SiPkgSchema> {
-        let (graph, root_idx) = self.as_petgraph();
+        let Some((graph, category_idx)) = self.category_graph(CategoryNode::Schemas)? else {
+            return Err(SiPkgError::NodeWithHashNotFound(hash));
+        };
 
-        let node_idx = idx_for_hash(graph, schema_node_idxs(graph, root_idx)?.into_iter(), hash)?;
+        let node_idx = idx_for_hash(
+            graph,
+            category_child_idxs(graph, category_idx).into_iter(),
+            hash,
+        )?;
 
         SiPkgSchema::from_graph(graph, node_idx)
     }
 
-    pub fn as_petgraph(&self) -> (&Graph<HashedNode<PkgNode>, ()>, NodeIndex) {
-        self.tree.as_petgraph()
+    /// Returns the full package tree as a [`Graph`]. For a lazily loaded package this reads every
+    /// node of the package.
+    pub fn as_petgraph(&self) -> PkgResult<(&Graph<HashedNode<PkgNode>, ()>, NodeIndex)> {
+        Ok(self.tree()?.as_petgraph())
+    }
+
+    fn tree(&self) -> PkgResult<&ObjectTree<PkgNode>> {
+        match &self.tree {
+            PkgTree::Loaded(tree) => Ok(tree),
+            PkgTree::Lazy(lazy) => lazy.full_tree(),
+        }
+    }
+
+    /// Returns the graph containing the given category node along with the index of the category
+    /// node, or `None` if the package has no such category.
+    fn category_graph(
+        &self,
+        category: CategoryNode,
+    ) -> PkgResult<Option<(&Graph<HashedNode<PkgNode>, ()>, NodeIndex)>> {
+        match &self.tree {
+            PkgTree::Loaded(tree) => {
+                let (graph, root_idx) = tree.as_petgraph();
+
+                Ok(category_node_idx(category, graph, root_idx).map(|idx| (graph, idx)))
+            }
+            PkgTree::Lazy(lazy) => Ok(lazy.category_tree(category)?.map(|tree| tree.as_petgraph())),
+        }
     }
 
     pub async fn to_spec(&self) -> PkgResult<PkgSpec> {
@@ -233,6 +429,10 @@ impl SiPkg {
             builder.schema(schema.to_spec().await?);
         }
 
+        for component_template in self.component_templates()? {
+            builder.component_template(ComponentTemplateSpec::try_from(component_template)?);
+        }
+
         if let SiPkgKind::WorkspaceBackup = metadata.kind() {
             if let Some(default_change_set) = metadata.default_change_set() {
                 builder.default_change_set(default_change_set);
@@ -272,35 +472,24 @@ fn idx_for_hash(
     Ok(node_idx)
 }
 
-fn category_node_idxs(
+fn category_node_idx(
     category_node: CategoryNode,
     graph: &Graph<HashedNode<PkgNode>, ()>,
     root_idx: NodeIndex,
-) -> PkgResult<Vec<NodeIndex>> {
-    let node_idxs = graph
+) -> Option<NodeIndex> {
+    graph
         .neighbors_directed(root_idx, Outgoing)
         .find(|node_idx| match &graph[*node_idx].inner() {
             PkgNode::Category(node) => *node == category_node,
             _ => false,
-        });
-
-    Ok(node_idxs
-        .map(|node_idx| graph.neighbors_directed(node_idx, Outgoing).collect())
-        .unwrap_or(vec![]))
-}
-
-fn schema_node_idxs(
-    graph: &Graph<HashedNode<PkgNode>, ()>,
-    root_idx: NodeIndex,
-) -> PkgResult<Vec<NodeIndex>> {
-    category_node_idxs(CategoryNode::Schemas, graph, root_idx)
+        })
 }
 
-fn func_node_idxs(
+fn category_child_idxs(
     graph: &Graph<HashedNode<PkgNode>, ()>,
-    root_idx: NodeIndex,
-) -> PkgResult<Vec<NodeIndex>> {
-    category_node_idxs(CategoryNode::Funcs, graph, root_idx)
+    category_idx: NodeIndex,
+) -> Vec<NodeIndex> {
+    graph.neighbors_directed(category_idx, Outgoing).collect()
 }
 
 #[derive(Clone)]
@@ -361,7 +550,10 @@ pub struct SiPkgMetadata {
 
 impl SiPkgMetadata {
     fn from_graph(graph: &Graph<HashedNode<PkgNode>, ()>, node_idx: NodeIndex) -> PkgResult<Self> {
-        let metadata_hashed_node = &graph[node_idx];
+        Self::from_hashed_node(&graph[node_idx])
+    }
+
+    fn from_hashed_node(metadata_hashed_node: &HashedNode<PkgNode>) -> PkgResult<Self> {
         let metadata_node = match metadata_hashed_node.inner() {
             PkgNode::Package(node) => node.clone(),
             unexpected => {
@@ -0,0 +1,77 @@
+use object_tree::{Hash, HashedNode};
+use petgraph::prelude::*;
+
+use super::{PkgResult, SiPkgError, Source};
+
+use crate::{node::PkgNode, ComponentTemplateSpec};
+
+#[derive(Clone, Debug)]
+pub struct SiPkgComponentTemplate<'a> {
+    name: String,
+    description: Option<String>,
+    definition: serde_json::Value,
+    hash: Hash,
+    source: Source<'a>,
+}
+
+impl<'a> SiPkgComponentTemplate<'a> {
+    pub fn from_graph(
+        graph: &'a Graph<HashedNode<PkgNode>, ()>,
+        node_idx: NodeIndex,
+    ) -> PkgResult<Self> {
+        let hashed_node = &graph[node_idx];
+        let node = match hashed_node.inner() {
+            PkgNode::ComponentTemplate(node) => node.clone(),
+            unexpected => {
+                return Err(SiPkgError::UnexpectedPkgNodeType(
+                    PkgNode::COMPONENT_TEMPLATE_KIND_STR,
+                    unexpected.node_kind_str(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            name: node.name,
+            description: node.description,
+            definition: node.definition,
+            hash: hashed_node.hash(),
+            source: Source::new(graph, node_idx),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn definition(&self) -> &serde_json::Value {
+        &self.definition
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub fn source(&self) -> &Source<'a> {
+        &self.source
+    }
+}
+
+impl<'a> TryFrom<SiPkgComponentTemplate<'a>> for ComponentTemplateSpec {
+    type Error = SiPkgError;
+
+    fn try_from(value: SiPkgComponentTemplate<'a>) -> Result<Self, Self::Error> {
+        let mut builder = ComponentTemplateSpec::builder();
+        if let Some(description) = value.description {
+            builder.description(description);
+        }
+
+        Ok(builder
+            .name(value.name)
+            .definition(value.definition)
+            .build()?)
+    }
+}
@@ -18,6 +18,8 @@ pub struct SiPkgPropData {
     pub widget_options: Option<serde_json::Value>,
     pub doc_link: Option<Url>,
     pub hidden: bool,
+    pub overridden: bool,
+    pub locked: bool,
 }
 
 #[remain::sorted]
@@ -169,6 +171,8 @@ impl<'a> SiPkgProp<'a> {
                          widget_options,
                          hidden,
                          doc_link,
+                         overridden,
+                         locked,
                      }| SiPkgPropData {
                         name,
                         default_value,
@@ -177,6 +181,8 @@ impl<'a> SiPkgProp<'a> {
                         widget_options,
                         hidden,
                         doc_link,
+                        overridden,
+                        locked,
                     },
                 ),
                 unique_id.to_owned(),
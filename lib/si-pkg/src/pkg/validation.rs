@@ -8,6 +8,22 @@ use crate::{node::PkgNode, ValidationSpec, ValidationSpecKind};
 #[remain::sorted]
 #[derive(Clone, Debug)]
 pub enum SiPkgValidation<'a> {
+    ArrayItemsAreUnique {
+        unique_id: Option<String>,
+        deleted: bool,
+
+        hash: Hash,
+        source: Source<'a>,
+    },
+    ArrayLengthIsBetween {
+        lower_bound: Option<i64>,
+        upper_bound: Option<i64>,
+        unique_id: Option<String>,
+        deleted: bool,
+
+        hash: Hash,
+        source: Source<'a>,
+    },
     CustomValidation {
         func_unique_id: String,
         unique_id: Option<String>,
@@ -25,6 +41,15 @@ pub enum SiPkgValidation<'a> {
         hash: Hash,
         source: Source<'a>,
     },
+    IntegerIsInRange {
+        lower_bound: Option<i64>,
+        upper_bound: Option<i64>,
+        unique_id: Option<String>,
+        deleted: bool,
+
+        hash: Hash,
+        source: Source<'a>,
+    },
     IntegerIsNotEmpty {
         unique_id: Option<String>,
         deleted: bool,
@@ -32,6 +57,22 @@ pub enum SiPkgValidation<'a> {
         hash: Hash,
         source: Source<'a>,
     },
+    MapKeysMatchRegex {
+        pattern: String,
+        unique_id: Option<String>,
+        deleted: bool,
+
+        hash: Hash,
+        source: Source<'a>,
+    },
+    ObjectMatchesJsonSchema {
+        schema: serde_json::Value,
+        unique_id: Option<String>,
+        deleted: bool,
+
+        hash: Hash,
+        source: Source<'a>,
+    },
     StringEquals {
         expected: String,
         unique_id: Option<String>,
@@ -71,10 +112,56 @@ pub enum SiPkgValidation<'a> {
         hash: Hash,
         source: Source<'a>,
     },
+    StringIsValidArn {
+        service: Option<String>,
+        unique_id: Option<String>,
+        deleted: bool,
+
+        hash: Hash,
+        source: Source<'a>,
+    },
+    StringIsValidCidr {
+        unique_id: Option<String>,
+        deleted: bool,
+
+        hash: Hash,
+        source: Source<'a>,
+    },
+    StringIsValidHostname {
+        unique_id: Option<String>,
+        deleted: bool,
+
+        hash: Hash,
+        source: Source<'a>,
+    },
     StringIsValidIpAddr {
         unique_id: Option<String>,
         deleted: bool,
 
+        hash: Hash,
+        source: Source<'a>,
+    },
+    StringIsValidUrl {
+        unique_id: Option<String>,
+        deleted: bool,
+
+        hash: Hash,
+        source: Source<'a>,
+    },
+    StringLengthIsBetween {
+        lower_bound: Option<i64>,
+        upper_bound: Option<i64>,
+        unique_id: Option<String>,
+        deleted: bool,
+
+        hash: Hash,
+        source: Source<'a>,
+    },
+    StringMatchesRegex {
+        pattern: String,
+        unique_id: Option<String>,
+        deleted: bool,
+
         hash: Hash,
         source: Source<'a>,
     },
@@ -177,6 +264,94 @@ impl<'a> SiPkgValidation<'a> {
                 hash,
                 source,
             },
+            ValidationSpecKind::ArrayItemsAreUnique => SiPkgValidation::ArrayItemsAreUnique {
+                unique_id,
+                deleted,
+                hash,
+                source,
+            },
+            ValidationSpecKind::ArrayLengthIsBetween => SiPkgValidation::ArrayLengthIsBetween {
+                lower_bound: node.lower_bound,
+                upper_bound: node.upper_bound,
+                unique_id,
+                deleted,
+                hash,
+                source,
+            },
+            ValidationSpecKind::IntegerIsInRange => SiPkgValidation::IntegerIsInRange {
+                lower_bound: node.lower_bound,
+                upper_bound: node.upper_bound,
+                unique_id,
+                deleted,
+                hash,
+                source,
+            },
+            ValidationSpecKind::MapKeysMatchRegex => SiPkgValidation::MapKeysMatchRegex {
+                pattern: node
+                    .expected_string
+                    .ok_or(SiPkgError::ValidationMissingField(
+                        "expected_string".to_string(),
+                    ))?,
+                unique_id,
+                deleted,
+                hash,
+                source,
+            },
+            ValidationSpecKind::ObjectMatchesJsonSchema => {
+                SiPkgValidation::ObjectMatchesJsonSchema {
+                    schema: node.json_schema.ok_or(SiPkgError::ValidationMissingField(
+                        "json_schema".to_string(),
+                    ))?,
+                    unique_id,
+                    deleted,
+                    hash,
+                    source,
+                }
+            }
+            ValidationSpecKind::StringIsValidArn => SiPkgValidation::StringIsValidArn {
+                service: node.expected_string,
+                unique_id,
+                deleted,
+                hash,
+                source,
+            },
+            ValidationSpecKind::StringIsValidCidr => SiPkgValidation::StringIsValidCidr {
+                unique_id,
+                deleted,
+                hash,
+                source,
+            },
+            ValidationSpecKind::StringIsValidHostname => SiPkgValidation::StringIsValidHostname {
+                unique_id,
+                deleted,
+                hash,
+                source,
+            },
+            ValidationSpecKind::StringIsValidUrl => SiPkgValidation::StringIsValidUrl {
+                unique_id,
+                deleted,
+                hash,
+                source,
+            },
+            ValidationSpecKind::StringLengthIsBetween => SiPkgValidation::StringLengthIsBetween {
+                lower_bound: node.lower_bound,
+                upper_bound: node.upper_bound,
+                unique_id,
+                deleted,
+                hash,
+                source,
+            },
+            ValidationSpecKind::StringMatchesRegex => SiPkgValidation::StringMatchesRegex {
+                pattern: node
+                    .expected_string
+                    .ok_or(SiPkgError::ValidationMissingField(
+                        "expected_string".to_string(),
+                    ))?,
+                unique_id,
+                deleted,
+                hash,
+                source,
+            },
             ValidationSpecKind::CustomValidation => {
                 SiPkgValidation::CustomValidation {
                     func_unique_id: node.func_unique_id.ok_or(
@@ -199,15 +374,30 @@ impl<'a> TryFrom<SiPkgValidation<'a>> for ValidationSpec {
         let mut builder = ValidationSpec::builder();
 
         let (unique_id, deleted) = match &value {
-            SiPkgValidation::CustomValidation {
+            SiPkgValidation::ArrayItemsAreUnique {
+                unique_id, deleted, ..
+            }
+            | SiPkgValidation::ArrayLengthIsBetween {
+                unique_id, deleted, ..
+            }
+            | SiPkgValidation::CustomValidation {
                 unique_id, deleted, ..
             }
             | SiPkgValidation::IntegerIsBetweenTwoIntegers {
                 unique_id, deleted, ..
             }
+            | SiPkgValidation::IntegerIsInRange {
+                unique_id, deleted, ..
+            }
             | SiPkgValidation::IntegerIsNotEmpty {
                 unique_id, deleted, ..
             }
+            | SiPkgValidation::MapKeysMatchRegex {
+                unique_id, deleted, ..
+            }
+            | SiPkgValidation::ObjectMatchesJsonSchema {
+                unique_id, deleted, ..
+            }
             | SiPkgValidation::StringEquals {
                 unique_id, deleted, ..
             }
@@ -223,8 +413,26 @@ impl<'a> TryFrom<SiPkgValidation<'a>> for ValidationSpec {
             | SiPkgValidation::StringIsNotEmpty {
                 unique_id, deleted, ..
             }
+            | SiPkgValidation::StringIsValidArn {
+                unique_id, deleted, ..
+            }
+            | SiPkgValidation::StringIsValidCidr {
+                unique_id, deleted, ..
+            }
+            | SiPkgValidation::StringIsValidHostname {
+                unique_id, deleted, ..
+            }
             | SiPkgValidation::StringIsValidIpAddr {
                 unique_id, deleted, ..
+            }
+            | SiPkgValidation::StringIsValidUrl {
+                unique_id, deleted, ..
+            }
+            | SiPkgValidation::StringLengthIsBetween {
+                unique_id, deleted, ..
+            }
+            | SiPkgValidation::StringMatchesRegex {
+                unique_id, deleted, ..
             } => (unique_id.to_owned(), *deleted),
         };
 
@@ -276,6 +484,75 @@ impl<'a> TryFrom<SiPkgValidation<'a>> for ValidationSpec {
             SiPkgValidation::StringIsNotEmpty { .. } => {
                 builder.kind(ValidationSpecKind::StringIsNotEmpty);
             }
+            SiPkgValidation::ArrayItemsAreUnique { .. } => {
+                builder.kind(ValidationSpecKind::ArrayItemsAreUnique);
+            }
+            SiPkgValidation::ArrayLengthIsBetween {
+                lower_bound,
+                upper_bound,
+                ..
+            } => {
+                builder.kind(ValidationSpecKind::ArrayLengthIsBetween);
+                if let Some(lower_bound) = lower_bound {
+                    builder.lower_bound(lower_bound);
+                }
+                if let Some(upper_bound) = upper_bound {
+                    builder.upper_bound(upper_bound);
+                }
+            }
+            SiPkgValidation::IntegerIsInRange {
+                lower_bound,
+                upper_bound,
+                ..
+            } => {
+                builder.kind(ValidationSpecKind::IntegerIsInRange);
+                if let Some(lower_bound) = lower_bound {
+                    builder.lower_bound(lower_bound);
+                }
+                if let Some(upper_bound) = upper_bound {
+                    builder.upper_bound(upper_bound);
+                }
+            }
+            SiPkgValidation::MapKeysMatchRegex { pattern, .. } => {
+                builder.kind(ValidationSpecKind::MapKeysMatchRegex);
+                builder.expected_string(pattern);
+            }
+            SiPkgValidation::ObjectMatchesJsonSchema { schema, .. } => {
+                builder.kind(ValidationSpecKind::ObjectMatchesJsonSchema);
+                builder.json_schema(schema);
+            }
+            SiPkgValidation::StringIsValidArn { service, .. } => {
+                builder.kind(ValidationSpecKind::StringIsValidArn);
+                if let Some(service) = service {
+                    builder.expected_string(service);
+                }
+            }
+            SiPkgValidation::StringIsValidCidr { .. } => {
+                builder.kind(ValidationSpecKind::StringIsValidCidr);
+            }
+            SiPkgValidation::StringIsValidHostname { .. } => {
+                builder.kind(ValidationSpecKind::StringIsValidHostname);
+            }
+            SiPkgValidation::StringIsValidUrl { .. } => {
+                builder.kind(ValidationSpecKind::StringIsValidUrl);
+            }
+            SiPkgValidation::StringLengthIsBetween {
+                lower_bound,
+                upper_bound,
+                ..
+            } => {
+                builder.kind(ValidationSpecKind::StringLengthIsBetween);
+                if let Some(lower_bound) = lower_bound {
+                    builder.lower_bound(lower_bound);
+                }
+                if let Some(upper_bound) = upper_bound {
+                    builder.upper_bound(upper_bound);
+                }
+            }
+            SiPkgValidation::StringMatchesRegex { pattern, .. } => {
+                builder.kind(ValidationSpecKind::StringMatchesRegex);
+                builder.expected_string(pattern);
+            }
         }
 
         Ok(builder.build()?)
@@ -15,7 +15,7 @@ use crate::{
     node::{PkgNode, PropChildNode, SchemaVariantChildNode},
     AttrFuncInputSpec, MapKeyFuncSpec, PropSpec, PropSpecBuilder, PropSpecKind, SchemaVariantSpec,
     SchemaVariantSpecBuilder, SchemaVariantSpecComponentType, SchemaVariantSpecData,
-    SchemaVariantSpecPropRoot,
+    SchemaVariantSpecPropRoot, ValidationSpec,
 };
 
 #[derive(Clone, Debug)]
@@ -505,12 +505,16 @@ async fn create_prop_stack(
                 widget_options,
                 func_unique_id,
                 hidden,
+                overridden,
+                locked,
                 ..
             }) = data
             {
                 builder
                     .has_data(true)
                     .hidden(*hidden)
+                    .overridden(*overridden)
+                    .locked(*locked)
                     .widget_kind(*widget_kind);
 
                 if let Some(widget_options) = widget_options {
@@ -523,6 +527,10 @@ async fn create_prop_stack(
                         builder.input(AttrFuncInputSpec::try_from(input)?);
                     }
                 }
+
+                for validation in spec.validations()? {
+                    builder.validation(ValidationSpec::try_from(validation)?);
+                }
             }
         }
     }
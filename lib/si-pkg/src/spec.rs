@@ -6,6 +6,7 @@ use thiserror::Error;
 mod action_func;
 mod attr_func_input;
 mod change_set;
+mod component_template;
 mod func;
 mod leaf_function;
 mod map_key_func;
@@ -17,8 +18,9 @@ mod validation;
 mod variant;
 
 pub use {
-    action_func::*, attr_func_input::*, change_set::*, func::*, leaf_function::*, map_key_func::*,
-    prop::*, schema::*, si_prop_func::*, socket::*, validation::*, variant::*,
+    action_func::*, attr_func_input::*, change_set::*, component_template::*, func::*,
+    leaf_function::*, map_key_func::*, prop::*, schema::*, si_prop_func::*, socket::*,
+    validation::*, variant::*,
 };
 
 use super::SiPkgKind;
@@ -59,6 +61,10 @@ pub struct PkgSpec {
     #[builder(setter(each(name = "change_set", into)), default)]
     #[serde(default)]
     pub change_sets: Vec<ChangeSetSpec>,
+
+    #[builder(setter(each(name = "component_template", into)), default)]
+    #[serde(default)]
+    pub component_templates: Vec<ComponentTemplateSpec>,
 }
 
 impl PkgSpec {
@@ -0,0 +1,27 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use super::SpecError;
+
+#[derive(Builder, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct ComponentTemplateSpec {
+    #[builder(setter(into))]
+    pub name: String,
+
+    #[builder(setter(into, strip_option), default)]
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The components, edges and parameters of the template, which are interpreted by whoever
+    /// installs the package.
+    #[builder(setter(into))]
+    pub definition: serde_json::Value,
+}
+
+impl ComponentTemplateSpec {
+    pub fn builder() -> ComponentTemplateSpecBuilder {
+        ComponentTemplateSpecBuilder::default()
+    }
+}
@@ -63,6 +63,7 @@ pub enum FuncSpecBackendKind {
     Array,
     Boolean,
     Diff,
+    Expression,
     Identity,
     Integer,
     JsAction,
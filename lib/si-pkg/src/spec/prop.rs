@@ -58,6 +58,10 @@ pub struct PropSpecData {
     pub widget_options: Option<serde_json::Value>,
     pub hidden: Option<bool>,
     pub doc_link: Option<Url>,
+    /// Whether the default value was set manually in place of the one computed by the function.
+    pub overridden: Option<bool>,
+    /// Whether the value is kept as is when the values it depends on change.
+    pub locked: Option<bool>,
 }
 
 #[remain::sorted]
@@ -132,8 +136,10 @@ pub struct PropSpecBuilder {
     hidden: bool,
     inputs: Vec<AttrFuncInputSpec>,
     kind: Option<PropSpecKind>,
+    locked: bool,
     map_key_funcs: Vec<MapKeyFuncSpec>,
     name: Option<String>,
+    overridden: bool,
     type_prop: Option<PropSpec>,
     validations: Vec<ValidationSpec>,
     widget_kind: Option<PropSpecWidgetKind>,
@@ -152,8 +158,10 @@ impl Default for PropSpecBuilder {
             hidden: false,
             inputs: vec![],
             kind: None,
+            locked: false,
             map_key_funcs: vec![],
             name: None,
+            overridden: false,
             type_prop: None,
             validations: vec![],
             widget_kind: None,
@@ -247,6 +255,16 @@ impl PropSpecBuilder {
         self
     }
 
+    pub fn overridden(&mut self, value: impl Into<bool>) -> &mut Self {
+        self.overridden = value.into();
+        self
+    }
+
+    pub fn locked(&mut self, value: impl Into<bool>) -> &mut Self {
+        self.locked = value.into();
+        self
+    }
+
     pub fn map_key_func(&mut self, value: impl Into<MapKeyFuncSpec>) -> &mut Self {
         self.has_data = true;
         self.map_key_funcs.push(value.into());
@@ -292,6 +310,8 @@ impl PropSpecBuilder {
         let widget_options = self.widget_options.to_owned();
         let hidden = self.hidden;
         let doc_link = self.doc_link.to_owned();
+        let overridden = self.overridden;
+        let locked = self.locked;
 
         Ok(match self.kind {
             Some(kind) => match kind {
@@ -309,6 +329,8 @@ impl PropSpecBuilder {
                             widget_options,
                             hidden: Some(hidden),
                             doc_link,
+                            overridden: Some(overridden),
+                            locked: Some(locked),
                         })
                     } else {
                         None
@@ -328,6 +350,8 @@ impl PropSpecBuilder {
                             widget_options,
                             hidden: Some(hidden),
                             doc_link,
+                            overridden: Some(overridden),
+                            locked: Some(locked),
                         })
                     } else {
                         None
@@ -347,6 +371,8 @@ impl PropSpecBuilder {
                             widget_options,
                             hidden: Some(hidden),
                             doc_link,
+                            overridden: Some(overridden),
+                            locked: Some(locked),
                         })
                     } else {
                         None
@@ -366,6 +392,8 @@ impl PropSpecBuilder {
                             widget_options,
                             hidden: Some(hidden),
                             doc_link,
+                            overridden: Some(overridden),
+                            locked: Some(locked),
                         })
                     } else {
                         None
@@ -392,6 +420,8 @@ impl PropSpecBuilder {
                             widget_options,
                             hidden: Some(hidden),
                             doc_link,
+                            overridden: Some(overridden),
+                            locked: Some(locked),
                         })
                     } else {
                         None
@@ -417,6 +447,8 @@ impl PropSpecBuilder {
                             widget_options,
                             hidden: Some(hidden),
                             doc_link,
+                            overridden: Some(overridden),
+                            locked: Some(locked),
                         })
                     } else {
                         None
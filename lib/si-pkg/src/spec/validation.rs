@@ -8,6 +8,26 @@ use super::SpecError;
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 pub enum ValidationSpec {
+    ArrayItemsAreUnique {
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
+    ArrayLengthIsBetween {
+        #[serde(alias = "lowerBound")]
+        #[serde(default)]
+        lower_bound: Option<i64>,
+        #[serde(alias = "upperBound")]
+        #[serde(default)]
+        upper_bound: Option<i64>,
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
     CustomValidation {
         #[serde(alias = "funcUniqueId")]
         func_unique_id: String,
@@ -28,11 +48,40 @@ pub enum ValidationSpec {
         #[serde(default)]
         deleted: bool,
     },
+    IntegerIsInRange {
+        #[serde(alias = "lowerBound")]
+        #[serde(default)]
+        lower_bound: Option<i64>,
+        #[serde(alias = "upperBound")]
+        #[serde(default)]
+        upper_bound: Option<i64>,
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
     IntegerIsNotEmpty {
         #[serde(alias = "uniqueId")]
         unique_id: Option<String>,
         deleted: bool,
     },
+    MapKeysMatchRegex {
+        pattern: String,
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
+    ObjectMatchesJsonSchema {
+        schema: serde_json::Value,
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
     StringEquals {
         expected: String,
         #[serde(alias = "uniqueId")]
@@ -73,6 +122,29 @@ pub enum ValidationSpec {
         #[serde(default)]
         deleted: bool,
     },
+    StringIsValidArn {
+        #[serde(default)]
+        service: Option<String>,
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
+    StringIsValidCidr {
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
+    StringIsValidHostname {
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
     StringIsValidIpAddr {
         #[serde(alias = "uniqueId")]
         #[serde(default)]
@@ -80,6 +152,34 @@ pub enum ValidationSpec {
         #[serde(default)]
         deleted: bool,
     },
+    StringIsValidUrl {
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
+    StringLengthIsBetween {
+        #[serde(alias = "lowerBound")]
+        #[serde(default)]
+        lower_bound: Option<i64>,
+        #[serde(alias = "upperBound")]
+        #[serde(default)]
+        upper_bound: Option<i64>,
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
+    StringMatchesRegex {
+        pattern: String,
+        #[serde(alias = "uniqueId")]
+        #[serde(default)]
+        unique_id: Option<String>,
+        #[serde(default)]
+        deleted: bool,
+    },
 }
 
 impl ValidationSpec {
@@ -87,31 +187,80 @@ impl ValidationSpec {
         ValidationSpecBuilder::default()
     }
 
+    pub fn kind(&self) -> ValidationSpecKind {
+        match self {
+            Self::ArrayItemsAreUnique { .. } => ValidationSpecKind::ArrayItemsAreUnique,
+            Self::ArrayLengthIsBetween { .. } => ValidationSpecKind::ArrayLengthIsBetween,
+            Self::CustomValidation { .. } => ValidationSpecKind::CustomValidation,
+            Self::IntegerIsBetweenTwoIntegers { .. } => {
+                ValidationSpecKind::IntegerIsBetweenTwoIntegers
+            }
+            Self::IntegerIsInRange { .. } => ValidationSpecKind::IntegerIsInRange,
+            Self::IntegerIsNotEmpty { .. } => ValidationSpecKind::IntegerIsNotEmpty,
+            Self::MapKeysMatchRegex { .. } => ValidationSpecKind::MapKeysMatchRegex,
+            Self::ObjectMatchesJsonSchema { .. } => ValidationSpecKind::ObjectMatchesJsonSchema,
+            Self::StringEquals { .. } => ValidationSpecKind::StringEquals,
+            Self::StringHasPrefix { .. } => ValidationSpecKind::StringHasPrefix,
+            Self::StringInStringArray { .. } => ValidationSpecKind::StringInStringArray,
+            Self::StringIsHexColor { .. } => ValidationSpecKind::StringIsHexColor,
+            Self::StringIsNotEmpty { .. } => ValidationSpecKind::StringIsNotEmpty,
+            Self::StringIsValidArn { .. } => ValidationSpecKind::StringIsValidArn,
+            Self::StringIsValidCidr { .. } => ValidationSpecKind::StringIsValidCidr,
+            Self::StringIsValidHostname { .. } => ValidationSpecKind::StringIsValidHostname,
+            Self::StringIsValidIpAddr { .. } => ValidationSpecKind::StringIsValidIpAddr,
+            Self::StringIsValidUrl { .. } => ValidationSpecKind::StringIsValidUrl,
+            Self::StringLengthIsBetween { .. } => ValidationSpecKind::StringLengthIsBetween,
+            Self::StringMatchesRegex { .. } => ValidationSpecKind::StringMatchesRegex,
+        }
+    }
+
     pub fn unique_id(&self) -> Option<&str> {
         match self {
-            Self::CustomValidation { unique_id, .. }
+            Self::ArrayItemsAreUnique { unique_id, .. }
+            | Self::ArrayLengthIsBetween { unique_id, .. }
+            | Self::CustomValidation { unique_id, .. }
             | Self::IntegerIsBetweenTwoIntegers { unique_id, .. }
+            | Self::IntegerIsInRange { unique_id, .. }
             | Self::IntegerIsNotEmpty { unique_id, .. }
+            | Self::MapKeysMatchRegex { unique_id, .. }
+            | Self::ObjectMatchesJsonSchema { unique_id, .. }
             | Self::StringEquals { unique_id, .. }
             | Self::StringHasPrefix { unique_id, .. }
             | Self::StringInStringArray { unique_id, .. }
             | Self::StringIsHexColor { unique_id, .. }
             | Self::StringIsNotEmpty { unique_id, .. }
-            | Self::StringIsValidIpAddr { unique_id, .. } => unique_id.as_deref(),
+            | Self::StringIsValidArn { unique_id, .. }
+            | Self::StringIsValidCidr { unique_id, .. }
+            | Self::StringIsValidHostname { unique_id, .. }
+            | Self::StringIsValidIpAddr { unique_id, .. }
+            | Self::StringIsValidUrl { unique_id, .. }
+            | Self::StringLengthIsBetween { unique_id, .. }
+            | Self::StringMatchesRegex { unique_id, .. } => unique_id.as_deref(),
         }
     }
 
     pub fn deleted(&self) -> bool {
         match self {
-            Self::CustomValidation { deleted, .. }
+            Self::ArrayItemsAreUnique { deleted, .. }
+            | Self::ArrayLengthIsBetween { deleted, .. }
+            | Self::CustomValidation { deleted, .. }
             | Self::IntegerIsBetweenTwoIntegers { deleted, .. }
+            | Self::IntegerIsInRange { deleted, .. }
             | Self::IntegerIsNotEmpty { deleted, .. }
+            | Self::MapKeysMatchRegex { deleted, .. }
+            | Self::ObjectMatchesJsonSchema { deleted, .. }
             | Self::StringEquals { deleted, .. }
             | Self::StringHasPrefix { deleted, .. }
             | Self::StringInStringArray { deleted, .. }
             | Self::StringIsHexColor { deleted, .. }
             | Self::StringIsNotEmpty { deleted, .. }
-            | Self::StringIsValidIpAddr { deleted, .. } => *deleted,
+            | Self::StringIsValidArn { deleted, .. }
+            | Self::StringIsValidCidr { deleted, .. }
+            | Self::StringIsValidHostname { deleted, .. }
+            | Self::StringIsValidIpAddr { deleted, .. }
+            | Self::StringIsValidUrl { deleted, .. }
+            | Self::StringLengthIsBetween { deleted, .. }
+            | Self::StringMatchesRegex { deleted, .. } => *deleted,
         }
     }
 }
@@ -121,15 +270,26 @@ impl ValidationSpec {
     Clone, Copy, Debug, Eq, Hash, PartialEq, EnumIter, EnumString, Display, Serialize, Deserialize,
 )]
 pub enum ValidationSpecKind {
+    ArrayItemsAreUnique,
+    ArrayLengthIsBetween,
     CustomValidation,
     IntegerIsBetweenTwoIntegers,
+    IntegerIsInRange,
     IntegerIsNotEmpty,
+    MapKeysMatchRegex,
+    ObjectMatchesJsonSchema,
     StringEquals,
     StringHasPrefix,
     StringInStringArray,
     StringIsHexColor,
     StringIsNotEmpty,
+    StringIsValidArn,
+    StringIsValidCidr,
+    StringIsValidHostname,
     StringIsValidIpAddr,
+    StringIsValidUrl,
+    StringLengthIsBetween,
+    StringMatchesRegex,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -141,6 +301,7 @@ pub struct ValidationSpecBuilder {
     expected_string_array: Option<Vec<String>>,
     display_expected: Option<bool>,
     func_unique_id: Option<String>,
+    json_schema: Option<serde_json::Value>,
     unique_id: Option<String>,
     deleted: bool,
 }
@@ -181,6 +342,11 @@ impl ValidationSpecBuilder {
         self
     }
 
+    pub fn json_schema(&mut self, json_schema: serde_json::Value) -> &mut Self {
+        self.json_schema = Some(json_schema);
+        self
+    }
+
     pub fn unique_id(&mut self, unique_id: impl Into<String>) -> &mut Self {
         self.unique_id = Some(unique_id.into());
         self
@@ -251,6 +417,75 @@ impl ValidationSpecBuilder {
                     unique_id: self.unique_id.to_owned(),
                     deleted: self.deleted,
                 },
+                ValidationSpecKind::ArrayItemsAreUnique => ValidationSpec::ArrayItemsAreUnique {
+                    unique_id: self.unique_id.to_owned(),
+                    deleted: self.deleted,
+                },
+                ValidationSpecKind::ArrayLengthIsBetween => ValidationSpec::ArrayLengthIsBetween {
+                    lower_bound: self.lower_bound,
+                    upper_bound: self.upper_bound,
+                    unique_id: self.unique_id.to_owned(),
+                    deleted: self.deleted,
+                },
+                ValidationSpecKind::IntegerIsInRange => ValidationSpec::IntegerIsInRange {
+                    lower_bound: self.lower_bound,
+                    upper_bound: self.upper_bound,
+                    unique_id: self.unique_id.to_owned(),
+                    deleted: self.deleted,
+                },
+                ValidationSpecKind::MapKeysMatchRegex => ValidationSpec::MapKeysMatchRegex {
+                    pattern: self
+                        .expected_string
+                        .to_owned()
+                        .ok_or(UninitializedFieldError::from("expected_string"))?,
+                    unique_id: self.unique_id.to_owned(),
+                    deleted: self.deleted,
+                },
+                ValidationSpecKind::ObjectMatchesJsonSchema => {
+                    ValidationSpec::ObjectMatchesJsonSchema {
+                        schema: self
+                            .json_schema
+                            .to_owned()
+                            .ok_or(UninitializedFieldError::from("json_schema"))?,
+                        unique_id: self.unique_id.to_owned(),
+                        deleted: self.deleted,
+                    }
+                }
+                ValidationSpecKind::StringIsValidArn => ValidationSpec::StringIsValidArn {
+                    service: self.expected_string.to_owned(),
+                    unique_id: self.unique_id.to_owned(),
+                    deleted: self.deleted,
+                },
+                ValidationSpecKind::StringIsValidCidr => ValidationSpec::StringIsValidCidr {
+                    unique_id: self.unique_id.to_owned(),
+                    deleted: self.deleted,
+                },
+                ValidationSpecKind::StringIsValidHostname => {
+                    ValidationSpec::StringIsValidHostname {
+                        unique_id: self.unique_id.to_owned(),
+                        deleted: self.deleted,
+                    }
+                }
+                ValidationSpecKind::StringIsValidUrl => ValidationSpec::StringIsValidUrl {
+                    unique_id: self.unique_id.to_owned(),
+                    deleted: self.deleted,
+                },
+                ValidationSpecKind::StringLengthIsBetween => {
+                    ValidationSpec::StringLengthIsBetween {
+                        lower_bound: self.lower_bound,
+                        upper_bound: self.upper_bound,
+                        unique_id: self.unique_id.to_owned(),
+                        deleted: self.deleted,
+                    }
+                }
+                ValidationSpecKind::StringMatchesRegex => ValidationSpec::StringMatchesRegex {
+                    pattern: self
+                        .expected_string
+                        .to_owned()
+                        .ok_or(UninitializedFieldError::from("expected_string"))?,
+                    unique_id: self.unique_id.to_owned(),
+                    deleted: self.deleted,
+                },
                 ValidationSpecKind::CustomValidation => ValidationSpec::CustomValidation {
                     func_unique_id: self
                         .func_unique_id
@@ -168,6 +168,8 @@ impl SchemaVariantSpecBuilder {
                 hidden: Some(false),
                 validations: None,
                 doc_link: None,
+                overridden: None,
+                locked: None,
             }),
             entries: vec![],
         }
@@ -187,6 +189,8 @@ impl SchemaVariantSpecBuilder {
                 hidden: Some(false),
                 validations: None,
                 doc_link: None,
+                overridden: None,
+                locked: None,
             }),
             entries: vec![],
         }
@@ -206,6 +210,8 @@ impl SchemaVariantSpecBuilder {
                 hidden: Some(false),
                 validations: None,
                 doc_link: None,
+                overridden: None,
+                locked: None,
             }),
             entries: vec![],
         })
@@ -225,6 +231,8 @@ impl SchemaVariantSpecBuilder {
                 hidden: Some(false),
                 validations: None,
                 doc_link: None,
+                overridden: None,
+                locked: None,
             }),
             entries: vec![],
         }
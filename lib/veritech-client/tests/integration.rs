@@ -271,6 +271,7 @@ async fn executes_simple_validation() {
         execution_id: "31337".to_string(),
         handler: "isThirtyThree".to_string(),
         value: 33.into(),
+        inputs: Default::default(),
         code_base64: base64_encode(
             "function isThirtyThree(value) { return { valid: value === 33 }; };",
         ),